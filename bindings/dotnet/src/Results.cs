@@ -9,6 +9,8 @@ namespace JsonSchemaLlm;
 /// <summary>Options for schema conversion.</summary>
 public sealed record ConvertOptions
 {
+    /// <summary>Named option bundle (e.g. "openai-tool-calling"); other properties override it.</summary>
+    public string? Preset { get; init; }
     public string? Target { get; init; }
     public string? Mode { get; init; }
     public int? MaxDepth { get; init; }
@@ -18,6 +20,7 @@ public sealed record ConvertOptions
     internal Dictionary<string, object> ToDictionary()
     {
         var dict = new Dictionary<string, object>();
+        if (Preset is not null) dict["preset"] = Preset;
         if (Target is not null) dict["target"] = Target;
         if (Mode is not null) dict["mode"] = Mode;
         if (MaxDepth is not null) dict["max-depth"] = MaxDepth.Value;
@@ -40,6 +43,23 @@ public sealed record ExtractOptions
     }
 }
 
+/// <summary>Options for rehydration.</summary>
+public sealed record RehydrateOptions
+{
+    /// <summary>Merge same-kind warnings at one schema path, keeping this many sample paths.</summary>
+    public int? AggregateWarnings { get; init; }
+    /// <summary>Keep this many warnings, then one "truncated" entry counting the rest.</summary>
+    public int? MaxWarnings { get; init; }
+
+    internal Dictionary<string, object> ToDictionary()
+    {
+        var dict = new Dictionary<string, object>();
+        if (AggregateWarnings is not null) dict["aggregate-warnings"] = AggregateWarnings.Value;
+        if (MaxWarnings is not null) dict["max-warnings"] = MaxWarnings.Value;
+        return dict;
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Result types
 // ---------------------------------------------------------------------------
@@ -65,6 +85,9 @@ public sealed record RehydrateWarning
     public required string DataPath { get; init; }
     public required string SchemaPath { get; init; }
     public required string Message { get; init; }
+    /// <summary>Occurrences merged into this entry by <see cref="RehydrateOptions.AggregateWarnings"/>.</summary>
+    public int Count { get; init; } = 1;
+    public IReadOnlyList<string> SamplePaths { get; init; } = [];
 }
 
 /// <summary>Result of a rehydration operation.</summary>
@@ -87,6 +110,10 @@ public sealed record RehydrateResult
                     DataPath = w.TryGetProperty("dataPath", out var dp) ? dp.GetString() ?? "" : "",
                     SchemaPath = w.TryGetProperty("schemaPath", out var sp) ? sp.GetString() ?? "" : "",
                     Message = w.TryGetProperty("message", out var msg) ? msg.GetString() ?? "" : "",
+                    Count = w.TryGetProperty("count", out var count) ? count.GetInt32() : 1,
+                    SamplePaths = w.TryGetProperty("samplePaths", out var samples)
+                        ? samples.EnumerateArray().Select(p => p.GetString() ?? "").ToList()
+                        : [],
                 });
             }
         }
@@ -100,16 +127,47 @@ public sealed record RehydrateResult
     }
 }
 
+/// <summary>A listed component with its stats and $ref relationships.</summary>
+public sealed record ComponentInfo
+{
+    public required string Pointer { get; init; }
+    public string? Title { get; init; }
+    public string? Description { get; init; }
+    public required int PropertyCount { get; init; }
+    public required int Depth { get; init; }
+    public required bool Recursive { get; init; }
+    public required string[] RefsOut { get; init; }
+    public required string[] RefsIn { get; init; }
+
+    internal static ComponentInfo FromJson(JsonElement c)
+    {
+        static string[] Strings(JsonElement arr) =>
+            arr.EnumerateArray().Select(s => s.GetString() ?? "").ToArray();
+
+        return new()
+        {
+            Pointer = c.GetProperty("pointer").GetString() ?? "",
+            Title = c.TryGetProperty("title", out var t) ? t.GetString() : null,
+            Description = c.TryGetProperty("description", out var d) ? d.GetString() : null,
+            PropertyCount = c.GetProperty("propertyCount").GetInt32(),
+            Depth = c.GetProperty("depth").GetInt32(),
+            Recursive = c.GetProperty("recursive").GetBoolean(),
+            RefsOut = Strings(c.GetProperty("refsOut")),
+            RefsIn = Strings(c.GetProperty("refsIn")),
+        };
+    }
+}
+
 /// <summary>Result of listing extractable components.</summary>
 public sealed record ListComponentsResult
 {
     public required string ApiVersion { get; init; }
-    public required string[] Components { get; init; }
+    public required ComponentInfo[] Components { get; init; }
 
     internal static ListComponentsResult FromJson(JsonElement root)
     {
         var components = root.GetProperty("components").EnumerateArray()
-            .Select(c => c.GetString() ?? "")
+            .Select(ComponentInfo.FromJson)
             .ToArray();
 
         return new()
@@ -65,12 +65,16 @@ public sealed class SchemaLlmEngine : IDisposable
         return ConvertResult.FromJson(CallJsl("jsl_convert", schemaJson, optsJson));
     }
 
-    public RehydrateResult Rehydrate(object data, object codec, object schema)
+    public RehydrateResult Rehydrate(object data, object codec, object schema, RehydrateOptions? options = null)
     {
         var dataJson = JsonSerializer.Serialize(data);
         var codecJson = JsonSerializer.Serialize(codec);
         var schemaJson = JsonSerializer.Serialize(schema);
-        return RehydrateResult.FromJson(CallJsl("jsl_rehydrate", dataJson, codecJson, schemaJson));
+        if (options is null)
+            return RehydrateResult.FromJson(CallJsl("jsl_rehydrate", dataJson, codecJson, schemaJson));
+        var optsJson = JsonSerializer.Serialize(options.ToDictionary(), KebabCaseOptions);
+        return RehydrateResult.FromJson(
+            CallJsl("jsl_rehydrate_with_options", dataJson, codecJson, schemaJson, optsJson));
     }
 
     public ListComponentsResult ListComponents(object schema)
@@ -79,6 +83,20 @@ public sealed class SchemaLlmEngine : IDisposable
         return ListComponentsResult.FromJson(CallJsl("jsl_list_components", schemaJson));
     }
 
+    /// <summary>
+    /// Fingerprint a schema as converted with <paramref name="options"/>:
+    /// <c>"sha256:&lt;hex&gt;"</c>, stable across key ordering.
+    /// </summary>
+    public string SchemaFingerprint(object schema, ConvertOptions? options = null)
+    {
+        var schemaJson = JsonSerializer.Serialize(schema);
+        var optsJson = options != null
+            ? JsonSerializer.Serialize(options.ToDictionary(), KebabCaseOptions)
+            : "{}";
+        return CallJsl("jsl_schema_fingerprint", schemaJson, optsJson)
+            .GetProperty("fingerprint").GetString() ?? "";
+    }
+
     public ExtractResult ExtractComponent(object schema, string pointer, ExtractOptions? options = null)
     {
         var schemaJson = JsonSerializer.Serialize(schema);
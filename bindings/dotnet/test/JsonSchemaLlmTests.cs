@@ -85,6 +85,33 @@ public class JsonSchemaLlmTests : IDisposable
         Assert.Equal("Ada", rehydrated.Data.GetProperty("name").GetString());
     }
 
+    [Fact]
+    public void RehydrateAggregatesWarnings()
+    {
+        var schema = new Dictionary<string, object>
+        {
+            ["type"] = "object",
+            ["properties"] = new Dictionary<string, object>
+            {
+                ["tags"] = new Dictionary<string, object>
+                {
+                    ["type"] = "array",
+                    ["items"] = new Dictionary<string, object> { ["type"] = "string", ["maxLength"] = 2 }
+                }
+            }
+        };
+
+        var convertResult = _engine.Convert(schema);
+
+        var data = new Dictionary<string, object> { ["tags"] = new[] { "long", "longer", "longest" } };
+        var rehydrated = _engine.Rehydrate(data, convertResult.Codec, schema,
+            new RehydrateOptions { AggregateWarnings = 2 });
+
+        var warning = Assert.Single(rehydrated.Warnings);
+        Assert.Equal(3, warning.Count);
+        Assert.Equal(new[] { "/tags/0", "/tags/1" }, warning.SamplePaths);
+    }
+
     [Fact]
     public void RehydrateError()
     {
@@ -128,6 +155,8 @@ public class JsonSchemaLlmTests : IDisposable
         var result = _engine.ListComponents(schema);
         Assert.NotEmpty(result.ApiVersion);
         Assert.Equal(2, result.Components.Length);
+        Assert.Equal("#/$defs/Pet", result.Components[0].Pointer);
+        Assert.False(result.Components[0].Recursive);
     }
 
     [Fact]
@@ -387,10 +387,13 @@ func TestConformance_ListComponents(t *testing.T) {
 				}
 			}
 
-			// components exact match
+			// components exact match (re-marshalled through any so keys sort alike)
 			if comps, ok := expected["components"]; ok {
 				expectedJSON, _ := json.Marshal(comps)
-				actualJSON, _ := json.Marshal(result.Components)
+				var actual any
+				raw, _ := json.Marshal(result.Components)
+				_ = json.Unmarshal(raw, &actual)
+				actualJSON, _ := json.Marshal(actual)
 				if string(actualJSON) != string(expectedJSON) {
 					t.Errorf("components mismatch:\n  got:  %s\n  want: %s", actualJSON, expectedJSON)
 				}
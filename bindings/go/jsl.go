@@ -38,6 +38,9 @@ const jslResultSize = 12
 
 // ConvertOptions configures schema conversion.
 type ConvertOptions struct {
+	// Preset names an option bundle (e.g. "openai-tool-calling"); the other
+	// fields override it.
+	Preset         string `json:"preset,omitempty"`
 	Target         string `json:"target,omitempty"`
 	Polymorphism   string `json:"polymorphism,omitempty"`
 	MaxDepth       int    `json:"max-depth,omitempty"`
@@ -63,6 +66,21 @@ type Warning struct {
 	SchemaPath string      `json:"schemaPath"`
 	Kind       WarningKind `json:"kind"`
 	Message    string      `json:"message"`
+	// Count is the number of occurrences merged into this entry; 0 (absent)
+	// means 1.
+	Count       int      `json:"count,omitempty"`
+	SamplePaths []string `json:"samplePaths,omitempty"`
+}
+
+// RehydrateOptions bounds the warnings a rehydrate returns. Zero values
+// keep the defaults.
+type RehydrateOptions struct {
+	// AggregateWarnings merges warnings of the same kind at the same schema
+	// path into one entry, keeping this many sample paths.
+	AggregateWarnings int `json:"aggregate-warnings,omitempty"`
+	// MaxWarnings keeps this many warnings, then one "truncated" entry
+	// counting the rest.
+	MaxWarnings int `json:"max-warnings,omitempty"`
 }
 
 // RehydrateResult is the result of a rehydrate operation.
@@ -86,10 +104,22 @@ type ExtractResult struct {
 	MissingRefs     []string       `json:"missingRefs"`
 }
 
+// ComponentInfo describes one listed component: its stats and $ref relationships.
+type ComponentInfo struct {
+	Pointer       string   `json:"pointer"`
+	Title         string   `json:"title,omitempty"`
+	Description   string   `json:"description,omitempty"`
+	PropertyCount int      `json:"propertyCount"`
+	Depth         int      `json:"depth"`
+	Recursive     bool     `json:"recursive"`
+	RefsOut       []string `json:"refsOut"`
+	RefsIn        []string `json:"refsIn"`
+}
+
 // ListComponentsResult is the result of a list_components operation.
 type ListComponentsResult struct {
-	APIVersion string   `json:"apiVersion"`
-	Components []string `json:"components"`
+	APIVersion string          `json:"apiVersion"`
+	Components []ComponentInfo `json:"components"`
 }
 
 // ConvertAllResult is the result of a convert_all_components operation.
@@ -242,6 +272,11 @@ func (e *SchemaLlmEngine) Convert(schema any, opts *ConvertOptions) (*ConvertRes
 
 // Rehydrate restores LLM output back to the original schema shape.
 func (e *SchemaLlmEngine) Rehydrate(data any, codec any, schema any) (*RehydrateResult, error) {
+	return e.RehydrateWithOptions(data, codec, schema, nil)
+}
+
+// RehydrateWithOptions is Rehydrate with opts; nil opts uses defaults.
+func (e *SchemaLlmEngine) RehydrateWithOptions(data any, codec any, schema any, opts *RehydrateOptions) (*RehydrateResult, error) {
 	dataBytes, err := json.Marshal(data)
 	if err != nil {
 		return nil, fmt.Errorf("marshal data: %w", err)
@@ -255,7 +290,16 @@ func (e *SchemaLlmEngine) Rehydrate(data any, codec any, schema any) (*Rehydrate
 		return nil, fmt.Errorf("marshal schema: %w", err)
 	}
 
-	payload, err := e.callJsl("jsl_rehydrate", dataBytes, codecBytes, schemaBytes)
+	var payload []byte
+	if opts != nil {
+		optsBytes, err := json.Marshal(opts)
+		if err != nil {
+			return nil, fmt.Errorf("marshal options: %w", err)
+		}
+		payload, err = e.callJsl("jsl_rehydrate_with_options", dataBytes, codecBytes, schemaBytes, optsBytes)
+	} else {
+		payload, err = e.callJsl("jsl_rehydrate", dataBytes, codecBytes, schemaBytes)
+	}
 	if err != nil {
 		return nil, err
 	}
@@ -286,6 +330,39 @@ func (e *SchemaLlmEngine) ListComponents(schema any) (*ListComponentsResult, err
 	return &result, nil
 }
 
+// SchemaFingerprint returns a "sha256:<hex>" fingerprint of a schema as
+// converted with opts, stable across key ordering. Use it to key caches or
+// to check that a stored codec still matches its schema.
+func (e *SchemaLlmEngine) SchemaFingerprint(schema any, opts *ConvertOptions) (string, error) {
+	schemaBytes, err := json.Marshal(schema)
+	if err != nil {
+		return "", fmt.Errorf("marshal schema: %w", err)
+	}
+
+	var optsBytes []byte
+	if opts != nil {
+		optsBytes, err = json.Marshal(opts)
+		if err != nil {
+			return "", fmt.Errorf("marshal options: %w", err)
+		}
+	} else {
+		optsBytes = []byte("{}")
+	}
+
+	payload, err := e.callJsl("jsl_schema_fingerprint", schemaBytes, optsBytes)
+	if err != nil {
+		return "", err
+	}
+
+	var result struct {
+		Fingerprint string `json:"fingerprint"`
+	}
+	if err := json.Unmarshal(payload, &result); err != nil {
+		return "", fmt.Errorf("unmarshal schema_fingerprint result: %w", err)
+	}
+	return result.Fingerprint, nil
+}
+
 // ExtractComponent extracts a single component from a schema by JSON Pointer.
 func (e *SchemaLlmEngine) ExtractComponent(schema any, pointer string, opts *ExtractOptions) (*ExtractResult, error) {
 	schemaBytes, err := json.Marshal(schema)
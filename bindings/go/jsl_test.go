@@ -165,6 +165,42 @@ func TestRehydrateError(t *testing.T) {
 	}
 }
 
+// TestRehydrateWithOptionsBoundsWarnings verifies MaxWarnings truncates
+// the warning list.
+func TestRehydrateWithOptionsBoundsWarnings(t *testing.T) {
+	eng, err := NewSchemaLlmEngine()
+	if err != nil {
+		t.Fatalf("NewSchemaLlmEngine() failed: %v", err)
+	}
+	defer eng.Close()
+
+	schema := map[string]any{
+		"type": "object",
+		"properties": map[string]any{
+			"tags": map[string]any{
+				"type":  "array",
+				"items": map[string]any{"type": "string", "maxLength": 2},
+			},
+		},
+	}
+	convertResult, err := eng.Convert(schema, nil)
+	if err != nil {
+		t.Fatalf("Convert() failed: %v", err)
+	}
+	data := map[string]any{"tags": []any{"long", "longer", "longest"}}
+
+	result, err := eng.RehydrateWithOptions(data, convertResult.Codec, schema, &RehydrateOptions{MaxWarnings: 1})
+	if err != nil {
+		t.Fatalf("RehydrateWithOptions() failed: %v", err)
+	}
+	if len(result.Warnings) != 2 {
+		t.Fatalf("expected 1 warning plus truncation, got %d", len(result.Warnings))
+	}
+	if result.Warnings[1].Kind.Type != "truncated" {
+		t.Errorf("last warning should be truncated, got %q", result.Warnings[1].Kind.Type)
+	}
+}
+
 // TestMultipleCalls verifies the engine can handle sequential calls.
 func TestMultipleCalls(t *testing.T) {
 	eng, err := NewSchemaLlmEngine()
@@ -306,7 +342,10 @@ func TestListComponents(t *testing.T) {
 		t.Error("apiVersion should not be empty")
 	}
 	if len(result.Components) != 2 {
-		t.Errorf("expected 2 components, got %d", len(result.Components))
+		t.Fatalf("expected 2 components, got %d", len(result.Components))
+	}
+	if result.Components[0].Pointer != "#/$defs/Pet" {
+		t.Errorf("expected #/$defs/Pet first, got %q", result.Components[0].Pointer)
 	}
 }
 
@@ -68,6 +68,16 @@ public final class ConvertOptions {
         private Builder() {
         }
 
+        /**
+         * Start from a curated option bundle; the other options override it.
+         * Values: "openai-structured-output", "openai-tool-calling",
+         * "gemini-structured-output", "claude-tool-use"
+         */
+        public Builder preset(String preset) {
+            root.put("preset", preset);
+            return this;
+        }
+
         /**
          * Set the LLM provider target format.
          * Values: "openai-strict", "gemini", "claude"
@@ -11,14 +11,14 @@ import com.fasterxml.jackson.databind.JsonNode;
  * <p>The expected JSON shape is:
  * <pre>{@code
  * {
- * "apiVersion": "1.0",
+ * "apiVersion": "2.0",
  * "schema": { ... },
  * "codec": { ... },
  * "providerCompatErrors": [ ... ] // optional
  * }
  * }</pre>
  *
- * @param apiVersion FFI envelope version (e.g. "1.0")
+ * @param apiVersion FFI envelope version (e.g. "2.0")
  * @param schema the converted LLM-compatible schema
  * @param codec the codec sidecar needed for rehydration
  * @param providerCompatErrors optional provider compatibility warnings (may be
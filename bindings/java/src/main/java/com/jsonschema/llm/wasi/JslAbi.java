@@ -70,6 +70,37 @@ final class JslAbi {
      */
     static JsonNode callExport(Instance instance, String funcName, String... jsonArgs)
             throws JslException {
+        ByteBuffer[] args = new ByteBuffer[jsonArgs.length];
+        for (int i = 0; i < jsonArgs.length; i++) {
+            args[i] = ByteBuffer.wrap(jsonArgs[i].getBytes(StandardCharsets.UTF_8));
+        }
+        ByteBuffer payload = callExportBytes(instance, funcName, args);
+        try {
+            return MAPPER.readTree(payload.array(), payload.arrayOffset(), payload.remaining());
+        } catch (Exception e) {
+            throw new RuntimeException("callExport failed: " + funcName, e);
+        }
+    }
+
+    /**
+     * Call a WASM export function with UTF-8 JSON byte arguments, returning the
+     * raw UTF-8 JSON payload.
+     *
+     * <p>
+     * Each argument's remaining bytes (position to limit) are copied straight
+     * into guest memory as a pointer + length pair, with no {@code String}
+     * decoding or re-encoding on either side; the buffers' positions are left
+     * untouched. Heap and direct buffers are both accepted. The payload length
+     * comes from the result struct, so payloads may contain any bytes.
+     *
+     * @param instance the WASM instance (fresh per call)
+     * @param funcName the export function name (e.g. "jsl_convert")
+     * @param args     UTF-8 JSON arguments
+     * @return a heap buffer holding the payload JSON
+     * @throws JslException if the WASM module returns an error status
+     */
+    static ByteBuffer callExportBytes(Instance instance, String funcName, ByteBuffer... args)
+            throws JslException {
         Memory memory = instance.memory();
         ExportFunction jslAlloc = instance.export("jsl_alloc");
         ExportFunction jslFree = instance.export("jsl_free");
@@ -81,8 +112,8 @@ final class JslAbi {
         int resultPtr = 0;
 
         try {
-            for (String arg : jsonArgs) {
-                byte[] bytes = arg.getBytes(StandardCharsets.UTF_8);
+            for (ByteBuffer arg : args) {
+                byte[] bytes = toBytes(arg);
                 long[] allocResult = jslAlloc.apply(bytes.length);
                 int ptr = (int) allocResult[0];
                 if (ptr == 0 && bytes.length > 0) {
@@ -115,10 +146,9 @@ final class JslAbi {
             }
 
             byte[] payloadBytes = memory.readBytes(payloadPtr, payloadLen);
-            String payloadStr = new String(payloadBytes, StandardCharsets.UTF_8);
-            JsonNode payload = MAPPER.readTree(payloadStr);
 
             if (status == STATUS_ERROR) {
+                JsonNode payload = MAPPER.readTree(payloadBytes);
                 throw new JslException(
                         payload.path("code").asText("unknown"),
                         payload.path("message").asText("unknown error"),
@@ -126,13 +156,14 @@ final class JslAbi {
             }
 
             if (status != STATUS_OK) {
+                String payloadStr = new String(payloadBytes, StandardCharsets.UTF_8);
                 throw new RuntimeException(
                         "ABI protocol violation: unexpected status=" + status
                                 + " (expected 0=OK or 1=ERROR). Payload: "
                                 + payloadStr.substring(0, Math.min(200, payloadStr.length())));
             }
 
-            return payload;
+            return ByteBuffer.wrap(payloadBytes);
         } catch (JslException e) {
             throw e;
         } catch (Exception e) {
@@ -146,4 +177,15 @@ final class JslAbi {
             }
         }
     }
+
+    /** The remaining bytes of {@code buf}, without moving its position. */
+    private static byte[] toBytes(ByteBuffer buf) {
+        if (buf.hasArray() && buf.arrayOffset() == 0 && buf.position() == 0
+                && buf.remaining() == buf.array().length) {
+            return buf.array();
+        }
+        byte[] bytes = new byte[buf.remaining()];
+        buf.duplicate().get(bytes);
+        return bytes;
+    }
 }
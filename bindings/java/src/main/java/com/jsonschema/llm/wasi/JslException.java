@@ -2,10 +2,13 @@ package com.jsonschema.llm.wasi;
 
 /**
  * Thrown when the json-schema-llm WASM module returns an error status.
- * Contains the error code, message, and optional path to the invalid data.
+ * Contains the error code, message, and optional path to the invalid data,
+ * mirroring the bridge error object {@code { code, message, path }} so callers
+ * can branch on {@link #getCode()} as Python and JS callers do.
  */
 public class JslException extends Exception {
     private final String code;
+    private final String errorMessage;
     private final String path;
 
     public JslException(String code, String message, String path) {
@@ -14,13 +17,23 @@ public class JslException extends Exception {
                 path != null && !path.isEmpty() ? " at " + path : "",
                 message));
         this.code = code;
+        this.errorMessage = message;
         this.path = path;
     }
 
+    /** Stable error code, e.g. {@code "schema_error"}; see the bridge API docs. */
     public String getCode() {
         return code;
     }
 
+    /**
+     * The bridge error message as returned, without the code and path prefix
+     * that {@link #getMessage()} adds.
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
     public String getPath() {
         return path;
     }
@@ -0,0 +1,104 @@
+package com.jsonschema.llm.wasi;
+
+import com.fasterxml.jackson.core.JsonProcessingException;
+import com.fasterxml.jackson.databind.ObjectMapper;
+import com.fasterxml.jackson.databind.node.ObjectNode;
+
+/**
+ * Fluent builder for rehydration options passed to the WASI
+ * {@code jsl_rehydrate_with_options} export.
+ *
+ * <p>
+ * Produces the kebab-case JSON that the WASM ABI expects.
+ *
+ * <p>
+ * Usage:
+ * 
+ * <pre>{@code
+ * RehydrateOptions opts = RehydrateOptions.builder()
+ *         .aggregateWarnings(3)
+ *         .maxWarnings(50)
+ *         .build();
+ *
+ * String json = opts.toJson(); // {"aggregate-warnings":3,"max-warnings":50}
+ * }</pre>
+ */
+public final class RehydrateOptions {
+
+    private static final ObjectMapper MAPPER = new ObjectMapper();
+    private final ObjectNode json;
+
+    private RehydrateOptions(ObjectNode json) {
+        this.json = json;
+    }
+
+    /**
+     * Create a new builder.
+     *
+     * @return a fresh builder instance
+     */
+    public static Builder builder() {
+        return new Builder();
+    }
+
+    /**
+     * Serialize this options object to a JSON string suitable for the WASM ABI.
+     *
+     * @return JSON string with kebab-case keys
+     */
+    public String toJson() {
+        try {
+            return MAPPER.writeValueAsString(json);
+        } catch (JsonProcessingException e) {
+            // Should never happen with ObjectNode
+            throw new RuntimeException("Failed to serialize RehydrateOptions", e);
+        }
+    }
+
+    /**
+     * Fluent builder for {@link RehydrateOptions}.
+     */
+    public static final class Builder {
+
+        private final ObjectNode root = MAPPER.createObjectNode();
+
+        private Builder() {
+        }
+
+        /**
+         * Merge warnings of the same kind at the same schema path into one
+         * entry with a {@code count}, keeping up to this many
+         * {@code samplePaths}. Default: off
+         */
+        public Builder aggregateWarnings(int maxSamples) {
+            if (maxSamples < 0) {
+                throw new IllegalArgumentException(
+                        "aggregateWarnings must be non-negative, got: " + maxSamples);
+            }
+            root.put("aggregate-warnings", maxSamples);
+            return this;
+        }
+
+        /**
+         * Keep at most this many warnings, then one {@code truncated} entry
+         * counting the rest. Default: unlimited
+         */
+        public Builder maxWarnings(int maxWarnings) {
+            if (maxWarnings < 0) {
+                throw new IllegalArgumentException(
+                        "maxWarnings must be non-negative, got: " + maxWarnings);
+            }
+            root.put("max-warnings", maxWarnings);
+            return this;
+        }
+
+        /**
+         * Build the immutable options object.
+         *
+         * @return a new {@link RehydrateOptions}
+         */
+        public RehydrateOptions build() {
+            return new RehydrateOptions(root.deepCopy());
+        }
+    }
+}
@@ -12,13 +12,13 @@ import com.fasterxml.jackson.databind.node.JsonNodeFactory;
  * <p>The expected JSON shape is:
  * <pre>{@code
  * {
- * "apiVersion": "1.0",
+ * "apiVersion": "2.0",
  * "data": { ... },
  * "warnings": [ ... ]
  * }
  * }</pre>
  *
- * @param apiVersion FFI envelope version (e.g. "1.0")
+ * @param apiVersion FFI envelope version (e.g. "2.0")
  * @param data the rehydrated data restored to match the original schema shape
  * @param warnings advisory warnings about constraint violations or type
  * coercions
@@ -10,6 +10,8 @@ import com.dylibso.chicory.wasm.WasmModule;
 import com.fasterxml.jackson.databind.ObjectMapper;
 
 import java.io.File;
+import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
 import java.nio.file.Path;
 
 /**
@@ -204,6 +206,27 @@ public class SchemaLlmEngine implements AutoCloseable {
      */
     public RehydrateResult rehydrate(Object data, Object codec, Object schema)
             throws JslException {
+        return rehydrate(data, codec, schema, null);
+    }
+
+    /**
+     * Rehydrate LLM output back to the original schema structure with
+     * options bounding the warnings returned.
+     *
+     * <p>
+     * Thread-safe: creates a fresh WASM Instance per call.
+     *
+     * @param data    the LLM-generated JSON data
+     * @param codec   the codec sidecar from a prior conversion
+     * @param schema  the original JSON Schema
+     * @param options rehydration options built via
+     *                {@link RehydrateOptions#builder()}, or null for defaults
+     * @return a typed {@link RehydrateResult} with data and warnings
+     * @throws JslException          if the WASM module returns an error
+     * @throws IllegalStateException if the engine has been closed
+     */
+    public RehydrateResult rehydrate(Object data, Object codec, Object schema, RehydrateOptions options)
+            throws JslException {
         ensureOpen();
         try (WasiScope scope = openWasiScope()) {
             String dataJson = MAPPER.writeValueAsString(data);
@@ -211,8 +234,10 @@ public class SchemaLlmEngine implements AutoCloseable {
             String schemaJson = MAPPER.writeValueAsString(schema);
 
             verifyAbiOnce(scope.instance);
-            com.fasterxml.jackson.databind.JsonNode raw = JslAbi.callExport(scope.instance, "jsl_rehydrate", dataJson,
-                    codecJson, schemaJson);
+            com.fasterxml.jackson.databind.JsonNode raw = options == null
+                    ? JslAbi.callExport(scope.instance, "jsl_rehydrate", dataJson, codecJson, schemaJson)
+                    : JslAbi.callExport(scope.instance, "jsl_rehydrate_with_options", dataJson, codecJson,
+                            schemaJson, options.toJson());
             return RehydrateResult.fromJson(raw);
         } catch (JslException e) {
             throw e;
@@ -222,10 +247,70 @@ public class SchemaLlmEngine implements AutoCloseable {
     }
 
     /**
-     * List all extractable component JSON Pointers in a schema.
+     * Convert a JSON Schema given as UTF-8 JSON bytes, returning the result
+     * envelope as UTF-8 JSON bytes.
+     *
+     * <p>
+     * For callers that already hold JSON as bytes (direct buffers from
+     * network or file channels): no Jackson tree or {@code String} is built on
+     * either side. Only the buffers' remaining bytes are read and their
+     * positions are not changed.
+     *
+     * <p>
+     * Thread-safe: creates a fresh WASM Instance per call.
+     *
+     * @param schemaJson  the JSON Schema as UTF-8 JSON
+     * @param optionsJson conversion options as UTF-8 JSON (kebab-case keys), or
+     *                    null for defaults
+     * @return the convert envelope ({@code apiVersion}, {@code schema},
+     *         {@code codec}, ...) as UTF-8 JSON
+     * @throws JslException          if the WASM module returns an error
+     * @throws IllegalStateException if the engine has been closed
+     */
+    public ByteBuffer convertBytes(ByteBuffer schemaJson, ByteBuffer optionsJson)
+            throws JslException {
+        ByteBuffer opts = optionsJson != null
+                ? optionsJson
+                : ByteBuffer.wrap("{}".getBytes(StandardCharsets.UTF_8));
+        return callBytes("jsl_convert", schemaJson, opts);
+    }
+
+    /**
+     * Rehydrate LLM output given as UTF-8 JSON bytes, returning the result
+     * envelope as UTF-8 JSON bytes. See {@link #convertBytes}.
+     *
+     * @param dataJson   the LLM-generated data as UTF-8 JSON
+     * @param codecJson  the codec sidecar as UTF-8 JSON
+     * @param schemaJson the original JSON Schema as UTF-8 JSON
+     * @return the rehydrate envelope ({@code apiVersion}, {@code data},
+     *         {@code warnings}) as UTF-8 JSON
+     * @throws JslException          if the WASM module returns an error
+     * @throws IllegalStateException if the engine has been closed
+     */
+    public ByteBuffer rehydrateBytes(ByteBuffer dataJson, ByteBuffer codecJson, ByteBuffer schemaJson)
+            throws JslException {
+        return callBytes("jsl_rehydrate", dataJson, codecJson, schemaJson);
+    }
+
+    private ByteBuffer callBytes(String funcName, ByteBuffer... args) throws JslException {
+        ensureOpen();
+        try (WasiScope scope = openWasiScope()) {
+            verifyAbiOnce(scope.instance);
+            return JslAbi.callExportBytes(scope.instance, funcName, args);
+        } catch (JslException e) {
+            throw e;
+        } catch (Exception e) {
+            throw new RuntimeException(funcName + " failed", e);
+        }
+    }
+
+    /**
+     * List all extractable components in a schema.
      *
      * @param schema the JSON Schema
-     * @return a JsonNode with apiVersion and components array
+     * @return a JsonNode with apiVersion and a components array, one entry per
+     *     component (pointer, title, description, propertyCount, depth,
+     *     recursive, refsOut, refsIn)
      * @throws JslException if the WASM module returns an error
      */
     public com.fasterxml.jackson.databind.JsonNode listComponents(Object schema)
@@ -243,6 +328,31 @@ public class SchemaLlmEngine implements AutoCloseable {
         }
     }
 
+    /**
+     * Fingerprint a schema as converted with the given options.
+     *
+     * @param schema  the JSON Schema (any Jackson-serializable object)
+     * @param options conversion options, or null for defaults
+     * @return {@code "sha256:<hex>"}, stable across key ordering
+     * @throws JslException if the WASM module returns an error
+     */
+    public String schemaFingerprint(Object schema, ConvertOptions options)
+            throws JslException {
+        ensureOpen();
+        try (WasiScope scope = openWasiScope()) {
+            String schemaJson = MAPPER.writeValueAsString(schema);
+            String optsJson = options != null ? options.toJson() : "{}";
+
+            verifyAbiOnce(scope.instance);
+            return JslAbi.callExport(scope.instance, "jsl_schema_fingerprint", schemaJson, optsJson)
+                    .get("fingerprint").asText();
+        } catch (JslException e) {
+            throw e;
+        } catch (Exception e) {
+            throw new RuntimeException("schemaFingerprint failed", e);
+        }
+    }
+
     /**
      * Extract a single component from a schema by JSON Pointer.
      *
@@ -9,6 +9,8 @@ import org.junit.jupiter.api.AfterAll;
 import org.junit.jupiter.api.BeforeAll;
 import org.junit.jupiter.api.Test;
 
+import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
 import java.nio.file.Path;
 import java.nio.file.Paths;
 import java.util.ArrayList;
@@ -122,6 +124,56 @@ class SchemaLlmEngineTest {
     assertEquals(95, rehydrateResult.data().get("score").asInt());
   }
 
+  @Test
+  void rehydrateWithOptionsAggregatesWarnings() throws Exception {
+    JsonNode schema = MAPPER.readTree("""
+        {
+          "type": "object",
+          "properties": {
+            "tags": { "type": "array", "items": { "type": "string", "maxLength": 2 } }
+          }
+        }
+        """);
+    ConvertResult convertResult = engine.convert(schema);
+
+    JsonNode llmData = MAPPER.readTree("{\"tags\": [\"long\", \"longer\", \"longest\"]}");
+    RehydrateResult rehydrateResult = engine.rehydrate(
+        llmData, convertResult.codec(), schema,
+        RehydrateOptions.builder().aggregateWarnings(2).build());
+
+    assertEquals(1, rehydrateResult.warnings().size());
+    JsonNode warning = rehydrateResult.warnings().get(0);
+    assertEquals(3, warning.get("count").asInt());
+    assertEquals(2, warning.get("samplePaths").size());
+  }
+
+  @Test
+  void byteBufferRoundTrip() throws Exception {
+    byte[] schema = """
+        {"type": "object", "properties": {"name": {"type": "string"}}, "required": ["name"]}
+        """.getBytes(StandardCharsets.UTF_8);
+    ByteBuffer direct = ByteBuffer.allocateDirect(schema.length).put(schema).flip();
+
+    ByteBuffer converted = engine.convertBytes(direct, null);
+    assertEquals(0, direct.position(), "argument position is untouched");
+    JsonNode envelope = MAPPER.readTree(StandardCharsets.UTF_8.decode(converted).toString());
+    assertEquals("1.0", envelope.get("apiVersion").asText());
+
+    ByteBuffer rehydrated = engine.rehydrateBytes(
+        ByteBuffer.wrap("{\"name\": \"Ada\"}".getBytes(StandardCharsets.UTF_8)),
+        ByteBuffer.wrap(MAPPER.writeValueAsBytes(envelope.get("codec"))),
+        ByteBuffer.wrap(schema));
+    JsonNode data = MAPPER.readTree(StandardCharsets.UTF_8.decode(rehydrated).toString()).get("data");
+    assertEquals("Ada", data.get("name").asText());
+  }
+
+  @Test
+  void byteBufferErrorsAreStructured() {
+    JslException e = assertThrows(JslException.class, () -> engine.convertBytes(
+        ByteBuffer.wrap("{not json".getBytes(StandardCharsets.UTF_8)), null));
+    assertEquals("json_parse_error", e.getCode());
+  }
+
   // ---------------------------------------------------------------
   // Thread safety
   // ---------------------------------------------------------------
@@ -233,6 +285,16 @@ class SchemaLlmEngineTest {
     assertThrows(JslException.class,
         () -> engine.rehydrate(data, "NOT_A_VALID_CODEC", schema));
   }
+
+  @Test
+  void errorCarriesCodeMessageAndPath() throws Exception {
+    JsonNode schema = MAPPER.readTree("{\"$ref\": \"#/$defs/Missing\"}");
+    JslException e = assertThrows(JslException.class, () -> engine.convert(schema));
+    assertEquals("unresolvable_ref", e.getCode());
+    assertFalse(e.getErrorMessage().isEmpty());
+    assertTrue(e.getMessage().contains(e.getErrorMessage()));
+    assertNotNull(e.getPath());
+  }
   // ---------------------------------------------------------------
   // Apply Patch — #276 diagnostic tests
   // ---------------------------------------------------------------
@@ -219,6 +219,26 @@ class TypedApiTest {
     });
   }
 
+  @Test
+  void rehydrateOptionsBuilderGeneratesCorrectJson() throws Exception {
+    RehydrateOptions opts = RehydrateOptions.builder()
+        .aggregateWarnings(3)
+        .maxWarnings(50)
+        .build();
+
+    JsonNode parsed = MAPPER.readTree(opts.toJson());
+
+    assertEquals(3, parsed.get("aggregate-warnings").asInt());
+    assertEquals(50, parsed.get("max-warnings").asInt());
+  }
+
+  @Test
+  void rehydrateOptionsBuilderRejectsNegativeMaxWarnings() {
+    assertThrows(IllegalArgumentException.class, () -> {
+      RehydrateOptions.builder().maxWarnings(-1).build();
+    });
+  }
+
   // ---------------------------------------------------------------
   // Round-trip integration tests
   // ---------------------------------------------------------------
@@ -1,7 +1,8 @@
 """json-schema-llm WASI bindings — consumer-ready Python SDK.
 
-Provides typed results, options builder, and SchemaLlmEngine facade
-for zero-config WASM-powered schema conversion and rehydration.
+Provides typed results, options builder, the SchemaLlmEngine facade and
+a caching Converter for zero-config WASM-powered schema conversion and
+rehydration.
 
 Usage::
 
@@ -10,25 +11,42 @@ Usage::
     with SchemaLlmEngine() as engine:
         result = engine.convert(schema, ConvertOptions(target="openai-strict"))
         print(result.schema)
+
+    with Converter(target="openai-strict") as c:
+        result = c.convert(schema)  # cached for the next call
 """
 
+from json_schema_llm_wasi.converter import Converter
 from json_schema_llm_wasi.engine import JslError, SchemaLlmEngine
+from json_schema_llm_wasi.helpers import (
+    rehydrate_many,
+    rehydrate_to_dataclass,
+    rehydrate_to_dataframe,
+)
 from json_schema_llm_wasi.types import (
+    ComponentInfo,
     ConvertAllComponentsResult,
     ConvertOptions,
     ConvertResult,
     ExtractComponentResult,
     ListComponentsResult,
+    RehydrateOptions,
     RehydrateResult,
 )
 
 __all__ = [
     "SchemaLlmEngine",
+    "Converter",
     "JslError",
     "ConvertResult",
     "RehydrateResult",
     "ListComponentsResult",
+    "ComponentInfo",
     "ExtractComponentResult",
     "ConvertAllComponentsResult",
     "ConvertOptions",
+    "RehydrateOptions",
+    "rehydrate_to_dataclass",
+    "rehydrate_many",
+    "rehydrate_to_dataframe",
 ]
@@ -0,0 +1,122 @@
+"""
+Converter: a configured, caching front end over SchemaLlmEngine.
+
+A Converter fixes one set of ConvertOptions, owns (or borrows) an engine
+with its compiled WASM module, and memoizes conversions by the schema's
+compact JSON. Repeated ``convert`` calls for a schema already seen, and
+``rehydrate`` calls against it, skip the conversion entirely, which is where
+most per-call time goes in services that serve a fixed set of schemas.
+
+Concurrency: like SchemaLlmEngine, a Converter is NOT thread-safe. Use one
+per thread.
+"""
+
+import dataclasses
+import json
+from collections import OrderedDict
+from typing import Any, Optional
+
+from json_schema_llm_wasi.engine import SchemaLlmEngine
+from json_schema_llm_wasi.types import ConvertOptions, ConvertResult, RehydrateResult
+
+DEFAULT_CACHE_SIZE = 128
+
+
+def _schema_key(schema: Any) -> str:
+    """Compact JSON for ``schema``. Key order is kept: it decides property
+    order in the converted schema, so it is part of the cache key."""
+    return json.dumps(schema, separators=(",", ":"))
+
+
+class Converter:
+    """Converts and rehydrates with fixed options, caching per schema.
+
+    Usage::
+
+        with Converter(target="openai-strict") as c:
+            result = c.convert(schema)          # converted once
+            restored = c.rehydrate(data, schema)  # reuses the cached codec
+
+    ``options`` sets the full ConvertOptions; ``target`` overrides its target.
+    Pass ``engine`` to share one compiled module between converters; the
+    converter then leaves it open on exit. Up to ``cache_size`` schemas are
+    kept, least recently used first out.
+    """
+
+    def __init__(
+        self,
+        target: Optional[str] = None,
+        *,
+        options: Optional[ConvertOptions] = None,
+        engine: Optional[SchemaLlmEngine] = None,
+        wasm_path: Optional[str] = None,
+        cache_size: int = DEFAULT_CACHE_SIZE,
+    ):
+        if cache_size < 1:
+            raise ValueError("cache_size must be at least 1")
+        options = options or ConvertOptions()
+        if target is not None:
+            options = dataclasses.replace(options, target=target)
+        self.options = options
+        self._options_json = json.dumps(options.to_dict())
+        self._owns_engine = engine is None
+        self._engine = engine if engine is not None else SchemaLlmEngine(wasm_path)
+        self._cache: "OrderedDict[str, ConvertResult]" = OrderedDict()
+        self._cache_size = cache_size
+        self._hits = 0
+        self._misses = 0
+        self._closed = False
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *_):
+        self.close()
+
+    def close(self) -> None:
+        """Drop the cache and release the engine if this converter made it."""
+        if self._closed:
+            return
+        self._closed = True
+        self._cache.clear()
+        if self._owns_engine:
+            self._engine.__exit__(None, None, None)
+
+    def convert(self, schema: Any) -> ConvertResult:
+        """Convert ``schema``, or return the cached result for an equal one."""
+        return self._lookup(schema)
+
+    def rehydrate(self, data: Any, schema: Any) -> RehydrateResult:
+        """Rehydrate LLM output for ``schema`` using its cached codec."""
+        converted = self._lookup(schema)
+        return self._engine.rehydrate(data, converted.codec, schema)
+
+    def cache_info(self) -> dict:
+        """Cache statistics: ``hits``, ``misses``, ``size`` and ``max_size``."""
+        return {
+            "hits": self._hits,
+            "misses": self._misses,
+            "size": len(self._cache),
+            "max_size": self._cache_size,
+        }
+
+    def clear_cache(self) -> None:
+        """Forget every cached conversion."""
+        self._cache.clear()
+
+    def _lookup(self, schema: Any) -> ConvertResult:
+        if self._closed:
+            raise RuntimeError("Converter is closed")
+        key = _schema_key(schema)
+        cached = self._cache.get(key)
+        if cached is not None:
+            self._hits += 1
+            self._cache.move_to_end(key)
+            return cached
+        self._misses += 1
+        raw = self._engine._call_jsl("jsl_convert", key, self._options_json)
+        result = ConvertResult.from_dict(raw)
+        self._cache[key] = result
+        if len(self._cache) > self._cache_size:
+            self._cache.popitem(last=False)
+        return result
@@ -4,12 +4,14 @@ WASI-backed SchemaLlmEngine for json-schema-llm.
 Uses wasmtime-py to load the universal WASI binary and exposes
 typed schema conversion and rehydration operations.
 
-Concurrency: Each SchemaLlmEngine owns its own Store. NOT thread-safe.
+Concurrency: Each SchemaLlmEngine owns its own Store. NOT thread-safe,
+except for ``cancel()``.
 """
 
 import json
 import os
 import struct
+import threading
 from typing import Any, Optional
 
 import wasmtime
@@ -20,6 +22,7 @@ from json_schema_llm_wasi.types import (
     ConvertResult,
     ExtractComponentResult,
     ListComponentsResult,
+    RehydrateOptions,
     RehydrateResult,
 )
 
@@ -61,7 +64,11 @@ class SchemaLlmEngine:
 
     def __init__(self, wasm_path: Optional[str] = None):
         path = wasm_path or os.environ.get("JSL_WASM_PATH", _DEFAULT_WASM_PATH)
-        self._engine = wasmtime.Engine()
+        config = wasmtime.Config()
+        # Lets cancel() interrupt a running guest call.
+        config.epoch_interruption = True
+        self._engine = wasmtime.Engine(config)
+        self._cancelled = False
         self._module = wasmtime.Module.from_file(self._engine, path)
         self._linker = wasmtime.Linker(self._engine)
         self._linker.define_wasi()
@@ -73,6 +80,17 @@ class SchemaLlmEngine:
     def __exit__(self, *_):
         pass  # No persistent resources to clean up
 
+    def cancel(self) -> None:
+        """Abort the call currently running on this engine.
+
+        Safe to call from another thread. The running call raises
+        ``JslError`` with code ``cancelled``; later calls are unaffected.
+        Ctrl-C during a call cancels it the same way and then raises
+        ``KeyboardInterrupt``.
+        """
+        self._cancelled = True
+        self._engine.increment_epoch()
+
     def convert(
         self, schema: Any, options: Optional[ConvertOptions] = None
     ) -> ConvertResult:
@@ -83,12 +101,24 @@ class SchemaLlmEngine:
         raw = self._call_jsl("jsl_convert", schema_json, opts_json)
         return ConvertResult.from_dict(raw)
 
-    def rehydrate(self, data: Any, codec: Any, schema: Any) -> RehydrateResult:
+    def rehydrate(
+        self,
+        data: Any,
+        codec: Any,
+        schema: Any,
+        options: Optional[RehydrateOptions] = None,
+    ) -> RehydrateResult:
         """Rehydrate LLM output back to original schema shape."""
         data_json = json.dumps(data)
         codec_json = json.dumps(codec)
         schema_json = json.dumps(schema)
-        raw = self._call_jsl("jsl_rehydrate", data_json, codec_json, schema_json)
+        if options is None:
+            raw = self._call_jsl("jsl_rehydrate", data_json, codec_json, schema_json)
+        else:
+            opts_json = json.dumps(options.to_dict())
+            raw = self._call_jsl(
+                "jsl_rehydrate_with_options", data_json, codec_json, schema_json, opts_json
+            )
         return RehydrateResult.from_dict(raw)
 
     def list_components(self, schema: Any) -> ListComponentsResult:
@@ -97,6 +127,16 @@ class SchemaLlmEngine:
         raw = self._call_jsl("jsl_list_components", schema_json)
         return ListComponentsResult.from_dict(raw)
 
+    def schema_fingerprint(
+        self, schema: Any, options: Optional[ConvertOptions] = None
+    ) -> str:
+        """Return a ``sha256:<hex>`` fingerprint of a schema as converted with
+        ``options``, stable across key ordering."""
+        schema_json = json.dumps(schema)
+        opts_json = json.dumps(options.to_dict() if options else {})
+        raw = self._call_jsl("jsl_schema_fingerprint", schema_json, opts_json)
+        return raw["fingerprint"]
+
     def extract_component(
         self, schema: Any, pointer: str, options: Optional[dict] = None
     ) -> ExtractComponentResult:
@@ -123,10 +163,40 @@ class SchemaLlmEngine:
         return ConvertAllComponentsResult.from_dict(raw)
 
     def _call_jsl(self, func_name: str, *json_args: str) -> dict:
+        """Run ``_run_jsl`` on a worker thread so Ctrl-C can interrupt it.
+
+        Python delivers KeyboardInterrupt to the main thread between
+        bytecodes, never inside a WASM call. Waiting on a worker instead lets
+        the interrupt arrive, cancel the guest, and propagate.
+        """
+        outcome: dict = {}
+
+        def run() -> None:
+            try:
+                outcome["value"] = self._run_jsl(func_name, *json_args)
+            except BaseException as e:  # re-raised on the calling thread
+                outcome["error"] = e
+
+        self._cancelled = False
+        worker = threading.Thread(target=run, name=f"jsl-{func_name}", daemon=True)
+        worker.start()
+        try:
+            while worker.is_alive():
+                worker.join(0.1)
+        except KeyboardInterrupt:
+            self.cancel()
+            worker.join()
+            raise
+        if "error" in outcome:
+            raise outcome["error"]
+        return outcome["value"]
+
+    def _run_jsl(self, func_name: str, *json_args: str) -> dict:
         """Execute a WASI export following the JslResult protocol."""
         # Fresh store + instance per call (WASI modules are single-use)
         store = wasmtime.Store(self._engine)
         store.set_wasi(wasmtime.WasiConfig())
+        store.set_epoch_deadline(1)
         instance = self._linker.instantiate(store, self._module)
 
         # ABI version handshake (once per Engine lifetime)
@@ -166,7 +236,14 @@ class SchemaLlmEngine:
         # Call, read, and parse — with guaranteed cleanup
         result_ptr = 0
         try:
-            result_ptr = func(store, *flat_args)
+            try:
+                result_ptr = func(store, *flat_args)
+            except wasmtime.Trap as e:
+                if self._cancelled:
+                    # The instance is dead; its memory is dropped with the store.
+                    allocs.clear()
+                    raise JslError(code="cancelled", message="conversion cancelled") from e
+                raise
             if result_ptr == 0:
                 raise RuntimeError(f"{func_name} returned null result pointer")
 
@@ -0,0 +1,140 @@
+"""
+Helpers that turn rehydrated output into Python objects.
+
+``rehydrate_to_dataclass`` and ``rehydrate_many`` rehydrate LLM output and map
+the resulting dicts onto dataclasses or Pydantic models; ``rehydrate_to_dataframe``
+flattens a batch into a pandas DataFrame. pandas and Pydantic are optional:
+they are only imported when used.
+
+Dataclass fields match schema properties by name, by a ``"json"`` metadata
+alias (``field(metadata={"json": "first-name"})``), or by snake_case form
+(``firstName`` → ``first_name``). Nested dataclasses, ``list[...]``,
+``dict[str, ...]`` and ``Optional[...]`` fields are built recursively;
+properties with no matching field are ignored.
+"""
+
+import dataclasses
+import functools
+import re
+import types
+import typing
+from typing import Any, Iterable, Optional, TypeVar
+
+from json_schema_llm_wasi.engine import SchemaLlmEngine
+
+T = TypeVar("T")
+
+
+@functools.lru_cache(maxsize=1)
+def _default_engine() -> SchemaLlmEngine:
+    return SchemaLlmEngine()
+
+
+def rehydrate_to_dataclass(
+    data: Any,
+    codec: Any,
+    schema: Any,
+    cls: "type[T]",
+    *,
+    engine: Optional[SchemaLlmEngine] = None,
+) -> T:
+    """Rehydrate ``data`` and build an instance of ``cls`` from it.
+
+    ``cls`` is a dataclass or a Pydantic model. Uses a shared default engine
+    unless ``engine`` is given.
+    """
+    result = (engine or _default_engine()).rehydrate(data, codec, schema)
+    return to_model(result.data, cls)
+
+
+def rehydrate_many(
+    items: Iterable[Any],
+    codec: Any,
+    schema: Any,
+    cls: "Optional[type[T]]" = None,
+    *,
+    engine: Optional[SchemaLlmEngine] = None,
+) -> list:
+    """Rehydrate each of ``items``; map them onto ``cls`` when given."""
+    engine = engine or _default_engine()
+    out = []
+    for item in items:
+        data = engine.rehydrate(item, codec, schema).data
+        out.append(to_model(data, cls) if cls is not None else data)
+    return out
+
+
+def rehydrate_to_dataframe(
+    items: Iterable[Any],
+    codec: Any,
+    schema: Any,
+    *,
+    engine: Optional[SchemaLlmEngine] = None,
+):
+    """Rehydrate ``items`` into a pandas DataFrame, one row per item.
+
+    Nested objects become dotted columns (``user.name``), as with
+    ``pandas.json_normalize``.
+    """
+    import pandas
+
+    return pandas.json_normalize(rehydrate_many(items, codec, schema, engine=engine))
+
+
+def to_model(value: Any, cls: "type[T]") -> T:
+    """Build ``cls`` (dataclass or Pydantic model) from a rehydrated value."""
+    if hasattr(cls, "model_validate"):  # Pydantic v2
+        return cls.model_validate(value)
+    if hasattr(cls, "parse_obj"):  # Pydantic v1
+        return cls.parse_obj(value)
+    return _build(value, cls)
+
+
+def _build(value: Any, tp: Any) -> Any:
+    if value is None:
+        return None
+    origin = typing.get_origin(tp)
+    args = typing.get_args(tp)
+    if origin is typing.Union or origin is types.UnionType:
+        inner = [a for a in args if a is not type(None)]
+        return _build(value, inner[0]) if len(inner) == 1 else value
+    if origin in (list, tuple, set) and isinstance(value, list):
+        item_type = args[0] if args else Any
+        return origin(_build(v, item_type) for v in value)
+    if origin is dict and isinstance(value, dict):
+        value_type = args[1] if len(args) == 2 else Any
+        return {k: _build(v, value_type) for k, v in value.items()}
+    if dataclasses.is_dataclass(tp) and isinstance(value, dict):
+        return _build_dataclass(value, tp)
+    if isinstance(tp, type) and hasattr(tp, "model_validate"):
+        return tp.model_validate(value)
+    return value
+
+
+def _build_dataclass(value: dict, cls: type) -> Any:
+    hints = typing.get_type_hints(cls)
+    kwargs = {}
+    for f in dataclasses.fields(cls):
+        if not f.init:
+            continue
+        key = _find_key(value, f)
+        if key is not None:
+            kwargs[f.name] = _build(value[key], hints.get(f.name, Any))
+    return cls(**kwargs)
+
+
+def _find_key(value: dict, f: dataclasses.Field) -> Optional[str]:
+    alias = f.metadata.get("json")
+    if alias is not None:
+        return alias if alias in value else None
+    if f.name in value:
+        return f.name
+    for key in value:
+        if _snake_case(key) == f.name:
+            return key
+    return None
+
+
+def _snake_case(name: str) -> str:
+    name = re.sub(r"([a-z0-9])([A-Z])", r"\1_\2", name)
+    return re.sub(r"[^0-9a-zA-Z]+", "_", name).lower()
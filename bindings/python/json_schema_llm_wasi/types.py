@@ -30,13 +30,56 @@ class ProviderCompatError:
 
 @dataclass(frozen=True)
 class RehydrateWarning:
-    """Warning produced during schema rehydration."""
+    """Warning produced during schema rehydration.
 
-    msg: str
+    ``count`` is above 1 and ``sample_paths`` non-empty only for warnings
+    merged by ``RehydrateOptions.aggregate_warnings``.
+    """
+
+    data_path: str
+    schema_path: str
+    kind: dict
+    message: str
+    count: int = 1
+    sample_paths: list[str] = field(default_factory=list)
 
     @classmethod
     def from_dict(cls, raw: dict) -> RehydrateWarning:
-        return cls(msg=raw["msg"])
+        return cls(
+            data_path=raw["dataPath"],
+            schema_path=raw["schemaPath"],
+            kind=raw["kind"],
+            message=raw["message"],
+            count=raw.get("count", 1),
+            sample_paths=raw.get("samplePaths", []),
+        )
+
+
+@dataclass(frozen=True)
+class ComponentInfo:
+    """A listed component with its stats and $ref relationships."""
+
+    pointer: str
+    property_count: int
+    depth: int
+    recursive: bool
+    refs_out: list[str]
+    refs_in: list[str]
+    title: str | None = None
+    description: str | None = None
+
+    @classmethod
+    def from_dict(cls, raw: dict) -> ComponentInfo:
+        return cls(
+            pointer=raw["pointer"],
+            property_count=raw["propertyCount"],
+            depth=raw["depth"],
+            recursive=raw["recursive"],
+            refs_out=raw["refsOut"],
+            refs_in=raw["refsIn"],
+            title=raw.get("title"),
+            description=raw.get("description"),
+        )
 
 
 @dataclass(frozen=True)
@@ -120,13 +163,13 @@ class ListComponentsResult:
     """Typed result of a list_components operation."""
 
     api_version: str
-    components: list[str]
+    components: list[ComponentInfo]
 
     @classmethod
     def from_dict(cls, raw: dict) -> ListComponentsResult:
         return cls(
             api_version=raw["apiVersion"],
-            components=raw["components"],
+            components=[ComponentInfo.from_dict(c) for c in raw["components"]],
         )
 
 
@@ -189,8 +232,12 @@ class ConvertOptions:
 
         # Direct construction (idiomatic Python)
         opts = ConvertOptions(target="openai-strict", max_depth=50)
+
+        # Named bundle, with explicit fields overriding it
+        opts = ConvertOptions(preset="openai-tool-calling", recursion_limit=2)
     """
 
+    preset: str | None = None
     target: str | None = None
     mode: str | None = None
     max_depth: int | None = None
@@ -200,6 +247,8 @@ class ConvertOptions:
     def to_dict(self) -> dict:
         """Serialize to a dict with kebab-case keys for the WASM ABI."""
         result = {}
+        if self.preset is not None:
+            result["preset"] = self.preset
         if self.target is not None:
             result["target"] = self.target
         if self.mode is not None:
@@ -211,3 +260,27 @@ class ConvertOptions:
         if self.polymorphism is not None:
             result["polymorphism"] = self.polymorphism
         return result
+
+
+@dataclass
+class RehydrateOptions:
+    """Options for rehydration, with WASM ABI key normalization.
+
+    Usage::
+
+        # One warning per kind and schema path, with up to 3 sample paths,
+        # and no more than 50 in all
+        opts = RehydrateOptions(aggregate_warnings=3, max_warnings=50)
+    """
+
+    aggregate_warnings: int | None = None
+    max_warnings: int | None = None
+
+    def to_dict(self) -> dict:
+        """Serialize to a dict with kebab-case keys for the WASM ABI."""
+        result = {}
+        if self.aggregate_warnings is not None:
+            result["aggregate-warnings"] = self.aggregate_warnings
+        if self.max_warnings is not None:
+            result["max-warnings"] = self.max_warnings
+        return result
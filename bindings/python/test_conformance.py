@@ -9,7 +9,7 @@ import os
 
 import pytest
 
-from json_schema_llm_wasi import SchemaLlmEngine, JslError, ConvertOptions
+from json_schema_llm_wasi import ComponentInfo, SchemaLlmEngine, JslError, ConvertOptions
 
 FIXTURES_PATH = os.path.join(
     os.path.dirname(__file__), "..", "..", "tests", "conformance", "fixtures.json"
@@ -230,7 +230,9 @@ def test_conformance_list_components(engine, fixtures, fixture_id):
         assert result.api_version == expected["apiVersion"]
 
     if "components" in expected:
-        assert result.components == expected["components"]
+        assert result.components == [
+            ComponentInfo.from_dict(c) for c in expected["components"]
+        ]
 
 
 # ---------------------------------------------------------------------------
@@ -0,0 +1,75 @@
+"""Tests for the caching Converter."""
+
+import pytest
+
+from json_schema_llm_wasi import Converter, ConvertOptions, ConvertResult, RehydrateResult
+
+
+class CountingEngine:
+    """Stand-in engine that records calls instead of running WASM."""
+
+    def __init__(self):
+        self.calls = []
+
+    def _call_jsl(self, func_name, *json_args):
+        self.calls.append((func_name, *json_args))
+        return {"apiVersion": "1.0", "schema": {}, "codec": {"n": len(self.calls)}}
+
+    def rehydrate(self, data, codec, schema):
+        self.calls.append(("rehydrate", codec))
+        return RehydrateResult(api_version="1.0", data=data)
+
+
+SCHEMA = {"type": "object", "properties": {"x": {"type": "string"}}}
+
+
+def test_convert_is_cached_per_schema():
+    engine = CountingEngine()
+    with Converter(target="gemini", engine=engine) as c:
+        first = c.convert(SCHEMA)
+        assert c.convert(dict(SCHEMA)) is first
+        c.convert({"type": "string"})
+        assert c.cache_info() == {"hits": 1, "misses": 2, "size": 2, "max_size": 128}
+    assert len(engine.calls) == 2
+    assert engine.calls[0][2] == '{"target": "gemini"}'
+
+
+def test_rehydrate_reuses_cached_codec():
+    engine = CountingEngine()
+    with Converter(engine=engine) as c:
+        codec = c.convert(SCHEMA).codec
+        result = c.rehydrate({"x": "a"}, SCHEMA)
+    assert result.data == {"x": "a"}
+    assert engine.calls[-1] == ("rehydrate", codec)
+    assert [call[0] for call in engine.calls] == ["jsl_convert", "rehydrate"]
+
+
+def test_target_overrides_options():
+    c = Converter("claude", options=ConvertOptions(target="gemini", max_depth=5), engine=CountingEngine())
+    assert c.options == ConvertOptions(target="claude", max_depth=5)
+
+
+def test_cache_evicts_least_recently_used():
+    engine = CountingEngine()
+    c = Converter(engine=engine, cache_size=1)
+    c.convert({"type": "string"})
+    c.convert({"type": "integer"})
+    c.convert({"type": "string"})
+    assert c.cache_info()["misses"] == 3
+
+
+def test_closed_converter_rejects_calls():
+    c = Converter(engine=CountingEngine())
+    c.close()
+    with pytest.raises(RuntimeError):
+        c.convert(SCHEMA)
+
+
+def test_converter_with_engine():
+    """Live round trip through the WASM binary."""
+    with Converter(target="openai-strict") as c:
+        result = c.convert(SCHEMA)
+        assert isinstance(result, ConvertResult)
+        restored = c.rehydrate({"x": "a"}, SCHEMA)
+        assert restored.data == {"x": "a"}
+        assert c.cache_info()["hits"] == 1
@@ -0,0 +1,74 @@
+"""Tests for the rehydration-to-object helpers."""
+
+from dataclasses import dataclass, field
+from typing import Optional
+
+from json_schema_llm_wasi import RehydrateResult, rehydrate_many, rehydrate_to_dataclass
+from json_schema_llm_wasi.helpers import to_model
+
+
+@dataclass
+class Address:
+    city: str
+    postal_code: Optional[str] = None
+
+
+@dataclass
+class User:
+    name: str
+    first_name: str
+    home: Address
+    tags: list[str] = field(default_factory=list)
+    past: list[Address] = field(default_factory=list)
+    zip_code: Optional[str] = field(default=None, metadata={"json": "zip-code"})
+
+
+class EchoEngine:
+    """Stand-in engine whose rehydration returns the data unchanged."""
+
+    def rehydrate(self, data, codec, schema):
+        return RehydrateResult(api_version="1.0", data=data)
+
+
+def test_to_model_builds_nested_dataclasses():
+    user = to_model(
+        {
+            "name": "Ada",
+            "firstName": "Augusta",
+            "home": {"city": "London", "postalCode": "W1"},
+            "past": [{"city": "Paris"}],
+            "zip-code": "12345",
+            "extra": True,
+        },
+        User,
+    )
+    assert user == User(
+        name="Ada",
+        first_name="Augusta",
+        home=Address(city="London", postal_code="W1"),
+        past=[Address(city="Paris")],
+        zip_code="12345",
+    )
+
+
+def test_rehydrate_to_dataclass_uses_engine():
+    addr = rehydrate_to_dataclass({"city": "Oslo"}, {}, {}, Address, engine=EchoEngine())
+    assert addr == Address(city="Oslo")
+
+
+def test_rehydrate_many_with_and_without_class():
+    items = [{"city": "A"}, {"city": "B"}]
+    assert rehydrate_many(items, {}, {}, engine=EchoEngine()) == items
+    assert rehydrate_many(items, {}, {}, Address, engine=EchoEngine()) == [
+        Address(city="A"),
+        Address(city="B"),
+    ]
+
+
+def test_to_model_uses_pydantic_validation():
+    class Model:
+        @classmethod
+        def model_validate(cls, value):
+            return ("validated", value)
+
+    assert to_model({"a": 1}, Model) == ("validated", {"a": 1})
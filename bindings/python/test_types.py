@@ -4,6 +4,7 @@ import pytest
 
 from json_schema_llm_wasi.types import (
     ComponentError,
+    ComponentInfo,
     ConvertAllComponentsResult,
     ConvertOptions,
     ConvertResult,
@@ -11,6 +12,7 @@ from json_schema_llm_wasi.types import (
     ExtractComponentResult,
     ListComponentsResult,
     ProviderCompatError,
+    RehydrateOptions,
     RehydrateResult,
     RehydrateWarning,
 )
@@ -74,10 +76,29 @@ class TestRehydrateResult:
         raw = {
             "apiVersion": "1",
             "data": {},
-            "warnings": [{"msg": "coerced"}],
+            "warnings": [
+                {
+                    "dataPath": "/tags/0",
+                    "schemaPath": "#/properties/tags/items",
+                    "kind": {"type": "constraint_violation", "constraint": "maxLength"},
+                    "message": "too long",
+                    "ruleId": "JSL-RH-001",
+                    "count": 3,
+                    "samplePaths": ["/tags/0", "/tags/1"],
+                }
+            ],
         }
         result = RehydrateResult.from_dict(raw)
-        assert result.warnings == [RehydrateWarning(msg="coerced")]
+        assert result.warnings == [
+            RehydrateWarning(
+                data_path="/tags/0",
+                schema_path="#/properties/tags/items",
+                kind={"type": "constraint_violation", "constraint": "maxLength"},
+                message="too long",
+                count=3,
+                sample_paths=["/tags/0", "/tags/1"],
+            )
+        ]
 
     def test_from_dict_missing_data(self):
         with pytest.raises(KeyError):
@@ -94,13 +115,27 @@ class TestListComponentsResult:
         raw = {
             "apiVersion": "1",
             "components": [
-                "#/$defs/Pet",
+                {
+                    "pointer": "#/$defs/Pet",
+                    "title": "Pet",
+                    "propertyCount": 2,
+                    "depth": 1,
+                    "recursive": False,
+                    "refsOut": ["#/$defs/Tag"],
+                    "refsIn": [],
+                },
             ],
         }
         result = ListComponentsResult.from_dict(raw)
         assert result.api_version == "1"
         assert len(result.components) == 1
-        assert result.components[0] == "#/$defs/Pet"
+        pet = result.components[0]
+        assert isinstance(pet, ComponentInfo)
+        assert pet.pointer == "#/$defs/Pet"
+        assert pet.title == "Pet"
+        assert pet.description is None
+        assert pet.property_count == 2
+        assert pet.refs_out == ["#/$defs/Tag"]
 
 
 # ---------------------------------------------------------------------------
@@ -195,7 +230,25 @@ class TestConvertOptions:
         assert "mode" not in d
         assert "max-depth" not in d
 
+    def test_to_dict_preset(self):
+        opts = ConvertOptions(preset="claude-tool-use", recursion_limit=2)
+        assert opts.to_dict() == {"preset": "claude-tool-use", "recursion-limit": 2}
+
     def test_frozen(self):
         opts = ConvertOptions(target="x")
         with pytest.raises(AttributeError):
             opts.target = "y"  # type: ignore[misc]
+
+
+# ---------------------------------------------------------------------------
+# RehydrateOptions
+# ---------------------------------------------------------------------------
+
+
+class TestRehydrateOptions:
+    def test_to_dict_kebab_case(self):
+        opts = RehydrateOptions(aggregate_warnings=3, max_warnings=50)
+        assert opts.to_dict() == {"aggregate-warnings": 3, "max-warnings": 50}
+
+    def test_to_dict_omits_none(self):
+        assert RehydrateOptions(max_warnings=1).to_dict() == {"max-warnings": 1}
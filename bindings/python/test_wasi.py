@@ -8,6 +8,7 @@ from json_schema_llm_wasi import (
     JslError,
     ConvertResult,
     ConvertOptions,
+    RehydrateOptions,
     RehydrateResult,
 )
 
@@ -73,6 +74,27 @@ def test_roundtrip(engine):
     assert rehydrate_result.data["name"] == "Ada"
 
 
+def test_rehydrate_options_bound_warnings(engine):
+    """max_warnings truncates the warning list."""
+    schema = {
+        "type": "object",
+        "properties": {
+            "tags": {"type": "array", "items": {"type": "string", "maxLength": 2}},
+        },
+    }
+    convert_result = engine.convert(schema)
+    data = {"tags": ["long", "longer", "longest"]}
+
+    result = engine.rehydrate(
+        data, convert_result.codec, schema, RehydrateOptions(max_warnings=1)
+    )
+
+    assert [w.kind["type"] for w in result.warnings] == [
+        "constraint_violation",
+        "truncated",
+    ]
+
+
 def test_rehydrate_error(engine):
     """Rehydrate with invalid codec returns error."""
     schema = {"type": "object"}
@@ -90,6 +112,13 @@ def test_multiple_calls(engine):
         assert result.schema is not None
 
 
+def test_cancel_between_calls_is_harmless(engine):
+    """cancel() with nothing running does not poison later calls."""
+    engine.cancel()
+    result = engine.convert({"type": "object", "properties": {"x": {"type": "string"}}})
+    assert isinstance(result, ConvertResult)
+
+
 def test_real_world_schema(engine):
     """Complex nested schema converts and roundtrips with typed results."""
     schema = {
@@ -47,18 +47,36 @@ module JsonSchemaLlm
       call_jsl("jsl_convert", schema_json, opts_json)
     end
 
-    def rehydrate(data, codec, schema)
+    # options: aggregate_warnings (merge same-kind warnings at one schema
+    # path, keeping that many "samplePaths") and max_warnings (keep that
+    # many, then one "truncated" entry).
+    def rehydrate(data, codec, schema, options = nil)
       data_json = JSON.generate(data)
       codec_json = JSON.generate(codec)
       schema_json = JSON.generate(schema)
-      call_jsl("jsl_rehydrate", data_json, codec_json, schema_json)
+      return call_jsl("jsl_rehydrate", data_json, codec_json, schema_json) if options.nil?
+
+      normalized = options.transform_keys { |k| k.to_s.tr("_", "-") }
+      call_jsl("jsl_rehydrate_with_options", data_json, codec_json, schema_json,
+               JSON.generate(normalized))
     end
 
+    # {"apiVersion" => "2.0", "components" => [...]}, one hash per component
+    # with "pointer", optional "title"/"description", "propertyCount",
+    # "depth", "recursive", "refsOut" and "refsIn".
     def list_components(schema)
       schema_json = JSON.generate(schema)
       call_jsl("jsl_list_components", schema_json)
     end
 
+    # "sha256:<hex>" fingerprint of the schema as converted with options,
+    # stable across key ordering.
+    def schema_fingerprint(schema, options = {})
+      schema_json = JSON.generate(schema)
+      normalized = options.transform_keys { |k| k.to_s.tr("_", "-") }
+      call_jsl("jsl_schema_fingerprint", schema_json, JSON.generate(normalized))["fingerprint"]
+    end
+
     def extract_component(schema, pointer, options = {})
       schema_json = JSON.generate(schema)
       opts_json = JSON.generate(options)
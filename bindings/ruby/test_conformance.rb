@@ -156,3 +156,35 @@ class ConformanceRehydrateErrorTest < Minitest::Test
     end
   end
 end
+
+class ConformanceListComponentsTest < Minitest::Test
+  def setup
+    @engine = JsonSchemaLlm::Engine.new
+  end
+
+  FIXTURES["suites"]["list_components"]["fixtures"].each do |fx|
+    define_method("test_#{fx['id']}") do
+      input = fx["input"]
+      expected = fx["expected"]
+
+      if input.key?("schema_raw")
+        assert expected["is_error"], "schema_raw fixture must have is_error=true"
+        err = assert_raises(JsonSchemaLlm::JslError) do
+          @engine.send(:call_jsl, "jsl_list_components", input["schema_raw"])
+        end
+        refute_nil err.code, "error missing 'code'"
+        return
+      end
+
+      result = @engine.list_components(input["schema"])
+
+      if expected.key?("apiVersion")
+        assert_equal expected["apiVersion"], result["apiVersion"]
+      end
+
+      if expected.key?("components")
+        assert_equal expected["components"], result["components"]
+      end
+    end
+  end
+end
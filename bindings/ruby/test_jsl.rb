@@ -47,6 +47,22 @@ class JsonSchemaLlmTest < Minitest::Test
     assert_equal "Ada", rehydrate_result["data"]["name"]
   end
 
+  def test_rehydrate_options_bound_warnings
+    schema = {
+      "type" => "object",
+      "properties" => {
+        "tags" => { "type" => "array", "items" => { "type" => "string", "maxLength" => 2 } }
+      }
+    }
+    convert_result = @engine.convert(schema)
+    data = { "tags" => %w[long longer longest] }
+
+    result = @engine.rehydrate(data, convert_result["codec"], schema, max_warnings: 1)
+
+    kinds = result["warnings"].map { |w| w["kind"]["type"] }
+    assert_equal %w[constraint_violation truncated], kinds
+  end
+
   def test_rehydrate_error
     assert_raises(JsonSchemaLlm::JslError) do
       @engine.send(:call_jsl, "jsl_rehydrate",
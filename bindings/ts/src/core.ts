@@ -29,6 +29,8 @@ const DEFAULT_WASM_PATH = join(
 );
 
 export interface ConvertOptions {
+  /** Named option bundle, e.g. "openai-tool-calling"; other fields override it. */
+  preset?: string;
   target?: string;
   polymorphism?: string;
   max_depth?: number;
@@ -46,6 +48,16 @@ export interface Warning {
   schemaPath: string;
   kind: { type: string; constraint?: string };
   message: string;
+  /** Occurrences merged into this entry; absent means 1. */
+  count?: number;
+  samplePaths?: string[];
+}
+
+export interface RehydrateOptions {
+  /** Merge same-kind warnings at one schema path, keeping this many `samplePaths`. */
+  aggregate_warnings?: number;
+  /** Keep this many warnings, then one "truncated" entry counting the rest. */
+  max_warnings?: number;
 }
 
 export interface RehydrateResult {
@@ -66,9 +78,20 @@ export interface ExtractResult {
   missingRefs: string[];
 }
 
+export interface ComponentInfo {
+  pointer: string;
+  title?: string;
+  description?: string;
+  propertyCount: number;
+  depth: number;
+  recursive: boolean;
+  refsOut: string[];
+  refsIn: string[];
+}
+
 export interface ListComponentsResult {
   apiVersion: string;
-  components: string[];
+  components: ComponentInfo[];
 }
 
 export interface ConvertAllResult {
@@ -89,6 +112,19 @@ export class JslError extends Error {
   }
 }
 
+/** Options with snake_case keys turned kebab-case for the WASI binary. */
+function toWasiOptions(options?: object): Record<string, unknown> {
+  const wasiOpts: Record<string, unknown> = {};
+  if (options) {
+    for (const [key, value] of Object.entries(options)) {
+      if (value !== undefined) {
+        wasiOpts[key.replace(/_/g, "-")] = value;
+      }
+    }
+  }
+  return wasiOpts;
+}
+
 export class Engine {
   private wasmBytes: Uint8Array;
   private compiledModule: WebAssembly.Module | null = null;
@@ -118,20 +154,29 @@ export class Engine {
     options?: ConvertOptions
   ): Promise<ConvertResult> {
     const schemaJson = JSON.stringify(schema);
-    // Transform snake_case keys to kebab-case for WASI binary compatibility
-    const wasiOpts: Record<string, unknown> = {};
-    if (options) {
-      for (const [key, value] of Object.entries(options)) {
-        if (value !== undefined) {
-          wasiOpts[key.replace(/_/g, "-")] = value;
-        }
-      }
-    }
-    const optsJson = JSON.stringify(wasiOpts);
+    const optsJson = JSON.stringify(toWasiOptions(options));
     const payload = await this.callJsl("jsl_convert", schemaJson, optsJson);
     return payload as ConvertResult;
   }
 
+  /**
+   * Fingerprint a schema as converted with `options`: `"sha256:<hex>"`,
+   * stable across key ordering.
+   */
+  async schemaFingerprint(
+    schema: unknown,
+    options?: ConvertOptions
+  ): Promise<string> {
+    const schemaJson = JSON.stringify(schema);
+    const optsJson = JSON.stringify(toWasiOptions(options));
+    const payload = await this.callJsl(
+      "jsl_schema_fingerprint",
+      schemaJson,
+      optsJson
+    );
+    return (payload as { fingerprint: string }).fingerprint;
+  }
+
   /**
    * Apply RFC 6902 JSON Patch operations to a JSON Schema via the WASM core.
    *
@@ -162,17 +207,21 @@ export class Engine {
   async rehydrate(
     data: unknown,
     codec: unknown,
-    schema: unknown
+    schema: unknown,
+    options?: RehydrateOptions
   ): Promise<RehydrateResult> {
     const dataJson = JSON.stringify(data);
     const codecJson = JSON.stringify(codec);
     const schemaJson = JSON.stringify(schema);
-    const payload = await this.callJsl(
-      "jsl_rehydrate",
-      dataJson,
-      codecJson,
-      schemaJson
-    );
+    const payload = options
+      ? await this.callJsl(
+          "jsl_rehydrate_with_options",
+          dataJson,
+          codecJson,
+          schemaJson,
+          JSON.stringify(toWasiOptions(options))
+        )
+      : await this.callJsl("jsl_rehydrate", dataJson, codecJson, schemaJson);
     return payload as RehydrateResult;
   }
 
@@ -10,10 +10,12 @@ export type {
   ConvertOptions,
   ConvertResult,
   Warning,
+  RehydrateOptions,
   RehydrateResult,
   ExtractOptions,
   ExtractResult,
   ListComponentsResult,
+  ComponentInfo,
   ConvertAllResult,
 } from "./core.js";
 
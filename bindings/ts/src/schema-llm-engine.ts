@@ -23,6 +23,7 @@ import { Engine } from "./core.js";
 import type {
   ConvertOptions,
   ConvertResult,
+  RehydrateOptions,
   RehydrateResult,
   ExtractOptions,
   ExtractResult,
@@ -124,6 +125,11 @@ export class SchemaLlmEngine {
     return this.engine.convert(schema, options);
   }
 
+  /** Fingerprint a schema as converted with `options` (`"sha256:<hex>"`). */
+  async schemaFingerprint(schema: unknown, options?: ConvertOptions): Promise<string> {
+    return this.engine.schemaFingerprint(schema, options);
+  }
+
   /**
    * Apply RFC 6902 JSON Patch operations to a JSON Schema via the WASM core.
    *
@@ -142,9 +148,10 @@ export class SchemaLlmEngine {
   async rehydrate(
     data: unknown,
     codec: unknown,
-    schema: unknown
+    schema: unknown,
+    options?: RehydrateOptions
   ): Promise<RehydrateResult> {
-    return this.engine.rehydrate(data, codec, schema);
+    return this.engine.rehydrate(data, codec, schema, options);
   }
 
   /** List extractable components from a schema. */
@@ -56,6 +56,26 @@ describe("JsonSchemaLlm WASI", () => {
     expect((rehydrated.data as any).name).toBe("Ada");
   });
 
+  it("bounds rehydrate warnings with options", async () => {
+    const schema = {
+      type: "object",
+      properties: {
+        tags: { type: "array", items: { type: "string", maxLength: 2 } },
+      },
+    };
+
+    const convertResult = await engine.convert(schema);
+    const data = { tags: ["long", "longer", "longest"] };
+    const rehydrated = await engine.rehydrate(data, convertResult.codec, schema, {
+      max_warnings: 1,
+    });
+
+    expect(rehydrated.warnings?.map((w) => w.kind.type)).toEqual([
+      "constraint_violation",
+      "truncated",
+    ]);
+  });
+
   it("returns error for invalid codec", async () => {
     await expect(
       (engine as any).callJsl(
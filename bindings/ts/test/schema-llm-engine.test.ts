@@ -105,6 +105,9 @@ describe("SchemaLlmEngine", () => {
       const result = await engine.listComponents(schema);
       expect(result.apiVersion).toBeTruthy();
       expect(Array.isArray(result.components)).toBe(true);
+      expect(result.components[0].pointer).toBe("#/$defs/Address");
+      expect(result.components[0].propertyCount).toBe(1);
+      expect(result.components[0].refsIn).toEqual([]);
     });
   });
 
@@ -0,0 +1,250 @@
+//! `conformance` subcommand: a live round trip against a real provider.
+//!
+//! Converts a schema, sends it with a canned prompt, and checks that the
+//! provider accepts the schema and that its answer extracts and rehydrates.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use json_schema_llm_core::providers::{build_request, PromptParams};
+use json_schema_llm_core::{
+    convert_str, parse_value, rehydrate_response, ConvertOptions, Mode, Target,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::output::Output;
+
+const SYSTEM_PROMPT: &str =
+    "You are a schema conformance check. Respond only with data matching the provided schema.";
+const USER_PROMPT: &str =
+    "Generate one realistic example. Fill every field with a plausible value.";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Inputs for [`run`], mirroring the CLI flags.
+pub struct ConformanceArgs<'a> {
+    pub schema: &'a Path,
+    pub target: Target,
+    pub mode: Mode,
+    pub api_key: Option<String>,
+    pub model: Option<String>,
+    pub base_url: Option<String>,
+}
+
+/// Outcome of one step of the check.
+#[derive(Serialize)]
+struct Step {
+    step: &'static str,
+    passed: bool,
+    detail: String,
+}
+
+/// The check's report: one line per step on stdout, or (with `--json`) the
+/// envelope's result.
+#[derive(Serialize)]
+struct Report<'o> {
+    target: String,
+    model: String,
+    passed: bool,
+    steps: Vec<Step>,
+    #[serde(skip)]
+    out: &'o mut Output,
+}
+
+/// Run the check, reporting each step. Fails if any step fails.
+pub fn run(args: ConformanceArgs<'_>, out: &mut Output) -> Result<()> {
+    let api_key = match args.api_key {
+        Some(key) => key,
+        None => std::env::var(api_key_env(args.target)).with_context(|| {
+            format!(
+                "No API key: pass --api-key or set {}",
+                api_key_env(args.target)
+            )
+        })?,
+    };
+    let model = args
+        .model
+        .unwrap_or_else(|| default_model(args.target).to_string());
+    let base_url = args
+        .base_url
+        .unwrap_or_else(|| default_base_url(args.target).to_string());
+
+    let mut report = Report {
+        target: target_name(args.target),
+        model: model.clone(),
+        passed: false,
+        steps: Vec::new(),
+        out,
+    };
+    if !report.out.is_json() {
+        println!("conformance: {} ({})", report.target, report.model);
+    }
+
+    let schema_text = std::fs::read_to_string(args.schema)
+        .with_context(|| format!("Failed to open schema file: {}", args.schema.display()))?;
+    let original: Value = parse_value(schema_text.as_bytes(), false)
+        .with_context(|| format!("Failed to parse schema from: {}", args.schema.display()))?;
+    let mut options = ConvertOptions::default();
+    options.target = args.target;
+    options.mode = args.mode;
+    let converted = match convert_str(&schema_text, &options) {
+        Ok(converted) => converted,
+        Err(e) => return report.fail("convert", e.to_string()),
+    };
+    report.pass(
+        "convert",
+        format!("{} diagnostic(s)", converted.provider_compat_errors.len()),
+    );
+
+    let params = PromptParams {
+        model: Some(model.clone()),
+        system: Some(SYSTEM_PROMPT.to_string()),
+        user: Some(USER_PROMPT.to_string()),
+        strict: Some(args.mode == Mode::Strict),
+        ..PromptParams::default()
+    };
+    let body = build_request(args.target, &converted.schema, &params);
+    let response = match send(args.target, &base_url, &model, &api_key, &body) {
+        Ok(response) => response,
+        Err(e) => return report.fail("provider accepted schema", e),
+    };
+    report.pass("provider accepted schema", String::new());
+
+    match rehydrate_response(args.target, &response, &converted.codec, &original) {
+        Ok(result) => {
+            report.pass(
+                "extract and rehydrate",
+                format!("{} warning(s)", result.warnings.len()),
+            );
+            for warning in &result.warnings {
+                if report.out.is_json() {
+                    report.out.warn(Some(warning.rule_id()), &warning.message);
+                } else {
+                    println!("    - [{}] {}", warning.rule_id(), warning.message);
+                }
+            }
+        }
+        Err(e) => return report.fail("extract and rehydrate", e.to_string()),
+    }
+
+    report.passed = true;
+    report.finish("PASS")?;
+    Ok(())
+}
+
+impl Report<'_> {
+    fn pass(&mut self, step: &'static str, detail: String) {
+        if !self.out.is_json() {
+            if detail.is_empty() {
+                println!("  [pass] {}", step);
+            } else {
+                println!("  [pass] {} ({})", step, detail);
+            }
+        }
+        self.steps.push(Step {
+            step,
+            passed: true,
+            detail,
+        });
+    }
+
+    fn fail(mut self, step: &'static str, detail: String) -> Result<()> {
+        if !self.out.is_json() {
+            println!("  [fail] {}: {}", step, detail);
+        }
+        self.steps.push(Step {
+            step,
+            passed: false,
+            detail,
+        });
+        self.finish("FAIL")?;
+        anyhow::bail!("Conformance check failed at: {}", step)
+    }
+
+    fn finish(self, verdict: &str) -> Result<()> {
+        if !self.out.is_json() {
+            println!("{}", verdict);
+        }
+        let result = serde_json::to_value(&self)?;
+        self.out.set_result(&result)
+    }
+}
+
+/// POST `body` to the provider, returning the parsed response or a message
+/// describing the rejection.
+fn send(
+    target: Target,
+    base_url: &str,
+    model: &str,
+    api_key: &str,
+    body: &Value,
+) -> std::result::Result<Value, String> {
+    let base_url = base_url.trim_end_matches('/');
+    let request = match target {
+        Target::OpenaiStrict => ureq::post(&format!("{}/v1/chat/completions", base_url))
+            .set("Authorization", &format!("Bearer {}", api_key)),
+        Target::Gemini => ureq::post(&format!(
+            "{}/v1beta/models/{}:generateContent",
+            base_url, model
+        ))
+        .set("x-goog-api-key", api_key),
+        Target::Claude => ureq::post(&format!("{}/v1/messages", base_url))
+            .set("x-api-key", api_key)
+            .set("anthropic-version", "2023-06-01")
+            .set("anthropic-beta", "structured-outputs-2025-11-13"),
+    };
+    let result = request
+        .timeout(REQUEST_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .send_string(&body.to_string());
+    let text = match result {
+        Ok(response) => response.into_string().map_err(|e| e.to_string())?,
+        Err(ureq::Error::Status(code, response)) => {
+            let text = response.into_string().unwrap_or_default();
+            return Err(format!("HTTP {}: {}", code, error_message(&text)));
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&text).map_err(|e| format!("Unparseable response body: {}", e))
+}
+
+/// The provider's error message, if the body has the usual `error.message`.
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v.pointer("/error/message")?.as_str().map(String::from))
+        .unwrap_or_else(|| body.to_string())
+}
+
+fn api_key_env(target: Target) -> &'static str {
+    match target {
+        Target::OpenaiStrict => "OPENAI_API_KEY",
+        Target::Gemini => "GEMINI_API_KEY",
+        Target::Claude => "ANTHROPIC_API_KEY",
+    }
+}
+
+fn default_model(target: Target) -> &'static str {
+    match target {
+        Target::OpenaiStrict => "gpt-4o-mini",
+        Target::Gemini => "gemini-2.0-flash",
+        Target::Claude => "claude-sonnet-4-5",
+    }
+}
+
+fn default_base_url(target: Target) -> &'static str {
+    match target {
+        Target::OpenaiStrict => "https://api.openai.com",
+        Target::Gemini => "https://generativelanguage.googleapis.com",
+        Target::Claude => "https://api.anthropic.com",
+    }
+}
+
+fn target_name(target: Target) -> String {
+    serde_json::to_value(target)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_else(|| "unknown".to_string())
+}
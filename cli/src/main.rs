@@ -1,18 +1,38 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use json_schema_llm_codegen::{ModuleLayout, ProjectMetadata, SdkConfig};
 use json_schema_llm_core::config::PolymorphismStrategy;
+use json_schema_llm_core::corpus::{self, CaseStatus};
+use json_schema_llm_core::diagnostics::Severity;
+use json_schema_llm_core::messages::{Locale, MessageCatalog};
 use json_schema_llm_core::{
-    convert, convert_all_components, extract_component, list_components, rehydrate, Codec,
-    ConvertOptions, ExtractOptions, Mode, Target,
+    check_duplicate_keys, codec_matches, convert, convert_all_components_streaming, convert_str,
+    describe_components, diagnose_failure, emit_types, extract_component,
+    find_components_referencing, generate_prompt_scaffold, lint_str, make_fewshot, parse_value,
+    project_schema, rehydrate, rehydrate_compiled, round_numbers, schema_fingerprint,
+    seed_property_ordering, split_schema, AdditionalPropertiesPolicy, Codec, CompiledCodec,
+    ComponentInfo, ConstraintHintPolicy, ContentSchemaPolicy, ConvertError, ConvertOptions,
+    ConvertResult, CycleInfo, DeprecatedPolicy, DuplicateKeyCheck, EnumDescriptionStyle, Envelope,
+    ErrorPolicy, ExtractOptions, MergeConflictPolicy, Mode, NullableStyle, Preset, Progress,
+    ProgressSink, PromptOptions, PropertyOrdering, RecursionLeaf, RehydrateOptions, RuleSelector,
+    Target, ToolRegistry, TypeLanguage, TypegenOptions,
 };
+use json_schema_llm_core::{graphql, proto, providers, sample};
 use serde::Deserialize;
 use serde_json::Value;
 use std::fs::{self, File};
-use std::io::{self, BufReader, BufWriter, Write};
+use std::io::{self, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tracing::level_filters::LevelFilter;
 
+mod conformance;
+mod output;
+
+use output::Output;
+
 #[derive(Parser)]
 #[command(name = "json-schema-llm")]
 #[command(about = "Convert any JSON Schema into an LLM-compatible structured output schema")]
@@ -24,8 +44,15 @@ struct Cli {
     /// Enable verbose logging (sets log level to debug)
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Print one JSON result envelope on stdout (result, files written,
+    /// diagnostics, error) instead of human-readable text
+    #[arg(long, global = true)]
+    json: bool,
 }
 
+// Parsed once at startup, so the size of `Convert` costs nothing.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Convert a JSON Schema to an LLM-compatible schema
@@ -33,6 +60,24 @@ enum Commands {
         /// Input JSON Schema file
         input: PathBuf,
 
+        /// Format of the input file
+        #[arg(long, value_enum, default_value_t = InputFormatArg::Json)]
+        input_format: InputFormatArg,
+
+        /// Fully qualified message type to convert, e.g. my.pkg.Order
+        /// (protobuf input)
+        #[arg(long, required_if_eq("input_format", "protobuf"))]
+        message: Option<String>,
+
+        /// GraphQL type to convert, e.g. Order (graphql input)
+        #[arg(long = "type", required_if_eq("input_format", "graphql"))]
+        type_name: Option<String>,
+
+        /// What to do when an object in the input repeats a key (JSON input;
+        /// parsing otherwise keeps the last occurrence silently)
+        #[arg(long, value_enum, default_value_t = DuplicateKeysArg::Ignore)]
+        duplicate_keys: DuplicateKeysArg,
+
         /// Output converted schema file (defaults to stdout if not specified)
         #[arg(short, long, conflicts_with = "output_dir")]
         output: Option<PathBuf>,
@@ -45,17 +90,30 @@ enum Commands {
         #[arg(long)]
         codec: Option<PathBuf>,
 
-        /// Target LLM provider
-        #[arg(short, long, value_enum, default_value_t = TargetArg::OpenaiStrict)]
-        target: TargetArg,
+        /// Curated option bundle to start from; other flags override it
+        #[arg(long, value_enum)]
+        preset: Option<PresetArg>,
 
-        /// Conversion mode (strict vs permissive)
-        #[arg(long, value_enum, default_value_t = ModeArg::Strict)]
-        mode: ModeArg,
+        /// Target LLM provider [default: openai-strict, or the preset's]
+        #[arg(short, long, value_enum)]
+        target: Option<TargetArg>,
+
+        /// Conversion mode (strict vs permissive) [default: strict, or the preset's]
+        #[arg(long, value_enum)]
+        mode: Option<ModeArg>,
+
+        /// Polymorphism strategy [default: anyof, or the preset's]
+        #[arg(long, value_enum)]
+        polymorphism: Option<PolymorphismArg>,
+
+        /// How allOf members that disagree on a keyword are merged
+        #[arg(long, value_enum, default_value_t = MergeConflictsArg::Intersect)]
+        merge_conflicts: MergeConflictsArg,
 
-        /// Polymorphism strategy
-        #[arg(long, value_enum, default_value_t = PolymorphismArg::AnyOf)]
-        polymorphism: PolymorphismArg,
+        /// What happens to a $ref that resolves to nothing: fail, drop the
+        /// property holding it, or replace it with an opaque JSON string
+        #[arg(long, value_enum, default_value_t = OnErrorArg::Fail)]
+        on_error: OnErrorArg,
 
         /// Max traversal depth for ref resolution
         #[arg(long, default_value_t = 50)]
@@ -65,15 +123,232 @@ enum Commands {
         #[arg(long, default_value_t = 3)]
         recursion_limit: usize,
 
+        /// What replaces a recursive reference past the recursion limit
+        #[arg(long, value_enum, default_value_t = RecursionLeafArg::OpaqueString)]
+        recursion_leaf: RecursionLeafArg,
+
         /// Skip processing $defs/components entirely
         #[arg(long, default_value_t = false)]
         skip_components: bool,
 
+        /// Provider nesting budget; deeper sub-schemas become opaque strings
+        /// (defaults to the target provider's limit)
+        #[arg(long)]
+        provider_max_depth: Option<usize>,
+
+        /// Cap on union variants; the rest collapse into one JSON-string branch
+        #[arg(long)]
+        max_anyof_variants: Option<usize>,
+
+        /// Cap on enum size; larger enums become plain strings/numbers and
+        /// are checked against the full list on rehydrate
+        #[arg(long)]
+        max_enum_values: Option<usize>,
+
+        /// How per-value enum descriptions (x-enum-descriptions, or a oneOf
+        /// of described consts) reach the model
+        #[arg(long, value_enum, default_value_t = EnumDescriptionsArg::Auto)]
+        enum_descriptions: EnumDescriptionsArg,
+
+        /// How constraints dropped for the target are restated in descriptions
+        /// [default: labeled, or the preset's]
+        #[arg(long, value_enum)]
+        constraint_hints: Option<ConstraintHintsArg>,
+
+        /// Leave dropped numeric bounds (minimum, maximum, multipleOf, ...)
+        /// out of constraint hints
+        #[arg(long, default_value_t = false)]
+        no_numeric_hints: bool,
+
+        /// Maximum characters of constraint hint appended to one description
+        #[arg(long)]
+        constraint_hint_budget: Option<usize>,
+
+        /// How optional properties are made nullable; ignored for
+        /// openai-strict, which always uses anyOf
+        #[arg(long, value_enum)]
+        nullable_style: Option<NullableStyleArg>,
+
+        /// Keep declared additionalProperties (open objects, typed maps)
+        /// instead of sealing objects; gemini and claude only
+        #[arg(long, value_enum, default_value_t = AdditionalPropertiesArg::Seal)]
+        additional_properties: AdditionalPropertiesArg,
+
+        /// Property that carries extra entries of objects mixing
+        /// properties and additionalProperties [default: _additional]
+        #[arg(long)]
+        additional_properties_name: Option<String>,
+
+        /// Remove readOnly properties (server-assigned IDs, timestamps)
+        #[arg(long, default_value_t = false)]
+        filter_read_only: bool,
+
+        /// Remove writeOnly properties (e.g. passwords)
+        #[arg(long, default_value_t = false)]
+        filter_write_only: bool,
+
+        /// What to do with deprecated properties [default: keep, or the preset's]
+        #[arg(long, value_enum)]
+        deprecated: Option<DeprecatedArg>,
+
+        /// How to treat strings embedding JSON (contentMediaType + contentSchema)
+        #[arg(long, value_enum, default_value_t = ContentSchemaArg::Opaque)]
+        content_schema: ContentSchemaArg,
+
+        /// Language of descriptions injected into the schema
+        #[arg(long, value_enum, default_value_t = LocaleArg::En)]
+        locale: LocaleArg,
+
+        /// JSON message catalog (kebab-case keys) overriding --locale;
+        /// missing entries fall back to English
+        #[arg(long, value_name = "FILE")]
+        messages: Option<PathBuf>,
+
+        /// Acknowledge a diagnostic rule, optionally under a JSON Pointer glob
+        /// (e.g. JSL-P9-001:#/properties/legacy/**). Repeatable.
+        #[arg(long, value_name = "RULE[:PATH]")]
+        suppress: Vec<RuleSelector>,
+
+        /// Emit Gemini propertyOrdering (original = declaration order in the input file)
+        /// [default: off, or the preset's]
+        #[arg(long, value_enum)]
+        property_ordering: Option<PropertyOrderingArg>,
+
+        /// Write the schema wrapped in the provider's request payload instead
+        /// of the bare schema
+        #[arg(long, value_enum)]
+        envelope: Option<EnvelopeArg>,
+
+        /// Name for the envelope (defaults to the schema title, else "response")
+        #[arg(long, requires = "envelope")]
+        envelope_name: Option<String>,
+
+        /// Don't show conversion progress (shown only when stderr is a terminal)
+        #[arg(long, default_value_t = false)]
+        no_progress: bool,
+
+        /// Before writing output, rehydrate a synthetic answer to the converted
+        /// schema and validate it against the original; fail if the round trip
+        /// is broken
+        #[arg(long, default_value_t = false)]
+        self_check: bool,
+
+        /// Fail if the converted schema breaks a requirement the target
+        /// provider publishes (e.g. OpenAI strict mode's complete `required`)
+        #[arg(long, default_value_t = false)]
+        verify_output: bool,
+
+        /// Check the input against the JSON Schema 2020-12 meta-schema first
+        /// and report every malformed keyword at once
+        #[arg(long, default_value_t = false)]
+        validate_input: bool,
+
+        /// Keep numbers in the schema exact (e.g. a 25-digit const) instead
+        /// of rounding those beyond 64-bit integer or double precision
+        #[arg(long, default_value_t = false)]
+        preserve_precision: bool,
+
+        /// Mark transformed nodes with an x-jsl-transform array naming the
+        /// transforms applied (for debugging; not meant for the provider)
+        #[arg(long, default_value_t = false)]
+        annotate: bool,
+
+        /// Drop descriptions and titles for a minimal-token schema; JSON-string
+        /// instructions stay. The codec is the same as without the flag.
+        #[arg(long, default_value_t = false)]
+        strip_descriptions: bool,
+
+        /// Keep descriptions under this JSON Pointer glob of the converted
+        /// schema when stripping (e.g. '#/properties/status'). Repeatable.
+        #[arg(long, value_name = "GLOB", requires = "strip_descriptions")]
+        keep_description: Vec<String>,
+
+        /// Write diff-stable output: sorted keys, sorted required/type arrays
+        /// and integral numbers, so checked-in schemas diff cleanly
+        #[arg(long, default_value_t = false)]
+        canonical: bool,
+
+        /// Convert only the fields at this data path (e.g. /user/name or
+        /// /items/*/price); rehydration fills the rest with null. Repeatable.
+        #[arg(long, value_name = "DATA_PATH", conflicts_with_all = ["output_dir", "self_check"])]
+        project: Vec<String>,
+
+        /// Keep every converted schema within this many bytes by splitting
+        /// root properties into follow-up parts; writes the plan (primary
+        /// schema plus parts) and a combined codec
+        #[arg(long, value_name = "BYTES", conflicts_with_all = ["output_dir", "self_check", "project"])]
+        split_max_bytes: Option<usize>,
+
+        /// Byte-identical --output-dir output for identical input: the
+        /// manifest's generatedAt comes from SOURCE_DATE_EPOCH or is left out
+        #[arg(long, default_value_t = false, requires = "output_dir")]
+        reproducible: bool,
+
+        /// Reuse an earlier --output-dir run: components whose source and
+        /// options fingerprint as recorded in its manifest are kept, and
+        /// only changed or failed ones are converted again
+        #[arg(long, default_value_t = false, requires = "output_dir")]
+        resume: bool,
+
+        /// Convert up to N --output-dir components in parallel (0: one per
+        /// CPU). Errors are still reported in manifest order.
+        #[arg(
+            short,
+            long,
+            value_name = "N",
+            default_value_t = 1,
+            requires = "output_dir"
+        )]
+        jobs: usize,
+
         /// Output format
         #[arg(long, value_enum, default_value_t = OutputFormat::Pretty)]
         format: OutputFormat,
     },
 
+    /// Report everything a conversion would transform or drop, without
+    /// writing output; fails if any finding is a warning
+    Lint {
+        /// Input JSON Schema file
+        input: PathBuf,
+
+        /// Curated option bundle to start from; other flags override it
+        #[arg(long, value_enum)]
+        preset: Option<PresetArg>,
+
+        /// Target LLM provider [default: openai-strict, or the preset's]
+        #[arg(short, long, value_enum)]
+        target: Option<TargetArg>,
+
+        /// Conversion mode (strict vs permissive) [default: strict, or the preset's]
+        #[arg(long, value_enum)]
+        mode: Option<ModeArg>,
+
+        /// Acknowledge a rule, optionally under a JSON Pointer glob
+        /// (e.g. JSL-CD-001:#/properties/legacy/**). Repeatable.
+        #[arg(long, value_name = "RULE[:PATH]")]
+        suppress: Vec<RuleSelector>,
+    },
+
+    /// Print a content fingerprint of a schema and its conversion options,
+    /// stable across key ordering (for cache keys and codec drift checks)
+    Fingerprint {
+        /// Input JSON Schema file
+        input: PathBuf,
+
+        /// Curated option bundle to start from; other flags override it
+        #[arg(long, value_enum)]
+        preset: Option<PresetArg>,
+
+        /// Target LLM provider [default: openai-strict, or the preset's]
+        #[arg(short, long, value_enum)]
+        target: Option<TargetArg>,
+
+        /// Conversion mode (strict vs permissive) [default: strict, or the preset's]
+        #[arg(long, value_enum)]
+        mode: Option<ModeArg>,
+    },
+
     /// Rehydrate LLM output back to the original schema shape
     Rehydrate {
         /// LLM output JSON file
@@ -91,6 +366,31 @@ enum Commands {
         #[arg(short, long)]
         output: Option<PathBuf>,
 
+        /// Treat the input as a raw API response body from this provider and
+        /// extract the structured output from it first
+        #[arg(long, value_enum, value_name = "TARGET")]
+        provider_response: Option<TargetArg>,
+
+        /// Treat the input as a raw API response body, detecting the provider
+        /// (OpenAI, Gemini or Claude) from its shape
+        #[arg(long, conflicts_with = "provider_response")]
+        from_provider_response: bool,
+
+        /// Keep numbers in the data and schema exact (e.g. a 25-digit ID)
+        /// instead of rounding those beyond 64-bit integer or double precision
+        #[arg(long, default_value_t = false)]
+        preserve_precision: bool,
+
+        /// Merge warnings of the same kind at the same schema path (e.g. one
+        /// per array element) into one entry with a count, keeping up to N
+        /// sample data paths
+        #[arg(long, value_name = "N")]
+        aggregate_warnings: Option<usize>,
+
+        /// Report at most N warnings, then one entry counting the rest
+        #[arg(long, value_name = "N")]
+        max_warnings: Option<usize>,
+
         /// Output format
         #[arg(long, value_enum, default_value_t = OutputFormat::Pretty)]
         format: OutputFormat,
@@ -114,25 +414,56 @@ enum Commands {
         format: OutputFormat,
     },
 
-    /// List all extractable component paths in a schema
+    /// List the extractable components of a schema with their stats
     ListComponents {
         /// Input JSON Schema file
         input: PathBuf,
+
+        /// Only list components that reference this definition, directly or
+        /// through others (e.g. '#/$defs/Money')
+        #[arg(long, value_name = "POINTER")]
+        referencing: Option<String>,
     },
 
     /// Generate a typed SDK project from converted schemas
     GenSdk {
-        /// Target language for the generated SDK
-        #[arg(short, long, value_enum)]
-        language: SdkLanguage,
+        /// Target language(s) for the generated SDK. Several (comma-separated)
+        /// generate sibling projects under --output
+        #[arg(
+            short,
+            long,
+            value_enum,
+            value_delimiter = ',',
+            required_unless_present = "all",
+            conflicts_with = "all"
+        )]
+        language: Vec<SdkLanguage>,
+
+        /// Generate an SDK for every supported language
+        #[arg(long, default_value_t = false)]
+        all: bool,
 
         /// Directory containing manifest.json and component schemas (output of `convert --output-dir`)
-        #[arg(short, long)]
-        schema: PathBuf,
+        #[arg(short, long, required_unless_present = "input")]
+        schema: Option<PathBuf>,
 
-        /// Package name (Java: "com.example.petstore", Python: "my-sdk")
-        #[arg(short, long)]
-        package: String,
+        /// JSON Schema file to convert first, as `convert --output-dir`
+        /// would, instead of reading a --schema directory
+        #[arg(long, conflicts_with = "schema")]
+        input: Option<PathBuf>,
+
+        /// Target LLM provider for converting --input [default: openai-strict]
+        #[arg(short, long, value_enum, conflicts_with = "schema")]
+        target: Option<TargetArg>,
+
+        /// Conversion mode for --input [default: strict]
+        #[arg(long, value_enum, conflicts_with = "schema")]
+        mode: Option<ModeArg>,
+
+        /// Package name (Java: "com.example.petstore", Python: "my-sdk").
+        /// Repeat as `<language>=<name>` to name each SDK separately
+        #[arg(short, long, required = true)]
+        package: Vec<String>,
 
         /// Output directory for the generated SDK project
         #[arg(short, long)]
@@ -145,9 +476,278 @@ enum Commands {
         /// Build tool for the generated project (default: maven for Java, setuptools for Python)
         #[arg(long, value_enum)]
         build_tool: Option<BuildToolArg>,
+
+        /// Directory of Tera templates overriding built-ins of the same name
+        /// (e.g. `pom.xml.tera`, `README.md.tera`)
+        #[arg(long)]
+        template_dir: Option<PathBuf>,
+
+        /// How component modules are grouped: flat, sub-packages by pointer
+        /// prefix, or one per group of $ref-linked components (python and
+        /// typescript only)
+        #[arg(long, value_enum, default_value_t = LayoutArg::Flat)]
+        layout: LayoutArg,
+
+        /// Version of the generated package (default: each ecosystem's
+        /// placeholder, e.g. 1.0.0-SNAPSHOT for Maven)
+        #[arg(long, value_name = "VERSION")]
+        sdk_version: Option<String>,
+
+        /// SPDX license identifier of the generated package, e.g. MIT
+        #[arg(long)]
+        license: Option<String>,
+
+        /// Package author as `Name` or `Name <email>`; repeatable
+        #[arg(long = "author", value_name = "AUTHOR")]
+        authors: Vec<String>,
+
+        /// Source repository URL of the generated package
+        #[arg(long)]
+        repository: Option<String>,
+
+        /// Byte-identical output for identical input: timestamps come from
+        /// SOURCE_DATE_EPOCH or are left out
+        #[arg(long, default_value_t = false)]
+        reproducible: bool,
+    },
+
+    /// Emit an OpenAPI 3.1 `components.schemas` fragment from converted schemas
+    GenOpenapi {
+        /// Directory containing manifest.json and component schemas (output of `convert --output-dir`)
+        #[arg(short, long)]
+        schema: PathBuf,
+
+        /// Output file (defaults to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Generate type declarations for the converted (model output) and
+    /// rehydrated (original) shapes of a schema
+    Typegen {
+        /// Input JSON Schema file
+        input: PathBuf,
+
+        /// Declaration language
+        #[arg(short, long, value_enum, default_value_t = TypeLanguageArg::Typescript)]
+        language: TypeLanguageArg,
+
+        /// Target LLM provider the converted shape is generated for
+        #[arg(short, long, value_enum, default_value_t = TargetArg::OpenaiStrict)]
+        target: TargetArg,
+
+        /// Conversion mode (strict vs permissive)
+        #[arg(long, value_enum, default_value_t = ModeArg::Strict)]
+        mode: ModeArg,
+
+        /// Root type name (defaults to the schema title, else "Root")
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Output file (defaults to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Write a system-prompt block describing the converted schema field by
+    /// field (types, enum values, JSON-string and null reminders)
+    Prompt {
+        /// Input JSON Schema file
+        input: PathBuf,
+
+        /// Target LLM provider the schema is converted for
+        #[arg(short, long, value_enum, default_value_t = TargetArg::OpenaiStrict)]
+        target: TargetArg,
+
+        /// Conversion mode (strict vs permissive)
+        #[arg(long, value_enum, default_value_t = ModeArg::Strict)]
+        mode: ModeArg,
+
+        /// Enum values listed per field before the rest are summarized
+        #[arg(long, default_value_t = 20)]
+        max_enum_values: usize,
+
+        /// Output file (defaults to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Reshape original-schema sample data into the converted shape, for
+    /// few-shot examples in prompts
+    Examples {
+        /// Input JSON Schema file
+        input: PathBuf,
+
+        /// Sample instances of the original schema, one JSON value per line
+        #[arg(long, value_name = "NDJSON")]
+        data: PathBuf,
+
+        /// Target LLM provider the examples are shaped for
+        #[arg(short, long, value_enum, default_value_t = TargetArg::OpenaiStrict)]
+        target: TargetArg,
+
+        /// Conversion mode (strict vs permissive)
+        #[arg(long, value_enum, default_value_t = ModeArg::Strict)]
+        mode: ModeArg,
+
+        /// Output NDJSON file (defaults to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Explain why an LLM answer can't be rehydrated and print a hint to
+    /// include in the retry prompt
+    Diagnose {
+        /// Raw LLM output file
+        input: PathBuf,
+
+        /// Converted schema file the answer was generated for
+        #[arg(long)]
+        schema: PathBuf,
+
+        /// Codec file from conversion
+        #[arg(long)]
+        codec: PathBuf,
+    },
+
+    /// Check that a codec belongs to a converted schema before rehydrating
+    /// answers with it
+    CheckCodec {
+        /// Converted schema file
+        #[arg(long)]
+        schema: PathBuf,
+
+        /// Codec file to check against it
+        #[arg(long)]
+        codec: PathBuf,
+    },
+
+    /// Convert several schemas into one provider `tools` array, plus the
+    /// per-tool codecs for rehydrating the model's calls
+    Tools {
+        /// Tools in the order offered, each as NAME=SCHEMA_FILE
+        #[arg(required = true, value_name = "NAME=FILE")]
+        tools: Vec<String>,
+
+        /// Target LLM provider the tools are declared for
+        #[arg(short, long, value_enum, default_value_t = TargetArg::OpenaiStrict)]
+        target: TargetArg,
+
+        /// Conversion mode (strict vs permissive)
+        #[arg(long, value_enum, default_value_t = ModeArg::Strict)]
+        mode: ModeArg,
+
+        /// Output file for the codecs keyed by tool name
+        #[arg(long)]
+        codecs: Option<PathBuf>,
+
+        /// Output file (defaults to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Snapshot regression corpus (see tests/corpus/README.md)
+    Corpus {
+        #[command(subcommand)]
+        action: CorpusAction,
+    },
+
+    /// Write binding conformance vectors (input, options, converted schema,
+    /// codec, rehydration) for every corpus case
+    GenConformance {
+        /// Output directory
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Corpus root directory to generate from
+        #[arg(long, default_value = "tests/corpus")]
+        corpus: PathBuf,
+    },
+
+    /// Live check: convert a schema, send it to the provider with a canned
+    /// prompt, and verify the response is accepted and rehydrates
+    Conformance {
+        /// Input JSON Schema file
+        #[arg(short, long)]
+        schema: PathBuf,
+
+        /// Target LLM provider
+        #[arg(short, long, value_enum, default_value_t = TargetArg::OpenaiStrict)]
+        target: TargetArg,
+
+        /// Conversion mode (strict vs permissive)
+        #[arg(long, value_enum, default_value_t = ModeArg::Strict)]
+        mode: ModeArg,
+
+        /// Provider API key (defaults to OPENAI_API_KEY, GEMINI_API_KEY or
+        /// ANTHROPIC_API_KEY)
+        #[arg(long)]
+        api_key: Option<String>,
+
+        /// Model ID (defaults to a small current model for the provider)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Override the provider's API base URL (e.g. for a proxy)
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Generate man pages (the top-level page to stdout, or one page per
+    /// subcommand with --out-dir)
+    Man {
+        /// Write json-schema-llm.1 and json-schema-llm-<command>.1 here
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CorpusAction {
+    /// Compare every case with its expected.json; fails on any difference
+    Check {
+        /// Corpus root directory
+        #[arg(long, default_value = "tests/corpus")]
+        dir: PathBuf,
+    },
+    /// Rewrite expected.json for every case (or just --case) from current output
+    Update {
+        /// Corpus root directory
+        #[arg(long, default_value = "tests/corpus")]
+        dir: PathBuf,
+
+        /// Only bless this case
+        #[arg(long)]
+        case: Option<String>,
     },
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum PresetArg {
+    OpenaiStructuredOutput,
+    OpenaiToolCalling,
+    GeminiStructuredOutput,
+    ClaudeToolUse,
+}
+
+impl From<PresetArg> for Preset {
+    fn from(val: PresetArg) -> Self {
+        match val {
+            PresetArg::OpenaiStructuredOutput => Preset::OpenaiStructuredOutput,
+            PresetArg::OpenaiToolCalling => Preset::OpenaiToolCalling,
+            PresetArg::GeminiStructuredOutput => Preset::GeminiStructuredOutput,
+            PresetArg::ClaudeToolUse => Preset::ClaudeToolUse,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum TargetArg {
     OpenaiStrict,
@@ -197,9 +797,234 @@ impl From<PolymorphismArg> for PolymorphismStrategy {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-enum OutputFormat {
-    Pretty,
-    Compact,
+enum MergeConflictsArg {
+    Intersect,
+    PreferFirst,
+    Error,
+}
+
+impl From<MergeConflictsArg> for MergeConflictPolicy {
+    fn from(val: MergeConflictsArg) -> Self {
+        match val {
+            MergeConflictsArg::Intersect => MergeConflictPolicy::Intersect,
+            MergeConflictsArg::PreferFirst => MergeConflictPolicy::PreferFirst,
+            MergeConflictsArg::Error => MergeConflictPolicy::Error,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum DuplicateKeysArg {
+    Ignore,
+    Warn,
+    Error,
+}
+
+impl From<DuplicateKeysArg> for DuplicateKeyCheck {
+    fn from(val: DuplicateKeysArg) -> Self {
+        match val {
+            DuplicateKeysArg::Ignore => DuplicateKeyCheck::Ignore,
+            DuplicateKeysArg::Warn => DuplicateKeyCheck::Warn,
+            DuplicateKeysArg::Error => DuplicateKeyCheck::Error,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum EnumDescriptionsArg {
+    Auto,
+    Fold,
+    OneOf,
+    Ignore,
+}
+
+impl From<EnumDescriptionsArg> for EnumDescriptionStyle {
+    fn from(val: EnumDescriptionsArg) -> Self {
+        match val {
+            EnumDescriptionsArg::Auto => EnumDescriptionStyle::Auto,
+            EnumDescriptionsArg::Fold => EnumDescriptionStyle::Fold,
+            EnumDescriptionsArg::OneOf => EnumDescriptionStyle::OneOf,
+            EnumDescriptionsArg::Ignore => EnumDescriptionStyle::Ignore,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum OnErrorArg {
+    Fail,
+    SkipSubtree,
+    Placeholder,
+}
+
+impl From<OnErrorArg> for ErrorPolicy {
+    fn from(val: OnErrorArg) -> Self {
+        match val {
+            OnErrorArg::Fail => ErrorPolicy::Fail,
+            OnErrorArg::SkipSubtree => ErrorPolicy::SkipSubtree,
+            OnErrorArg::Placeholder => ErrorPolicy::Placeholder,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum PropertyOrderingArg {
+    Off,
+    Original,
+}
+
+impl From<PropertyOrderingArg> for PropertyOrdering {
+    fn from(val: PropertyOrderingArg) -> Self {
+        match val {
+            PropertyOrderingArg::Off => PropertyOrdering::Off,
+            PropertyOrderingArg::Original => PropertyOrdering::Original,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum ConstraintHintsArg {
+    Off,
+    Labeled,
+    Compact,
+}
+
+impl From<ConstraintHintsArg> for ConstraintHintPolicy {
+    fn from(val: ConstraintHintsArg) -> Self {
+        match val {
+            ConstraintHintsArg::Off => ConstraintHintPolicy::Off,
+            ConstraintHintsArg::Labeled => ConstraintHintPolicy::Labeled,
+            ConstraintHintsArg::Compact => ConstraintHintPolicy::Compact,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum InputFormatArg {
+    /// JSON Schema
+    Json,
+    /// Protocol Buffers FileDescriptorSet (protoc --descriptor_set_out)
+    Protobuf,
+    /// GraphQL schema definition language
+    Graphql,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum ContentSchemaArg {
+    Opaque,
+    Inline,
+}
+
+impl From<ContentSchemaArg> for ContentSchemaPolicy {
+    fn from(val: ContentSchemaArg) -> Self {
+        match val {
+            ContentSchemaArg::Opaque => ContentSchemaPolicy::Opaque,
+            ContentSchemaArg::Inline => ContentSchemaPolicy::Inline,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum DeprecatedArg {
+    Keep,
+    Drop,
+    Annotate,
+}
+
+impl From<DeprecatedArg> for DeprecatedPolicy {
+    fn from(val: DeprecatedArg) -> Self {
+        match val {
+            DeprecatedArg::Keep => DeprecatedPolicy::Keep,
+            DeprecatedArg::Drop => DeprecatedPolicy::Drop,
+            DeprecatedArg::Annotate => DeprecatedPolicy::Annotate,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum RecursionLeafArg {
+    OpaqueString,
+    Null,
+    NodeId,
+}
+
+impl From<RecursionLeafArg> for RecursionLeaf {
+    fn from(val: RecursionLeafArg) -> Self {
+        match val {
+            RecursionLeafArg::OpaqueString => RecursionLeaf::OpaqueString,
+            RecursionLeafArg::Null => RecursionLeaf::Null,
+            RecursionLeafArg::NodeId => RecursionLeaf::NodeId,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum NullableStyleArg {
+    AnyOfNull,
+    TypeArray,
+    OpenApiNullable,
+}
+
+impl From<NullableStyleArg> for NullableStyle {
+    fn from(val: NullableStyleArg) -> Self {
+        match val {
+            NullableStyleArg::AnyOfNull => NullableStyle::AnyOfNull,
+            NullableStyleArg::TypeArray => NullableStyle::TypeArray,
+            NullableStyleArg::OpenApiNullable => NullableStyle::OpenApiNullable,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum AdditionalPropertiesArg {
+    Seal,
+    Preserve,
+}
+
+impl From<AdditionalPropertiesArg> for AdditionalPropertiesPolicy {
+    fn from(val: AdditionalPropertiesArg) -> Self {
+        match val {
+            AdditionalPropertiesArg::Seal => AdditionalPropertiesPolicy::Seal,
+            AdditionalPropertiesArg::Preserve => AdditionalPropertiesPolicy::Preserve,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum LocaleArg {
+    En,
+    Es,
+    Ja,
+}
+
+impl From<LocaleArg> for Locale {
+    fn from(val: LocaleArg) -> Self {
+        match val {
+            LocaleArg::En => Locale::En,
+            LocaleArg::Es => Locale::Es,
+            LocaleArg::Ja => Locale::Ja,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum EnvelopeArg {
+    ResponseFormat,
+    ToolFunction,
+}
+
+impl From<EnvelopeArg> for Envelope {
+    fn from(val: EnvelopeArg) -> Self {
+        match val {
+            EnvelopeArg::ResponseFormat => Envelope::ResponseFormat,
+            EnvelopeArg::ToolFunction => Envelope::ToolFunction,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum OutputFormat {
+    Pretty,
+    Compact,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -209,6 +1034,39 @@ enum SdkLanguage {
     #[value(name = "typescript")]
     TypeScript,
     Ruby,
+    Rust,
+    Kotlin,
+    Swift,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum TypeLanguageArg {
+    Typescript,
+}
+
+impl From<TypeLanguageArg> for TypeLanguage {
+    fn from(val: TypeLanguageArg) -> Self {
+        match val {
+            TypeLanguageArg::Typescript => TypeLanguage::Typescript,
+        }
+    }
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum LayoutArg {
+    Flat,
+    PointerPrefix,
+    Connected,
+}
+
+impl From<LayoutArg> for ModuleLayout {
+    fn from(layout: LayoutArg) -> Self {
+        match layout {
+            LayoutArg::Flat => ModuleLayout::Flat,
+            LayoutArg::PointerPrefix => ModuleLayout::PointerPrefix,
+            LayoutArg::Connected => ModuleLayout::Connected,
+        }
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -217,24 +1075,32 @@ enum BuildToolArg {
     Setuptools,
     Npm,
     Bundler,
+    Cargo,
+    Gradle,
+    Swiftpm,
 }
 
 // ---------------------------------------------------------------------------
 // Manifest types (#179)
 // ---------------------------------------------------------------------------
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct Manifest {
     version: String,
-    generated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generated_at: Option<String>,
     source_schema: String,
+    fingerprint: String,
     target: String,
     mode: String,
     components: Vec<ManifestComponent>,
+    /// Components that failed to convert; `--resume` retries them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    component_errors: Vec<ManifestComponentError>,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ManifestComponent {
     name: String,
@@ -243,10 +1109,48 @@ struct ManifestComponent {
     codec_path: String,
     original_path: String,
     dependency_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    refs_out: Vec<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestComponentError {
+    pointer: String,
+    message: String,
+}
+
+/// Top-level metadata for a multi-language `gen-sdk` run.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SdkWorkspace {
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generated_at: Option<String>,
+    schema_dir: String,
+    sdks: Vec<WorkspaceSdk>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceSdk {
+    language: String,
+    path: String,
+    package: String,
+    build_tool: String,
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let mut command = Vec::new();
+    let mut sub = &matches;
+    while let Some((name, next)) = sub.subcommand() {
+        command.push(name);
+        sub = next;
+    }
 
     // Initialize tracing — logs go to stderr so stdout stays clean for JSON
     let log_level = if cli.verbose {
@@ -259,60 +1163,355 @@ fn main() -> Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
-    match cli.command {
+    let mut out = Output::new(cli.json);
+    let outcome = run(cli.command, &mut out);
+    if !out.finish(&command.join(" "), outcome)? {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run(command: Commands, out: &mut Output) -> Result<()> {
+    match command {
         Commands::Convert {
             input,
+            input_format,
+            duplicate_keys,
+            message,
+            type_name,
             output,
             output_dir,
             codec: codec_path,
+            preset,
             target,
             mode,
             polymorphism,
+            merge_conflicts,
+            on_error,
             max_depth,
             recursion_limit,
+            recursion_leaf,
             skip_components,
+            provider_max_depth,
+            max_anyof_variants,
+            max_enum_values,
+            enum_descriptions,
+            constraint_hints,
+            no_numeric_hints,
+            constraint_hint_budget,
+            nullable_style,
+            additional_properties,
+            additional_properties_name,
+            filter_read_only,
+            filter_write_only,
+            deprecated,
+            content_schema,
+            locale,
+            messages,
+            suppress,
+            property_ordering,
+            envelope,
+            envelope_name,
+            no_progress,
+            self_check,
+            verify_output,
+            validate_input,
+            preserve_precision,
+            annotate,
+            strip_descriptions,
+            keep_description,
+            canonical,
+            project,
+            split_max_bytes,
+            reproducible,
+            resume,
+            jobs,
             format,
         } => {
-            let schema = read_schema(&input)?;
+            check_preserve_precision(preserve_precision)?;
+            let (schema_text, schema) = match input_format {
+                InputFormatArg::Json => {
+                    let text = fs::read_to_string(&input).with_context(|| {
+                        format!("Failed to open input file: {}", input.display())
+                    })?;
+                    let schema: Value = parse_value(text.as_bytes(), preserve_precision)
+                        .with_context(|| {
+                            format!("Failed to parse schema from: {}", input.display())
+                        })?;
+                    (text, schema)
+                }
+                InputFormatArg::Protobuf => {
+                    let bytes = fs::read(&input).with_context(|| {
+                        format!("Failed to open input file: {}", input.display())
+                    })?;
+                    let message = message.expect("clap enforces --message for protobuf input");
+                    let schema = proto::message_schema(&bytes, &message).map_err(|e| {
+                        anyhow::Error::from(e).context(format!(
+                            "Failed to read descriptors from: {}",
+                            input.display()
+                        ))
+                    })?;
+                    (serde_json::to_string(&schema)?, schema)
+                }
+                InputFormatArg::Graphql => {
+                    let sdl = fs::read_to_string(&input).with_context(|| {
+                        format!("Failed to open input file: {}", input.display())
+                    })?;
+                    let type_name = type_name.expect("clap enforces --type for graphql input");
+                    let schema = graphql::type_schema(&sdl, &type_name).map_err(|e| {
+                        anyhow::Error::from(e).context(format!(
+                            "Failed to read GraphQL schema from: {}",
+                            input.display()
+                        ))
+                    })?;
+                    (serde_json::to_string(&schema)?, schema)
+                }
+            };
 
-            let mut options = ConvertOptions::default();
-            options.target = target.into();
-            options.mode = mode.into();
-            options.polymorphism = polymorphism.into();
+            let mut options = preset.map_or_else(ConvertOptions::default, |p| {
+                ConvertOptions::preset(p.into())
+            });
+            if let Some(target) = target {
+                options.target = target.into();
+            }
+            if let Some(mode) = mode {
+                options.mode = mode.into();
+            }
+            if let Some(polymorphism) = polymorphism {
+                options.polymorphism = polymorphism.into();
+            }
+            options.merge_conflicts = merge_conflicts.into();
+            options.on_error = on_error.into();
             options.max_depth = max_depth;
             options.recursion_limit = recursion_limit;
+            options.recursion_leaf = recursion_leaf.into();
             options.skip_components = skip_components;
+            options.provider_max_depth = provider_max_depth;
+            options.max_anyof_variants = max_anyof_variants;
+            options.max_enum_values = max_enum_values;
+            options.enum_descriptions = enum_descriptions.into();
+            if let Some(constraint_hints) = constraint_hints {
+                options.constraint_hints = constraint_hints.into();
+            }
+            options.numeric_constraint_hints = !no_numeric_hints;
+            options.constraint_hint_budget = constraint_hint_budget;
+            if let Some(nullable_style) = nullable_style {
+                options.nullable_style = nullable_style.into();
+            }
+            options.additional_properties = additional_properties.into();
+            options.additional_properties_name = additional_properties_name;
+            options.filter_read_only |= filter_read_only;
+            options.filter_write_only |= filter_write_only;
+            if let Some(deprecated) = deprecated {
+                options.deprecated_properties = deprecated.into();
+            }
+            options.content_schema = content_schema.into();
+            options.locale = locale.into();
+            if let Some(path) = messages {
+                let text = fs::read_to_string(&path).with_context(|| {
+                    format!("Failed to open message catalog: {}", path.display())
+                })?;
+                let catalog: MessageCatalog = serde_json::from_str(&text).with_context(|| {
+                    format!("Failed to parse message catalog from: {}", path.display())
+                })?;
+                options.message_catalog = Some(catalog);
+            }
+            options.suppress = suppress;
+            options.verify_output |= verify_output;
+            options.validate_input |= validate_input;
+            options.preserve_precision |= preserve_precision;
+            options.annotate |= annotate;
+            options.strip_descriptions |= strip_descriptions;
+            options.keep_descriptions.extend(keep_description);
+            options.canonical |= canonical;
+            if let Some(property_ordering) = property_ordering {
+                options.property_ordering = property_ordering.into();
+            }
+            options.envelope = envelope.map(Into::into);
+            options.envelope_name = envelope_name;
+            if !no_progress && !out.is_json() {
+                options.progress = Some(progress_sink());
+            }
+
+            // Checked once here for every output mode, so `convert_str`
+            // below must not report them again.
+            if input_format == InputFormatArg::Json {
+                options.duplicate_keys = duplicate_keys.into();
+                let findings = check_duplicate_keys(&schema_text, &options)
+                    .map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+                for finding in &findings {
+                    out.finding(finding.rule_id(), finding);
+                }
+                options.duplicate_keys = DuplicateKeyCheck::Ignore;
+            }
+
+            // `convert_str` keeps Gemini's source key order itself; the
+            // `Value` entry points below need it recorded on the schema.
+            let schema = if options.target == Target::Gemini
+                && options.property_ordering == PropertyOrdering::Original
+            {
+                seed_property_ordering(schema, &schema_text)?
+            } else {
+                schema
+            };
 
             if let Some(ref dir) = output_dir {
                 // --output-dir mode: multi-file output with components
-                handle_output_dir(&schema, &input, dir, &options, format)?;
+                let settings = OutputDirSettings {
+                    format,
+                    self_check,
+                    reproducible,
+                    resume,
+                    jobs: match jobs {
+                        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+                        n => n,
+                    },
+                };
+                handle_output_dir(&schema, &input, dir, &options, settings, out)?;
+            } else if let Some(max_bytes) = split_max_bytes {
+                let plan = split_schema(&schema, max_bytes, &options);
+                clear_progress();
+                let plan = plan.map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+                if codec_path.is_none() {
+                    out.warn(
+                        None,
+                        "No codec file specified. You will not be able to rehydrate LLM outputs.",
+                    );
+                }
+
+                let schema_of = |result: &ConvertResult| {
+                    result
+                        .envelope
+                        .clone()
+                        .unwrap_or_else(|| result.schema.clone())
+                };
+                let document = serde_json::json!({
+                    "primary": schema_of(&plan.primary),
+                    "parts": plan.parts.iter().map(|part| serde_json::json!({
+                        "dataPath": part.data_path,
+                        "schema": schema_of(&part.result),
+                    })).collect::<Vec<_>>(),
+                });
+                out.emit(&document, output.as_deref(), format)?;
+                if let Some(path) = codec_path {
+                    out.write_file(&plan.codec(), &path, format)?;
+                }
+
+                report_compat_diagnostics(&plan.primary, out);
+                for part in &plan.parts {
+                    report_compat_diagnostics(&part.result, out);
+                }
             } else {
                 // Single-file output mode (original behavior)
-                let result = convert(&schema, &options)
-                    .map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+                let result = if project.is_empty() {
+                    convert_str(&schema_text, &options)
+                } else {
+                    let paths: Vec<&str> = project.iter().map(String::as_str).collect();
+                    project_schema(&schema, &paths, &options)
+                };
+                clear_progress();
+                let result =
+                    result.map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+                if self_check {
+                    run_self_check(&schema, &result, out)?;
+                }
 
                 // Warn if no codec file specified
                 if codec_path.is_none() {
-                    eprintln!(
-                        "Warning: No codec file specified. You will not be able to rehydrate LLM outputs."
+                    out.warn(
+                        None,
+                        "No codec file specified. You will not be able to rehydrate LLM outputs.",
                     );
                 }
 
-                // Write converted schema
-                write_json(&result.schema, output.as_ref(), format)?;
+                // Write converted schema (wrapped, if an envelope was requested)
+                let schema_out = result.envelope.as_ref().unwrap_or(&result.schema);
+                out.emit(schema_out, output.as_deref(), format)?;
 
                 // Write codec sidecar
                 if let Some(path) = codec_path {
-                    write_json(&result.codec, Some(&path), format)?;
+                    out.write_file(&result.codec, &path, format)?;
                 }
 
                 // Report provider compat diagnostics (informational — transforms were applied)
-                if !result.provider_compat_errors.is_empty() {
-                    eprintln!("Provider compatibility diagnostics:");
-                    for err in &result.provider_compat_errors {
-                        eprintln!("- {}", err);
-                    }
+                report_compat_diagnostics(&result, out);
+            }
+        }
+        Commands::Fingerprint {
+            input,
+            preset,
+            target,
+            mode,
+        } => {
+            let schema = read_schema(&input)?;
+            let mut options = preset.map_or_else(ConvertOptions::default, |p| {
+                ConvertOptions::preset(p.into())
+            });
+            if let Some(target) = target {
+                options.target = target.into();
+            }
+            if let Some(mode) = mode {
+                options.mode = mode.into();
+            }
+            let fingerprint = schema_fingerprint(&schema, &options);
+            if out.is_json() {
+                out.set_result(&fingerprint)?;
+            } else {
+                println!("{}", fingerprint);
+            }
+        }
+        Commands::Lint {
+            input,
+            preset,
+            target,
+            mode,
+            suppress,
+        } => {
+            let text = fs::read_to_string(&input)
+                .with_context(|| format!("Failed to open input file: {}", input.display()))?;
+            let mut options = preset.map_or_else(ConvertOptions::default, |p| {
+                ConvertOptions::preset(p.into())
+            });
+            if let Some(target) = target {
+                options.target = target.into();
+            }
+            if let Some(mode) = mode {
+                options.mode = mode.into();
+            }
+            options.suppress = suppress;
+            options.duplicate_keys = DuplicateKeyCheck::Warn;
+            let report = lint_str(&text, &options)
+                .map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+
+            if out.is_json() {
+                out.set_result(&report)?;
+            } else {
+                for finding in &report.findings {
+                    let location = finding
+                        .location
+                        .map(|loc| format!("{}:{}:{}", input.display(), loc.line, loc.column))
+                        .unwrap_or_else(|| input.display().to_string());
+                    let severity = match finding.severity {
+                        Severity::Warning => "warning",
+                        Severity::Info => "info",
+                    };
+                    println!(
+                        "{location}: {severity} [{}] {}: {}",
+                        finding.rule_id, finding.path, finding.message
+                    );
                 }
+                println!(
+                    "{} finding(s), {} warning(s), {} suppressed",
+                    report.findings.len(),
+                    report.warnings(),
+                    report.suppressed
+                );
+            }
+            if report.warnings() > 0 {
+                anyhow::bail!(
+                    "{} lint warning(s); acknowledge intended ones with --suppress",
+                    report.warnings()
+                );
             }
         }
         Commands::Rehydrate {
@@ -320,9 +1519,14 @@ fn main() -> Result<()> {
             codec,
             schema,
             output,
+            provider_response,
+            from_provider_response,
+            preserve_precision,
+            aggregate_warnings,
+            max_warnings,
             format,
         } => {
-            let data: serde_json::Value = {
+            let mut data: serde_json::Value = {
                 let raw = std::fs::read_to_string(&input)
                     .with_context(|| format!("Failed to read input file: {}", input.display()))?;
 
@@ -335,30 +1539,57 @@ fn main() -> Result<()> {
                 })?
             };
 
+            check_preserve_precision(preserve_precision)?;
             let codec_obj: Codec = {
                 let file = File::open(&codec)
                     .with_context(|| format!("Failed to open codec file: {}", codec.display()))?;
-                let reader = BufReader::new(file);
-                serde_json::from_reader(reader)
+                parse_value(BufReader::new(file), preserve_precision)
                     .with_context(|| format!("Failed to parse codec from: {}", codec.display()))?
             };
 
             let original_schema: serde_json::Value = {
                 let file = File::open(&schema)
                     .with_context(|| format!("Failed to open schema file: {}", schema.display()))?;
-                let reader = BufReader::new(file);
-                serde_json::from_reader(reader)
+                parse_value(BufReader::new(file), preserve_precision)
                     .with_context(|| format!("Failed to parse schema from: {}", schema.display()))?
             };
+            if !preserve_precision {
+                round_numbers(&mut data);
+            }
 
-            let result = rehydrate(&data, &codec_obj, &original_schema)
-                .map_err(|e| anyhow::Error::from(e).context("Rehydration failed"))?;
+            let provider = match provider_response {
+                Some(target) => Some(target.into()),
+                None if from_provider_response => {
+                    Some(providers::detect_provider(&data).with_context(|| {
+                        format!(
+                            "Could not detect the provider of the response in: {} \
+                             (pass --provider-response TARGET)",
+                            input.display()
+                        )
+                    })?)
+                }
+                None => None,
+            };
+            let mut options = RehydrateOptions::new();
+            options.aggregate_warnings = aggregate_warnings;
+            options.max_warnings = max_warnings;
+            let result = match provider {
+                Some(target) => providers::extract_structured_output(target, &data),
+                None => Ok(data),
+            }
+            // The parsed input is not needed afterwards, so hand it over
+            // rather than have `rehydrate` clone it.
+            .and_then(|data| {
+                let compiled = CompiledCodec::new(codec_obj)?;
+                rehydrate_compiled(data, &compiled, &original_schema, &options)
+            })
+            .map_err(|e| anyhow::Error::from(e).context("Rehydration failed"))?;
 
             for warning in &result.warnings {
-                eprintln!("Warning: {}", warning.message);
+                out.warn_at(warning.rule_id(), &warning.data_path, &warning.message);
             }
 
-            write_json(&result.data, output.as_ref(), format)?;
+            out.emit(&result.data, output.as_deref(), format)?;
         }
         Commands::Extract {
             input,
@@ -371,175 +1602,482 @@ fn main() -> Result<()> {
             let result = extract_component(&schema, &pointer, &extract_opts)
                 .map_err(|e| anyhow::Error::from(e).context("Extraction failed"))?;
 
-            write_json(&result.schema, output.as_ref(), format)?;
+            out.emit(&result.schema, output.as_deref(), format)?;
         }
-        Commands::ListComponents { input } => {
+        Commands::ListComponents { input, referencing } => {
             let schema = read_schema(&input)?;
-            let components = list_components(&schema);
-            for pointer in &components {
-                println!("{}", pointer);
+            let mut components = describe_components(&schema)
+                .map_err(|e| anyhow::Error::from(e).context("Listing components failed"))?;
+            if let Some(pointer) = referencing {
+                let dependents = find_components_referencing(&schema, &pointer)
+                    .map_err(|e| anyhow::Error::from(e).context("Listing components failed"))?;
+                components.retain(|c| dependents.contains(&c.pointer));
+            }
+            if out.is_json() {
+                out.set_result(&components)?;
+            } else {
+                print_component_table(&components);
             }
         }
-        Commands::GenSdk {
+        Commands::GenOpenapi { schema, output } => {
+            let fragment = json_schema_llm_codegen::openapi::generate(&schema)?;
+            out.emit(&fragment, output.as_deref(), OutputFormat::Pretty)?;
+        }
+        Commands::Typegen {
+            input,
             language,
-            schema,
-            package,
+            target,
+            mode,
+            name,
             output,
-            git_init,
-            build_tool,
         } => {
-            // Language-aware package name validation
-            match language {
-                SdkLanguage::Java => {
-                    if !package
-                        .chars()
-                        .all(|c| c.is_alphanumeric() || c == '.' || c == '_')
-                    {
-                        anyhow::bail!(
-                            "Invalid Java package name '{}': must contain only alphanumeric, dot, and underscore",
-                            package
-                        );
-                    }
-                }
-                SdkLanguage::Python => {
-                    // PEP 508: ^[a-zA-Z0-9]([a-zA-Z0-9._-]*[a-zA-Z0-9])?$
-                    let valid = !package.is_empty()
-                        && package.starts_with(|c: char| c.is_ascii_alphanumeric())
-                        && package.ends_with(|c: char| c.is_ascii_alphanumeric())
-                        && package
-                            .chars()
-                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
-                    if !valid {
-                        anyhow::bail!(
-                            "Invalid Python package name '{}': must start and end with alphanumeric, \
-                             contain only alphanumeric, hyphen, underscore, and dot (PEP 508)",
-                            package
-                        );
-                    }
-                }
-                SdkLanguage::TypeScript => {
-                    // npm package names: lowercase, may be scoped (@scope/name)
-                    let valid = !package.is_empty()
-                        && package.chars().all(|c| {
-                            c.is_ascii_lowercase() || c.is_ascii_digit() || "-_@/.".contains(c)
-                        });
-                    if !valid {
-                        anyhow::bail!(
-                            "Invalid npm package name '{}': must contain only lowercase alphanumeric, \
-                             hyphen, underscore, @, /, and dot",
-                            package
-                        );
-                    }
+            let schema = read_schema(&input)?;
+            let mut options = ConvertOptions::default();
+            options.target = target.into();
+            options.mode = mode.into();
+            let result = convert(&schema, &options)
+                .map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+
+            let mut typegen_options = TypegenOptions::default();
+            typegen_options.language = language.into();
+            typegen_options.root_name = name;
+            let declarations = emit_types(&schema, &result.schema, &typegen_options);
+
+            match output {
+                Some(path) => {
+                    fs::write(&path, declarations).with_context(|| {
+                        format!("Failed to write output file: {}", path.display())
+                    })?;
+                    out.record_file(&path);
                 }
-                SdkLanguage::Ruby => {
-                    // Ruby gem names: lowercase, start with letter, alphanumeric + hyphen + underscore
-                    let valid = !package.is_empty()
-                        && package.starts_with(|c: char| c.is_ascii_lowercase())
-                        && package.chars().all(|c| {
-                            c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'
-                        });
-                    if !valid {
-                        anyhow::bail!(
-                            "Invalid Ruby gem name '{}': must start with a lowercase letter, \
-                             contain only lowercase alphanumeric, hyphen, and underscore",
-                            package
-                        );
-                    }
+                None if out.is_json() => out.set_result(&declarations)?,
+                None => print!("{}", declarations),
+            }
+        }
+        Commands::Prompt {
+            input,
+            target,
+            mode,
+            max_enum_values,
+            output,
+        } => {
+            let schema = read_schema(&input)?;
+            let mut options = ConvertOptions::default();
+            options.target = target.into();
+            options.mode = mode.into();
+            let result = convert(&schema, &options)
+                .map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+
+            let mut prompt_options = PromptOptions::default();
+            prompt_options.codec = Some(result.codec);
+            prompt_options.max_enum_values = max_enum_values;
+            let scaffold = generate_prompt_scaffold(&result.schema, &prompt_options);
+
+            match output {
+                Some(path) => {
+                    fs::write(&path, scaffold).with_context(|| {
+                        format!("Failed to write output file: {}", path.display())
+                    })?;
+                    out.record_file(&path);
                 }
+                None if out.is_json() => out.set_result(&scaffold)?,
+                None => print!("{}", scaffold),
             }
+        }
+        Commands::Examples {
+            input,
+            data,
+            target,
+            mode,
+            output,
+        } => {
+            let schema = read_schema(&input)?;
+            let text = fs::read_to_string(&data)
+                .with_context(|| format!("Failed to open data file: {}", data.display()))?;
+            let instances = text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| {
+                    parse_value(line.as_bytes(), false).with_context(|| {
+                        format!("Failed to parse line {} of: {}", i + 1, data.display())
+                    })
+                })
+                .collect::<Result<Vec<Value>>>()?;
+            let mut options = ConvertOptions::default();
+            options.target = target.into();
+            options.mode = mode.into();
+            let examples = make_fewshot(&schema, &instances, &options)
+                .map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
 
-            // Derive artifact name from package
-            let artifact_name = match language {
-                SdkLanguage::Java => package
-                    .trim_end_matches('.')
-                    .rsplit('.')
-                    .next()
-                    .unwrap()
-                    .to_string(),
-                SdkLanguage::Python => package.clone(),
-                SdkLanguage::TypeScript => {
-                    // For scoped packages like @scope/name, use "name" as artifact
-                    package.rsplit('/').next().unwrap_or(&package).to_string()
+            let mut ndjson = String::new();
+            for example in &examples {
+                ndjson.push_str(&serde_json::to_string(example)?);
+                ndjson.push('\n');
+            }
+            match output {
+                Some(path) => {
+                    fs::write(&path, ndjson).with_context(|| {
+                        format!("Failed to write output file: {}", path.display())
+                    })?;
+                    out.record_file(&path);
                 }
-                SdkLanguage::Ruby => package.clone(),
+                None if out.is_json() => out.set_result(&examples)?,
+                None => print!("{}", ndjson),
+            }
+        }
+        Commands::Diagnose {
+            input,
+            schema,
+            codec,
+        } => {
+            let raw = fs::read_to_string(&input)
+                .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+            let converted = read_schema(&schema)?;
+            let codec_obj: Codec = {
+                let file = File::open(&codec)
+                    .with_context(|| format!("Failed to open codec file: {}", codec.display()))?;
+                parse_value(BufReader::new(file), false)
+                    .with_context(|| format!("Failed to parse codec from: {}", codec.display()))?
             };
+            let report = diagnose_failure(&raw, &converted, &codec_obj);
 
-            // Resolve build tool: validate combo, then apply language default if omitted
-            let resolved_build_tool = match (language, build_tool) {
-                // Explicit and valid combos
-                (SdkLanguage::Java, Some(BuildToolArg::Maven)) => {
-                    json_schema_llm_codegen::BuildTool::Maven
-                }
-                (SdkLanguage::Python, Some(BuildToolArg::Setuptools)) => {
-                    json_schema_llm_codegen::BuildTool::Setuptools
-                }
-                (SdkLanguage::TypeScript, Some(BuildToolArg::Npm)) => {
-                    json_schema_llm_codegen::BuildTool::Npm
+            if out.is_json() {
+                out.set_result(&report)?;
+            } else if let Some(hint) = &report.retry_hint {
+                println!("{}", hint);
+            }
+            if !report.failures.is_empty() {
+                anyhow::bail!("{} problem(s) found in the answer", report.failures.len());
+            }
+        }
+        Commands::CheckCodec { schema, codec } => {
+            let converted = read_schema(&schema)?;
+            let codec_obj: Codec = {
+                let file = File::open(&codec)
+                    .with_context(|| format!("Failed to open codec file: {}", codec.display()))?;
+                parse_value(BufReader::new(file), false)
+                    .with_context(|| format!("Failed to parse codec from: {}", codec.display()))?
+            };
+            let report = codec_matches(&codec_obj, &converted);
+
+            if out.is_json() {
+                out.set_result(&report)?;
+            } else {
+                for issue in &report.issues {
+                    match &issue.converted_path {
+                        Some(path) => println!("{}: {}", path, issue.message),
+                        None => println!("{}", issue.message),
+                    }
                 }
-                (SdkLanguage::Ruby, Some(BuildToolArg::Bundler)) => {
-                    json_schema_llm_codegen::BuildTool::Bundler
+            }
+            if !report.is_compatible() {
+                anyhow::bail!(
+                    "codec does not match the schema ({} issue(s))",
+                    report.issues.len()
+                );
+            }
+        }
+        Commands::Tools {
+            tools,
+            target,
+            mode,
+            codecs,
+            output,
+        } => {
+            let mut options = ConvertOptions::default();
+            options.target = target.into();
+            options.mode = mode.into();
+            let mut registry = ToolRegistry::new(options);
+            for spec in &tools {
+                let (name, file) = spec
+                    .split_once('=')
+                    .with_context(|| format!("Expected NAME=FILE, got: {}", spec))?;
+                let schema = read_schema(Path::new(file))?;
+                registry.register(name, &schema).map_err(|e| {
+                    anyhow::Error::from(e).context(format!("Registering tool `{}` failed", name))
+                })?;
+            }
+
+            if let Some(path) = codecs {
+                out.write_file(&registry.codec_index(), &path, OutputFormat::Pretty)?;
+            }
+            out.emit(&registry.tools(), output.as_deref(), OutputFormat::Pretty)?;
+        }
+        Commands::Corpus { action } => match action {
+            CorpusAction::Check { dir } => {
+                let cases = corpus::discover(&dir)
+                    .with_context(|| format!("Failed to read corpus: {}", dir.display()))?;
+                let mut failures = 0;
+                for case in &cases {
+                    let status = match corpus::check(case) {
+                        CaseStatus::Match => continue,
+                        CaseStatus::Mismatch { .. } => "differs from expected.json".to_string(),
+                        CaseStatus::Missing { .. } => "has no expected.json".to_string(),
+                        CaseStatus::Invalid(e) => e,
+                    };
+                    failures += 1;
+                    out.error(format_args!("{}: {}", case.name, status));
                 }
-                // Invalid combos
-                (SdkLanguage::Python, Some(BuildToolArg::Maven)) => {
+                out.set_result(&serde_json::json!({
+                    "cases": cases.len(),
+                    "failures": failures,
+                }))?;
+                if failures > 0 {
                     anyhow::bail!(
-                        "Invalid combination: --language python requires --build-tool setuptools \
-                         (maven is a Java build tool)"
+                        "{} corpus case(s) failed; bless intended changes with `json-schema-llm corpus update`",
+                        failures
                     );
                 }
-                (SdkLanguage::Java, Some(BuildToolArg::Setuptools)) => {
-                    anyhow::bail!(
-                        "Invalid combination: --language java requires --build-tool maven \
-                         (setuptools is a Python build tool)"
-                    );
+            }
+            CorpusAction::Update { dir, case } => {
+                let cases = corpus::discover(&dir)
+                    .with_context(|| format!("Failed to read corpus: {}", dir.display()))?;
+                let cases: Vec<_> = match &case {
+                    Some(name) => cases.into_iter().filter(|c| &c.name == name).collect(),
+                    None => cases,
+                };
+                if let (Some(name), true) = (&case, cases.is_empty()) {
+                    anyhow::bail!("No corpus case named '{}' in {}", name, dir.display());
                 }
-                (SdkLanguage::Java, Some(BuildToolArg::Npm))
-                | (SdkLanguage::Python, Some(BuildToolArg::Npm)) => {
-                    anyhow::bail!(
-                        "Invalid combination: --build-tool npm requires --language typescript"
-                    );
+                let mut updated = Vec::new();
+                for case in &cases {
+                    if corpus::bless(case).map_err(anyhow::Error::msg)? {
+                        out.info(format_args!("updated {}", case.name));
+                        updated.push(&case.name);
+                    }
                 }
-                (SdkLanguage::TypeScript, Some(BuildToolArg::Maven))
-                | (SdkLanguage::TypeScript, Some(BuildToolArg::Setuptools)) => {
-                    anyhow::bail!(
-                        "Invalid combination: --language typescript requires --build-tool npm"
-                    );
+                out.set_result(&serde_json::json!({ "updated": updated }))?;
+            }
+        },
+        Commands::GenConformance {
+            out: vectors_dir,
+            corpus: dir,
+        } => {
+            let cases = corpus::discover(&dir)
+                .with_context(|| format!("Failed to read corpus: {}", dir.display()))?;
+            corpus::write_vectors(&cases, &vectors_dir).map_err(anyhow::Error::msg)?;
+            out.record_file(&vectors_dir);
+            out.set_result(&serde_json::json!({ "cases": cases.len() }))?;
+            out.info(format_args!(
+                "wrote {} conformance vector(s) to {}",
+                cases.len(),
+                vectors_dir.display()
+            ));
+        }
+        Commands::Conformance {
+            schema,
+            target,
+            mode,
+            api_key,
+            model,
+            base_url,
+        } => {
+            conformance::run(
+                conformance::ConformanceArgs {
+                    schema: &schema,
+                    target: target.into(),
+                    mode: mode.into(),
+                    api_key,
+                    model,
+                    base_url,
+                },
+                out,
+            )?;
+        }
+        Commands::Completions { shell } => {
+            let mut cmd = Cli::command();
+            let name = cmd.get_name().to_string();
+            // `generate` panics on a write error, so render to memory first.
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut cmd, name, &mut script);
+            if out.is_json() {
+                out.set_result(&String::from_utf8_lossy(&script))?;
+            } else {
+                write_stdout(&script).context("Failed to write completions")?;
+            }
+        }
+        Commands::Man { out_dir } => {
+            let cmd = Cli::command();
+            match out_dir {
+                Some(dir) => {
+                    fs::create_dir_all(&dir).with_context(|| {
+                        format!("Failed to create directory: {}", dir.display())
+                    })?;
+                    clap_mangen::generate_to(cmd, &dir).with_context(|| {
+                        format!("Failed to write man pages to: {}", dir.display())
+                    })?;
+                    out.record_file(&dir);
+                    out.info(format_args!("Man pages written to: {}", dir.display()));
                 }
-                (SdkLanguage::Ruby, Some(BuildToolArg::Maven))
-                | (SdkLanguage::Ruby, Some(BuildToolArg::Setuptools))
-                | (SdkLanguage::Ruby, Some(BuildToolArg::Npm)) => {
-                    anyhow::bail!(
-                        "Invalid combination: --language ruby requires --build-tool bundler"
-                    );
+                None if out.is_json() => {
+                    let mut page = Vec::new();
+                    clap_mangen::Man::new(cmd)
+                        .render(&mut page)
+                        .context("Failed to write man page")?;
+                    out.set_result(&String::from_utf8_lossy(&page))?;
+                }
+                None => {
+                    let mut page = Vec::new();
+                    clap_mangen::Man::new(cmd)
+                        .render(&mut page)
+                        .context("Failed to write man page")?;
+                    write_stdout(&page).context("Failed to write man page")?;
+                }
+            }
+        }
+        Commands::GenSdk {
+            language,
+            all,
+            schema,
+            input,
+            target,
+            mode,
+            package,
+            output,
+            git_init,
+            build_tool,
+            template_dir,
+            layout,
+            sdk_version,
+            license,
+            authors,
+            repository,
+            reproducible,
+        } => {
+            let staged = input
+                .as_deref()
+                .map(|input| stage_schemas(input, target, mode, reproducible, out))
+                .transpose()?;
+            let schema = match &staged {
+                Some(staged) => staged.0.clone(),
+                None => schema.expect("clap enforces --schema without --input"),
+            };
+            let mut languages = if all {
+                SdkLanguage::value_variants().to_vec()
+            } else {
+                language
+            };
+            languages.sort();
+            languages.dedup();
+            for entry in &package {
+                if let Some((name, _)) = entry.split_once('=') {
+                    if SdkLanguage::from_str(name, true).is_err() {
+                        anyhow::bail!("Unknown language '{}' in --package {}", name, entry);
+                    }
                 }
-                (SdkLanguage::Java, Some(BuildToolArg::Bundler))
-                | (SdkLanguage::Python, Some(BuildToolArg::Bundler))
-                | (SdkLanguage::TypeScript, Some(BuildToolArg::Bundler)) => {
+            }
+
+            let layout = ModuleLayout::from(layout);
+            if layout != ModuleLayout::Flat {
+                let unsupported = languages
+                    .iter()
+                    .find(|l| !matches!(l, SdkLanguage::Python | SdkLanguage::TypeScript));
+                if let Some(&language) = unsupported {
                     anyhow::bail!(
-                        "Invalid combination: --build-tool bundler requires --language ruby"
+                        "--layout {} is supported for python and typescript, not {}",
+                        layout,
+                        sdk_language_name(language)
                     );
                 }
-                // Language defaults when --build-tool is omitted
-                (SdkLanguage::Java, None) => json_schema_llm_codegen::BuildTool::Maven,
-                (SdkLanguage::Python, None) => json_schema_llm_codegen::BuildTool::Setuptools,
-                (SdkLanguage::TypeScript, None) => json_schema_llm_codegen::BuildTool::Npm,
-                (SdkLanguage::Ruby, None) => json_schema_llm_codegen::BuildTool::Bundler,
-            };
+            }
 
-            let config = json_schema_llm_codegen::SdkConfig {
-                package,
-                artifact_name,
-                schema_dir: schema,
-                output_dir: output,
-                git_init,
-                build_tool: resolved_build_tool,
+            let metadata = ProjectMetadata {
+                version: sdk_version,
+                license,
+                authors,
+                repository,
             };
 
-            json_schema_llm_codegen::generate(&config).context("SDK generation failed")?;
+            if let [language] = languages[..] {
+                let config = SdkConfig {
+                    layout,
+                    metadata,
+                    ..sdk_config(
+                        language,
+                        package_for(&package, language)?,
+                        build_tool,
+                        schema,
+                        output,
+                        git_init,
+                        template_dir,
+                    )?
+                };
+                json_schema_llm_codegen::generate(&config).context("SDK generation failed")?;
+
+                out.record_file(&config.output_dir);
+                out.info(format_args!(
+                    "SDK generated successfully at: {}",
+                    config.output_dir.display()
+                ));
+                return Ok(());
+            }
+
+            if build_tool.is_some() {
+                anyhow::bail!("--build-tool applies to a single --language");
+            }
+            // Validate every SDK before writing any of them.
+            let configs = languages
+                .iter()
+                .map(|&language| {
+                    let name = sdk_language_name(language);
+                    Ok(SdkConfig {
+                        layout,
+                        metadata: metadata.clone(),
+                        ..sdk_config(
+                            language,
+                            package_for(&package, language)?,
+                            None,
+                            schema.clone(),
+                            output.join(name),
+                            false,
+                            template_dir.clone(),
+                        )?
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut sdks = Vec::new();
+            for (&language, config) in languages.iter().zip(&configs) {
+                let name = sdk_language_name(language);
+                json_schema_llm_codegen::generate(config)
+                    .with_context(|| format!("{} SDK generation failed", name))?;
+                sdks.push(WorkspaceSdk {
+                    language: name.to_string(),
+                    path: name.to_string(),
+                    package: config.package.clone(),
+                    build_tool: config.build_tool.to_string(),
+                });
+            }
+            let workspace = SdkWorkspace {
+                version: "1".to_string(),
+                generated_at: generated_at(reproducible)?,
+                schema_dir: input.as_ref().unwrap_or(&schema).display().to_string(),
+                sdks,
+            };
+            for config in &configs {
+                out.record_file(&config.output_dir);
+            }
+            out.write_file(
+                &workspace,
+                &output.join("sdk-workspace.json"),
+                OutputFormat::Pretty,
+            )?;
+            out.set_result(&workspace)?;
+
+            if git_init {
+                std::process::Command::new("git")
+                    .arg("init")
+                    .current_dir(&output)
+                    .output()
+                    .context("Failed to run git init")?;
+            }
 
-            eprintln!(
-                "SDK generated successfully at: {}",
-                config.output_dir.display()
-            );
+            out.info(format_args!(
+                "{} SDKs generated successfully at: {}",
+                configs.len(),
+                output.display()
+            ));
         }
     }
 
@@ -550,26 +2088,541 @@ fn main() -> Result<()> {
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Validate a gen-sdk request for one language and resolve its build tool
+/// and artifact name.
+/// Converted schemas staged for `gen-sdk --input`, removed once the SDK is
+/// generated (the generators copy what they need).
+struct StagedSchemas(PathBuf);
+
+impl Drop for StagedSchemas {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Convert `input` into a temporary `convert --output-dir` layout.
+fn stage_schemas(
+    input: &Path,
+    target: Option<TargetArg>,
+    mode: Option<ModeArg>,
+    reproducible: bool,
+    out: &mut Output,
+) -> Result<StagedSchemas> {
+    let schema = read_schema(input)?;
+    let mut options = ConvertOptions::default();
+    if let Some(target) = target {
+        options.target = target.into();
+    }
+    if let Some(mode) = mode {
+        options.mode = mode.into();
+    }
+    let staged = StagedSchemas(
+        std::env::temp_dir().join(format!("json-schema-llm-gen-sdk-{}", std::process::id())),
+    );
+    // The staged files are scratch; only diagnostics reach the output.
+    let mut scratch = Output::new(out.is_json());
+    let settings = OutputDirSettings {
+        format: OutputFormat::Pretty,
+        self_check: false,
+        reproducible,
+        resume: false,
+        jobs: 1,
+    };
+    handle_output_dir(&schema, input, &staged.0, &options, settings, &mut scratch)?;
+    out.merge_diagnostics(scratch);
+    Ok(staged)
+}
+
+fn sdk_config(
+    language: SdkLanguage,
+    package: String,
+    build_tool: Option<BuildToolArg>,
+    schema: PathBuf,
+    output: PathBuf,
+    git_init: bool,
+    template_dir: Option<PathBuf>,
+) -> Result<SdkConfig> {
+    // Language-aware package name validation
+    match language {
+        SdkLanguage::Java | SdkLanguage::Kotlin => {
+            if !package
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '.' || c == '_')
+            {
+                anyhow::bail!(
+                    "Invalid Java package name '{}': must contain only alphanumeric, dot, and underscore",
+                    package
+                );
+            }
+        }
+        SdkLanguage::Python => {
+            // PEP 508: ^[a-zA-Z0-9]([a-zA-Z0-9._-]*[a-zA-Z0-9])?$
+            let valid = !package.is_empty()
+                && package.starts_with(|c: char| c.is_ascii_alphanumeric())
+                && package.ends_with(|c: char| c.is_ascii_alphanumeric())
+                && package
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+            if !valid {
+                anyhow::bail!(
+                    "Invalid Python package name '{}': must start and end with alphanumeric, \
+                     contain only alphanumeric, hyphen, underscore, and dot (PEP 508)",
+                    package
+                );
+            }
+        }
+        SdkLanguage::TypeScript => {
+            // npm package names: lowercase, may be scoped (@scope/name)
+            let valid = !package.is_empty()
+                && package
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_@/.".contains(c));
+            if !valid {
+                anyhow::bail!(
+                    "Invalid npm package name '{}': must contain only lowercase alphanumeric, \
+                     hyphen, underscore, @, /, and dot",
+                    package
+                );
+            }
+        }
+        SdkLanguage::Rust => {
+            // Cargo package names: alphanumeric, hyphen, underscore; no leading digit
+            let valid = !package.is_empty()
+                && package.starts_with(|c: char| c.is_ascii_alphabetic())
+                && package
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                anyhow::bail!(
+                    "Invalid Cargo package name '{}': must start with a letter, \
+                     contain only alphanumeric, hyphen, and underscore",
+                    package
+                );
+            }
+        }
+        SdkLanguage::Swift => {
+            // SwiftPM package names: alphanumeric, hyphen, underscore; no leading digit
+            let valid = !package.is_empty()
+                && package.starts_with(|c: char| c.is_ascii_alphabetic())
+                && package
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                anyhow::bail!(
+                    "Invalid Swift package name '{}': must start with a letter, \
+                     contain only alphanumeric, hyphen, and underscore",
+                    package
+                );
+            }
+        }
+        SdkLanguage::Ruby => {
+            // Ruby gem names: lowercase, start with letter, alphanumeric + hyphen + underscore
+            let valid = !package.is_empty()
+                && package.starts_with(|c: char| c.is_ascii_lowercase())
+                && package
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+            if !valid {
+                anyhow::bail!(
+                    "Invalid Ruby gem name '{}': must start with a lowercase letter, \
+                     contain only lowercase alphanumeric, hyphen, and underscore",
+                    package
+                );
+            }
+        }
+    }
+
+    // Derive artifact name from package
+    let artifact_name = match language {
+        SdkLanguage::Java | SdkLanguage::Kotlin => package
+            .trim_end_matches('.')
+            .rsplit('.')
+            .next()
+            .unwrap()
+            .to_string(),
+        SdkLanguage::Python => package.clone(),
+        SdkLanguage::TypeScript => {
+            // For scoped packages like @scope/name, use "name" as artifact
+            package.rsplit('/').next().unwrap_or(&package).to_string()
+        }
+        SdkLanguage::Ruby => package.clone(),
+        SdkLanguage::Rust => package.clone(),
+        SdkLanguage::Swift => package.clone(),
+    };
+
+    // Resolve build tool: validate combo, then apply language default if omitted
+    let resolved_build_tool = match (language, build_tool) {
+        // Explicit and valid combos
+        (SdkLanguage::Java, Some(BuildToolArg::Maven)) => json_schema_llm_codegen::BuildTool::Maven,
+        (SdkLanguage::Python, Some(BuildToolArg::Setuptools)) => {
+            json_schema_llm_codegen::BuildTool::Setuptools
+        }
+        (SdkLanguage::TypeScript, Some(BuildToolArg::Npm)) => {
+            json_schema_llm_codegen::BuildTool::Npm
+        }
+        (SdkLanguage::Ruby, Some(BuildToolArg::Bundler)) => {
+            json_schema_llm_codegen::BuildTool::Bundler
+        }
+        (SdkLanguage::Rust, Some(BuildToolArg::Cargo)) => json_schema_llm_codegen::BuildTool::Cargo,
+        // Invalid combos
+        (SdkLanguage::Python, Some(BuildToolArg::Maven)) => {
+            anyhow::bail!(
+                "Invalid combination: --language python requires --build-tool setuptools \
+                 (maven is a Java build tool)"
+            );
+        }
+        (SdkLanguage::Java, Some(BuildToolArg::Setuptools)) => {
+            anyhow::bail!(
+                "Invalid combination: --language java requires --build-tool maven \
+                 (setuptools is a Python build tool)"
+            );
+        }
+        (SdkLanguage::Java, Some(BuildToolArg::Npm))
+        | (SdkLanguage::Python, Some(BuildToolArg::Npm)) => {
+            anyhow::bail!("Invalid combination: --build-tool npm requires --language typescript");
+        }
+        (SdkLanguage::TypeScript, Some(BuildToolArg::Maven))
+        | (SdkLanguage::TypeScript, Some(BuildToolArg::Setuptools)) => {
+            anyhow::bail!("Invalid combination: --language typescript requires --build-tool npm");
+        }
+        (SdkLanguage::Ruby, Some(BuildToolArg::Maven))
+        | (SdkLanguage::Ruby, Some(BuildToolArg::Setuptools))
+        | (SdkLanguage::Ruby, Some(BuildToolArg::Npm)) => {
+            anyhow::bail!("Invalid combination: --language ruby requires --build-tool bundler");
+        }
+        (SdkLanguage::Java, Some(BuildToolArg::Bundler))
+        | (SdkLanguage::Python, Some(BuildToolArg::Bundler))
+        | (SdkLanguage::TypeScript, Some(BuildToolArg::Bundler)) => {
+            anyhow::bail!("Invalid combination: --build-tool bundler requires --language ruby");
+        }
+        (SdkLanguage::Kotlin, Some(BuildToolArg::Gradle)) => {
+            json_schema_llm_codegen::BuildTool::Gradle
+        }
+        (SdkLanguage::Kotlin, Some(_)) => {
+            anyhow::bail!("Invalid combination: --language kotlin requires --build-tool gradle");
+        }
+        (_, Some(BuildToolArg::Gradle)) => {
+            anyhow::bail!("Invalid combination: --build-tool gradle requires --language kotlin");
+        }
+        (SdkLanguage::Rust, Some(_)) => {
+            anyhow::bail!("Invalid combination: --language rust requires --build-tool cargo");
+        }
+        (_, Some(BuildToolArg::Cargo)) => {
+            anyhow::bail!("Invalid combination: --build-tool cargo requires --language rust");
+        }
+        (SdkLanguage::Swift, Some(BuildToolArg::Swiftpm)) => {
+            json_schema_llm_codegen::BuildTool::Swiftpm
+        }
+        (SdkLanguage::Swift, Some(_)) => {
+            anyhow::bail!("Invalid combination: --language swift requires --build-tool swiftpm");
+        }
+        (_, Some(BuildToolArg::Swiftpm)) => {
+            anyhow::bail!("Invalid combination: --build-tool swiftpm requires --language swift");
+        }
+        // Language defaults when --build-tool is omitted
+        (SdkLanguage::Java, None) => json_schema_llm_codegen::BuildTool::Maven,
+        (SdkLanguage::Python, None) => json_schema_llm_codegen::BuildTool::Setuptools,
+        (SdkLanguage::TypeScript, None) => json_schema_llm_codegen::BuildTool::Npm,
+        (SdkLanguage::Ruby, None) => json_schema_llm_codegen::BuildTool::Bundler,
+        (SdkLanguage::Rust, None) => json_schema_llm_codegen::BuildTool::Cargo,
+        (SdkLanguage::Kotlin, None) => json_schema_llm_codegen::BuildTool::Gradle,
+        (SdkLanguage::Swift, None) => json_schema_llm_codegen::BuildTool::Swiftpm,
+    };
+
+    Ok(SdkConfig {
+        package,
+        artifact_name,
+        schema_dir: schema,
+        output_dir: output,
+        git_init,
+        build_tool: resolved_build_tool,
+        template_dir,
+        layout: ModuleLayout::Flat,
+        metadata: ProjectMetadata::default(),
+    })
+}
+
+/// The `--language` value naming `language`.
+fn sdk_language_name(language: SdkLanguage) -> &'static str {
+    match language {
+        SdkLanguage::Java => "java",
+        SdkLanguage::Python => "python",
+        SdkLanguage::TypeScript => "typescript",
+        SdkLanguage::Ruby => "ruby",
+        SdkLanguage::Rust => "rust",
+        SdkLanguage::Kotlin => "kotlin",
+        SdkLanguage::Swift => "swift",
+    }
+}
+
+/// Pick the package for `language` from `--package` values: a
+/// `<language>=<name>` entry wins over a bare name.
+fn package_for(packages: &[String], language: SdkLanguage) -> Result<String> {
+    let name = sdk_language_name(language);
+    packages
+        .iter()
+        .find_map(|entry| entry.strip_prefix(name)?.strip_prefix('='))
+        .or_else(|| {
+            packages
+                .iter()
+                .find(|entry| !entry.contains('='))
+                .map(String::as_str)
+        })
+        .map(str::to_string)
+        .with_context(|| format!("No --package for {name}; pass --package {name}=<name>"))
+}
+
 /// Read and parse a JSON Schema from a file path.
 fn read_schema(input: &Path) -> Result<Value> {
     let file = File::open(input)
         .with_context(|| format!("Failed to open input file: {}", input.display()))?;
-    let reader = BufReader::new(file);
-    serde_json::from_reader(reader)
+    parse_value(BufReader::new(file), false)
         .with_context(|| format!("Failed to parse schema from: {}", input.display()))
 }
 
+/// Write `bytes` to stdout. A reader that closed the pipe early (`| head`)
+/// has all it wants, so that is not an error.
+fn write_stdout(bytes: &[u8]) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    match stdout.write_all(bytes).and_then(|()| stdout.flush()) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// Fail `--preserve-precision` in a build that cannot honor it.
+fn check_preserve_precision(preserve_precision: bool) -> Result<()> {
+    if preserve_precision && !cfg!(feature = "arbitrary-precision") {
+        anyhow::bail!("--preserve-precision needs a build with the arbitrary-precision feature");
+    }
+    Ok(())
+}
+
+/// Report provider compat diagnostics, noting how many were acknowledged
+/// via `--suppress`.
+fn report_compat_diagnostics(result: &ConvertResult, out: &mut Output) {
+    if !result.provider_compat_errors.is_empty() {
+        if !out.is_json() {
+            eprintln!("Provider compatibility diagnostics:");
+        }
+        for err in &result.provider_compat_errors {
+            out.finding(err.rule_id(), err);
+        }
+    }
+    if !result.suppressed_compat_errors.is_empty() {
+        out.info(format_args!(
+            "{} diagnostic(s) suppressed.",
+            result.suppressed_compat_errors.len()
+        ));
+    }
+    if !result.constraint_hints.is_empty() {
+        out.info(format_args!(
+            "{} description(s) carry constraints the target does not enforce.",
+            result.constraint_hints.len()
+        ));
+    }
+    report_recursion_cycles(&result.recursion_cycles, out);
+}
+
+/// Summarize broken recursion cycles, one line per distinct cycle with how
+/// often it was cut, so the hottest ones stand out.
+fn report_recursion_cycles(cycles: &[CycleInfo], out: &mut Output) {
+    let mut distinct: Vec<(&CycleInfo, usize)> = Vec::new();
+    for cycle in cycles {
+        match distinct
+            .iter_mut()
+            .find(|(seen, _)| seen.refs == cycle.refs)
+        {
+            Some((_, count)) => *count += 1,
+            None => distinct.push((cycle, 1)),
+        }
+    }
+    for (cycle, count) in distinct {
+        let strategy = serde_json::to_value(cycle.strategy).unwrap_or_default();
+        out.info(format_args!(
+            "Recursion cycle {} -> {} cut {} time(s), first at {} ({} placeholder)",
+            cycle.refs.join(" -> "),
+            cycle.refs[0],
+            count,
+            cycle.break_path,
+            strategy.as_str().unwrap_or_default()
+        ));
+    }
+}
+
+/// `list-components` table: one row per component, pointer first.
+fn print_component_table(components: &[ComponentInfo]) {
+    if components.is_empty() {
+        return;
+    }
+    let width = components
+        .iter()
+        .map(|c| c.pointer.len())
+        .max()
+        .unwrap_or(0)
+        .max("POINTER".len());
+    println!(
+        "{:<width$}  {:>5}  {:>5}  {:<9}  {:>3}  {:>3}  TITLE",
+        "POINTER", "PROPS", "DEPTH", "RECURSIVE", "IN", "OUT"
+    );
+    for c in components {
+        println!(
+            "{:<width$}  {:>5}  {:>5}  {:<9}  {:>3}  {:>3}  {}",
+            c.pointer,
+            c.property_count,
+            c.depth,
+            if c.recursive { "yes" } else { "no" },
+            c.refs_in.len(),
+            c.refs_out.len(),
+            c.title.as_deref().unwrap_or("")
+        );
+    }
+}
+
+/// Keywords the self-check does not hold its sample to: value patterns the
+/// sampler cannot synthesize, and cross-property or exclusivity rules that a
+/// converted schema cannot express either (so a model is not held to them).
+const UNSAMPLED_KEYWORDS: &[&str] = &[
+    "pattern",
+    "format",
+    "propertyNames",
+    "uniqueItems",
+    "contains",
+    "not",
+    "oneOf",
+    "if",
+    "then",
+    "else",
+    "dependentRequired",
+    "dependentSchemas",
+    "unevaluatedProperties",
+];
+
+/// `convert --self-check`: rehydrate a synthetic answer to the converted
+/// schema and validate it against the original schema.
+fn run_self_check(original: &Value, result: &ConvertResult, out: &mut Output) -> Result<()> {
+    let answer = sample::sample_converted(&result.schema, &result.codec, original);
+    let rehydrated = rehydrate(&answer, &result.codec, original).map_err(|e| {
+        anyhow::Error::from(e).context("Self-check failed: the sample answer did not rehydrate")
+    })?;
+    let validator = jsonschema::validator_for(original).map_err(|e| {
+        anyhow::anyhow!(
+            "Self-check failed: cannot validate against the original schema: {}",
+            e
+        )
+    })?;
+    let violations: Vec<String> = validator
+        .iter_errors(&rehydrated.data)
+        .filter(|e| {
+            let keyword = e.schema_path.as_str().rsplit('/').next().unwrap_or("");
+            !UNSAMPLED_KEYWORDS.contains(&keyword)
+        })
+        .map(|e| format!("#{}: {}", e.instance_path, e))
+        .collect();
+    if !violations.is_empty() {
+        anyhow::bail!(
+            "Self-check failed: the rehydrated sample does not match the original schema:\n  {}",
+            violations.join("\n  ")
+        );
+    }
+    out.info(
+        "Self-check passed: a sample answer rehydrates to a valid instance of the original schema.",
+    );
+    Ok(())
+}
+
+/// The `convert` progress bar. Its stderr draw target stays hidden when
+/// stderr is not a terminal, so redirected output and CI logs stay clean.
+static PROGRESS_BAR: OnceLock<ProgressBar> = OnceLock::new();
+
+/// Progress sink for `convert`, drawing [`PROGRESS_BAR`].
+fn progress_sink() -> ProgressSink {
+    let bar = PROGRESS_BAR
+        .get_or_init(|| ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr()))
+        .clone();
+    ProgressSink::new(move |progress| {
+        let (template, position, length, message) = match progress {
+            Progress::Pass { pass, index, total } => {
+                let name = serde_json::to_value(pass)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                ("Converting: pass {pos}/{len} ({msg})", index, total, name)
+            }
+            Progress::Components { done, total } => (
+                "Components: [{bar:30}] {pos}/{len}",
+                done,
+                total,
+                String::new(),
+            ),
+            _ => return,
+        };
+        // A phase cleared by `clear_progress` starts a fresh line.
+        if bar.is_finished() {
+            bar.reset();
+        }
+        let style = ProgressStyle::with_template(template)
+            .expect("progress templates are valid")
+            .progress_chars("#-");
+        bar.set_style(style);
+        bar.set_length(length as u64);
+        bar.set_position(position as u64);
+        bar.set_message(message);
+    })
+}
+
+/// Erase the progress bar, if one was drawn.
+fn clear_progress() {
+    if let Some(bar) = PROGRESS_BAR.get() {
+        bar.finish_and_clear();
+    }
+}
+
+/// `convert --output-dir` flags that shape how the directory is written.
+#[derive(Clone, Copy)]
+struct OutputDirSettings {
+    format: OutputFormat,
+    self_check: bool,
+    reproducible: bool,
+    resume: bool,
+    /// Components converted at a time.
+    jobs: usize,
+}
+
 /// Handle `--output-dir` mode: convert all components and write to directory.
 fn handle_output_dir(
     schema: &Value,
     input_path: &Path,
     output_dir: &Path,
     options: &ConvertOptions,
-    format: OutputFormat,
+    settings: OutputDirSettings,
+    out: &mut Output,
 ) -> Result<()> {
+    let OutputDirSettings {
+        format,
+        self_check,
+        reproducible,
+        resume,
+        jobs,
+    } = settings;
     let extract_opts = ExtractOptions::default();
-    let result = convert_all_components(schema, options, &extract_opts)
-        .map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+    let unchanged = if resume {
+        unchanged_components(schema, output_dir, options, out)
+    } else {
+        std::collections::BTreeMap::new()
+    };
+    // Components are converted as they are written, so only one is held at
+    // a time.
+    let stream = convert_all_components_streaming(schema, options, &extract_opts);
+    clear_progress();
+    let stream = stream.map_err(|e| anyhow::Error::from(e).context("Conversion failed"))?;
+    let mut stream = stream
+        .skip_where(|pointer| unchanged.contains_key(pointer))
+        .jobs(jobs);
+    let full = &stream.full;
+    if self_check {
+        run_self_check(schema, full, out)?;
+    }
 
     // Create output directory
     fs::create_dir_all(output_dir).with_context(|| {
@@ -580,29 +2633,37 @@ fn handle_output_dir(
     })?;
 
     // Write full schema and codec at root
-    write_json(
-        &result.full.schema,
-        Some(&output_dir.join("schema.json")),
-        format,
-    )?;
-    write_json(
-        &result.full.codec,
-        Some(&output_dir.join("codec.json")),
-        format,
-    )?;
+    out.write_file(&full.schema, &output_dir.join("schema.json"), format)?;
+    out.write_file(&full.codec, &output_dir.join("codec.json"), format)?;
+    if let Some(envelope) = &full.envelope {
+        out.write_file(envelope, &output_dir.join("envelope.json"), format)?;
+    }
 
     // Report provider compat diagnostics
-    if !result.full.provider_compat_errors.is_empty() {
-        eprintln!("Provider compatibility diagnostics:");
-        for err in &result.full.provider_compat_errors {
-            eprintln!("- {}", err);
-        }
-    }
+    report_compat_diagnostics(full, out);
+
+    // Direct `$ref` links between components, for grouping SDK modules
+    let refs_out: std::collections::HashMap<String, Vec<String>> = describe_components(schema)
+        .map(|infos| infos.into_iter().map(|i| (i.pointer, i.refs_out)).collect())
+        .unwrap_or_default();
 
     // Write per-component files
     let mut manifest_components: Vec<ManifestComponent> = Vec::new();
+    let mut component_errors: Vec<(String, String)> = Vec::new();
 
-    for (pointer, conv_result) in &result.components {
+    for (pointer, conv_result) in &mut stream {
+        let conv_result = match conv_result {
+            Err(e @ ConvertError::Cancelled { .. }) => {
+                clear_progress();
+                return Err(anyhow::Error::from(e).context("Conversion failed"));
+            }
+            Err(e) => {
+                component_errors.push((pointer, e.to_string()));
+                continue;
+            }
+            Ok(conv_result) => conv_result,
+        };
+        let pointer = &pointer;
         let rel_dir = pointer_to_dir_path(pointer);
         let comp_dir = output_dir.join(&rel_dir);
         fs::create_dir_all(&comp_dir).with_context(|| {
@@ -612,16 +2673,11 @@ fn handle_output_dir(
             )
         })?;
 
-        write_json(
-            &conv_result.schema,
-            Some(&comp_dir.join("schema.json")),
-            format,
-        )?;
-        write_json(
-            &conv_result.codec,
-            Some(&comp_dir.join("codec.json")),
-            format,
-        )?;
+        out.write_file(&conv_result.schema, &comp_dir.join("schema.json"), format)?;
+        out.write_file(&conv_result.codec, &comp_dir.join("codec.json"), format)?;
+        if let Some(envelope) = &conv_result.envelope {
+            out.write_file(envelope, &comp_dir.join("envelope.json"), format)?;
+        }
 
         // Get dependency count and original extracted schema
         let extract_result = extract_component(schema, pointer, &extract_opts);
@@ -633,12 +2689,12 @@ fn handle_output_dir(
         // Write original.json (the extracted, self-contained sub-schema
         // before LLM conversion — used for validation in engines)
         if let Ok(ref result) = extract_result {
-            write_json(
-                &result.schema,
-                Some(&comp_dir.join("original.json")),
-                format,
-            )?;
+            out.write_file(&result.schema, &comp_dir.join("original.json"), format)?;
         }
+        let fingerprint = extract_result
+            .as_ref()
+            .ok()
+            .map(|r| schema_fingerprint(&r.schema, options));
 
         let name = pointer
             .rsplit('/')
@@ -654,12 +2710,26 @@ fn handle_output_dir(
             codec_path: format!("{}/codec.json", rel_dir),
             original_path: format!("{}/original.json", rel_dir),
             dependency_count: dep_count,
+            fingerprint,
+            refs_out: refs_out.get(pointer).cloned().unwrap_or_default(),
         });
     }
 
-    // Write component errors to stderr
-    for (pointer, error) in &result.component_errors {
-        eprintln!("Component error ({}): {}", pointer, error);
+    clear_progress();
+
+    if resume {
+        out.info(format_args!(
+            "Resumed: {} component(s) unchanged, {} converted",
+            unchanged.len(),
+            manifest_components.len()
+        ));
+        manifest_components.extend(unchanged.into_values());
+        manifest_components.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+    }
+
+    // Report component errors
+    for (pointer, error) in &component_errors {
+        out.error(format_args!("Component error ({}): {}", pointer, error));
     }
 
     // Derive target/mode strings for manifest
@@ -677,30 +2747,91 @@ fn handle_output_dir(
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| "unknown".to_string());
 
-    let generated_at = if std::env::var("JSON_SCHEMA_LLM_DETERMINISTIC").is_ok() {
-        "2026-01-01T00:00:00Z".to_string()
-    } else {
-        Utc::now().to_rfc3339()
-    };
-
     let manifest = Manifest {
         version: "1".to_string(),
-        generated_at,
+        generated_at: generated_at(reproducible)?,
         source_schema: source_name,
+        fingerprint: schema_fingerprint(schema, options),
         target: target_str,
         mode: mode_str,
         components: manifest_components,
+        component_errors: component_errors
+            .into_iter()
+            .map(|(pointer, message)| ManifestComponentError { pointer, message })
+            .collect(),
     };
 
-    write_json(
+    out.write_file(
         &manifest,
-        Some(&output_dir.join("manifest.json")),
+        &output_dir.join("manifest.json"),
         OutputFormat::Pretty,
     )?;
 
     Ok(())
 }
 
+/// Entries of the manifest already in `output_dir` that `--resume` can keep:
+/// components still in `schema` whose extracted source fingerprints the same
+/// with `options` and whose files are all in place, keyed by pointer. An
+/// entry whose file paths are absolute or climb out of `output_dir` is
+/// treated as changed.
+fn unchanged_components(
+    schema: &Value,
+    output_dir: &Path,
+    options: &ConvertOptions,
+    out: &mut Output,
+) -> std::collections::BTreeMap<String, ManifestComponent> {
+    let path = output_dir.join("manifest.json");
+    let Ok(text) = fs::read_to_string(&path) else {
+        return std::collections::BTreeMap::new();
+    };
+    let previous: Manifest = match serde_json::from_str(&text) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            out.warn(
+                None,
+                format_args!(
+                    "Ignoring unreadable {} ({}); converting every component",
+                    path.display(),
+                    e
+                ),
+            );
+            return std::collections::BTreeMap::new();
+        }
+    };
+    let extract_opts = ExtractOptions::default();
+    previous
+        .components
+        .into_iter()
+        .filter(|component| {
+            let files_present = [
+                &component.schema_path,
+                &component.codec_path,
+                &component.original_path,
+            ]
+            .iter()
+            .all(|file| is_relative_within(file) && output_dir.join(file).is_file());
+            files_present
+                && component.fingerprint.as_ref().is_some_and(|fingerprint| {
+                    extract_component(schema, &component.pointer, &extract_opts)
+                        .is_ok_and(|r| schema_fingerprint(&r.schema, options) == *fingerprint)
+                })
+        })
+        .map(|component| (component.pointer.clone(), component))
+        .collect()
+}
+
+/// Whether `file`, a path read from a manifest, stays inside the directory
+/// it is joined to: relative, with no `..` components.
+fn is_relative_within(file: &str) -> bool {
+    Path::new(file).components().all(|c| {
+        matches!(
+            c,
+            std::path::Component::Normal(_) | std::path::Component::CurDir
+        )
+    })
+}
+
 /// Convert a JSON Pointer to a relative directory path.
 ///
 /// Strips the leading `#/` and uses the remaining segments as directory hierarchy.
@@ -718,30 +2849,30 @@ fn pointer_to_dir_path(pointer: &str) -> String {
         .join("/")
 }
 
-fn write_json<T: serde::Serialize>(
-    val: &T,
-    path: Option<&PathBuf>,
-    format: OutputFormat,
-) -> Result<()> {
-    let mut writer: Box<dyn Write> = if let Some(p) = path {
-        let file = File::create(p)
-            .with_context(|| format!("Failed to create output file: {}", p.display()))?;
-        Box::new(BufWriter::new(file))
+/// Timestamp for generated metadata. `SOURCE_DATE_EPOCH` wins when set;
+/// otherwise `--reproducible` leaves it out, and `JSON_SCHEMA_LLM_DETERMINISTIC`
+/// fixes it.
+fn generated_at(reproducible: bool) -> Result<Option<String>> {
+    if let Ok(epoch) = std::env::var("SOURCE_DATE_EPOCH") {
+        let time = epoch
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .with_context(|| {
+                format!(
+                    "SOURCE_DATE_EPOCH must be a Unix timestamp, got '{}'",
+                    epoch
+                )
+            })?;
+        Ok(Some(
+            time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        ))
+    } else if reproducible {
+        Ok(None)
+    } else if std::env::var("JSON_SCHEMA_LLM_DETERMINISTIC").is_ok() {
+        Ok(Some("2026-01-01T00:00:00Z".to_string()))
     } else {
-        Box::new(BufWriter::new(io::stdout()))
-    };
-
-    match format {
-        OutputFormat::Pretty => {
-            serde_json::to_writer_pretty(&mut writer, val).context("Failed to write JSON")?;
-        }
-        OutputFormat::Compact => {
-            serde_json::to_writer(&mut writer, val).context("Failed to write JSON")?;
-        }
+        Ok(Some(Utc::now().to_rfc3339()))
     }
-
-    // Ensure trailing newline
-    writeln!(writer).context("Failed to write trailing newline")?;
-
-    Ok(())
 }
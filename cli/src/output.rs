@@ -0,0 +1,255 @@
+//! Command output: human text by default, or with the global `--json` flag a
+//! single result envelope on stdout.
+//!
+//! The envelope carries the command's result (what it would otherwise print
+//! to stdout), the files it wrote, and its diagnostics, so tools wrapping the
+//! CLI never have to scrape stderr:
+//!
+//! ```json
+//! {
+//!   "command": "convert",
+//!   "ok": true,
+//!   "result": { "type": "object" },
+//!   "files": ["codec.json"],
+//!   "diagnostics": [{ "level": "warning", "rule": "JSL-CD-001", "message": "..." }]
+//! }
+//! ```
+//!
+//! A failed command has `"ok": false` and an `error` with the message, its
+//! causes and, for library errors, the stable error `code`.
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use json_schema_llm_core::{ConvertError, ErrorCode};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::OutputFormat;
+
+/// Collects a command's output for the JSON envelope, or passes it straight
+/// through to stdout/stderr in text mode.
+pub struct Output {
+    json: bool,
+    result: Option<Value>,
+    files: Vec<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Serialize)]
+struct Diagnostic {
+    level: Level,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule: Option<String>,
+    /// JSON Pointer into the data the diagnostic is about.
+    #[serde(rename = "dataPath", skip_serializing_if = "Option::is_none")]
+    data_path: Option<String>,
+    message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope<'a> {
+    command: &'a str,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    files: Vec<String>,
+    diagnostics: Vec<Diagnostic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorBody>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorBody {
+    message: String,
+    causes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<ErrorCode>,
+}
+
+impl Output {
+    pub fn new(json: bool) -> Self {
+        Self {
+            json,
+            result: None,
+            files: Vec::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Whether output is going into a JSON envelope.
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+
+    /// Status line on stderr, or an `info` diagnostic.
+    pub fn info(&mut self, message: impl Display) {
+        self.diagnostic(Level::Info, None, None, message.to_string(), |m| {
+            m.to_string()
+        });
+    }
+
+    /// `Warning: ...` on stderr, or a `warning` diagnostic.
+    pub fn warn(&mut self, rule: Option<&str>, message: impl Display) {
+        self.diagnostic(Level::Warning, rule, None, message.to_string(), |m| {
+            format!("Warning: {}", m)
+        });
+    }
+
+    /// [`Self::warn`] about the data at `data_path`, which the diagnostic
+    /// carries as `dataPath`.
+    pub fn warn_at(&mut self, rule: &str, data_path: &str, message: impl Display) {
+        self.diagnostic(
+            Level::Warning,
+            Some(rule),
+            Some(data_path),
+            message.to_string(),
+            |m| format!("Warning: {}", m),
+        );
+    }
+
+    /// Rule finding as a `- [RULE] message` line on stderr, or a `warning`
+    /// diagnostic.
+    pub fn finding(&mut self, rule: &str, message: impl Display) {
+        self.diagnostic(Level::Warning, Some(rule), None, message.to_string(), |m| {
+            format!("- [{}] {}", rule, m)
+        });
+    }
+
+    /// Error line on stderr for a failure that does not stop the command,
+    /// or an `error` diagnostic.
+    pub fn error(&mut self, message: impl Display) {
+        self.diagnostic(Level::Error, None, None, message.to_string(), |m| {
+            m.to_string()
+        });
+    }
+
+    /// Set the envelope's `result`. Text mode ignores it.
+    pub fn set_result<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        if self.json {
+            self.result = Some(serde_json::to_value(value).context("Failed to encode result")?);
+        }
+        Ok(())
+    }
+
+    /// Note a file or directory the command wrote.
+    pub fn record_file(&mut self, path: &Path) {
+        self.files.push(path.display().to_string());
+    }
+
+    /// Take over the diagnostics `other` collected, e.g. from an
+    /// intermediate step whose files are not part of this command's output.
+    pub fn merge_diagnostics(&mut self, other: Output) {
+        self.diagnostics.extend(other.diagnostics);
+    }
+
+    /// Write `value` to `path`, or to stdout when there is none (the
+    /// envelope's `result` in JSON mode).
+    pub fn emit<T: Serialize>(
+        &mut self,
+        value: &T,
+        path: Option<&Path>,
+        format: OutputFormat,
+    ) -> Result<()> {
+        match path {
+            Some(path) => self.write_file(value, path, format),
+            None if self.json => self.set_result(value),
+            None => write_json(value, &mut BufWriter::new(io::stdout()), format),
+        }
+    }
+
+    /// Write `value` as JSON to `path` and record it.
+    pub fn write_file<T: Serialize>(
+        &mut self,
+        value: &T,
+        path: &Path,
+        format: OutputFormat,
+    ) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+        write_json(value, &mut BufWriter::new(file), format)?;
+        self.record_file(path);
+        Ok(())
+    }
+
+    /// Finish the command. In JSON mode, print the envelope for `outcome`
+    /// and report only whether it succeeded; in text mode, hand `outcome`
+    /// back unchanged.
+    pub fn finish(self, command: &str, outcome: Result<()>) -> Result<bool> {
+        if !self.json {
+            return outcome.map(|()| true);
+        }
+        let ok = outcome.is_ok();
+        let envelope = Envelope {
+            command,
+            ok,
+            result: self.result,
+            files: self.files,
+            diagnostics: self.diagnostics,
+            error: outcome.err().map(|err| ErrorBody {
+                message: err.to_string(),
+                causes: err.chain().skip(1).map(ToString::to_string).collect(),
+                code: err
+                    .chain()
+                    .find_map(|e| e.downcast_ref::<ConvertError>())
+                    .map(ConvertError::error_code),
+            }),
+        };
+        write_json(
+            &envelope,
+            &mut BufWriter::new(io::stdout()),
+            OutputFormat::Pretty,
+        )?;
+        Ok(ok)
+    }
+
+    fn diagnostic(
+        &mut self,
+        level: Level,
+        rule: Option<&str>,
+        data_path: Option<&str>,
+        message: String,
+        text: impl FnOnce(&str) -> String,
+    ) {
+        if self.json {
+            self.diagnostics.push(Diagnostic {
+                level,
+                rule: rule.map(String::from),
+                data_path: data_path.map(String::from),
+                message,
+            });
+        } else {
+            eprintln!("{}", text(&message));
+        }
+    }
+}
+
+fn write_json<T: Serialize>(val: &T, writer: &mut impl Write, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Pretty => {
+            serde_json::to_writer_pretty(&mut *writer, val).context("Failed to write JSON")?;
+        }
+        OutputFormat::Compact => {
+            serde_json::to_writer(&mut *writer, val).context("Failed to write JSON")?;
+        }
+    }
+
+    // Ensure trailing newline
+    writeln!(writer).context("Failed to write trailing newline")?;
+    writer.flush().context("Failed to write JSON")?;
+
+    Ok(())
+}
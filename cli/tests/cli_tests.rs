@@ -65,262 +65,1563 @@ fn test_convert_to_stdout() {
         .stdout(predicate::str::contains("\"type\""));
 }
 
-// ── Rehydrate ───────────────────────────────────────────────────────────────
-
 #[test]
-fn test_rehydrate() {
+fn test_convert_provider_max_depth() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
-    let converted = dir.path().join("converted.json");
-    let codec_file = dir.path().join("codec.json");
-    let llm_output = dir.path().join("llm_output.json");
-    let rehydrated = dir.path().join("rehydrated.json");
-
-    // Step 1: Convert
-    fs::write(&input, simple_schema()).unwrap();
-    cmd()
-        .args(["convert", input.to_str().unwrap()])
-        .args(["-o", converted.to_str().unwrap()])
-        .args(["--codec", codec_file.to_str().unwrap()])
-        .assert()
-        .success();
-
-    // Step 2: Create fake LLM output matching the converted schema
-    let llm_data = serde_json::json!({
-        "name": "Alice",
-        "age": 30
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "outer": {
+                "type": "object",
+                "properties": { "inner": { "type": "string" } },
+                "required": ["inner"]
+            }
+        },
+        "required": ["outer"]
     });
-    fs::write(&llm_output, llm_data.to_string()).unwrap();
-
-    // Step 3: Rehydrate (--schema is required for type coercion)
-    cmd()
-        .args(["rehydrate", llm_output.to_str().unwrap()])
-        .args(["--codec", codec_file.to_str().unwrap()])
-        .args(["--schema", input.to_str().unwrap()])
-        .args(["-o", rehydrated.to_str().unwrap()])
-        .assert()
-        .success();
+    fs::write(&input, schema.to_string()).unwrap();
 
-    let content = fs::read_to_string(&rehydrated).expect("rehydrated file should exist");
-    let data: serde_json::Value =
-        serde_json::from_str(&content).expect("rehydrated output should be valid JSON");
-    assert_eq!(data["name"], serde_json::json!("Alice"));
+    let out = cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--provider-max-depth", "1"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let converted: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(converted["properties"]["outer"]["type"], "string");
 }
 
-// ── Target Flag ─────────────────────────────────────────────────────────────
-
 #[test]
-fn test_target_flag() {
+fn test_convert_project() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
     fs::write(&input, simple_schema()).unwrap();
 
+    let out = cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--project", "/name"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let converted: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert!(converted["properties"]["name"].is_object());
+    assert!(converted["properties"].get("age").is_none());
+
     cmd()
-        .args(["convert", input.to_str().unwrap(), "--target", "gemini"])
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--project", "/nickname"])
         .assert()
-        .success();
+        .failure()
+        .stderr(predicate::str::contains("/nickname"));
 }
 
-// ── Invalid Input ───────────────────────────────────────────────────────────
-
 #[test]
-fn test_invalid_input() {
+fn test_convert_split_max_bytes() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    fs::write(
+        &input,
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "address": {
+                    "type": "object",
+                    "properties": {
+                        "street": { "type": "string", "description": "Street and number." },
+                        "city": { "type": "string", "description": "City or town." }
+                    },
+                    "required": ["street", "city"]
+                }
+            },
+            "required": ["id", "address"]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let out = cmd()
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--split-max-bytes",
+            "250",
+        ])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let plan: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert!(plan["primary"]["properties"].get("address").is_none());
+    assert_eq!(plan["parts"][0]["dataPath"], "/address");
+    assert!(plan["parts"][0]["schema"]["properties"]["street"].is_object());
+    let codec: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&codec_file).unwrap()).unwrap();
+    assert_eq!(codec["parts"][0]["dataPath"], "/address");
+
     cmd()
-        .args(["convert", "/nonexistent/path/schema.json"])
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--split-max-bytes",
+            "10",
+        ])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Failed to open input file"));
+        .stderr(predicate::str::contains("limit: 10"));
 }
 
-// ── Help Output ─────────────────────────────────────────────────────────────
-
 #[test]
-fn test_help_output() {
+fn test_convert_error_reports_source_location() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        "{\n  \"type\": \"object\",\n  \"properties\": {\n    \"a\": { \"$ref\": \"#/$defs/Nope\" }\n  }\n}\n",
+    )
+    .unwrap();
+
     cmd()
-        .arg("--help")
+        .args(["convert", input.to_str().unwrap()])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("convert"))
-        .stdout(predicate::str::contains("rehydrate"));
+        .failure()
+        .stderr(predicate::str::contains("line 4, column 20"));
 }
 
 #[test]
-fn test_convert_help() {
+fn test_convert_on_error_skip_subtree() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let output = dir.path().join("out.json");
+    fs::write(
+        &input,
+        r##"{"type": "object", "properties": {"id": {"type": "string"}, "a": {"$ref": "#/$defs/Nope"}}, "required": ["id", "a"]}"##,
+    )
+    .unwrap();
+
     cmd()
-        .args(["convert", "--help"])
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--on-error",
+            "skip-subtree",
+        ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("--target"))
-        .stdout(predicate::str::contains("--codec"))
-        .stdout(predicate::str::contains("--polymorphism"))
-        .stdout(predicate::str::contains("--output-dir"));
+        .stderr(predicate::str::contains("[JSL-P0-002]"));
+
+    let converted: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+    assert!(converted["properties"].get("a").is_none());
+    assert_eq!(converted["required"], serde_json::json!(["id"]));
 }
 
-// ── #178: Extract subcommand ────────────────────────────────────────────────
+#[test]
+fn test_convert_validate_input() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        r#"{"type": "object", "properties": {"a": {"minLength": -1}}, "required": "a"}"#,
+    )
+    .unwrap();
 
-fn schema_with_defs() -> String {
-    serde_json::json!({
-        "type": "object",
-        "properties": {
-            "pet": { "$ref": "#/$defs/Pet" }
-        },
-        "$defs": {
-            "Pet": {
-                "type": "object",
-                "properties": {
-                    "name": { "type": "string" },
-                    "tag": { "$ref": "#/$defs/Tag" }
-                },
-                "required": ["name"]
-            },
-            "Tag": {
-                "type": "object",
-                "properties": {
-                    "id": { "type": "integer" },
-                    "label": { "type": "string" }
-                }
-            }
-        }
-    })
-    .to_string()
+    cmd()
+        .args(["convert", input.to_str().unwrap(), "--validate-input"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("2 issue(s)"))
+        .stderr(predicate::str::contains(
+            "#/properties/a: `minLength` must be a non-negative integer",
+        ));
 }
 
 #[test]
-fn test_extract_to_stdout() {
+fn test_convert_duplicate_keys() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
-    fs::write(&input, schema_with_defs()).unwrap();
+    fs::write(
+        &input,
+        "{\n  \"type\": \"object\",\n  \"properties\": {\n    \"a\": { \"type\": \"string\" },\n    \"a\": { \"type\": \"integer\" }\n  }\n}\n",
+    )
+    .unwrap();
 
     cmd()
-        .args(["extract", input.to_str().unwrap()])
-        .args(["--pointer", "#/$defs/Pet"])
+        .args(["convert", input.to_str().unwrap()])
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"type\""))
-        .stdout(predicate::str::contains("\"name\""));
+        .stderr(predicate::str::contains("[JSL-P0-003]").not());
+
+    cmd()
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--duplicate-keys",
+            "warn",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[JSL-P0-003]").count(1));
+
+    cmd()
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--duplicate-keys",
+            "error",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("#/properties/a"))
+        .stderr(predicate::str::contains("line 5, column 5"));
 }
 
 #[test]
-fn test_extract_to_file() {
+fn test_convert_enum_descriptions() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
-    let output = dir.path().join("pet.json");
-    fs::write(&input, schema_with_defs()).unwrap();
+    fs::write(
+        &input,
+        r#"{"type": "string", "enum": ["open", "closed"], "x-enum-descriptions": ["Awaiting payment", "Shipped"]}"#,
+    )
+    .unwrap();
 
     cmd()
-        .args(["extract", input.to_str().unwrap()])
-        .args(["--pointer", "#/$defs/Pet"])
-        .args(["-o", output.to_str().unwrap()])
+        .args(["convert", input.to_str().unwrap()])
         .assert()
-        .success();
+        .success()
+        .stdout(predicate::str::contains(
+            r#"Values: \"open\": Awaiting payment; \"closed\": Shipped."#,
+        ))
+        .stdout(predicate::str::contains("x-enum-descriptions").not());
 
-    let content = fs::read_to_string(&output).expect("output file should exist");
-    let val: serde_json::Value =
-        serde_json::from_str(&content).expect("output should be valid JSON");
-    // Extracted Pet should have Tag in $defs
-    assert!(val["$defs"]["Tag"].is_object(), "Tag dep should be inlined");
+    cmd()
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--target",
+            "gemini",
+            "--enum-descriptions",
+            "one-of",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#""description": "Awaiting payment""#,
+        ));
 }
 
 #[test]
-fn test_extract_missing_pointer() {
+#[cfg(not(feature = "arbitrary-precision"))]
+fn test_preserve_precision_needs_feature() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
-    fs::write(&input, schema_with_defs()).unwrap();
+    fs::write(&input, r#"{"type": "object"}"#).unwrap();
 
     cmd()
-        .args(["extract", input.to_str().unwrap()])
-        .args(["--pointer", "#/$defs/DoesNotExist"])
+        .args(["convert", input.to_str().unwrap(), "--preserve-precision"])
         .assert()
         .failure()
-        .stderr(predicate::str::is_empty().not());
+        .stderr(predicate::str::contains("arbitrary-precision feature"));
 }
 
-// ── #178: ListComponents subcommand ─────────────────────────────────────────
-
 #[test]
-fn test_list_components() {
+#[cfg(feature = "arbitrary-precision")]
+fn test_preserve_precision() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
-    fs::write(&input, schema_with_defs()).unwrap();
+    fs::write(
+        &input,
+        r#"{"type": "object", "properties": {"id": {"type": "number", "maximum": 12345678901234567890123}}, "required": ["id"]}"#,
+    )
+    .unwrap();
+    let codec = dir.path().join("codec.json");
+    let data = dir.path().join("data.json");
+    fs::write(&data, r#"{"id": 98765432109876543210987}"#).unwrap();
 
-    let output = cmd()
-        .args(["list-components", input.to_str().unwrap()])
+    cmd()
+        .args(["convert", input.to_str().unwrap(), "--target", "gemini"])
         .assert()
         .success()
-        .get_output()
-        .stdout
-        .clone();
+        .stdout(predicate::str::contains("1.2345678901234568e+22"));
 
-    let stdout = String::from_utf8(output).unwrap();
-    let lines: Vec<&str> = stdout.lines().collect();
+    cmd()
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--target",
+            "gemini",
+            "--codec",
+            codec.to_str().unwrap(),
+            "--preserve-precision",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("12345678901234567890123"));
 
-    assert_eq!(lines.len(), 2, "should list 2 components");
-    // Sorted: Pet < Tag
-    assert_eq!(lines[0], "#/$defs/Pet");
-    assert_eq!(lines[1], "#/$defs/Tag");
+    cmd()
+        .args([
+            "rehydrate",
+            data.to_str().unwrap(),
+            "--codec",
+            codec.to_str().unwrap(),
+            "--schema",
+            input.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("9.876543210987654e+22"));
+
+    cmd()
+        .args([
+            "rehydrate",
+            data.to_str().unwrap(),
+            "--codec",
+            codec.to_str().unwrap(),
+            "--schema",
+            input.to_str().unwrap(),
+            "--preserve-precision",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("98765432109876543210987"));
 }
 
 #[test]
-fn test_list_components_empty() {
+fn test_convert_gemini_property_ordering() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
-    fs::write(&input, simple_schema()).unwrap();
+    fs::write(
+        &input,
+        r#"{"type": "object", "properties": {"reasoning": {"type": "string"}, "answer": {"type": "string"}}}"#,
+    )
+    .unwrap();
 
     let output = cmd()
-        .args(["list-components", input.to_str().unwrap()])
-        .assert()
-        .success()
-        .get_output()
-        .stdout
-        .clone();
-
-    let stdout = String::from_utf8(output).unwrap();
-    assert!(stdout.trim().is_empty(), "no defs → empty output");
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--target",
+            "gemini",
+            "--property-ordering",
+            "original",
+            "--format",
+            "compact",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["propertyOrdering"],
+        serde_json::json!(["reasoning", "answer"])
+    );
 }
 
-// ── #178: Convert --output-dir ──────────────────────────────────────────────
-
 #[test]
-fn test_convert_output_dir() {
+fn test_convert_preserve_additional_properties() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
-    let out_dir = dir.path().join("output");
-    fs::write(&input, schema_with_defs()).unwrap();
-
-    cmd()
-        .args(["convert", input.to_str().unwrap()])
-        .args(["--output-dir", out_dir.to_str().unwrap()])
-        .assert()
-        .success();
+    fs::write(
+        &input,
+        r#"{"type": "object", "properties": {"tags": {"type": "object", "additionalProperties": {"type": "string"}}}, "required": ["tags"]}"#,
+    )
+    .unwrap();
 
-    // Root files
-    assert!(out_dir.join("schema.json").exists(), "root schema.json");
-    assert!(out_dir.join("codec.json").exists(), "root codec.json");
-    // Component directories (full pointer path: $defs/Pet, $defs/Tag)
-    assert!(
-        out_dir.join("$defs/Pet/schema.json").exists(),
-        "Pet schema.json"
-    );
-    assert!(
-        out_dir.join("$defs/Pet/codec.json").exists(),
-        "Pet codec.json"
-    );
-    assert!(
-        out_dir.join("$defs/Pet/original.json").exists(),
-        "Pet original.json"
-    );
-    assert!(
-        out_dir.join("$defs/Tag/schema.json").exists(),
-        "Tag schema.json"
-    );
-    assert!(
-        out_dir.join("$defs/Tag/original.json").exists(),
-        "Tag original.json"
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap(), "--target", "claude"])
+        .args(["--additional-properties", "preserve"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["properties"]["tags"]["additionalProperties"],
+        serde_json::json!({"type": "string"})
     );
+    assert!(schema.get("additionalProperties").is_none());
 }
 
 #[test]
-fn test_convert_output_dir_skip_components() {
+fn test_convert_nullable_style() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        r#"{"type": "object", "properties": {"nick": {"type": "string"}}}"#,
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap(), "--target", "gemini"])
+        .args(["--nullable-style", "type-array"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["properties"]["nick"],
+        serde_json::json!({"type": ["string", "null"]})
+    );
+}
+
+#[test]
+fn test_convert_preset() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        r#"{"type": "object", "properties": {"id": {"type": "string", "readOnly": true}, "name": {"type": "string"}}}"#,
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args([
+            "--preset",
+            "gemini-structured-output",
+            "--format",
+            "compact",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["propertyOrdering"],
+        serde_json::json!(["id", "name"])
+    );
+
+    // Explicit flags override the preset: OpenAI strict has no propertyOrdering.
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args([
+            "--preset",
+            "gemini-structured-output",
+            "--target",
+            "openai-strict",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(schema.get("propertyOrdering").is_none());
+    assert!(schema["properties"].get("id").is_some());
+
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--preset", "openai-tool-calling"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(schema["properties"].get("id").is_none());
+}
+
+#[test]
+fn test_convert_envelope_tool_function() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    let output = cmd()
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--envelope",
+            "tool-function",
+            "--envelope-name",
+            "save_person",
+            "--format",
+            "compact",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let payload: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(payload["type"], "function");
+    assert_eq!(payload["function"]["name"], "save_person");
+    assert_eq!(payload["function"]["strict"], true);
+    assert_eq!(payload["function"]["parameters"]["type"], "object");
+}
+
+#[test]
+fn test_convert_suppress_diagnostic() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        r#"{"type": "object", "properties": {"a": {"enum": ["x", 1]}}, "required": ["a"]}"#,
+    )
+    .unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[JSL-P9-003]"));
+
+    cmd()
+        .args([
+            "convert",
+            input.to_str().unwrap(),
+            "--suppress",
+            "JSL-P9-003:#/properties/*",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[JSL-P9-003]").not())
+        .stderr(predicate::str::contains("1 diagnostic(s) suppressed."));
+
+    cmd()
+        .args(["convert", input.to_str().unwrap(), "--suppress", "JSL-XX-1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown rule ID"));
+}
+
+// ── Protobuf / GraphQL input ────────────────────────────────────────────────
+
+/// `FileDescriptorSet` for `package t; message M { string x = 1; }`.
+const PROTO_DESCRIPTOR: &[u8] = &[
+    0x0a, 0x1c, // file
+    0x0a, 0x07, b't', b'.', b'p', b'r', b'o', b't', b'o', // name
+    0x12, 0x01, b't', // package
+    0x22, 0x0e, // message_type
+    0x0a, 0x01, b'M', // name
+    0x12, 0x09, // field
+    0x0a, 0x01, b'x', 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, // x = 1, optional string
+];
+
+#[test]
+fn test_convert_protobuf_message() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("t.pb");
+    fs::write(&input, PROTO_DESCRIPTOR).unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--input-format", "protobuf", "--message", "t.M"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"x\""));
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--input-format", "protobuf"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--message"));
+}
+
+#[test]
+fn test_convert_graphql_type() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.graphql");
+    fs::write(&input, "type Order { id: ID!, total: Float }").unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--input-format", "graphql", "--type", "Order"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"total\""));
+}
+
+// ── Rehydrate ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_rehydrate() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let converted = dir.path().join("converted.json");
+    let codec_file = dir.path().join("codec.json");
+    let llm_output = dir.path().join("llm_output.json");
+    let rehydrated = dir.path().join("rehydrated.json");
+
+    // Step 1: Convert
+    fs::write(&input, simple_schema()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["-o", converted.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    // Step 2: Create fake LLM output matching the converted schema
+    let llm_data = serde_json::json!({
+        "name": "Alice",
+        "age": 30
+    });
+    fs::write(&llm_output, llm_data.to_string()).unwrap();
+
+    // Step 3: Rehydrate (--schema is required for type coercion)
+    cmd()
+        .args(["rehydrate", llm_output.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .args(["--schema", input.to_str().unwrap()])
+        .args(["-o", rehydrated.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&rehydrated).expect("rehydrated file should exist");
+    let data: serde_json::Value =
+        serde_json::from_str(&content).expect("rehydrated output should be valid JSON");
+    assert_eq!(data["name"], serde_json::json!("Alice"));
+}
+
+#[test]
+fn test_rehydrate_provider_response() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    let response = dir.path().join("response.json");
+
+    fs::write(&input, simple_schema()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let body = serde_json::json!({
+        "choices": [{ "message": { "role": "assistant", "content": "{\"name\": \"Alice\", \"age\": 30}" } }]
+    });
+    fs::write(&response, body.to_string()).unwrap();
+
+    let output = cmd()
+        .args(["rehydrate", response.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .args(["--schema", input.to_str().unwrap()])
+        .args(["--provider-response", "openai-strict"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let data: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(data["name"], "Alice");
+}
+
+#[test]
+fn test_rehydrate_from_provider_response_detects_provider() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    let response = dir.path().join("response.json");
+
+    fs::write(&input, simple_schema()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let rehydrate = |body: serde_json::Value| {
+        fs::write(&response, body.to_string()).unwrap();
+        cmd()
+            .args(["rehydrate", response.to_str().unwrap()])
+            .args(["--codec", codec_file.to_str().unwrap()])
+            .args(["--schema", input.to_str().unwrap()])
+            .arg("--from-provider-response")
+            .output()
+            .unwrap()
+    };
+
+    let gemini = serde_json::json!({
+        "candidates": [{ "content": { "parts": [{ "text": "```json\n{\"name\": \"Bob\", \"age\": 4}\n```" }] } }]
+    });
+    let output = rehydrate(gemini);
+    assert!(output.status.success());
+    let data: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(data["name"], "Bob");
+
+    let claude = serde_json::json!({
+        "content": [{ "type": "tool_use", "name": "f", "input": { "name": "Cy", "age": 5 } }]
+    });
+    let output = rehydrate(claude);
+    assert!(output.status.success());
+    let data: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(data["name"], "Cy");
+
+    let output = rehydrate(serde_json::json!({ "name": "Dee" }));
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Could not detect the provider"));
+}
+
+#[test]
+fn test_rehydrate_json_diagnostics_carry_data_path() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    let response = dir.path().join("response.json");
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "m": { "type": "object", "additionalProperties": { "type": "integer" } }
+        },
+        "required": ["m"]
+    });
+    fs::write(&input, schema.to_string()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success();
+    let body = serde_json::json!({
+        "m": [{ "key": "a", "value": 1 }, { "key": "a", "value": 2 }]
+    });
+    fs::write(&response, body.to_string()).unwrap();
+
+    let output = cmd()
+        .args(["--json", "rehydrate", response.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .args(["--schema", input.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let diagnostic = &envelope["diagnostics"][0];
+    assert_eq!(diagnostic["rule"], "JSL-RH-006");
+    assert_eq!(diagnostic["dataPath"], "/m");
+}
+
+#[test]
+fn test_rehydrate_bounds_warnings() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    let response = dir.path().join("response.json");
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "tags": { "type": "array", "items": { "type": "string", "maxLength": 2 } }
+        },
+        "required": ["tags"]
+    });
+    fs::write(&input, schema.to_string()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success();
+    fs::write(&response, r#"{"tags": ["long", "longer", "longest"]}"#).unwrap();
+
+    let rules = |extra: &[&str]| -> Vec<String> {
+        let output = cmd()
+            .args(["--json", "rehydrate", response.to_str().unwrap()])
+            .args(["--codec", codec_file.to_str().unwrap()])
+            .args(["--schema", input.to_str().unwrap()])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        envelope["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["rule"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(rules(&[]), ["JSL-RH-001"; 3]);
+    assert_eq!(rules(&["--aggregate-warnings", "2"]), ["JSL-RH-001"]);
+    assert_eq!(
+        rules(&["--max-warnings", "1"]),
+        ["JSL-RH-001", "JSL-RH-005"]
+    );
+}
+
+// ── Completions & Man Pages ─────────────────────────────────────────────────
+
+#[test]
+fn test_completions_bash_lists_subcommands() {
+    let output = cmd().args(["completions", "bash"]).output().unwrap();
+    assert!(output.status.success());
+    let script = String::from_utf8(output.stdout).unwrap();
+    assert!(script.contains("complete -F"));
+    assert!(script.contains("gen-sdk"));
+    assert!(script.contains("rehydrate"));
+}
+
+#[test]
+fn test_completions_closed_stdout_is_not_an_error() {
+    // The reader is gone before the child writes, like `completions bash | head -0`.
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_json-schema-llm"))
+        .args(["completions", "bash"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(!stderr.contains("panicked"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_man_writes_page_per_subcommand() {
+    let output = cmd().arg("man").output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with(".ie"));
+
+    let dir = TempDir::new().unwrap();
+    let out = dir.path().join("man");
+    cmd()
+        .args(["man", "--out-dir", out.to_str().unwrap()])
+        .assert()
+        .success();
+    assert!(out.join("json-schema-llm.1").exists());
+    assert!(out.join("json-schema-llm-convert.1").exists());
+    assert!(out.join("json-schema-llm-corpus-check.1").exists());
+}
+
+#[test]
+fn test_convert_reports_recursion_cycles() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    fs::write(
+        &input,
+        r##"{
+            "type": "object",
+            "properties": { "next": { "$ref": "#/$defs/Node" } },
+            "$defs": {
+                "Node": { "type": "object", "properties": { "next": { "$ref": "#/$defs/Node" } } }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .args(["--recursion-limit", "1", "--recursion-leaf", "null"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Recursion cycle #/$defs/Node -> #/$defs/Node cut 1 time(s), \
+             first at #/properties/next/properties/next/properties/next/properties/next \
+             (null placeholder)",
+        ));
+}
+
+// ── JSON Output Mode ────────────────────────────────────────────────────────
+
+#[test]
+fn test_convert_self_check_passes_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap(), "--self-check"])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"type\""))
+        .stderr(predicate::str::contains("Self-check passed"));
+}
+
+#[test]
+fn test_convert_verify_output_passes_for_conforming_schema() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    for target in ["openai-strict", "gemini", "claude"] {
+        cmd()
+            .args(["convert", input.to_str().unwrap(), "--verify-output"])
+            .args(["--target", target])
+            .args(["--codec", codec_file.to_str().unwrap()])
+            .assert()
+            .success();
+    }
+}
+
+#[test]
+fn test_convert_annotate_marks_transforms() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap(), "--annotate"])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["properties"]["age"]["x-jsl-transform"],
+        serde_json::json!(["nullable_optional"])
+    );
+    assert!(schema["properties"]["name"]
+        .get("x-jsl-transform")
+        .is_none());
+}
+
+#[test]
+fn test_convert_canonical_is_diff_stable() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.json");
+    let second = dir.path().join("second.json");
+    fs::write(
+        &first,
+        r#"{"type": "object", "properties": {"b": {"type": "integer", "minimum": 1.0}, "a": {"type": "string"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        &second,
+        r#"{"properties": {"a": {"type": "string"}, "b": {"minimum": 1, "type": "integer"}}, "type": "object"}"#,
+    )
+    .unwrap();
+
+    let convert = |input: &std::path::Path| {
+        cmd()
+            .args(["convert", input.to_str().unwrap(), "--canonical"])
+            .args(["--target", "gemini"])
+            .output()
+            .unwrap()
+            .stdout
+    };
+    let output = convert(&first);
+    assert_eq!(output, convert(&second));
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains(r#""minimum": 1,"#));
+}
+
+#[test]
+fn test_convert_strip_descriptions() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        r#"{
+            "type": "object",
+            "description": "A person.",
+            "properties": {
+                "name": { "type": "string", "description": "Full name." },
+                "age": { "type": "integer", "description": "Age in years." },
+                "meta": {}
+            },
+            "required": ["name", "age", "meta"]
+        }"#,
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap(), "--strip-descriptions"])
+        .args(["--keep-description", "#/properties/age"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(schema.get("description").is_none());
+    assert!(schema["properties"]["name"].get("description").is_none());
+    assert_eq!(schema["properties"]["age"]["description"], "Age in years.");
+    assert!(schema["properties"]["meta"]["description"]
+        .as_str()
+        .unwrap()
+        .contains("JSON"));
+}
+
+#[test]
+fn test_json_envelope_for_convert() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let codec_file = dir.path().join("codec.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    let output = cmd()
+        .args(["--json", "convert", input.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty(), "stderr should stay quiet");
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["command"], "convert");
+    assert_eq!(envelope["ok"], true);
+    assert_eq!(envelope["result"]["type"], "object");
+    assert_eq!(envelope["files"][0], codec_file.to_str().unwrap());
+    assert!(codec_file.exists());
+}
+
+#[test]
+fn test_json_envelope_collects_diagnostics() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    let output = cmd()
+        .args(["convert", input.to_str().unwrap(), "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let diagnostics = envelope["diagnostics"].as_array().unwrap();
+    assert!(diagnostics.iter().any(|d| d["level"] == "warning"
+        && d["message"]
+            .as_str()
+            .unwrap()
+            .contains("No codec file specified")));
+}
+
+#[test]
+fn test_json_envelope_for_list_components() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        r#"{"type": "object", "$defs": {"Pet": {"type": "string"}}}"#,
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args(["list-components", input.to_str().unwrap(), "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["result"][0]["pointer"], "#/$defs/Pet");
+    assert_eq!(envelope["result"][0]["recursive"], false);
+}
+
+#[test]
+fn test_json_envelope_reports_errors() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    let output = cmd()
+        .args(["extract", input.to_str().unwrap()])
+        .args(["--pointer", "#/$defs/Missing", "--json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(output.stderr.is_empty());
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["ok"], false);
+    assert_eq!(envelope["error"]["message"], "Extraction failed");
+    assert_eq!(envelope["error"]["code"], "unresolvable_ref");
+}
+
+// ── Target Flag ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_target_flag() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap(), "--target", "gemini"])
+        .assert()
+        .success();
+}
+
+// ── Invalid Input ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_invalid_input() {
+    cmd()
+        .args(["convert", "/nonexistent/path/schema.json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to open input file"));
+}
+
+// ── Help Output ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_help_output() {
+    cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("convert"))
+        .stdout(predicate::str::contains("rehydrate"));
+}
+
+#[test]
+fn test_convert_help() {
+    cmd()
+        .args(["convert", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--target"))
+        .stdout(predicate::str::contains("--codec"))
+        .stdout(predicate::str::contains("--polymorphism"))
+        .stdout(predicate::str::contains(
+            "[default: anyof, or the preset's]",
+        ))
+        .stdout(predicate::str::contains("--output-dir"));
+}
+
+// ── #178: Extract subcommand ────────────────────────────────────────────────
+
+fn schema_with_defs() -> String {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "pet": { "$ref": "#/$defs/Pet" }
+        },
+        "$defs": {
+            "Pet": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "tag": { "$ref": "#/$defs/Tag" }
+                },
+                "required": ["name"]
+            },
+            "Tag": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer" },
+                    "label": { "type": "string" }
+                }
+            }
+        }
+    })
+    .to_string()
+}
+
+#[test]
+fn test_extract_to_stdout() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["extract", input.to_str().unwrap()])
+        .args(["--pointer", "#/$defs/Pet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"type\""))
+        .stdout(predicate::str::contains("\"name\""));
+}
+
+#[test]
+fn test_extract_to_file() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let output = dir.path().join("pet.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["extract", input.to_str().unwrap()])
+        .args(["--pointer", "#/$defs/Pet"])
+        .args(["-o", output.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&output).expect("output file should exist");
+    let val: serde_json::Value =
+        serde_json::from_str(&content).expect("output should be valid JSON");
+    // Extracted Pet should have Tag in $defs
+    assert!(val["$defs"]["Tag"].is_object(), "Tag dep should be inlined");
+}
+
+#[test]
+fn test_extract_missing_pointer() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["extract", input.to_str().unwrap()])
+        .args(["--pointer", "#/$defs/DoesNotExist"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::is_empty().not());
+}
+
+// ── #178: ListComponents subcommand ─────────────────────────────────────────
+
+#[test]
+fn test_list_components() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    let output = cmd()
+        .args(["list-components", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stdout = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 3, "header + 2 components");
+    assert!(lines[0].starts_with("POINTER"));
+    // Sorted: Pet < Tag
+    assert!(lines[1].starts_with("#/$defs/Pet "));
+    assert!(lines[2].starts_with("#/$defs/Tag "));
+    let pet: Vec<&str> = lines[1].split_whitespace().collect();
+    assert_eq!(pet, ["#/$defs/Pet", "2", "1", "no", "0", "1"]);
+}
+
+#[test]
+fn test_list_components_referencing() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    let output = cmd()
+        .args(["list-components", input.to_str().unwrap()])
+        .args(["--referencing", "#/$defs/Tag", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let pointers: Vec<&str> = envelope["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["pointer"].as_str().unwrap())
+        .collect();
+    assert_eq!(pointers, ["#/$defs/Pet"]);
+}
+
+#[test]
+fn test_list_components_empty() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    let output = cmd()
+        .args(["list-components", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stdout = String::from_utf8(output).unwrap();
+    assert!(stdout.trim().is_empty(), "no defs → empty output");
+}
+
+// ── Typegen subcommand ──────────────────────────────────────────────────────
+
+#[test]
+fn test_typegen_typescript() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args([
+            "typegen",
+            "--language",
+            "typescript",
+            input.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("export namespace Converted {"))
+        .stdout(predicate::str::contains("export namespace Original {"))
+        .stdout(predicate::str::contains("export interface Pet {"));
+}
+
+// ── Prompt subcommand ───────────────────────────────────────────────────────
+
+#[test]
+fn test_prompt_scaffold() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    cmd()
+        .args(["prompt", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Response format"))
+        .stdout(predicate::str::contains("- `name` (string, required)"))
+        .stdout(predicate::str::contains(
+            "- `age` (integer, nullable, required)",
+        ));
+}
+
+// ── Examples subcommand ─────────────────────────────────────────────────────
+
+#[test]
+fn test_examples_reshapes_samples() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let data = dir.path().join("samples.ndjson");
+    fs::write(&input, simple_schema()).unwrap();
+    fs::write(
+        &data,
+        "{\"name\": \"Ada\"}\n\n{\"name\": \"Bo\", \"age\": 7}\n",
+    )
+    .unwrap();
+
+    cmd()
+        .args(["examples", input.to_str().unwrap()])
+        .args(["--data", data.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("{\"age\":null,\"name\":\"Ada\"}\n{\"age\":7,\"name\":\"Bo\"}\n");
+}
+
+// ── Diagnose subcommand ─────────────────────────────────────────────────────
+
+#[test]
+fn test_diagnose_reports_missing_field() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let converted = dir.path().join("converted.json");
+    let codec_file = dir.path().join("codec.json");
+    let llm_output = dir.path().join("llm_output.json");
+
+    fs::write(&input, simple_schema()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["-o", converted.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    fs::write(&llm_output, r#"{"name": "Alice"}"#).unwrap();
+    cmd()
+        .args(["diagnose", llm_output.to_str().unwrap()])
+        .args(["--schema", converted.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "include the required field \"age\"",
+        ))
+        .stderr(predicate::str::contains("1 problem(s)"));
+
+    fs::write(&llm_output, r#"{"name": "Alice", "age": null}"#).unwrap();
+    cmd()
+        .args(["diagnose", llm_output.to_str().unwrap()])
+        .args(["--schema", converted.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+// ── Check-codec subcommand ──────────────────────────────────────────────────
+
+#[test]
+fn test_check_codec_flags_stale_codec() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let converted = dir.path().join("converted.json");
+    let codec_file = dir.path().join("codec.json");
+
+    fs::write(&input, simple_schema()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["-o", converted.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success();
+    cmd()
+        .args(["check-codec", "--schema", converted.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("");
+
+    // `age` became required, so the old codec's nullable_optional is stale.
+    let edited = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "age": { "type": "integer" }
+        },
+        "required": ["name", "age"]
+    });
+    fs::write(&input, edited.to_string()).unwrap();
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["-o", converted.to_str().unwrap()])
+        .assert()
+        .success();
+    cmd()
+        .args(["check-codec", "--schema", converted.to_str().unwrap()])
+        .args(["--codec", codec_file.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "#/properties/age: expected a nullable property",
+        ))
+        .stderr(predicate::str::contains("1 issue(s)"));
+}
+
+// ── Tools subcommand ────────────────────────────────────────────────────────
+
+#[test]
+fn test_tools_registry() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.json");
+    let second = dir.path().join("second.json");
+    let codecs = dir.path().join("codecs.json");
+    fs::write(&first, simple_schema()).unwrap();
+    fs::write(&second, simple_schema()).unwrap();
+
+    let output = cmd()
+        .args(["tools", "--target", "claude"])
+        .arg(format!("create_user={}", first.display()))
+        .arg(format!("update_user={}", second.display()))
+        .args(["--codecs", codecs.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let tools: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(tools[0]["name"], "create_user");
+    assert_eq!(tools[1]["name"], "update_user");
+
+    let index: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&codecs).unwrap()).unwrap();
+    assert!(index["create_user"]["transforms"].is_array());
+    assert!(index["update_user"]["transforms"].is_array());
+}
+
+// ── Fingerprint subcommand ──────────────────────────────────────────────────
+
+#[test]
+fn test_fingerprint_ignores_key_order() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.json");
+    let second = dir.path().join("second.json");
+    fs::write(
+        &first,
+        r#"{"type": "object", "properties": {"a": {"type": "string"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        &second,
+        r#"{"properties": {"a": {"type": "string"}}, "type": "object"}"#,
+    )
+    .unwrap();
+
+    let fingerprint = |path: &std::path::Path, target: &str| {
+        let output = cmd()
+            .args(["fingerprint", path.to_str().unwrap(), "--target", target])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let a = fingerprint(&first, "openai-strict");
+    assert!(a.starts_with("sha256:"));
+    assert_eq!(a, fingerprint(&second, "openai-strict"));
+    assert_ne!(a, fingerprint(&first, "gemini"));
+}
+
+// ── Lint subcommand ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_lint_reports_findings_and_fails_on_warnings() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(
+        &input,
+        "{\n  \"type\": \"object\",\n  \"properties\": {\n    \"code\": {\"type\": \"string\", \"minLength\": 3}\n  }\n}",
+    )
+    .unwrap();
+
+    cmd()
+        .args(["lint", input.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "schema.json:4:13: warning [JSL-CD-001] #/properties/code",
+        ))
+        .stderr(predicate::str::contains("1 lint warning(s)"));
+
+    let output = cmd()
+        .args(["lint", input.to_str().unwrap(), "--json"])
+        .args(["--suppress", "JSL-CD-001:#/properties/**"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["command"], "lint");
+    let report = &envelope["result"];
+    assert_eq!(report["suppressed"], 1);
+    assert!(report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|f| f["severity"] == "info"));
+}
+
+#[test]
+fn test_lint_writes_no_files() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["lint", "schema.json", "--target", "gemini"])
+        .assert()
+        .success();
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+// ── #178: Convert --output-dir ──────────────────────────────────────────────
+
+#[test]
+fn test_convert_output_dir() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let out_dir = dir.path().join("output");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--output-dir", out_dir.to_str().unwrap()])
+        .assert()
+        .success();
+
+    // Root files
+    assert!(out_dir.join("schema.json").exists(), "root schema.json");
+    assert!(out_dir.join("codec.json").exists(), "root codec.json");
+    // Component directories (full pointer path: $defs/Pet, $defs/Tag)
+    assert!(
+        out_dir.join("$defs/Pet/schema.json").exists(),
+        "Pet schema.json"
+    );
+    assert!(
+        out_dir.join("$defs/Pet/codec.json").exists(),
+        "Pet codec.json"
+    );
+    assert!(
+        out_dir.join("$defs/Pet/original.json").exists(),
+        "Pet original.json"
+    );
+    assert!(
+        out_dir.join("$defs/Tag/schema.json").exists(),
+        "Tag schema.json"
+    );
+    assert!(
+        out_dir.join("$defs/Tag/original.json").exists(),
+        "Tag original.json"
+    );
+}
+
+#[test]
+fn test_convert_output_dir_skip_components() {
     let dir = TempDir::new().unwrap();
     let input = dir.path().join("schema.json");
     let out_dir = dir.path().join("output");
@@ -343,6 +1644,30 @@ fn test_convert_output_dir_skip_components() {
     );
 }
 
+#[test]
+fn test_convert_output_dir_no_progress_when_stderr_redirected() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let out_dir = dir.path().join("output");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    // assert_cmd captures stderr, so it is not a terminal.
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--output-dir", out_dir.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Components:").not())
+        .stderr(predicate::str::contains("\x1b[2K").not());
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--output-dir", out_dir.to_str().unwrap()])
+        .arg("--no-progress")
+        .assert()
+        .success();
+}
+
 // ── #179: Manifest ──────────────────────────────────────────────────────────
 
 #[test]
@@ -380,11 +1705,221 @@ fn test_convert_output_dir_manifest() {
         assert!(comp["codecPath"].is_string());
         assert!(comp["originalPath"].is_string());
         assert!(comp["dependencyCount"].is_number());
+        assert!(comp["fingerprint"]
+            .as_str()
+            .is_some_and(|f| f.starts_with("sha256:")));
     }
+    assert!(manifest["fingerprint"].is_string());
 }
 
 // ── Mutual exclusion: --output-dir vs -o ────────────────────────────────────
 
+#[test]
+fn test_convert_output_dir_reproducible() {
+    fn files(dir: &std::path::Path, out: &mut Vec<(String, Vec<u8>)>) {
+        let mut entries: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                files(&path, out);
+            } else {
+                out.push((path.display().to_string(), fs::read(&path).unwrap()));
+            }
+        }
+    }
+
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+    let snapshot = |name: &str, epoch: Option<&str>| {
+        let out_dir = dir.path().join(name);
+        let mut command = cmd();
+        command
+            .args(["convert", input.to_str().unwrap(), "--reproducible"])
+            .args(["--output-dir", out_dir.to_str().unwrap()])
+            .env_remove("SOURCE_DATE_EPOCH");
+        if let Some(epoch) = epoch {
+            command.env("SOURCE_DATE_EPOCH", epoch);
+        }
+        command.assert().success();
+        let mut found = Vec::new();
+        files(&out_dir, &mut found);
+        found
+            .into_iter()
+            .map(|(path, bytes)| (path.replacen(out_dir.to_str().unwrap(), "", 1), bytes))
+            .collect::<Vec<_>>()
+    };
+
+    let first = snapshot("a", None);
+    assert_eq!(first, snapshot("b", None));
+    let manifest: serde_json::Value = serde_json::from_slice(
+        &first
+            .iter()
+            .find(|(path, _)| path.ends_with("manifest.json"))
+            .unwrap()
+            .1,
+    )
+    .unwrap();
+    assert!(manifest.get("generatedAt").is_none());
+
+    let dated = snapshot("c", Some("1767225600"));
+    let manifest: serde_json::Value = serde_json::from_slice(
+        &dated
+            .iter()
+            .find(|(path, _)| path.ends_with("manifest.json"))
+            .unwrap()
+            .1,
+    )
+    .unwrap();
+    assert_eq!(manifest["generatedAt"], "2026-01-01T00:00:00Z");
+
+    cmd()
+        .args(["convert", input.to_str().unwrap(), "--reproducible"])
+        .args(["--output-dir", dir.path().join("d").to_str().unwrap()])
+        .env("SOURCE_DATE_EPOCH", "yesterday")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "SOURCE_DATE_EPOCH must be a Unix timestamp",
+        ));
+}
+
+#[test]
+fn test_convert_output_dir_resume() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let out_dir = dir.path().join("output");
+    fs::write(&input, schema_with_defs()).unwrap();
+    let convert = |extra: &[&str]| {
+        let mut command = cmd();
+        command
+            .args(["convert", input.to_str().unwrap(), "--resume"])
+            .args(["--output-dir", out_dir.to_str().unwrap()])
+            .args(extra);
+        command.assert().success()
+    };
+
+    // No earlier run: everything is converted.
+    convert(&[]).stderr(predicate::str::contains(
+        "Resumed: 0 component(s) unchanged, 2 converted",
+    ));
+
+    // Marker files show which components are left alone.
+    let tag_schema = out_dir.join("$defs/Tag/schema.json");
+    let pet_schema = out_dir.join("$defs/Pet/schema.json");
+    fs::write(&tag_schema, "{\"marker\": true}").unwrap();
+    fs::write(&pet_schema, "{\"marker\": true}").unwrap();
+
+    // Changing Pet leaves Tag, which it references, untouched.
+    let mut schema: serde_json::Value = serde_json::from_str(&schema_with_defs()).unwrap();
+    schema["$defs"]["Pet"]["properties"]["age"] = serde_json::json!({ "type": "integer" });
+    fs::write(&input, schema.to_string()).unwrap();
+    convert(&[]).stderr(predicate::str::contains(
+        "Resumed: 1 component(s) unchanged, 1 converted",
+    ));
+    assert_eq!(
+        fs::read_to_string(&tag_schema).unwrap(),
+        "{\"marker\": true}"
+    );
+    assert_ne!(
+        fs::read_to_string(&pet_schema).unwrap(),
+        "{\"marker\": true}"
+    );
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("manifest.json")).unwrap()).unwrap();
+    let pointers: Vec<&str> = manifest["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["pointer"].as_str().unwrap())
+        .collect();
+    assert_eq!(pointers, vec!["#/$defs/Pet", "#/$defs/Tag"]);
+
+    // Different options change every fingerprint.
+    convert(&["--target", "gemini"]).stderr(predicate::str::contains(
+        "Resumed: 0 component(s) unchanged, 2 converted",
+    ));
+    assert_ne!(
+        fs::read_to_string(&tag_schema).unwrap(),
+        "{\"marker\": true}"
+    );
+}
+
+#[test]
+fn test_convert_output_dir_resume_rejects_escaping_manifest_paths() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let out_dir = dir.path().join("output");
+    fs::write(&input, schema_with_defs()).unwrap();
+    let convert = || {
+        let mut command = cmd();
+        command
+            .args(["convert", input.to_str().unwrap(), "--resume"])
+            .args(["--output-dir", out_dir.to_str().unwrap()]);
+        command.assert().success()
+    };
+    convert();
+
+    // Point the components at real files outside the output directory.
+    let outside = dir.path().join("outside.json");
+    fs::write(&outside, "{}").unwrap();
+    let manifest_path = out_dir.join("manifest.json");
+    let mut manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+    let components = manifest["components"].as_array_mut().unwrap();
+    components[0]["schemaPath"] = serde_json::json!("../outside.json");
+    components[1]["codecPath"] = serde_json::json!(outside.to_str().unwrap());
+    fs::write(&manifest_path, manifest.to_string()).unwrap();
+
+    convert().stderr(predicate::str::contains(
+        "Resumed: 0 component(s) unchanged, 2 converted",
+    ));
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+    for component in manifest["components"].as_array().unwrap() {
+        for key in ["schemaPath", "codecPath", "originalPath"] {
+            let file = component[key].as_str().unwrap();
+            assert!(
+                !file.contains("..") && !std::path::Path::new(file).is_absolute(),
+                "{file}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_convert_output_dir_jobs() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let mut defs = serde_json::Map::new();
+    for i in 0..8 {
+        defs.insert(
+            format!("Def{}", i),
+            serde_json::json!({ "type": "object", "properties": { "n": { "type": "integer" } } }),
+        );
+    }
+    fs::write(&input, serde_json::json!({ "$defs": defs }).to_string()).unwrap();
+
+    let manifest = |jobs: &str| {
+        let out_dir = dir.path().join(format!("jobs-{}", jobs));
+        cmd()
+            .args(["convert", input.to_str().unwrap(), "--reproducible"])
+            .args(["--output-dir", out_dir.to_str().unwrap()])
+            .args(["--jobs", jobs])
+            .env_remove("SOURCE_DATE_EPOCH")
+            .assert()
+            .success();
+        fs::read_to_string(out_dir.join("manifest.json")).unwrap()
+    };
+    let serial = manifest("1");
+    assert_eq!(manifest("3"), serial);
+    assert_eq!(manifest("0"), serial);
+    assert!(serial.find("#/$defs/Def0").unwrap() < serial.find("#/$defs/Def7").unwrap());
+}
+
 #[test]
 fn test_output_dir_conflicts_with_output() {
     let dir = TempDir::new().unwrap();
@@ -473,125 +2008,363 @@ fn setup_gen_sdk_fixtures(dir: &TempDir) -> std::path::PathBuf {
     )
     .unwrap();
 
-    let comp_dir = schema_dir.join("user-profile");
-    fs::create_dir_all(&comp_dir).unwrap();
-    fs::write(comp_dir.join("schema.json"), "{}").unwrap();
-    fs::write(comp_dir.join("codec.json"), "{}").unwrap();
-    fs::write(comp_dir.join("original.json"), "{}").unwrap();
+    let comp_dir = schema_dir.join("user-profile");
+    fs::create_dir_all(&comp_dir).unwrap();
+    fs::write(comp_dir.join("schema.json"), "{}").unwrap();
+    fs::write(comp_dir.join("codec.json"), "{}").unwrap();
+    fs::write(comp_dir.join("original.json"), "{}").unwrap();
+
+    schema_dir
+}
+
+#[test]
+fn test_gen_sdk_python_produces_valid_project() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+    let output = dir.path().join("my-sdk");
+
+    cmd()
+        .args(["gen-sdk", "--language", "python"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "my-test-sdk"])
+        .args(["--output", output.to_str().unwrap()])
+        .args(["--build-tool", "setuptools"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("SDK generated successfully"));
+
+    assert!(output.join("pyproject.toml").exists());
+    assert!(output.join("README.md").exists());
+    assert!(output.join(".gitignore").exists());
+    // snake_case import name directory
+    assert!(output.join("my_test_sdk/__init__.py").exists());
+    assert!(output.join("my_test_sdk/user_profile.py").exists());
+    assert!(output.join("my_test_sdk/generator.py").exists());
+    // Schema resources inside package
+    assert!(output
+        .join("my_test_sdk/schemas/user-profile/schema.json")
+        .exists());
+}
+
+#[test]
+fn test_gen_sdk_python_rejects_invalid_package() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+
+    // Spaces + special chars
+    cmd()
+        .args(["gen-sdk", "--language", "python"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "my sdk!"])
+        .args(["--output", dir.path().join("out").to_str().unwrap()])
+        .args(["--build-tool", "setuptools"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid Python package name"));
+
+    // PEP 508: leading dot disallowed
+    cmd()
+        .args(["gen-sdk", "--language", "python"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", ".my-sdk"])
+        .args(["--output", dir.path().join("out2").to_str().unwrap()])
+        .args(["--build-tool", "setuptools"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("PEP 508"));
+
+    // PEP 508: must start with alphanumeric
+    cmd()
+        .args(["gen-sdk", "--language", "python"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "_my_sdk"])
+        .args(["--output", dir.path().join("out3").to_str().unwrap()])
+        .args(["--build-tool", "setuptools"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("PEP 508"));
+}
+
+#[test]
+fn test_gen_sdk_help_shows_python() {
+    cmd()
+        .args(["gen-sdk", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("python"))
+        .stdout(predicate::str::contains("setuptools"));
+}
+
+// ── Language / build-tool coupling ─────────────────────────────────────
+
+#[test]
+fn test_gen_sdk_python_maven_combo_rejected() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+
+    cmd()
+        .args(["gen-sdk", "--language", "python"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "my-sdk"])
+        .args(["--output", dir.path().join("out").to_str().unwrap()])
+        .args(["--build-tool", "maven"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid combination"));
+}
+
+#[test]
+fn test_gen_sdk_java_setuptools_combo_rejected() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+
+    cmd()
+        .args(["gen-sdk", "--language", "java"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "com.example.sdk"])
+        .args(["--output", dir.path().join("out").to_str().unwrap()])
+        .args(["--build-tool", "setuptools"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid combination"));
+}
+
+#[test]
+fn test_gen_sdk_rust_produces_cargo_crate() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+    let out = dir.path().join("out");
+
+    cmd()
+        .args(["gen-sdk", "--language", "rust", "--build-tool", "cargo"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "my-sdk"])
+        .args(["--output", out.to_str().unwrap()])
+        .assert()
+        .success();
+
+    assert!(out.join("Cargo.toml").exists());
+    assert!(out.join("src/lib.rs").exists());
+}
+
+#[test]
+fn test_gen_sdk_cargo_requires_rust() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+
+    cmd()
+        .args(["gen-sdk", "--language", "python"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "my-sdk"])
+        .args(["--output", dir.path().join("out").to_str().unwrap()])
+        .args(["--build-tool", "cargo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid combination"));
+}
+
+#[test]
+fn test_gen_sdk_kotlin_produces_gradle_project() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+    let out = dir.path().join("out");
+
+    cmd()
+        .args(["gen-sdk", "--language", "kotlin", "--build-tool", "gradle"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "com.example.sdk"])
+        .args(["--output", out.to_str().unwrap()])
+        .assert()
+        .success();
 
-    schema_dir
+    assert!(out.join("build.gradle.kts").exists());
+    assert!(out
+        .join("src/main/kotlin/com/example/sdk/SchemaGenerator.kt")
+        .exists());
 }
 
 #[test]
-fn test_gen_sdk_python_produces_valid_project() {
+fn test_gen_sdk_gradle_requires_kotlin() {
     let dir = TempDir::new().unwrap();
     let schema_dir = setup_gen_sdk_fixtures(&dir);
-    let output = dir.path().join("my-sdk");
 
     cmd()
-        .args(["gen-sdk", "--language", "python"])
+        .args(["gen-sdk", "--language", "java"])
         .args(["--schema", schema_dir.to_str().unwrap()])
-        .args(["--package", "my-test-sdk"])
-        .args(["--output", output.to_str().unwrap()])
-        .args(["--build-tool", "setuptools"])
+        .args(["--package", "com.example.sdk"])
+        .args(["--output", dir.path().join("out").to_str().unwrap()])
+        .args(["--build-tool", "gradle"])
         .assert()
-        .success()
-        .stderr(predicate::str::contains("SDK generated successfully"));
+        .failure()
+        .stderr(predicate::str::contains("Invalid combination"));
+}
 
-    assert!(output.join("pyproject.toml").exists());
-    assert!(output.join("README.md").exists());
-    assert!(output.join(".gitignore").exists());
-    // snake_case import name directory
-    assert!(output.join("my_test_sdk/__init__.py").exists());
-    assert!(output.join("my_test_sdk/user_profile.py").exists());
-    assert!(output.join("my_test_sdk/generator.py").exists());
-    // Schema resources inside package
-    assert!(output
-        .join("my_test_sdk/schemas/user-profile/schema.json")
-        .exists());
+#[test]
+fn test_gen_sdk_swift_produces_swiftpm_package() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+    let out = dir.path().join("out");
+
+    cmd()
+        .args(["gen-sdk", "--language", "swift"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "MySdk"])
+        .args(["--output", out.to_str().unwrap()])
+        .assert()
+        .success();
+
+    assert!(out.join("Package.swift").exists());
+    assert!(out.join("Sources/MySdk/JsonSchemaLlm.swift").exists());
 }
 
 #[test]
-fn test_gen_sdk_python_rejects_invalid_package() {
+fn test_gen_sdk_swiftpm_requires_swift() {
     let dir = TempDir::new().unwrap();
     let schema_dir = setup_gen_sdk_fixtures(&dir);
 
-    // Spaces + special chars
     cmd()
-        .args(["gen-sdk", "--language", "python"])
+        .args(["gen-sdk", "--language", "rust"])
         .args(["--schema", schema_dir.to_str().unwrap()])
-        .args(["--package", "my sdk!"])
+        .args(["--package", "my-sdk"])
         .args(["--output", dir.path().join("out").to_str().unwrap()])
-        .args(["--build-tool", "setuptools"])
+        .args(["--build-tool", "swiftpm"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Invalid Python package name"));
+        .stderr(predicate::str::contains("Invalid combination"));
+}
+
+#[test]
+fn test_gen_sdk_template_dir_missing_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
 
-    // PEP 508: leading dot disallowed
     cmd()
         .args(["gen-sdk", "--language", "python"])
         .args(["--schema", schema_dir.to_str().unwrap()])
-        .args(["--package", ".my-sdk"])
-        .args(["--output", dir.path().join("out2").to_str().unwrap()])
-        .args(["--build-tool", "setuptools"])
+        .args(["--package", "my-sdk"])
+        .args(["--output", dir.path().join("out").to_str().unwrap()])
+        .args(["--template-dir", dir.path().join("nope").to_str().unwrap()])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("PEP 508"));
+        .stderr(predicate::str::contains("Template directory not found"));
+}
+
+#[test]
+fn test_gen_sdk_template_dir_overrides_readme() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+    let templates = dir.path().join("templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(templates.join("README.md.tera"), "# Internal SDK\n").unwrap();
+    let out = dir.path().join("out");
 
-    // PEP 508: must start with alphanumeric
     cmd()
         .args(["gen-sdk", "--language", "python"])
         .args(["--schema", schema_dir.to_str().unwrap()])
-        .args(["--package", "_my_sdk"])
-        .args(["--output", dir.path().join("out3").to_str().unwrap()])
-        .args(["--build-tool", "setuptools"])
+        .args(["--package", "my-sdk"])
+        .args(["--output", out.to_str().unwrap()])
+        .args(["--template-dir", templates.to_str().unwrap()])
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("PEP 508"));
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(out.join("README.md")).unwrap(),
+        "# Internal SDK\n"
+    );
+    assert!(out.join("pyproject.toml").exists());
 }
 
 #[test]
-fn test_gen_sdk_help_shows_python() {
+fn test_gen_sdk_bundles_smoke_test_examples() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let converted = dir.path().join("converted");
+    fs::write(&input, schema_with_defs()).unwrap();
     cmd()
-        .args(["gen-sdk", "--help"])
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--output-dir", converted.to_str().unwrap()])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("python"))
-        .stdout(predicate::str::contains("setuptools"));
+        .success();
+
+    let out = dir.path().join("out");
+    cmd()
+        .args(["gen-sdk", "--language", "rust"])
+        .args(["--schema", converted.to_str().unwrap()])
+        .args(["--package", "pet-sdk"])
+        .args(["--output", out.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let smoke = fs::read_to_string(out.join("tests/smoke.rs")).unwrap();
+    assert!(smoke.contains("fn round_trip_pet()"));
+    let example: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out.join("tests/examples/pet.json")).unwrap())
+            .unwrap();
+    assert_eq!(example["rehydrated"], serde_json::json!({"name": ""}));
 }
 
-// ── Language / build-tool coupling ─────────────────────────────────────
+#[test]
+fn test_gen_sdk_multiple_languages_into_sibling_dirs() {
+    let dir = TempDir::new().unwrap();
+    let schema_dir = setup_gen_sdk_fixtures(&dir);
+    let out = dir.path().join("sdks");
+
+    cmd()
+        .args(["gen-sdk", "--language", "java,python,typescript"])
+        .args(["--schema", schema_dir.to_str().unwrap()])
+        .args(["--package", "java=com.example.sdk"])
+        .args(["--package", "my-sdk"])
+        .args(["--output", out.to_str().unwrap()])
+        .env("JSON_SCHEMA_LLM_DETERMINISTIC", "1")
+        .assert()
+        .success();
+
+    assert!(out.join("java/pom.xml").exists());
+    assert!(out.join("python/pyproject.toml").exists());
+    assert!(out.join("typescript/package.json").exists());
+
+    let workspace: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out.join("sdk-workspace.json")).unwrap()).unwrap();
+    assert_eq!(workspace["generatedAt"], "2026-01-01T00:00:00Z");
+    let sdks = workspace["sdks"].as_array().unwrap();
+    assert_eq!(sdks.len(), 3);
+    assert_eq!(sdks[0]["language"], "java");
+    assert_eq!(sdks[0]["package"], "com.example.sdk");
+    assert_eq!(sdks[0]["buildTool"], "maven");
+    assert_eq!(sdks[1]["package"], "my-sdk");
+}
 
 #[test]
-fn test_gen_sdk_python_maven_combo_rejected() {
+fn test_gen_sdk_multiple_languages_validates_before_writing() {
     let dir = TempDir::new().unwrap();
     let schema_dir = setup_gen_sdk_fixtures(&dir);
+    let out = dir.path().join("sdks");
 
+    // "my-sdk" is not a valid Java package; nothing should be generated.
     cmd()
-        .args(["gen-sdk", "--language", "python"])
+        .args(["gen-sdk", "--language", "python,java"])
         .args(["--schema", schema_dir.to_str().unwrap()])
         .args(["--package", "my-sdk"])
-        .args(["--output", dir.path().join("out").to_str().unwrap()])
-        .args(["--build-tool", "maven"])
+        .args(["--output", out.to_str().unwrap()])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Invalid combination"));
+        .stderr(predicate::str::contains("Invalid Java package name"));
+    assert!(!out.exists());
 }
 
 #[test]
-fn test_gen_sdk_java_setuptools_combo_rejected() {
+fn test_gen_sdk_all_requires_package_per_language() {
     let dir = TempDir::new().unwrap();
     let schema_dir = setup_gen_sdk_fixtures(&dir);
 
     cmd()
-        .args(["gen-sdk", "--language", "java"])
+        .args(["gen-sdk", "--all"])
         .args(["--schema", schema_dir.to_str().unwrap()])
-        .args(["--package", "com.example.sdk"])
+        .args(["--package", "java=com.example.sdk"])
         .args(["--output", dir.path().join("out").to_str().unwrap()])
-        .args(["--build-tool", "setuptools"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Invalid combination"));
+        .stderr(predicate::str::contains("No --package for python"));
 }
 
 #[test]
@@ -619,3 +2392,355 @@ fn test_gen_sdk_python_default_build_tool_is_setuptools() {
         "should NOT produce pom.xml"
     );
 }
+
+#[test]
+fn test_gen_sdk_from_input_schema() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let output = dir.path().join("pet-sdk");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["gen-sdk", "--language", "python"])
+        .args(["--input", input.to_str().unwrap(), "--target", "gemini"])
+        .args(["--package", "pet-sdk"])
+        .args(["--output", output.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("SDK generated successfully"));
+
+    let schema_root = output.join("pet_sdk/schemas");
+    assert!(schema_root.join("$defs/Pet/schema.json").exists());
+    assert!(schema_root.join("$defs/Tag/codec.json").exists());
+
+    cmd()
+        .args(["gen-sdk", "--language", "python", "--target", "gemini"])
+        .args(["--schema", dir.path().to_str().unwrap()])
+        .args(["--package", "pet-sdk"])
+        .args(["--output", output.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_gen_sdk_connected_layout() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let converted = dir.path().join("converted");
+    let output = dir.path().join("pet-sdk");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--output-dir", converted.to_str().unwrap()])
+        .assert()
+        .success();
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(converted.join("manifest.json")).unwrap())
+            .unwrap();
+    let pet = manifest["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "Pet")
+        .unwrap();
+    assert_eq!(pet["refsOut"], serde_json::json!(["#/$defs/Tag"]));
+
+    cmd()
+        .args(["gen-sdk", "--language", "python", "--layout", "connected"])
+        .args(["--schema", converted.to_str().unwrap()])
+        .args(["--package", "pet-sdk"])
+        .args(["--output", output.to_str().unwrap()])
+        .assert()
+        .success();
+    // Pet pulls in Tag, so both live in the `pet` sub-package.
+    assert!(output.join("pet_sdk/pet/pet.py").exists());
+    assert!(output.join("pet_sdk/pet/tag.py").exists());
+
+    cmd()
+        .args(["gen-sdk", "--language", "java", "--layout", "connected"])
+        .args(["--schema", converted.to_str().unwrap()])
+        .args(["--package", "com.example.pets"])
+        .args(["--output", dir.path().join("java").to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not java"));
+}
+
+#[test]
+fn test_gen_sdk_project_metadata() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let output = dir.path().join("sdks");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["gen-sdk", "--language", "rust,ruby"])
+        .args(["--input", input.to_str().unwrap()])
+        .args(["--package", "pet-sdk"])
+        .args(["--output", output.to_str().unwrap()])
+        .args(["--sdk-version", "3.0.0", "--license", "MIT"])
+        .args(["--author", "Jane Doe <jane@example.com>"])
+        .args(["--repository", "https://github.com/example/pet-sdk"])
+        .assert()
+        .success();
+
+    let cargo = fs::read_to_string(output.join("rust/Cargo.toml")).unwrap();
+    assert!(cargo.contains("version = \"3.0.0\"\n"));
+    assert!(cargo.contains("license = \"MIT\"\n"));
+    assert!(cargo.contains("authors = [\"Jane Doe <jane@example.com>\"]\n"));
+    assert!(cargo.contains("repository = \"https://github.com/example/pet-sdk\"\n"));
+
+    let gemspec = fs::read_to_string(output.join("ruby/pet-sdk.gemspec")).unwrap();
+    assert!(gemspec.contains("spec.version       = \"3.0.0\"\n"));
+    assert!(gemspec.contains("spec.authors       = [\"Jane Doe\"]\n"));
+    assert!(gemspec.contains("spec.email         = [\"jane@example.com\"]\n"));
+    assert!(gemspec.contains("spec.license       = \"MIT\"\n"));
+    assert!(gemspec.contains("spec.homepage      = \"https://github.com/example/pet-sdk\"\n"));
+}
+
+#[test]
+fn test_gen_sdk_project_metadata_escaped() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let output = dir.path().join("sdks");
+    fs::write(&input, schema_with_defs()).unwrap();
+    let version = r#"1.0.0-"a"&<#{v}>"#;
+    let license = r#"MIT & "X" <Y> #{exit} ${x}"#;
+    let author = r#"Jane "JD" & <Co> #{id}"#;
+    let email = r##"j&"#{x}"@x.io"##;
+    let repository = r#"https://x.io/?a=1&b="2"<#{x}>"#;
+
+    cmd()
+        .args([
+            "gen-sdk",
+            "--language",
+            "typescript,python,rust,java,ruby,kotlin",
+        ])
+        .args(["--input", input.to_str().unwrap()])
+        .args(["--package", "pet-sdk"])
+        .args(["--package", "java=com.example.pets"])
+        .args(["--package", "kotlin=com.example.pets"])
+        .args(["--output", output.to_str().unwrap()])
+        .args(["--sdk-version", version, "--license", license])
+        .args(["--author", &format!("{} <{}>", author, email)])
+        .args(["--repository", repository])
+        .assert()
+        .success();
+    let read = |path: &str| fs::read_to_string(output.join(path)).unwrap();
+
+    let package: serde_json::Value =
+        serde_json::from_str(&read("typescript/package.json")).unwrap();
+    assert_eq!(package["version"], version);
+    assert_eq!(package["license"], license);
+    assert_eq!(package["author"], format!("{} <{}>", author, email));
+    assert_eq!(package["repository"]["url"], repository);
+
+    // JSON string escapes are TOML basic-string escapes.
+    let toml = |s: &str| serde_json::to_string(s).unwrap();
+    let cargo = read("rust/Cargo.toml");
+    assert!(cargo.contains(&format!("license = {}\n", toml(license))));
+    assert!(cargo.contains(&format!("repository = {}\n", toml(repository))));
+    let pyproject = read("python/pyproject.toml");
+    assert!(pyproject.contains(&format!(
+        "{{ name = {}, email = {} }}",
+        toml(author),
+        toml(email)
+    )));
+    assert!(pyproject.contains(&format!("license = {{ text = {} }}", toml(license))));
+
+    let pom = read("java/pom.xml");
+    assert!(pom.contains("<name>MIT &amp; &quot;X&quot; &lt;Y&gt; #{exit} ${x}</name>"));
+    assert!(pom.contains("<name>Jane &quot;JD&quot; &amp; &lt;Co&gt; #{id}</name>"));
+    assert!(pom.contains("<url>https://x.io/?a=1&amp;b=&quot;2&quot;&lt;#{x}&gt;</url>"));
+
+    let gemspec = read("ruby/pet-sdk.gemspec");
+    assert!(gemspec.contains(r#"spec.license       = "MIT & \"X\" <Y> \#{exit} ${x}""#));
+    assert!(gemspec.contains(r#"spec.authors       = ["Jane \"JD\" & <Co> \#{id}"]"#));
+    assert!(gemspec.contains(r##"spec.email         = ["j&\"\#{x}\"@x.io"]"##));
+    assert!(!gemspec.contains("\"#{"));
+
+    let gradle = read("kotlin/build.gradle.kts");
+    assert!(gradle.contains(r#"name.set("MIT & \"X\" <Y> #{exit} \${x}")"#));
+}
+
+// ── gen-openapi ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_gen_openapi_components_fragment() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    let out_dir = dir.path().join("converted");
+    let fragment = dir.path().join("components.json");
+    fs::write(&input, schema_with_defs()).unwrap();
+
+    cmd()
+        .args(["convert", input.to_str().unwrap()])
+        .args(["--output-dir", out_dir.to_str().unwrap()])
+        .assert()
+        .success();
+    cmd()
+        .args(["gen-openapi", "--schema", out_dir.to_str().unwrap()])
+        .args(["--output", fragment.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&fragment).unwrap()).unwrap();
+    let schemas = parsed["components"]["schemas"].as_object().unwrap();
+    assert!(schemas.contains_key("Pet"));
+    assert!(schemas.contains_key("Tag"));
+}
+
+// ── Corpus ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_corpus_update_then_check() {
+    let dir = TempDir::new().unwrap();
+    let case = dir.path().join("simple");
+    fs::create_dir(&case).unwrap();
+    fs::write(case.join("input.json"), simple_schema()).unwrap();
+    let root = dir.path().to_str().unwrap();
+
+    cmd()
+        .args(["corpus", "check", "--dir", root])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("simple: has no expected.json"));
+
+    cmd()
+        .args(["corpus", "update", "--dir", root])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("updated simple"));
+    assert!(case.join("expected.json").exists());
+
+    cmd()
+        .args(["corpus", "check", "--dir", root])
+        .assert()
+        .success();
+
+    cmd()
+        .args(["corpus", "update", "--dir", root, "--case", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No corpus case named 'nope'"));
+}
+
+#[test]
+fn test_gen_conformance_writes_vectors() {
+    let dir = TempDir::new().unwrap();
+    let case = dir.path().join("corpus").join("simple");
+    fs::create_dir_all(&case).unwrap();
+    fs::write(case.join("input.json"), simple_schema()).unwrap();
+    fs::write(case.join("data.json"), r#"{"name": "Ada", "age": 36}"#).unwrap();
+    let out = dir.path().join("vectors");
+
+    cmd()
+        .args(["gen-conformance", "--out"])
+        .arg(&out)
+        .arg("--corpus")
+        .arg(dir.path().join("corpus"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("wrote 1 conformance vector(s)"));
+
+    for file in [
+        "input.json",
+        "options.json",
+        "expected-schema.json",
+        "codec.json",
+        "data.json",
+        "rehydrated.json",
+    ] {
+        assert!(out.join("simple").join(file).exists(), "missing {}", file);
+    }
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["cases"], serde_json::json!(["simple"]));
+}
+
+// ── Conformance (against a local stand-in for the provider) ─────────────────
+
+/// Serve one HTTP request with `status` and `body`; the handle yields the
+/// raw request text.
+fn serve_once(status: &'static str, body: String) -> (String, std::thread::JoinHandle<String>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = len.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let mut payload = vec![0; content_length];
+        reader.read_exact(&mut payload).unwrap();
+        request.push_str(&String::from_utf8(payload).unwrap());
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+        request
+    });
+    (base_url, handle)
+}
+
+#[test]
+fn test_conformance_pass() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+    let response = serde_json::json!({
+        "choices": [{ "message": { "content": "{\"name\": \"Ada\", \"age\": 36}" } }]
+    });
+    let (base_url, server) = serve_once("200 OK", response.to_string());
+
+    cmd()
+        .args(["conformance", "--schema", input.to_str().unwrap()])
+        .args(["--api-key", "test-key", "--base-url", &base_url])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[pass] provider accepted schema"))
+        .stdout(predicate::str::contains("PASS"));
+
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /v1/chat/completions"));
+    assert!(request.contains("Bearer test-key"));
+    assert!(request.contains("\"response_format\""));
+}
+
+#[test]
+fn test_conformance_reports_schema_rejection() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("schema.json");
+    fs::write(&input, simple_schema()).unwrap();
+    let error = serde_json::json!({ "error": { "message": "Invalid schema for response_format" } });
+    let (base_url, server) = serve_once("400 Bad Request", error.to_string());
+
+    cmd()
+        .args(["conformance", "--schema", input.to_str().unwrap()])
+        .args(["--api-key", "test-key", "--base-url", &base_url])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "[fail] provider accepted schema: HTTP 400: Invalid schema for response_format",
+        ))
+        .stdout(predicate::str::contains("FAIL"));
+    server.join().unwrap();
+}
@@ -0,0 +1,268 @@
+//! Smoke-test examples for generated SDKs.
+//!
+//! Each generator bundles one example per component next to its smoke-test
+//! suite: a minimal instance of the converted (LLM-facing) schema, as a model
+//! would return it, and the data the core rehydrator produces from it. The
+//! generated tests push `llmOutput` through the language binding's
+//! `rehydrate` and compare the result with `rehydrated`, so a binding that
+//! drifts from the core shows up as a failing test in the SDK itself.
+//!
+//! The instance is synthesized from the schema rather than taken from its
+//! `examples`, which describe the original shape: nullable values are
+//! `null`, enums and consts take their first value, scalars their zero value,
+//! arrays are empty and objects carry only their required properties.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use json_schema_llm_core::Codec;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::ManifestComponent;
+
+/// Nesting limit when following `$ref`s, so recursive schemas terminate.
+const MAX_DEPTH: usize = 32;
+
+/// A bundled smoke-test example for one component.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Example {
+    /// An instance of the converted schema, as the model would return it.
+    pub llm_output: Value,
+    /// The core rehydrator's output for `llm_output`.
+    pub rehydrated: Value,
+}
+
+/// Build the example for `component`, or `None` when its converted schema
+/// has no finite instance or its codec cannot rehydrate one.
+pub fn build(schema_dir: &Path, component: &ManifestComponent) -> Result<Option<Example>> {
+    let schema = crate::read_component_json(schema_dir, &component.schema_path)?;
+    let codec = crate::read_component_json(schema_dir, &component.codec_path)?;
+    let original = crate::read_component_json(schema_dir, &component.original_path)?;
+
+    let Ok(codec) = serde_json::from_value::<Codec>(codec) else {
+        return Ok(None);
+    };
+    let Some(llm_output) = instance(&schema, &schema, 0) else {
+        return Ok(None);
+    };
+    let Ok(result) = json_schema_llm_core::rehydrate(&llm_output, &codec, &original) else {
+        return Ok(None);
+    };
+    Ok(Some(Example {
+        llm_output,
+        rehydrated: result.data,
+    }))
+}
+
+/// Write the example for `component` to `path` as pretty JSON. Returns
+/// whether one was written.
+pub(crate) fn write(schema_dir: &Path, component: &ManifestComponent, path: &Path) -> Result<bool> {
+    let Some(example) = build(schema_dir, component)? else {
+        return Ok(false);
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(&example)?;
+    fs::write(path, content + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// A minimal instance of `schema`, resolving local `$ref`s against `root`.
+fn instance(schema: &Value, root: &Value, depth: usize) -> Option<Value> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let obj = match schema {
+        Value::Bool(true) => return Some(Value::Null),
+        Value::Object(obj) => obj,
+        _ => return None,
+    };
+
+    if let Some(target) = obj.get("$ref").and_then(Value::as_str) {
+        let resolved = target
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))?;
+        return instance(resolved, root, depth + 1);
+    }
+    if let Some(value) = obj.get("const") {
+        return Some(value.clone());
+    }
+    if let Some(first) = obj
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|v| v.first())
+    {
+        return Some(first.clone());
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(branches) = obj.get(keyword).and_then(Value::as_array) {
+            if branches.iter().any(is_null_schema) {
+                return Some(Value::Null);
+            }
+            return branches
+                .iter()
+                .find_map(|branch| instance(branch, root, depth + 1));
+        }
+    }
+
+    let ty = match obj.get("type") {
+        Some(Value::String(ty)) => ty.as_str(),
+        Some(Value::Array(types)) => {
+            if types.iter().any(|t| t == "null") {
+                return Some(Value::Null);
+            }
+            types.first().and_then(Value::as_str)?
+        }
+        _ if obj.contains_key("properties") => "object",
+        _ => return Some(Value::Null),
+    };
+    match ty {
+        "null" => Some(Value::Null),
+        "boolean" => Some(Value::Bool(false)),
+        "integer" | "number" => Some(Value::from(0)),
+        "string" => Some(Value::String(String::new())),
+        "array" => Some(Value::Array(Vec::new())),
+        "object" => {
+            let properties = obj.get("properties").and_then(Value::as_object);
+            let required = obj.get("required").and_then(Value::as_array);
+            let mut out = Map::new();
+            for name in required.into_iter().flatten().filter_map(Value::as_str) {
+                let property = properties
+                    .and_then(|p| p.get(name))
+                    .unwrap_or(&Value::Bool(true));
+                out.insert(name.to_string(), instance(property, root, depth + 1)?);
+            }
+            Some(Value::Object(out))
+        }
+        _ => None,
+    }
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// Write a `convert --output-dir` style directory with one component,
+/// `alert`, whose codec stringifies an integer enum.
+#[cfg(test)]
+pub(crate) fn write_schema_dir(dir: &Path) {
+    use serde_json::json;
+
+    let comp_dir = dir.join("alert");
+    fs::create_dir_all(&comp_dir).unwrap();
+    let schema = json!({
+        "type": "object",
+        "properties": { "level": { "type": "string", "enum": ["1", "high"] } },
+        "required": ["level"],
+        "additionalProperties": false
+    });
+    let codec = json!({
+        "$schema": "https://jsonschema-llm.dev/codec/v1",
+        "transforms": [{
+            "type": "enum_stringify",
+            "path": "#/properties/level",
+            "originalValues": [1, "high"]
+        }],
+        "droppedConstraints": []
+    });
+    let original = json!({
+        "type": "object",
+        "properties": { "level": { "enum": [1, "high"] } },
+        "required": ["level"]
+    });
+    fs::write(comp_dir.join("schema.json"), schema.to_string()).unwrap();
+    fs::write(comp_dir.join("codec.json"), codec.to_string()).unwrap();
+    fs::write(comp_dir.join("original.json"), original.to_string()).unwrap();
+
+    let manifest = json!({
+        "version": "1",
+        "generatedAt": "2026-01-01T00:00:00Z",
+        "sourceSchema": "alert.json",
+        "target": "openai-strict",
+        "mode": "strict",
+        "components": [{
+            "name": "alert",
+            "pointer": "#/$defs/alert",
+            "schemaPath": "alert/schema.json",
+            "codecPath": "alert/codec.json",
+            "originalPath": "alert/original.json",
+            "dependencyCount": 0
+        }]
+    });
+    fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_instance_fills_required_properties() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer" },
+                "name": { "type": "string" },
+                "level": { "type": "string", "enum": ["low", "high"] },
+                "note": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "owner": { "$ref": "#/$defs/Owner" },
+                "extra": { "type": "string" }
+            },
+            "required": ["id", "name", "level", "note", "tags", "owner"],
+            "$defs": {
+                "Owner": {
+                    "type": "object",
+                    "properties": { "active": { "type": "boolean" } },
+                    "required": ["active"]
+                }
+            }
+        });
+        assert_eq!(
+            instance(&schema, &schema, 0),
+            Some(json!({
+                "id": 0,
+                "name": "",
+                "level": "low",
+                "note": null,
+                "tags": [],
+                "owner": { "active": false }
+            }))
+        );
+    }
+
+    #[test]
+    fn test_instance_gives_up_on_unbounded_recursion() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "next": { "$ref": "#" } },
+            "required": ["next"]
+        });
+        assert_eq!(instance(&schema, &schema, 0), None);
+    }
+
+    #[test]
+    fn test_build_rehydrates_through_codec() {
+        let tmp = TempDir::new().unwrap();
+        write_schema_dir(tmp.path());
+        let manifest: crate::Manifest =
+            serde_json::from_str(&fs::read_to_string(tmp.path().join("manifest.json")).unwrap())
+                .unwrap();
+        let component = &manifest.components[0];
+
+        let example = build(tmp.path(), component).unwrap().unwrap();
+        assert_eq!(example.llm_output, json!({ "level": "1" }));
+        assert_eq!(example.rehydrated, json!({ "level": 1 }));
+
+        fs::write(tmp.path().join("alert/codec.json"), "{}").unwrap();
+        assert!(build(tmp.path(), component).unwrap().is_none());
+    }
+}
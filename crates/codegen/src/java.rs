@@ -6,7 +6,7 @@ use rust_embed::Embed;
 use serde::Serialize;
 use tera::Tera;
 
-use crate::{Manifest, SdkConfig};
+use crate::{Manifest, MetadataContext, SdkConfig};
 
 #[derive(Embed)]
 #[folder = "templates/java/"]
@@ -18,6 +18,7 @@ struct PomContext {
     group_id: String,
     artifact_id: String,
     engine_version: String,
+    metadata: MetadataContext,
 }
 
 /// Template context for the Generator facade class.
@@ -28,7 +29,7 @@ struct GeneratorContext {
 }
 
 /// Template context for a single component class.
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct ComponentContext {
     package_name: String,
     class_name: String,
@@ -41,6 +42,8 @@ struct ComponentContext {
 
 /// Generate a Java Maven SDK project.
 pub fn generate(config: &SdkConfig) -> Result<()> {
+    crate::ensure_flat_layout(config, "Java")?;
+
     // Read and parse manifest
     let manifest_path = config.schema_dir.join("manifest.json");
     let manifest_content = fs::read_to_string(&manifest_path)
@@ -48,16 +51,8 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     let manifest: Manifest =
         serde_json::from_str(&manifest_content).with_context(|| "Failed to parse manifest.json")?;
 
-    // Build Tera engine from embedded templates
-    let mut tera = Tera::default();
-    for file_name in JavaTemplates::iter() {
-        let file = JavaTemplates::get(&file_name)
-            .with_context(|| format!("Failed to load embedded template: {}", file_name))?;
-        let content = std::str::from_utf8(file.data.as_ref())
-            .with_context(|| format!("Template {} is not valid UTF-8", file_name))?;
-        tera.add_raw_template(&file_name, content)
-            .with_context(|| format!("Failed to register template: {}", file_name))?;
-    }
+    // Build Tera engine from embedded templates, with user overrides
+    let tera = crate::load_templates::<JavaTemplates>(config.template_dir.as_deref())?;
 
     // Create output directory
     fs::create_dir_all(&config.output_dir).with_context(|| {
@@ -72,6 +67,7 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         group_id: config.package.clone(),
         artifact_id: config.artifact_name.clone(),
         engine_version: env!("CARGO_PKG_VERSION").to_string(),
+        metadata: config.metadata.context("1.0.0-SNAPSHOT"),
     };
     render_to_file(
         &tera,
@@ -95,11 +91,15 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         )
     })?;
 
+    // Smoke-test examples are bundled as test resources
+    let examples_dir = config.output_dir.join("src/test/resources/examples");
+
     // Build component contexts and generate component classes
     let component_names: Vec<String> = manifest.components.iter().map(|c| c.name.clone()).collect();
     let resolved_components = crate::resolve_collisions(&component_names);
 
     let mut component_contexts = Vec::new();
+    let mut smoke_components = Vec::new();
     for (component, resolved) in manifest.components.iter().zip(resolved_components.iter()) {
         // Validate paths are relative and don't contain traversal
         for path in [&component.schema_path, &component.codec_path] {
@@ -140,6 +140,11 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         copy_schema_file(&config.schema_dir, &component.codec_path, &resources_dir)?;
         copy_schema_file(&config.schema_dir, &component.original_path, &resources_dir)?;
 
+        let example_path = examples_dir.join(format!("{}.json", class_name));
+        if crate::examples::write(&config.schema_dir, component, &example_path)? {
+            smoke_components.push(ctx.clone());
+        }
+
         component_contexts.push(ctx);
     }
 
@@ -163,6 +168,24 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         &src_dir.join("JsonPatchOp.java"),
     )?;
 
+    // Generate the smoke-test suite over the components with examples
+    if !smoke_components.is_empty() {
+        let smoke_ctx = GeneratorContext {
+            package_name: config.package.clone(),
+            components: smoke_components,
+        };
+        render_to_file(
+            &tera,
+            "SmokeTest.java.tera",
+            &smoke_ctx,
+            &config
+                .output_dir
+                .join("src/test/java")
+                .join(&package_dir)
+                .join("SmokeTest.java"),
+        )?;
+    }
+
     // Generate README
     let readme_ctx = tera::Context::from_serialize(&gen_ctx)?;
     let readme_content = tera.render("README.md.tera", &readme_ctx)?;
@@ -234,7 +257,7 @@ fn copy_schema_file(schema_dir: &Path, relative_path: &str, resources_dir: &Path
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::BuildTool;
+    use crate::{BuildTool, ModuleLayout, ProjectMetadata};
     use tempfile::TempDir;
 
     #[test]
@@ -293,6 +316,9 @@ mod tests {
             output_dir: output_dir.clone(),
             git_init: false,
             build_tool: BuildTool::Maven,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         generate(&config).expect("generate should succeed");
@@ -494,6 +520,9 @@ mod tests {
             output_dir,
             git_init: false,
             build_tool: BuildTool::Maven,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         let err =
@@ -504,4 +533,138 @@ mod tests {
             "error message should mention missing file, got: {msg}"
         );
     }
+
+    #[test]
+    fn template_dir_overrides_individual_templates() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        let comp_dir = schema_dir.join("user-profile");
+        fs::create_dir_all(&comp_dir).unwrap();
+        let manifest = serde_json::json!({
+            "version": "1",
+            "generatedAt": "2026-01-01T00:00:00Z",
+            "sourceSchema": "test.json",
+            "target": "openai-strict",
+            "mode": "strict",
+            "components": [
+                {
+                    "name": "user-profile",
+                    "pointer": "#/$defs/user-profile",
+                    "schemaPath": "user-profile/schema.json",
+                    "codecPath": "user-profile/codec.json",
+                    "originalPath": "user-profile/original.json",
+                    "dependencyCount": 0
+                }
+            ]
+        });
+        fs::write(schema_dir.join("manifest.json"), manifest.to_string()).unwrap();
+        for file in ["schema.json", "codec.json", "original.json"] {
+            fs::write(comp_dir.join(file), "{}").unwrap();
+        }
+
+        let template_dir = tmp.path().join("templates");
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(
+            template_dir.join("pom.xml.tera"),
+            "<!-- Copyright Example Corp -->\n<artifactId>{{ artifact_id }}</artifactId>\n",
+        )
+        .unwrap();
+        // Not a Java template; ignored.
+        fs::write(template_dir.join("setup.py.tera"), "{{ broken").unwrap();
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "com.example.test".to_string(),
+            artifact_name: "test-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Maven,
+            template_dir: Some(template_dir),
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let pom = fs::read_to_string(output_dir.join("pom.xml")).unwrap();
+        assert_eq!(
+            pom,
+            "<!-- Copyright Example Corp -->\n<artifactId>test-sdk</artifactId>\n"
+        );
+        // Built-ins fill in everything not overridden.
+        assert!(output_dir
+            .join("src/main/java/com/example/test/UserProfile.java")
+            .exists());
+    }
+
+    #[test]
+    fn generate_emits_smoke_tests() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "com.example.test".to_string(),
+            artifact_name: "test-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Maven,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let example: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(output_dir.join("src/test/resources/examples/Alert.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(example["llmOutput"], serde_json::json!({"level": "1"}));
+        assert_eq!(example["rehydrated"], serde_json::json!({"level": 1}));
+
+        let smoke =
+            fs::read_to_string(output_dir.join("src/test/java/com/example/test/SmokeTest.java"))
+                .unwrap();
+        assert!(smoke.contains("void roundTripAlert() throws Exception {"));
+    }
+
+    #[test]
+    fn generate_surfaces_project_metadata() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "com.example.test".to_string(),
+            artifact_name: "test-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Maven,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata {
+                version: Some("2.1.0".to_string()),
+                license: Some("MIT".to_string()),
+                authors: vec![
+                    "Jane Doe <jane@example.com>".to_string(),
+                    "Build Bot".to_string(),
+                ],
+                repository: Some("https://github.com/example/sdk".to_string()),
+            },
+        };
+        generate(&config).expect("generate should succeed");
+
+        let pom = fs::read_to_string(output_dir.join("pom.xml")).unwrap();
+        assert!(pom.contains("<version>2.1.0</version>"));
+        assert!(pom.contains("<name>MIT</name>"));
+        assert!(pom.contains("<name>Jane Doe</name>\n            <email>jane@example.com</email>"));
+        assert!(pom.contains("<name>Build Bot</name>\n        </developer>"));
+        assert!(pom.contains("<scm>\n        <url>https://github.com/example/sdk</url>"));
+    }
 }
@@ -0,0 +1,652 @@
+//! Kotlin SDK generator.
+//!
+//! Generates a Gradle (Kotlin DSL) project from converted schemas. Each
+//! component gets its own subpackage holding a schema accessor object and
+//! kotlinx.serialization data classes for the component's original shape;
+//! rehydration goes through the JVM binding (`SchemaLlmEngine`).
+//! Follows the same architecture as `java.rs` and `rust.rs`.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use heck::{ToLowerCamelCase, ToShoutySnakeCase};
+use rust_embed::Embed;
+use serde::Serialize;
+use serde_json::Value;
+use tera::Tera;
+
+use crate::types::{self, TypeDef, TypeKind, TypeRef};
+use crate::{Manifest, MetadataContext, SdkConfig};
+
+// ---------------------------------------------------------------------------
+// Embedded templates
+// ---------------------------------------------------------------------------
+
+#[derive(Embed)]
+#[folder = "templates/kotlin/"]
+struct KotlinTemplates;
+
+// ---------------------------------------------------------------------------
+// Template contexts
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+struct BuildContext {
+    group_id: String,
+    artifact_id: String,
+    engine_version: String,
+    metadata: MetadataContext,
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentContext {
+    package_name: String,
+    subpackage: String,
+    component_name: String,
+    /// `component_name` as a Kotlin string literal, quotes included.
+    component_literal: String,
+    enum_name: String,
+    accessor_name: String,
+    type_name: String,
+    schema_path: String,
+    codec_path: String,
+    original_path: String,
+    types: String,
+}
+
+#[derive(Serialize)]
+struct GeneratorContext {
+    package_name: String,
+    artifact_id: String,
+    components: Vec<ComponentContext>,
+}
+
+// ---------------------------------------------------------------------------
+// Generator
+// ---------------------------------------------------------------------------
+
+/// Generate a Kotlin Gradle SDK project.
+pub fn generate(config: &SdkConfig) -> Result<()> {
+    crate::ensure_flat_layout(config, "Kotlin")?;
+
+    // Read and parse manifest
+    let manifest_path = config.schema_dir.join("manifest.json");
+    let manifest_content = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read manifest at {}", manifest_path.display()))?;
+    let manifest: Manifest =
+        serde_json::from_str(&manifest_content).with_context(|| "Failed to parse manifest.json")?;
+
+    // Build Tera engine from embedded templates, with user overrides
+    let tera = crate::load_templates::<KotlinTemplates>(config.template_dir.as_deref())?;
+
+    let build_ctx = BuildContext {
+        group_id: config.package.clone(),
+        artifact_id: config.artifact_name.clone(),
+        engine_version: env!("CARGO_PKG_VERSION").to_string(),
+        metadata: config.metadata.context("1.0.0-SNAPSHOT"),
+    };
+    render_to_file(
+        &tera,
+        "build.gradle.kts.tera",
+        &build_ctx,
+        &config.output_dir.join("build.gradle.kts"),
+    )?;
+    render_to_file(
+        &tera,
+        "settings.gradle.kts.tera",
+        &build_ctx,
+        &config.output_dir.join("settings.gradle.kts"),
+    )?;
+
+    let src_dir = config
+        .output_dir
+        .join("src/main/kotlin")
+        .join(config.package.replace('.', "/"));
+    let resources_dir = config.output_dir.join("src/main/resources/schemas");
+
+    let component_names: Vec<String> = manifest.components.iter().map(|c| c.name.clone()).collect();
+    let resolved_components = crate::resolve_collisions(&component_names);
+
+    // Smoke-test examples are bundled as test resources
+    let examples_dir = config.output_dir.join("src/test/resources/examples");
+
+    let mut component_contexts = Vec::new();
+    let mut smoke_components = Vec::new();
+    for (component, resolved) in manifest.components.iter().zip(resolved_components.iter()) {
+        for rel in [
+            &component.schema_path,
+            &component.codec_path,
+            &component.original_path,
+        ] {
+            copy_schema_file(&config.schema_dir, rel, &resources_dir)?;
+        }
+        let original: Value = serde_json::from_str(&fs::read_to_string(
+            config.schema_dir.join(&component.original_path),
+        )?)
+        .with_context(|| format!("Invalid JSON in {}", component.original_path))?;
+
+        // Each component gets its own subpackage: components extracted from
+        // the same source share `$defs`, so their nested types would clash.
+        let subpackage = escape_package_segment(&resolved.module_name.replace('_', ""));
+        let accessor_name = format!("{}Component", resolved.class_name);
+        let (type_name, types) = emit_types(&original, &resolved.class_name, &accessor_name);
+
+        let ctx = ComponentContext {
+            package_name: config.package.clone(),
+            subpackage: subpackage.clone(),
+            component_name: resolved.original_name.clone(),
+            component_literal: string_literal(&resolved.original_name),
+            enum_name: resolved.enum_name.clone(),
+            accessor_name,
+            type_name,
+            // Paths are embedded in string literals, where `$defs` would
+            // otherwise be a template expression.
+            schema_path: escape_dollar(&component.schema_path),
+            codec_path: escape_dollar(&component.codec_path),
+            original_path: escape_dollar(&component.original_path),
+            types,
+        };
+        render_to_file(
+            &tera,
+            "Component.kt.tera",
+            &ctx,
+            &src_dir
+                .join(&subpackage)
+                .join(format!("{}.kt", resolved.class_name)),
+        )?;
+
+        let example_path = examples_dir.join(format!("{}.json", ctx.accessor_name));
+        if crate::examples::write(&config.schema_dir, component, &example_path)? {
+            smoke_components.push(ctx.clone());
+        }
+        component_contexts.push(ctx);
+    }
+
+    let gen_ctx = GeneratorContext {
+        package_name: config.package.clone(),
+        artifact_id: config.artifact_name.clone(),
+        components: component_contexts,
+    };
+    render_to_file(
+        &tera,
+        "Generator.kt.tera",
+        &gen_ctx,
+        &src_dir.join("SchemaGenerator.kt"),
+    )?;
+    if !smoke_components.is_empty() {
+        let smoke_ctx = GeneratorContext {
+            package_name: config.package.clone(),
+            artifact_id: config.artifact_name.clone(),
+            components: smoke_components,
+        };
+        render_to_file(
+            &tera,
+            "SmokeTest.kt.tera",
+            &smoke_ctx,
+            &config
+                .output_dir
+                .join("src/test/kotlin")
+                .join(config.package.replace('.', "/"))
+                .join("SmokeTest.kt"),
+        )?;
+    }
+    render_to_file(
+        &tera,
+        "README.md.tera",
+        &gen_ctx,
+        &config.output_dir.join("README.md"),
+    )?;
+    render_to_file(
+        &tera,
+        "gitignore.tera",
+        &gen_ctx,
+        &config.output_dir.join(".gitignore"),
+    )?;
+
+    // Optionally git init
+    if config.git_init {
+        std::process::Command::new("git")
+            .arg("init")
+            .current_dir(&config.output_dir)
+            .output()
+            .context("Failed to run git init")?;
+    }
+
+    Ok(())
+}
+
+/// Render a Tera template to a file.
+fn render_to_file<T: Serialize>(
+    tera: &Tera,
+    template_name: &str,
+    context: &T,
+    output_path: &Path,
+) -> Result<()> {
+    let ctx = tera::Context::from_serialize(context)?;
+    let rendered = tera
+        .render(template_name, &ctx)
+        .with_context(|| format!("Failed to render template: {}", template_name))?;
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(output_path, rendered)
+        .with_context(|| format!("Failed to write: {}", output_path.display()))?;
+
+    Ok(())
+}
+
+/// Copy a schema/codec file into the resources directory, preserving the
+/// relative path. Missing sources are a hard error.
+fn copy_schema_file(schema_dir: &Path, relative_path: &str, resources_dir: &Path) -> Result<()> {
+    if relative_path.contains("..") || relative_path.starts_with('/') {
+        anyhow::bail!(
+            "Invalid path in manifest: '{}' (must be relative, no traversal)",
+            relative_path
+        );
+    }
+    let src = schema_dir.join(relative_path);
+    let dst = resources_dir.join(relative_path);
+
+    if !src.exists() {
+        anyhow::bail!(
+            "Schema file not found: '{}' (referenced in manifest but missing from schema directory '{}')",
+            src.display(),
+            schema_dir.display()
+        );
+    }
+
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(&src, &dst)
+        .with_context(|| format!("Failed to copy {} to {}", src.display(), dst.display()))?;
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Name helpers
+// ---------------------------------------------------------------------------
+
+/// Kotlin hard keywords; these need backticks as identifiers.
+const KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Names the generated files already use; a schema type with one of these
+/// names would shadow it.
+const RESERVED_TYPES: &[&str] = &[
+    "Any",
+    "Boolean",
+    "Double",
+    "Int",
+    "Json",
+    "JsonElement",
+    "JsonNode",
+    "JsonNull",
+    "JsonObject",
+    "List",
+    "Long",
+    "Map",
+    "Nothing",
+    "SerialName",
+    "Serializable",
+    "String",
+    "Unit",
+];
+
+fn escape_package_segment(segment: &str) -> String {
+    match segment {
+        "" => "component".to_string(),
+        s if KEYWORDS.contains(&s) => format!("{s}_"),
+        s => s.to_string(),
+    }
+}
+
+/// A JSON property name as a lowerCamelCase property identifier.
+fn property_ident(key: &str) -> String {
+    let ident = crate::sanitize_identifier(key).to_lower_camel_case();
+    match ident.as_str() {
+        "" => "field".to_string(),
+        _ if ident.starts_with(|c: char| c.is_ascii_digit()) => format!("field{ident}"),
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("`{ident}`"),
+        _ => ident,
+    }
+}
+
+/// An enum value as a SHOUTY_SNAKE_CASE entry identifier.
+fn entry_ident(value: &str) -> String {
+    let ident = crate::sanitize_identifier(value).to_shouty_snake_case();
+    match ident.as_str() {
+        "" => "EMPTY".to_string(),
+        _ if ident.starts_with(|c: char| c.is_ascii_digit()) => format!("V_{ident}"),
+        _ => ident,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Type rendering
+// ---------------------------------------------------------------------------
+
+/// Render kotlinx.serialization types for a component's original schema.
+/// Returns the root type's name and the declarations.
+fn emit_types(schema: &Value, root_name: &str, accessor_name: &str) -> (String, String) {
+    let mut reserved = RESERVED_TYPES.to_vec();
+    reserved.push(accessor_name);
+    let defs = types::build(schema, root_name, &reserved);
+    let items: Vec<String> = defs.iter().map(render_def).collect();
+    (defs[0].name.clone(), items.join("\n"))
+}
+
+fn render_def(def: &TypeDef) -> String {
+    let mut out = String::new();
+    let name = &def.name;
+    match &def.kind {
+        TypeKind::Struct { fields, .. } => {
+            push_kdoc(&mut out, def.description.as_deref(), "");
+            push_deprecated(&mut out, def.deprecated, "");
+            out.push_str("@Serializable\n");
+            if fields.is_empty() {
+                out.push_str(&format!("class {name}\n"));
+                return out;
+            }
+            out.push_str(&format!("data class {name}(\n"));
+            let mut taken = HashSet::new();
+            for field in fields {
+                let ident = types::unique(property_ident(&field.key), &mut taken);
+                let ty = render_type(&field.ty);
+                push_kdoc(&mut out, field.description.as_deref(), "    ");
+                push_deprecated(&mut out, field.deprecated, "    ");
+                if ident.trim_matches('`') != field.key {
+                    out.push_str(&format!(
+                        "    @SerialName({})\n",
+                        string_literal(&field.key)
+                    ));
+                }
+                let decl = match (field.required, ty.ends_with('?')) {
+                    (true, _) => ty,
+                    (false, true) => format!("{ty} = null"),
+                    (false, false) => format!("{ty}? = null"),
+                };
+                out.push_str(&format!("    val {ident}: {decl},\n"));
+            }
+            out.push_str(")\n");
+        }
+        TypeKind::StringEnum(values) => {
+            push_kdoc(&mut out, def.description.as_deref(), "");
+            push_deprecated(&mut out, def.deprecated, "");
+            out.push_str(&format!("@Serializable\nenum class {name} {{\n"));
+            let mut taken = HashSet::new();
+            for value in values {
+                let ident = types::unique(entry_ident(value), &mut taken);
+                out.push_str(&format!("    @SerialName({})\n", string_literal(value)));
+                out.push_str(&format!("    {ident},\n"));
+            }
+            out.push_str("}\n");
+        }
+        TypeKind::Union(variants) => {
+            // kotlinx.serialization has no untagged unions; keep the raw
+            // element and say what it may hold.
+            let mut doc = def.description.clone().unwrap_or_default();
+            if !doc.is_empty() {
+                doc.push_str("\n\n");
+            }
+            let shapes: Vec<String> = variants.iter().map(|v| render_type(&v.ty)).collect();
+            doc.push_str(&format!("One of: {}.", shapes.join(", ")));
+            push_kdoc(&mut out, Some(&doc), "");
+            push_deprecated(&mut out, def.deprecated, "");
+            out.push_str(&format!("typealias {name} = JsonElement\n"));
+        }
+        TypeKind::Alias(ty) => {
+            push_kdoc(&mut out, def.description.as_deref(), "");
+            push_deprecated(&mut out, def.deprecated, "");
+            out.push_str(&format!("typealias {name} = {}\n", render_type(ty)));
+        }
+    }
+    out
+}
+
+fn render_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "String".to_string(),
+        TypeRef::Integer => "Long".to_string(),
+        TypeRef::Number => "Double".to_string(),
+        TypeRef::Boolean => "Boolean".to_string(),
+        TypeRef::Null => "JsonNull".to_string(),
+        TypeRef::Any => "JsonElement".to_string(),
+        TypeRef::Object => "JsonObject".to_string(),
+        TypeRef::Array(item) => format!("List<{}>", render_type(item)),
+        TypeRef::Map(value) => format!("Map<String, {}>", render_type(value)),
+        TypeRef::Optional(inner) => {
+            let inner = render_type(inner);
+            if inner.ends_with('?') {
+                inner
+            } else {
+                format!("{inner}?")
+            }
+        }
+        TypeRef::Named { name, .. } => name.clone(),
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    escape_dollar(&serde_json::to_string(value).unwrap_or_default())
+}
+
+/// Escape `$` for use inside a Kotlin string literal.
+fn escape_dollar(value: &str) -> String {
+    value.replace('$', "${'$'}")
+}
+
+fn push_kdoc(out: &mut String, text: Option<&str>, indent: &str) {
+    let Some(text) = text.filter(|t| !t.is_empty()) else {
+        return;
+    };
+    let text = text.replace("*/", "*&#47;");
+    let lines: Vec<&str> = text.lines().collect();
+    if let [line] = lines.as_slice() {
+        out.push_str(&format!("{indent}/** {line} */\n"));
+        return;
+    }
+    out.push_str(&format!("{indent}/**\n"));
+    for line in lines {
+        if line.is_empty() {
+            out.push_str(&format!("{indent} *\n"));
+        } else {
+            out.push_str(&format!("{indent} * {line}\n"));
+        }
+    }
+    out.push_str(&format!("{indent} */\n"));
+}
+
+fn push_deprecated(out: &mut String, deprecated: bool, indent: &str) {
+    if deprecated {
+        out.push_str(&format!(
+            "{indent}@Deprecated(\"Deprecated in the source schema\")\n"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildTool, ModuleLayout, ProjectMetadata};
+    use serde_json::json;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_emit_types_data_class_and_enum() {
+        let (name, types) = emit_types(
+            &json!({
+                "type": "object",
+                "description": "A user.",
+                "properties": {
+                    "display_name": { "type": "string" },
+                    "role": { "enum": ["admin", "read-only"] },
+                    "age": { "type": ["integer", "null"] },
+                    "in": { "type": "boolean" }
+                },
+                "required": ["display_name", "role"]
+            }),
+            "User",
+            "UserComponent",
+        );
+        assert_eq!(name, "User");
+        assert!(types.contains("/** A user. */\n@Serializable\ndata class User(\n"));
+        assert!(types.contains("    val age: Long? = null,\n"));
+        assert!(types.contains("    @SerialName(\"display_name\")\n    val displayName: String,\n"));
+        assert!(types.contains("    val `in`: Boolean? = null,\n"));
+        assert!(types.contains("    val role: UserRole,\n"));
+        assert!(types.contains(
+            "enum class UserRole {\n    @SerialName(\"admin\")\n    ADMIN,\n    @SerialName(\"read-only\")\n    READ_ONLY,\n}"
+        ));
+    }
+
+    #[test]
+    fn test_emit_types_union_falls_back_to_json_element() {
+        let (_, types) = emit_types(
+            &json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] }),
+            "Id",
+            "IdComponent",
+        );
+        assert!(types.contains("/** One of: String, Long. */\ntypealias Id = JsonElement\n"));
+    }
+
+    #[test]
+    fn test_generate_creates_gradle_project() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        let comp_dir = schema_dir.join("user-profile");
+        fs::create_dir_all(&comp_dir).unwrap();
+        let manifest = json!({
+            "version": "1",
+            "generatedAt": "2026-01-01T00:00:00Z",
+            "sourceSchema": "test-schema.json",
+            "target": "openai-strict",
+            "mode": "strict",
+            "components": [{
+                "name": "user-profile",
+                "pointer": "#/$defs/user-profile",
+                "schemaPath": "user-profile/schema.json",
+                "codecPath": "user-profile/codec.json",
+                "originalPath": "user-profile/original.json",
+                "dependencyCount": 0
+            }]
+        });
+        fs::write(schema_dir.join("manifest.json"), manifest.to_string()).unwrap();
+        fs::write(comp_dir.join("schema.json"), r#"{"type":"object"}"#).unwrap();
+        fs::write(comp_dir.join("codec.json"), r#"{"transforms":[]}"#).unwrap();
+        fs::write(
+            comp_dir.join("original.json"),
+            r#"{"type":"object","properties":{"name":{"type":"string"}},"required":["name"]}"#,
+        )
+        .unwrap();
+
+        let output_dir = tmp.path().join("out");
+        let config = SdkConfig {
+            package: "com.example.sdk".to_string(),
+            artifact_name: "sdk".to_string(),
+            schema_dir: PathBuf::from(&schema_dir),
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Gradle,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generation should succeed");
+
+        assert!(output_dir.join("settings.gradle.kts").exists());
+        assert!(output_dir.join(".gitignore").exists());
+        assert!(output_dir
+            .join("src/main/resources/schemas/user-profile/original.json")
+            .exists());
+
+        let build = fs::read_to_string(output_dir.join("build.gradle.kts")).unwrap();
+        assert!(build.contains("kotlinx-serialization-json"));
+        assert!(build.contains("group = \"com.example.sdk\""));
+
+        let src = output_dir.join("src/main/kotlin/com/example/sdk");
+        let component = fs::read_to_string(src.join("userprofile/UserProfile.kt")).unwrap();
+        assert!(component.contains("package com.example.sdk.userprofile\n"));
+        assert!(component.contains("object UserProfileComponent {"));
+        assert!(component.contains("data class UserProfile(\n    val name: String,\n)"));
+        assert!(component
+            .contains("fun rehydrate(engine: SchemaLlmEngine, llmOutput: JsonNode): UserProfile"));
+
+        let generator = fs::read_to_string(src.join("SchemaGenerator.kt")).unwrap();
+        assert!(generator.contains("USER_PROFILE(\"user-profile\")"));
+    }
+
+    #[test]
+    fn test_string_literal_escapes_templates() {
+        assert_eq!(string_literal("$defs/a\"b"), "\"${'$'}defs/a\\\"b\"");
+    }
+
+    #[test]
+    fn test_generate_emits_smoke_tests() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "com.example.sdk".to_string(),
+            artifact_name: "sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Gradle,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let example: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(output_dir.join("src/test/resources/examples/AlertComponent.json"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(example["llmOutput"], serde_json::json!({"level": "1"}));
+        assert_eq!(example["rehydrated"], serde_json::json!({"level": 1}));
+
+        let smoke =
+            fs::read_to_string(output_dir.join("src/test/kotlin/com/example/sdk/SmokeTest.kt"))
+                .unwrap();
+        assert!(smoke.contains("import com.example.sdk.alert.AlertComponent\n"));
+    }
+}
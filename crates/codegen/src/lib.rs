@@ -1,11 +1,17 @@
+pub mod examples;
 pub mod java;
+pub mod kotlin;
+pub mod openapi;
 pub mod python;
 pub mod ruby;
+pub mod rust;
+pub mod swift;
+pub mod types;
 pub mod typescript;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +22,9 @@ pub enum BuildTool {
     Setuptools,
     Npm,
     Bundler,
+    Cargo,
+    Gradle,
+    Swiftpm,
 }
 
 impl std::fmt::Display for BuildTool {
@@ -25,6 +34,36 @@ impl std::fmt::Display for BuildTool {
             BuildTool::Setuptools => write!(f, "setuptools"),
             BuildTool::Npm => write!(f, "npm"),
             BuildTool::Bundler => write!(f, "bundler"),
+            BuildTool::Cargo => write!(f, "cargo"),
+            BuildTool::Gradle => write!(f, "gradle"),
+            BuildTool::Swiftpm => write!(f, "swiftpm"),
+        }
+    }
+}
+
+/// How component modules are arranged in the generated package.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModuleLayout {
+    /// One module per component, side by side.
+    #[default]
+    Flat,
+    /// Sub-packages named after each component's pointer prefix:
+    /// `#/components/schemas/User` goes under `components/schemas`,
+    /// `#/$defs/Tag` under `defs`.
+    PointerPrefix,
+    /// One sub-package per group of components linked by `$ref`s (the
+    /// manifest's `refsOut`), named after the member with the most
+    /// dependencies. Components linked to no other stay at the top level.
+    Connected,
+}
+
+impl std::fmt::Display for ModuleLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModuleLayout::Flat => write!(f, "flat"),
+            ModuleLayout::PointerPrefix => write!(f, "pointer-prefix"),
+            ModuleLayout::Connected => write!(f, "connected"),
         }
     }
 }
@@ -44,6 +83,85 @@ pub struct SdkConfig {
     pub git_init: bool,
     /// Build tool to use
     pub build_tool: BuildTool,
+    /// Directory of Tera templates overriding built-ins of the same file name
+    /// (e.g. `pom.xml.tera`, `README.md.tera`); built-ins fill in the rest
+    pub template_dir: Option<PathBuf>,
+    /// Arrangement of component modules; only Python and TypeScript
+    /// support layouts other than [`ModuleLayout::Flat`]
+    pub layout: ModuleLayout,
+    /// Version, license, authors, and repository of the generated package
+    pub metadata: ProjectMetadata,
+}
+
+/// Package metadata surfaced in every generator's project file.
+///
+/// Unset fields fall back to each ecosystem's previous defaults (e.g.
+/// `1.0.0-SNAPSHOT` for Maven, `0.1.0` for npm) or are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectMetadata {
+    /// Package version, e.g. "2.1.0"
+    pub version: Option<String>,
+    /// SPDX license identifier, e.g. "MIT"
+    pub license: Option<String>,
+    /// Authors as `Name` or `Name <email>`
+    pub authors: Vec<String>,
+    /// Source repository URL
+    pub repository: Option<String>,
+}
+
+impl ProjectMetadata {
+    /// Template context, with `default_version` standing in for an unset
+    /// version.
+    pub(crate) fn context(&self, default_version: &str) -> MetadataContext {
+        MetadataContext {
+            version: self
+                .version
+                .clone()
+                .unwrap_or_else(|| default_version.to_string()),
+            license: self.license.clone(),
+            authors: self
+                .authors
+                .iter()
+                .map(|a| AuthorContext::parse(a))
+                .collect(),
+            repository: self.repository.clone(),
+        }
+    }
+}
+
+/// [`ProjectMetadata`] as the project-file templates see it.
+#[derive(Debug, Serialize)]
+pub(crate) struct MetadataContext {
+    version: String,
+    license: Option<String>,
+    authors: Vec<AuthorContext>,
+    repository: Option<String>,
+}
+
+/// One author, split for ecosystems that keep name and email apart.
+#[derive(Debug, Serialize)]
+pub(crate) struct AuthorContext {
+    /// The author as given.
+    full: String,
+    name: String,
+    email: Option<String>,
+}
+
+impl AuthorContext {
+    /// Parse `Name <email>`; anything else is a bare name.
+    fn parse(author: &str) -> Self {
+        let author = author.trim();
+        let split = author
+            .strip_suffix('>')
+            .and_then(|rest| rest.rsplit_once('<'))
+            .map(|(name, email)| (name.trim().to_string(), Some(email.trim().to_string())));
+        let (name, email) = split.unwrap_or_else(|| (author.to_string(), None));
+        AuthorContext {
+            full: author.to_string(),
+            name,
+            email,
+        }
+    }
 }
 
 /// A component entry from manifest.json.
@@ -56,6 +174,12 @@ pub struct ManifestComponent {
     pub codec_path: String,
     pub original_path: String,
     pub dependency_count: usize,
+    /// `schema_fingerprint` of the component's original schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    /// Pointers of the components this one `$ref`s directly.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refs_out: Vec<String>,
 }
 
 /// Parsed manifest.json structure.
@@ -63,8 +187,13 @@ pub struct ManifestComponent {
 #[serde(rename_all = "camelCase")]
 pub struct Manifest {
     pub version: String,
-    pub generated_at: String,
+    /// Absent in `--reproducible` manifests without `SOURCE_DATE_EPOCH`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<String>,
     pub source_schema: String,
+    /// `schema_fingerprint` of the source schema and conversion options.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
     pub target: String,
     pub mode: String,
     pub components: Vec<ManifestComponent>,
@@ -77,6 +206,106 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         BuildTool::Setuptools => python::generate(config),
         BuildTool::Npm => typescript::generate(config),
         BuildTool::Bundler => ruby::generate(config),
+        BuildTool::Cargo => rust::generate(config),
+        BuildTool::Gradle => kotlin::generate(config),
+        BuildTool::Swiftpm => swift::generate(config),
+    }
+}
+
+/// Fail unless `config` asks for the flat layout, for generators that
+/// support no other.
+pub(crate) fn ensure_flat_layout(config: &SdkConfig, language: &str) -> Result<()> {
+    if config.layout != ModuleLayout::Flat {
+        anyhow::bail!(
+            "Module layout '{}' is not supported for {}; use 'flat'",
+            config.layout,
+            language
+        );
+    }
+    Ok(())
+}
+
+/// The sub-package of each manifest component under `layout`, as snake_case
+/// path segments; empty for the top level. Names are safe as both Python
+/// package and directory names.
+pub fn module_groups(manifest: &Manifest, layout: ModuleLayout) -> Vec<Vec<String>> {
+    let components = &manifest.components;
+    match layout {
+        ModuleLayout::Flat => vec![Vec::new(); components.len()],
+        ModuleLayout::PointerPrefix => components
+            .iter()
+            .map(|component| {
+                let mut segments: Vec<&str> = component
+                    .pointer
+                    .trim_start_matches('#')
+                    .split('/')
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                segments.pop();
+                segments
+                    .into_iter()
+                    .map(|s| group_segment(&s.replace("~1", "/").replace("~0", "~")))
+                    .collect()
+            })
+            .collect(),
+        ModuleLayout::Connected => {
+            // Union-find over the `$ref` edges between listed components.
+            let index: std::collections::HashMap<&str, usize> = components
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (c.pointer.as_str(), i))
+                .collect();
+            let mut parent: Vec<usize> = (0..components.len()).collect();
+            fn find(parent: &mut [usize], i: usize) -> usize {
+                let mut root = i;
+                while parent[root] != root {
+                    root = parent[root];
+                }
+                parent[i] = root;
+                root
+            }
+            for (i, component) in components.iter().enumerate() {
+                for target in &component.refs_out {
+                    if let Some(&j) = index.get(target.as_str()) {
+                        let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                        parent[a] = b;
+                    }
+                }
+            }
+
+            let roots: Vec<usize> = (0..components.len())
+                .map(|i| find(&mut parent, i))
+                .collect();
+            let names = resolve_collisions(components.iter().map(|c| &c.name));
+            // Name each group after its member with the most dependencies,
+            // the first in manifest order on a tie.
+            let mut groups: std::collections::HashMap<usize, (usize, usize)> =
+                std::collections::HashMap::new();
+            for (i, &root) in roots.iter().enumerate() {
+                let (best, size) = groups.entry(root).or_insert((i, 0));
+                *size += 1;
+                if components[i].dependency_count > components[*best].dependency_count {
+                    *best = i;
+                }
+            }
+            roots
+                .iter()
+                .map(|root| match groups[root] {
+                    (best, size) if size > 1 => vec![names[best].module_name.clone()],
+                    _ => Vec::new(),
+                })
+                .collect()
+        }
+    }
+}
+
+/// `segment` as a snake_case package name.
+fn group_segment(segment: &str) -> String {
+    let name = sanitize_identifier(segment).to_snake_case();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
     }
 }
 
@@ -102,6 +331,103 @@ pub fn sanitize_identifier(name: &str) -> String {
     sanitized
 }
 
+/// Read and parse a manifest-relative JSON file from `schema_dir`, rejecting
+/// absolute paths and `..` traversal.
+pub(crate) fn read_component_json(
+    schema_dir: &Path,
+    relative_path: &str,
+) -> Result<serde_json::Value> {
+    if relative_path.contains("..") || relative_path.starts_with('/') {
+        anyhow::bail!(
+            "Invalid path in manifest: '{}' (must be relative, no traversal)",
+            relative_path
+        );
+    }
+    let path = schema_dir.join(relative_path);
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid JSON in {}", relative_path))
+}
+
+/// Build a Tera engine from a generator's embedded templates. A file in
+/// `template_dir` named like a built-in (e.g. `pom.xml.tera`) replaces it;
+/// other files there are ignored, so one directory can serve several
+/// languages.
+pub(crate) fn load_templates<T: rust_embed::RustEmbed>(
+    template_dir: Option<&Path>,
+) -> Result<tera::Tera> {
+    if let Some(dir) = template_dir {
+        if !dir.is_dir() {
+            anyhow::bail!("Template directory not found: {}", dir.display());
+        }
+    }
+    let mut tera = tera::Tera::default();
+    for file_name in T::iter() {
+        let override_path = template_dir
+            .map(|dir| dir.join(file_name.as_ref()))
+            .filter(|path| path.is_file());
+        let content = match override_path {
+            Some(path) => std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read template override: {}", path.display()))?,
+            None => {
+                let file = T::get(&file_name)
+                    .with_context(|| format!("Failed to load embedded template: {}", file_name))?;
+                String::from_utf8(file.data.into_owned())
+                    .with_context(|| format!("Template {} is not valid UTF-8", file_name))?
+            }
+        };
+        tera.add_raw_template(&file_name, &content)
+            .with_context(|| format!("Failed to register template: {}", file_name))?;
+    }
+    tera.register_filter("ruby_string", string_filter(ruby_string));
+    tera.register_filter("kotlin_string", string_filter(kotlin_string));
+    Ok(tera)
+}
+
+/// A Tera filter rendering a string argument as a quoted literal.
+fn string_filter(quote: fn(&str) -> String) -> impl tera::Filter {
+    move |value: &tera::Value, _: &std::collections::HashMap<String, tera::Value>| {
+        let s = value
+            .as_str()
+            .ok_or_else(|| tera::Error::msg(format!("expected a string, got {}", value)))?;
+        Ok(tera::Value::String(quote(s)))
+    }
+}
+
+/// A double-quoted Ruby string literal; `#` is escaped so `#{...}` in the
+/// text is not interpolated.
+fn ruby_string(s: &str) -> String {
+    quote_escaped(s, |c| match c {
+        '\\' | '"' | '#' => Some(format!("\\{}", c)),
+        _ => None,
+    })
+}
+
+/// A Kotlin string literal; `$` is escaped so templates in the text are not
+/// evaluated.
+fn kotlin_string(s: &str) -> String {
+    quote_escaped(s, |c| match c {
+        '\\' | '"' | '$' => Some(format!("\\{}", c)),
+        _ => None,
+    })
+}
+
+/// Wrap `s` in double quotes, escaping characters per `escape` and control
+/// characters as `\uXXXX`.
+fn quote_escaped(s: &str, escape: impl Fn(char) -> Option<String>) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match escape(c) {
+            Some(escaped) => out.push_str(&escaped),
+            None if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            None => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
 /// A wrapper struct for resolving identifier collisions.
 #[derive(Debug, Clone)]
 pub struct ResolvedComponent {
@@ -243,4 +569,83 @@ mod tests {
         assert_eq!(resolved[2].module_name, "user_profile");
         assert_eq!(resolved[3].module_name, "reference_3");
     }
+
+    fn manifest(components: &[(&str, &str, usize, &[&str])]) -> Manifest {
+        Manifest {
+            version: "1".to_string(),
+            generated_at: Some("2026-01-01T00:00:00Z".to_string()),
+            source_schema: "api.json".to_string(),
+            fingerprint: None,
+            target: "openai-strict".to_string(),
+            mode: "strict".to_string(),
+            components: components
+                .iter()
+                .map(
+                    |(name, pointer, dependency_count, refs_out)| ManifestComponent {
+                        name: name.to_string(),
+                        pointer: pointer.to_string(),
+                        schema_path: format!("{}/schema.json", name),
+                        codec_path: format!("{}/codec.json", name),
+                        original_path: format!("{}/original.json", name),
+                        dependency_count: *dependency_count,
+                        fingerprint: None,
+                        refs_out: refs_out.iter().map(|r| r.to_string()).collect(),
+                    },
+                )
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_metadata_context() {
+        let metadata = ProjectMetadata {
+            authors: vec!["Jane Doe <jane@example.com>".into(), "Bot".into()],
+            ..ProjectMetadata::default()
+        };
+        let ctx = metadata.context("0.1.0");
+        assert_eq!(ctx.version, "0.1.0");
+        assert_eq!(ctx.authors[0].name, "Jane Doe");
+        assert_eq!(ctx.authors[0].email.as_deref(), Some("jane@example.com"));
+        assert_eq!(ctx.authors[0].full, "Jane Doe <jane@example.com>");
+        assert_eq!(ctx.authors[1].name, "Bot");
+        assert_eq!(ctx.authors[1].email, None);
+
+        let pinned = ProjectMetadata {
+            version: Some("2.0.0".into()),
+            ..metadata
+        };
+        assert_eq!(pinned.context("0.1.0").version, "2.0.0");
+    }
+
+    #[test]
+    fn test_string_literal_filters() {
+        let text = "Jane \"JD\" #{`id`} $x\\\n";
+        assert_eq!(ruby_string(text), r#""Jane \"JD\" \#{`id`} $x\\\u000a""#);
+        assert_eq!(kotlin_string(text), r#""Jane \"JD\" #{`id`} \$x\\\u000a""#);
+    }
+
+    #[test]
+    fn test_module_groups() {
+        let manifest = manifest(&[
+            ("Order", "#/components/schemas/Order", 2, &["#/$defs/Item"]),
+            ("Item", "#/$defs/Item", 1, &["#/$defs/Money"]),
+            ("Money", "#/$defs/Money", 0, &[]),
+            ("Ping", "#/$defs/Ping", 0, &[]),
+        ]);
+
+        let flat = module_groups(&manifest, ModuleLayout::Flat);
+        assert!(flat.iter().all(Vec::is_empty));
+
+        let by_prefix = module_groups(&manifest, ModuleLayout::PointerPrefix);
+        assert_eq!(by_prefix[0], ["components", "schemas"]);
+        assert_eq!(by_prefix[1], ["defs"]);
+
+        // Order, Item and Money are linked and named after Order, which has
+        // the most dependencies; Ping stays at the top level.
+        let connected = module_groups(&manifest, ModuleLayout::Connected);
+        assert_eq!(connected[0], ["order"]);
+        assert_eq!(connected[1], ["order"]);
+        assert_eq!(connected[2], ["order"]);
+        assert!(connected[3].is_empty());
+    }
 }
@@ -0,0 +1,249 @@
+//! OpenAPI components back-port.
+//!
+//! Collects the converted component schemas from a `convert --output-dir`
+//! directory into an OpenAPI 3.1 `components.schemas` fragment, for teams
+//! that document the LLM-facing contract next to their API. Each component
+//! keeps its descriptions (a root description or title dropped by conversion
+//! is restored from `original.json`), enums the conversion stringified carry
+//! their original values, and internal `$ref`s are re-rooted under the
+//! component so they resolve inside the OpenAPI document.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::Manifest;
+
+/// Extension keyword holding the pre-stringification values of an enum.
+pub const ORIGINAL_ENUM_EXTENSION: &str = "x-original-enum";
+
+/// Build a `{"components": {"schemas": {...}}}` fragment from the manifest
+/// and component files in `schema_dir`.
+pub fn generate(schema_dir: &Path) -> Result<Value> {
+    let manifest_path = schema_dir.join("manifest.json");
+    let manifest_content = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read manifest at {}", manifest_path.display()))?;
+    let manifest: Manifest =
+        serde_json::from_str(&manifest_content).with_context(|| "Failed to parse manifest.json")?;
+
+    let mut schemas = Map::new();
+    let mut taken = HashSet::new();
+    for component in &manifest.components {
+        let mut schema = crate::read_component_json(schema_dir, &component.schema_path)?;
+        let codec = crate::read_component_json(schema_dir, &component.codec_path)?;
+        let original = crate::read_component_json(schema_dir, &component.original_path)?;
+
+        annotate_stringified_enums(&mut schema, &codec);
+        restore_root_docs(&mut schema, &original);
+        if let Some(obj) = schema.as_object_mut() {
+            obj.remove("$schema");
+        }
+
+        let key = crate::types::unique(component_key(&component.name), &mut taken);
+        reroot_refs(&mut schema, &format!("#/components/schemas/{key}"));
+        schemas.insert(key, schema);
+    }
+
+    Ok(json!({ "components": { "schemas": schemas } }))
+}
+
+/// A component name as an OpenAPI component key (`^[a-zA-Z0-9._-]+$`).
+fn component_key(name: &str) -> String {
+    let key: String = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+    match key.as_str() {
+        "" => "Component".to_string(),
+        _ => key,
+    }
+}
+
+/// Record the original values of every enum listed as `enum_stringify` in
+/// the codec, as an extension keyword and a note in the description.
+fn annotate_stringified_enums(schema: &mut Value, codec: &Value) {
+    let transforms = codec.get("transforms").and_then(Value::as_array);
+    for transform in transforms.into_iter().flatten() {
+        if transform.get("type").and_then(Value::as_str) != Some("enum_stringify") {
+            continue;
+        }
+        let (Some(path), Some(values)) = (
+            transform.get("path").and_then(Value::as_str),
+            transform.get("originalValues").and_then(Value::as_array),
+        ) else {
+            continue;
+        };
+        let Some(Value::Object(node)) = schema.pointer_mut(path.trim_start_matches('#')) else {
+            continue;
+        };
+
+        let listed: Vec<String> = values.iter().map(Value::to_string).collect();
+        let note = format!("Sent as strings; original values: {}.", listed.join(", "));
+        let description = match node.get("description").and_then(Value::as_str) {
+            Some(existing) if !existing.is_empty() => format!("{existing}\n\n{note}"),
+            _ => note,
+        };
+        node.insert("description".to_string(), Value::String(description));
+        node.insert(
+            ORIGINAL_ENUM_EXTENSION.to_string(),
+            Value::Array(values.clone()),
+        );
+    }
+}
+
+/// Copy the original root `title`/`description` onto the converted schema
+/// where conversion left none.
+fn restore_root_docs(schema: &mut Value, original: &Value) {
+    let Some(obj) = schema.as_object_mut() else {
+        return;
+    };
+    for key in ["title", "description"] {
+        if obj.contains_key(key) {
+            continue;
+        }
+        if let Some(value) = original.get(key).filter(|v| v.is_string()) {
+            obj.insert(key.to_string(), value.clone());
+        }
+    }
+}
+
+/// Rewrite document-local `$ref`s (`#`, `#/...`) to point under `base`.
+fn reroot_refs(value: &mut Value, base: &str) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                match child {
+                    Value::String(target) if key == "$ref" && target.starts_with('#') => {
+                        *target = format!("{base}{}", &target[1..]);
+                    }
+                    _ => reroot_refs(child, base),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| reroot_refs(item, base)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_component(dir: &Path, name: &str, schema: Value, codec: Value, original: Value) {
+        let comp_dir = dir.join(name);
+        fs::create_dir_all(&comp_dir).unwrap();
+        fs::write(comp_dir.join("schema.json"), schema.to_string()).unwrap();
+        fs::write(comp_dir.join("codec.json"), codec.to_string()).unwrap();
+        fs::write(comp_dir.join("original.json"), original.to_string()).unwrap();
+    }
+
+    fn write_manifest(dir: &Path, names: &[&str]) {
+        let components: Vec<Value> = names
+            .iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "pointer": format!("#/$defs/{name}"),
+                    "schemaPath": format!("{name}/schema.json"),
+                    "codecPath": format!("{name}/codec.json"),
+                    "originalPath": format!("{name}/original.json"),
+                    "dependencyCount": 0
+                })
+            })
+            .collect();
+        let manifest = json!({
+            "version": "1",
+            "generatedAt": "2026-01-01T00:00:00Z",
+            "sourceSchema": "test-schema.json",
+            "target": "openai-strict",
+            "mode": "strict",
+            "components": components
+        });
+        fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_generate_annotates_stringified_enums() {
+        let tmp = TempDir::new().unwrap();
+        write_component(
+            tmp.path(),
+            "Alert",
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "level": {
+                        "type": "string",
+                        "description": "Severity.",
+                        "enum": ["1", "high"]
+                    }
+                },
+                "required": ["level"],
+                "additionalProperties": false
+            }),
+            json!({
+                "transforms": [{
+                    "type": "enum_stringify",
+                    "path": "#/properties/level",
+                    "originalValues": [1, "high"]
+                }]
+            }),
+            json!({ "description": "An alert.", "type": "object" }),
+        );
+        write_manifest(tmp.path(), &["Alert"]);
+
+        let fragment = generate(tmp.path()).unwrap();
+        let alert = &fragment["components"]["schemas"]["Alert"];
+        assert!(alert.get("$schema").is_none());
+        assert_eq!(alert["description"], "An alert.");
+        let level = &alert["properties"]["level"];
+        assert_eq!(
+            level["description"],
+            "Severity.\n\nSent as strings; original values: 1, \"high\"."
+        );
+        assert_eq!(level[ORIGINAL_ENUM_EXTENSION], json!([1, "high"]));
+    }
+
+    #[test]
+    fn test_generate_reroots_refs_and_sanitizes_keys() {
+        let tmp = TempDir::new().unwrap();
+        write_component(
+            tmp.path(),
+            "tree node",
+            json!({
+                "type": "object",
+                "properties": {
+                    "child": { "$ref": "#/$defs/Leaf" },
+                    "parent": { "$ref": "#" },
+                    "remote": { "$ref": "https://example.com/x.json" }
+                },
+                "$defs": { "Leaf": { "type": "string" } }
+            }),
+            json!({ "transforms": [] }),
+            json!({}),
+        );
+        write_manifest(tmp.path(), &["tree node"]);
+
+        let fragment = generate(tmp.path()).unwrap();
+        let node = &fragment["components"]["schemas"]["tree_node"];
+        assert_eq!(
+            node["properties"]["child"]["$ref"],
+            "#/components/schemas/tree_node/$defs/Leaf"
+        );
+        assert_eq!(
+            node["properties"]["parent"]["$ref"],
+            "#/components/schemas/tree_node"
+        );
+        assert_eq!(
+            node["properties"]["remote"]["$ref"],
+            "https://example.com/x.json"
+        );
+    }
+}
@@ -6,7 +6,7 @@ use rust_embed::Embed;
 use serde::Serialize;
 use tera::Tera;
 
-use crate::{Manifest, SdkConfig};
+use crate::{Manifest, MetadataContext, SdkConfig};
 
 #[derive(Embed)]
 #[folder = "templates/python/"]
@@ -17,6 +17,7 @@ struct PythonTemplates;
 struct PyprojectContext {
     package_name: String,
     import_name: String,
+    metadata: MetadataContext,
 }
 
 /// Template context for the generator facade module.
@@ -28,10 +29,12 @@ struct GeneratorContext {
 }
 
 /// Template context for a single component module.
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct ComponentContext {
     package_name: String,
     module_name: String,
+    /// Dotted package the module lives in, e.g. `my_sdk.components.schemas`.
+    import_from: String,
     enum_name: String,
     component_name: String,
     schema_path: String,
@@ -54,16 +57,8 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     let manifest: Manifest =
         serde_json::from_str(&manifest_content).with_context(|| "Failed to parse manifest.json")?;
 
-    // Build Tera engine from embedded templates
-    let mut tera = Tera::default();
-    for file_name in PythonTemplates::iter() {
-        let file = PythonTemplates::get(&file_name)
-            .with_context(|| format!("Failed to load embedded template: {}", file_name))?;
-        let content = std::str::from_utf8(file.data.as_ref())
-            .with_context(|| format!("Template {} is not valid UTF-8", file_name))?;
-        tera.add_raw_template(&file_name, content)
-            .with_context(|| format!("Failed to register template: {}", file_name))?;
-    }
+    // Build Tera engine from embedded templates, with user overrides
+    let tera = crate::load_templates::<PythonTemplates>(config.template_dir.as_deref())?;
 
     // Derive import name from package name
     let import_name = to_import_name(&config.package);
@@ -80,6 +75,7 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     let pyproject_ctx = PyprojectContext {
         package_name: config.package.clone(),
         import_name: import_name.clone(),
+        metadata: config.metadata.context("1.0.0"),
     };
     render_to_file(
         &tera,
@@ -104,9 +100,19 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     // Build component contexts and generate component modules
     let component_names: Vec<String> = manifest.components.iter().map(|c| c.name.clone()).collect();
     let resolved_components = crate::resolve_collisions(&component_names);
+    let groups = crate::module_groups(&manifest, config.layout);
+
+    // Smoke-test examples live next to the test suite, outside the package
+    let tests_dir = config.output_dir.join("tests");
 
     let mut component_contexts = Vec::new();
-    for (component, resolved) in manifest.components.iter().zip(resolved_components.iter()) {
+    let mut smoke_components = Vec::new();
+    for ((component, resolved), group) in manifest
+        .components
+        .iter()
+        .zip(resolved_components.iter())
+        .zip(&groups)
+    {
         // Path traversal guards
         for path in [&component.schema_path, &component.codec_path] {
             if path.contains("..") || path.starts_with('/') {
@@ -118,9 +124,14 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         }
 
         let module_name = resolved.module_name.clone();
+        let module_dir = create_subpackage(&pkg_dir, group)?;
         let ctx = ComponentContext {
             package_name: import_name.clone(),
             module_name: module_name.clone(),
+            import_from: std::iter::once(import_name.as_str())
+                .chain(group.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join("."),
             enum_name: resolved.enum_name.clone(),
             component_name: resolved.original_name.clone(),
             schema_path: component.schema_path.clone(),
@@ -132,7 +143,7 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
             &tera,
             "component.py.tera",
             &ctx,
-            &pkg_dir.join(format!("{}.py", module_name)),
+            &module_dir.join(format!("{}.py", module_name)),
         )?;
 
         // Copy schema, codec, and original files
@@ -140,6 +151,13 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         copy_schema_file(&config.schema_dir, &component.codec_path, &schemas_dir)?;
         copy_schema_file(&config.schema_dir, &component.original_path, &schemas_dir)?;
 
+        let example_path = tests_dir
+            .join("examples")
+            .join(format!("{}.json", module_name));
+        if crate::examples::write(&config.schema_dir, component, &example_path)? {
+            smoke_components.push(ctx.clone());
+        }
+
         component_contexts.push(ctx);
     }
 
@@ -164,6 +182,21 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         &pkg_dir.join("json_patch.py"),
     )?;
 
+    // Generate the smoke-test suite over the components with examples
+    if !smoke_components.is_empty() {
+        let smoke_ctx = GeneratorContext {
+            package_name: import_name.clone(),
+            import_name: import_name.clone(),
+            components: smoke_components,
+        };
+        render_to_file(
+            &tera,
+            "test_smoke.py.tera",
+            &smoke_ctx,
+            &tests_dir.join("test_smoke.py"),
+        )?;
+    }
+
     // Generate README
     let readme_ctx = tera::Context::from_serialize(&gen_ctx)?;
     let readme_content = tera.render("README.md.tera", &readme_ctx)?;
@@ -185,6 +218,22 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     Ok(())
 }
 
+/// Create the sub-package `group` under `pkg_dir`, with an `__init__.py` at
+/// each level, and return its directory.
+fn create_subpackage(pkg_dir: &Path, group: &[String]) -> Result<std::path::PathBuf> {
+    let mut dir = pkg_dir.to_path_buf();
+    for segment in group {
+        dir.push(segment);
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create package dir: {}", dir.display()))?;
+        let init = dir.join("__init__.py");
+        if !init.exists() {
+            fs::write(&init, "")?;
+        }
+    }
+    Ok(dir)
+}
+
 /// Render a Tera template to a file.
 fn render_to_file<T: Serialize>(
     tera: &Tera,
@@ -234,7 +283,7 @@ fn copy_schema_file(schema_dir: &Path, relative_path: &str, schemas_dir: &Path)
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::BuildTool;
+    use crate::{BuildTool, ModuleLayout, ProjectMetadata};
     use tempfile::TempDir;
 
     #[test]
@@ -291,6 +340,9 @@ mod tests {
             output_dir: output_dir.clone(),
             git_init: false,
             build_tool: BuildTool::Setuptools,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         generate(&config).expect("generate should succeed");
@@ -467,6 +519,9 @@ mod tests {
             output_dir,
             git_init: false,
             build_tool: BuildTool::Setuptools,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         let err =
@@ -477,4 +532,107 @@ mod tests {
             "error message should mention missing file, got: {msg}"
         );
     }
+
+    #[test]
+    fn generate_groups_modules_by_pointer_prefix() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-test-sdk".to_string(),
+            artifact_name: "my-test-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Setuptools,
+            template_dir: None,
+            layout: ModuleLayout::PointerPrefix,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let pkg_dir = output_dir.join("my_test_sdk");
+        assert!(pkg_dir.join("defs/__init__.py").exists());
+        assert!(pkg_dir.join("defs/alert.py").exists());
+        assert!(!pkg_dir.join("alert.py").exists());
+        let generator = fs::read_to_string(pkg_dir.join("generator.py")).unwrap();
+        assert!(generator.contains("from my_test_sdk.defs.alert import ("));
+        let smoke = fs::read_to_string(output_dir.join("tests/test_smoke.py")).unwrap();
+        assert!(smoke.contains("from my_test_sdk.defs import alert"));
+    }
+
+    #[test]
+    fn generate_emits_smoke_tests() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-test-sdk".to_string(),
+            artifact_name: "my-test-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Setuptools,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let example: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(output_dir.join("tests/examples/alert.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(example["llmOutput"], serde_json::json!({"level": "1"}));
+        assert_eq!(example["rehydrated"], serde_json::json!({"level": 1}));
+
+        let smoke = fs::read_to_string(output_dir.join("tests/test_smoke.py")).unwrap();
+        assert!(smoke.contains("    (alert, \"alert.json\"),\n"));
+    }
+
+    #[test]
+    fn generate_surfaces_project_metadata() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Setuptools,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata {
+                version: Some("2.1.0".to_string()),
+                license: Some("MIT".to_string()),
+                authors: vec![
+                    "Jane Doe <jane@example.com>".to_string(),
+                    "Build Bot".to_string(),
+                ],
+                repository: Some("https://github.com/example/sdk".to_string()),
+            },
+        };
+        generate(&config).expect("generate should succeed");
+
+        let pyproject = fs::read_to_string(output_dir.join("pyproject.toml")).unwrap();
+        assert!(pyproject.contains("version = \"2.1.0\"\n"));
+        assert!(pyproject.contains("license = { text = \"MIT\" }\n"));
+        assert!(pyproject.contains(
+            "    { name = \"Jane Doe\", email = \"jane@example.com\" },\n    { name = \"Build Bot\" },\n"
+        ));
+        assert!(
+            pyproject.contains("[project.urls]\nRepository = \"https://github.com/example/sdk\"\n")
+        );
+    }
 }
@@ -10,7 +10,7 @@ use std::fs;
 use std::path::Path;
 use tera::Tera;
 
-use crate::{Manifest, SdkConfig};
+use crate::{Manifest, MetadataContext, SdkConfig};
 
 // ---------------------------------------------------------------------------
 // Embedded templates
@@ -27,6 +27,7 @@ struct RubyTemplates;
 #[derive(Serialize)]
 struct GemspecContext {
     sdk_name: String,
+    metadata: MetadataContext,
 }
 
 #[derive(Serialize)]
@@ -36,7 +37,7 @@ struct GeneratorContext {
     components: Vec<GeneratorComponent>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct GeneratorComponent {
     name: String,
     enum_name: String,
@@ -44,6 +45,13 @@ struct GeneratorComponent {
     file_name: String,
 }
 
+#[derive(Serialize)]
+struct SmokeTestContext {
+    lib_name: String,
+    generator_module: String,
+    components: Vec<GeneratorComponent>,
+}
+
 #[derive(Serialize)]
 struct ComponentContext {
     generator_module: String,
@@ -106,6 +114,8 @@ fn component_to_file_name(name: &str) -> String {
 // ---------------------------------------------------------------------------
 
 pub fn generate(config: &SdkConfig) -> Result<()> {
+    crate::ensure_flat_layout(config, "Ruby")?;
+
     let output_dir = &config.output_dir;
     let sdk_name = &config.package;
     let generator_module = to_module_name(sdk_name);
@@ -124,26 +134,15 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     fs::create_dir_all(&schemas_dir)
         .with_context(|| format!("Failed to create schemas dir: {}", schemas_dir.display()))?;
 
-    // Initialize Tera
-    let mut tera = Tera::default();
-    for name in &[
-        "gemspec.tera",
-        "component.rb.tera",
-        "generator.rb.tera",
-        "json_patch.rb.tera",
-        "README.md.tera",
-        "gitignore.tera",
-    ] {
-        let content = RubyTemplates::get(name)
-            .with_context(|| format!("Missing embedded template: {name}"))?;
-        let content_str = std::str::from_utf8(content.data.as_ref())
-            .with_context(|| format!("Invalid UTF-8 in template: {name}"))?;
-        tera.add_raw_template(name, content_str)
-            .with_context(|| format!("Failed to add template: {name}"))?;
-    }
+    // Build Tera engine from embedded templates, with user overrides
+    let tera = crate::load_templates::<RubyTemplates>(config.template_dir.as_deref())?;
+
+    // Smoke-test examples live next to the test suite
+    let test_dir = output_dir.join("test");
 
     // Build component data
     let mut gen_components = Vec::new();
+    let mut smoke_components = Vec::new();
     let mut readme_components = Vec::new();
     let component_names: Vec<String> = manifest.components.iter().map(|c| c.name.clone()).collect();
     let resolved_components = crate::resolve_collisions(&component_names);
@@ -152,12 +151,12 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         let module_name = resolved.class_name.clone();
         let file_name = resolved.module_name.clone();
 
-        gen_components.push(GeneratorComponent {
+        let gen_component = GeneratorComponent {
             name: resolved.original_name.clone(),
             enum_name: resolved.enum_name.clone(),
             module_name: module_name.clone(),
             file_name: file_name.clone(),
-        });
+        };
 
         readme_components.push(ReadmeComponent {
             name: resolved.original_name.clone(),
@@ -209,6 +208,14 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
             &comp_ctx,
             &module_dir.join(format!("{}.rb", file_name)),
         )?;
+
+        let example_path = test_dir
+            .join("examples")
+            .join(format!("{}.json", file_name));
+        if crate::examples::write(&config.schema_dir, comp, &example_path)? {
+            smoke_components.push(gen_component.clone());
+        }
+        gen_components.push(gen_component);
     }
 
     // Render generator facade
@@ -232,6 +239,21 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         &module_dir.join("json_patch.rb"),
     )?;
 
+    // Render the smoke-test suite over the components with examples
+    if !smoke_components.is_empty() {
+        let smoke_ctx = SmokeTestContext {
+            lib_name: component_to_file_name(sdk_name),
+            generator_module: generator_module.clone(),
+            components: smoke_components,
+        };
+        render_to_file(
+            &tera,
+            "smoke_test.rb.tera",
+            &smoke_ctx,
+            &test_dir.join("smoke_test.rb"),
+        )?;
+    }
+
     // Render barrel require file
     let barrel_content = format!(
         "# frozen_string_literal: true\n\nrequire_relative \"{}/generator\"\n",
@@ -246,6 +268,7 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     // Render gemspec
     let gemspec_ctx = GemspecContext {
         sdk_name: sdk_name.clone(),
+        metadata: config.metadata.context("0.1.0"),
     };
     render_to_file(
         &tera,
@@ -340,7 +363,7 @@ fn copy_schema_file(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{BuildTool, SdkConfig};
+    use crate::{BuildTool, ModuleLayout, ProjectMetadata, SdkConfig};
     use std::path::PathBuf;
 
     fn create_test_schema_dir(tmp: &Path) -> PathBuf {
@@ -407,6 +430,9 @@ mod tests {
             output_dir: output_dir.clone(),
             git_init: false,
             build_tool: BuildTool::Bundler,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         generate(&config).unwrap();
@@ -551,6 +577,9 @@ mod tests {
             output_dir: tmp.path().join("output"),
             git_init: false,
             build_tool: BuildTool::Bundler,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         let result = generate(&config);
@@ -574,4 +603,39 @@ mod tests {
         assert_eq!(component_to_file_name("UserProfile"), "user_profile");
         assert_eq!(component_to_file_name("my-component"), "my_component");
     }
+
+    #[test]
+    fn generate_emits_smoke_tests() {
+        use crate::{BuildTool, ModuleLayout, ProjectMetadata};
+        use tempfile::TempDir;
+
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-petstore-sdk".to_string(),
+            artifact_name: "my-petstore-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Bundler,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let example: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(output_dir.join("test/examples/alert.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(example["llmOutput"], serde_json::json!({"level": "1"}));
+        assert_eq!(example["rehydrated"], serde_json::json!({"level": 1}));
+
+        let smoke = fs::read_to_string(output_dir.join("test/smoke_test.rb")).unwrap();
+        assert!(smoke.contains("assert_round_trip(MyPetstoreSdk::Alert, \"alert.json\")"));
+    }
 }
@@ -0,0 +1,603 @@
+//! Rust SDK generator.
+//!
+//! Generates a Cargo library crate from converted schemas. Unlike the other
+//! generators, each component module also carries serde-derive types for
+//! the component's original (rehydrated) shape, so `rehydrate()` hands back
+//! a typed value instead of raw JSON.
+//! Follows the same architecture as `java.rs`, `python.rs`, and `typescript.rs`.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use heck::ToSnakeCase;
+use rust_embed::Embed;
+use serde::Serialize;
+use serde_json::Value;
+use tera::Tera;
+
+use crate::types::{self, TypeDef, TypeKind, TypeRef};
+use crate::{Manifest, MetadataContext, SdkConfig};
+
+// ---------------------------------------------------------------------------
+// Embedded templates
+// ---------------------------------------------------------------------------
+
+#[derive(Embed)]
+#[folder = "templates/rust/"]
+struct RustTemplates;
+
+// ---------------------------------------------------------------------------
+// Template contexts
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+struct CargoContext {
+    package_name: String,
+    metadata: MetadataContext,
+}
+
+#[derive(Serialize)]
+struct ComponentContext {
+    component_name: String,
+    module_name: String,
+    type_name: String,
+    schema_path: String,
+    codec_path: String,
+    original_path: String,
+    types: String,
+    /// Whether any type derives `Serialize`/`Deserialize` (not just aliases).
+    derives_serde: bool,
+}
+
+#[derive(Serialize)]
+struct SmokeComponent {
+    module_name: String,
+    /// `module_name` without raw-identifier escaping; names the example and test.
+    example_name: String,
+}
+
+#[derive(Serialize)]
+struct SmokeTestContext {
+    crate_name: String,
+    components: Vec<SmokeComponent>,
+}
+
+#[derive(Serialize)]
+struct LibContext {
+    package_name: String,
+    crate_name: String,
+    source_schema: String,
+    components: Vec<ComponentContext>,
+}
+
+// ---------------------------------------------------------------------------
+// Generator
+// ---------------------------------------------------------------------------
+
+/// Generate a Rust (Cargo) SDK crate.
+pub fn generate(config: &SdkConfig) -> Result<()> {
+    crate::ensure_flat_layout(config, "Rust")?;
+
+    // Read and parse manifest
+    let manifest_path = config.schema_dir.join("manifest.json");
+    let manifest_content = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read manifest at {}", manifest_path.display()))?;
+    let manifest: Manifest =
+        serde_json::from_str(&manifest_content).with_context(|| "Failed to parse manifest.json")?;
+
+    // Build Tera engine from embedded templates, with user overrides
+    let tera = crate::load_templates::<RustTemplates>(config.template_dir.as_deref())?;
+
+    let src_dir = config.output_dir.join("src");
+    fs::create_dir_all(&src_dir)
+        .with_context(|| format!("Failed to create src dir: {}", src_dir.display()))?;
+    let schemas_dir = config.output_dir.join("schemas");
+    fs::create_dir_all(&schemas_dir)
+        .with_context(|| format!("Failed to create schemas dir: {}", schemas_dir.display()))?;
+
+    render_to_file(
+        &tera,
+        "Cargo.toml.tera",
+        &CargoContext {
+            package_name: config.package.clone(),
+            metadata: config.metadata.context("0.1.0"),
+        },
+        &config.output_dir.join("Cargo.toml"),
+    )?;
+
+    let component_names: Vec<String> = manifest.components.iter().map(|c| c.name.clone()).collect();
+    let resolved_components = crate::resolve_collisions(&component_names);
+
+    // Smoke-test examples live next to the integration test
+    let tests_dir = config.output_dir.join("tests");
+
+    let mut component_contexts = Vec::new();
+    let mut smoke_components = Vec::new();
+    for (component, resolved) in manifest.components.iter().zip(resolved_components.iter()) {
+        // Copy schema, codec and original into schemas/, preserving relative paths
+        for (kind, rel) in [
+            ("Schema file", &component.schema_path),
+            ("Codec file", &component.codec_path),
+            ("Original schema", &component.original_path),
+        ] {
+            let src = config.schema_dir.join(rel);
+            if !src.exists() {
+                anyhow::bail!(
+                    "{} not found for component '{}': {}",
+                    kind,
+                    component.name,
+                    src.display()
+                );
+            }
+            let dest = schemas_dir.join(rel);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&src, &dest).with_context(|| {
+                format!("Failed to copy: {} -> {}", src.display(), dest.display())
+            })?;
+        }
+
+        let original_src = config.schema_dir.join(&component.original_path);
+        let original: Value = serde_json::from_str(
+            &fs::read_to_string(&original_src)
+                .with_context(|| format!("Failed to read: {}", original_src.display()))?,
+        )
+        .with_context(|| format!("Invalid JSON in {}", original_src.display()))?;
+
+        let module_name = escape_module(&resolved.module_name);
+        let (type_name, types, derives_serde) = emit_types(&original, &resolved.class_name);
+        let ctx = ComponentContext {
+            component_name: resolved.original_name.clone(),
+            module_name: module_name.clone(),
+            type_name,
+            schema_path: component.schema_path.clone(),
+            codec_path: component.codec_path.clone(),
+            original_path: component.original_path.clone(),
+            types,
+            derives_serde,
+        };
+        render_to_file(
+            &tera,
+            "component.rs.tera",
+            &ctx,
+            &src_dir.join(format!("{}.rs", module_name)),
+        )?;
+
+        let example_path = tests_dir
+            .join("examples")
+            .join(format!("{}.json", resolved.module_name));
+        if crate::examples::write(&config.schema_dir, component, &example_path)? {
+            smoke_components.push(SmokeComponent {
+                module_name: module_name.clone(),
+                example_name: resolved.module_name.clone(),
+            });
+        }
+        component_contexts.push(ctx);
+    }
+
+    let lib_ctx = LibContext {
+        package_name: config.package.clone(),
+        crate_name: config.package.replace('-', "_"),
+        source_schema: manifest.source_schema.clone(),
+        components: component_contexts,
+    };
+    render_to_file(&tera, "lib.rs.tera", &lib_ctx, &src_dir.join("lib.rs"))?;
+    if !smoke_components.is_empty() {
+        let smoke_ctx = SmokeTestContext {
+            crate_name: lib_ctx.crate_name.clone(),
+            components: smoke_components,
+        };
+        render_to_file(
+            &tera,
+            "smoke.rs.tera",
+            &smoke_ctx,
+            &tests_dir.join("smoke.rs"),
+        )?;
+    }
+    render_to_file(
+        &tera,
+        "README.md.tera",
+        &lib_ctx,
+        &config.output_dir.join("README.md"),
+    )?;
+    render_to_file(
+        &tera,
+        "gitignore.tera",
+        &lib_ctx,
+        &config.output_dir.join(".gitignore"),
+    )?;
+
+    // Optionally initialize git
+    if config.git_init {
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(&config.output_dir)
+            .status()
+            .ok();
+    }
+
+    Ok(())
+}
+
+/// Render a Tera template to a file.
+fn render_to_file<T: Serialize>(
+    tera: &Tera,
+    template_name: &str,
+    context: &T,
+    output_path: &Path,
+) -> Result<()> {
+    let ctx =
+        tera::Context::from_serialize(context).with_context(|| "Failed to serialize context")?;
+    let rendered = tera
+        .render(template_name, &ctx)
+        .with_context(|| format!("Failed to render template: {}", template_name))?;
+    fs::write(output_path, rendered)
+        .with_context(|| format!("Failed to write: {}", output_path.display()))?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Name helpers
+// ---------------------------------------------------------------------------
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Names the generated modules already use; a schema type with one of these
+/// names would shadow it.
+const RESERVED_TYPES: &[&str] = &[
+    "Box",
+    "Deserialize",
+    "Error",
+    "Option",
+    "Result",
+    "Self",
+    "Serialize",
+    "String",
+    "Value",
+    "Vec",
+];
+
+/// Module names can't be raw identifiers (the file name has to match), so
+/// keywords get a trailing underscore.
+fn escape_module(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+/// A JSON property name as a snake_case field identifier.
+fn field_ident(key: &str) -> String {
+    let ident = crate::sanitize_identifier(key).to_snake_case();
+    match ident.as_str() {
+        "" => "field".to_string(),
+        _ if ident.starts_with(|c: char| c.is_ascii_digit()) => format!("field_{ident}"),
+        // These can't be raw identifiers.
+        "crate" | "self" | "super" => format!("{ident}_"),
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("r#{ident}"),
+        _ => ident,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Type rendering
+// ---------------------------------------------------------------------------
+
+/// Render serde-derive types for a component's original schema. Returns the
+/// root type's name (it may differ from `root_name` on collision), the
+/// declarations, and whether any of them derives serde's traits (aliases
+/// do not).
+fn emit_types(schema: &Value, root_name: &str) -> (String, String, bool) {
+    let defs = types::build(schema, root_name, RESERVED_TYPES);
+    let items: Vec<String> = defs.iter().map(render_def).collect();
+    let derives_serde = defs
+        .iter()
+        .any(|def| !matches!(def.kind, TypeKind::Alias(_)));
+    (defs[0].name.clone(), items.join("\n"), derives_serde)
+}
+
+fn render_def(def: &TypeDef) -> String {
+    let mut out = String::new();
+    push_docs(&mut out, def.description.as_deref(), def.deprecated, "");
+    let name = &def.name;
+    match &def.kind {
+        TypeKind::Struct { fields, extra } => {
+            out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+            out.push_str(&format!("pub struct {name} {{\n"));
+            let mut field_names = HashSet::new();
+            for field in fields {
+                let ident = types::unique(field_ident(&field.key), &mut field_names);
+                let mut ty = render_type(&field.ty);
+                let mut attrs = Vec::new();
+                if ident.trim_start_matches("r#") != field.key {
+                    attrs.push(format!("rename = \"{}\"", field.key.escape_default()));
+                }
+                if !field.required {
+                    if !matches!(field.ty, TypeRef::Optional(_)) {
+                        ty = format!("Option<{ty}>");
+                    }
+                    attrs.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+                }
+                push_docs(
+                    &mut out,
+                    field.description.as_deref(),
+                    field.deprecated,
+                    "    ",
+                );
+                if !attrs.is_empty() {
+                    out.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
+                }
+                out.push_str(&format!("    pub {ident}: {ty},\n"));
+            }
+            if let Some(extra) = extra {
+                let ident = types::unique("extra".to_string(), &mut field_names);
+                out.push_str("    /// Properties beyond the declared ones.\n");
+                out.push_str("    #[serde(flatten)]\n");
+                out.push_str(&format!(
+                    "    pub {ident}: std::collections::BTreeMap<String, {}>,\n",
+                    render_type(extra)
+                ));
+            }
+            out.push_str("}\n");
+        }
+        TypeKind::StringEnum(values) => {
+            out.push_str(
+                "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n",
+            );
+            out.push_str(&format!("pub enum {name} {{\n"));
+            let mut taken = HashSet::new();
+            for value in values {
+                let ident = types::unique(types::type_ident(value), &mut taken);
+                if ident != *value {
+                    out.push_str(&format!(
+                        "    #[serde(rename = \"{}\")]\n",
+                        value.escape_default()
+                    ));
+                }
+                out.push_str(&format!("    {ident},\n"));
+            }
+            out.push_str("}\n");
+        }
+        TypeKind::Union(variants) => {
+            out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+            out.push_str("#[serde(untagged)]\n");
+            out.push_str(&format!("pub enum {name} {{\n"));
+            for variant in variants {
+                push_docs(&mut out, variant.description.as_deref(), false, "    ");
+                out.push_str(&format!(
+                    "    {}({}),\n",
+                    variant.name,
+                    render_type(&variant.ty)
+                ));
+            }
+            out.push_str("}\n");
+        }
+        TypeKind::Alias(ty) => {
+            out.push_str(&format!("pub type {name} = {};\n", render_type(ty)));
+        }
+    }
+    out
+}
+
+fn render_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "String".to_string(),
+        TypeRef::Integer => "i64".to_string(),
+        TypeRef::Number => "f64".to_string(),
+        TypeRef::Boolean => "bool".to_string(),
+        TypeRef::Null => "()".to_string(),
+        TypeRef::Any => "Value".to_string(),
+        TypeRef::Object => "serde_json::Map<String, Value>".to_string(),
+        TypeRef::Array(item) => format!("Vec<{}>", render_type(item)),
+        TypeRef::Map(value) => {
+            format!("std::collections::BTreeMap<String, {}>", render_type(value))
+        }
+        TypeRef::Optional(inner) => format!("Option<{}>", render_type(inner)),
+        TypeRef::Named {
+            name,
+            recursive: true,
+        } => format!("Box<{name}>"),
+        TypeRef::Named { name, .. } => name.clone(),
+    }
+}
+
+/// Write a description (and `deprecated`) as outer doc comments.
+fn push_docs(out: &mut String, description: Option<&str>, deprecated: bool, indent: &str) {
+    for line in description.into_iter().flat_map(str::lines) {
+        if line.is_empty() {
+            out.push_str(&format!("{indent}///\n"));
+        } else {
+            out.push_str(&format!("{indent}/// {line}\n"));
+        }
+    }
+    if deprecated {
+        out.push_str(&format!("{indent}#[deprecated]\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildTool, ModuleLayout, ProjectMetadata};
+    use serde_json::json;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_emit_types_struct_with_optional_and_renamed_fields() {
+        let (_, types, _) = emit_types(
+            &json!({
+                "type": "object",
+                "description": "A user.",
+                "properties": {
+                    "displayName": { "type": "string" },
+                    "type": { "enum": ["admin", "guest"] },
+                    "age": { "type": ["integer", "null"] }
+                },
+                "required": ["displayName", "type"]
+            }),
+            "User",
+        );
+        assert!(types.contains("/// A user.\n#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct User {"));
+        assert!(types
+            .contains("    #[serde(rename = \"displayName\")]\n    pub display_name: String,\n"));
+        assert!(types.contains("    pub r#type: UserType,\n"));
+        assert!(types.contains(
+            "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub age: Option<i64>,\n"
+        ));
+        assert!(types.contains("pub enum UserType {\n    #[serde(rename = \"admin\")]\n    Admin,"));
+    }
+
+    #[test]
+    fn test_emit_types_defs_unions_and_recursion() {
+        let (_, types, _) = emit_types(
+            &json!({
+                "type": "object",
+                "properties": {
+                    "child": { "$ref": "#/$defs/Node" },
+                    "shape": { "anyOf": [{ "$ref": "#/$defs/Node" }, { "type": "string" }] },
+                    "labels": { "type": "object", "additionalProperties": { "type": "string" } }
+                },
+                "required": ["child", "shape", "labels"],
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "next": { "$ref": "#/$defs/Node" },
+                            "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } }
+                        }
+                    }
+                }
+            }),
+            "Tree",
+        );
+        assert!(types.contains("    pub child: Node,\n"));
+        assert!(types.contains("    pub shape: TreeShape,\n"));
+        assert!(types.contains(
+            "#[serde(untagged)]\npub enum TreeShape {\n    Node(Node),\n    String(String),\n}"
+        ));
+        assert!(types.contains("    pub labels: std::collections::BTreeMap<String, String>,\n"));
+        assert!(types.contains("    pub next: Option<Box<Node>>,\n"));
+        assert!(types.contains("    pub children: Option<Vec<Node>>,\n"));
+    }
+
+    #[test]
+    fn test_emit_types_alias_only_derives_nothing() {
+        let (name, types, derives_serde) = emit_types(&json!({}), "ExplodeDefault");
+        assert_eq!(name, "ExplodeDefault");
+        assert_eq!(types, "pub type ExplodeDefault = Value;\n");
+        assert!(!derives_serde);
+
+        let (_, _, derives_serde) = emit_types(&json!({ "enum": ["a"] }), "Kind");
+        assert!(derives_serde);
+    }
+
+    #[test]
+    fn test_generate_creates_cargo_project() {
+        let schema_dir = TempDir::new().unwrap();
+        let output_dir = TempDir::new().unwrap();
+        let manifest = json!({
+            "version": "1.0.0",
+            "generatedAt": "2026-01-01T00:00:00Z",
+            "sourceSchema": "test-schema.json",
+            "target": "openai-strict",
+            "mode": "strict",
+            "components": [{
+                "name": "user-profile",
+                "pointer": "#/$defs/UserProfile",
+                "schemaPath": "$defs/UserProfile/schema.json",
+                "codecPath": "$defs/UserProfile/codec.json",
+                "originalPath": "$defs/UserProfile/original.json",
+                "dependencyCount": 0
+            }]
+        });
+        fs::write(
+            schema_dir.path().join("manifest.json"),
+            manifest.to_string(),
+        )
+        .unwrap();
+        let comp_dir = schema_dir.path().join("$defs").join("UserProfile");
+        fs::create_dir_all(&comp_dir).unwrap();
+        fs::write(comp_dir.join("schema.json"), r#"{"type":"object"}"#).unwrap();
+        fs::write(comp_dir.join("codec.json"), r#"{"transforms":[]}"#).unwrap();
+        fs::write(
+            comp_dir.join("original.json"),
+            r#"{"type":"object","properties":{"name":{"type":"string"}},"required":["name"]}"#,
+        )
+        .unwrap();
+
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir: PathBuf::from(schema_dir.path()),
+            output_dir: PathBuf::from(output_dir.path()),
+            git_init: false,
+            build_tool: BuildTool::Cargo,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generation should succeed");
+
+        let out = output_dir.path();
+        assert!(out.join("Cargo.toml").exists());
+        assert!(out.join("README.md").exists());
+        assert!(out.join(".gitignore").exists());
+        assert!(out.join("schemas/$defs/UserProfile/original.json").exists());
+
+        let cargo = fs::read_to_string(out.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"my-sdk\""));
+        assert!(cargo.contains("json-schema-llm-core"));
+
+        let lib = fs::read_to_string(out.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod user_profile;"));
+        assert!(lib.contains("pub enum Component {"));
+
+        let component = fs::read_to_string(out.join("src/user_profile.rs")).unwrap();
+        assert!(component.contains("pub struct UserProfile {\n    pub name: String,\n}"));
+        assert!(component
+            .contains("pub fn rehydrate(llm_output: &Value) -> Result<UserProfile, Error>"));
+    }
+
+    #[test]
+    fn test_generate_emits_smoke_tests() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Cargo,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let example: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(output_dir.join("tests/examples/alert.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(example["llmOutput"], serde_json::json!({"level": "1"}));
+        assert_eq!(example["rehydrated"], serde_json::json!({"level": 1}));
+
+        let smoke = fs::read_to_string(output_dir.join("tests/smoke.rs")).unwrap();
+        assert!(smoke.contains("use my_sdk::alert as component;"));
+    }
+}
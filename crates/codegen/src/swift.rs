@@ -0,0 +1,741 @@
+//! Swift SDK generator.
+//!
+//! Generates a SwiftPM package from converted schemas. Each component gets
+//! an accessor enum with nested Codable types for the component's original
+//! shape; convert/rehydrate go through a thin wrapper over the `jsl_*` C ABI
+//! of the WASI binary, hosted in-process by WasmKit so it runs on iOS.
+//! Follows the same architecture as `kotlin.rs` and `rust.rs`.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+use rust_embed::Embed;
+use serde::Serialize;
+use serde_json::Value;
+use tera::Tera;
+
+use crate::types::{self, TypeDef, TypeKind, TypeRef};
+use crate::{Manifest, MetadataContext, SdkConfig};
+
+// ---------------------------------------------------------------------------
+// Embedded templates
+// ---------------------------------------------------------------------------
+
+#[derive(Embed)]
+#[folder = "templates/swift/"]
+struct SwiftTemplates;
+
+/// Minimum WasmKit release the generated package depends on.
+const WASMKIT_VERSION: &str = "0.1.0";
+
+// ---------------------------------------------------------------------------
+// Template contexts
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+struct PackageContext {
+    package_name: String,
+    target_name: String,
+    wasmkit_version: String,
+    /// Whether to declare the smoke-test target.
+    smoke_tests: bool,
+    /// SwiftPM has no metadata fields (versions are git tags), so these
+    /// only end up in a header comment; an empty version is left out.
+    metadata: MetadataContext,
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentContext {
+    component_name: String,
+    /// `component_name` as a Swift string literal, quotes included.
+    component_literal: String,
+    case_name: String,
+    accessor_name: String,
+    type_name: String,
+    /// Resource paths as Swift string literals, quotes included.
+    schema_path: String,
+    codec_path: String,
+    original_path: String,
+    types: String,
+}
+
+#[derive(Serialize)]
+struct GeneratorContext {
+    package_name: String,
+    target_name: String,
+    components: Vec<ComponentContext>,
+}
+
+// ---------------------------------------------------------------------------
+// Generator
+// ---------------------------------------------------------------------------
+
+/// Generate a Swift package.
+pub fn generate(config: &SdkConfig) -> Result<()> {
+    crate::ensure_flat_layout(config, "Swift")?;
+
+    // Read and parse manifest
+    let manifest_path = config.schema_dir.join("manifest.json");
+    let manifest_content = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read manifest at {}", manifest_path.display()))?;
+    let manifest: Manifest =
+        serde_json::from_str(&manifest_content).with_context(|| "Failed to parse manifest.json")?;
+
+    // Build Tera engine from embedded templates, with user overrides
+    let tera = crate::load_templates::<SwiftTemplates>(config.template_dir.as_deref())?;
+
+    let target_name = target_name(&config.artifact_name);
+    let mut package_ctx = PackageContext {
+        package_name: config.artifact_name.clone(),
+        target_name: target_name.clone(),
+        wasmkit_version: WASMKIT_VERSION.to_string(),
+        smoke_tests: false,
+        metadata: config.metadata.context(""),
+    };
+
+    let src_dir = config.output_dir.join("Sources").join(&target_name);
+    let resources_dir = src_dir.join("Resources/schemas");
+    render_to_file(
+        &tera,
+        "Runtime.swift.tera",
+        &package_ctx,
+        &src_dir.join("JsonSchemaLlm.swift"),
+    )?;
+
+    let component_names: Vec<String> = manifest.components.iter().map(|c| c.name.clone()).collect();
+    let resolved_components = crate::resolve_collisions(&component_names);
+
+    // Smoke-test examples are bundled as test-target resources
+    let tests_dir = config
+        .output_dir
+        .join("Tests")
+        .join(format!("{target_name}Tests"));
+
+    let mut component_contexts = Vec::new();
+    let mut smoke_components = Vec::new();
+    for (component, resolved) in manifest.components.iter().zip(resolved_components.iter()) {
+        for rel in [
+            &component.schema_path,
+            &component.codec_path,
+            &component.original_path,
+        ] {
+            copy_schema_file(&config.schema_dir, rel, &resources_dir)?;
+        }
+        let original: Value = serde_json::from_str(&fs::read_to_string(
+            config.schema_dir.join(&component.original_path),
+        )?)
+        .with_context(|| format!("Invalid JSON in {}", component.original_path))?;
+
+        // Swift file names must be unique within a module, so each file is
+        // named after its accessor rather than the bare class name.
+        let accessor_name = format!("{}Component", resolved.class_name);
+        let (type_name, types) = emit_types(&original, &resolved.class_name, &accessor_name);
+
+        let ctx = ComponentContext {
+            component_name: resolved.original_name.clone(),
+            component_literal: string_literal(&resolved.original_name),
+            case_name: escape_keyword(resolved.module_name_camel.clone()),
+            accessor_name: accessor_name.clone(),
+            type_name,
+            schema_path: string_literal(&component.schema_path),
+            codec_path: string_literal(&component.codec_path),
+            original_path: string_literal(&component.original_path),
+            types,
+        };
+        render_to_file(
+            &tera,
+            "Component.swift.tera",
+            &ctx,
+            &src_dir.join(format!("{accessor_name}.swift")),
+        )?;
+
+        let example_path = tests_dir
+            .join("examples")
+            .join(format!("{accessor_name}.json"));
+        if crate::examples::write(&config.schema_dir, component, &example_path)? {
+            smoke_components.push(ctx.clone());
+        }
+        component_contexts.push(ctx);
+    }
+
+    package_ctx.smoke_tests = !smoke_components.is_empty();
+    render_to_file(
+        &tera,
+        "Package.swift.tera",
+        &package_ctx,
+        &config.output_dir.join("Package.swift"),
+    )?;
+    if package_ctx.smoke_tests {
+        let smoke_ctx = GeneratorContext {
+            package_name: config.artifact_name.clone(),
+            target_name: target_name.clone(),
+            components: smoke_components,
+        };
+        render_to_file(
+            &tera,
+            "SmokeTests.swift.tera",
+            &smoke_ctx,
+            &tests_dir.join("SmokeTests.swift"),
+        )?;
+    }
+
+    let gen_ctx = GeneratorContext {
+        package_name: config.artifact_name.clone(),
+        target_name,
+        components: component_contexts,
+    };
+    render_to_file(
+        &tera,
+        "Generator.swift.tera",
+        &gen_ctx,
+        &src_dir.join("SchemaGenerator.swift"),
+    )?;
+    render_to_file(
+        &tera,
+        "README.md.tera",
+        &gen_ctx,
+        &config.output_dir.join("README.md"),
+    )?;
+    render_to_file(
+        &tera,
+        "gitignore.tera",
+        &gen_ctx,
+        &config.output_dir.join(".gitignore"),
+    )?;
+
+    // Optionally git init
+    if config.git_init {
+        std::process::Command::new("git")
+            .arg("init")
+            .current_dir(&config.output_dir)
+            .output()
+            .context("Failed to run git init")?;
+    }
+
+    Ok(())
+}
+
+/// Render a Tera template to a file.
+fn render_to_file<T: Serialize>(
+    tera: &Tera,
+    template_name: &str,
+    context: &T,
+    output_path: &Path,
+) -> Result<()> {
+    let ctx = tera::Context::from_serialize(context)?;
+    let rendered = tera
+        .render(template_name, &ctx)
+        .with_context(|| format!("Failed to render template: {}", template_name))?;
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(output_path, rendered)
+        .with_context(|| format!("Failed to write: {}", output_path.display()))?;
+
+    Ok(())
+}
+
+/// Copy a schema/codec file into the resources directory, preserving the
+/// relative path. Missing sources are a hard error.
+fn copy_schema_file(schema_dir: &Path, relative_path: &str, resources_dir: &Path) -> Result<()> {
+    if relative_path.contains("..") || relative_path.starts_with('/') {
+        anyhow::bail!(
+            "Invalid path in manifest: '{}' (must be relative, no traversal)",
+            relative_path
+        );
+    }
+    let src = schema_dir.join(relative_path);
+    let dst = resources_dir.join(relative_path);
+
+    if !src.exists() {
+        anyhow::bail!(
+            "Schema file not found: '{}' (referenced in manifest but missing from schema directory '{}')",
+            src.display(),
+            schema_dir.display()
+        );
+    }
+
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(&src, &dst)
+        .with_context(|| format!("Failed to copy {} to {}", src.display(), dst.display()))?;
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Name helpers
+// ---------------------------------------------------------------------------
+
+/// Swift keywords that need backticks as identifiers.
+const KEYWORDS: &[&str] = &[
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "private",
+    "precedencegroup",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+/// Names the generated files already use; a schema type with one of these
+/// names would shadow it inside the accessor enum.
+const RESERVED_TYPES: &[&str] = &[
+    "Any",
+    "Array",
+    "Bool",
+    "CodingKeys",
+    "Data",
+    "Decodable",
+    "Dictionary",
+    "Double",
+    "Encodable",
+    "Indirect",
+    "Int",
+    "JSONValue",
+    "Optional",
+    "Protocol",
+    "SchemaLlmEngine",
+    "Self",
+    "String",
+    "Type",
+];
+
+/// The SwiftPM package name as an UpperCamelCase module name.
+fn target_name(package: &str) -> String {
+    let name = crate::sanitize_identifier(package).to_upper_camel_case();
+    match name.as_str() {
+        "" => "Sdk".to_string(),
+        _ if name.starts_with(|c: char| c.is_ascii_digit()) => format!("Sdk{name}"),
+        _ => name,
+    }
+}
+
+fn escape_keyword(ident: String) -> String {
+    match ident.as_str() {
+        // `self` is rejected even in backticks as a member name.
+        "self" => "self_".to_string(),
+        s if KEYWORDS.contains(&s) => format!("`{s}`"),
+        _ => ident,
+    }
+}
+
+/// A JSON property or enum value as a lowerCamelCase identifier.
+fn member_ident(name: &str, fallback: &str) -> String {
+    let ident = crate::sanitize_identifier(name).to_lower_camel_case();
+    match ident.as_str() {
+        "" => fallback.to_string(),
+        _ if ident.starts_with(|c: char| c.is_ascii_digit()) => format!("{fallback}{ident}"),
+        _ => escape_keyword(ident),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Type rendering
+// ---------------------------------------------------------------------------
+
+/// Render Codable types for a component's original schema, indented for
+/// nesting inside the accessor enum. Returns the root type's name and the
+/// declarations.
+fn emit_types(schema: &Value, root_name: &str, accessor_name: &str) -> (String, String) {
+    let mut reserved = RESERVED_TYPES.to_vec();
+    reserved.push(accessor_name);
+    let defs = types::build(schema, root_name, &reserved);
+    let items: Vec<String> = defs.iter().map(render_def).collect();
+    (defs[0].name.clone(), items.join("\n"))
+}
+
+fn render_def(def: &TypeDef) -> String {
+    let mut out = String::new();
+    let name = &def.name;
+    match &def.kind {
+        TypeKind::Struct { fields, .. } => {
+            push_docs(&mut out, def.description.as_deref(), "    ");
+            push_deprecated(&mut out, def.deprecated, "    ");
+            out.push_str(&format!(
+                "    public struct {name}: Codable, Equatable {{\n"
+            ));
+            let mut taken = HashSet::new();
+            let members: Vec<(String, String)> = fields
+                .iter()
+                .map(|field| {
+                    let ident = types::unique(member_ident(&field.key, "field"), &mut taken);
+                    let ty = render_type(&field.ty);
+                    let ty = match (field.required, ty.ends_with('?')) {
+                        (false, false) => format!("{ty}?"),
+                        _ => ty,
+                    };
+                    (ident, ty)
+                })
+                .collect();
+            for (field, (ident, ty)) in fields.iter().zip(&members) {
+                push_docs(&mut out, field.description.as_deref(), "        ");
+                push_deprecated(&mut out, field.deprecated, "        ");
+                out.push_str(&format!("        public var {ident}: {ty}\n"));
+            }
+            if !members.is_empty() {
+                out.push('\n');
+            }
+            let params: Vec<String> = members
+                .iter()
+                .map(|(ident, ty)| match ty.ends_with('?') {
+                    true => format!("{ident}: {ty} = nil"),
+                    false => format!("{ident}: {ty}"),
+                })
+                .collect();
+            out.push_str(&format!("        public init({}) {{\n", params.join(", ")));
+            for (ident, _) in &members {
+                out.push_str(&format!("            self.{ident} = {ident}\n"));
+            }
+            out.push_str("        }\n");
+            if fields
+                .iter()
+                .zip(&members)
+                .any(|(field, (ident, _))| ident.trim_matches('`') != field.key)
+            {
+                out.push_str("\n        private enum CodingKeys: String, CodingKey {\n");
+                for (field, (ident, _)) in fields.iter().zip(&members) {
+                    if ident.trim_matches('`') == field.key {
+                        out.push_str(&format!("            case {ident}\n"));
+                    } else {
+                        out.push_str(&format!(
+                            "            case {ident} = {}\n",
+                            string_literal(&field.key)
+                        ));
+                    }
+                }
+                out.push_str("        }\n");
+            }
+            out.push_str("    }\n");
+        }
+        TypeKind::StringEnum(values) => {
+            push_docs(&mut out, def.description.as_deref(), "    ");
+            push_deprecated(&mut out, def.deprecated, "    ");
+            out.push_str(&format!(
+                "    public enum {name}: String, Codable, CaseIterable {{\n"
+            ));
+            let mut taken = HashSet::new();
+            for value in values {
+                let ident = types::unique(member_ident(value, "value"), &mut taken);
+                if ident.trim_matches('`') == value {
+                    out.push_str(&format!("        case {ident}\n"));
+                } else {
+                    out.push_str(&format!(
+                        "        case {ident} = {}\n",
+                        string_literal(value)
+                    ));
+                }
+            }
+            out.push_str("    }\n");
+        }
+        TypeKind::Union(variants) => {
+            // Codable has no untagged unions; keep the raw value and say
+            // what it may hold.
+            let mut doc = def.description.clone().unwrap_or_default();
+            if !doc.is_empty() {
+                doc.push_str("\n\n");
+            }
+            let shapes: Vec<String> = variants.iter().map(|v| render_type(&v.ty)).collect();
+            doc.push_str(&format!("One of: {}.", shapes.join(", ")));
+            push_docs(&mut out, Some(&doc), "    ");
+            push_deprecated(&mut out, def.deprecated, "    ");
+            out.push_str(&format!("    public typealias {name} = JSONValue\n"));
+        }
+        TypeKind::Alias(ty) => {
+            push_docs(&mut out, def.description.as_deref(), "    ");
+            push_deprecated(&mut out, def.deprecated, "    ");
+            out.push_str(&format!(
+                "    public typealias {name} = {}\n",
+                render_type(ty)
+            ));
+        }
+    }
+    out
+}
+
+fn render_type(ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::String => "String".to_string(),
+        TypeRef::Integer => "Int".to_string(),
+        TypeRef::Number => "Double".to_string(),
+        TypeRef::Boolean => "Bool".to_string(),
+        TypeRef::Null | TypeRef::Any => "JSONValue".to_string(),
+        TypeRef::Object => "[String: JSONValue]".to_string(),
+        TypeRef::Array(item) => format!("[{}]", render_type(item)),
+        TypeRef::Map(value) => format!("[String: {}]", render_type(value)),
+        TypeRef::Optional(inner) => {
+            let inner = render_type(inner);
+            if inner.ends_with('?') {
+                inner
+            } else {
+                format!("{inner}?")
+            }
+        }
+        TypeRef::Named {
+            name,
+            recursive: true,
+        } => format!("Indirect<{name}>"),
+        TypeRef::Named { name, .. } => name.clone(),
+    }
+}
+
+/// A Swift string literal, quotes included.
+fn string_literal(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn push_docs(out: &mut String, text: Option<&str>, indent: &str) {
+    for line in text.into_iter().flat_map(str::lines) {
+        if line.is_empty() {
+            out.push_str(&format!("{indent}///\n"));
+        } else {
+            out.push_str(&format!("{indent}/// {line}\n"));
+        }
+    }
+}
+
+fn push_deprecated(out: &mut String, deprecated: bool, indent: &str) {
+    if deprecated {
+        out.push_str(&format!(
+            "{indent}@available(*, deprecated, message: \"Deprecated in the source schema\")\n"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildTool, ModuleLayout, ProjectMetadata};
+    use serde_json::json;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_emit_types_struct_and_enum() {
+        let (name, types) = emit_types(
+            &json!({
+                "type": "object",
+                "description": "A user.",
+                "properties": {
+                    "display_name": { "type": "string" },
+                    "role": { "enum": ["admin", "read-only"] },
+                    "age": { "type": ["integer", "null"] },
+                    "default": { "type": "boolean" }
+                },
+                "required": ["display_name", "role"]
+            }),
+            "User",
+            "UserComponent",
+        );
+        assert_eq!(name, "User");
+        assert!(types.contains("    /// A user.\n    public struct User: Codable, Equatable {\n"));
+        assert!(types.contains("        public var age: Int?\n"));
+        assert!(types.contains("        public var `default`: Bool?\n"));
+        assert!(types.contains("        public var displayName: String\n"));
+        assert!(types.contains(
+            "        public init(age: Int? = nil, `default`: Bool? = nil, displayName: String, role: UserRole) {\n"
+        ));
+        assert!(types.contains("            case displayName = \"display_name\"\n"));
+        assert!(types.contains("            case `default`\n"));
+        assert!(types.contains(
+            "    public enum UserRole: String, Codable, CaseIterable {\n        case admin\n        case readOnly = \"read-only\"\n    }"
+        ));
+    }
+
+    #[test]
+    fn test_emit_types_recursion_is_indirect() {
+        let (_, types) = emit_types(
+            &json!({
+                "$ref": "#/$defs/Node",
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "next": { "$ref": "#/$defs/Node" },
+                            "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } }
+                        }
+                    }
+                }
+            }),
+            "Root",
+            "RootComponent",
+        );
+        assert!(types.contains("public var next: Indirect<Node>?\n"));
+        assert!(types.contains("public var children: [Node]?\n"));
+    }
+
+    #[test]
+    fn test_string_literal_escapes() {
+        assert_eq!(string_literal("a\"b\\(c)\u{1}"), "\"a\\\"b\\\\(c)\\u{1}\"");
+        assert_eq!(string_literal("$defs/x"), "\"$defs/x\"");
+    }
+
+    #[test]
+    fn test_generate_creates_swift_package() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        let comp_dir = schema_dir.join("user-profile");
+        fs::create_dir_all(&comp_dir).unwrap();
+        let manifest = json!({
+            "version": "1",
+            "generatedAt": "2026-01-01T00:00:00Z",
+            "sourceSchema": "test-schema.json",
+            "target": "openai-strict",
+            "mode": "strict",
+            "components": [{
+                "name": "user-profile",
+                "pointer": "#/$defs/user-profile",
+                "schemaPath": "user-profile/schema.json",
+                "codecPath": "user-profile/codec.json",
+                "originalPath": "user-profile/original.json",
+                "dependencyCount": 0
+            }]
+        });
+        fs::write(schema_dir.join("manifest.json"), manifest.to_string()).unwrap();
+        fs::write(comp_dir.join("schema.json"), r#"{"type":"object"}"#).unwrap();
+        fs::write(comp_dir.join("codec.json"), r#"{"transforms":[]}"#).unwrap();
+        fs::write(
+            comp_dir.join("original.json"),
+            r#"{"type":"object","properties":{"name":{"type":"string"}},"required":["name"]}"#,
+        )
+        .unwrap();
+
+        let output_dir = tmp.path().join("out");
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir: PathBuf::from(&schema_dir),
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Swiftpm,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generation should succeed");
+
+        let package = fs::read_to_string(output_dir.join("Package.swift")).unwrap();
+        assert!(package.contains("name: \"my-sdk\""));
+        assert!(package.contains(".library(name: \"MySdk\", targets: [\"MySdk\"])"));
+        assert!(package.contains("WasmKitWASI"));
+        assert!(output_dir.join(".gitignore").exists());
+
+        let src = output_dir.join("Sources/MySdk");
+        assert!(src
+            .join("Resources/schemas/user-profile/original.json")
+            .exists());
+        let runtime = fs::read_to_string(src.join("JsonSchemaLlm.swift")).unwrap();
+        assert!(runtime.contains("\"jsl_rehydrate\""));
+
+        let component = fs::read_to_string(src.join("UserProfileComponent.swift")).unwrap();
+        assert!(component.contains("public enum UserProfileComponent {"));
+        assert!(component.contains("try loadSchemaResource(\"user-profile/schema.json\")"));
+        assert!(component.contains("public struct UserProfile: Codable, Equatable {"));
+        assert!(component.contains(
+            "public static func rehydrate(_ llmOutput: JSONValue, engine: SchemaLlmEngine) throws -> UserProfile {"
+        ));
+
+        let generator = fs::read_to_string(src.join("SchemaGenerator.swift")).unwrap();
+        assert!(generator.contains("    case userProfile = \"user-profile\"\n"));
+        assert!(generator.contains("case .userProfile: return try UserProfileComponent.schema()"));
+    }
+
+    #[test]
+    fn test_generate_emits_smoke_tests() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Swiftpm,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let example: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(output_dir.join("Tests/MySdkTests/examples/AlertComponent.json"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(example["llmOutput"], serde_json::json!({"level": "1"}));
+        assert_eq!(example["rehydrated"], serde_json::json!({"level": 1}));
+
+        let smoke =
+            fs::read_to_string(output_dir.join("Tests/MySdkTests/SmokeTests.swift")).unwrap();
+        assert!(smoke.contains("func testRoundTripAlertComponent() throws {"));
+        let package = fs::read_to_string(output_dir.join("Package.swift")).unwrap();
+        assert!(package.contains(".testTarget(\n            name: \"MySdkTests\","));
+    }
+}
@@ -0,0 +1,593 @@
+//! Language-neutral type model for a component's original schema.
+//!
+//! [`build`] walks a self-contained component schema (as written to
+//! `original.json`) and produces named [`TypeDef`]s that each typed
+//! generator (`rust.rs`, `kotlin.rs`, `swift.rs`) renders in its own idiom.
+//! `$defs`/`definitions` entries become named types; inline objects,
+//! string enums and unions get names derived from their parent.
+//! Schemas with no faithful static shape fall back to [`TypeRef::Any`].
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use heck::ToUpperCamelCase;
+use serde_json::{Map, Value};
+
+/// A named type declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDef {
+    pub name: String,
+    pub description: Option<String>,
+    pub deprecated: bool,
+    pub kind: TypeKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeKind {
+    /// An object with known properties. `extra` is the value type of
+    /// additional properties, when the schema admits them explicitly.
+    Struct {
+        fields: Vec<Field>,
+        extra: Option<TypeRef>,
+    },
+    /// An `enum` of strings.
+    StringEnum(Vec<String>),
+    /// An `anyOf`/`oneOf` with more than one non-null branch.
+    Union(Vec<Variant>),
+    /// Anything else, named.
+    Alias(TypeRef),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// The property name as it appears in JSON.
+    pub key: String,
+    pub ty: TypeRef,
+    pub required: bool,
+    pub description: Option<String>,
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    /// UpperCamelCase variant name, unique within the union.
+    pub name: String,
+    pub ty: TypeRef,
+    pub description: Option<String>,
+}
+
+/// A reference to a type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Null,
+    /// Any JSON value.
+    Any,
+    /// A JSON object with no declared properties.
+    Object,
+    Array(Box<TypeRef>),
+    Map(Box<TypeRef>),
+    Optional(Box<TypeRef>),
+    /// A [`TypeDef`] by name. `recursive` marks a reference contained
+    /// directly (outside arrays or maps) by a type it leads back to, which
+    /// languages with by-value fields must box.
+    Named {
+        name: String,
+        recursive: bool,
+    },
+}
+
+/// Build the type model for `schema`. The first entry is the root type,
+/// named `root_name` unless that collides with `reserved`.
+pub fn build(schema: &Value, root_name: &str, reserved: &[&str]) -> Vec<TypeDef> {
+    Builder::new(schema, root_name, reserved).build()
+}
+
+/// A name as an UpperCamelCase type or variant identifier.
+pub fn type_ident(name: &str) -> String {
+    let ident = crate::sanitize_identifier(name).to_upper_camel_case();
+    match ident.as_str() {
+        "" => "Unnamed".to_string(),
+        _ if ident.starts_with(|c: char| c.is_ascii_digit()) => format!("V{ident}"),
+        _ => ident,
+    }
+}
+
+/// Return `base`, or `base2`, `base3`, … if already taken, and mark it taken.
+pub fn unique(base: String, taken: &mut HashSet<String>) -> String {
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while !taken.insert(name.clone()) {
+        name = format!("{base}{suffix}");
+        suffix += 1;
+    }
+    name
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+struct Builder<'a> {
+    root: &'a Value,
+    root_name: String,
+    taken: HashSet<String>,
+    /// `$ref` pointer → (type name, schema).
+    defs: HashMap<String, (String, &'a Value)>,
+    /// `$ref` pointer → pointers it contains without indirection.
+    direct: HashMap<String, BTreeSet<String>>,
+    /// Slots are reserved before a definition's children are built, so
+    /// parents come out ahead of the inline types they name.
+    out: Vec<Option<TypeDef>>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(root: &'a Value, root_name: &str, reserved: &[&str]) -> Self {
+        let mut taken: HashSet<String> = reserved.iter().map(|s| s.to_string()).collect();
+        let root_name = unique(type_ident(root_name), &mut taken);
+        let mut defs = HashMap::from([("#".to_string(), (root_name.clone(), root))]);
+        for keyword in ["$defs", "definitions"] {
+            if let Some(map) = root.get(keyword).and_then(Value::as_object) {
+                for (key, schema) in map {
+                    let pointer =
+                        format!("#/{keyword}/{}", key.replace('~', "~0").replace('/', "~1"));
+                    defs.insert(pointer, (unique(type_ident(key), &mut taken), schema));
+                }
+            }
+        }
+        let direct = defs
+            .iter()
+            .map(|(pointer, (_, schema))| {
+                let mut refs = BTreeSet::new();
+                collect_direct_refs(schema, &mut refs);
+                (pointer.clone(), refs)
+            })
+            .collect();
+
+        Builder {
+            root,
+            root_name,
+            taken,
+            defs,
+            direct,
+            out: Vec::new(),
+        }
+    }
+
+    fn build(mut self) -> Vec<TypeDef> {
+        let root_name = self.root_name.clone();
+        self.define(&root_name, self.root, "#", true);
+
+        let mut named: Vec<(String, String)> = self
+            .defs
+            .iter()
+            .filter(|(pointer, _)| pointer.as_str() != "#")
+            .map(|(pointer, (name, _))| (name.clone(), pointer.clone()))
+            .collect();
+        named.sort();
+        for (name, pointer) in named {
+            let schema = self.defs[&pointer].1;
+            self.define(&name, schema, &pointer, true);
+        }
+
+        self.out.into_iter().flatten().collect()
+    }
+
+    /// Define `name` for `schema`. `owner` is the `$ref` pointer of the
+    /// enclosing named definition, for recursion detection. With
+    /// `keep_nullable`, a nullable schema becomes an alias of its optional
+    /// type; inline types are defined for the non-null half only, since the
+    /// referencing site already carries the `Optional`.
+    fn define(&mut self, name: &str, schema: &'a Value, owner: &str, keep_nullable: bool) {
+        let slot = self.out.len();
+        self.out.push(None);
+
+        let kind = match schema.as_object() {
+            Some(obj) if keep_nullable && nullable_inner(obj).is_some() => None,
+            Some(obj) => self.kind(name, obj, owner),
+            None => None,
+        }
+        .unwrap_or_else(|| TypeKind::Alias(self.type_ref(schema, name, owner, true)));
+
+        self.out[slot] = Some(TypeDef {
+            name: name.to_string(),
+            description: description(schema),
+            deprecated: is_deprecated(schema),
+            kind,
+        });
+    }
+
+    /// The struct, enum or union `obj` warrants, if any.
+    fn kind(&mut self, name: &str, obj: &'a Map<String, Value>, owner: &str) -> Option<TypeKind> {
+        if let Some(values) = string_enum(obj) {
+            return Some(TypeKind::StringEnum(
+                values.into_iter().map(str::to_string).collect(),
+            ));
+        }
+        if let Some(props) = self.struct_properties(obj) {
+            return Some(self.struct_kind(name, obj, props, owner));
+        }
+        let branches = non_null_branches(obj)?;
+        (branches.len() > 1).then(|| self.union_kind(name, &branches, owner))
+    }
+
+    /// Properties (with their `required` flags) if `obj` maps to a struct.
+    /// `allOf` branches that are all objects are merged into one.
+    fn struct_properties(
+        &self,
+        obj: &'a Map<String, Value>,
+    ) -> Option<Vec<(&'a str, &'a Value, bool)>> {
+        let is_object = match obj.get("type") {
+            Some(Value::String(t)) => t == "object",
+            Some(Value::Array(_)) => matches!(nullable_inner(obj), Some(Nullable::Type("object"))),
+            None => true,
+            _ => false,
+        };
+        if !is_object || obj.contains_key("anyOf") || obj.contains_key("oneOf") {
+            return None;
+        }
+
+        let mut parts = vec![obj];
+        if let Some(all_of) = obj.get("allOf").and_then(Value::as_array) {
+            for branch in all_of {
+                let branch = match branch.get("$ref").and_then(Value::as_str) {
+                    Some(pointer) => self.defs.get(pointer)?.1,
+                    None => branch,
+                };
+                parts.push(branch.as_object()?);
+            }
+        }
+
+        let mut props = Vec::new();
+        let mut seen = HashSet::new();
+        for part in parts {
+            let required: HashSet<&str> = part
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|r| r.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            for (key, schema) in part.get("properties").and_then(Value::as_object)?.iter() {
+                if seen.insert(key.as_str()) {
+                    props.push((key.as_str(), schema, required.contains(key.as_str())));
+                }
+            }
+        }
+        (!props.is_empty() || obj.get("additionalProperties") == Some(&Value::Bool(false)))
+            .then_some(props)
+    }
+
+    fn struct_kind(
+        &mut self,
+        name: &str,
+        obj: &'a Map<String, Value>,
+        props: Vec<(&'a str, &'a Value, bool)>,
+        owner: &str,
+    ) -> TypeKind {
+        let fields = props
+            .into_iter()
+            .map(|(key, schema, required)| Field {
+                key: key.to_string(),
+                ty: self.type_ref(schema, &format!("{name}{}", type_ident(key)), owner, true),
+                required,
+                description: description(schema),
+                deprecated: is_deprecated(schema),
+            })
+            .collect();
+        let extra = match obj.get("additionalProperties") {
+            Some(Value::Bool(true)) => Some(TypeRef::Any),
+            Some(schema @ Value::Object(_)) => {
+                Some(self.type_ref(schema, &format!("{name}Extra"), owner, false))
+            }
+            _ => None,
+        };
+        TypeKind::Struct { fields, extra }
+    }
+
+    fn union_kind(&mut self, name: &str, branches: &[&'a Value], owner: &str) -> TypeKind {
+        let mut taken = HashSet::new();
+        let variants = branches
+            .iter()
+            .enumerate()
+            .map(|(i, branch)| {
+                let base = match branch.get("$ref").and_then(Value::as_str) {
+                    Some(pointer) => self.defs.get(pointer).map(|(n, _)| n.clone()),
+                    None => branch
+                        .get("title")
+                        .and_then(Value::as_str)
+                        .map(type_ident)
+                        .or_else(|| branch.get("type").and_then(Value::as_str).map(type_ident)),
+                }
+                .unwrap_or_else(|| format!("Variant{}", i + 1));
+                let variant = unique(base, &mut taken);
+                Variant {
+                    ty: self.type_ref(branch, &format!("{name}{variant}"), owner, true),
+                    name: variant,
+                    description: description(branch),
+                }
+            })
+            .collect();
+        TypeKind::Union(variants)
+    }
+
+    /// The type of `schema`, defining inline types under `hint` as needed.
+    /// `direct` is false once the value sits behind an array or map.
+    fn type_ref(&mut self, schema: &'a Value, hint: &str, owner: &str, direct: bool) -> TypeRef {
+        let obj = match schema {
+            Value::Object(obj) => obj,
+            _ => return TypeRef::Any,
+        };
+
+        if let Some(pointer) = obj.get("$ref").and_then(Value::as_str) {
+            let Some((name, _)) = self.defs.get(pointer) else {
+                return TypeRef::Any;
+            };
+            return TypeRef::Named {
+                name: name.clone(),
+                recursive: direct && self.reaches(pointer, owner),
+            };
+        }
+
+        // `[T, "null"]` and `anyOf: [T, {type: null}]` are just optional `T`.
+        if let Some(inner) = nullable_inner(obj) {
+            let ty = match inner {
+                Nullable::Type(t) => self.typed(schema, obj, t, hint, owner),
+                Nullable::Branch(branch) => self.type_ref(branch, hint, owner, direct),
+            };
+            return TypeRef::Optional(Box::new(ty));
+        }
+
+        if let Some(value) = obj.get("const") {
+            return match value {
+                Value::String(_) => TypeRef::String,
+                Value::Bool(_) => TypeRef::Boolean,
+                Value::Number(n) if n.is_f64() => TypeRef::Number,
+                Value::Number(_) => TypeRef::Integer,
+                _ => TypeRef::Any,
+            };
+        }
+
+        if string_enum(obj).is_some() || non_null_branches(obj).is_some_and(|b| b.len() > 1) {
+            return self.define_inline(schema, hint, owner);
+        }
+        if let Some([single]) = obj
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            if self.struct_properties(obj).is_none() {
+                return self.type_ref(single, hint, owner, direct);
+            }
+        }
+        if let Some(branches) = non_null_branches(obj) {
+            if let [single] = branches.as_slice() {
+                return self.type_ref(single, hint, owner, direct);
+            }
+        }
+
+        match obj.get("type").and_then(Value::as_str) {
+            Some(t) => self.typed(schema, obj, t, hint, owner),
+            None if self.struct_properties(obj).is_some() => {
+                self.define_inline(schema, hint, owner)
+            }
+            None => TypeRef::Any,
+        }
+    }
+
+    /// The type of `schema` read as JSON type `type_name`.
+    fn typed(
+        &mut self,
+        schema: &'a Value,
+        obj: &'a Map<String, Value>,
+        type_name: &str,
+        hint: &str,
+        owner: &str,
+    ) -> TypeRef {
+        match type_name {
+            "array" => TypeRef::Array(Box::new(match obj.get("items") {
+                Some(items @ Value::Object(_)) => {
+                    self.type_ref(items, &format!("{hint}Item"), owner, false)
+                }
+                _ => TypeRef::Any,
+            })),
+            "object" if self.struct_properties(obj).is_some() => {
+                self.define_inline(schema, hint, owner)
+            }
+            "object" => match obj.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => TypeRef::Map(Box::new(self.type_ref(
+                    values,
+                    &format!("{hint}Value"),
+                    owner,
+                    false,
+                ))),
+                _ => TypeRef::Object,
+            },
+            "string" => TypeRef::String,
+            "integer" => TypeRef::Integer,
+            "number" => TypeRef::Number,
+            "boolean" => TypeRef::Boolean,
+            "null" => TypeRef::Null,
+            _ => TypeRef::Any,
+        }
+    }
+
+    /// Define an inline type under a fresh name derived from `hint`.
+    fn define_inline(&mut self, schema: &'a Value, hint: &str, owner: &str) -> TypeRef {
+        let name = unique(hint.to_string(), &mut self.taken);
+        self.define(&name, schema, owner, false);
+        TypeRef::Named {
+            name,
+            recursive: false,
+        }
+    }
+
+    /// Whether the definition at `from` contains `to` without indirection.
+    fn reaches(&self, from: &str, to: &str) -> bool {
+        let mut stack = vec![from];
+        let mut seen = HashSet::new();
+        while let Some(pointer) = stack.pop() {
+            if pointer == to {
+                return true;
+            }
+            if seen.insert(pointer) {
+                if let Some(next) = self.direct.get(pointer) {
+                    stack.extend(next.iter().map(String::as_str));
+                }
+            }
+        }
+        false
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Schema helpers
+// ---------------------------------------------------------------------------
+
+enum Nullable<'a> {
+    Type(&'a str),
+    Branch(&'a Value),
+}
+
+/// The non-null half of a two-way nullable schema.
+fn nullable_inner(obj: &Map<String, Value>) -> Option<Nullable<'_>> {
+    if let Some(Value::Array(types)) = obj.get("type") {
+        let rest: Vec<&str> = types
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|t| *t != "null")
+            .collect();
+        return (types.len() == 2 && rest.len() == 1).then(|| Nullable::Type(rest[0]));
+    }
+    let branches = obj
+        .get("anyOf")
+        .or_else(|| obj.get("oneOf"))
+        .and_then(Value::as_array)?;
+    let rest = non_null_branches(obj)?;
+    (branches.len() == 2 && rest.len() == 1).then(|| Nullable::Branch(rest[0]))
+}
+
+/// `anyOf`/`oneOf` branches other than `{"type": "null"}`.
+fn non_null_branches(obj: &Map<String, Value>) -> Option<Vec<&Value>> {
+    let branches = obj
+        .get("anyOf")
+        .or_else(|| obj.get("oneOf"))
+        .and_then(Value::as_array)?;
+    Some(
+        branches
+            .iter()
+            .filter(|b| b.get("type").and_then(Value::as_str) != Some("null"))
+            .collect(),
+    )
+}
+
+/// The values of an all-string `enum`.
+fn string_enum(obj: &Map<String, Value>) -> Option<Vec<&str>> {
+    obj.get("enum")?
+        .as_array()?
+        .iter()
+        .map(Value::as_str)
+        .collect::<Option<Vec<_>>>()
+        .filter(|values| !values.is_empty())
+}
+
+/// `$ref`s reachable from `schema` without passing through array items or
+/// map values.
+fn collect_direct_refs(schema: &Value, out: &mut BTreeSet<String>) {
+    let Some(obj) = schema.as_object() else {
+        return;
+    };
+    if let Some(pointer) = obj.get("$ref").and_then(Value::as_str) {
+        out.insert(pointer.to_string());
+    }
+    if let Some(props) = obj.get("properties").and_then(Value::as_object) {
+        props.values().for_each(|p| collect_direct_refs(p, out));
+    }
+    for keyword in ["anyOf", "oneOf", "allOf"] {
+        if let Some(branches) = obj.get(keyword).and_then(Value::as_array) {
+            branches.iter().for_each(|b| collect_direct_refs(b, out));
+        }
+    }
+}
+
+fn description(schema: &Value) -> Option<String> {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn is_deprecated(schema: &Value) -> bool {
+    schema.get("deprecated").and_then(Value::as_bool) == Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_names_inline_types_after_parent() {
+        let defs = build(
+            &json!({
+                "type": "object",
+                "properties": {
+                    "kind": { "enum": ["a", "b"] },
+                    "owner": { "type": ["object", "null"], "properties": { "id": { "type": "integer" } } }
+                },
+                "required": ["kind"]
+            }),
+            "pet",
+            &[],
+        );
+        let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["Pet", "PetKind", "PetOwner"]);
+        let TypeKind::Struct { fields, .. } = &defs[0].kind else {
+            panic!("root should be a struct");
+        };
+        assert_eq!(
+            fields[1].ty,
+            TypeRef::Optional(Box::new(TypeRef::Named {
+                name: "PetOwner".to_string(),
+                recursive: false
+            }))
+        );
+    }
+
+    #[test]
+    fn test_build_marks_direct_recursion_only() {
+        let defs = build(
+            &json!({
+                "$ref": "#/$defs/Node",
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "next": { "$ref": "#/$defs/Node" },
+                            "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } }
+                        }
+                    }
+                }
+            }),
+            "Root",
+            &[],
+        );
+        let node = defs.iter().find(|d| d.name == "Node").unwrap();
+        let TypeKind::Struct { fields, .. } = &node.kind else {
+            panic!("Node should be a struct");
+        };
+        let named = |recursive| TypeRef::Named {
+            name: "Node".to_string(),
+            recursive,
+        };
+        assert_eq!(fields[0].ty, TypeRef::Array(Box::new(named(false))));
+        assert_eq!(fields[1].ty, named(true));
+    }
+
+    #[test]
+    fn test_build_reserved_root_name_is_renamed() {
+        let defs = build(&json!({ "type": "string" }), "Error", &["Error"]);
+        assert_eq!(defs[0].name, "Error2");
+        assert_eq!(defs[0].kind, TypeKind::Alias(TypeRef::String));
+    }
+}
@@ -6,7 +6,7 @@ use rust_embed::Embed;
 use serde::Serialize;
 use tera::Tera;
 
-use crate::{Manifest, SdkConfig};
+use crate::{Manifest, MetadataContext, SdkConfig};
 
 #[derive(Embed)]
 #[folder = "templates/typescript/"]
@@ -16,13 +16,20 @@ struct TypeScriptTemplates;
 #[derive(Serialize)]
 struct PackageContext {
     package_name: String,
+    metadata: MetadataContext,
 }
 
 /// Template context for a single component module.
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct ComponentContext {
     component_name: String,
     module_name: String,
+    /// Module path under `src/` without extension, e.g. `defs/userProfile`.
+    module_file: String,
+    /// Relative path from the module's directory back to `src/`.
+    src_root: String,
+    /// Relative path from the module's directory to the package root.
+    package_root: String,
     enum_name: String,
     schema_path: String,
     codec_path: String,
@@ -46,16 +53,8 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     let manifest: Manifest =
         serde_json::from_str(&manifest_content).with_context(|| "Failed to parse manifest.json")?;
 
-    // Build Tera engine from embedded templates
-    let mut tera = Tera::default();
-    for file_name in TypeScriptTemplates::iter() {
-        let file = TypeScriptTemplates::get(&file_name)
-            .with_context(|| format!("Failed to load embedded template: {}", file_name))?;
-        let content = std::str::from_utf8(file.data.as_ref())
-            .with_context(|| format!("Template {} is not valid UTF-8", file_name))?;
-        tera.add_raw_template(&file_name, content)
-            .with_context(|| format!("Failed to register template: {}", file_name))?;
-    }
+    // Build Tera engine from embedded templates, with user overrides
+    let tera = crate::load_templates::<TypeScriptTemplates>(config.template_dir.as_deref())?;
 
     // Create output directory
     fs::create_dir_all(&config.output_dir).with_context(|| {
@@ -68,6 +67,7 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
     // Generate package.json
     let pkg_ctx = PackageContext {
         package_name: config.package.clone(),
+        metadata: config.metadata.context("0.1.0"),
     };
     render_to_file(
         &tera,
@@ -95,11 +95,21 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         .with_context(|| format!("Failed to create schemas dir: {}", schemas_dir.display()))?;
 
     // Build component contexts
+    // Smoke-test examples live next to the test suite
+    let test_dir = config.output_dir.join("test");
+
     let mut component_contexts: Vec<ComponentContext> = Vec::new();
+    let mut smoke_components: Vec<ComponentContext> = Vec::new();
     let component_names: Vec<String> = manifest.components.iter().map(|c| c.name.clone()).collect();
     let resolved_components = crate::resolve_collisions(&component_names);
-
-    for (component, resolved) in manifest.components.iter().zip(resolved_components.iter()) {
+    let groups = crate::module_groups(&manifest, config.layout);
+
+    for ((component, resolved), group) in manifest
+        .components
+        .iter()
+        .zip(resolved_components.iter())
+        .zip(&groups)
+    {
         let module_name = resolved.module_name_camel.clone();
 
         // Validate source schema/codec files exist
@@ -167,9 +177,24 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
             })?;
         }
 
+        let module_dir = group.iter().fold(src_dir.clone(), |dir, s| dir.join(s));
+        fs::create_dir_all(&module_dir)
+            .with_context(|| format!("Failed to create module dir: {}", module_dir.display()))?;
         let ctx = ComponentContext {
             component_name: resolved.original_name.clone(),
             module_name: module_name.clone(),
+            module_file: group
+                .iter()
+                .chain(std::iter::once(&module_name))
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("/"),
+            src_root: if group.is_empty() {
+                ".".to_string()
+            } else {
+                vec![".."; group.len()].join("/")
+            },
+            package_root: vec![".."; group.len() + 1].join("/"),
             enum_name: resolved.class_name.clone(), // TypeScript uses PascalCase for Enums matching the class
             schema_path: component.schema_path.clone(),
             codec_path: component.codec_path.clone(),
@@ -181,9 +206,16 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
             &tera,
             "component.ts.tera",
             &ctx,
-            &src_dir.join(format!("{}.ts", module_name)),
+            &module_dir.join(format!("{}.ts", module_name)),
         )?;
 
+        let example_path = test_dir
+            .join("examples")
+            .join(format!("{}.json", module_name));
+        if crate::examples::write(&config.schema_dir, component, &example_path)? {
+            smoke_components.push(ctx.clone());
+        }
+
         component_contexts.push(ctx);
     }
 
@@ -208,6 +240,21 @@ pub fn generate(config: &SdkConfig) -> Result<()> {
         &src_dir.join("jsonPatch.ts"),
     )?;
 
+    // Generate the smoke-test suite over the components with examples
+    if !smoke_components.is_empty() {
+        let smoke_ctx = IndexContext {
+            package_name: config.package.clone(),
+            source_schema: manifest.source_schema.clone(),
+            components: smoke_components,
+        };
+        render_to_file(
+            &tera,
+            "smoke.test.ts.tera",
+            &smoke_ctx,
+            &test_dir.join("smoke.test.ts"),
+        )?;
+    }
+
     // Generate README.md
     render_to_file(
         &tera,
@@ -254,7 +301,7 @@ fn render_to_file<T: Serialize>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::BuildTool;
+    use crate::{BuildTool, ModuleLayout, ProjectMetadata};
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -303,6 +350,9 @@ mod tests {
             output_dir: PathBuf::from(output_dir.path()),
             git_init: false,
             build_tool: BuildTool::Npm,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         generate(&config).expect("generation should succeed");
@@ -461,6 +511,9 @@ mod tests {
             output_dir: PathBuf::from(output_dir.path()),
             git_init: false,
             build_tool: BuildTool::Npm,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
         };
 
         let result = generate(&config);
@@ -470,4 +523,105 @@ mod tests {
             .to_string()
             .contains("Schema file not found"));
     }
+
+    #[test]
+    fn test_generate_emits_smoke_tests() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Npm,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let example: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(output_dir.join("test/examples/alert.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(example["llmOutput"], serde_json::json!({"level": "1"}));
+        assert_eq!(example["rehydrated"], serde_json::json!({"level": 1}));
+
+        let smoke = fs::read_to_string(output_dir.join("test/smoke.test.ts")).unwrap();
+        assert!(smoke.contains("import * as alert from \"../src/alert.js\";"));
+    }
+
+    #[test]
+    fn test_generate_nests_modules_by_pointer_prefix() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Npm,
+            template_dir: None,
+            layout: ModuleLayout::PointerPrefix,
+            metadata: ProjectMetadata::default(),
+        };
+        generate(&config).expect("generate should succeed");
+
+        let module = fs::read_to_string(output_dir.join("src/defs/alert.ts")).unwrap();
+        assert!(module.contains("from \"../jsonPatch.js\";"));
+        assert!(module.contains("join(__dirname, \"../..\", \"schemas\""));
+        let index = fs::read_to_string(output_dir.join("src/index.ts")).unwrap();
+        assert!(index.contains("import * as alert from \"./defs/alert.js\";"));
+        let smoke = fs::read_to_string(output_dir.join("test/smoke.test.ts")).unwrap();
+        assert!(smoke.contains("import * as alert from \"../src/defs/alert.js\";"));
+    }
+
+    #[test]
+    fn test_generate_surfaces_project_metadata() {
+        let tmp = TempDir::new().unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schema_dir).unwrap();
+        crate::examples::write_schema_dir(&schema_dir);
+
+        let output_dir = tmp.path().join("output");
+        let config = SdkConfig {
+            package: "my-sdk".to_string(),
+            artifact_name: "my-sdk".to_string(),
+            schema_dir,
+            output_dir: output_dir.clone(),
+            git_init: false,
+            build_tool: BuildTool::Npm,
+            template_dir: None,
+            layout: ModuleLayout::Flat,
+            metadata: ProjectMetadata {
+                version: Some("2.1.0".to_string()),
+                license: Some("MIT".to_string()),
+                authors: vec![
+                    "Jane Doe <jane@example.com>".to_string(),
+                    "Build Bot".to_string(),
+                ],
+                repository: Some("https://github.com/example/sdk".to_string()),
+            },
+        };
+        generate(&config).expect("generate should succeed");
+
+        let pkg: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output_dir.join("package.json")).unwrap())
+                .unwrap();
+        assert_eq!(pkg["version"], "2.1.0");
+        assert_eq!(pkg["license"], "MIT");
+        assert_eq!(pkg["author"], "Jane Doe <jane@example.com>");
+        assert_eq!(pkg["contributors"], serde_json::json!(["Build Bot"]));
+        assert_eq!(pkg["repository"]["url"], "https://github.com/example/sdk");
+    }
 }
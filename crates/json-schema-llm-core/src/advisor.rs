@@ -0,0 +1,484 @@
+//! Retry advice for structured output that failed to rehydrate.
+//!
+//! When a model's answer can't be used, a retry prompt that names the
+//! mistake works far better than a bare "try again". [`diagnose_failure`]
+//! checks raw model output against the converted schema and codec and
+//! classifies what went wrong:
+//!
+//! - the text is not JSON (or was cut off);
+//! - a non-object root was not wrapped in its wrapper key;
+//! - a required property is missing;
+//! - a discriminator names no variant;
+//! - a JSON-string field does not hold parseable JSON.
+//!
+//! Each [`OutputFailure`] carries a `remediation` written as an instruction
+//! to the model, and [`FailureReport::retry_hint`] joins them into one block
+//! for the retry prompt.
+//!
+//! ```rust
+//! use json_schema_llm_core::{convert, diagnose_failure, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({"type": "array", "items": {"type": "string"}});
+//! let result = convert(&schema, &ConvertOptions::default()).unwrap();
+//!
+//! let report = diagnose_failure(r#"["a", "b"]"#, &result.schema, &result.codec);
+//! assert_eq!(report.failures.len(), 1);
+//! assert!(report.retry_hint.unwrap().contains("\"result\""));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::codec::{Codec, Transform};
+use crate::codec_warning::Warning;
+use crate::error::ConvertError;
+use crate::path_map::transform_path;
+use crate::rehydrator::{
+    build_pattern_properties_cache, walk, Rehydrate, RehydrateOptions, TransformSite,
+};
+use crate::schema_utils::{escape_pointer_segment, resolve_pointer, split_path};
+
+/// Nesting depth past which required properties are no longer checked.
+const MAX_DEPTH: usize = 64;
+
+/// Longest excerpt of a bad value quoted in a message.
+const EXCERPT_CHARS: usize = 60;
+
+/// Everything found wrong with one model answer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureReport {
+    /// Each problem found, in the order the answer was checked.
+    pub failures: Vec<OutputFailure>,
+    /// The failures' remediations as one block for a retry prompt; `None`
+    /// when nothing was found.
+    pub retry_hint: Option<String>,
+}
+
+/// One problem with a model answer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputFailure {
+    /// Data location of the problem (e.g. `/items/0/kind`); `/` for the root.
+    pub data_path: String,
+    /// Classification of the problem.
+    pub kind: FailureKind,
+    /// Human-readable description.
+    pub message: String,
+    /// What the model should do differently, worded for a retry prompt.
+    pub remediation: String,
+}
+
+/// Classification of an [`OutputFailure`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FailureKind {
+    /// The output does not parse as JSON.
+    InvalidJson {
+        /// The output ended before the JSON value was complete.
+        truncated: bool,
+    },
+    /// The schema root was wrapped in an object, but the output is not.
+    MissingWrapperKey {
+        #[serde(rename = "wrapperKey")]
+        wrapper_key: String,
+    },
+    /// A property the converted schema requires is absent.
+    MissingProperty { property: String },
+    /// A discriminator holds a value naming none of the union's variants.
+    WrongDiscriminator {
+        discriminator: String,
+        /// The value found; `None` when absent or not a string.
+        value: Option<String>,
+        expected: Vec<String>,
+    },
+    /// A field the codec parses from a JSON string does not hold valid JSON.
+    UnparseableJsonString,
+}
+
+/// Classify why `raw_llm_output` can't be rehydrated with `codec`, the
+/// codec of `converted_schema`.
+///
+/// Problems are looked for in order: JSON syntax (which stops the rest),
+/// the root wrapper, required properties, then each codec transform as
+/// rehydration would apply it. An answer that merely violates dropped
+/// constraints is not a failure here; see the warnings of
+/// [`crate::rehydrate`].
+pub fn diagnose_failure(
+    raw_llm_output: &str,
+    converted_schema: &Value,
+    codec: &Codec,
+) -> FailureReport {
+    let mut failures = Vec::new();
+    match serde_json::from_str::<Value>(raw_llm_output.trim()) {
+        Ok(data) => diagnose_value(data, converted_schema, codec, &mut failures),
+        Err(e) => failures.push(invalid_json(raw_llm_output, &e)),
+    }
+    let retry_hint = (!failures.is_empty()).then(|| {
+        let mut hint = String::from("Your previous answer could not be used:");
+        for failure in &failures {
+            hint.push_str("\n- ");
+            hint.push_str(&failure.remediation);
+        }
+        hint
+    });
+    FailureReport {
+        failures,
+        retry_hint,
+    }
+}
+
+fn diagnose_value(
+    mut data: Value,
+    converted_schema: &Value,
+    codec: &Codec,
+    failures: &mut Vec<OutputFailure>,
+) {
+    // An unwrapped answer is diagnosed as if it had been wrapped, so its
+    // other problems are reported in the same pass.
+    let wrapper = codec.transforms.iter().find_map(|t| match t {
+        Transform::RootObjectWrapper { wrapper_key, .. } => Some(wrapper_key),
+        _ => None,
+    });
+    if let Some(wrapper_key) = wrapper {
+        let wrapped = data
+            .as_object()
+            .is_some_and(|obj| obj.contains_key(wrapper_key));
+        if !wrapped {
+            failures.push(OutputFailure {
+                data_path: "/".to_string(),
+                kind: FailureKind::MissingWrapperKey {
+                    wrapper_key: wrapper_key.clone(),
+                },
+                message: format!("root is not wrapped in an object under `{}`", wrapper_key),
+                remediation: format!(
+                    "Wrap the whole answer in an object under the key \"{}\": {{\"{}\": <answer>}}.",
+                    wrapper_key, wrapper_key
+                ),
+            });
+            let mut obj = Map::new();
+            obj.insert(wrapper_key.clone(), data);
+            data = Value::Object(obj);
+        }
+    }
+
+    check_required(&data, converted_schema, converted_schema, "", 0, failures);
+
+    let regex_cache = build_pattern_properties_cache(codec);
+    let options = RehydrateOptions::default();
+    let mut warnings: Vec<Warning> = Vec::new();
+    let mut site = Diagnose {
+        inner: Rehydrate::new(&options, &mut warnings),
+        failures,
+    };
+    for transform in codec.transforms.iter().rev() {
+        if matches!(transform, Transform::Custom { .. }) {
+            continue;
+        }
+        let segments = split_path(transform_path(transform));
+        let seg_refs: Vec<&str> = segments.iter().map(String::as_str).collect();
+        // Diagnose records failures instead of returning them.
+        let _ = walk(&mut data, &seg_refs, transform, &regex_cache, "", &mut site);
+    }
+}
+
+fn invalid_json(raw: &str, error: &serde_json::Error) -> OutputFailure {
+    let truncated = error.is_eof();
+    let fenced = raw.trim_start().starts_with("```");
+    let remediation = if truncated {
+        "Your answer was cut off before the JSON was complete. Reply with the complete JSON value, keeping long text fields brief.".to_string()
+    } else if fenced {
+        "Reply with the JSON value only, without Markdown code fences.".to_string()
+    } else {
+        format!(
+            "Reply with a single valid JSON value and nothing else, no prose before or after it (the last answer failed to parse: {}).",
+            error
+        )
+    };
+    OutputFailure {
+        data_path: "/".to_string(),
+        kind: FailureKind::InvalidJson { truncated },
+        message: format!("output is not valid JSON: {}", error),
+        remediation,
+    }
+}
+
+/// Report required properties of `schema` missing from `data`, through
+/// objects, arrays and nullable unions.
+fn check_required(
+    data: &Value,
+    schema: &Value,
+    root: &Value,
+    data_path: &str,
+    depth: usize,
+    failures: &mut Vec<OutputFailure>,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    if let Some(target) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| resolve_pointer(root, r))
+    {
+        return check_required(data, target, root, data_path, depth + 1, failures);
+    }
+    match data {
+        Value::Object(obj) => {
+            for name in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .filter(|name| !obj.contains_key(*name))
+            {
+                failures.push(OutputFailure {
+                    data_path: display_path(data_path),
+                    kind: FailureKind::MissingProperty {
+                        property: name.to_string(),
+                    },
+                    message: format!("required property `{}` is missing", name),
+                    remediation: format!(
+                        "At {}, include the required field \"{}\"; use null if it allows null and the value is unknown.",
+                        display_path(data_path),
+                        name
+                    ),
+                });
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, value) in obj {
+                if let Some(prop) = properties.and_then(|p| p.get(key)) {
+                    let child = format!("{}/{}", data_path, escape_pointer_segment(key));
+                    check_required(value, prop, root, &child, depth + 1, failures);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items").filter(|i| i.is_object()) {
+                for (i, item) in items.iter().enumerate() {
+                    let child = format!("{}/{}", data_path, i);
+                    check_required(item, item_schema, root, &child, depth + 1, failures);
+                }
+            }
+        }
+        _ => {}
+    }
+    if !data.is_null() {
+        if let Some(inner) = nullable_inner(schema) {
+            check_required(data, inner, root, data_path, depth + 1, failures);
+        }
+    }
+}
+
+/// The one non-`null` branch of a nullable union.
+fn nullable_inner(schema: &Value) -> Option<&Value> {
+    let mut branches = ["anyOf", "oneOf"]
+        .iter()
+        .filter_map(|k| schema.get(*k).and_then(Value::as_array))
+        .flatten()
+        .filter(|b| b.get("type").and_then(Value::as_str) != Some("null"));
+    let only = branches.next()?;
+    branches.next().is_none().then_some(only)
+}
+
+fn display_path(data_path: &str) -> String {
+    if data_path.is_empty() {
+        "/".to_string()
+    } else {
+        data_path.to_string()
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    let mut chars = text.chars();
+    let head: String = chars.by_ref().take(EXCERPT_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}…", head)
+    } else {
+        head
+    }
+}
+
+/// Rehydration that records what goes wrong instead of failing.
+struct Diagnose<'a, 'f> {
+    inner: Rehydrate<'a>,
+    failures: &'f mut Vec<OutputFailure>,
+}
+
+impl TransformSite for Diagnose<'_, '_> {
+    fn at_node(
+        &mut self,
+        data: &mut Value,
+        transform: &Transform,
+        data_path: &str,
+    ) -> Result<(), ConvertError> {
+        if let Transform::DiscriminatorAnyOf {
+            discriminator,
+            variants,
+            ..
+        } = transform
+        {
+            let value = data.get(discriminator).and_then(Value::as_str);
+            if data.is_object()
+                && !variants.is_empty()
+                && !value.is_some_and(|v| variants.iter().any(|x| x == v))
+            {
+                let at = format!("{}/{}", data_path, escape_pointer_segment(discriminator));
+                let expected = variants
+                    .iter()
+                    .map(|v| format!("\"{}\"", v))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.failures.push(OutputFailure {
+                    data_path: at.clone(),
+                    kind: FailureKind::WrongDiscriminator {
+                        discriminator: discriminator.clone(),
+                        value: value.map(String::from),
+                        expected: variants.clone(),
+                    },
+                    message: match value {
+                        Some(v) => format!("discriminator {:?} names no variant", v),
+                        None => "discriminator is missing or not a string".to_string(),
+                    },
+                    remediation: format!(
+                        "At {}, \"{}\" must be exactly one of: {}.",
+                        at, discriminator, expected
+                    ),
+                });
+            }
+        }
+
+        if let Err(e) = self.inner.at_node(data, transform, data_path) {
+            if matches!(
+                transform,
+                Transform::JsonStringParse { .. } | Transform::RecursiveInflate { .. }
+            ) {
+                let at = display_path(data_path);
+                let found = excerpt(data.as_str().unwrap_or_default());
+                self.failures.push(OutputFailure {
+                    data_path: at.clone(),
+                    kind: FailureKind::UnparseableJsonString,
+                    message: e.to_string(),
+                    remediation: format!(
+                        "At {}, the string must hold valid serialized JSON with inner quotes escaped, e.g. \"{{\\\"key\\\": \\\"value\\\"}}\"; got: {}",
+                        at, found
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn at_property(&mut self, parent: &mut Value, key: &str, transform: &Transform) -> bool {
+        self.inner.at_property(parent, key, transform)
+    }
+
+    fn at_items(&mut self, items: &mut Vec<Value>, transform: &Transform) -> bool {
+        self.inner.at_items(items, transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, ConvertOptions};
+    use serde_json::json;
+
+    fn kinds(report: &FailureReport) -> Vec<&FailureKind> {
+        report.failures.iter().map(|f| &f.kind).collect()
+    }
+
+    #[test]
+    fn test_diagnose_invalid_json() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "a": { "type": "array", "items": { "type": "integer" } } }
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+
+        let report = diagnose_failure("{\"a\": [1, 2", &result.schema, &result.codec);
+        assert_eq!(
+            kinds(&report),
+            [&FailureKind::InvalidJson { truncated: true }]
+        );
+
+        let report = diagnose_failure("```json\n{}\n```", &result.schema, &result.codec);
+        assert_eq!(
+            kinds(&report),
+            [&FailureKind::InvalidJson { truncated: false }]
+        );
+        assert!(report.retry_hint.unwrap().contains("code fences"));
+
+        let report = diagnose_failure("{\"a\": null}", &result.schema, &result.codec);
+        assert_eq!(report, FailureReport::default());
+    }
+
+    #[test]
+    fn test_diagnose_missing_property_and_json_string() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "meta": {}
+            },
+            "required": ["name", "meta"]
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+
+        let report = diagnose_failure(r#"{"meta": "{key: 1}"}"#, &result.schema, &result.codec);
+        assert_eq!(
+            kinds(&report),
+            [
+                &FailureKind::MissingProperty {
+                    property: "name".to_string()
+                },
+                &FailureKind::UnparseableJsonString,
+            ]
+        );
+        assert_eq!(report.failures[1].data_path, "/meta");
+        assert!(report.failures[1].remediation.contains("{key: 1}"));
+    }
+
+    #[test]
+    fn test_diagnose_wrong_discriminator() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "pet": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "properties": { "kind": { "const": "cat" }, "lives": { "type": "integer" } },
+                            "required": ["kind", "lives"]
+                        },
+                        {
+                            "type": "object",
+                            "properties": { "kind": { "const": "dog" }, "breed": { "type": "string" } },
+                            "required": ["kind", "breed"]
+                        }
+                    ],
+                    "discriminator": { "propertyName": "kind" }
+                }
+            },
+            "required": ["pet"]
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+
+        let report = diagnose_failure(
+            r#"{"pet": {"kind": "bird", "lives": 1}}"#,
+            &result.schema,
+            &result.codec,
+        );
+        assert_eq!(
+            kinds(&report),
+            [&FailureKind::WrongDiscriminator {
+                discriminator: "kind".to_string(),
+                value: Some("bird".to_string()),
+                expected: vec!["cat".to_string(), "dog".to_string()],
+            }]
+        );
+        assert_eq!(report.failures[0].data_path, "/pet/kind");
+    }
+}
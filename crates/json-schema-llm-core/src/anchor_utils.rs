@@ -2,7 +2,9 @@
 //!
 //! Builds a map from absolute URI strings (incorporating `$id` base URI scoping)
 //! to JSON Pointer paths. Used by `extract.rs` and `p0_normalize.rs` to resolve
-//! `$anchor`-style `$ref` values (e.g., `"#stepId"`) into standard JSON Pointers.
+//! `$anchor`-style `$ref` values (e.g., `"#stepId"`) and refs to embedded
+//! `$id` resources (e.g., `"https://example.com/address"`) into standard JSON
+//! Pointers.
 
 use std::collections::HashMap;
 
@@ -20,6 +22,7 @@ const DEFAULT_BASE: &str = "file:///schema.json";
 
 /// Build a map of absolute URI strings → JSON Pointer paths by scanning
 /// the schema for `$anchor` declarations, respecting `$id` base URI scoping.
+/// Every `$id` resource is indexed too, under its URI without fragment.
 ///
 /// ## Arguments
 ///
@@ -30,7 +33,8 @@ const DEFAULT_BASE: &str = "file:///schema.json";
 /// ## Returns
 ///
 /// A `HashMap<String, String>` where:
-/// * **key** = absolute URI with fragment (e.g., `"file:///schema.json#stepId"`)
+/// * **key** = absolute URI with fragment (e.g., `"file:///schema.json#stepId"`),
+///   or a resource URI (e.g., `"https://example.com/address"`)
 /// * **value** = JSON Pointer path (e.g., `"#/$defs/step-object"`)
 ///
 /// ## Errors
@@ -43,7 +47,17 @@ pub(crate) fn build_anchor_map(
     let default_base = Url::parse(DEFAULT_BASE).expect("DEFAULT_BASE is a valid URL");
     let base = base_uri.unwrap_or(&default_base);
     let mut map = HashMap::new();
-    scan_anchors(schema, base, "#", &mut map)?;
+    scan_anchors(schema, "$anchor", base, "#", &mut map)?;
+    Ok(map)
+}
+
+/// Like [`build_anchor_map`], but for `$dynamicAnchor` declarations.
+pub(crate) fn build_dynamic_anchor_map(
+    schema: &Value,
+    base_uri: &Url,
+) -> Result<HashMap<String, String>, ConvertError> {
+    let mut map = HashMap::new();
+    scan_anchors(schema, "$dynamicAnchor", base_uri, "#", &mut map)?;
     Ok(map)
 }
 
@@ -54,16 +68,26 @@ pub(crate) fn default_base_uri() -> Url {
 
 /// Resolve a `$ref` string against a base URI and look it up in the anchor map.
 ///
-/// Returns `Some(json_pointer)` if the resolved URI maps to an anchor,
-/// `None` otherwise.
+/// Returns `Some(json_pointer)` if the resolved URI maps to an anchor, or to
+/// an embedded `$id` resource (optionally followed by a JSON Pointer fragment
+/// into it), `None` otherwise.
 pub(crate) fn resolve_ref_via_anchor_map(
     ref_str: &str,
     base_uri: &Url,
     anchor_map: &HashMap<String, String>,
 ) -> Option<String> {
     // Try to resolve the ref_str as a URL against the base URI.
-    let resolved = base_uri.join(ref_str).ok()?;
-    anchor_map.get(resolved.as_str()).cloned()
+    let mut resolved = base_uri.join(ref_str).ok()?;
+    if let Some(pointer) = anchor_map.get(resolved.as_str()) {
+        return Some(pointer.clone());
+    }
+    let fragment = resolved.fragment().unwrap_or("").to_string();
+    if !fragment.is_empty() && !fragment.starts_with('/') {
+        return None;
+    }
+    resolved.set_fragment(None);
+    let resource = anchor_map.get(resolved.as_str())?;
+    Some(format!("{}{}", resource, fragment))
 }
 
 /// Result of resolving a `$ref` string through the anchor map.
@@ -98,12 +122,14 @@ pub(crate) fn resolve_ref(
     }
 }
 
-/// Recursive DFS scanner that builds the anchor map.
+/// Recursive DFS scanner that builds the anchor map for `keyword`
+/// (`$anchor` or `$dynamicAnchor`).
 ///
 /// Tracks `current_base` (updated by `$id`) and `pointer` (the JSON Pointer
 /// path to the current node).
 fn scan_anchors(
     node: &Value,
+    keyword: &str,
     current_base: &Url,
     pointer: &str,
     map: &mut HashMap<String, String>,
@@ -113,7 +139,7 @@ fn scan_anchors(
         Value::Array(arr) => {
             for (i, item) in arr.iter().enumerate() {
                 let child_pointer = format!("{}/{}", pointer, i);
-                scan_anchors(item, current_base, &child_pointer, map)?;
+                scan_anchors(item, keyword, current_base, &child_pointer, map)?;
             }
             return Ok(());
         }
@@ -123,15 +149,23 @@ fn scan_anchors(
     // Check for $id — updates the base URI for this scope and below.
     let scoped_base = if let Some(id_val) = obj.get("$id").and_then(Value::as_str) {
         match current_base.join(id_val) {
-            Ok(new_base) => new_base,
+            Ok(mut new_base) => {
+                // Index the resource itself (plain anchor map only).
+                if keyword == "$anchor" {
+                    new_base.set_fragment(None);
+                    map.entry(new_base.to_string())
+                        .or_insert_with(|| pointer.to_string());
+                }
+                new_base
+            }
             Err(_) => current_base.clone(), // malformed $id — ignore
         }
     } else {
         current_base.clone()
     };
 
-    // Check for $anchor — register it in the map.
-    if let Some(anchor_val) = obj.get("$anchor").and_then(Value::as_str) {
+    // Check for the anchor keyword — register it in the map.
+    if let Some(anchor_val) = obj.get(keyword).and_then(Value::as_str) {
         let fragment = format!("#{}", anchor_val);
         if let Ok(absolute_uri) = scoped_base.join(&fragment) {
             let uri_str = absolute_uri.to_string();
@@ -144,12 +178,12 @@ fn scan_anchors(
 
     // Recurse into all child values.
     for (key, val) in obj {
-        if key == "$anchor" || key == "$id" {
+        if key == keyword || key == "$id" {
             continue; // already processed
         }
         let escaped_key = escape_pointer_segment(key);
         let child_pointer = format!("{}/{}", pointer, escaped_key);
-        scan_anchors(val, &scoped_base, &child_pointer, map)?;
+        scan_anchors(val, keyword, &scoped_base, &child_pointer, map)?;
     }
 
     Ok(())
@@ -0,0 +1,89 @@
+//! Cooperative cancellation for long conversions.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+
+/// Shared flag that aborts a conversion from another thread.
+///
+/// Set it as [`ConvertOptions::cancellation`]; once [`cancel`](Self::cancel)
+/// is called, the conversion stops at the next schema node it visits and
+/// returns [`ConvertError::Cancelled`]. Clones share the same flag.
+///
+/// ```rust
+/// use json_schema_llm_core::{convert, CancellationToken, ConvertOptions, ErrorCode};
+///
+/// let token = CancellationToken::new();
+/// let mut options = ConvertOptions::default();
+/// options.cancellation = Some(token.clone());
+///
+/// token.cancel();
+/// let err = convert(&serde_json::json!({"type": "string"}), &options).unwrap_err();
+/// assert_eq!(err.error_code(), ErrorCode::Cancelled);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Idempotent.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail with [`ConvertError::Cancelled`] at `path` once cancelled.
+    pub(crate) fn check(&self, path: &str) -> Result<(), ConvertError> {
+        if self.is_cancelled() {
+            return Err(ConvertError::Cancelled {
+                path: path.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// [`CancellationToken::check`] for the token in `options`, if any.
+pub(crate) fn check(options: &ConvertOptions, path: &str) -> Result<(), ConvertError> {
+    match &options.cancellation {
+        Some(token) => token.check(path),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_flag() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn test_check_reports_path() {
+        let mut options = ConvertOptions::default();
+        assert!(check(&options, "#").is_ok());
+
+        let token = CancellationToken::new();
+        options.cancellation = Some(token.clone());
+        assert!(check(&options, "#").is_ok());
+
+        token.cancel();
+        let err = check(&options, "#/properties/a").unwrap_err();
+        assert_eq!(err.path(), Some("#/properties/a"));
+    }
+}
@@ -0,0 +1,114 @@
+//! Diff-stable form of a converted schema, for
+//! [`ConvertOptions::canonical`](crate::ConvertOptions::canonical).
+//!
+//! Two conversions of equivalent input should produce byte-identical files
+//! so that checked-in schemas only change when their meaning does. The
+//! canonical form sorts object keys (properties included), sorts and
+//! de-duplicates the set-valued `required` and `type` arrays, and writes
+//! integral floats as integers (`1.0` → `1`). Arrays whose order carries
+//! meaning — `enum`, `anyOf`, `prefixItems`, `propertyOrdering` — are left
+//! alone.
+
+use serde_json::{Map, Number, Value};
+
+use crate::schema_utils::recurse_into_children;
+
+/// Bring `schema` into canonical form.
+pub(crate) fn canonicalize(schema: Value) -> Value {
+    canonical_node(schema, "#", 0).expect("canonicalization does not fail")
+}
+
+fn canonical_node(
+    schema: Value,
+    path: &str,
+    depth: usize,
+) -> Result<Value, crate::error::ConvertError> {
+    let Value::Object(mut obj) = schema else {
+        return Ok(canonical_value(schema));
+    };
+    recurse_into_children(&mut obj, path, depth, &mut canonical_node)?;
+    for keyword in ["required", "type"] {
+        if let Some(Value::Array(items)) = obj.get_mut(keyword) {
+            sort_set(items);
+        }
+    }
+    Ok(Value::Object(sorted(obj, canonical_value)))
+}
+
+/// Sort and de-duplicate an array of strings; leave other arrays as they are.
+fn sort_set(items: &mut Vec<Value>) {
+    if !items.iter().all(Value::is_string) {
+        return;
+    }
+    items.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    items.dedup();
+}
+
+/// Canonical form of a non-schema value: sorted keys and integral numbers,
+/// at every level.
+fn canonical_value(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(sorted(map, canonical_value)),
+        Value::Array(items) => Value::Array(items.into_iter().map(canonical_value).collect()),
+        Value::Number(n) => Value::Number(integral(n)),
+        other => other,
+    }
+}
+
+/// `map` with its keys in sorted order and `f` applied to each value.
+/// A no-op reordering unless `serde_json`'s `preserve_order` is enabled.
+fn sorted(map: Map<String, Value>, f: impl Fn(Value) -> Value) -> Map<String, Value> {
+    let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries.into_iter().map(|(k, v)| (k, f(v))).collect()
+}
+
+/// `n` as an integer when it is an integral float within `i64` precision.
+pub(crate) fn integral(n: Number) -> Number {
+    match n.as_f64() {
+        Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() < 9.007_199_254_740_992e15 => {
+            Number::from(f as i64)
+        }
+        _ => n,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonicalize_sorts_sets_and_numbers() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "b": { "type": ["string", "null"], "maxLength": 10.0 },
+                "a": { "enum": ["z", "a"], "default": { "y": 2.0, "x": 1 } }
+            },
+            "required": ["b", "a", "b"],
+            "anyOf": [{ "required": ["b"] }, { "required": ["a"] }]
+        });
+        let canonical = canonicalize(schema);
+        assert_eq!(canonical["required"], json!(["a", "b"]));
+        assert_eq!(
+            canonical["properties"]["b"]["type"],
+            json!(["null", "string"])
+        );
+        assert_eq!(canonical["properties"]["b"]["maxLength"].to_string(), "10");
+        assert_eq!(canonical["properties"]["a"]["enum"], json!(["z", "a"]));
+        assert_eq!(
+            canonical["properties"]["a"]["default"].to_string(),
+            r#"{"x":1,"y":2}"#
+        );
+        assert_eq!(canonical["anyOf"][0]["required"], json!(["b"]));
+    }
+
+    #[test]
+    fn test_canonicalize_leaves_data_arrays_alone() {
+        // `required` inside a default value is data, not a keyword.
+        let schema = json!({ "default": { "required": ["b", "a"] }, "type": "object" });
+        let canonical = canonicalize(schema.clone());
+        assert_eq!(canonical, schema);
+    }
+}
@@ -1,5 +1,7 @@
 //! Rehydration codec — metadata for reconstructing original shape from LLM output.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Codec format version URI constant.
@@ -28,10 +30,20 @@ pub struct Codec {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Transform {
+    /// The map at `path` became an array of `{<key_field>, value}` entries.
+    /// When its value schema was a union, each entry also names the branch
+    /// its value takes in `variant_field`, and transforms under another
+    /// branch of the union skip it.
     MapToArray {
         path: String,
         #[serde(rename = "keyField")]
         key_field: String,
+        #[serde(
+            rename = "variantField",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        variant_field: Option<String>,
     },
     JsonStringParse {
         path: String,
@@ -41,10 +53,20 @@ pub enum Transform {
         #[serde(rename = "originalRequired")]
         original_required: bool,
     },
+    /// The union at `path` has a `discriminator` on property
+    /// `discriminator`, whose values select one of `variants`; `required`
+    /// lists the required properties of each value's variant. Rehydration
+    /// repairs near-miss values (see
+    /// [`RehydrateOptions::normalize_discriminators`]) and reports objects
+    /// that match no variant cleanly.
+    ///
+    /// [`RehydrateOptions::normalize_discriminators`]: crate::RehydrateOptions::normalize_discriminators
     DiscriminatorAnyOf {
         path: String,
         discriminator: String,
         variants: Vec<String>,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        required: BTreeMap<String, Vec<String>>,
     },
     ExtractAdditionalProperties {
         path: String,
@@ -56,6 +78,27 @@ pub enum Transform {
         #[serde(rename = "originalRef")]
         original_ref: String,
     },
+    /// A recursive `$ref` past the recursion limit was replaced with `null`
+    /// ([`RecursionLeaf::Null`]). The `null` is dropped from its parent
+    /// object or array.
+    ///
+    /// [`RecursionLeaf::Null`]: crate::RecursionLeaf::Null
+    RecursiveTerminate {
+        path: String,
+        #[serde(rename = "originalRef")]
+        original_ref: String,
+    },
+    /// A recursive `$ref` past the recursion limit was replaced with a
+    /// `{"$node_id": string}` reference ([`RecursionLeaf::NodeId`]). Once all
+    /// other transforms are applied, each reference becomes a copy of the
+    /// node carrying that `$node_id`, and the ids are removed.
+    ///
+    /// [`RecursionLeaf::NodeId`]: crate::RecursionLeaf::NodeId
+    RecursiveNodeRef {
+        path: String,
+        #[serde(rename = "originalRef")]
+        original_ref: String,
+    },
     RootObjectWrapper {
         path: String,
         #[serde(rename = "wrapperKey")]
@@ -66,6 +109,80 @@ pub enum Transform {
         #[serde(rename = "originalValues")]
         original_values: Vec<serde_json::Value>,
     },
+    /// Union variants beyond [`ConvertOptions::max_anyof_variants`] were
+    /// collapsed into one JSON-string branch of the union at `path`. A string
+    /// there is parsed back; if a kept variant also accepts strings
+    /// (`kept_string`), only strings holding a JSON object or array are.
+    ///
+    /// [`ConvertOptions::max_anyof_variants`]: crate::ConvertOptions::max_anyof_variants
+    AnyOfOverflow {
+        path: String,
+        #[serde(rename = "keptString")]
+        kept_string: bool,
+    },
+    /// Property `property` of the object at `path` was removed because it
+    /// is marked `keyword` (`readOnly` or `writeOnly`; see
+    /// [`ConvertOptions::filter_read_only`]). Rehydration re-inserts
+    /// `placeholder`, taken from the property's `default` or `const`, if the
+    /// schema had one; otherwise the property stays absent.
+    ///
+    /// [`ConvertOptions::filter_read_only`]: crate::ConvertOptions::filter_read_only
+    FilteredProperty {
+        path: String,
+        property: String,
+        keyword: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        placeholder: Option<serde_json::Value>,
+    },
+    /// Projection (see [`project_schema`]) narrowed the `anyOf` / `oneOf`
+    /// branches of the object at `path` to `variants`. Rehydration applies
+    /// only the `transforms` of the first variant the object fits, so a
+    /// branch's removed properties come back only in objects taking it.
+    ///
+    /// [`project_schema`]: crate::project_schema
+    ProjectedVariants {
+        path: String,
+        variants: Vec<ProjectedVariant>,
+    },
+    /// A string field embedding JSON (`contentMediaType: application/json`)
+    /// was replaced by its `contentSchema`; see
+    /// [`ContentSchemaPolicy::Inline`]. The inverse of [`JsonStringParse`]:
+    /// rehydration serializes the structured value back into a JSON string.
+    ///
+    /// [`ContentSchemaPolicy::Inline`]: crate::ContentSchemaPolicy::Inline
+    /// [`JsonStringParse`]: Transform::JsonStringParse
+    JsonStringEncode {
+        path: String,
+    },
+    /// A transform recorded by a custom [`ConversionPass`]. The built-in
+    /// rehydrator only reverses it through a handler registered for `kind`
+    /// in [`RehydrateOptions`]; otherwise it is skipped with a warning.
+    ///
+    /// [`ConversionPass`]: crate::pass::ConversionPass
+    /// [`RehydrateOptions`]: crate::rehydrator::RehydrateOptions
+    Custom {
+        /// Handler key, e.g. `"acme_unit_convert"`.
+        kind: String,
+        path: String,
+        /// Whatever the handler needs to undo the transform.
+        #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+        payload: serde_json::Value,
+    },
+}
+
+/// One branch of a [`Transform::ProjectedVariants`] union.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectedVariant {
+    /// Properties the branch kept.
+    pub properties: Vec<String>,
+    /// Kept properties the branch requires.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+    /// Transforms undoing the branch's projection, with paths relative to
+    /// the union.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transforms: Vec<Transform>,
 }
 
 /// A constraint that was dropped during conversion.
@@ -9,8 +9,13 @@
 use serde::{Deserialize, Serialize};
 
 /// A warning emitted when a dropped constraint is violated by LLM output.
+///
+/// Serialized with a `ruleId` field derived from [`kind`](Self::kind); see
+/// [`crate::diagnostics`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Warning {
     /// Data location where the violation occurred (e.g. "/users/0/email").
     pub data_path: String,
@@ -20,10 +25,73 @@ pub struct Warning {
     pub kind: WarningKind,
     /// Human-readable description of the violation.
     pub message: String,
+    /// How many occurrences this entry stands for: more than 1 once
+    /// identical warnings (same kind at the same schema path) are merged by
+    /// [`RehydrateOptions::aggregate_warnings`]. Default: 1.
+    ///
+    /// [`RehydrateOptions::aggregate_warnings`]: crate::RehydrateOptions::aggregate_warnings
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub count: usize,
+    /// Data paths of the first merged occurrences, capped. Empty unless
+    /// aggregated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sample_paths: Vec<String>,
+}
+
+impl Warning {
+    /// A single occurrence of `kind` at `data_path`, checked against the
+    /// schema at `schema_path`.
+    pub fn new(
+        data_path: impl Into<String>,
+        schema_path: impl Into<String>,
+        kind: WarningKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            data_path: data_path.into(),
+            schema_path: schema_path.into(),
+            kind,
+            message: message.into(),
+            count: 1,
+            sample_paths: Vec::new(),
+        }
+    }
+}
+
+fn one() -> usize {
+    1
+}
+
+fn is_one(n: &usize) -> bool {
+    *n == 1
+}
+
+impl Serialize for Warning {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct WithRuleId<'a> {
+            #[serde(flatten, with = "Warning")]
+            inner: &'a Warning,
+            #[serde(rename = "ruleId")]
+            rule_id: &'static str,
+        }
+        WithRuleId {
+            inner: self,
+            rule_id: self.rule_id(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Warning {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // `ruleId` is derived from `kind` and ignored on input.
+        Warning::deserialize(deserializer)
+    }
 }
 
 /// Classification of rehydration warnings.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum WarningKind {
     /// A dropped constraint was violated by the LLM output.
@@ -38,4 +106,64 @@ pub enum WarningKind {
     },
     /// A codec path could not be resolved in the output data.
     PathNotFound,
+    /// A custom codec transform had no registered rehydration handler, so
+    /// the data at its path was left as generated.
+    UnhandledTransform {
+        /// The transform's `kind`.
+        transform: String,
+    },
+    /// A restored map had several entries with this key; all but one value
+    /// were dropped (see [`RehydrateOptions::duplicate_key_policy`]).
+    ///
+    /// [`RehydrateOptions::duplicate_key_policy`]: crate::RehydrateOptions::duplicate_key_policy
+    DuplicateKey {
+        /// The repeated key.
+        key: String,
+    },
+    /// A discriminator value matched one of its union's variants only up to
+    /// case and whitespace, and was rewritten to it (see
+    /// [`RehydrateOptions::normalize_discriminators`]).
+    ///
+    /// [`RehydrateOptions::normalize_discriminators`]: crate::RehydrateOptions::normalize_discriminators
+    DiscriminatorNormalized {
+        /// The value as generated.
+        from: String,
+        /// The variant value it was rewritten to.
+        to: String,
+    },
+    /// An object under a discriminated union matches none of its variants
+    /// cleanly: its discriminator names no variant, or the named variant's
+    /// required properties are missing.
+    VariantMismatch {
+        /// The discriminator value found, if it was a string.
+        value: Option<String>,
+        /// The variants it was compared with, closest first.
+        candidates: Vec<VariantCandidate>,
+    },
+    /// A `{"$node_id": ...}` reference left by
+    /// [`RecursionLeaf::NodeId`](crate::RecursionLeaf::NodeId) names no node,
+    /// or an enclosing one (which cannot be copied into itself); it was kept
+    /// as is.
+    UnresolvedNodeRef {
+        /// The referenced id.
+        id: String,
+    },
+    /// Warnings past [`RehydrateOptions::max_warnings`] were dropped; this
+    /// summary entry stands in for them.
+    ///
+    /// [`RehydrateOptions::max_warnings`]: crate::RehydrateOptions::max_warnings
+    Truncated {
+        /// Number of occurrences dropped, counting merged ones.
+        omitted: usize,
+    },
+}
+
+/// One variant considered for a [`WarningKind::VariantMismatch`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantCandidate {
+    /// The variant's discriminator value.
+    pub variant: String,
+    /// Its required properties absent from the object.
+    pub missing: Vec<String>,
 }
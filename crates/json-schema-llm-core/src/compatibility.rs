@@ -0,0 +1,292 @@
+//! Checking that a codec belongs to a converted schema.
+//!
+//! A codec only rehydrates answers to the schema it was produced with.
+//! Pair it with a different conversion — an older deploy, another target,
+//! a schema edited since — and rehydration still runs, silently reshaping
+//! the wrong locations. [`codec_matches`] catches that at startup: every
+//! transform must land on a node of the right shape in the converted
+//! schema.
+//!
+//! ```rust
+//! use json_schema_llm_core::{codec_matches, convert, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let options = ConvertOptions::default();
+//! let v1 = convert(&json!({
+//!     "type": "object",
+//!     "properties": { "tags": { "type": "object", "additionalProperties": { "type": "string" } } }
+//! }), &options).unwrap();
+//! let v2 = convert(&json!({
+//!     "type": "object",
+//!     "properties": { "labels": { "type": "object", "additionalProperties": { "type": "string" } } }
+//! }), &options).unwrap();
+//!
+//! assert!(codec_matches(&v1.codec, &v1.schema).is_compatible());
+//! assert!(!codec_matches(&v1.codec, &v2.schema).is_compatible());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::codec::{Codec, Transform};
+use crate::path_map::{converted_transform_paths, transform_kind, transform_path};
+use crate::rehydrator::validate_codec_version;
+use crate::schema_utils::resolve_pointer;
+
+/// Outcome of [`codec_matches`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityReport {
+    /// Every mismatch found, in codec order; empty when the codec fits.
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+impl CompatibilityReport {
+    /// Whether the codec can rehydrate answers to the schema.
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// One codec entry that does not fit the converted schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityIssue {
+    /// Index into the codec's `transforms`; `None` for codec-level problems
+    /// such as an unsupported version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform_index: Option<usize>,
+    /// Transform type, e.g. `map_to_array`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<String>,
+    /// Where the transform applies in the converted schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted_path: Option<String>,
+    /// What does not match.
+    pub message: String,
+}
+
+/// Check that `codec` was produced together with `converted_schema`.
+///
+/// Each transform's location must exist in `converted_schema` and have the
+/// shape the transform undoes: an entry array for `map_to_array`, a string
+/// for `json_string_parse`, a nullable node for `nullable_optional`, and so
+/// on. Custom transforms are only checked for their location. A codec whose
+/// major version this build can't read is reported too.
+pub fn codec_matches(codec: &Codec, converted_schema: &Value) -> CompatibilityReport {
+    let mut issues = Vec::new();
+    if let Err(e) = validate_codec_version(codec) {
+        issues.push(CompatibilityIssue {
+            transform_index: None,
+            transform: None,
+            converted_path: None,
+            message: e.to_string(),
+        });
+    }
+
+    let paths = converted_transform_paths(codec, converted_schema);
+    for (index, (transform, path)) in codec.transforms.iter().zip(paths).enumerate() {
+        let problem = match resolve_pointer(converted_schema, &path) {
+            None => Some(format!(
+                "no schema at {} (codec path {})",
+                path,
+                transform_path(transform)
+            )),
+            Some(node) => shape_mismatch(transform, node),
+        };
+        if let Some(message) = problem {
+            issues.push(CompatibilityIssue {
+                transform_index: Some(index),
+                transform: Some(transform_kind(transform).to_string()),
+                converted_path: Some(path),
+                message,
+            });
+        }
+    }
+    CompatibilityReport { issues }
+}
+
+/// Why `node` is not what `transform` expects, if it isn't.
+fn shape_mismatch(transform: &Transform, node: &Value) -> Option<String> {
+    let inner = non_null(node);
+    match transform {
+        Transform::MapToArray { key_field, .. } => {
+            let has_key = inner
+                .get("items")
+                .and_then(|items| items.get("properties"))
+                .and_then(Value::as_object)
+                .is_some_and(|props| props.contains_key(key_field));
+            (!has_key).then(|| format!("expected an array of entries with a `{}` field", key_field))
+        }
+        Transform::JsonStringParse { .. } | Transform::RecursiveInflate { .. } => {
+            (!allows_type(inner, "string")).then(|| "expected a JSON-string field".to_string())
+        }
+        Transform::NullableOptional { .. } => {
+            (!is_nullable(node)).then(|| "expected a nullable property".to_string())
+        }
+        Transform::DiscriminatorAnyOf { .. } => {
+            let union = inner.get("anyOf").or_else(|| inner.get("oneOf"));
+            (!union.is_some_and(Value::is_array)).then(|| "expected a union".to_string())
+        }
+        Transform::ExtractAdditionalProperties { property_name, .. } => {
+            missing_property(inner, property_name)
+        }
+        Transform::EnumStringify { .. } => {
+            let stringified = inner
+                .get("enum")
+                .and_then(Value::as_array)
+                .is_some_and(|values| values.iter().all(|v| v.is_string() || v.is_null()));
+            (!stringified).then(|| "expected an enum of strings".to_string())
+        }
+        Transform::FilteredProperty { property, .. } => inner
+            .get("properties")
+            .and_then(Value::as_object)
+            .is_some_and(|props| props.contains_key(property))
+            .then(|| format!("property `{}` should have been filtered out", property)),
+        // The converted path already runs through the wrapper key.
+        Transform::RootObjectWrapper { .. }
+        | Transform::RecursiveTerminate { .. }
+        | Transform::RecursiveNodeRef { .. }
+        | Transform::AnyOfOverflow { .. }
+        | Transform::ProjectedVariants { .. }
+        | Transform::JsonStringEncode { .. }
+        | Transform::Custom { .. } => None,
+    }
+}
+
+fn missing_property(node: &Value, name: &str) -> Option<String> {
+    let present = node
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|props| props.contains_key(name));
+    (!present).then(|| format!("expected a `{}` property", name))
+}
+
+/// The non-`null` branch of a two-branch nullable union, else `node`.
+fn non_null(node: &Value) -> &Value {
+    let branches = node
+        .get("anyOf")
+        .or_else(|| node.get("oneOf"))
+        .and_then(Value::as_array);
+    match branches.map(Vec::as_slice) {
+        Some([a, b]) if is_null_type(b) => a,
+        Some([a, b]) if is_null_type(a) => b,
+        _ => node,
+    }
+}
+
+fn is_null_type(node: &Value) -> bool {
+    node.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// Whether `node` accepts `null`, in any of the nullable styles.
+fn is_nullable(node: &Value) -> bool {
+    allows_type(node, "null")
+        || node.get("nullable").and_then(Value::as_bool) == Some(true)
+        || ["anyOf", "oneOf"]
+            .iter()
+            .filter_map(|k| node.get(*k).and_then(Value::as_array))
+            .flatten()
+            .any(is_null_type)
+}
+
+fn allows_type(node: &Value, ty: &str) -> bool {
+    match node.get("type") {
+        Some(Value::String(t)) => t == ty,
+        Some(Value::Array(types)) => types.iter().any(|t| t.as_str() == Some(ty)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, ConvertOptions, Target};
+    use serde_json::json;
+
+    fn kitchen_sink() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "nickname": { "type": "string" },
+                "meta": {},
+                "scores": { "type": "object", "additionalProperties": { "type": "integer" } },
+                "level": { "enum": [1, 2, "max"] },
+                "extra": {
+                    "type": "object",
+                    "properties": { "id": { "type": "string" } },
+                    "additionalProperties": { "type": "number" }
+                },
+                "pet": {
+                    "oneOf": [
+                        { "type": "object", "properties": { "kind": { "const": "cat" } }, "required": ["kind"] },
+                        { "type": "object", "properties": { "kind": { "const": "dog" } }, "required": ["kind"] }
+                    ],
+                    "discriminator": { "propertyName": "kind" }
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    #[test]
+    fn test_codec_matches_own_schema_for_every_target() {
+        for target in [Target::OpenaiStrict, Target::Gemini, Target::Claude] {
+            let options = ConvertOptions {
+                target,
+                ..ConvertOptions::default()
+            };
+            let result = convert(&kitchen_sink(), &options).unwrap();
+            assert!(!result.codec.transforms.is_empty());
+            let report = codec_matches(&result.codec, &result.schema);
+            assert!(report.is_compatible(), "{:?}: {:?}", target, report.issues);
+        }
+    }
+
+    #[test]
+    fn test_codec_matches_flags_stale_codec() {
+        let old = convert(&kitchen_sink(), &ConvertOptions::default()).unwrap();
+        let mut edited = kitchen_sink();
+        edited["properties"]["scores"] = json!({ "type": "integer" });
+        edited["properties"]["meta"] = json!({ "type": "integer" });
+        let new = convert(&edited, &ConvertOptions::default()).unwrap();
+
+        let report = codec_matches(&old.codec, &new.schema);
+        let flagged: Vec<_> = report
+            .issues
+            .iter()
+            .map(|i| {
+                (
+                    i.transform.as_deref().unwrap(),
+                    i.converted_path.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                ("map_to_array", "#/properties/scores"),
+                ("json_string_parse", "#/properties/meta")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_codec_matches_flags_version_and_missing_root_wrapper() {
+        let array = json!({ "type": "array", "items": { "type": "string" } });
+        let mut result = convert(&array, &ConvertOptions::default()).unwrap();
+        result.codec.schema = "https://json-schema-llm.dev/codec/v99".to_string();
+
+        let report = codec_matches(
+            &result.codec,
+            &json!({ "type": "object", "properties": {} }),
+        );
+        assert_eq!(report.issues.len(), 2);
+        assert_eq!(report.issues[0].transform_index, None);
+        assert_eq!(
+            report.issues[1].converted_path.as_deref(),
+            Some("#/properties/result")
+        );
+    }
+}
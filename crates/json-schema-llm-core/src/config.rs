@@ -1,7 +1,15 @@
 //! Configuration for schema conversion.
 
+use std::fmt;
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
+use crate::cancel::CancellationToken;
+use crate::diagnostics::RuleSelector;
+use crate::messages::{Locale, MessageCatalog};
+use crate::pass::{BuiltinPass, ExtraPass};
+
 /// Target LLM provider for schema conversion.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -14,6 +22,11 @@ pub enum Target {
     Claude,
 }
 
+impl Target {
+    /// Every target, in declaration order.
+    pub const ALL: [Target; 3] = [Target::OpenaiStrict, Target::Gemini, Target::Claude];
+}
+
 /// Conversion mode — controls how aggressively the pipeline transforms the schema.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -31,7 +44,16 @@ pub enum Mode {
 ///
 /// Fields are serialized in `kebab-case` (e.g., `max-depth`, `recursion-limit`).
 /// This naming convention is part of the public API contract for FFI and config files.
+///
+/// On input, a `"preset"` key (see [`Preset`]) supplies the starting values;
+/// the other keys override them.
+///
+/// `ConvertOptions` is `Send + Sync`: build one profile and share it across
+/// threads behind an `Arc`.
+// `remote = "Self"` generates inherent (de)serialize fns that the manual
+// impls below wrap to apply `preset`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 #[serde(rename_all = "kebab-case", default)]
 #[non_exhaustive]
 pub struct ConvertOptions {
@@ -46,14 +68,481 @@ pub struct ConvertOptions {
     /// being replaced with an opaque JSON-string placeholder (Pass 5).
     /// Default: 3. Keep low to avoid exponential schema expansion.
     pub recursion_limit: usize,
+    /// What replaces a recursive `$ref` past `recursion_limit`. Default:
+    /// [`RecursionLeaf::OpaqueString`].
+    pub recursion_leaf: RecursionLeaf,
     /// Polymorphism strategy override.
     pub polymorphism: PolymorphismStrategy,
+    /// How `allOf` members that disagree on a keyword are merged. Default:
+    /// [`MergeConflictPolicy::Intersect`].
+    pub merge_conflicts: MergeConflictPolicy,
+    /// What happens to a `$ref` that resolves to nothing. Default:
+    /// [`ErrorPolicy::Fail`].
+    pub on_error: ErrorPolicy,
     /// If `true`, [`convert_all_components`](crate::convert_all_components) skips
     /// per-component extraction and returns only the full-schema result.
     ///
     /// This flag is a no-op when calling [`convert`](crate::convert) directly.
     /// Default: `false`.
     pub skip_components: bool,
+    /// Maximum serialized size of the input schema in bytes. Checked before
+    /// any pass runs. `None` (default) disables the check.
+    pub max_schema_bytes: Option<usize>,
+    /// What to do when an object in the schema text repeats a key, which
+    /// parsing silently resolves in favour of the last occurrence. Only
+    /// checked for text input ([`convert_str`](crate::convert_str) and the
+    /// `convert_json` bridge). Default: [`DuplicateKeyCheck::Ignore`].
+    pub duplicate_keys: DuplicateKeyCheck,
+    /// Keep numbers in the schema text exact, such as a 25-digit `const` or
+    /// a `multipleOf` of `0.1234567890123456789`, instead of rounding those
+    /// that do not fit an `i64`, `u64` or `f64`. Only applies to text input
+    /// ([`convert_str`](crate::convert_str) and the `convert_json` bridge),
+    /// and needs the `arbitrary-precision` feature: without it, text input
+    /// fails with an `unsupported_feature` error. Default: `false`.
+    pub preserve_precision: bool,
+    /// Maximum number of schema nodes any one pass visits, counting the
+    /// copies made by Pass 0 `$ref` expansion and Pass 5 recursion inlining.
+    /// Guards against "billion laughs"-style ref fan-out that inflates a small
+    /// input into an enormous tree. `None` (default) disables the check.
+    pub max_nodes: Option<usize>,
+    /// Wall-clock budget for a single [`convert`](crate::convert) call, in
+    /// milliseconds. Checked periodically while each pass walks the schema,
+    /// and between passes.
+    /// `None` (default) disables the check.
+    ///
+    /// Not enforced on `wasm32-unknown-unknown`, which has no monotonic clock,
+    /// or in builds without the `time` feature.
+    pub time_budget_ms: Option<u64>,
+    /// Nesting budget enforced by Pass 9 for the target provider. Sub-schemas
+    /// at or beyond this depth are replaced with opaque JSON strings carrying
+    /// a structural summary. Applies to every target and mode when set.
+    /// `None` (default) uses the provider's documented limit (5 for OpenAI
+    /// strict mode; none elsewhere).
+    pub provider_max_depth: Option<usize>,
+    /// Maximum number of `anyOf` (or, for Gemini, `oneOf`) variants per
+    /// union. Larger unions keep their first `n - 1` variants and collapse
+    /// the rest into one JSON-string branch, parsed back on rehydration.
+    /// Values below 2 act as 2. `None` (default) disables the cap.
+    pub max_anyof_variants: Option<usize>,
+    /// Maximum number of `enum` values kept in the schema. Larger string or
+    /// numeric enums become a plain typed field with a sample of values in
+    /// its description (plus a character-class `pattern` for strings where
+    /// the target supports it); the full list goes to the codec and
+    /// membership is checked on rehydration. `None` (default) disables the
+    /// cap.
+    pub max_enum_values: Option<usize>,
+    /// How per-value enum descriptions are presented. Default:
+    /// [`EnumDescriptionStyle::Auto`].
+    pub enum_descriptions: EnumDescriptionStyle,
+    /// How constraints dropped for the target are restated in descriptions.
+    /// Default: [`ConstraintHintPolicy::Labeled`].
+    pub constraint_hints: ConstraintHintPolicy,
+    /// Whether dropped numeric bounds (`minimum`, `maximum`,
+    /// `exclusiveMinimum`, `exclusiveMaximum`, `multipleOf`) are included in
+    /// constraint hints. Default: `true`.
+    pub numeric_constraint_hints: bool,
+    /// Maximum length, in characters, of the hint appended to any one
+    /// description. Hints are cut at a whole constraint; one that cannot fit
+    /// even a single constraint is omitted. `None` (default) is unlimited.
+    pub constraint_hint_budget: Option<usize>,
+    /// How optional properties made required are marked nullable. Default:
+    /// [`NullableStyle::AnyOfNull`].
+    pub nullable_style: NullableStyle,
+    /// Whether objects are sealed with `additionalProperties: false` or keep
+    /// the `additionalProperties` they declare. Default:
+    /// [`AdditionalPropertiesPolicy::Seal`].
+    pub additional_properties: AdditionalPropertiesPolicy,
+    /// Name of the synthetic property that carries the extra entries of an
+    /// object with both `properties` and a schema-valued
+    /// `additionalProperties`. If the object already declares it, `_extra`
+    /// (then underscores) is appended; the name used is recorded in the
+    /// codec. Default: `None`, meaning `_additional`.
+    pub additional_properties_name: Option<String>,
+    /// Remove properties marked `readOnly: true` (server-assigned IDs,
+    /// timestamps) so the model is not asked to generate them. Default:
+    /// `false`.
+    pub filter_read_only: bool,
+    /// Remove properties marked `writeOnly: true` (e.g. passwords), for
+    /// schemas describing responses. Default: `false`.
+    pub filter_write_only: bool,
+    /// What to do with properties marked `deprecated: true`. Default:
+    /// [`DeprecatedPolicy::Keep`].
+    pub deprecated_properties: DeprecatedPolicy,
+    /// What to do with string fields that embed JSON (`contentMediaType:
+    /// application/json` plus `contentSchema`). Default:
+    /// [`ContentSchemaPolicy::Opaque`].
+    pub content_schema: ContentSchemaPolicy,
+    /// Language of descriptions injected into the schema. Default: English.
+    pub locale: Locale,
+    /// Custom text for injected descriptions, overriding `locale`.
+    /// Default: `None`.
+    pub message_catalog: Option<MessageCatalog>,
+    /// Provider compatibility diagnostics to acknowledge. Matching findings
+    /// move from [`ConvertResult::provider_compat_errors`] to
+    /// [`ConvertResult::suppressed_compat_errors`]; the transforms themselves
+    /// still apply. Default: empty.
+    ///
+    /// [`ConvertResult::provider_compat_errors`]: crate::ConvertResult::provider_compat_errors
+    /// [`ConvertResult::suppressed_compat_errors`]: crate::ConvertResult::suppressed_compat_errors
+    pub suppress: Vec<RuleSelector>,
+    /// Check the converted schema against the target provider's published
+    /// requirements before returning, and fail with a
+    /// [`ConvertError::ProviderCompatFailure`] listing every violation (see
+    /// [`validate_conformance`]). Catches converter bugs before the provider
+    /// API does. Only [`Mode::Strict`] output is checked. Default: `false`.
+    ///
+    /// [`ConvertError::ProviderCompatFailure`]: crate::ConvertError::ProviderCompatFailure
+    /// [`validate_conformance`]: crate::validate_conformance
+    pub verify_output: bool,
+    /// Check the input's keywords against the 2020-12 meta-schema before
+    /// any pass runs, and fail with a [`ConvertError::InvalidSchema`]
+    /// listing every malformed one (see [`validate_input`]), rather than
+    /// with the first one a pass trips over. Default: `false`.
+    ///
+    /// [`ConvertError::InvalidSchema`]: crate::ConvertError::InvalidSchema
+    /// [`validate_input`]: crate::validate_input
+    pub validate_input: bool,
+    /// Remove `description` and `title` from the converted schema for a
+    /// minimal-token variant. Descriptions that are the model's only
+    /// instruction (JSON-string fields, `$node_id` references) stay, as do
+    /// those matched by [`keep_descriptions`](Self::keep_descriptions). The
+    /// codec is unaffected, so it also rehydrates answers to the full
+    /// variant. Default: `false`.
+    pub strip_descriptions: bool,
+    /// JSON Pointer globs (`*`, `**`) into the converted schema whose nodes
+    /// keep their `description` and `title` under
+    /// [`strip_descriptions`](Self::strip_descriptions). Default: empty.
+    pub keep_descriptions: Vec<String>,
+    /// Mark each transformed node of the converted schema with an
+    /// `x-jsl-transform` array naming the transforms applied there (e.g.
+    /// `["map_to_array"]`), so tools can show why a node looks the way it
+    /// does. For debugging: the markers are not meant for the provider.
+    /// Default: `false`.
+    pub annotate: bool,
+    /// Write the converted schema in a diff-stable canonical form: sorted
+    /// keys, sorted and de-duplicated `required` and `type` arrays, and
+    /// integral numbers without a fraction. Order-sensitive arrays (`enum`,
+    /// `anyOf`, `propertyOrdering`) keep their order. Default: `false`.
+    pub canonical: bool,
+    /// Emit Gemini `propertyOrdering` arrays so fields are generated in a
+    /// deliberate order. Ignored for other targets. Default: Off.
+    pub property_ordering: PropertyOrdering,
+    /// Custom ordering for Gemini `propertyOrdering`. When set, ordering is
+    /// emitted even if [`property_ordering`](Self::property_ordering) is Off.
+    /// Not serializable; programmatic use only.
+    #[serde(skip)]
+    pub property_order_fn: Option<PropertyOrderFn>,
+    /// Also return the converted schema wrapped in the target provider's
+    /// request payload (see [`ConvertResult::envelope`]). Default: `None`.
+    ///
+    /// [`ConvertResult::envelope`]: crate::ConvertResult::envelope
+    pub envelope: Option<Envelope>,
+    /// `name` for the envelope. Default: the schema's `title` (sanitized to
+    /// `[a-zA-Z0-9_-]`), else `"response"`.
+    pub envelope_name: Option<String>,
+    /// Custom passes to run between the built-in ones. Default: empty.
+    /// Not serializable; programmatic use only.
+    #[serde(skip)]
+    pub extra_passes: Vec<ExtraPass>,
+    /// Called as a conversion advances, for progress display on large
+    /// schemas. Default: `None`. Not serializable; programmatic use only.
+    #[serde(skip)]
+    pub progress: Option<ProgressSink>,
+    /// Token for aborting the conversion from another thread. Default:
+    /// `None`. Not serializable; programmatic use only.
+    #[serde(skip)]
+    pub cancellation: Option<CancellationToken>,
+}
+
+/// Provider payload shape for [`ConvertOptions::envelope`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Envelope {
+    /// Structured-output setting: OpenAI `response_format`, Gemini
+    /// `generationConfig` fields, or Claude `output_format`.
+    ResponseFormat,
+    /// Tool definition: an OpenAI `tools[]` function, a Gemini
+    /// `functionDeclarations[]` entry, or a Claude `tools[]` entry.
+    ToolFunction,
+}
+
+/// Whether and how dropped constraints are folded into descriptions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConstraintHintPolicy {
+    /// Leave descriptions untouched; dropped constraints live only in the codec.
+    Off,
+    /// `Constraints: minimum length: 3 character(s); format: email.`
+    #[default]
+    Labeled,
+    /// One short phrase: `Must match ^[A-Z]{3}$, 3–5 chars, email format.`
+    Compact,
+}
+
+/// How per-value descriptions of an enum reach the model, for
+/// [`ConvertOptions::enum_descriptions`]. They are read from
+/// `x-enum-descriptions` (or `x-enumDescriptions`): an array parallel to
+/// `enum`, or an object keyed by value. A `oneOf` or `anyOf` of `const`s
+/// with descriptions is the standard form of the same thing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EnumDescriptionStyle {
+    /// Fold `x-enum-descriptions` into the description, and keep unions of
+    /// `const`s as written: every target accepts a per-variant description.
+    #[default]
+    Auto,
+    /// Rewrite both forms as a plain `enum`, with the value glosses appended
+    /// to the description: `Values: "s": Small; "l": Large.` The fewest
+    /// tokens.
+    Fold,
+    /// Rewrite `x-enum-descriptions` as a `oneOf` of `const`s, each carrying
+    /// its own description. Unions of `const`s are kept.
+    OneOf,
+    /// Leave both forms as written; `x-enum-descriptions` reaches the
+    /// provider untouched.
+    Ignore,
+}
+
+/// Resolution of `allOf` members that disagree on a keyword (no common
+/// `type`, different `const`s, disjoint `enum`s, different `format`,
+/// `pattern`, `multipleOf` or content annotations), for
+/// [`ConvertOptions::merge_conflicts`]. Conflicts that are resolved rather
+/// than rejected are reported as `merge_conflict` diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeConflictPolicy {
+    /// Keep what every member allows: an empty `enum` for disjoint ones, and
+    /// the later member's value where no intersection can be written (such
+    /// as two `pattern`s). No common `type` or different `const`s is an
+    /// error, since nothing could satisfy the schema.
+    #[default]
+    Intersect,
+    /// Keep the earlier member's value.
+    PreferFirst,
+    /// Fail the conversion on any conflict.
+    Error,
+}
+
+/// Handling of repeated keys in schema text, for
+/// [`ConvertOptions::duplicate_keys`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateKeyCheck {
+    /// Keep the last occurrence without checking.
+    #[default]
+    Ignore,
+    /// Keep the last occurrence and report each repeat as a
+    /// `duplicate_schema_key` diagnostic.
+    Warn,
+    /// Fail on the first repeat with a
+    /// [`ConvertError::SchemaError`](crate::ConvertError::SchemaError) at
+    /// its path.
+    Error,
+}
+
+/// Handling of a `$ref` whose target does not exist, for
+/// [`ConvertOptions::on_error`]. Recovered references are reported as
+/// `unresolvable_ref_recovered` diagnostics, so a schema waiting on an
+/// upstream fix can still be converted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorPolicy {
+    /// Fail the conversion.
+    #[default]
+    Fail,
+    /// Remove the property holding the reference, and from `required`; it
+    /// stays absent after rehydration. References outside `properties`
+    /// fall back to a placeholder.
+    SkipSubtree,
+    /// Replace the reference with an opaque JSON string, parsed back as
+    /// whatever the model writes on rehydration.
+    Placeholder,
+}
+
+/// Handling of `deprecated: true` properties, for
+/// [`ConvertOptions::deprecated_properties`]. Dropped and annotated
+/// properties are reported as `deprecated_property` diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeprecatedPolicy {
+    /// Leave them as they are.
+    #[default]
+    Keep,
+    /// Remove them, as with `filter_read_only`; a `default` or `const` is
+    /// restored on rehydration.
+    Drop,
+    /// Keep them, with a note in the description telling the model to avoid
+    /// populating them.
+    Annotate,
+}
+
+/// Placeholder for a recursive `$ref` cut off at the recursion limit, for
+/// [`ConvertOptions::recursion_leaf`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecursionLeaf {
+    /// A JSON string holding the rest of the subtree, parsed back on
+    /// rehydration.
+    #[default]
+    OpaqueString,
+    /// Always `null`: the tree stops there. Rehydration drops the `null`
+    /// from its parent object.
+    Null,
+    /// `{"$node_id": string}` naming another node of the same type. Inlined
+    /// nodes of a recursive type gain an optional `$node_id` property;
+    /// rehydration replaces each reference with a copy of the node it names.
+    NodeId,
+}
+
+/// How strict mode marks an optional property as nullable once it is made
+/// required, for [`ConvertOptions::nullable_style`]. Rehydration drops the
+/// `null` the same way for every style.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NullableStyle {
+    /// `anyOf: [T, {type: null}]`. The only form `openai-strict` accepts, so
+    /// always used for that target.
+    #[default]
+    AnyOfNull,
+    /// `type: [T, "null"]`, with `null` added to any `enum` (a `const`
+    /// becomes a two-value `enum`).
+    TypeArray,
+    /// OpenAPI 3.0 `nullable: true`, as Gemini's schema dialect expects.
+    OpenApiNullable,
+}
+
+/// Handling of undeclared object properties, for
+/// [`ConvertOptions::additional_properties`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AdditionalPropertiesPolicy {
+    /// Seal every object with `additionalProperties: false`; maps become
+    /// key/value arrays (except for Gemini, which accepts maps).
+    #[default]
+    Seal,
+    /// Keep each object's declared `additionalProperties` — `true`, a value
+    /// schema, or absent — so open objects stay open and maps stay typed
+    /// maps. Only applies in [`Mode::Strict`] to the Gemini and Claude
+    /// targets; `openai-strict` accepts nothing but sealed objects.
+    Preserve,
+}
+
+/// Handling of string fields whose content is JSON described by
+/// `contentSchema`, for [`ConvertOptions::content_schema`]. Fields with a
+/// `contentEncoding` (e.g. base64) are left alone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContentSchemaPolicy {
+    /// Keep the field a string, with the `contentSchema` structure spelled
+    /// out in its description.
+    #[default]
+    Opaque,
+    /// Replace the field with `contentSchema` itself, so the model fills in
+    /// structured output; rehydration serializes it back into a string.
+    Inline,
+}
+
+/// Source of Gemini `propertyOrdering` arrays.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PropertyOrdering {
+    /// Don't emit `propertyOrdering`.
+    #[default]
+    Off,
+    /// Declaration order from the original schema. Source order survives
+    /// only through [`convert_str`](crate::convert_str) (or a `Value` built
+    /// with serde_json's `preserve_order` feature); otherwise map order is
+    /// alphabetical. An existing `propertyOrdering` is kept.
+    Original,
+}
+
+/// Callback deciding the generation order of an object's properties.
+///
+/// Called with the object's path in the converted schema and its properties
+/// in [`PropertyOrdering::Original`] order; returns the desired order. Unknown
+/// names are ignored and omitted properties keep their relative order at the
+/// end.
+#[derive(Clone)]
+pub struct PropertyOrderFn(Arc<OrderFn>);
+
+type OrderFn = dyn Fn(&str, &[String]) -> Vec<String> + Send + Sync;
+
+impl PropertyOrderFn {
+    pub fn new(f: impl Fn(&str, &[String]) -> Vec<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    pub(crate) fn call(&self, path: &str, properties: &[String]) -> Vec<String> {
+        (self.0)(path, properties)
+    }
+}
+
+impl fmt::Debug for PropertyOrderFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PropertyOrderFn(..)")
+    }
+}
+
+/// A step reported to [`ConvertOptions::progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Progress {
+    /// Built-in pass `pass`, number `index` (from 1) of `total`, is starting.
+    /// Skipped passes are not reported, and neither are the passes run for
+    /// each component by [`convert_all_components`](crate::convert_all_components).
+    Pass {
+        pass: BuiltinPass,
+        index: usize,
+        total: usize,
+    },
+    /// [`convert_all_components`](crate::convert_all_components) has
+    /// processed `done` of `total` components, successfully or not.
+    Components { done: usize, total: usize },
+}
+
+/// Callback receiving [`Progress`] events.
+///
+/// Component progress is reported before the first component, then every
+/// [`component_step`](Self::component_step) components and after the last.
+#[derive(Clone)]
+pub struct ProgressSink {
+    f: Arc<ProgressFn>,
+    component_step: usize,
+}
+
+type ProgressFn = dyn Fn(Progress) + Send + Sync;
+
+impl ProgressSink {
+    pub fn new(f: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        Self {
+            f: Arc::new(f),
+            component_step: 1,
+        }
+    }
+
+    /// Report component progress every `step` components. Default: 1.
+    pub fn component_step(mut self, step: usize) -> Self {
+        self.component_step = step.max(1);
+        self
+    }
+
+    pub(crate) fn report(&self, progress: Progress) {
+        (self.f)(progress)
+    }
+
+    pub(crate) fn report_components(&self, done: usize, total: usize) {
+        if done == 0 || done == total || done.is_multiple_of(self.component_step) {
+            self.report(Progress::Components { done, total });
+        }
+    }
+}
+
+impl fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressSink")
+            .field("component_step", &self.component_step)
+            .finish_non_exhaustive()
+    }
 }
 
 /// Strategy for handling oneOf/anyOf polymorphism.
@@ -67,6 +556,41 @@ pub enum PolymorphismStrategy {
     Flatten,
 }
 
+/// Curated option bundle for a common use case, for
+/// [`ConvertOptions::preset`] or the `"preset"` key of serialized options.
+///
+/// Presets only set the fields listed for each variant; everything else keeps
+/// its [`Default`] value. The bundles are maintained with the crate and may be
+/// tuned between releases as provider behavior changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Preset {
+    /// OpenAI `response_format` structured output: `openai-strict` target,
+    /// strict mode, `anyOf` polymorphism.
+    OpenaiStructuredOutput,
+    /// OpenAI function calling: `openai-strict` target, `readOnly` and
+    /// deprecated properties dropped, compact constraint hints to keep tool
+    /// descriptions short.
+    OpenaiToolCalling,
+    /// Gemini `responseSchema`: `gemini` target, `propertyOrdering` emitted in
+    /// declaration order, `nullable: true` for optional fields.
+    GeminiStructuredOutput,
+    /// Claude tool use: `claude` target, `readOnly` and deprecated properties
+    /// dropped.
+    ClaudeToolUse,
+}
+
+impl Preset {
+    /// Every preset, in declaration order.
+    pub const ALL: [Preset; 4] = [
+        Preset::OpenaiStructuredOutput,
+        Preset::OpenaiToolCalling,
+        Preset::GeminiStructuredOutput,
+        Preset::ClaudeToolUse,
+    ];
+}
+
 impl Default for ConvertOptions {
     fn default() -> Self {
         Self {
@@ -74,12 +598,141 @@ impl Default for ConvertOptions {
             mode: Mode::Strict,
             max_depth: 50,
             recursion_limit: 3,
+            recursion_leaf: RecursionLeaf::OpaqueString,
             polymorphism: PolymorphismStrategy::AnyOf,
+            merge_conflicts: MergeConflictPolicy::Intersect,
+            on_error: ErrorPolicy::Fail,
             skip_components: false,
+            max_schema_bytes: None,
+            duplicate_keys: DuplicateKeyCheck::Ignore,
+            preserve_precision: false,
+            max_nodes: None,
+            time_budget_ms: None,
+            provider_max_depth: None,
+            max_anyof_variants: None,
+            max_enum_values: None,
+            enum_descriptions: EnumDescriptionStyle::Auto,
+            constraint_hints: ConstraintHintPolicy::Labeled,
+            numeric_constraint_hints: true,
+            constraint_hint_budget: None,
+            nullable_style: NullableStyle::AnyOfNull,
+            additional_properties: AdditionalPropertiesPolicy::Seal,
+            additional_properties_name: None,
+            filter_read_only: false,
+            filter_write_only: false,
+            deprecated_properties: DeprecatedPolicy::Keep,
+            content_schema: ContentSchemaPolicy::Opaque,
+            locale: Locale::En,
+            message_catalog: None,
+            suppress: Vec::new(),
+            verify_output: false,
+            validate_input: false,
+            strip_descriptions: false,
+            keep_descriptions: Vec::new(),
+            annotate: false,
+            canonical: false,
+            property_ordering: PropertyOrdering::Off,
+            property_order_fn: None,
+            envelope: None,
+            envelope_name: None,
+            extra_passes: Vec::new(),
+            progress: None,
+            cancellation: None,
         }
     }
 }
 
+impl Serialize for ConvertOptions {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ConvertOptions::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConvertOptions {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut fields = serde_json::Map::deserialize(deserializer)?;
+        if let Some(preset) = fields.remove("preset") {
+            let preset = Preset::deserialize(preset).map_err(D::Error::custom)?;
+            let serde_json::Value::Object(mut base) =
+                serde_json::to_value(ConvertOptions::preset(preset)).map_err(D::Error::custom)?
+            else {
+                unreachable!("ConvertOptions serializes to an object");
+            };
+            base.extend(fields);
+            fields = base;
+        }
+        ConvertOptions::deserialize(serde_json::Value::Object(fields)).map_err(D::Error::custom)
+    }
+}
+
+impl ConvertOptions {
+    /// Options for a named [`Preset`]. Adjust individual fields afterwards as
+    /// needed.
+    ///
+    /// ```rust
+    /// use json_schema_llm_core::{ConvertOptions, Preset, Target};
+    ///
+    /// let mut options = ConvertOptions::preset(Preset::ClaudeToolUse);
+    /// options.recursion_limit = 2;
+    /// assert_eq!(options.target, Target::Claude);
+    /// ```
+    pub fn preset(preset: Preset) -> Self {
+        let defaults = Self::default();
+        match preset {
+            Preset::OpenaiStructuredOutput => Self {
+                target: Target::OpenaiStrict,
+                mode: Mode::Strict,
+                polymorphism: PolymorphismStrategy::AnyOf,
+                ..defaults
+            },
+            Preset::OpenaiToolCalling => Self {
+                target: Target::OpenaiStrict,
+                filter_read_only: true,
+                deprecated_properties: DeprecatedPolicy::Drop,
+                constraint_hints: ConstraintHintPolicy::Compact,
+                ..defaults
+            },
+            Preset::GeminiStructuredOutput => Self {
+                target: Target::Gemini,
+                property_ordering: PropertyOrdering::Original,
+                nullable_style: NullableStyle::OpenApiNullable,
+                ..defaults
+            },
+            Preset::ClaudeToolUse => Self {
+                target: Target::Claude,
+                filter_read_only: true,
+                deprecated_properties: DeprecatedPolicy::Drop,
+                ..defaults
+            },
+        }
+    }
+
+    /// Whether [`AdditionalPropertiesPolicy::Preserve`] is in effect for the
+    /// target.
+    pub(crate) fn preserves_additional_properties(&self) -> bool {
+        self.additional_properties == AdditionalPropertiesPolicy::Preserve
+            && self.target != Target::OpenaiStrict
+    }
+
+    /// [`Self::nullable_style`] as applied for the target.
+    pub(crate) fn effective_nullable_style(&self) -> NullableStyle {
+        match self.target {
+            Target::OpenaiStrict => NullableStyle::AnyOfNull,
+            _ => self.nullable_style,
+        }
+    }
+
+    /// Catalog for injected descriptions: `message_catalog` if set, else the
+    /// built-in one for `locale`.
+    pub fn messages(&self) -> &MessageCatalog {
+        self.message_catalog
+            .as_ref()
+            .unwrap_or_else(|| MessageCatalog::builtin(self.locale))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +798,49 @@ mod tests {
             serde_json::json!("permissive")
         );
     }
+
+    #[test]
+    fn test_preset_serde_values() {
+        assert_eq!(
+            serde_json::to_value(Preset::OpenaiToolCalling).unwrap(),
+            serde_json::json!("openai-tool-calling")
+        );
+        for preset in Preset::ALL {
+            let json = serde_json::to_value(preset).unwrap();
+            assert_eq!(serde_json::from_value::<Preset>(json).unwrap(), preset);
+        }
+    }
+
+    #[test]
+    fn test_preset_key_seeds_options() {
+        let opts: ConvertOptions =
+            serde_json::from_str(r#"{"preset": "claude-tool-use", "recursion-limit": 1}"#).unwrap();
+        assert_eq!(opts.target, Target::Claude);
+        assert!(opts.filter_read_only);
+        assert_eq!(opts.deprecated_properties, DeprecatedPolicy::Drop);
+        assert_eq!(opts.recursion_limit, 1);
+    }
+
+    #[test]
+    fn test_explicit_keys_override_preset() {
+        let opts: ConvertOptions = serde_json::from_str(
+            r#"{"target": "gemini", "filter-read-only": false, "preset": "openai-tool-calling"}"#,
+        )
+        .unwrap();
+        assert_eq!(opts.target, Target::Gemini);
+        assert!(!opts.filter_read_only);
+        assert_eq!(opts.constraint_hints, ConstraintHintPolicy::Compact);
+    }
+
+    #[test]
+    fn test_unknown_preset_is_rejected() {
+        let err = serde_json::from_str::<ConvertOptions>(r#"{"preset": "nope"}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown variant"), "{err}");
+    }
+
+    #[test]
+    fn test_convert_options_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ConvertOptions>();
+    }
 }
@@ -0,0 +1,314 @@
+//! Snapshot regression harness over a directory of schemas.
+//!
+//! A corpus is a directory with one sub-directory per case:
+//!
+//! ```text
+//! tests/corpus/
+//!   maps/
+//!     input.json      # the schema to convert
+//!     options.json    # optional ConvertOptions (kebab-case); default openai-strict
+//!     expected.json   # blessed snapshot: schema + codec + diagnostics
+//!     data.json       # optional LLM output, for rehydration vectors
+//! ```
+//!
+//! [`check`] converts a case and compares it with `expected.json`;
+//! [`bless`] rewrites `expected.json` from the current output. Keeping the
+//! snapshot as one pretty-printed file per case makes any pass change show up
+//! as a reviewable fixture diff.
+//!
+//! [`write_vectors`] exports the same cases as binding conformance vectors:
+//! flat input / expected-output files that every language wrapper replays
+//! through its own convert and rehydrate calls.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::config::ConvertOptions;
+
+const INPUT_FILE: &str = "input.json";
+const OPTIONS_FILE: &str = "options.json";
+const EXPECTED_FILE: &str = "expected.json";
+const DATA_FILE: &str = "data.json";
+
+/// Layout version of the directory written by [`write_vectors`]. Bump when
+/// files are renamed or their shape changes.
+pub const VECTORS_FORMAT_VERSION: u32 = 1;
+
+/// One corpus case directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorpusCase {
+    /// Directory name, used to identify the case.
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+/// Result of comparing a case with its blessed snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaseStatus {
+    /// Output matches `expected.json`.
+    Match,
+    /// Output differs from `expected.json`.
+    Mismatch { expected: Value, actual: Value },
+    /// No `expected.json` yet.
+    Missing { actual: Value },
+    /// The case could not be run (unreadable or invalid input/options).
+    Invalid(String),
+}
+
+/// All cases under `root` (sub-directories containing `input.json`),
+/// sorted by name.
+pub fn discover(root: &Path) -> io::Result<Vec<CorpusCase>> {
+    let mut cases = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let dir = entry?.path();
+        if dir.join(INPUT_FILE).is_file() {
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            cases.push(CorpusCase { name, dir });
+        }
+    }
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+/// Convert `case` and build its snapshot value. Conversion failures are
+/// snapshotted too (as `{"error": ...}`), so error behaviour is pinned.
+pub fn snapshot(case: &CorpusCase) -> Result<Value, String> {
+    let input = read(&case.dir.join(INPUT_FILE))?;
+    let (_, options) = read_options(case)?;
+
+    Ok(match crate::convert_str(&input, &options) {
+        Ok(result) => {
+            let mut snap = json!({
+                "schema": result.schema,
+                "codec": result.codec,
+                "diagnostics": result.provider_compat_errors,
+            });
+            if !result.suppressed_compat_errors.is_empty() {
+                snap["suppressed"] = json!(result.suppressed_compat_errors);
+            }
+            snap
+        }
+        Err(e) => json!({ "error": e.to_json() }),
+    })
+}
+
+/// Compare `case` with its blessed snapshot.
+pub fn check(case: &CorpusCase) -> CaseStatus {
+    let actual = match snapshot(case) {
+        Ok(actual) => actual,
+        Err(e) => return CaseStatus::Invalid(e),
+    };
+    let expected_path = case.dir.join(EXPECTED_FILE);
+    if !expected_path.is_file() {
+        return CaseStatus::Missing { actual };
+    }
+    let expected: Value = match read(&expected_path)
+        .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+    {
+        Ok(expected) => expected,
+        Err(e) => return CaseStatus::Invalid(format!("{}: {}", expected_path.display(), e)),
+    };
+    if expected == actual {
+        CaseStatus::Match
+    } else {
+        CaseStatus::Mismatch { expected, actual }
+    }
+}
+
+/// Write the current output of `case` to `expected.json`. Returns whether
+/// the file changed.
+pub fn bless(case: &CorpusCase) -> Result<bool, String> {
+    let actual = snapshot(case)?;
+    let mut text = serde_json::to_string_pretty(&actual).map_err(|e| e.to_string())?;
+    text.push('\n');
+    let path = case.dir.join(EXPECTED_FILE);
+    if fs::read_to_string(&path).is_ok_and(|old| old == text) {
+        return Ok(false);
+    }
+    fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(true)
+}
+
+/// Write conformance vectors for `cases` under `out`:
+///
+/// ```text
+/// out/
+///   manifest.json             # format, crate and codec versions; case names
+///   maps/
+///     input.json              # schema to convert
+///     options.json            # convert options, kebab-case ({} for defaults)
+///     expected-schema.json    # converted schema
+///     codec.json              # rehydration codec
+///     error.json              # instead of the two above, if convert fails
+///     data.json               # LLM output (cases with data.json only)
+///     rehydrated.json         # {"data", "warnings"} from rehydrating it
+/// ```
+///
+/// Every file is pretty-printed with a trailing newline, so regenerating
+/// unchanged vectors produces no diff.
+pub fn write_vectors(cases: &[CorpusCase], out: &Path) -> Result<(), String> {
+    for case in cases {
+        let dir = out.join(&case.name);
+        fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        for (file, value) in case_vectors(case)? {
+            write_json(&dir.join(file), &value)?;
+        }
+    }
+    let manifest = json!({
+        "formatVersion": VECTORS_FORMAT_VERSION,
+        "version": crate::VERSION,
+        "apiVersion": crate::API_VERSION,
+        "codecMajorVersion": crate::codec::CODEC_MAJOR_VERSION,
+        "cases": cases.iter().map(|c| &c.name).collect::<Vec<_>>(),
+    });
+    write_json(&out.join("manifest.json"), &manifest)
+}
+
+/// The vector files for one case, in the order [`write_vectors`] lists them.
+fn case_vectors(case: &CorpusCase) -> Result<Vec<(&'static str, Value)>, String> {
+    let input_text = read(&case.dir.join(INPUT_FILE))?;
+    let input: Value = serde_json::from_str(&input_text)
+        .map_err(|e| format!("{}: {}", case.dir.join(INPUT_FILE).display(), e))?;
+    let (raw_options, options) = read_options(case)?;
+    let mut files = vec![(INPUT_FILE, input.clone()), (OPTIONS_FILE, raw_options)];
+
+    let result = match crate::convert_str(&input_text, &options) {
+        Ok(result) => result,
+        Err(e) => {
+            files.push(("error.json", e.to_json()));
+            return Ok(files);
+        }
+    };
+    files.push(("expected-schema.json", result.schema));
+    files.push((
+        "codec.json",
+        serde_json::to_value(&result.codec).map_err(|e| e.to_string())?,
+    ));
+
+    let data_path = case.dir.join(DATA_FILE);
+    if data_path.is_file() {
+        let data: Value = serde_json::from_str(&read(&data_path)?)
+            .map_err(|e| format!("{}: {}", data_path.display(), e))?;
+        let rehydrated = match crate::rehydrate(&data, &result.codec, &input) {
+            Ok(r) => json!({ "data": r.data, "warnings": r.warnings }),
+            Err(e) => json!({ "error": e.to_json() }),
+        };
+        files.push((DATA_FILE, data));
+        files.push(("rehydrated.json", rehydrated));
+    }
+    Ok(files)
+}
+
+/// The case's options as written (`{}` when absent) and as parsed.
+fn read_options(case: &CorpusCase) -> Result<(Value, ConvertOptions), String> {
+    let options_path = case.dir.join(OPTIONS_FILE);
+    if !options_path.is_file() {
+        return Ok((json!({}), ConvertOptions::default()));
+    }
+    let raw: Value = serde_json::from_str(&read(&options_path)?)
+        .map_err(|e| format!("{}: {}", options_path.display(), e))?;
+    let options = serde_json::from_value(raw.clone())
+        .map_err(|e| format!("{}: {}", options_path.display(), e))?;
+    Ok((raw, options))
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<(), String> {
+    let mut text = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    text.push('\n');
+    fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_case(name: &str, input: &str, options: Option<&str>) -> CorpusCase {
+        let dir = std::env::temp_dir()
+            .join(format!("jsl-corpus-{}-{}", std::process::id(), name))
+            .join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(INPUT_FILE), input).unwrap();
+        if let Some(options) = options {
+            fs::write(dir.join(OPTIONS_FILE), options).unwrap();
+        }
+        CorpusCase {
+            name: name.to_string(),
+            dir,
+        }
+    }
+
+    #[test]
+    fn test_bless_then_check_matches() {
+        let case = temp_case(
+            "simple",
+            r#"{"type": "object", "properties": {"a": {"type": "string"}}}"#,
+            None,
+        );
+        assert!(matches!(check(&case), CaseStatus::Missing { .. }));
+        assert!(bless(&case).unwrap());
+        assert!(!bless(&case).unwrap(), "second bless is a no-op");
+        assert_eq!(check(&case), CaseStatus::Match);
+
+        fs::write(
+            case.dir.join(INPUT_FILE),
+            r#"{"type": "object", "properties": {"b": {"type": "string"}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(check(&case), CaseStatus::Mismatch { .. }));
+        fs::remove_dir_all(case.dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_errors_are_snapshotted() {
+        let case = temp_case("bad-ref", r##"{"$ref": "#/$defs/Nope"}"##, None);
+        let snap = snapshot(&case).unwrap();
+        assert_eq!(snap["error"]["code"], "unresolvable_ref");
+        fs::remove_dir_all(case.dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_write_vectors_with_rehydration() {
+        let case = temp_case(
+            "vectors",
+            r#"{"type": "object", "properties": {"n": {"type": "integer"}}, "required": ["n"]}"#,
+            None,
+        );
+        fs::write(case.dir.join(DATA_FILE), r#"{"n": 3.0}"#).unwrap();
+        let out = case.dir.parent().unwrap().join("out");
+        write_vectors(std::slice::from_ref(&case), &out).unwrap();
+
+        let load = |file: &str| -> Value {
+            serde_json::from_str(&fs::read_to_string(out.join(file)).unwrap()).unwrap()
+        };
+        let manifest = load("manifest.json");
+        assert_eq!(manifest["formatVersion"], VECTORS_FORMAT_VERSION);
+        assert_eq!(manifest["cases"], json!(["vectors"]));
+        assert_eq!(load("vectors/options.json"), json!({}));
+        assert_eq!(
+            load("vectors/expected-schema.json")["additionalProperties"],
+            false
+        );
+        assert!(load("vectors/codec.json")["$schema"].is_string());
+        let rehydrated = load("vectors/rehydrated.json");
+        assert_eq!(rehydrated["data"], json!({"n": 3}));
+        assert_eq!(rehydrated["warnings"].as_array().unwrap().len(), 1);
+        fs::remove_dir_all(case.dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_options_reported() {
+        let case = temp_case("bad-options", "{}", Some(r#"{"target": "nope"}"#));
+        assert!(matches!(check(&case), CaseStatus::Invalid(_)));
+        fs::remove_dir_all(case.dir.parent().unwrap()).unwrap();
+    }
+}
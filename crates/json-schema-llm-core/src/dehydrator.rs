@@ -0,0 +1,341 @@
+//! Forward transforms: original-shape data → converted-shape data.
+//!
+//! The codec records how the schema changed so rehydration can undo it.
+//! [`dehydrate`] applies the same transforms the other way, turning data
+//! shaped for the original schema into an answer to the converted one:
+//! maps become entry arrays, missing optional properties become `null`,
+//! opaque and recursive subtrees become JSON strings, a non-object root is
+//! wrapped, and so on. Rehydrating the result gives the data back.
+//!
+//! Some transforms have no single forward image and are left as they are:
+//! values of variants collapsed by `max_anyof_variants`, `$node_id`
+//! recursion references, and custom transforms. Two more need the converted
+//! schema, passed to [`dehydrate_with_schema`]: which keys of an object
+//! mixing `properties` and `additionalProperties` are extra entries, and
+//! the `variant` index of a map entry with a union value (the first branch
+//! whose `type`, `const` and `enum` accept the value). [`dehydrate`] leaves
+//! such objects as they are and gives every entry variant 0.
+//!
+//! ```rust,no_run
+//! use json_schema_llm_core::{convert, dehydrate, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "tags": { "type": "object", "additionalProperties": { "type": "string" } }
+//!     }
+//! });
+//! let result = convert(&schema, &ConvertOptions::default()).unwrap();
+//! let converted = dehydrate(&json!({ "tags": { "role": "admin" } }), &result.codec).unwrap();
+//! assert_eq!(converted, json!({ "tags": [{ "key": "role", "value": "admin" }] }));
+//! ```
+
+use serde_json::{Map, Value};
+
+use crate::codec::{Codec, Transform};
+use crate::error::ConvertError;
+use crate::path_map::{converted_transform_paths, transform_path};
+use crate::rehydrator::{build_pattern_properties_cache, walk, RegexCache, TransformSite};
+use crate::schema_utils::{build_path, resolve_pointer, split_path};
+
+/// Reshape `data`, an instance of the original schema, into an instance of
+/// the converted schema `codec` belongs to.
+///
+/// # Errors
+///
+/// [`ConvertError::SchemaError`] if a value can't be serialized into a
+/// JSON-string field.
+pub fn dehydrate(data: &Value, codec: &Codec) -> Result<Value, ConvertError> {
+    Dehydrator::new(codec, None).apply(data)
+}
+
+/// [`dehydrate`], consulting `converted_schema` (the schema `codec` belongs
+/// to) for the extra entries of mixed objects and map entry variants.
+pub fn dehydrate_with_schema(
+    data: &Value,
+    codec: &Codec,
+    converted_schema: &Value,
+) -> Result<Value, ConvertError> {
+    Dehydrator::new(codec, Some(converted_schema)).apply(data)
+}
+
+/// A codec prepared for dehydrating several instances.
+pub(crate) struct Dehydrator<'a> {
+    codec: &'a Codec,
+    regex_cache: RegexCache,
+    site: Forward<'a>,
+}
+
+impl<'a> Dehydrator<'a> {
+    pub(crate) fn new(codec: &'a Codec, converted_schema: Option<&'a Value>) -> Self {
+        Self {
+            codec,
+            regex_cache: build_pattern_properties_cache(codec),
+            site: Forward {
+                converted: converted_schema,
+                converted_paths: converted_schema
+                    .map(|schema| converted_transform_paths(codec, schema))
+                    .unwrap_or_default(),
+                current: 0,
+            },
+        }
+    }
+
+    pub(crate) fn apply(&mut self, data: &Value) -> Result<Value, ConvertError> {
+        let mut data = data.clone();
+        for (i, transform) in self.codec.transforms.iter().enumerate() {
+            self.site.current = i;
+            let segments = split_path(transform_path(transform));
+            let seg_refs: Vec<&str> = segments.iter().map(String::as_str).collect();
+            walk(
+                &mut data,
+                &seg_refs,
+                transform,
+                &self.regex_cache,
+                "",
+                &mut self.site,
+            )?;
+        }
+        Ok(data)
+    }
+}
+
+/// Each transform applied in the conversion's direction.
+struct Forward<'a> {
+    converted: Option<&'a Value>,
+    /// Where each transform landed in `converted`, in codec order.
+    converted_paths: Vec<String>,
+    /// Index of the transform being applied.
+    current: usize,
+}
+
+impl Forward<'_> {
+    /// The converted schema at `suffix` below the current transform.
+    fn converted_node(&self, suffix: &[&str]) -> Option<&Value> {
+        let path = build_path(self.converted_paths.get(self.current)?, suffix);
+        resolve_pointer(self.converted?, &path)
+    }
+}
+
+impl TransformSite for Forward<'_> {
+    fn at_node(
+        &mut self,
+        data: &mut Value,
+        transform: &Transform,
+        data_path: &str,
+    ) -> Result<(), ConvertError> {
+        match transform {
+            Transform::MapToArray {
+                key_field,
+                variant_field,
+                ..
+            } => {
+                let branches = variant_field.as_ref().and_then(|_| {
+                    self.converted_node(&["items", "properties", "value"])
+                        .and_then(union_branches)
+                });
+                if let Value::Object(map) = data {
+                    let entries = std::mem::take(map)
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let mut entry = Map::new();
+                            entry.insert(key_field.clone(), Value::String(key));
+                            if let Some(field) = variant_field {
+                                let index = branches
+                                    .and_then(|b| b.iter().position(|s| accepts(s, &value)))
+                                    .unwrap_or(0);
+                                entry.insert(field.clone(), Value::from(index));
+                            }
+                            entry.insert("value".to_string(), value);
+                            Value::Object(entry)
+                        })
+                        .collect();
+                    *data = Value::Array(entries);
+                }
+            }
+            Transform::JsonStringParse { .. } | Transform::RecursiveInflate { .. } => {
+                let text = serde_json::to_string(data).map_err(|e| ConvertError::SchemaError {
+                    path: data_path.to_string(),
+                    message: format!("cannot serialize value into a JSON string: {}", e),
+                    location: None,
+                })?;
+                *data = Value::String(text);
+            }
+            Transform::ExtractAdditionalProperties { property_name, .. } => {
+                // Without the converted schema, declared properties can't be
+                // told apart from extra entries; the object is left as is.
+                let declared = self.converted_node(&[]).and_then(object_properties);
+                if let (Some(declared), Some(obj)) = (declared, data.as_object_mut()) {
+                    let extra_keys: Vec<String> = obj
+                        .keys()
+                        .filter(|k| !declared.contains_key(*k))
+                        .cloned()
+                        .collect();
+                    let extra: Map<String, Value> = extra_keys
+                        .into_iter()
+                        .filter_map(|k| obj.remove(&k).map(|v| (k, v)))
+                        .collect();
+                    obj.insert(property_name.clone(), Value::Object(extra));
+                }
+            }
+            Transform::RootObjectWrapper { wrapper_key, .. } => {
+                let inner = std::mem::take(data);
+                let mut wrapper = Map::new();
+                wrapper.insert(wrapper_key.clone(), inner);
+                *data = Value::Object(wrapper);
+            }
+            Transform::EnumStringify { .. } => {
+                if !data.is_string() && !data.is_null() {
+                    *data = Value::String(data.to_string());
+                }
+            }
+            Transform::FilteredProperty { property, .. } => {
+                if let Some(obj) = data.as_object_mut() {
+                    obj.remove(property);
+                }
+            }
+            Transform::ProjectedVariants { variants, .. } => {
+                let variant = data
+                    .as_object()
+                    .and_then(|obj| crate::project::original_variant(variants, obj));
+                if let Some(variant) = variant {
+                    let codec = Codec {
+                        transforms: variant.transforms.clone(),
+                        ..Codec::new()
+                    };
+                    *data = Dehydrator::new(&codec, None).apply(data)?;
+                }
+            }
+            Transform::JsonStringEncode { .. } => {
+                if let Some(parsed) = data.as_str().and_then(|s| serde_json::from_str(s).ok()) {
+                    *data = parsed;
+                }
+            }
+            Transform::NullableOptional { .. }
+            | Transform::DiscriminatorAnyOf { .. }
+            | Transform::RecursiveTerminate { .. }
+            | Transform::RecursiveNodeRef { .. }
+            | Transform::AnyOfOverflow { .. }
+            | Transform::Custom { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn at_property(&mut self, parent: &mut Value, key: &str, transform: &Transform) -> bool {
+        match transform {
+            Transform::NullableOptional { .. } => {
+                if let Some(obj) = parent.as_object_mut() {
+                    obj.entry(key.to_string()).or_insert(Value::Null);
+                }
+                true
+            }
+            Transform::RecursiveTerminate { .. } => true,
+            _ => false,
+        }
+    }
+
+    fn at_items(&mut self, _items: &mut Vec<Value>, transform: &Transform) -> bool {
+        matches!(transform, Transform::RecursiveTerminate { .. })
+    }
+}
+
+/// The `anyOf` / `oneOf` branches of a union schema.
+fn union_branches(node: &Value) -> Option<&Vec<Value>> {
+    node.get("anyOf")
+        .or_else(|| node.get("oneOf"))
+        .and_then(Value::as_array)
+}
+
+/// The `properties` of an object schema, looking through a nullable or
+/// other union wrapper.
+fn object_properties(node: &Value) -> Option<&Map<String, Value>> {
+    node.get("properties")
+        .and_then(Value::as_object)
+        .or_else(|| union_branches(node)?.iter().find_map(object_properties))
+}
+
+/// Whether `schema`'s `type`, `const` and `enum` admit `value`.
+fn accepts(schema: &Value, value: &Value) -> bool {
+    if let Some(expected) = schema.get("const") {
+        return expected == value;
+    }
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        return options.contains(value);
+    }
+    let type_matches = |t: &Value| match t.as_str() {
+        Some("null") => value.is_null(),
+        Some("boolean") => value.is_boolean(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("number") => value.is_number(),
+        Some("string") => value.is_string(),
+        Some("array") => value.is_array(),
+        Some("object") => value.is_object(),
+        _ => true,
+    };
+    match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().any(type_matches),
+        Some(t) => type_matches(t),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, rehydrate, ConvertOptions};
+    use serde_json::json;
+
+    #[test]
+    fn test_dehydrate_rehydrate_round_trip() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "nickname": { "type": "string" },
+                "meta": {},
+                "labels": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "required": ["name", "meta", "labels"]
+        });
+        let data = json!({ "name": "Ada", "meta": { "x": [1] }, "labels": { "a": "b" } });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+
+        let converted = dehydrate(&data, &result.codec).unwrap();
+        assert_eq!(
+            converted,
+            json!({
+                "name": "Ada",
+                "nickname": null,
+                "meta": "{\"x\":[1]}",
+                "labels": [{ "key": "a", "value": "b" }]
+            })
+        );
+        let restored = rehydrate(&converted, &result.codec, &schema).unwrap();
+        assert_eq!(restored.data, data);
+    }
+
+    #[test]
+    fn test_dehydrate_mixed_object_needs_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "id": { "type": "string" } },
+            "additionalProperties": { "type": "integer" },
+            "required": ["id"]
+        });
+        let data = json!({ "id": "x", "count": 3 });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+
+        let converted = dehydrate_with_schema(&data, &result.codec, &result.schema).unwrap();
+        assert_eq!(
+            converted,
+            json!({ "id": "x", "_additional": [{ "key": "count", "value": 3 }] })
+        );
+        let restored = rehydrate(&converted, &result.codec, &schema).unwrap();
+        assert_eq!(restored.data, data);
+
+        // Without the schema the extra entry stays where it was.
+        let converted = dehydrate(&data, &result.codec).unwrap();
+        assert_eq!(converted["count"], 3);
+    }
+}
@@ -0,0 +1,509 @@
+//! Stable rule IDs for conversion and rehydration diagnostics.
+//!
+//! Every [`ProviderCompatError`] and rehydration
+//! [`WarningKind`](crate::codec_warning::WarningKind) maps to a rule with a
+//! stable ID (e.g. `JSL-P9-001`). IDs are included in serialized output as
+//! `ruleId` and, like [`ErrorCode`](crate::ErrorCode) strings, never change
+//! meaning once published — so callers can gate on or suppress specific
+//! rules the way they would with a linter.
+//!
+//! [`RuleSelector`]s in [`ConvertOptions::suppress`](crate::ConvertOptions::suppress)
+//! acknowledge specific findings, optionally only under a path glob.
+//!
+//! ```rust
+//! use json_schema_llm_core::diagnostics::{find_rule, rule_catalog};
+//!
+//! let depth = find_rule("JSL-P9-001").unwrap();
+//! assert_eq!(depth.name, "depth_budget_exceeded");
+//! assert!(rule_catalog().iter().all(|r| r.id.starts_with("JSL-")));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::codec_warning::{Warning, WarningKind};
+use crate::config::RecursionLeaf;
+use crate::error::ProviderCompatError;
+
+/// Dropped constraints restated in a node's `description`, per
+/// [`ConvertOptions::constraint_hints`](crate::ConvertOptions::constraint_hints).
+/// Listed in [`ConvertResult::constraint_hints`](crate::ConvertResult::constraint_hints)
+/// so callers can see which intent survived only as prose.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConstraintHint {
+    /// Schema location, in converted-schema coordinates.
+    pub path: String,
+    /// The dropped keywords the text covers, e.g. `["pattern", "minLength"]`.
+    pub constraints: Vec<String>,
+    /// The text appended to the description.
+    pub text: String,
+}
+
+/// A recursive `$ref` cycle cut off at
+/// [`ConvertOptions::recursion_limit`](crate::ConvertOptions::recursion_limit).
+/// Listed in [`ConvertResult::recursion_cycles`](crate::ConvertResult::recursion_cycles),
+/// one per break point, so schema authors can see which cycles are hot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycleInfo {
+    /// The `$ref`s around the cycle, starting with the one cut off.
+    pub refs: Vec<String>,
+    /// Schema location of the placeholder, as in its codec transform.
+    pub break_path: String,
+    /// The placeholder used, per
+    /// [`ConvertOptions::recursion_leaf`](crate::ConvertOptions::recursion_leaf).
+    pub strategy: RecursionLeaf,
+}
+
+/// How seriously a rule's findings should be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Information was lost: the LLM can no longer be constrained exactly as
+    /// the original schema intended, or output may fail validation.
+    Warning,
+    /// A lossless rewrite the caller may want to know about.
+    Info,
+}
+
+/// A catalog entry describing one diagnostic rule.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    /// Stable identifier, e.g. `JSL-P9-001`.
+    pub id: &'static str,
+    /// The `type` tag of the diagnostic in serialized output.
+    pub name: &'static str,
+    pub severity: Severity,
+    /// One-line description of what triggers the rule.
+    pub summary: &'static str,
+}
+
+const fn rule(
+    id: &'static str,
+    name: &'static str,
+    severity: Severity,
+    summary: &'static str,
+) -> Rule {
+    Rule {
+        id,
+        name,
+        severity,
+        summary,
+    }
+}
+
+/// Every known rule, grouped by source (`CD` = codec, reported by
+/// [`lint`](crate::lint), `P0` = normalization pass, `P1` = composition
+/// pass, `P9` = provider compatibility pass, `RH` = rehydration) and ordered
+/// by ID.
+static RULES: &[Rule] = &[
+    rule(
+        "JSL-CD-001",
+        "constraint_dropped",
+        Severity::Warning,
+        "Constraint the target cannot express was dropped; it is checked on rehydration.",
+    ),
+    rule(
+        "JSL-CD-002",
+        "shape_rewritten",
+        Severity::Info,
+        "Schema shape was rewritten for the target and is restored on rehydration.",
+    ),
+    rule(
+        "JSL-P0-001",
+        "legacy_keyword",
+        Severity::Warning,
+        "Legacy-draft keyword without a 2020-12 equivalent was removed.",
+    ),
+    rule(
+        "JSL-P0-002",
+        "unresolvable_ref_recovered",
+        Severity::Warning,
+        "Unresolvable $ref was skipped or replaced with a placeholder.",
+    ),
+    rule(
+        "JSL-P0-003",
+        "duplicate_schema_key",
+        Severity::Warning,
+        "Object key repeated in the schema text; the last occurrence was kept.",
+    ),
+    rule(
+        "JSL-P1-001",
+        "deprecated_property",
+        Severity::Info,
+        "Deprecated property was dropped or annotated.",
+    ),
+    rule(
+        "JSL-P1-002",
+        "merge_conflict",
+        Severity::Warning,
+        "allOf members disagreed on a keyword; the merge kept one side or an empty intersection.",
+    ),
+    rule(
+        "JSL-P9-001",
+        "depth_budget_exceeded",
+        Severity::Warning,
+        "Sub-schema at the provider's nesting limit was replaced with an opaque JSON string.",
+    ),
+    rule(
+        "JSL-P9-002",
+        "root_type_incompatible",
+        Severity::Info,
+        "Non-object root was wrapped in an object for a provider that requires one.",
+    ),
+    rule(
+        "JSL-P9-003",
+        "mixed_enum_types",
+        Severity::Warning,
+        "Enum with mixed value types was stringified.",
+    ),
+    rule(
+        "JSL-P9-004",
+        "unconstrained_schema",
+        Severity::Warning,
+        "Boolean or empty schema was replaced with an opaque string or sealed object.",
+    ),
+    rule(
+        "JSL-P9-005",
+        "pattern_properties_stripped",
+        Severity::Warning,
+        "patternProperties was dropped from a typed object.",
+    ),
+    rule(
+        "JSL-P9-006",
+        "pattern_properties_stringified",
+        Severity::Warning,
+        "patternProperties-only object was replaced with an opaque JSON string.",
+    ),
+    rule(
+        "JSL-P9-007",
+        "ref_keyword_stripped",
+        Severity::Info,
+        "Reference-mechanism keyword ($anchor, $dynamicRef, ...) was removed.",
+    ),
+    rule(
+        "JSL-P9-008",
+        "bare_required_stripped",
+        Severity::Info,
+        "Redundant required-only anyOf branch was removed.",
+    ),
+    rule(
+        "JSL-P9-009",
+        "type_array_converted",
+        Severity::Info,
+        "Type array was rewritten as anyOf branches.",
+    ),
+    rule(
+        "JSL-RH-001",
+        "constraint_violation",
+        Severity::Warning,
+        "LLM output violates a constraint that was dropped during conversion.",
+    ),
+    rule(
+        "JSL-RH-002",
+        "constraint_unevaluable",
+        Severity::Warning,
+        "A dropped constraint could not be evaluated against LLM output.",
+    ),
+    rule(
+        "JSL-RH-003",
+        "path_not_found",
+        Severity::Info,
+        "A codec path did not resolve in the LLM output.",
+    ),
+    rule(
+        "JSL-RH-004",
+        "unhandled_transform",
+        Severity::Warning,
+        "A custom codec transform had no registered rehydration handler.",
+    ),
+    rule(
+        "JSL-RH-005",
+        "truncated",
+        Severity::Info,
+        "Rehydration warnings past the configured limit were omitted.",
+    ),
+    rule(
+        "JSL-RH-006",
+        "duplicate_key",
+        Severity::Warning,
+        "A restored map had several entries with the same key; values were lost.",
+    ),
+    rule(
+        "JSL-RH-007",
+        "discriminator_normalized",
+        Severity::Warning,
+        "A near-miss discriminator value was rewritten to its variant's value.",
+    ),
+    rule(
+        "JSL-RH-008",
+        "variant_mismatch",
+        Severity::Warning,
+        "An object under a discriminated union matches none of its variants cleanly.",
+    ),
+    rule(
+        "JSL-RH-009",
+        "unresolved_node_ref",
+        Severity::Warning,
+        "A recursive node reference named no node, or an enclosing one, and was kept as is.",
+    ),
+];
+
+/// All diagnostic rules, ordered by ID.
+pub fn rule_catalog() -> &'static [Rule] {
+    RULES
+}
+
+/// Look up a rule by its stable ID.
+pub fn find_rule(id: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|r| r.id == id)
+}
+
+impl ProviderCompatError {
+    /// Stable rule ID for this diagnostic, e.g. `JSL-P9-001`.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            ProviderCompatError::DepthBudgetExceeded { .. } => "JSL-P9-001",
+            ProviderCompatError::RootTypeIncompatible { .. } => "JSL-P9-002",
+            ProviderCompatError::MixedEnumTypes { .. } => "JSL-P9-003",
+            ProviderCompatError::UnconstrainedSchema { .. } => "JSL-P9-004",
+            ProviderCompatError::PatternPropertiesStripped { .. } => "JSL-P9-005",
+            ProviderCompatError::PatternPropertiesStringified { .. } => "JSL-P9-006",
+            ProviderCompatError::RefKeywordStripped { .. } => "JSL-P9-007",
+            ProviderCompatError::BareRequiredStripped { .. } => "JSL-P9-008",
+            ProviderCompatError::TypeArrayConverted { .. } => "JSL-P9-009",
+            ProviderCompatError::DeprecatedProperty { .. } => "JSL-P1-001",
+            ProviderCompatError::MergeConflict { .. } => "JSL-P1-002",
+            ProviderCompatError::LegacyKeyword { .. } => "JSL-P0-001",
+            ProviderCompatError::UnresolvableRefRecovered { .. } => "JSL-P0-002",
+            ProviderCompatError::DuplicateSchemaKey { .. } => "JSL-P0-003",
+        }
+    }
+}
+
+impl WarningKind {
+    /// Stable rule ID for this warning kind, e.g. `JSL-RH-001`.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            WarningKind::ConstraintViolation { .. } => "JSL-RH-001",
+            WarningKind::ConstraintUnevaluable { .. } => "JSL-RH-002",
+            WarningKind::PathNotFound => "JSL-RH-003",
+            WarningKind::UnhandledTransform { .. } => "JSL-RH-004",
+            WarningKind::Truncated { .. } => "JSL-RH-005",
+            WarningKind::DuplicateKey { .. } => "JSL-RH-006",
+            WarningKind::DiscriminatorNormalized { .. } => "JSL-RH-007",
+            WarningKind::VariantMismatch { .. } => "JSL-RH-008",
+            WarningKind::UnresolvedNodeRef { .. } => "JSL-RH-009",
+        }
+    }
+}
+
+impl Warning {
+    /// Stable rule ID for this warning; see [`WarningKind::rule_id`].
+    pub fn rule_id(&self) -> &'static str {
+        self.kind.rule_id()
+    }
+}
+
+/// Selects diagnostics by rule ID and, optionally, by schema path.
+///
+/// `path` is a glob over JSON Pointer segments: `*` matches within one
+/// segment and `**` matches any number of segments, so
+/// `#/properties/legacy/**` covers everything under `legacy`. Paths are
+/// matched in the same (post-conversion) coordinates the diagnostics report.
+///
+/// Parses from `RULE` or `RULE:GLOB`, e.g. `JSL-P9-001:#/properties/*`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RuleSelector {
+    /// Rule ID to match, e.g. `JSL-P9-001`.
+    pub rule: String,
+    /// Path glob. `None` matches the rule everywhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl RuleSelector {
+    /// Select every finding of `rule`.
+    pub fn rule(rule: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            path: None,
+        }
+    }
+
+    /// Select findings of `rule` whose path matches `glob`.
+    pub fn at(rule: impl Into<String>, glob: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            path: Some(glob.into()),
+        }
+    }
+
+    /// Whether this selector covers a finding of `rule_id` at `path`.
+    pub fn matches_rule_at(&self, rule_id: &str, path: &str) -> bool {
+        self.rule == rule_id
+            && self
+                .path
+                .as_deref()
+                .is_none_or(|glob| glob_match(glob, path))
+    }
+
+    /// Whether this selector covers `err`.
+    pub fn matches(&self, err: &ProviderCompatError) -> bool {
+        self.matches_rule_at(err.rule_id(), err.path())
+    }
+}
+
+impl FromStr for RuleSelector {
+    type Err = String;
+
+    /// Parse `RULE` or `RULE:GLOB`. Unknown rule IDs are rejected so that a
+    /// typo doesn't silently suppress nothing.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rule, path) = match s.split_once(':') {
+            Some((rule, glob)) => (rule, Some(glob.to_string())),
+            None => (s, None),
+        };
+        if find_rule(rule).is_none() {
+            return Err(format!("unknown rule ID '{}'", rule));
+        }
+        Ok(Self {
+            rule: rule.to_string(),
+            path,
+        })
+    }
+}
+
+impl fmt::Display for RuleSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(glob) => write!(f, "{}:{}", self.rule, glob),
+            None => f.write_str(&self.rule),
+        }
+    }
+}
+
+pub(crate) fn glob_match(glob: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = glob.split('/').collect();
+    let segments: Vec<&str> = path.split('/').collect();
+    match_segments(&pattern, &segments)
+}
+
+fn match_segments(pattern: &[&str], segments: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+        Some((&"**", rest)) => (0..=segments.len()).any(|i| match_segments(rest, &segments[i..])),
+        Some((seg, rest)) => segments
+            .split_first()
+            .is_some_and(|(head, tail)| wildcard_match(seg, head) && match_segments(rest, tail)),
+    }
+}
+
+/// Match one segment against a pattern where `*` matches any run of chars.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: exact match.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Target;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rule_ids_unique_and_sorted() {
+        let ids: Vec<&str> = RULES.iter().map(|r| r.id).collect();
+        let unique: HashSet<&str> = ids.iter().copied().collect();
+        assert_eq!(unique.len(), ids.len(), "duplicate rule ID");
+        let mut sorted = ids.clone();
+        sorted.sort_unstable();
+        assert_eq!(ids, sorted, "catalog must stay ordered by ID");
+    }
+
+    #[test]
+    fn test_provider_compat_rule_matches_serialized_type() {
+        let err = ProviderCompatError::MixedEnumTypes {
+            path: "#/properties/x".to_string(),
+            types_found: vec!["string".to_string(), "integer".to_string()],
+            target: Target::OpenaiStrict,
+            hint: String::new(),
+        };
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["ruleId"], "JSL-P9-003");
+        let rule = find_rule(err.rule_id()).unwrap();
+        assert_eq!(json["type"], rule.name);
+
+        // ruleId is output-only: it round-trips without complaint.
+        let back: ProviderCompatError = serde_json::from_value(json).unwrap();
+        assert_eq!(back.rule_id(), "JSL-P9-003");
+    }
+
+    #[test]
+    fn test_warning_rule_matches_serialized_kind() {
+        let warning = Warning::new(
+            "/x",
+            "#/properties/x",
+            WarningKind::PathNotFound,
+            String::new(),
+        );
+        let json = serde_json::to_value(&warning).unwrap();
+        assert_eq!(json["ruleId"], "JSL-RH-003");
+        assert_eq!(json["kind"]["type"], find_rule("JSL-RH-003").unwrap().name);
+        let back: Warning = serde_json::from_value(json).unwrap();
+        assert_eq!(back.rule_id(), "JSL-RH-003");
+    }
+
+    #[test]
+    fn test_selector_glob() {
+        let sel = RuleSelector::at("JSL-P9-001", "#/properties/legacy/**");
+        assert!(sel.matches_rule_at("JSL-P9-001", "#/properties/legacy"));
+        assert!(sel.matches_rule_at("JSL-P9-001", "#/properties/legacy/items/properties/x"));
+        assert!(!sel.matches_rule_at("JSL-P9-001", "#/properties/legacyish"));
+        assert!(!sel.matches_rule_at("JSL-P9-003", "#/properties/legacy"));
+
+        let sel = RuleSelector::at("JSL-P9-003", "#/properties/*_code");
+        assert!(sel.matches_rule_at("JSL-P9-003", "#/properties/status_code"));
+        assert!(!sel.matches_rule_at("JSL-P9-003", "#/properties/status_code/items"));
+        assert!(!sel.matches_rule_at("JSL-P9-003", "#/properties/status"));
+
+        assert!(RuleSelector::rule("JSL-P9-003").matches_rule_at("JSL-P9-003", "#/anything"));
+    }
+
+    #[test]
+    fn test_selector_parse() {
+        let sel: RuleSelector = "JSL-P9-001:#/properties/*".parse().unwrap();
+        assert_eq!(sel, RuleSelector::at("JSL-P9-001", "#/properties/*"));
+        assert_eq!(sel.to_string(), "JSL-P9-001:#/properties/*");
+        assert_eq!(
+            "JSL-P9-002".parse::<RuleSelector>().unwrap(),
+            RuleSelector::rule("JSL-P9-002")
+        );
+        assert!("JSL-P9-999".parse::<RuleSelector>().is_err());
+    }
+
+    #[test]
+    fn test_find_rule_unknown() {
+        assert!(find_rule("JSL-P9-999").is_none());
+    }
+}
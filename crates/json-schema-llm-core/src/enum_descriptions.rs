@@ -0,0 +1,347 @@
+//! Per-value enum descriptions, for
+//! [`ConvertOptions::enum_descriptions`](crate::ConvertOptions::enum_descriptions).
+//!
+//! Models pick enum values far more reliably when each value comes with a
+//! gloss. Schemas carry glosses in two forms: an `x-enum-descriptions`
+//! extension next to `enum`, which no provider reads, and a `oneOf` of
+//! `const`s with a `description` each. This step runs right after Pass 0,
+//! once `$ref`s are inlined, and per [`EnumDescriptionStyle`] folds the
+//! glosses into the node's `description` or rewrites the extension as a
+//! union.
+//!
+//! Neither rewrite changes the values the model produces, so nothing is
+//! recorded in the codec.
+
+use serde_json::{json, Map, Value};
+
+use crate::config::{ConvertOptions, EnumDescriptionStyle};
+use crate::error::ConvertError;
+use crate::limits::ResourceGuard;
+use crate::messages::{fill, MessageCatalog};
+use crate::schema_utils::{append_to_description, recurse_into_children};
+
+/// Extension keywords holding per-value descriptions next to `enum`.
+const EXTENSION_KEYWORDS: &[&str] = &["x-enum-descriptions", "x-enumDescriptions"];
+
+/// Keywords a `const` variant may carry besides its value and still be
+/// folded into an `enum`.
+const VARIANT_ANNOTATIONS: &[&str] = &["$comment", "description", "title", "type"];
+
+/// A value and its gloss, if it has one.
+type Gloss = (Value, Option<String>);
+
+/// Present per-value enum descriptions per `options.enum_descriptions`,
+/// ticking `guard` at every node visited.
+pub(crate) fn apply(
+    schema: Value,
+    options: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<Value, ConvertError> {
+    if options.enum_descriptions == EnumDescriptionStyle::Ignore {
+        return Ok(schema);
+    }
+    walk(schema, "#", 0, options, guard)
+}
+
+fn walk(
+    node: Value,
+    path: &str,
+    depth: usize,
+    options: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<Value, ConvertError> {
+    if depth > options.max_depth {
+        return Ok(node);
+    }
+    guard.tick(path)?;
+
+    let mut obj = match node {
+        Value::Object(obj) => obj,
+        other => return Ok(other),
+    };
+
+    let messages = options.messages();
+    match options.enum_descriptions {
+        EnumDescriptionStyle::Auto => fold_extension(&mut obj, messages),
+        EnumDescriptionStyle::Fold => {
+            fold_extension(&mut obj, messages);
+            fold_union(&mut obj, messages);
+        }
+        EnumDescriptionStyle::OneOf => {
+            // A node that already has a union cannot take a second one.
+            if !expand_extension(&mut obj) {
+                fold_extension(&mut obj, messages);
+            }
+        }
+        EnumDescriptionStyle::Ignore => {}
+    }
+
+    recurse_into_children(&mut obj, path, depth, &mut |val, child_path, d| {
+        walk(val, child_path, d, options, guard)
+    })?;
+    Ok(Value::Object(obj))
+}
+
+/// Pair each `enum` value with its entry in the extension: an array
+/// parallel to `enum`, or an object keyed by value (a string value as is,
+/// any other as JSON text). `None` if the node has no extension or it does
+/// not line up with `enum`.
+fn extension_glosses(obj: &Map<String, Value>) -> Option<Vec<Gloss>> {
+    let extension = EXTENSION_KEYWORDS.iter().find_map(|k| obj.get(*k))?;
+    let values = obj.get("enum")?.as_array()?;
+    let gloss = |entry: Option<&Value>| entry.and_then(Value::as_str).map(str::to_string);
+    match extension {
+        Value::Array(entries) if entries.len() == values.len() => Some(
+            values
+                .iter()
+                .zip(entries)
+                .map(|(value, entry)| (value.clone(), gloss(Some(entry))))
+                .collect(),
+        ),
+        Value::Object(entries) => Some(
+            values
+                .iter()
+                .map(|value| {
+                    let key = value
+                        .as_str()
+                        .map_or_else(|| value.to_string(), str::to_string);
+                    (value.clone(), gloss(entries.get(&key)))
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn remove_extension(obj: &mut Map<String, Value>) {
+    for keyword in EXTENSION_KEYWORDS {
+        obj.remove(*keyword);
+    }
+}
+
+/// Move the extension's glosses into the node's `description`.
+fn fold_extension(obj: &mut Map<String, Value>, messages: &MessageCatalog) {
+    let Some(glosses) = extension_glosses(obj) else {
+        return;
+    };
+    remove_extension(obj);
+    append_glosses(obj, &glosses, messages);
+}
+
+/// Replace `enum` and the extension with a `oneOf` of described `const`s.
+/// Returns `false`, leaving the node as is, if it has no usable extension
+/// or already has a union.
+fn expand_extension(obj: &mut Map<String, Value>) -> bool {
+    if obj.contains_key("oneOf") || obj.contains_key("anyOf") {
+        return false;
+    }
+    let Some(glosses) = extension_glosses(obj) else {
+        return false;
+    };
+    remove_extension(obj);
+    obj.remove("enum");
+    let variants = glosses
+        .into_iter()
+        .map(|(value, gloss)| match gloss {
+            Some(description) => json!({ "const": value, "description": description }),
+            None => json!({ "const": value }),
+        })
+        .collect();
+    obj.insert("oneOf".to_string(), Value::Array(variants));
+    true
+}
+
+/// Replace a `oneOf` or `anyOf` whose variants are all annotated `const`s
+/// with an `enum`, moving the variants' descriptions (or titles) into the
+/// node's `description`. A `type` every variant declares is kept.
+fn fold_union(obj: &mut Map<String, Value>, messages: &MessageCatalog) {
+    if obj.contains_key("enum") || obj.contains_key("const") {
+        return;
+    }
+    for keyword in ["oneOf", "anyOf"] {
+        let Some(variants) = obj.get(keyword).and_then(Value::as_array) else {
+            continue;
+        };
+        let Some(glosses) = variants
+            .iter()
+            .map(const_variant)
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        if glosses.is_empty() {
+            continue;
+        }
+
+        let types: Vec<Option<&Value>> = variants.iter().map(|v| v.get("type")).collect();
+        let common_type = match types.first() {
+            Some(Some(first)) if types.iter().all(|t| *t == Some(*first)) => Some((*first).clone()),
+            _ => None,
+        };
+        if let Some(common_type) = common_type {
+            obj.entry("type").or_insert(common_type);
+        }
+
+        obj.remove(keyword);
+        let values = glosses.iter().map(|(value, _)| value.clone()).collect();
+        obj.insert("enum".to_string(), Value::Array(values));
+        append_glosses(obj, &glosses, messages);
+        return;
+    }
+}
+
+/// The value and gloss of a variant holding only a `const` (or a
+/// one-value `enum`) and annotations.
+fn const_variant(variant: &Value) -> Option<Gloss> {
+    let obj = variant.as_object()?;
+    if !obj
+        .keys()
+        .all(|k| k == "const" || k == "enum" || VARIANT_ANNOTATIONS.contains(&k.as_str()))
+    {
+        return None;
+    }
+    let value = match (obj.get("const"), obj.get("enum")) {
+        (Some(value), None) => value.clone(),
+        (None, Some(Value::Array(values))) if values.len() == 1 => values[0].clone(),
+        _ => return None,
+    };
+    let gloss = obj
+        .get("description")
+        .or_else(|| obj.get("title"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    Some((value, gloss))
+}
+
+/// Append `Values: "a": gloss; ...` for every value that has a gloss.
+fn append_glosses(obj: &mut Map<String, Value>, glosses: &[Gloss], messages: &MessageCatalog) {
+    let entries: Vec<String> = glosses
+        .iter()
+        .filter_map(|(value, gloss)| {
+            let gloss = gloss.as_deref().filter(|g| !g.trim().is_empty())?;
+            Some(fill(
+                &messages.enum_value,
+                &[("value", value), ("description", &gloss)],
+            ))
+        })
+        .collect();
+    if entries.is_empty() {
+        return;
+    }
+    let list = entries.join(&messages.enum_value_separator);
+    append_to_description(obj, &fill(&messages.enum_values, &[("list", &list)]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts(style: EnumDescriptionStyle) -> ConvertOptions {
+        ConvertOptions {
+            enum_descriptions: style,
+            ..ConvertOptions::default()
+        }
+    }
+
+    fn run(schema: Value, style: EnumDescriptionStyle) -> Value {
+        let options = opts(style);
+        apply(schema, &options, &ResourceGuard::new(&options)).unwrap()
+    }
+
+    fn status() -> Value {
+        json!({
+            "type": "string",
+            "description": "Order status",
+            "enum": ["open", "closed"],
+            "x-enum-descriptions": ["Awaiting payment", "Shipped and paid"]
+        })
+    }
+
+    fn size() -> Value {
+        json!({
+            "oneOf": [
+                { "const": "s", "type": "string", "description": "Small" },
+                { "const": "l", "type": "string", "title": "Large" }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_auto_folds_extension_and_keeps_union() {
+        assert_eq!(
+            run(status(), EnumDescriptionStyle::Auto),
+            json!({
+                "type": "string",
+                "description": "Order status. Values: \"open\": Awaiting payment; \"closed\": Shipped and paid.",
+                "enum": ["open", "closed"]
+            })
+        );
+        assert_eq!(run(size(), EnumDescriptionStyle::Auto), size());
+    }
+
+    #[test]
+    fn test_fold_rewrites_union_as_enum() {
+        let result = run(size(), EnumDescriptionStyle::Fold);
+        assert_eq!(
+            result,
+            json!({
+                "type": "string",
+                "enum": ["s", "l"],
+                "description": "Values: \"s\": Small; \"l\": Large."
+            })
+        );
+    }
+
+    #[test]
+    fn test_fold_keeps_union_with_structural_variants() {
+        let schema = json!({
+            "oneOf": [
+                { "const": "s", "description": "Small" },
+                { "type": "object", "properties": { "kg": { "type": "number" } } }
+            ]
+        });
+        let result = run(schema.clone(), EnumDescriptionStyle::Fold);
+        assert_eq!(result, schema);
+    }
+
+    #[test]
+    fn test_one_of_expands_keyed_extension() {
+        let schema = json!({
+            "properties": {
+                "level": {
+                    "type": "integer",
+                    "enum": [1, 2, 3],
+                    "x-enumDescriptions": { "1": "Low", "3": "High" }
+                }
+            }
+        });
+        let result = run(schema, EnumDescriptionStyle::OneOf);
+        assert_eq!(
+            result["properties"]["level"],
+            json!({
+                "type": "integer",
+                "oneOf": [
+                    { "const": 1, "description": "Low" },
+                    { "const": 2 },
+                    { "const": 3, "description": "High" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_mismatched_extension_is_left_alone() {
+        let schema = json!({
+            "enum": ["a", "b"],
+            "x-enum-descriptions": ["Only one"]
+        });
+        let result = run(schema.clone(), EnumDescriptionStyle::Fold);
+        assert_eq!(result, schema);
+    }
+
+    #[test]
+    fn test_ignore_leaves_schema_untouched() {
+        let result = run(status(), EnumDescriptionStyle::Ignore);
+        assert_eq!(result, status());
+    }
+}
@@ -28,16 +28,28 @@ pub enum ErrorCode {
     CodecVersionMismatch,
     /// Schema violates a specific constraint of the target provider (e.g. strict mode).
     ProviderCompatFailure,
+    /// A configured resource guard (input size, node count, time budget) was exceeded.
+    ResourceLimitExceeded,
+    /// The conversion was aborted through a cancellation token.
+    Cancelled,
 }
 
-use crate::config::Target;
+use crate::config::{DeprecatedPolicy, ErrorPolicy, MergeConflictPolicy, Target};
+use crate::validation::meta_schema::SchemaIssue;
+use crate::validation::strict_mode::StrictModeViolation;
 use std::fmt;
 
 /// Provider compatibility failure — the schema violates a target provider's constraints.
 ///
 /// These are pre-flight checks: they detect issues the provider API would reject,
 /// before the API call is made.
+///
+/// Serialized with a `ruleId` field alongside `type`; see
+/// [`rule_id`](Self::rule_id) and the [`diagnostics`](crate::diagnostics) catalog.
+// `remote = "Self"` generates inherent (de)serialize fns that the manual
+// impls below wrap to add `ruleId`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum ProviderCompatError {
@@ -49,6 +61,7 @@ pub enum ProviderCompatError {
     },
     /// Schema nesting exceeds provider limit
     DepthBudgetExceeded {
+        path: String,
         actual_depth: usize,
         max_depth: usize,
         target: Target,
@@ -100,6 +113,92 @@ pub enum ProviderCompatError {
         target: Target,
         hint: String,
     },
+    /// `deprecated` property dropped or annotated per
+    /// `ConvertOptions::deprecated_properties`
+    DeprecatedProperty {
+        path: String,
+        action: DeprecatedPolicy,
+        target: Target,
+        hint: String,
+    },
+    /// Legacy-draft keyword with no 2020-12 equivalent removed in pass 0
+    LegacyKeyword {
+        path: String,
+        keyword: String,
+        target: Target,
+        hint: String,
+    },
+    /// `allOf` members disagreeing on a keyword, merged per
+    /// `ConvertOptions::merge_conflicts`
+    MergeConflict {
+        path: String,
+        keyword: String,
+        resolution: MergeConflictPolicy,
+        target: Target,
+        hint: String,
+    },
+    /// Key repeated within an object of the schema text; the last
+    /// occurrence was kept (`ConvertOptions::duplicate_keys`)
+    DuplicateSchemaKey {
+        path: String,
+        target: Target,
+        hint: String,
+    },
+    /// `$ref` to a missing location recovered per `ConvertOptions::on_error`
+    UnresolvableRefRecovered {
+        path: String,
+        reference: String,
+        action: ErrorPolicy,
+        target: Target,
+        hint: String,
+    },
+}
+
+impl Serialize for ProviderCompatError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct WithRuleId<'a> {
+            #[serde(flatten, with = "ProviderCompatError")]
+            inner: &'a ProviderCompatError,
+            #[serde(rename = "ruleId")]
+            rule_id: &'static str,
+        }
+        WithRuleId {
+            inner: self,
+            rule_id: self.rule_id(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ProviderCompatError {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // `ruleId` is derived from `type`; the tagged representation ignores it.
+        ProviderCompatError::deserialize(deserializer)
+    }
+}
+
+impl ProviderCompatError {
+    /// JSON Pointer to the sub-schema the diagnostic is about, in
+    /// post-conversion coordinates. Root-level diagnostics report `#`.
+    pub fn path(&self) -> &str {
+        match self {
+            ProviderCompatError::RootTypeIncompatible { .. } => "#",
+            ProviderCompatError::DepthBudgetExceeded { path, .. }
+            | ProviderCompatError::MixedEnumTypes { path, .. }
+            | ProviderCompatError::UnconstrainedSchema { path, .. }
+            | ProviderCompatError::PatternPropertiesStripped { path, .. }
+            | ProviderCompatError::PatternPropertiesStringified { path, .. }
+            | ProviderCompatError::RefKeywordStripped { path, .. }
+            | ProviderCompatError::BareRequiredStripped { path, .. }
+            | ProviderCompatError::TypeArrayConverted { path, .. }
+            | ProviderCompatError::DeprecatedProperty { path, .. }
+            | ProviderCompatError::LegacyKeyword { path, .. }
+            | ProviderCompatError::MergeConflict { path, .. }
+            | ProviderCompatError::DuplicateSchemaKey { path, .. }
+            | ProviderCompatError::UnresolvableRefRecovered { path, .. } => path,
+        }
+    }
 }
 
 impl fmt::Display for ProviderCompatError {
@@ -115,6 +214,7 @@ impl fmt::Display for ProviderCompatError {
                 actual_type, hint
             ),
             ProviderCompatError::DepthBudgetExceeded {
+                path: _,
                 actual_depth,
                 max_depth,
                 target: _,
@@ -183,17 +283,82 @@ impl fmt::Display for ProviderCompatError {
                 "type array {:?} converted to anyOf at '{}'. {}",
                 types, path, hint
             ),
+            ProviderCompatError::DeprecatedProperty {
+                path,
+                action,
+                target: _,
+                hint,
+            } => {
+                let action = match action {
+                    DeprecatedPolicy::Keep => "kept",
+                    DeprecatedPolicy::Drop => "dropped",
+                    DeprecatedPolicy::Annotate => "annotated",
+                };
+                write!(f, "deprecated property {} at '{}'. {}", action, path, hint)
+            }
+            ProviderCompatError::LegacyKeyword {
+                path,
+                keyword,
+                target: _,
+                hint,
+            } => write!(
+                f,
+                "unsupported legacy keyword '{}' at '{}'. {}",
+                keyword, path, hint
+            ),
+            ProviderCompatError::MergeConflict {
+                path,
+                keyword,
+                resolution: _,
+                target: _,
+                hint,
+            } => write!(
+                f,
+                "allOf members conflict on '{}' at '{}'. {}",
+                keyword, path, hint
+            ),
+            ProviderCompatError::DuplicateSchemaKey {
+                path,
+                target: _,
+                hint,
+            } => write!(f, "duplicate key at '{}'. {}", path, hint),
+            ProviderCompatError::UnresolvableRefRecovered {
+                path,
+                reference,
+                action,
+                target: _,
+                hint,
+            } => {
+                let action = match action {
+                    ErrorPolicy::SkipSubtree => "skipped",
+                    ErrorPolicy::Fail | ErrorPolicy::Placeholder => "replaced with a placeholder",
+                };
+                write!(
+                    f,
+                    "unresolvable $ref '{}' {} at '{}'. {}",
+                    reference, action, path, hint
+                )
+            }
         }
     }
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ConvertError {
     #[error("JSON (de)serialization error: {0}")]
     JsonError(#[from] serde_json::Error),
 
-    #[error("Schema error at {path}: {message}")]
-    SchemaError { path: String, message: String },
+    /// Build with [`ConvertError::schema_error`] outside this crate.
+    #[error("Schema error at {path}{}: {message}", fmt_location(.location))]
+    #[non_exhaustive]
+    SchemaError {
+        path: String,
+        message: String,
+        /// Position in the source text, when the schema was converted from
+        /// text via [`convert_str`](crate::convert_str).
+        location: Option<SourceLocation>,
+    },
 
     #[error("Recursion depth exceeded at {path} (max: {max_depth})")]
     RecursionDepthExceeded { path: String, max_depth: usize },
@@ -201,17 +366,78 @@ pub enum ConvertError {
     #[error("Unsupported schema feature at {path}: {feature}")]
     UnsupportedFeature { path: String, feature: String },
 
-    #[error("Unresolvable $ref at {path}: {reference}")]
-    UnresolvableRef { path: String, reference: String },
+    /// Build with [`ConvertError::unresolvable_ref`] outside this crate.
+    #[error("Unresolvable $ref at {path}{}: {reference}", fmt_location(.location))]
+    #[non_exhaustive]
+    UnresolvableRef {
+        path: String,
+        reference: String,
+        /// Position of the offending `$ref` in the source text, when the
+        /// schema was converted from text via [`convert_str`](crate::convert_str).
+        location: Option<SourceLocation>,
+    },
 
     #[error("Rehydration error: {0}")]
     RehydrationError(String),
 
     #[error("Codec version mismatch: found {found}, expected {expected}")]
     CodecVersionMismatch { found: String, expected: String },
+
+    #[error("Resource limit exceeded at {path}: {resource} (limit: {limit})")]
+    ResourceLimitExceeded {
+        path: String,
+        resource: String,
+        limit: u64,
+    },
+
+    #[error("Conversion cancelled at {path}")]
+    Cancelled { path: String },
+
+    #[error(
+        "Converted schema does not meet {} requirements ({} violation(s)): {}",
+        target_name(*.target),
+        .violations.len(),
+        fmt_violations(.violations)
+    )]
+    ProviderCompatFailure {
+        target: Target,
+        /// Every violation found by
+        /// [`validate_conformance`](crate::validate_conformance).
+        violations: Vec<StrictModeViolation>,
+    },
+
+    #[error(
+        "Schema is not valid JSON Schema ({} issue(s)): {}",
+        .issues.len(),
+        fmt_issues(.issues)
+    )]
+    InvalidSchema {
+        /// Every malformed keyword found by
+        /// [`validate_input`](crate::validate_input).
+        issues: Vec<SchemaIssue>,
+    },
 }
 
 impl ConvertError {
+    /// A [`SchemaError`](Self::SchemaError) at `path`, with no source location.
+    pub fn schema_error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        ConvertError::SchemaError {
+            path: path.into(),
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// An [`UnresolvableRef`](Self::UnresolvableRef) of `reference` at
+    /// `path`, with no source location.
+    pub fn unresolvable_ref(path: impl Into<String>, reference: impl Into<String>) -> Self {
+        ConvertError::UnresolvableRef {
+            path: path.into(),
+            reference: reference.into(),
+            location: None,
+        }
+    }
+
     /// Returns the stable error code for this error variant.
     pub fn error_code(&self) -> ErrorCode {
         match self {
@@ -222,6 +448,10 @@ impl ConvertError {
             ConvertError::UnresolvableRef { .. } => ErrorCode::UnresolvableRef,
             ConvertError::RehydrationError(_) => ErrorCode::RehydrationError,
             ConvertError::CodecVersionMismatch { .. } => ErrorCode::CodecVersionMismatch,
+            ConvertError::ResourceLimitExceeded { .. } => ErrorCode::ResourceLimitExceeded,
+            ConvertError::Cancelled { .. } => ErrorCode::Cancelled,
+            ConvertError::ProviderCompatFailure { .. } => ErrorCode::ProviderCompatFailure,
+            ConvertError::InvalidSchema { .. } => ErrorCode::SchemaError,
         }
     }
 
@@ -237,21 +467,106 @@ impl ConvertError {
             ConvertError::UnresolvableRef { path, .. } => Some(path),
             ConvertError::RehydrationError(_) => None,
             ConvertError::CodecVersionMismatch { .. } => None,
+            ConvertError::ResourceLimitExceeded { path, .. } => Some(path),
+            ConvertError::Cancelled { path } => Some(path),
+            ConvertError::ProviderCompatFailure { violations, .. } => {
+                violations.first().map(|v| v.path.as_str())
+            }
+            ConvertError::InvalidSchema { issues } => issues.first().map(|i| i.path.as_str()),
+        }
+    }
+
+    /// Source-text position of the error, if known.
+    ///
+    /// Only populated for `schema_error` and `unresolvable_ref` errors raised
+    /// by [`convert_str`](crate::convert_str) (or the `convert_json` bridge).
+    pub fn location(&self) -> Option<SourceLocation> {
+        match self {
+            ConvertError::SchemaError { location, .. }
+            | ConvertError::UnresolvableRef { location, .. } => *location,
+            _ => None,
+        }
+    }
+
+    /// Fill in [`location`](Self::location) by finding this error's path in
+    /// the JSON text the schema was parsed from.
+    ///
+    /// Paths are reported in post-`$ref`-expansion coordinates, so the
+    /// nearest enclosing location that exists in the text is used.
+    pub fn with_source_location(mut self, text: &str) -> Self {
+        match &mut self {
+            ConvertError::SchemaError { path, location, .. } => {
+                *location = crate::span::locate_pointer(text, path);
+            }
+            ConvertError::UnresolvableRef { path, location, .. } => {
+                let ref_site = crate::schema_utils::build_path(path, &["$ref"]);
+                *location = crate::span::locate_pointer_exact(text, &ref_site)
+                    .or_else(|| crate::span::locate_pointer(text, path));
+            }
+            _ => {}
         }
+        self
     }
 
     /// Produces a structured JSON error for FFI consumers.
     ///
-    /// Format: `{"code": "...", "message": "...", "path": "..." | null}`
+    /// Format: `{"code": "...", "message": "...", "path": "..." | null}`, plus
+    /// `"line"` and `"column"` when a [source location](Self::location) is known.
     pub fn to_json(&self) -> serde_json::Value {
-        serde_json::json!({
+        let mut json = serde_json::json!({
             "code": self.error_code(),
             "message": self.to_string(),
             "path": self.path(),
-        })
+        });
+        if let Some(loc) = self.location() {
+            json["line"] = loc.line.into();
+            json["column"] = loc.column.into();
+        }
+        json
+    }
+}
+
+/// A 1-based line/column position in schema source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+fn fmt_location(location: &Option<SourceLocation>) -> String {
+    location.map(|loc| format!(" ({loc})")).unwrap_or_default()
+}
+
+fn target_name(target: Target) -> &'static str {
+    match target {
+        Target::OpenaiStrict => "openai-strict",
+        Target::Gemini => "gemini",
+        Target::Claude => "claude",
     }
 }
 
+fn fmt_violations(violations: &[StrictModeViolation]) -> String {
+    violations
+        .iter()
+        .map(|v| v.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn fmt_issues(issues: &[SchemaIssue]) -> String {
+    issues
+        .iter()
+        .map(|i| format!("{}: {}", i.path, i.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 // ===========================================================================
 // Tests
 // ===========================================================================
@@ -294,6 +609,7 @@ mod tests {
     #[test]
     fn test_provider_compat_error_depth_budget_serializes() {
         let err = ProviderCompatError::DepthBudgetExceeded {
+            path: "#/properties/a/properties/b".to_string(),
             actual_depth: 15,
             max_depth: 10,
             target: Target::OpenaiStrict,
@@ -304,6 +620,7 @@ mod tests {
         assert_eq!(json["type"], json!("depth_budget_exceeded"));
         assert_eq!(json["actual_depth"], json!(15));
         assert_eq!(json["max_depth"], json!(10));
+        assert_eq!(json["path"], json!("#/properties/a/properties/b"));
     }
 
     #[test]
@@ -68,6 +68,30 @@ pub struct ExtractResult {
     pub missing_refs: Vec<String>,
 }
 
+/// A component together with what a picker needs to present it, as
+/// returned by [`describe_components`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentInfo {
+    /// JSON Pointer to the component.
+    pub pointer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Number of entries in the component's own `properties`.
+    pub property_count: usize,
+    /// Levels of nested properties / items below the component, without
+    /// following `$ref`s.
+    pub depth: usize,
+    /// Whether the component reaches itself through `$ref`s.
+    pub recursive: bool,
+    /// Pointers the component `$ref`s directly, sorted.
+    pub refs_out: Vec<String>,
+    /// Components that `$ref` this one directly, sorted.
+    pub refs_in: Vec<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -107,6 +131,125 @@ pub fn list_components(schema: &Value) -> Vec<String> {
     pointers
 }
 
+/// Enumerate components as [`list_components`] does, with each one's title,
+/// description, size and `$ref` relationships.
+///
+/// # Errors
+///
+/// Returns `ConvertError::SchemaError` for malformed schemas.
+pub fn describe_components(schema: &Value) -> Result<Vec<ComponentInfo>, ConvertError> {
+    let graph = DependencyGraph::build(schema)?;
+    let pointers = list_components(schema);
+    let refs_out = |pointer: &str| -> Vec<String> {
+        let Some(&id) = graph.pointer_to_id.get(pointer) else {
+            return Vec::new();
+        };
+        let mut targets: Vec<String> = graph.edges[id]
+            .iter()
+            .map(|&t| graph.pointers[t].clone())
+            .collect();
+        targets.sort();
+        targets.dedup();
+        targets
+    };
+    let outgoing: Vec<Vec<String>> = pointers.iter().map(|p| refs_out(p)).collect();
+
+    Ok(pointers
+        .iter()
+        .zip(&outgoing)
+        .map(|(pointer, refs_out)| {
+            let node = resolve_pointer(schema, pointer);
+            let text = |key: &str| {
+                node.and_then(|n| n.get(key))
+                    .and_then(Value::as_str)
+                    .map(String::from)
+            };
+            ComponentInfo {
+                pointer: pointer.clone(),
+                title: text("title"),
+                description: text("description"),
+                property_count: node
+                    .and_then(|n| n.get("properties"))
+                    .and_then(Value::as_object)
+                    .map_or(0, Map::len),
+                depth: node.map_or(0, nesting_depth),
+                recursive: graph
+                    .pointer_to_id
+                    .get(pointer)
+                    .is_some_and(|&id| graph.reaches(id, |t| t == id)),
+                refs_out: refs_out.clone(),
+                refs_in: pointers
+                    .iter()
+                    .zip(&outgoing)
+                    .filter(|(_, out)| out.contains(pointer))
+                    .map(|(source, _)| source.clone())
+                    .collect(),
+            }
+        })
+        .collect())
+}
+
+/// Components whose subtree references `pointer`, directly or through other
+/// definitions — the ones to reconvert when that definition changes.
+///
+/// Refs into the definition (e.g. `#/$defs/Money/properties/amount`) count
+/// too. The definition itself is only listed if it is recursive. Returns a
+/// sorted list of component pointers.
+///
+/// # Errors
+///
+/// Returns `ConvertError::SchemaError` for malformed schemas.
+pub fn find_components_referencing(
+    schema: &Value,
+    pointer: &str,
+) -> Result<Vec<String>, ConvertError> {
+    let graph = DependencyGraph::build(schema)?;
+    let is_target = |id: usize| {
+        let candidate = &graph.pointers[id];
+        candidate
+            .strip_prefix(pointer)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    };
+    Ok(list_components(schema)
+        .into_iter()
+        .filter(|component| {
+            graph
+                .pointer_to_id
+                .get(component)
+                .is_some_and(|&id| graph.reaches(id, is_target))
+        })
+        .collect())
+}
+
+/// Levels of `properties` / `items` nesting below `schema`. Composition
+/// keywords are looked through; `$ref`s are not followed.
+fn nesting_depth(schema: &Value) -> usize {
+    let Some(obj) = schema.as_object() else {
+        return 0;
+    };
+    let mut children: Vec<(&Value, usize)> = Vec::new();
+    for keyword in ["properties", "patternProperties"] {
+        if let Some(Value::Object(map)) = obj.get(keyword) {
+            children.extend(map.values().map(|v| (v, 1)));
+        }
+    }
+    for keyword in ["additionalProperties", "items"] {
+        if let Some(child) = obj.get(keyword).filter(|v| v.is_object()) {
+            children.push((child, 1));
+        }
+    }
+    for (keyword, step) in [("prefixItems", 1), ("anyOf", 0), ("oneOf", 0), ("allOf", 0)] {
+        if let Some(Value::Array(arr)) = obj.get(keyword) {
+            children.extend(arr.iter().map(|v| (v, step)));
+        }
+    }
+    children
+        .into_iter()
+        .map(|(child, step)| step + nesting_depth(child))
+        .max()
+        .unwrap_or(0)
+}
+
 /// Recursive helper for [`list_components`].
 ///
 /// Walks ALL object properties to discover `$defs`, `definitions`, and OAS
@@ -217,11 +360,25 @@ pub fn extract_component(
     schema: &Value,
     pointer: &str,
     options: &ExtractOptions,
+) -> Result<ExtractResult, ConvertError> {
+    let span = crate::telemetry::OpSpan::extract(schema, pointer);
+    let result = span.in_scope(|| extract_closure(schema, pointer, options));
+    span.record_extract(&result);
+    result
+}
+
+/// The resolution, dependency walk and tree-shaking behind
+/// [`extract_component`].
+fn extract_closure(
+    schema: &Value,
+    pointer: &str,
+    options: &ExtractOptions,
 ) -> Result<ExtractResult, ConvertError> {
     // Phase 1: Resolve the target pointer (hard error if missing).
     let target = resolve_pointer(schema, pointer).ok_or_else(|| ConvertError::UnresolvableRef {
         path: pointer.to_string(),
         reference: pointer.to_string(),
+        location: None,
     })?;
     let target = target.clone();
 
@@ -658,6 +815,7 @@ impl<'a> DependencyGraph<'a> {
                 return Err(ConvertError::UnresolvableRef {
                     path: pointer.to_string(),
                     reference: pointer.to_string(),
+                    location: None,
                 });
             }
         };
@@ -669,6 +827,7 @@ impl<'a> DependencyGraph<'a> {
                 .ok_or_else(|| ConvertError::UnresolvableRef {
                     path: pointer.to_string(),
                     reference: pointer.to_string(),
+                    location: None,
                 })?;
         let target = (*target).clone();
 
@@ -751,6 +910,22 @@ impl<'a> DependencyGraph<'a> {
         })
     }
 
+    /// Whether a node matching `is_target` is reachable from `from` through
+    /// at least one `$ref`.
+    fn reaches(&self, from: usize, is_target: impl Fn(usize) -> bool) -> bool {
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut stack = self.edges[from].clone();
+        while let Some(id) = stack.pop() {
+            if is_target(id) {
+                return true;
+            }
+            if visited.insert(id) {
+                stack.extend(&self.edges[id]);
+            }
+        }
+        false
+    }
+
     /// DFS over the adjacency list to compute the transitive closure for a root.
     fn closure_dfs(
         &self,
@@ -1442,6 +1617,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_components_referencing_is_transitive() {
+        let schema = json!({
+            "$defs": {
+                "Money": {
+                    "type": "object",
+                    "properties": { "amount": { "type": "number" } }
+                },
+                "Price": { "$ref": "#/$defs/Money" },
+                "Order": {
+                    "type": "object",
+                    "properties": { "total": { "$ref": "#/$defs/Price" } }
+                },
+                "Refund": {
+                    "type": "object",
+                    "properties": { "amount": { "$ref": "#/$defs/Money/properties/amount" } }
+                },
+                "Tag": { "type": "string" }
+            }
+        });
+        let found = find_components_referencing(&schema, "#/$defs/Money").unwrap();
+        assert_eq!(found, ["#/$defs/Order", "#/$defs/Price", "#/$defs/Refund"]);
+
+        // Prefix matches stop at segment boundaries.
+        let none = find_components_referencing(&schema, "#/$defs/Mon").unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_describe_components_reports_stats_and_refs() {
+        let schema = json!({
+            "$defs": {
+                "Pet": {
+                    "title": "Pet",
+                    "description": "A pet.",
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "tags": { "type": "array", "items": { "$ref": "#/$defs/Tag" } }
+                    }
+                },
+                "Tag": { "type": "object", "properties": { "label": { "type": "string" } } },
+                "Node": {
+                    "type": "object",
+                    "properties": { "next": { "$ref": "#/$defs/Node" } }
+                }
+            }
+        });
+        let info = describe_components(&schema).unwrap();
+        let pointers: Vec<&str> = info.iter().map(|c| c.pointer.as_str()).collect();
+        assert_eq!(pointers, list_components(&schema));
+
+        let pet = &info[1];
+        assert_eq!(pet.title.as_deref(), Some("Pet"));
+        assert_eq!(pet.description.as_deref(), Some("A pet."));
+        assert_eq!(pet.property_count, 2);
+        assert_eq!(pet.depth, 2);
+        assert!(!pet.recursive);
+        assert_eq!(pet.refs_out, vec!["#/$defs/Tag"]);
+        assert!(pet.refs_in.is_empty());
+
+        let tag = &info[2];
+        assert_eq!(tag.refs_in, vec!["#/$defs/Pet"]);
+        assert!(tag.title.is_none());
+
+        let node = &info[0];
+        assert!(node.recursive);
+        assert_eq!(node.refs_in, vec!["#/$defs/Node"]);
+    }
+
     #[test]
     fn test_extracted_passes_convert() {
         let schema = json!({
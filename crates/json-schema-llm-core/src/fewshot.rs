@@ -0,0 +1,140 @@
+//! Few-shot examples in the converted shape.
+//!
+//! Prompts work better with a few worked examples, but real data is shaped
+//! for the original schema, while the model answers in the converted one.
+//! [`make_fewshot`] converts the schema and runs each original instance
+//! forward through the codec (see [`crate::dehydrator`]): maps become entry
+//! arrays, missing optional properties become `null`, and so on.
+//! Rehydrating an example gives back the instance it came from.
+//!
+//! ```rust,no_run
+//! use json_schema_llm_core::{make_fewshot, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "name": { "type": "string" },
+//!         "tags": { "type": "object", "additionalProperties": { "type": "string" } }
+//!     },
+//!     "required": ["name"]
+//! });
+//! let examples = make_fewshot(
+//!     &schema,
+//!     &[json!({ "name": "Ada", "tags": { "role": "admin" } })],
+//!     &ConvertOptions::default(),
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     examples[0],
+//!     json!({ "name": "Ada", "tags": [{ "key": "role", "value": "admin" }] })
+//! );
+//! ```
+
+use serde_json::Value;
+
+use crate::config::ConvertOptions;
+use crate::dehydrator::Dehydrator;
+use crate::error::ConvertError;
+use crate::ConvertResult;
+
+/// Convert `schema` and reshape each of `original_instances` into an
+/// answer to the converted schema, for use as a few-shot example.
+///
+/// # Errors
+///
+/// Whatever [`convert`](crate::convert) returns for `schema`, or a
+/// [`ConvertError::SchemaError`] if an instance can't be serialized into a
+/// JSON-string field.
+pub fn make_fewshot(
+    schema: &Value,
+    original_instances: &[Value],
+    options: &ConvertOptions,
+) -> Result<Vec<Value>, ConvertError> {
+    let result = crate::convert(schema, options)?;
+    fewshot_from_result(&result, original_instances)
+}
+
+/// [`make_fewshot`] for a schema already converted into `result`.
+pub fn fewshot_from_result(
+    result: &ConvertResult,
+    original_instances: &[Value],
+) -> Result<Vec<Value>, ConvertError> {
+    let mut dehydrator = Dehydrator::new(&result.codec, Some(&result.schema));
+    original_instances
+        .iter()
+        .map(|instance| dehydrator.apply(instance))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Rehydrating each example gives back its instance.
+    fn assert_round_trip(schema: &Value, instance: Value, options: &ConvertOptions) -> Value {
+        let result = crate::convert(schema, options).unwrap();
+        let examples = fewshot_from_result(&result, std::slice::from_ref(&instance)).unwrap();
+        let restored = crate::rehydrate(&examples[0], &result.codec, schema).unwrap();
+        assert_eq!(restored.data, instance);
+        examples[0].clone()
+    }
+
+    #[test]
+    fn test_fewshot_map_and_nullable() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "nickname": { "type": "string" },
+                "scores": {
+                    "type": "object",
+                    "additionalProperties": { "anyOf": [{ "type": "integer" }, { "type": "string" }] }
+                }
+            },
+            "required": ["name", "scores"]
+        });
+        let example = assert_round_trip(
+            &schema,
+            json!({ "name": "Ada", "scores": { "math": 9, "art": "A" } }),
+            &ConvertOptions::default(),
+        );
+        assert_eq!(
+            example,
+            json!({
+                "name": "Ada",
+                "nickname": null,
+                "scores": [
+                    { "key": "art", "variant": 1, "value": "A" },
+                    { "key": "math", "variant": 0, "value": 9 }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_fewshot_root_wrapper_and_enum() {
+        let schema = json!({
+            "type": "array",
+            "items": { "enum": [1, 2, "three"] }
+        });
+        let example = assert_round_trip(&schema, json!([2, "three"]), &ConvertOptions::default());
+        assert_eq!(example, json!({ "result": ["2", "three"] }));
+    }
+
+    #[test]
+    fn test_fewshot_opaque_json_string() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "meta": {} },
+            "required": ["meta"]
+        });
+        let example = assert_round_trip(
+            &schema,
+            json!({ "meta": { "any": [1, true] } }),
+            &ConvertOptions::default(),
+        );
+        assert!(example["meta"].is_string());
+    }
+}
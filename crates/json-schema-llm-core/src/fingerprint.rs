@@ -0,0 +1,166 @@
+//! Content fingerprints of a schema and the options it is converted with.
+//!
+//! [`schema_fingerprint`] hashes a canonical form of the schema together
+//! with the options that shape the conversion, so two inputs that convert
+//! the same way get the same fingerprint however their keys are ordered or
+//! their numbers are written (`1` and `1.0`). Use it to key caches of
+//! conversions, to tell whether a stored codec still matches a schema, or
+//! to stamp manifests.
+//!
+//! ```rust
+//! use json_schema_llm_core::{schema_fingerprint, ConvertOptions, Target};
+//! use serde_json::json;
+//!
+//! let options = ConvertOptions::default();
+//! let a = schema_fingerprint(&json!({"type": "object", "minProperties": 1}), &options);
+//! let b = schema_fingerprint(&json!({"minProperties": 1.0, "type": "object"}), &options);
+//! assert_eq!(a, b);
+//! assert!(a.starts_with("sha256:"));
+//!
+//! let mut gemini = ConvertOptions::default();
+//! gemini.target = Target::Gemini;
+//! assert_ne!(a, schema_fingerprint(&json!({"type": "object", "minProperties": 1}), &gemini));
+//! ```
+
+use std::fmt::Write;
+
+use serde_json::{Map, Number, Value};
+use sha2::{Digest, Sha256};
+
+use crate::canonical::integral;
+use crate::codec::CODEC_MAJOR_VERSION;
+use crate::config::ConvertOptions;
+
+/// Prefix naming the hash, so the scheme can change without ambiguity.
+pub const FINGERPRINT_PREFIX: &str = "sha256:";
+
+/// Options that bound or report on a conversion without changing its
+/// output, left out of the fingerprint.
+const OPERATIONAL_OPTIONS: &[&str] = &[
+    "locale",
+    "max-nodes",
+    "max-schema-bytes",
+    "message-catalog",
+    "skip-components",
+    "suppress",
+    "time-budget-ms",
+    "validate-input",
+    "verify-output",
+];
+
+/// Fingerprint `schema` as converted with `options`: `sha256:` followed by
+/// 64 lowercase hex digits.
+///
+/// The hash covers the canonical schema, every option that affects the
+/// converted schema or codec, and the codec major version. Options set only
+/// programmatically (custom passes, property-order functions, progress and
+/// cancellation) are not covered; neither is source key order, which only
+/// matters to [`convert_str`](crate::convert_str) with
+/// [`PropertyOrdering::Original`](crate::PropertyOrdering::Original).
+pub fn schema_fingerprint(schema: &Value, options: &ConvertOptions) -> String {
+    let mut options = serde_json::to_value(options).unwrap_or(Value::Null);
+    if let Value::Object(fields) = &mut options {
+        fields.retain(|key, _| !OPERATIONAL_OPTIONS.contains(&key.as_str()));
+    }
+
+    let mut canonical = String::new();
+    canonical.push_str("{\"codec\":");
+    canonical.push_str(&CODEC_MAJOR_VERSION.to_string());
+    canonical.push_str(",\"options\":");
+    write_canonical(&options, &mut canonical);
+    canonical.push_str(",\"schema\":");
+    write_canonical(schema, &mut canonical);
+    canonical.push('}');
+
+    let digest = Sha256::digest(canonical.as_bytes());
+    let mut fingerprint = String::with_capacity(FINGERPRINT_PREFIX.len() + 64);
+    fingerprint.push_str(FINGERPRINT_PREFIX);
+    for byte in digest.iter() {
+        let _ = write!(fingerprint, "{:02x}", byte);
+    }
+    fingerprint
+}
+
+/// Compact JSON with object keys sorted and integral floats written as
+/// integers, independent of `serde_json`'s `preserve_order` feature.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => write_object(map, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Number(n) => out.push_str(&canonical_number(n)),
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+fn write_object(map: &Map<String, Value>, out: &mut String) {
+    let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    out.push('{');
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&Value::String(key.clone()).to_string());
+        out.push(':');
+        write_canonical(value, out);
+    }
+    out.push('}');
+}
+
+fn canonical_number(n: &Number) -> String {
+    integral(n.clone()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Mode;
+    use serde_json::json;
+
+    #[test]
+    fn test_fingerprint_ignores_key_order_and_number_form() {
+        let options = ConvertOptions::default();
+        let a = json!({
+            "type": "object",
+            "properties": { "n": { "type": "number", "maximum": 10 }, "s": { "type": "string" } }
+        });
+        let b = json!({
+            "properties": { "s": { "type": "string" }, "n": { "maximum": 10.0, "type": "number" } },
+            "type": "object"
+        });
+        let fingerprint = schema_fingerprint(&a, &options);
+        assert_eq!(fingerprint, schema_fingerprint(&b, &options));
+        assert_eq!(fingerprint.len(), FINGERPRINT_PREFIX.len() + 64);
+
+        let c = json!({ "type": "object", "properties": { "s": { "type": "integer" } } });
+        assert_ne!(fingerprint, schema_fingerprint(&c, &options));
+    }
+
+    #[test]
+    fn test_fingerprint_covers_output_options_only() {
+        let schema = json!({ "type": "object", "properties": { "s": { "type": "string" } } });
+        let base = schema_fingerprint(&schema, &ConvertOptions::default());
+
+        let permissive = ConvertOptions {
+            mode: Mode::Permissive,
+            ..ConvertOptions::default()
+        };
+        assert_ne!(base, schema_fingerprint(&schema, &permissive));
+
+        let budgeted = ConvertOptions {
+            time_budget_ms: Some(50),
+            skip_components: true,
+            ..ConvertOptions::default()
+        };
+        assert_eq!(base, schema_fingerprint(&schema, &budgeted));
+    }
+}
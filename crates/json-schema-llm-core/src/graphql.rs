@@ -0,0 +1,590 @@
+//! GraphQL SDL input (feature `graphql`).
+//!
+//! Turns a named type of a GraphQL schema into a JSON Schema, then runs it
+//! through [`convert`](crate::convert). Rehydrated output has the shape of
+//! a GraphQL response (or input value) for that type.
+//!
+//! ```rust
+//! use json_schema_llm_core::{graphql, ConvertOptions};
+//!
+//! let sdl = r#"
+//!     type Order {
+//!         id: ID!
+//!         "Line items, in display order."
+//!         lines: [Line!]!
+//!         status: Status
+//!     }
+//!     type Line { sku: String!, qty: Int! }
+//!     enum Status { OPEN, PAID }
+//! "#;
+//! let schema = graphql::type_schema(sdl, "Order").unwrap();
+//! assert_eq!(schema["required"], serde_json::json!(["id", "lines"]));
+//!
+//! let result = graphql::convert_type(sdl, "Order", &ConvertOptions::default()).unwrap();
+//! assert_eq!(result.schema["properties"]["status"]["anyOf"][0]["enum"][1], "PAID");
+//! ```
+//!
+//! ## Mapping
+//!
+//! | GraphQL                        | JSON Schema                                           |
+//! | ------------------------------ | ----------------------------------------------------- |
+//! | object / input / interface     | closed object in `$defs`, referenced by `$ref`        |
+//! | `T!`                           | listed in `required`                                  |
+//! | `[T]`                          | array; nullable items are `anyOf: [T, null]`          |
+//! | union, interface as field type | `anyOf` of the object types                           |
+//! | enum                           | string enum                                           |
+//! | `Int` / `Float` / `Boolean`    | `integer` / `number` / `boolean`                      |
+//! | `String` / `ID` / custom       | `string` (common custom scalars get a `format`)       |
+//! | description / `@deprecated`    | `description` / `deprecated: true`                    |
+//! | input field default            | `default`                                             |
+//!
+//! Object types that belong to a union or implement an interface get a
+//! required `__typename` property, so rehydrated values can be told apart
+//! the way a GraphQL client would. Field arguments are ignored; `extend`
+//! definitions are merged into their types.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use graphql_parser::query::Type;
+use graphql_parser::schema::{
+    Definition, Directive, EnumValue, Field, InputValue, TypeDefinition, TypeExtension,
+    Value as GqlValue,
+};
+use graphql_parser::Pos;
+use serde_json::{json, Map, Value};
+
+use crate::config::ConvertOptions;
+use crate::error::{ConvertError, SourceLocation};
+use crate::ConvertResult;
+
+/// JSON Schema for type `type_name` of the GraphQL schema `sdl`.
+pub fn type_schema(sdl: &str, type_name: &str) -> Result<Value, ConvertError> {
+    let document =
+        graphql_parser::parse_schema::<&str>(sdl).map_err(|e| ConvertError::SchemaError {
+            path: "#".to_string(),
+            message: format!("invalid GraphQL schema: {}", e),
+            location: None,
+        })?;
+    let index = Index::new(&document.definitions);
+    let root = index
+        .types
+        .get(type_name)
+        .ok_or_else(|| ConvertError::SchemaError {
+            path: "#".to_string(),
+            message: format!("type `{}` not found in GraphQL schema", type_name),
+            location: None,
+        })?;
+    if matches!(root.kind, Kind::Enum(_) | Kind::Scalar) {
+        return Err(ConvertError::SchemaError {
+            path: "#".to_string(),
+            message: format!(
+                "type `{}` is not an object, input, interface or union",
+                type_name
+            ),
+            location: Some(location(root.position)),
+        });
+    }
+
+    let mut builder = SchemaBuilder {
+        index: &index,
+        defs: Map::new(),
+        queued: HashSet::new(),
+        pending: Vec::new(),
+    };
+    let mut schema = builder.definition(type_name)?;
+    while let Some(name) = builder.pending.pop() {
+        let def = builder.definition(name)?;
+        builder.defs.insert(name.to_string(), def);
+    }
+
+    let obj = schema
+        .as_object_mut()
+        .expect("invariant: type schemas are objects");
+    obj.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    obj.insert("title".to_string(), json!(type_name));
+    if !builder.defs.is_empty() {
+        obj.insert("$defs".to_string(), Value::Object(builder.defs));
+    }
+    Ok(schema)
+}
+
+/// [`type_schema`] followed by [`convert`](crate::convert).
+pub fn convert_type(
+    sdl: &str,
+    type_name: &str,
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    crate::convert(&type_schema(sdl, type_name)?, options)
+}
+
+type Doc<'a> = &'a str;
+
+/// A named type with its extensions merged in.
+struct TypeDef<'a> {
+    position: Pos,
+    description: Option<&'a str>,
+    deprecated: bool,
+    kind: Kind<'a>,
+}
+
+enum Kind<'a> {
+    Object(Vec<&'a Field<'a, Doc<'a>>>),
+    Interface(Vec<&'a Field<'a, Doc<'a>>>),
+    Input(Vec<&'a InputValue<'a, Doc<'a>>>),
+    Union(Vec<&'a str>),
+    Enum(Vec<&'a EnumValue<'a, Doc<'a>>>),
+    Scalar,
+}
+
+struct Index<'a> {
+    types: HashMap<&'a str, TypeDef<'a>>,
+    /// Object types implementing each interface, in definition order.
+    implementors: HashMap<&'a str, Vec<&'a str>>,
+    /// Object types that need a `__typename` discriminator.
+    discriminated: HashSet<&'a str>,
+}
+
+impl<'a> Index<'a> {
+    fn new(definitions: &'a [Definition<'a, Doc<'a>>]) -> Self {
+        let mut types = HashMap::new();
+        let mut implementors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for def in definitions {
+            let Definition::TypeDefinition(def) = def else {
+                continue;
+            };
+            let (name, position, description, directives, kind) = match def {
+                TypeDefinition::Object(t) => {
+                    for interface in &t.implements_interfaces {
+                        implementors.entry(*interface).or_default().push(t.name);
+                    }
+                    let kind = Kind::Object(t.fields.iter().collect());
+                    (t.name, t.position, &t.description, &t.directives, kind)
+                }
+                TypeDefinition::Interface(t) => {
+                    let kind = Kind::Interface(t.fields.iter().collect());
+                    (t.name, t.position, &t.description, &t.directives, kind)
+                }
+                TypeDefinition::InputObject(t) => {
+                    let kind = Kind::Input(t.fields.iter().collect());
+                    (t.name, t.position, &t.description, &t.directives, kind)
+                }
+                TypeDefinition::Union(t) => {
+                    let kind = Kind::Union(t.types.clone());
+                    (t.name, t.position, &t.description, &t.directives, kind)
+                }
+                TypeDefinition::Enum(t) => {
+                    let kind = Kind::Enum(t.values.iter().collect());
+                    (t.name, t.position, &t.description, &t.directives, kind)
+                }
+                TypeDefinition::Scalar(t) => (
+                    t.name,
+                    t.position,
+                    &t.description,
+                    &t.directives,
+                    Kind::Scalar,
+                ),
+            };
+            types.insert(
+                name,
+                TypeDef {
+                    position,
+                    description: description.as_deref(),
+                    deprecated: is_deprecated(directives),
+                    kind,
+                },
+            );
+        }
+
+        for def in definitions {
+            let Definition::TypeExtension(ext) = def else {
+                continue;
+            };
+            match ext {
+                TypeExtension::Object(ext) => {
+                    for interface in &ext.implements_interfaces {
+                        implementors.entry(*interface).or_default().push(ext.name);
+                    }
+                    if let Some(TypeDef {
+                        kind: Kind::Object(fields),
+                        ..
+                    }) = types.get_mut(ext.name)
+                    {
+                        fields.extend(&ext.fields);
+                    }
+                }
+                TypeExtension::Interface(ext) => {
+                    if let Some(TypeDef {
+                        kind: Kind::Interface(fields),
+                        ..
+                    }) = types.get_mut(ext.name)
+                    {
+                        fields.extend(&ext.fields);
+                    }
+                }
+                TypeExtension::InputObject(ext) => {
+                    if let Some(TypeDef {
+                        kind: Kind::Input(fields),
+                        ..
+                    }) = types.get_mut(ext.name)
+                    {
+                        fields.extend(&ext.fields);
+                    }
+                }
+                TypeExtension::Union(ext) => {
+                    if let Some(TypeDef {
+                        kind: Kind::Union(members),
+                        ..
+                    }) = types.get_mut(ext.name)
+                    {
+                        members.extend(&ext.types);
+                    }
+                }
+                TypeExtension::Enum(ext) => {
+                    if let Some(TypeDef {
+                        kind: Kind::Enum(values),
+                        ..
+                    }) = types.get_mut(ext.name)
+                    {
+                        values.extend(&ext.values);
+                    }
+                }
+                TypeExtension::Scalar(_) => {}
+            }
+        }
+
+        let discriminated = types
+            .values()
+            .filter_map(|t| match &t.kind {
+                Kind::Union(members) => Some(members.clone()),
+                _ => None,
+            })
+            .flatten()
+            .chain(implementors.values().flatten().copied())
+            .collect();
+        Index {
+            types,
+            implementors,
+            discriminated,
+        }
+    }
+}
+
+struct SchemaBuilder<'a> {
+    index: &'a Index<'a>,
+    defs: Map<String, Value>,
+    /// Types referenced so far, built or waiting in `pending`.
+    queued: HashSet<&'a str>,
+    pending: Vec<&'a str>,
+}
+
+impl<'a> SchemaBuilder<'a> {
+    /// Schema of an object, input, interface or union type.
+    fn definition(&mut self, name: &'a str) -> Result<Value, ConvertError> {
+        let def = &self.index.types[name];
+        let mut schema = match &def.kind {
+            Kind::Object(fields) | Kind::Interface(fields) => {
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                if self.index.discriminated.contains(name) {
+                    properties.insert("__typename".to_string(), json!({ "enum": [name] }));
+                    required.push(json!("__typename"));
+                }
+                for field in fields {
+                    let (schema, non_null) = self.field_type(&field.field_type, field.position)?;
+                    let schema = annotate(
+                        schema,
+                        field.description.as_deref(),
+                        is_deprecated(&field.directives),
+                    );
+                    if non_null {
+                        required.push(json!(field.name));
+                    }
+                    properties.insert(field.name.to_string(), schema);
+                }
+                object_schema(properties, required)
+            }
+            Kind::Input(fields) => {
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for field in fields {
+                    let (mut schema, non_null) =
+                        self.field_type(&field.value_type, field.position)?;
+                    if let Some(default) = &field.default_value {
+                        schema["default"] = to_json(default);
+                    } else if non_null {
+                        required.push(json!(field.name));
+                    }
+                    let schema = annotate(
+                        schema,
+                        field.description.as_deref(),
+                        is_deprecated(&field.directives),
+                    );
+                    properties.insert(field.name.to_string(), schema);
+                }
+                object_schema(properties, required)
+            }
+            Kind::Union(members) => {
+                let branches = members
+                    .iter()
+                    .map(|member| self.named_type(member, def.position))
+                    .collect::<Result<Vec<_>, _>>()?;
+                json!({ "anyOf": branches })
+            }
+            Kind::Enum(_) | Kind::Scalar => {
+                unreachable!("enums and scalars are inlined, never defined")
+            }
+        };
+        if let Some(text) = def.description {
+            schema["description"] = json!(text);
+        }
+        Ok(schema)
+    }
+
+    /// Schema for a field's type, and whether it is non-null.
+    fn field_type(
+        &mut self,
+        ty: &Type<'a, Doc<'a>>,
+        at: Pos,
+    ) -> Result<(Value, bool), ConvertError> {
+        Ok(match ty {
+            Type::NonNullType(inner) => (self.field_type(inner, at)?.0, true),
+            Type::ListType(inner) => {
+                let (items, non_null) = self.field_type(inner, at)?;
+                let items = if non_null {
+                    items
+                } else {
+                    json!({ "anyOf": [items, { "type": "null" }] })
+                };
+                (json!({ "type": "array", "items": items }), false)
+            }
+            Type::NamedType(name) => (self.named_type(name, at)?, false),
+        })
+    }
+
+    fn named_type(&mut self, name: &'a str, at: Pos) -> Result<Value, ConvertError> {
+        match name {
+            "Int" => return Ok(json!({ "type": "integer" })),
+            "Float" => return Ok(json!({ "type": "number" })),
+            "Boolean" => return Ok(json!({ "type": "boolean" })),
+            "String" | "ID" => return Ok(json!({ "type": "string" })),
+            _ => {}
+        }
+        let def = self
+            .index
+            .types
+            .get(name)
+            .ok_or_else(|| ConvertError::SchemaError {
+                path: "#".to_string(),
+                message: format!("unknown GraphQL type `{}`", name),
+                location: Some(location(at)),
+            })?;
+        match &def.kind {
+            Kind::Scalar => Ok(annotate(
+                custom_scalar(name),
+                def.description,
+                def.deprecated,
+            )),
+            Kind::Enum(values) => {
+                let names: Vec<&str> = values.iter().map(|v| v.name).collect();
+                let schema = json!({ "type": "string", "enum": names });
+                Ok(annotate(schema, def.description, def.deprecated))
+            }
+            // Only implementations can appear in a response.
+            Kind::Interface(_) if self.index.implementors.contains_key(name) => {
+                let branches = self.index.implementors[name]
+                    .iter()
+                    .map(|member| self.named_type(member, at))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(json!({ "anyOf": branches }))
+            }
+            Kind::Object(_) | Kind::Interface(_) | Kind::Input(_) | Kind::Union(_) => {
+                if self.queued.insert(name) {
+                    self.pending.push(name);
+                }
+                Ok(json!({ "$ref": crate::schema_utils::build_path("#", &["$defs", name]) }))
+            }
+        }
+    }
+}
+
+fn object_schema(properties: Map<String, Value>, required: Vec<Value>) -> Value {
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// Commonly used custom scalars; anything else is a plain string.
+fn custom_scalar(name: &str) -> Value {
+    match name {
+        "DateTime" | "Timestamp" => json!({ "type": "string", "format": "date-time" }),
+        "Date" => json!({ "type": "string", "format": "date" }),
+        "Time" => json!({ "type": "string", "format": "time" }),
+        "UUID" => json!({ "type": "string", "format": "uuid" }),
+        "URL" | "URI" | "Url" => json!({ "type": "string", "format": "uri" }),
+        "Email" | "EmailAddress" => json!({ "type": "string", "format": "email" }),
+        "BigInt" | "Long" => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+        "JSON" | "Json" => json!({}),
+        "JSONObject" => json!({ "type": "object" }),
+        _ => json!({ "type": "string" }),
+    }
+}
+
+/// Add a description and `deprecated: true` to `schema`.
+fn annotate(mut schema: Value, description: Option<&str>, deprecated: bool) -> Value {
+    if let Some(text) = description {
+        schema["description"] = json!(text);
+    }
+    if deprecated {
+        schema["deprecated"] = json!(true);
+    }
+    schema
+}
+
+fn is_deprecated<'a>(directives: &[Directive<'a, Doc<'a>>]) -> bool {
+    directives.iter().any(|d| d.name == "deprecated")
+}
+
+fn to_json<'a>(value: &GqlValue<'a, Doc<'a>>) -> Value {
+    match value {
+        GqlValue::Int(n) => n.as_i64().map_or(Value::Null, Value::from),
+        GqlValue::Float(f) => json!(f),
+        GqlValue::String(s) => json!(s),
+        GqlValue::Boolean(b) => json!(b),
+        GqlValue::Enum(name) => json!(name),
+        GqlValue::List(items) => Value::Array(items.iter().map(to_json).collect()),
+        GqlValue::Object(fields) => {
+            let fields: BTreeMap<_, _> = fields.iter().map(|(k, v)| (*k, to_json(v))).collect();
+            json!(fields)
+        }
+        GqlValue::Null | GqlValue::Variable(_) => Value::Null,
+    }
+}
+
+fn location(pos: Pos) -> SourceLocation {
+    SourceLocation {
+        line: pos.line,
+        column: pos.column,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SDL: &str = r#"
+        "A customer order."
+        type Order implements Node {
+            id: ID!
+            placedAt: DateTime
+            tags: [String]!
+            status: Status!
+            payment: Payment
+            owner: Node
+            parent: Order
+            legacyCode: String @deprecated(reason: "Use id")
+        }
+
+        interface Node { id: ID! }
+
+        type Card implements Node { id: ID!, last4: String! }
+        type Invoice { number: Int! }
+        union Payment = Card | Invoice
+
+        enum Status { OPEN, PAID }
+        scalar DateTime
+
+        input OrderFilter {
+            status: Status = OPEN
+            limit: Int!
+        }
+
+        extend type Order { note: String }
+    "#;
+
+    #[test]
+    fn test_type_schema_maps_object_type() {
+        let schema = type_schema(SDL, "Order").unwrap();
+        assert_eq!(schema["title"], "Order");
+        assert_eq!(schema["description"], "A customer order.");
+        assert_eq!(
+            schema["required"],
+            json!(["__typename", "id", "tags", "status"])
+        );
+        let props = &schema["properties"];
+        assert_eq!(props["__typename"], json!({"enum": ["Order"]}));
+        assert_eq!(props["placedAt"]["format"], "date-time");
+        assert_eq!(
+            props["tags"],
+            json!({"type": "array", "items": {"anyOf": [{"type": "string"}, {"type": "null"}]}})
+        );
+        assert_eq!(
+            props["status"],
+            json!({"type": "string", "enum": ["OPEN", "PAID"]})
+        );
+        assert_eq!(props["payment"], json!({"$ref": "#/$defs/Payment"}));
+        assert_eq!(
+            props["owner"],
+            json!({"anyOf": [{"$ref": "#/$defs/Order"}, {"$ref": "#/$defs/Card"}]})
+        );
+        assert_eq!(props["legacyCode"]["deprecated"], true);
+        assert_eq!(props["note"]["type"], "string");
+
+        let defs = &schema["$defs"];
+        assert_eq!(
+            defs["Payment"]["anyOf"],
+            json!([{"$ref": "#/$defs/Card"}, {"$ref": "#/$defs/Invoice"}])
+        );
+        assert_eq!(
+            defs["Invoice"]["properties"]["__typename"]["enum"][0],
+            "Invoice"
+        );
+        assert_eq!(defs["Card"]["additionalProperties"], false);
+    }
+
+    #[test]
+    fn test_input_defaults_are_not_required() {
+        let schema = type_schema(SDL, "OrderFilter").unwrap();
+        assert_eq!(schema["required"], json!(["limit"]));
+        assert_eq!(schema["properties"]["status"]["default"], "OPEN");
+        assert!(schema["properties"].get("__typename").is_none());
+    }
+
+    #[test]
+    fn test_convert_type_round_trip() {
+        let result = convert_type(SDL, "Invoice", &ConvertOptions::default()).unwrap();
+        let schema = type_schema(SDL, "Invoice").unwrap();
+        let output = json!({"__typename": "Invoice", "number": 7});
+        let rehydrated = crate::rehydrate(&output, &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data, output);
+        assert!(rehydrated.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = type_schema(SDL, "Missing").unwrap_err();
+        assert!(err.to_string().contains("`Missing` not found"), "{err}");
+
+        let err = type_schema(SDL, "Status").unwrap_err();
+        assert!(err.to_string().contains("not an object"), "{err}");
+
+        let err = type_schema("type A { b: Nope }", "A").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown GraphQL type `Nope`"),
+            "{err}"
+        );
+        assert_eq!(err.location().map(|l| l.line), Some(1));
+
+        let err = type_schema("type {", "A").unwrap_err();
+        assert!(err.to_string().contains("invalid GraphQL schema"), "{err}");
+    }
+}
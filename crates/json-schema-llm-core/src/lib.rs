@@ -21,34 +21,132 @@
 //! println!("Converted: {}", serde_json::to_string_pretty(&result.schema).unwrap());
 //! println!("Codec: {}", serde_json::to_string_pretty(&result.codec).unwrap());
 //! ```
-
+//!
+//! ## Cargo features
+//!
+//! The default features can be turned off for a smaller build, e.g. for
+//! embedded or edge WebAssembly runtimes:
+//!
+//! | Feature   | Default | Without it                                              |
+//! |-----------|---------|---------------------------------------------------------|
+//! | `regex`   | yes     | Dropped `pattern` constraints are reported as `constraint_unevaluable` warnings on rehydration, and transforms under `patternProperties` are skipped. |
+//! | `time`    | yes     | [`ConvertOptions::time_budget_ms`] is not enforced and [`ConvertResult::timings`] durations read zero. |
+//! | `tracing` | yes     | No debug/trace logging or per-pass spans.               |
+//! | `otel`    | no      | No operation spans with OpenTelemetry attributes (see `telemetry`). |
+//! | `proto`   | no      | No `proto` module (Protocol Buffers input).             |
+//! | `graphql` | no      | No `graphql` module (GraphQL SDL input).                |
+
+pub mod advisor;
 pub(crate) mod anchor_utils;
+pub mod cancel;
+pub(crate) mod canonical;
 pub mod codec;
 pub mod codec_warning;
+pub mod compatibility;
 pub mod config;
+pub mod corpus;
+pub mod dehydrator;
+pub mod diagnostics;
+pub(crate) mod enum_descriptions;
 pub mod error;
 pub mod extract;
+pub mod fewshot;
+pub mod fingerprint;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub(crate) mod limits;
+pub mod lint;
+pub(crate) mod logging;
+pub mod messages;
+pub mod pass;
 pub(crate) mod passes;
+pub mod path_map;
+pub mod pipeline;
+pub mod project;
+pub mod prompt;
+pub(crate) mod property_ordering;
+#[cfg(feature = "proto")]
+pub mod proto;
+pub mod providers;
 pub mod rehydrator;
 pub(crate) mod resolver;
+pub mod sample;
 pub(crate) mod schema_utils;
 pub(crate) mod schema_walker;
+pub(crate) mod span;
+pub mod split;
+pub(crate) mod strip;
+pub(crate) mod telemetry;
+pub mod tools;
+pub mod typegen;
 pub mod validation;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use pass::BuiltinPass;
+
+pub use advisor::{diagnose_failure, FailureKind, FailureReport, OutputFailure};
+pub use cancel::CancellationToken;
 pub use codec::Codec;
 pub use codec_warning::Warning;
-pub use config::{ConvertOptions, Mode, PolymorphismStrategy, Target};
-pub use error::{ConvertError, ErrorCode, ProviderCompatError};
-pub use extract::{extract_component, list_components, ExtractOptions, ExtractResult};
-pub use rehydrator::{coerce_types, RehydrateResult};
-pub use schema_utils::{build_path, escape_pointer_segment, split_path, unescape_pointer_segment};
+pub use compatibility::{codec_matches, CompatibilityIssue, CompatibilityReport};
+pub use config::{
+    AdditionalPropertiesPolicy, ConstraintHintPolicy, ContentSchemaPolicy, ConvertOptions,
+    DeprecatedPolicy, DuplicateKeyCheck, EnumDescriptionStyle, Envelope, ErrorPolicy,
+    MergeConflictPolicy, Mode, NullableStyle, PolymorphismStrategy, Preset, Progress, ProgressSink,
+    PropertyOrderFn, PropertyOrdering, RecursionLeaf, Target,
+};
+pub use dehydrator::{dehydrate, dehydrate_with_schema};
+pub use diagnostics::{ConstraintHint, CycleInfo, RuleSelector};
+pub use error::{ConvertError, ErrorCode, ProviderCompatError, SourceLocation};
+pub use extract::{
+    describe_components, extract_component, find_components_referencing, list_components,
+    ComponentInfo, ExtractOptions, ExtractResult,
+};
+pub use fewshot::{fewshot_from_result, make_fewshot};
+pub use fingerprint::schema_fingerprint;
+pub use lint::{lint, lint_str, LintFinding, LintReport};
+pub use pass::PassResult;
+pub use path_map::{PathMap, PathMapping, TRANSFORM_ANNOTATION};
+pub use pipeline::{ConvertPipeline, PassTiming};
+pub use project::project_schema;
+pub use prompt::{generate_prompt_scaffold, PromptOptions};
+pub use rehydrator::{
+    coerce_types, CompiledCodec, DuplicateKeyPolicy, RehydrateOptions, RehydrateResult,
+};
+pub use schema_utils::{
+    build_path, escape_pointer_segment, parse_value, round_numbers, split_path,
+    unescape_pointer_segment,
+};
+pub use split::{split_schema, SplitCodec, SplitCodecPart, SplitPart, SplitPlan};
+pub use tools::{RegisteredTool, ToolRegistry};
+pub use typegen::{emit_types, TypeLanguage, TypegenOptions};
+pub use validation::conformance::validate_conformance;
+pub use validation::meta_schema::{validate_input, SchemaIssue};
 pub use validation::strict_mode::{validate_strict_mode, StrictModeRule, StrictModeViolation};
 
 /// Bridge API version. Included in all FFI JSON responses.
-pub const API_VERSION: &str = "1.0";
+pub const API_VERSION: &str = "2.0";
+
+/// Crate version (semver), as published.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Cargo features this build was compiled with, in manifest order.
+pub fn compiled_features() -> Vec<&'static str> {
+    let features = [
+        ("regex", cfg!(feature = "regex")),
+        ("time", cfg!(feature = "time")),
+        ("tracing", cfg!(feature = "tracing")),
+        ("otel", cfg!(feature = "otel")),
+        ("proto", cfg!(feature = "proto")),
+        ("graphql", cfg!(feature = "graphql")),
+    ];
+    features
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+}
 
 /// Result of a schema conversion.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +158,49 @@ pub struct ConvertResult {
     /// Provider compatibility warnings/soft-errors.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub provider_compat_errors: Vec<ProviderCompatError>,
+    /// Diagnostics matched by [`ConvertOptions::suppress`]. Kept rather than
+    /// dropped so reports can still count acknowledged lossy transforms.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed_compat_errors: Vec<ProviderCompatError>,
+    /// Descriptions that dropped constraints were folded into, per
+    /// [`ConvertOptions::constraint_hints`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraint_hints: Vec<ConstraintHint>,
+    /// Recursive `$ref` cycles broken by Pass 5, one per break point.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recursion_cycles: Vec<CycleInfo>,
+    /// `schema` wrapped in the provider request payload selected by
+    /// [`ConvertOptions::envelope`], ready to embed in an API call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envelope: Option<Value>,
+    /// How long each built-in pass took, in pipeline order; skipped passes
+    /// are absent. Not serialized, since it varies from run to run.
+    #[serde(skip)]
+    pub timings: Vec<PassTiming>,
+}
+
+impl ConvertResult {
+    /// Map every transformed location between the original and converted
+    /// schema, e.g. `#` → `#/properties/result` after root wrapping, or a
+    /// map's `additionalProperties` → its entry array's `value` property.
+    pub fn path_map(&self) -> PathMap {
+        PathMap::from_codec(&self.codec, &self.schema)
+    }
+
+    /// Add diagnostics found in the schema text ahead of the pipeline's,
+    /// honouring [`ConvertOptions::suppress`].
+    pub(crate) fn with_source_diagnostics(
+        mut self,
+        diagnostics: Vec<ProviderCompatError>,
+        options: &ConvertOptions,
+    ) -> Self {
+        let (suppressed, reported): (Vec<_>, Vec<_>) = diagnostics
+            .into_iter()
+            .partition(|e| options.suppress.iter().any(|sel| sel.matches(e)));
+        self.provider_compat_errors.splice(0..0, reported);
+        self.suppressed_compat_errors.splice(0..0, suppressed);
+        self
+    }
 }
 
 /// Convert a JSON Schema into an LLM-compatible structured output schema.
@@ -73,67 +214,360 @@ pub struct ConvertResult {
 ///
 /// A `ConvertResult` containing the converted schema and codec.
 pub fn convert(schema: &Value, options: &ConvertOptions) -> Result<ConvertResult, ConvertError> {
+    convert_staged(schema, options, &pipeline::Stages::default())
+}
+
+/// [`convert`], with built-in passes skipped and hooked per `stages`.
+pub(crate) fn convert_staged(
+    schema: &Value,
+    options: &ConvertOptions,
+    stages: &pipeline::Stages<'_>,
+) -> Result<ConvertResult, ConvertError> {
+    let span = telemetry::OpSpan::convert(schema, options);
+    let result = span.in_scope(|| run_passes(schema, options, stages));
+    span.record_convert(&result);
+    result
+}
+
+/// The pass chain behind [`convert_staged`].
+fn run_passes(
+    schema: &Value,
+    options: &ConvertOptions,
+    stages: &pipeline::Stages<'_>,
+) -> Result<ConvertResult, ConvertError> {
+    let original = schema;
+    if options.max_schema_bytes.is_some() {
+        limits::check_schema_bytes(limits::serialized_len(schema), options)?;
+    }
+    if options.validate_input {
+        let issues = validation::meta_schema::validate_input(schema);
+        if !issues.is_empty() {
+            return Err(ConvertError::InvalidSchema { issues });
+        }
+    }
+    let guard = limits::ResourceGuard::new(options);
     let mut codec = Codec::new();
 
     // Pass 0: Normalize ($ref resolution, draft migration)
-    let p0 = passes::p0_normalize::normalize(schema, options)?;
-    let schema = p0.pass.schema;
-
-    if !p0.recursive_refs.is_empty() {
-        tracing::debug!(
-            recursive_refs = ?p0.recursive_refs,
-            "detected {} recursive $ref cycle(s) — will be broken in Pass 5",
-            p0.recursive_refs.len()
-        );
-    }
+    let (schema, normalize_diagnostics) = stages.run(
+        BuiltinPass::Normalize,
+        schema.clone(),
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p0 = passes::p0_normalize::normalize_with_guard(&schema, options, &guard)?;
+            if !p0.recursive_refs.is_empty() {
+                crate::logging::debug!(
+                    recursive_refs = ?p0.recursive_refs,
+                    "detected {} recursive $ref cycle(s) — will be broken in Pass 5",
+                    p0.recursive_refs.len()
+                );
+            }
+            Ok((p0.pass.merge_into_codec(codec), p0.diagnostics))
+        },
+    )?;
 
-    // Pass 1: Composition (allOf merge)
-    let p1 = passes::p1_composition::compile_composition(schema, options)?;
-    let schema = p1.merge_into_codec(&mut codec);
+    guard.start_pass("#")?;
+    // Per-value enum descriptions (refs are inlined by now; no codec entries)
+    let schema = enum_descriptions::apply(schema, options, &guard)?;
 
+    guard.start_pass("#")?;
+    // Pass 1: Composition (allOf merge)
+    let (schema, composition_diagnostics) = stages.run(
+        BuiltinPass::Composition,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p1 =
+                passes::p1_composition::compile_composition_with_guard(schema, options, &guard)?;
+            Ok((p1.pass.merge_into_codec(codec), p1.diagnostics))
+        },
+    )?;
+
+    guard.start_pass("#")?;
     // Pass 2: Polymorphism (oneOf → anyOf)
-    let p2 = passes::p2_polymorphism::simplify_polymorphism(schema, options)?;
-    let schema = p2.merge_into_codec(&mut codec);
-
+    let (schema, ()) = stages.run(
+        BuiltinPass::Polymorphism,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p2 =
+                passes::p2_polymorphism::simplify_polymorphism_with_guard(schema, options, &guard)?;
+            Ok((p2.merge_into_codec(codec), ()))
+        },
+    )?;
+
+    guard.start_pass("#")?;
     // Pass 3: Dictionary (Map → Array)
-    let p3 = passes::p3_dictionary::transpile_dictionaries(schema, options)?;
-    let schema = p3.merge_into_codec(&mut codec);
-
+    let (schema, ()) = stages.run(
+        BuiltinPass::Dictionary,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p3 =
+                passes::p3_dictionary::transpile_dictionaries_with_guard(schema, options, &guard)?;
+            Ok((p3.merge_into_codec(codec), ()))
+        },
+    )?;
+
+    guard.start_pass("#")?;
     // Pass 4: Opaque (open objects → string)
-    let p4 = passes::p4_opaque::stringify_opaque(schema, options)?;
-    let schema = p4.merge_into_codec(&mut codec);
-
+    let (schema, ()) = stages.run(
+        BuiltinPass::Opaque,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p4 = passes::p4_opaque::stringify_opaque_with_guard(schema, options, &guard)?;
+            Ok((p4.merge_into_codec(codec), ()))
+        },
+    )?;
+
+    guard.start_pass("#")?;
     // Pass 5: Recursion Breaking
-    let p5 = passes::p5_recursion::break_recursion(schema, options)?;
-    let mut schema = p5.merge_into_codec(&mut codec);
-
+    let (schema, recursion_cycles) = stages.run(
+        BuiltinPass::Recursion,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p5 = passes::p5_recursion::break_recursion_with_guard(schema, options, &guard)?;
+            Ok((p5.pass.merge_into_codec(codec), p5.cycles))
+        },
+    )?;
+
+    guard.start_pass("#")?;
     // Pass 6: Strict enforcement
-    if options.mode == Mode::Strict {
-        let p6 = passes::p6_strict::enforce_strict(schema, options)?;
-        schema = p6.merge_into_codec(&mut codec);
-    }
+    let (schema, ()) = stages.run(
+        BuiltinPass::Strict,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            if options.mode != Mode::Strict {
+                return Ok((schema, ()));
+            }
+            let p6 = passes::p6_strict::enforce_strict_with_guard(schema, options, &guard)?;
+            Ok((p6.merge_into_codec(codec), ()))
+        },
+    )?;
 
+    guard.start_pass("#")?;
     // Pass 8: Adaptive opaque stringification (before constraint pruning
     // so it can detect `contains`, closed-tuple `prefixItems`, etc.)
-    let p8 = passes::p8_adaptive_opaque::adaptive_opaque(schema, options)?;
-    let schema = p8.merge_into_codec(&mut codec);
-
+    let (schema, ()) = stages.run(
+        BuiltinPass::AdaptiveOpaque,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p8 =
+                passes::p8_adaptive_opaque::adaptive_opaque_with_guard(schema, options, &guard)?;
+            Ok((p8.merge_into_codec(codec), ()))
+        },
+    )?;
+
+    guard.start_pass("#")?;
     // Pass 7: Constraint pruning
-    let p7 = passes::p7_constraints::prune_constraints(schema, options)?;
-    let schema = p7.merge_into_codec(&mut codec);
-
+    let (schema, constraint_hints) = stages.run(
+        BuiltinPass::Constraints,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p7 = passes::p7_constraints::prune_constraints_with_guard(schema, options, &guard)?;
+            Ok((p7.pass.merge_into_codec(codec), p7.hints))
+        },
+    )?;
+
+    guard.start_pass("#")?;
     // Pass 9: Provider compatibility checks (soft errors)
-    let p9 = passes::p9_provider_compat::check_provider_compat(schema, options);
-    let provider_compat_errors = p9.errors;
-    let schema = p9.pass.merge_into_codec(&mut codec);
+    let (mut schema, compat_errors) = stages.run(
+        BuiltinPass::ProviderCompat,
+        schema,
+        options,
+        &guard,
+        &mut codec,
+        |schema, codec| {
+            let p9 = passes::p9_provider_compat::check_provider_compat(schema, options);
+            Ok((p9.pass.merge_into_codec(codec), p9.errors))
+        },
+    )?;
+    let (suppressed_compat_errors, provider_compat_errors) = normalize_diagnostics
+        .into_iter()
+        .chain(composition_diagnostics)
+        .chain(compat_errors)
+        .partition(|e| options.suppress.iter().any(|sel| sel.matches(e)));
+
+    // Gemini field generation order (not a transform: nothing to rehydrate)
+    if property_ordering::enabled(options) {
+        schema = property_ordering::apply(schema, options)?;
+    }
+
+    if options.strip_descriptions {
+        schema = strip::strip_descriptions(schema, &codec, &options.keep_descriptions);
+    }
+
+    if options.canonical {
+        schema = canonical::canonicalize(schema);
+    }
+
+    if options.annotate {
+        path_map::annotate_transforms(&mut schema, &codec);
+    }
+
+    if options.verify_output {
+        let violations = validation::conformance::validate_conformance(&schema, options);
+        if !violations.is_empty() {
+            return Err(ConvertError::ProviderCompatFailure {
+                target: options.target,
+                violations,
+            });
+        }
+    }
+
+    let envelope = options
+        .envelope
+        .map(|envelope| providers::envelope_for(envelope, &schema, original, options));
 
     Ok(ConvertResult {
         schema,
         codec,
         provider_compat_errors,
+        suppressed_compat_errors,
+        constraint_hints,
+        recursion_cycles,
+        envelope,
+        timings: stages.take_timings(),
     })
 }
 
+/// Run the [`ConvertOptions::extra_passes`] registered after `after`, in order.
+fn run_extra_passes(
+    after: BuiltinPass,
+    mut schema: Value,
+    options: &ConvertOptions,
+    codec: &mut Codec,
+) -> Result<Value, ConvertError> {
+    let ctx = pass::PassContext { options, after };
+    for extra in options.extra_passes.iter().filter(|e| e.after == after) {
+        crate::logging::debug!(pass = extra.pass.name(), ?after, "running custom pass");
+        schema = extra.pass.run(schema, &ctx)?.merge_into_codec(codec);
+    }
+    Ok(schema)
+}
+
+/// Convert a JSON Schema given as text.
+///
+/// Equivalent to parsing `schema_json` and calling [`convert`], except that
+/// `schema_error` and `unresolvable_ref` failures carry the line/column of
+/// the offending location in `schema_json` (see [`ConvertError::location`]).
+/// [`ConvertOptions::max_schema_bytes`] is checked against the text length
+/// before parsing. With [`PropertyOrdering::Original`], Gemini
+/// `propertyOrdering` follows the key order in `schema_json`. Repeated keys
+/// are reported per [`ConvertOptions::duplicate_keys`], and numbers are
+/// kept exact with [`ConvertOptions::preserve_precision`].
+pub fn convert_str(
+    schema_json: &str,
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    let (schema, diagnostics) = parse_schema_str(schema_json, options)?;
+    let result = convert(&schema, options).map_err(|e| e.with_source_location(schema_json))?;
+    Ok(result.with_source_diagnostics(diagnostics, options))
+}
+
+/// Parse `schema_json` for [`convert_str`]: size-check it, check it for
+/// duplicate keys and seed Gemini property ordering from its key order when
+/// enabled. Returns the schema and the text's own diagnostics.
+pub(crate) fn parse_schema_str(
+    schema_json: &str,
+    options: &ConvertOptions,
+) -> Result<(Value, Vec<ProviderCompatError>), ConvertError> {
+    limits::check_schema_bytes(schema_json.len(), options)?;
+    schema_utils::check_preserve_precision(options.preserve_precision)?;
+    let mut schema: Value = serde_json::from_str(schema_json)?;
+    if !options.preserve_precision {
+        round_numbers(&mut schema);
+    }
+    let diagnostics = duplicate_key_diagnostics(schema_json, options)?;
+    if property_ordering::enabled(options) {
+        return Ok((seed_property_ordering(schema, schema_json)?, diagnostics));
+    }
+    Ok((schema, diagnostics))
+}
+
+/// Check `schema_json` for objects that repeat a key, per
+/// [`ConvertOptions::duplicate_keys`]: `duplicate_schema_key` diagnostics
+/// under [`DuplicateKeyCheck::Warn`] (less those matched by
+/// [`ConvertOptions::suppress`]), a `schema_error` at the first repeat under
+/// [`DuplicateKeyCheck::Error`].
+///
+/// [`convert_str`] does this itself; use it when converting the parsed
+/// `Value`.
+pub fn check_duplicate_keys(
+    schema_json: &str,
+    options: &ConvertOptions,
+) -> Result<Vec<ProviderCompatError>, ConvertError> {
+    let mut diagnostics = duplicate_key_diagnostics(schema_json, options)?;
+    diagnostics.retain(|e| !options.suppress.iter().any(|sel| sel.matches(e)));
+    Ok(diagnostics)
+}
+
+fn duplicate_key_diagnostics(
+    schema_json: &str,
+    options: &ConvertOptions,
+) -> Result<Vec<ProviderCompatError>, ConvertError> {
+    if options.duplicate_keys == DuplicateKeyCheck::Ignore {
+        return Ok(Vec::new());
+    }
+    let duplicates = span::duplicate_keys(schema_json).unwrap_or_default();
+    if options.duplicate_keys == DuplicateKeyCheck::Error {
+        if let Some((path, location)) = duplicates.into_iter().next() {
+            return Err(ConvertError::SchemaError {
+                message: "duplicate key; the earlier occurrence would be silently dropped"
+                    .to_string(),
+                path,
+                location: Some(location),
+            });
+        }
+        return Ok(Vec::new());
+    }
+    Ok(duplicates
+        .into_iter()
+        .map(|(path, location)| ProviderCompatError::DuplicateSchemaKey {
+            path,
+            target: options.target,
+            hint: format!(
+                "Repeated at line {}, column {}; the last occurrence is the one kept.",
+                location.line, location.column
+            ),
+        })
+        .collect())
+}
+
+/// Record each object's key order in `schema_json` as a Gemini
+/// `propertyOrdering` on `schema` (its parsed form), keeping any ordering
+/// already present.
+///
+/// [`convert_str`] does this itself; use it to keep source order with the
+/// `Value` APIs such as [`convert_all_components`]. Only useful when
+/// targeting Gemini with [`PropertyOrdering::Original`].
+pub fn seed_property_ordering(schema: Value, schema_json: &str) -> Result<Value, ConvertError> {
+    property_ordering::seed_from_source(schema, schema_json)
+}
+
 /// Rehydrate LLM output back to the original schema shape using the codec.
 ///
 /// Type coercion is always applied using the provided `original_schema`
@@ -180,22 +614,189 @@ pub fn rehydrate(
     data: &Value,
     codec: &Codec,
     original_schema: &Value,
+) -> Result<RehydrateResult, ConvertError> {
+    rehydrate_owned(
+        data.clone(),
+        codec,
+        original_schema,
+        &RehydrateOptions::default(),
+    )
+}
+
+/// [`rehydrate`] with [`RehydrateOptions`], e.g. handlers for
+/// [`Transform::Custom`](codec::Transform::Custom) entries recorded by
+/// custom passes.
+///
+/// ```rust
+/// use json_schema_llm_core::codec::{Codec, Transform};
+/// use json_schema_llm_core::{rehydrate_with_options, RehydrateOptions};
+/// use serde_json::json;
+///
+/// let mut codec = Codec::new();
+/// codec.transforms.push(Transform::Custom {
+///     kind: "cents".to_string(),
+///     path: "#/properties/price".to_string(),
+///     payload: json!({"divisor": 100}),
+/// });
+/// let mut options = RehydrateOptions::new();
+/// options.register_handler("cents", |data, payload| {
+///     if let (Some(n), Some(d)) = (data.as_f64(), payload["divisor"].as_f64()) {
+///         *data = json!(n / d);
+///     }
+///     Ok(())
+/// });
+/// let original = json!({"type": "object", "properties": {"price": {"type": "number"}}});
+/// let result = rehydrate_with_options(&json!({"price": 1250}), &codec, &original, &options).unwrap();
+/// assert_eq!(result.data, json!({"price": 12.5}));
+/// ```
+pub fn rehydrate_with_options(
+    data: &Value,
+    codec: &Codec,
+    original_schema: &Value,
+    options: &RehydrateOptions,
+) -> Result<RehydrateResult, ConvertError> {
+    rehydrate_owned(data.clone(), codec, original_schema, options)
+}
+
+/// Result of a [`rehydrate_text`] call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RehydrateTextResult {
+    /// The rehydrated data, serialized as compact JSON text.
+    pub data: String,
+    /// Warnings about dropped constraint violations and type coercions.
+    pub warnings: Vec<Warning>,
+}
+
+/// Rehydrate LLM output held as JSON text, returning JSON text.
+///
+/// A convenience for hosts that already hold the model response as a
+/// string: it parses the text into an owned [`Value`] and rehydrates that in
+/// place, saving the defensive clone [`rehydrate`] makes of borrowed input.
+/// It is not a streaming transcoder — the whole tree is still built, since
+/// several transforms need a node's siblings or descendants.
+///
+/// As with [`rehydrate_json`], the whole string must be one JSON value;
+/// anything but whitespace after it is a parse error.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use json_schema_llm_core::{convert, rehydrate_text, ConvertOptions};
+/// use serde_json::json;
+///
+/// let schema = json!({"type": "object", "properties": {"name": {"type": "string"}}});
+/// let result = convert(&schema, &ConvertOptions::default()).unwrap();
+///
+/// let rehydrated = rehydrate_text(r#"{"name": "Ada"}"#, &result.codec, &schema).unwrap();
+/// assert_eq!(rehydrated.data, r#"{"name":"Ada"}"#);
+/// ```
+pub fn rehydrate_text(
+    data_json: &str,
+    codec: &Codec,
+    original_schema: &Value,
+) -> Result<RehydrateTextResult, ConvertError> {
+    let data = serde_json::from_str(data_json)?;
+    let result = rehydrate_owned(data, codec, original_schema, &RehydrateOptions::default())?;
+    Ok(RehydrateTextResult {
+        data: serde_json::to_string(&result.data)?,
+        warnings: result.warnings,
+    })
+}
+
+/// Rehydrate a raw provider response body (e.g. an OpenAI Chat Completions
+/// response) by first locating the structured output in it with
+/// [`providers::extract_structured_output`].
+pub fn rehydrate_response(
+    target: Target,
+    raw_response: &Value,
+    codec: &Codec,
+    original_schema: &Value,
+) -> Result<RehydrateResult, ConvertError> {
+    let data = providers::extract_structured_output(target, raw_response)?;
+    rehydrate_owned(data, codec, original_schema, &RehydrateOptions::default())
+}
+
+/// Shared rehydration pipeline operating on an owned data tree.
+fn rehydrate_owned(
+    data: Value,
+    codec: &Codec,
+    original_schema: &Value,
+    options: &RehydrateOptions,
+) -> Result<RehydrateResult, ConvertError> {
+    let span = telemetry::OpSpan::rehydrate(&data, codec);
+    let result = span.in_scope(|| {
+        rehydrator::validate_codec_version(codec)?;
+        let regex_cache = rehydrator::build_pattern_properties_cache(codec);
+        rehydrate_phases(data, codec, &regex_cache, original_schema, options)
+    });
+    span.record_rehydrate(&result);
+    result
+}
+
+/// [`rehydrate_with_options`] against a [`CompiledCodec`], taking ownership
+/// of `data`. The codec's version check and regex compilation are not
+/// repeated, so prefer this when rehydrating a stream of responses for one
+/// conversion.
+///
+/// ```rust
+/// use json_schema_llm_core::{convert, rehydrate_compiled, CompiledCodec, ConvertOptions, RehydrateOptions};
+/// use serde_json::json;
+///
+/// let schema = json!({"type": "object", "properties": {"n": {"type": "integer"}}});
+/// let result = convert(&schema, &ConvertOptions::default()).unwrap();
+/// let compiled = CompiledCodec::new(result.codec).unwrap();
+/// for (response, n) in [(json!({"n": 1}), 1), (json!({"n": "2"}), 2)] {
+///     let rehydrated =
+///         rehydrate_compiled(response, &compiled, &schema, &RehydrateOptions::default()).unwrap();
+///     assert_eq!(rehydrated.data, json!({"n": n}));
+/// }
+/// ```
+pub fn rehydrate_compiled(
+    data: Value,
+    codec: &CompiledCodec,
+    original_schema: &Value,
+    options: &RehydrateOptions,
+) -> Result<RehydrateResult, ConvertError> {
+    let span = telemetry::OpSpan::rehydrate(&data, codec.codec());
+    let result = span.in_scope(|| {
+        rehydrate_phases(
+            data,
+            codec.codec(),
+            codec.regex_cache(),
+            original_schema,
+            options,
+        )
+    });
+    span.record_rehydrate(&result);
+    result
+}
+
+/// The phases behind [`rehydrate_owned`], for a version-checked codec whose
+/// patterns are compiled into `regex_cache`.
+fn rehydrate_phases(
+    data: Value,
+    codec: &Codec,
+    regex_cache: &rehydrator::RegexCache,
+    original_schema: &Value,
+    options: &RehydrateOptions,
 ) -> Result<RehydrateResult, ConvertError> {
     // Phase 1: Apply transforms (reverse codec operations)
-    let mut result = rehydrator::apply_transforms(data, codec)?;
+    let mut result = rehydrator::apply_transforms_cached(data, codec, regex_cache, options)?;
 
     // Phase 2: Type coercion (e.g., string "42" → integer 42)
-    let coercion_warnings = rehydrator::coerce_types(&mut result.data, original_schema);
+    let mut coerced = std::collections::HashSet::new();
+    let coercion_warnings =
+        rehydrator::coerce_types_recording(&mut result.data, original_schema, &mut coerced);
     result.warnings.extend(coercion_warnings);
 
     // Phase 3: Constraint enforcement + validation (runs AFTER coercion so
     // constraints evaluate against correctly-typed values)
-    let regex_cache = rehydrator::build_pattern_properties_cache(codec);
     let enforcement_warnings =
-        rehydrator::enforce_constraints(&mut result.data, codec, &regex_cache);
-    let validation_warnings = rehydrator::validate_constraints(&result.data, codec, &regex_cache);
+        rehydrator::enforce_constraints(&mut result.data, codec, regex_cache, &coerced);
+    let validation_warnings = rehydrator::validate_constraints(&result.data, codec, regex_cache);
     result.warnings.extend(enforcement_warnings);
     result.warnings.extend(validation_warnings);
+    result.warnings = options.bound_warnings(result.warnings);
 
     Ok(result)
 }
@@ -229,7 +830,10 @@ pub struct ConvertAllResult {
 ///    [`DependencyGraph`] in a single `O(M)` pass, then extracts each component
 ///    via `O(avg_deps)` sub-graph slicing and runs [`convert`] on it
 /// 3. Per-component errors are collected in [`ConvertAllResult::component_errors`]
-///    and do not abort the batch
+///    and do not abort the batch, except [`ConvertError::Cancelled`]
+///
+/// Every result is held in memory; [`convert_all_components_streaming`]
+/// yields them one at a time instead.
 ///
 /// # Performance
 ///
@@ -240,40 +844,15 @@ pub fn convert_all_components(
     convert_options: &ConvertOptions,
     extract_options: &ExtractOptions,
 ) -> Result<ConvertAllResult, ConvertError> {
-    // Step 1: Always convert the full schema.
-    let full = convert(schema, convert_options)?;
-
-    // Step 2: Early-exit if component extraction is suppressed.
-    if convert_options.skip_components {
-        return Ok(ConvertAllResult {
-            full,
-            components: Vec::new(),
-            component_errors: Vec::new(),
-        });
-    }
-
-    // Step 3: Build global dependency graph (single O(M) pass).
-    let graph = extract::DependencyGraph::build(schema)?;
-
-    // Step 4: Enumerate and process each component — best-effort.
-    let pointers = list_components(schema);
-    let mut components: Vec<(String, ConvertResult)> = Vec::with_capacity(pointers.len());
+    let mut stream = convert_all_components_streaming(schema, convert_options, extract_options)?;
+    let mut components: Vec<(String, ConvertResult)> = Vec::new();
     let mut component_errors: Vec<(String, String)> = Vec::new();
-
-    for pointer in pointers {
-        let extract_result = graph.extract(&pointer, extract_options);
-        match extract_result {
-            Err(e) => {
-                component_errors.push((pointer, e.to_string()));
-            }
-            Ok(extracted) => match convert(&extracted.schema, convert_options) {
-                Err(e) => {
-                    component_errors.push((pointer, e.to_string()));
-                }
-                Ok(conv) => {
-                    components.push((pointer, conv));
-                }
-            },
+    for (pointer, result) in &mut stream {
+        match result {
+            // Cancellation aborts the whole run, not just this component.
+            Err(e @ ConvertError::Cancelled { .. }) => return Err(e),
+            Err(e) => component_errors.push((pointer, e.to_string())),
+            Ok(conv) => components.push((pointer, conv)),
         }
     }
 
@@ -281,12 +860,164 @@ pub fn convert_all_components(
     component_errors.sort_by(|(a, _), (b, _)| a.cmp(b));
 
     Ok(ConvertAllResult {
-        full,
+        full: stream.full,
         components,
         component_errors,
     })
 }
 
+/// [`convert_all_components`] one component at a time, so a caller can
+/// write and drop each result before the next is built.
+///
+/// The full schema is converted up front into [`ComponentStream::full`];
+/// iterating then extracts and converts each component in pointer order,
+/// yielding `(pointer, result)`. A failed component does not end the
+/// stream, except [`ConvertError::Cancelled`], which is yielded last.
+pub fn convert_all_components_streaming<'a>(
+    schema: &'a Value,
+    convert_options: &ConvertOptions,
+    extract_options: &'a ExtractOptions,
+) -> Result<ComponentStream<'a>, ConvertError> {
+    let full = convert(schema, convert_options)?;
+
+    let (graph, pointers) = if convert_options.skip_components {
+        (None, Vec::new())
+    } else {
+        (
+            Some(extract::DependencyGraph::build(schema)?),
+            list_components(schema),
+        )
+    };
+    let total = pointers.len();
+
+    Ok(ComponentStream {
+        full,
+        graph,
+        pointers: pointers.into_iter(),
+        ready: std::collections::VecDeque::new(),
+        jobs: 1,
+        started: false,
+        done: 0,
+        total,
+        // Per-pass progress covers the full schema only; components report
+        // as a count.
+        progress: convert_options.progress.clone(),
+        options: ConvertOptions {
+            progress: None,
+            ..convert_options.clone()
+        },
+        extract_options,
+    })
+}
+
+/// Per-component results of [`convert_all_components_streaming`], produced
+/// on demand.
+pub struct ComponentStream<'a> {
+    /// Conversion result for the entire input schema.
+    pub full: ConvertResult,
+    graph: Option<extract::DependencyGraph<'a>>,
+    pointers: std::vec::IntoIter<String>,
+    /// Converted but not yet yielded, in pointer order.
+    ready: std::collections::VecDeque<(String, Result<ConvertResult, ConvertError>)>,
+    jobs: usize,
+    started: bool,
+    done: usize,
+    total: usize,
+    progress: Option<config::ProgressSink>,
+    options: ConvertOptions,
+    extract_options: &'a ExtractOptions,
+}
+
+impl ComponentStream<'_> {
+    /// Number of components the stream covers, including those already
+    /// yielded.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Leave out the not-yet-yielded components whose pointer `skip`
+    /// accepts, without extracting or converting them — e.g. ones already
+    /// converted by an earlier run.
+    pub fn skip_where(mut self, mut skip: impl FnMut(&str) -> bool) -> Self {
+        let kept: Vec<String> = self.pointers.by_ref().filter(|p| !skip(p)).collect();
+        self.total = self.done + kept.len();
+        self.pointers = kept.into_iter();
+        self
+    }
+
+    /// Convert up to `jobs` components at a time on scoped threads
+    /// (default 1: one by one on the calling thread). Results are still
+    /// yielded in pointer order, whatever order they finish in, and at most
+    /// `jobs` of them are held at once.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Extract and convert the next batch of components into `ready`.
+    fn convert_batch(&mut self) {
+        let Some(graph) = &self.graph else {
+            return;
+        };
+        let batch: Vec<String> = self.pointers.by_ref().take(self.jobs).collect();
+        // `self.options` has no progress sink: per-pass events from parallel
+        // workers would interleave, so only the stream reports, as a count.
+        let (options, extract_options) = (&self.options, self.extract_options);
+        let convert_one = |pointer: &str| {
+            cancel::check(options, pointer)?;
+            let extracted = graph.extract(pointer, extract_options)?;
+            convert(&extracted.schema, options)
+        };
+        let results: Vec<_> = if batch.len() > 1 {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = batch
+                    .iter()
+                    .map(|pointer| scope.spawn(|| convert_one(pointer)))
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                    })
+                    .collect()
+            })
+        } else {
+            batch.iter().map(|pointer| convert_one(pointer)).collect()
+        };
+        self.ready.extend(batch.into_iter().zip(results));
+    }
+}
+
+impl Iterator for ComponentStream<'_> {
+    type Item = (String, Result<ConvertResult, ConvertError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.graph.as_ref()?;
+        if !self.started {
+            self.started = true;
+            if let Some(progress) = &self.progress {
+                progress.report_components(0, self.total);
+            }
+        }
+        if self.ready.is_empty() {
+            self.convert_batch();
+        }
+        let (pointer, result) = self.ready.pop_front()?;
+        if let Err(ConvertError::Cancelled { .. }) = result {
+            self.pointers = Vec::new().into_iter();
+            self.ready.clear();
+            return Some((pointer, result));
+        }
+        self.done += 1;
+        if let Some(progress) = &self.progress {
+            progress.report_components(self.done, self.total);
+        }
+        Some((pointer, result))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // JSON-String Bridge API (FFI surface)
 // ---------------------------------------------------------------------------
@@ -310,7 +1041,7 @@ fn err_json(e: ConvertError) -> String {
 ///
 /// # Returns
 ///
-/// * `Ok(String)` — `{"apiVersion": "1.0", "schema": {...}}` (the patched schema)
+/// * `Ok(String)` — `{"apiVersion": "2.0", "schema": {...}}` (the patched schema)
 /// * `Err(String)` — `{"code": "...", "message": "...", "path": null}`
 pub fn apply_patch_json(schema_json: &str, patch_json: &str) -> Result<String, String> {
     let mut schema: Value =
@@ -332,6 +1063,24 @@ pub fn apply_patch_json(schema_json: &str, patch_json: &str) -> Result<String, S
     serde_json::to_string(&result).map_err(|e| err_json(ConvertError::JsonError(e)))
 }
 
+/// Describe this build (as a JSON string) so FFI wrappers can gate
+/// functionality at runtime.
+///
+/// # Returns
+///
+/// `{"apiVersion": "2.0", "version": "0.1.0", "codecMajorVersion": 1,
+/// "targets": ["openai-strict", ...], "features": ["regex", ...]}`
+pub fn version_json() -> String {
+    serde_json::json!({
+        "apiVersion": API_VERSION,
+        "version": VERSION,
+        "codecMajorVersion": codec::CODEC_MAJOR_VERSION,
+        "targets": Target::ALL,
+        "features": compiled_features(),
+    })
+    .to_string()
+}
+
 // ---------------------------------------------------------------------------
 // Bridge Wrapper DTOs — inject apiVersion for FFI consumers
 // ---------------------------------------------------------------------------
@@ -371,14 +1120,12 @@ struct BridgeRehydrateResult<'a> {
 ///
 /// # Returns
 ///
-/// * `Ok(String)` — `{"apiVersion": "1.0", "schema": {...}, "codec": {...}}`
+/// * `Ok(String)` — `{"apiVersion": "2.0", "schema": {...}, "codec": {...}}`
 /// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
 pub fn convert_json(schema_json: &str, options_json: &str) -> Result<String, String> {
-    let schema: Value =
-        serde_json::from_str(schema_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
     let options: ConvertOptions =
         serde_json::from_str(options_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
-    let result = convert(&schema, &options).map_err(err_json)?;
+    let result = convert_str(schema_json, &options).map_err(err_json)?;
     let bridge = BridgeConvertResult {
         api_version: API_VERSION,
         inner: &result,
@@ -393,18 +1140,40 @@ pub fn convert_json(schema_json: &str, options_json: &str) -> Result<String, Str
 ///
 /// # Arguments
 ///
-/// * `data_json` — The LLM-generated JSON data as a string
+/// * `data_json` — The LLM-generated JSON data as a string. The whole
+///   string must be one JSON value; trailing characters are a parse error.
 /// * `codec_json` — The codec sidecar (from a prior conversion) as a JSON string
 /// * `original_schema_json` — The original JSON Schema as a string (for type coercion)
 ///
+/// Built with the `arbitrary-precision` feature (off by default, here and in
+/// the WASI bridge), numbers in `data_json` are restored exactly, however
+/// many digits they have.
+///
 /// # Returns
 ///
-/// * `Ok(String)` — `{"apiVersion": "1.0", "data": {...}, "warnings": [...]}`
+/// * `Ok(String)` — `{"apiVersion": "2.0", "data": {...}, "warnings": [...]}`
 /// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
 pub fn rehydrate_json(
     data_json: &str,
     codec_json: &str,
     original_schema_json: &str,
+) -> Result<String, String> {
+    rehydrate_json_with_options(data_json, codec_json, original_schema_json, "{}")
+}
+
+/// [`rehydrate_json`] with [`RehydrateOptions`] as a JSON string. Fields
+/// use `kebab-case` naming (e.g. `"aggregate-warnings"`, `"max-warnings"`);
+/// missing fields take their defaults.
+///
+/// # Returns
+///
+/// * `Ok(String)` — `{"apiVersion": "2.0", "data": {...}, "warnings": [...]}`
+/// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
+pub fn rehydrate_json_with_options(
+    data_json: &str,
+    codec_json: &str,
+    original_schema_json: &str,
+    options_json: &str,
 ) -> Result<String, String> {
     let data: Value =
         serde_json::from_str(data_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
@@ -412,7 +1181,50 @@ pub fn rehydrate_json(
         serde_json::from_str(codec_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
     let original_schema: Value = serde_json::from_str(original_schema_json)
         .map_err(|e| err_json(ConvertError::JsonError(e)))?;
-    let result = rehydrate(&data, &codec, &original_schema).map_err(err_json)?;
+    let options: RehydrateOptions =
+        serde_json::from_str(options_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
+    let result = rehydrate_owned(data, &codec, &original_schema, &options).map_err(err_json)?;
+    let bridge = BridgeRehydrateResult {
+        api_version: API_VERSION,
+        inner: &result,
+    };
+    serde_json::to_string(&bridge).map_err(|e| err_json(ConvertError::JsonError(e)))
+}
+
+/// Parse and compile a codec sidecar (as a JSON string) for
+/// [`rehydrate_compiled_json`].
+///
+/// # Returns
+///
+/// * `Ok(CompiledCodec)` — the codec, version-checked with its patterns compiled
+/// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
+pub fn compile_codec_json(codec_json: &str) -> Result<CompiledCodec, String> {
+    let codec: Codec =
+        serde_json::from_str(codec_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
+    CompiledCodec::new(codec).map_err(err_json)
+}
+
+/// [`rehydrate_json_with_options`] against a codec from
+/// [`compile_codec_json`], so a caller rehydrating many responses for one
+/// conversion parses and compiles the codec once.
+///
+/// # Returns
+///
+/// * `Ok(String)` — `{"apiVersion": "2.0", "data": {...}, "warnings": [...]}`
+/// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
+pub fn rehydrate_compiled_json(
+    data_json: &str,
+    codec: &CompiledCodec,
+    original_schema_json: &str,
+    options_json: &str,
+) -> Result<String, String> {
+    let data: Value =
+        serde_json::from_str(data_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
+    let original_schema: Value = serde_json::from_str(original_schema_json)
+        .map_err(|e| err_json(ConvertError::JsonError(e)))?;
+    let options: RehydrateOptions =
+        serde_json::from_str(options_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
+    let result = rehydrate_compiled(data, codec, &original_schema, &options).map_err(err_json)?;
     let bridge = BridgeRehydrateResult {
         api_version: API_VERSION,
         inner: &result,
@@ -429,10 +1241,10 @@ struct BridgeConvertAllResult<'a> {
     inner: &'a ConvertAllResult,
 }
 
-/// List all extractable component JSON Pointers in a schema (as a JSON string).
+/// List all extractable components in a schema (as a JSON string).
 ///
 /// This is the FFI-friendly entry point — accepts and returns plain JSON strings.
-/// The typed [`list_components`] API remains available for Rust consumers.
+/// The typed [`describe_components`] API remains available for Rust consumers.
 ///
 /// # Arguments
 ///
@@ -440,12 +1252,13 @@ struct BridgeConvertAllResult<'a> {
 ///
 /// # Returns
 ///
-/// * `Ok(String)` — `{"apiVersion": "1.0", "components": ["#/$defs/Foo", ...]}`
+/// * `Ok(String)` — `{"apiVersion": "2.0", "components": [{"pointer": "#/$defs/Foo", ...}, ...]}`,
+///   one [`ComponentInfo`] per component
 /// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
 pub fn list_components_json(schema_json: &str) -> Result<String, String> {
     let schema: Value =
         serde_json::from_str(schema_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
-    let components = list_components(&schema);
+    let components = describe_components(&schema).map_err(err_json)?;
     let result = serde_json::json!({
         "apiVersion": API_VERSION,
         "components": components,
@@ -467,7 +1280,7 @@ pub fn list_components_json(schema_json: &str) -> Result<String, String> {
 ///
 /// # Returns
 ///
-/// * `Ok(String)` — `{"apiVersion": "1.0", "schema": {...}, "pointer": "...", ...}`
+/// * `Ok(String)` — `{"apiVersion": "2.0", "schema": {...}, "pointer": "...", ...}`
 /// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
 pub fn extract_component_json(
     schema_json: &str,
@@ -504,7 +1317,7 @@ pub fn extract_component_json(
 ///
 /// # Returns
 ///
-/// * `Ok(String)` — `{"apiVersion": "1.0", "full": {...}, "components": [...], ...}`
+/// * `Ok(String)` — `{"apiVersion": "2.0", "full": {...}, "components": [...], ...}`
 /// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
 pub fn convert_all_components_json(
     schema_json: &str,
@@ -526,24 +1339,51 @@ pub fn convert_all_components_json(
     serde_json::to_string(&bridge).map_err(|e| err_json(ConvertError::JsonError(e)))
 }
 
-// ===========================================================================
-// Tests (TDD — written before implementation)
-// ===========================================================================
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json::json;
-
-    fn default_opts() -> ConvertOptions {
-        ConvertOptions::default()
-    }
-
-    fn default_extract_opts() -> ExtractOptions {
-        ExtractOptions::default()
-    }
-
-    // -----------------------------------------------------------------------
+/// Fingerprint a JSON Schema (as a JSON string) with options (as a JSON string).
+///
+/// This is the FFI-friendly entry point — accepts and returns plain JSON strings.
+/// The typed [`schema_fingerprint`] API remains available for Rust consumers.
+///
+/// # Arguments
+///
+/// * `schema_json` — A JSON Schema document as a string
+/// * `options_json` — Conversion options as a JSON string (kebab-case keys).
+///   Pass `"{}"` for defaults.
+///
+/// # Returns
+///
+/// * `Ok(String)` — `{"apiVersion": "2.0", "fingerprint": "sha256:..."}`
+/// * `Err(String)` — `{"code": "...", "message": "...", "path": ...}`
+pub fn schema_fingerprint_json(schema_json: &str, options_json: &str) -> Result<String, String> {
+    let schema: Value =
+        serde_json::from_str(schema_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
+    let options: ConvertOptions =
+        serde_json::from_str(options_json).map_err(|e| err_json(ConvertError::JsonError(e)))?;
+    let result = serde_json::json!({
+        "apiVersion": API_VERSION,
+        "fingerprint": schema_fingerprint(&schema, &options),
+    });
+    serde_json::to_string(&result).map_err(|e| err_json(ConvertError::JsonError(e)))
+}
+
+// ===========================================================================
+// Tests (TDD — written before implementation)
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn default_opts() -> ConvertOptions {
+        ConvertOptions::default()
+    }
+
+    fn default_extract_opts() -> ExtractOptions {
+        ExtractOptions::default()
+    }
+
+    // -----------------------------------------------------------------------
     // convert_all_components() — TDD gate tests (#176)
     // -----------------------------------------------------------------------
 
@@ -684,6 +1524,243 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_convert_all_reports_progress() {
+        use std::sync::{Arc, Mutex};
+
+        let schema = json!({
+            "$defs": {
+                "A": { "type": "string" },
+                "B": { "type": "integer" },
+                "C": { "type": "boolean" }
+            }
+        });
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let mut opts = default_opts();
+        opts.progress = Some(
+            ProgressSink::new(move |progress| sink.lock().unwrap().push(progress))
+                .component_step(2),
+        );
+        convert_all_components(&schema, &opts, &default_extract_opts()).unwrap();
+
+        let events = events.lock().unwrap();
+        let passes: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                Progress::Pass { pass, index, total } => Some((*pass, *index, *total)),
+                _ => None,
+            })
+            .collect();
+        // Reported for the full schema only, not again per component.
+        assert_eq!(passes.len(), BuiltinPass::ALL.len());
+        assert_eq!(passes[0], (BuiltinPass::Normalize, 1, 10));
+        assert_eq!(passes[9], (BuiltinPass::ProviderCompat, 10, 10));
+
+        let components: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                Progress::Components { done, total } => Some((*done, *total)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(components, vec![(0, 3), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn test_parallel_components_report_counts_only() {
+        use std::sync::{Arc, Mutex};
+
+        let schema = json!({
+            "$defs": {
+                "A": { "type": "string" },
+                "B": { "type": "integer" },
+                "C": { "type": "boolean" }
+            }
+        });
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let mut opts = default_opts();
+        opts.progress = Some(ProgressSink::new(move |progress| {
+            sink.lock().unwrap().push(progress)
+        }));
+        let extract_opts = default_extract_opts();
+        let stream = convert_all_components_streaming(&schema, &opts, &extract_opts).unwrap();
+        assert_eq!(stream.jobs(3).count(), 3);
+
+        let events = events.lock().unwrap();
+        let (passes, components): (Vec<_>, Vec<_>) = events
+            .iter()
+            .partition(|e| matches!(e, Progress::Pass { .. }));
+        assert_eq!(passes.len(), BuiltinPass::ALL.len());
+        let counts: Vec<_> = components
+            .iter()
+            .map(|e| match e {
+                Progress::Components { done, total } => (*done, *total),
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(counts, vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn test_cancellation_stops_at_next_node() {
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let mut opts = default_opts();
+        opts.cancellation = Some(token);
+        // Cancel as Pass 3 starts: its walk fails on the root node.
+        opts.progress = Some(ProgressSink::new(move |progress| {
+            if let Progress::Pass {
+                pass: BuiltinPass::Dictionary,
+                ..
+            } = progress
+            {
+                cancel.cancel();
+            }
+        }));
+        let schema = json!({"type": "object", "properties": {"a": {"type": "string"}}});
+        let err = convert(&schema, &opts).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::Cancelled);
+        assert_eq!(err.path(), Some("#"));
+        assert_eq!(err.to_json()["code"], "cancelled");
+    }
+
+    #[test]
+    fn test_convert_all_cancellation_aborts_batch() {
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let mut opts = default_opts();
+        opts.cancellation = Some(token);
+        opts.progress = Some(ProgressSink::new(move |progress| {
+            if let Progress::Components { done: 1, .. } = progress {
+                cancel.cancel();
+            }
+        }));
+        let schema = json!({
+            "$defs": {
+                "A": { "type": "string" },
+                "B": { "type": "integer" }
+            }
+        });
+        let err = convert_all_components(&schema, &opts, &default_extract_opts()).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::Cancelled);
+    }
+
+    #[test]
+    fn test_convert_all_streaming_matches_batch() {
+        let schema = json!({
+            "$defs": {
+                "A": { "type": "string" },
+                "B": { "type": "integer" },
+                "C": { "type": "object", "properties": { "a": { "$ref": "#/$defs/A" } } }
+            }
+        });
+        let batch =
+            convert_all_components(&schema, &default_opts(), &default_extract_opts()).unwrap();
+        let extract_opts = default_extract_opts();
+        let mut stream =
+            convert_all_components_streaming(&schema, &default_opts(), &extract_opts).unwrap();
+        assert_eq!(stream.total(), 3);
+        assert_eq!(stream.full.schema, batch.full.schema);
+
+        let mut converted = Vec::new();
+        let mut failed = Vec::new();
+        for (pointer, result) in &mut stream {
+            match result {
+                Ok(conv) => converted.push((pointer, conv.schema)),
+                Err(e) => failed.push((pointer, e.to_string())),
+            }
+        }
+        let batch_converted: Vec<_> = batch
+            .components
+            .into_iter()
+            .map(|(pointer, conv)| (pointer, conv.schema))
+            .collect();
+        assert_eq!(converted, batch_converted);
+        assert_eq!(failed, batch.component_errors);
+
+        // Parallel batches still yield in pointer order.
+        let parallel: Vec<_> =
+            convert_all_components_streaming(&schema, &default_opts(), &extract_opts)
+                .unwrap()
+                .jobs(2)
+                .map(|(pointer, result)| (pointer, result.unwrap().schema))
+                .collect();
+        assert_eq!(parallel, batch_converted);
+
+        let rest = convert_all_components_streaming(&schema, &default_opts(), &extract_opts)
+            .unwrap()
+            .skip_where(|pointer| pointer == "#/$defs/A");
+        assert_eq!(rest.total(), 2);
+        let pointers: Vec<String> = rest.map(|(pointer, _)| pointer).collect();
+        assert_eq!(pointers, vec!["#/$defs/B", "#/$defs/C"]);
+    }
+
+    /// Drops the root's `required`, as a buggy pass might.
+    struct DropRequired;
+
+    impl pass::ConversionPass for DropRequired {
+        fn name(&self) -> &str {
+            "drop-required"
+        }
+
+        fn run(
+            &self,
+            mut schema: Value,
+            _ctx: &pass::PassContext<'_>,
+        ) -> Result<PassResult, ConvertError> {
+            if let Some(obj) = schema.as_object_mut() {
+                obj.remove("required");
+            }
+            Ok(PassResult::schema_only(schema))
+        }
+    }
+
+    #[test]
+    fn test_verify_output_fails_on_nonconforming_schema() {
+        let schema = json!({"type": "object", "properties": {"a": {"type": "string"}}});
+        let mut opts = default_opts();
+        opts.verify_output = true;
+        assert!(convert(&schema, &opts).is_ok());
+
+        opts.extra_passes = vec![pass::ExtraPass::new(
+            BuiltinPass::ProviderCompat,
+            DropRequired,
+        )];
+        let err = convert(&schema, &opts).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::ProviderCompatFailure);
+        assert_eq!(err.path(), Some("#"));
+        assert!(err.to_string().contains("openai-strict"), "{}", err);
+        assert!(err.to_string().contains("'required'"), "{}", err);
+
+        opts.verify_output = false;
+        assert!(convert(&schema, &opts).is_ok());
+    }
+
+    #[test]
+    fn test_validate_input_reports_all_issues() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "a": { "type": "string", "maxLength": "10" },
+                "b": { "$ref": "#/$defs/B" }
+            },
+            "required": "a",
+            "$defs": { "B": { "type": "object", "properties": [] } }
+        });
+        let mut opts = default_opts();
+        opts.validate_input = true;
+        let err = convert(&schema, &opts).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::SchemaError);
+        let ConvertError::InvalidSchema { issues } = &err else {
+            panic!("expected InvalidSchema, got {err:?}");
+        };
+        assert_eq!(issues.len(), 3, "{}", err);
+        assert_eq!(err.path(), Some("#/$defs/B"));
+        assert!(err.to_string().contains("3 issue(s)"), "{}", err);
+    }
+
     // -----------------------------------------------------------------------
     // skip_components in ConvertOptions serde round-trip (#176)
     // -----------------------------------------------------------------------
@@ -714,6 +1791,24 @@ mod tests {
     // Bridge JSON API — unit tests (#177)
     // -----------------------------------------------------------------------
 
+    #[test]
+    fn test_version_json_describes_build() {
+        let info: Value = serde_json::from_str(&version_json()).unwrap();
+        assert_eq!(info["apiVersion"], API_VERSION);
+        assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(info["codecMajorVersion"], codec::CODEC_MAJOR_VERSION);
+        assert_eq!(
+            info["targets"],
+            json!(["openai-strict", "gemini", "claude"])
+        );
+        let features = info["features"].as_array().unwrap();
+        assert_eq!(
+            features.iter().any(|f| f == "regex"),
+            cfg!(feature = "regex")
+        );
+        assert_eq!(features.iter().any(|f| f == "otel"), cfg!(feature = "otel"));
+    }
+
     #[test]
     fn test_list_components_json_returns_sorted_pointers() {
         let schema = json!({
@@ -726,12 +1821,14 @@ mod tests {
         let result =
             list_components_json(&schema_json).expect("list_components_json should succeed");
         let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
-        assert_eq!(parsed["apiVersion"], "1.0");
+        assert_eq!(parsed["apiVersion"], "2.0");
         let components = parsed["components"].as_array().unwrap();
         assert_eq!(components.len(), 2);
         // Sorted A < B
-        assert_eq!(components[0], "#/$defs/A");
-        assert_eq!(components[1], "#/$defs/B");
+        assert_eq!(components[0]["pointer"], "#/$defs/A");
+        assert_eq!(components[1]["pointer"], "#/$defs/B");
+        assert_eq!(components[0]["propertyCount"], 0);
+        assert_eq!(components[0]["refsIn"], json!([]));
     }
 
     #[test]
@@ -750,6 +1847,23 @@ mod tests {
         assert!(err.get("code").is_some(), "error should have a code field");
     }
 
+    #[test]
+    fn test_schema_fingerprint_json() {
+        let a = schema_fingerprint_json(r#"{"type": "string", "minLength": 1}"#, "{}").unwrap();
+        let b = schema_fingerprint_json(r#"{"minLength": 1, "type": "string"}"#, "{}").unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&a).unwrap();
+        assert_eq!(parsed["apiVersion"], "2.0");
+        assert!(parsed["fingerprint"]
+            .as_str()
+            .unwrap()
+            .starts_with("sha256:"));
+        assert_eq!(a, b);
+
+        let gemini = schema_fingerprint_json(r#"{"type": "string"}"#, r#"{"target": "gemini"}"#);
+        assert_ne!(gemini.unwrap(), a);
+        assert!(schema_fingerprint_json("not json", "{}").is_err());
+    }
+
     #[test]
     fn test_extract_component_json_happy_path() {
         let schema = json!({
@@ -762,7 +1876,7 @@ mod tests {
         let result = extract_component_json(&schema_json, "#/$defs/Pet", "{}")
             .expect("extract_component_json should succeed");
         let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
-        assert_eq!(parsed["apiVersion"], "1.0");
+        assert_eq!(parsed["apiVersion"], "2.0");
         assert_eq!(parsed["pointer"], "#/$defs/Pet");
         assert!(parsed["schema"].is_object());
         assert_eq!(parsed["dependencyCount"], 0);
@@ -805,7 +1919,7 @@ mod tests {
         let result = convert_all_components_json(&schema_json, "{}", "{}")
             .expect("convert_all_components_json should succeed");
         let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
-        assert_eq!(parsed["apiVersion"], "1.0");
+        assert_eq!(parsed["apiVersion"], "2.0");
         assert!(parsed["full"].is_object());
         let components = parsed["components"].as_array().unwrap();
         assert_eq!(components.len(), 2);
@@ -838,6 +1952,579 @@ mod tests {
         assert!(opts.max_depth.is_none());
     }
 
+    // -----------------------------------------------------------------------
+    // rehydrate_text() — text-in/text-out rehydration
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_rehydrate_text_matches_value_api() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tags": { "type": "object", "additionalProperties": { "type": "integer" } },
+                "count": { "type": "integer" }
+            },
+            "required": ["tags"]
+        });
+        let converted = convert(&schema, &default_opts()).unwrap();
+        let llm_output = json!({
+            "tags": [{ "key": "a", "value": 1 }],
+            "count": "7"
+        });
+
+        let via_value = rehydrate(&llm_output, &converted.codec, &schema).unwrap();
+        let via_text = rehydrate_text(&llm_output.to_string(), &converted.codec, &schema).unwrap();
+
+        let parsed: Value = serde_json::from_str(&via_text.data).unwrap();
+        assert_eq!(parsed, via_value.data);
+        assert_eq!(parsed, json!({ "tags": { "a": 1 }, "count": 7 }));
+        assert_eq!(via_text.warnings.len(), via_value.warnings.len());
+    }
+
+    #[test]
+    fn test_rehydrate_text_rejects_trailing_characters_like_rehydrate_json() {
+        let schema = json!({ "type": "object", "properties": { "a": { "type": "string" } } });
+        let converted = convert(&schema, &default_opts()).unwrap();
+        let codec_json = serde_json::to_string(&converted.codec).unwrap();
+        let schema_json = schema.to_string();
+
+        let err = rehydrate_text(r#"{"a": "x"}}"#, &converted.codec, &schema).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::JsonParseError);
+        let bridge_err = rehydrate_json(r#"{"a": "x"}}"#, &codec_json, &schema_json).unwrap_err();
+        let bridge_err: Value = serde_json::from_str(&bridge_err).unwrap();
+        assert_eq!(bridge_err["code"], "json_parse_error");
+
+        let result = rehydrate_text("{\"a\": \"x\"}\n", &converted.codec, &schema).unwrap();
+        assert_eq!(result.data, r#"{"a":"x"}"#);
+        assert!(rehydrate_json("{\"a\": \"x\"}\n", &codec_json, &schema_json).is_ok());
+    }
+
+    #[test]
+    fn test_rehydrate_text_invalid_json_is_parse_error() {
+        let codec = Codec::new();
+        let err = rehydrate_text("{not json", &codec, &json!({})).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::JsonParseError);
+    }
+
+    #[test]
+    fn test_rehydrate_compiled_matches_value_api() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tags": { "type": "object", "patternProperties": { "^x-": { "type": "integer" } } },
+                "code": { "type": "string", "pattern": "^[A-Z]{3}$" }
+            }
+        });
+        let converted = convert(&schema, &default_opts()).unwrap();
+        let compiled = CompiledCodec::new(converted.codec.clone()).unwrap();
+        for llm_output in [json!({ "code": "ABC" }), json!({ "code": "abcd" })] {
+            let via_value = rehydrate(&llm_output, &converted.codec, &schema).unwrap();
+            let via_compiled =
+                rehydrate_compiled(llm_output, &compiled, &schema, &RehydrateOptions::default())
+                    .unwrap();
+            assert_eq!(via_compiled.data, via_value.data);
+            assert_eq!(
+                serde_json::to_value(&via_compiled.warnings).unwrap(),
+                serde_json::to_value(&via_value.warnings).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_compiled_codec_rejects_incompatible_version() {
+        let mut codec = Codec::new();
+        codec.schema = "https://json-schema-llm.dev/codec/v99".to_string();
+        let err = CompiledCodec::new(codec).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::CodecVersionMismatch);
+    }
+
+    // -----------------------------------------------------------------------
+    // Resource limits — max_schema_bytes / max_nodes / time_budget_ms
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_convert_max_schema_bytes_exceeded() {
+        let schema = json!({ "type": "object", "properties": { "a": { "type": "string" } } });
+        let mut opts = default_opts();
+        opts.max_schema_bytes = Some(16);
+        let err = convert(&schema, &opts).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::ResourceLimitExceeded);
+        assert_eq!(err.path(), Some("#"));
+    }
+
+    #[test]
+    fn test_convert_max_nodes_catches_ref_fan_out() {
+        // Each level references the previous one twice: 2^6 expanded leaves
+        // from a handful of definitions.
+        let mut defs = serde_json::Map::new();
+        defs.insert("l0".into(), json!({ "type": "string" }));
+        for i in 1..=6 {
+            let prev = format!("#/$defs/l{}", i - 1);
+            defs.insert(
+                format!("l{i}"),
+                json!({
+                    "type": "object",
+                    "properties": { "a": { "$ref": prev }, "b": { "$ref": prev } }
+                }),
+            );
+        }
+        let schema = json!({ "$ref": "#/$defs/l6", "$defs": defs });
+
+        let mut opts = default_opts();
+        assert!(convert(&schema, &opts).is_ok());
+
+        opts.max_nodes = Some(50);
+        let err = convert(&schema, &opts).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::ResourceLimitExceeded);
+        assert!(err.to_string().contains("max_nodes"), "got: {err}");
+    }
+
+    #[test]
+    fn test_convert_max_nodes_trips_mid_pass() {
+        // Small for Pass 0, which leaves the recursive refs alone; Pass 5
+        // inlines them three levels deep.
+        let schema = json!({
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": {
+                        "a": { "$ref": "#/$defs/Node" },
+                        "b": { "$ref": "#/$defs/Node" },
+                        "c": { "$ref": "#/$defs/Node" }
+                    }
+                }
+            }
+        });
+        let mut opts = default_opts();
+        opts.max_nodes = Some(40);
+        let err = convert(&schema, &opts).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::ResourceLimitExceeded);
+        assert!(err.to_string().contains("max_nodes"), "got: {err}");
+        let path = err.path().unwrap();
+        assert!(path.matches("/properties/").count() >= 2, "got: {path}");
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_convert_zero_time_budget_exceeded() {
+        let schema = json!({ "type": "object", "properties": { "a": { "type": "string" } } });
+        let mut opts = default_opts();
+        opts.time_budget_ms = Some(0);
+        let err = convert(&schema, &opts).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::ResourceLimitExceeded);
+        assert!(err.to_string().contains("time_budget_ms"), "got: {err}");
+    }
+
+    #[test]
+    fn test_convert_json_rejects_oversized_text_before_parsing() {
+        // Not valid JSON: the size check must fire before the parser does.
+        let schema_json = "x".repeat(64);
+        let err = convert_json(&schema_json, r#"{"max-schema-bytes": 32}"#).unwrap_err();
+        let err: Value = serde_json::from_str(&err).unwrap();
+        assert_eq!(err["code"], "resource_limit_exceeded");
+    }
+
+    // -----------------------------------------------------------------------
+    // convert_str() — source locations for text input
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_convert_str_unresolvable_ref_has_location() {
+        let text = "{\n  \"type\": \"object\",\n  \"properties\": {\n    \"a\": { \"$ref\": \"#/$defs/Nope\" }\n  }\n}";
+        let err = convert_str(text, &default_opts()).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::UnresolvableRef);
+        assert_eq!(
+            err.location(),
+            Some(SourceLocation {
+                line: 4,
+                column: 20
+            })
+        );
+        assert!(
+            err.to_string().contains("(line 4, column 20)"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn test_on_error_placeholder_converts_and_rehydrates() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "customer": { "$ref": "#/$defs/Missing" }
+            },
+            "required": ["id", "customer"]
+        });
+        let options = ConvertOptions {
+            on_error: ErrorPolicy::Placeholder,
+            ..default_opts()
+        };
+        let result = convert(&schema, &options).unwrap();
+        assert_eq!(result.schema["properties"]["customer"]["type"], "string");
+        assert!(matches!(
+            &result.provider_compat_errors[..],
+            [ProviderCompatError::UnresolvableRefRecovered { path, .. }]
+                if path == "#/properties/customer"
+        ));
+
+        let output = json!({ "id": "o-1", "customer": "{\"name\":\"Ada\"}" });
+        let rehydrated = rehydrate(&output, &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data["customer"], json!({ "name": "Ada" }));
+    }
+
+    #[test]
+    fn test_convert_str_schema_error_has_location() {
+        let text = r#"{
+  "type": "object",
+  "properties": {
+    "x": {
+      "allOf": [{ "type": "string" }, { "type": "boolean" }]
+    }
+  }
+}"#;
+        let err = convert_str(text, &default_opts()).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::SchemaError);
+        assert_eq!(
+            err.location(),
+            Some(SourceLocation {
+                line: 4,
+                column: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_value_errors_have_no_location() {
+        let schema = json!({ "properties": { "a": { "$ref": "#/$defs/Nope" } } });
+        let err = convert(&schema, &default_opts()).unwrap_err();
+        assert_eq!(err.location(), None);
+        assert!(err.to_json().get("line").is_none());
+    }
+
+    #[test]
+    fn test_convert_json_error_includes_line_and_column() {
+        let text = "{\n  \"$ref\": \"#/$defs/Nope\"\n}";
+        let err = convert_json(text, "{}").unwrap_err();
+        let err: Value = serde_json::from_str(&err).unwrap();
+        assert_eq!(err["code"], "unresolvable_ref");
+        assert_eq!(err["line"], 2);
+        assert_eq!(err["column"], 11);
+    }
+
+    const DUPLICATED: &str = r#"{
+  "type": "object",
+  "properties": {
+    "id": { "type": "string" },
+    "id": { "type": "integer" }
+  },
+  "required": ["id"]
+}"#;
+
+    #[test]
+    fn test_convert_json_duplicate_keys() {
+        // Default: the last occurrence wins silently.
+        let out: Value = serde_json::from_str(&convert_json(DUPLICATED, "{}").unwrap()).unwrap();
+        assert_eq!(out["schema"]["properties"]["id"]["type"], "integer");
+        assert!(out.get("provider_compat_errors").is_none());
+
+        let out = convert_json(DUPLICATED, r#"{"duplicate-keys": "warn"}"#).unwrap();
+        let out: Value = serde_json::from_str(&out).unwrap();
+        let diagnostics = out["provider_compat_errors"].as_array().unwrap();
+        assert_eq!(diagnostics[0]["type"], "duplicate_schema_key");
+        assert_eq!(diagnostics[0]["ruleId"], "JSL-P0-003");
+        assert_eq!(diagnostics[0]["path"], "#/properties/id");
+
+        let err = convert_json(DUPLICATED, r#"{"duplicate-keys": "error"}"#).unwrap_err();
+        let err: Value = serde_json::from_str(&err).unwrap();
+        assert_eq!(err["code"], "schema_error");
+        assert_eq!(err["path"], "#/properties/id");
+        assert_eq!(err["line"], 5);
+        assert_eq!(err["column"], 5);
+    }
+
+    #[test]
+    fn test_check_duplicate_keys_honours_suppress() {
+        let options = ConvertOptions {
+            duplicate_keys: DuplicateKeyCheck::Warn,
+            ..default_opts()
+        };
+        assert_eq!(check_duplicate_keys(DUPLICATED, &options).unwrap().len(), 1);
+
+        let options = ConvertOptions {
+            suppress: vec![RuleSelector::rule("JSL-P0-003")],
+            ..options
+        };
+        assert!(check_duplicate_keys(DUPLICATED, &options)
+            .unwrap()
+            .is_empty());
+        let result = convert_str(DUPLICATED, &options).unwrap();
+        assert_eq!(result.suppressed_compat_errors.len(), 1);
+    }
+
+    #[test]
+    fn test_check_duplicate_keys_deep_nesting() {
+        let options = ConvertOptions {
+            duplicate_keys: DuplicateKeyCheck::Warn,
+            ..default_opts()
+        };
+        let text = format!("{}{}", "[".repeat(50_000), "]".repeat(50_000));
+        assert!(check_duplicate_keys(&text, &options).unwrap().is_empty());
+    }
+
+    const PRECISE: &str = r#"{"type":"object","properties":{"id":{"type":"number","maximum":12345678901234567890123}},"required":["id"]}"#;
+
+    #[test]
+    fn test_convert_str_rounds_numbers_by_default() {
+        let options = ConvertOptions {
+            target: Target::Gemini,
+            ..default_opts()
+        };
+        let result = convert_str(PRECISE, &options).unwrap();
+        assert_eq!(
+            result.schema["properties"]["id"]["maximum"],
+            json!(1.2345678901234568e22)
+        );
+    }
+
+    #[test]
+    fn test_const_restored_exactly_on_every_target() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "v": { "const": 1 } },
+            "required": ["v"]
+        });
+        for target in Target::ALL {
+            let options = ConvertOptions {
+                target,
+                ..default_opts()
+            };
+            let result = convert(&schema, &options).unwrap();
+            let rehydrated = rehydrate(&json!({ "v": 1.0 }), &result.codec, &schema).unwrap();
+            assert_eq!(
+                serde_json::to_string(&rehydrated.data).unwrap(),
+                r#"{"v":1}"#,
+                "{target:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_const_restored_exactly_on_rehydrate() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "code": { "type": "string", "const": "007" },
+                "size": { "anyOf": [{ "const": "s" }, { "const": "l" }] },
+                "opt": { "type": "string", "const": "fixed" }
+            },
+            "required": ["code", "size"]
+        });
+        let result = convert(&schema, &default_opts()).unwrap();
+        assert_eq!(result.schema["properties"]["code"]["enum"], json!(["007"]));
+
+        let rehydrated = rehydrate(
+            &json!({ "code": 7, "size": "l", "opt": null }),
+            &result.codec,
+            &schema,
+        )
+        .unwrap();
+        assert_eq!(rehydrated.data, json!({ "code": "007", "size": "l" }));
+        let restored: Vec<_> = rehydrated
+            .warnings
+            .iter()
+            .filter(|w| matches!(&w.kind, codec_warning::WarningKind::ConstraintViolation { constraint } if constraint == "const"))
+            .map(|w| w.message.as_str())
+            .collect();
+        assert_eq!(restored, ["value \"7\" restored to constant \"007\""]);
+
+        let rehydrated = rehydrate(
+            &json!({ "code": "008", "size": "s", "opt": "fixed" }),
+            &result.codec,
+            &schema,
+        )
+        .unwrap();
+        let violations: Vec<_> = rehydrated
+            .warnings
+            .iter()
+            .map(|w| (w.data_path.as_str(), w.message.as_str()))
+            .collect();
+        assert_eq!(
+            violations,
+            [("/code", "value \"008\" does not equal constant \"007\"")]
+        );
+
+        // A string the model produced is not rewritten, however numeric.
+        let rehydrated =
+            rehydrate(&json!({ "code": "7", "size": "s" }), &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data["code"], "7");
+        let messages: Vec<_> = rehydrated
+            .warnings
+            .iter()
+            .map(|w| w.message.as_str())
+            .collect();
+        assert_eq!(messages, ["value \"7\" does not equal constant \"007\""]);
+    }
+
+    #[test]
+    fn test_enum_descriptions_fold_referenced_union() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "size": { "$ref": "#/$defs/Size" } },
+            "required": ["size"],
+            "$defs": {
+                "Size": {
+                    "oneOf": [
+                        { "const": "s", "description": "Small" },
+                        { "const": "l", "description": "Large" }
+                    ]
+                }
+            }
+        });
+        let options = ConvertOptions {
+            enum_descriptions: EnumDescriptionStyle::Fold,
+            ..default_opts()
+        };
+        let result = convert(&schema, &options).unwrap();
+        assert_eq!(
+            result.schema["properties"]["size"],
+            json!({ "enum": ["s", "l"], "description": "Values: \"s\": Small; \"l\": Large." })
+        );
+
+        let data = json!({ "size": "l" });
+        let rehydrated = rehydrate(&data, &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data, data);
+        assert!(rehydrated.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_convert_str_preserve_precision() {
+        let options = ConvertOptions {
+            target: Target::Gemini,
+            preserve_precision: true,
+            ..default_opts()
+        };
+        let result = convert_str(PRECISE, &options);
+        if cfg!(feature = "arbitrary-precision") {
+            let maximum = &result.unwrap().schema["properties"]["id"]["maximum"];
+            assert_eq!(maximum.to_string(), "12345678901234567890123");
+        } else {
+            assert!(matches!(
+                result,
+                Err(ConvertError::UnsupportedFeature { .. })
+            ));
+        }
+    }
+
+    fn mixed_enum_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "legacy": { "enum": ["a", 1] },
+                "current": { "enum": ["b", 2] }
+            },
+            "required": ["legacy", "current"]
+        })
+    }
+
+    #[test]
+    fn test_suppress_moves_matching_diagnostics_only() {
+        let options = ConvertOptions {
+            suppress: vec![RuleSelector::at("JSL-P9-003", "#/properties/legacy")],
+            ..ConvertOptions::default()
+        };
+        let result = convert(&mixed_enum_schema(), &options).unwrap();
+
+        let paths = |errs: &[ProviderCompatError]| -> Vec<String> {
+            errs.iter().map(|e| e.path().to_string()).collect()
+        };
+        assert_eq!(
+            paths(&result.suppressed_compat_errors),
+            ["#/properties/legacy"]
+        );
+        assert_eq!(
+            paths(&result.provider_compat_errors),
+            ["#/properties/current"]
+        );
+        // Suppression is reporting-only: the schema is the same either way.
+        let unsuppressed = convert(&mixed_enum_schema(), &ConvertOptions::default()).unwrap();
+        assert_eq!(result.schema, unsuppressed.schema);
+        assert!(unsuppressed.suppressed_compat_errors.is_empty());
+    }
+
+    #[test]
+    fn test_convert_str_gemini_property_ordering_follows_source() {
+        let text = r##"{
+            "type": "object",
+            "properties": {
+                "reasoning": { "type": "string" },
+                "answer": { "$ref": "#/$defs/Answer" }
+            },
+            "$defs": {
+                "Answer": {
+                    "allOf": [
+                        { "type": "object", "properties": { "value": { "type": "string" } } },
+                        { "properties": { "confidence": { "type": "number" } } }
+                    ]
+                }
+            }
+        }"##;
+        let options = ConvertOptions {
+            target: Target::Gemini,
+            property_ordering: PropertyOrdering::Original,
+            ..ConvertOptions::default()
+        };
+        let result = convert_str(text, &options).unwrap();
+        assert_eq!(
+            result.schema["propertyOrdering"],
+            json!(["reasoning", "answer"])
+        );
+        assert_eq!(
+            result.schema["properties"]["answer"]["anyOf"][0]["propertyOrdering"],
+            json!(["value", "confidence"])
+        );
+
+        // Off by default, and never emitted for other targets.
+        let plain = convert_str(text, &ConvertOptions::default()).unwrap();
+        assert!(!plain.schema.to_string().contains("propertyOrdering"));
+        let openai = ConvertOptions {
+            property_ordering: PropertyOrdering::Original,
+            ..ConvertOptions::default()
+        };
+        let openai = convert_str(text, &openai).unwrap();
+        assert!(!openai.schema.to_string().contains("propertyOrdering"));
+    }
+
+    #[test]
+    fn test_convert_envelope_wraps_final_schema() {
+        let schema = json!({
+            "title": "Person",
+            "type": "object",
+            "properties": { "name": { "type": "string" } }
+        });
+        let plain = convert(&schema, &ConvertOptions::default()).unwrap();
+        assert!(plain.envelope.is_none());
+
+        let options: ConvertOptions =
+            serde_json::from_str(r#"{"envelope": "response-format"}"#).unwrap();
+        let result = convert(&schema, &options).unwrap();
+        let envelope = result.envelope.as_ref().unwrap();
+        assert_eq!(envelope["type"], "json_schema");
+        assert_eq!(envelope["json_schema"]["name"], "Person");
+        assert_eq!(envelope["json_schema"]["strict"], true);
+        assert_eq!(envelope["json_schema"]["schema"], result.schema);
+    }
+
+    #[test]
+    fn test_suppress_via_convert_json_options() {
+        let options = r#"{"suppress": [{"rule": "JSL-P9-003"}]}"#;
+        let out = convert_json(&mixed_enum_schema().to_string(), options).unwrap();
+        let out: Value = serde_json::from_str(&out).unwrap();
+        assert!(out.get("provider_compat_errors").is_none());
+        assert_eq!(out["suppressed_compat_errors"].as_array().unwrap().len(), 2);
+    }
+
     // -----------------------------------------------------------------------
     // apply_patch_json() — JSON Patch bridge tests (#261)
     // -----------------------------------------------------------------------
@@ -865,7 +2552,7 @@ mod tests {
         )
         .expect("apply_patch_json should succeed");
         let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
-        assert_eq!(parsed["apiVersion"], "1.0");
+        assert_eq!(parsed["apiVersion"], "2.0");
         let patched = &parsed["schema"];
         assert_eq!(
             patched["properties"]["config"]["properties"]["rate"]["type"],
@@ -0,0 +1,307 @@
+//! Resource guards for a single conversion.
+//!
+//! Enforces [`ConvertOptions::max_schema_bytes`], [`ConvertOptions::max_nodes`]
+//! and [`ConvertOptions::time_budget_ms`] so that hostile inputs fail with
+//! [`ConvertError::ResourceLimitExceeded`] instead of exhausting the host,
+//! and observes [`ConvertOptions::cancellation`].
+
+use std::cell::Cell;
+use std::io;
+
+use serde_json::Value;
+
+use crate::cancel::CancellationToken;
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+
+/// How many node visits between wall-clock checks. Reading the clock on
+/// every node would dominate the cost of small schemas.
+const CLOCK_CHECK_INTERVAL: usize = 256;
+
+pub(crate) use clock::Stopwatch;
+use clock::{deadline_passed, start_deadline, Deadline};
+
+/// Tracks node visits, elapsed time and cancellation for one `convert` call.
+///
+/// Interior mutability lets passes share the guard by `&` reference.
+#[derive(Debug)]
+pub(crate) struct ResourceGuard {
+    max_nodes: Option<usize>,
+    nodes: Cell<usize>,
+    budget_ms: Option<u64>,
+    deadline: Deadline,
+    cancellation: Option<CancellationToken>,
+}
+
+impl ResourceGuard {
+    /// Start the clock for a conversion with the given options.
+    pub(crate) fn new(options: &ConvertOptions) -> Self {
+        Self {
+            max_nodes: options.max_nodes,
+            nodes: Cell::new(0),
+            budget_ms: options.time_budget_ms,
+            deadline: start_deadline(options.time_budget_ms),
+            cancellation: options.cancellation.clone(),
+        }
+    }
+
+    /// Whether a node budget is configured.
+    pub(crate) fn counts_nodes(&self) -> bool {
+        self.max_nodes.is_some()
+    }
+
+    /// Node visits recorded since the current pass started.
+    pub(crate) fn nodes(&self) -> usize {
+        self.nodes.get()
+    }
+
+    /// Record one node visit at `path`, failing if the node budget is spent.
+    pub(crate) fn tick(&self, path: &str) -> Result<(), ConvertError> {
+        self.tick_n(path, 1)
+    }
+
+    /// Record `n` node visits at `path` (e.g. a memoized subtree being
+    /// copied in). Also checks for cancellation, and the time budget every
+    /// [`CLOCK_CHECK_INTERVAL`] visits.
+    pub(crate) fn tick_n(&self, path: &str, n: usize) -> Result<(), ConvertError> {
+        self.check_cancelled(path)?;
+        let before = self.nodes.get();
+        let after = before.saturating_add(n);
+        self.nodes.set(after);
+        if let Some(max) = self.max_nodes {
+            if after > max {
+                return Err(ConvertError::ResourceLimitExceeded {
+                    path: path.to_string(),
+                    resource: "max_nodes".to_string(),
+                    limit: max as u64,
+                });
+            }
+        }
+        if after / CLOCK_CHECK_INTERVAL != before / CLOCK_CHECK_INTERVAL {
+            self.check_time(path)?;
+        }
+        Ok(())
+    }
+
+    /// Start the next pass: fail if the conversion was cancelled or the time
+    /// budget has elapsed, then reset the node count, which
+    /// [`ConvertOptions::max_nodes`] bounds per pass.
+    pub(crate) fn start_pass(&self, path: &str) -> Result<(), ConvertError> {
+        self.check(path)?;
+        self.nodes.set(0);
+        Ok(())
+    }
+
+    /// Fail if the conversion was cancelled or the time budget has elapsed.
+    pub(crate) fn check(&self, path: &str) -> Result<(), ConvertError> {
+        self.check_cancelled(path)?;
+        self.check_time(path)
+    }
+
+    fn check_cancelled(&self, path: &str) -> Result<(), ConvertError> {
+        match &self.cancellation {
+            Some(token) => token.check(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail if the time budget has elapsed.
+    pub(crate) fn check_time(&self, path: &str) -> Result<(), ConvertError> {
+        if deadline_passed(&self.deadline) {
+            return Err(ConvertError::ResourceLimitExceeded {
+                path: path.to_string(),
+                resource: "time_budget_ms".to_string(),
+                limit: self.budget_ms.unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    feature = "time",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+mod clock {
+    use std::time::{Duration, Instant};
+
+    pub(super) type Deadline = Option<Instant>;
+
+    /// Measures elapsed wall-clock time.
+    pub(crate) struct Stopwatch(Instant);
+
+    impl Stopwatch {
+        pub(crate) fn start() -> Self {
+            Self(Instant::now())
+        }
+
+        pub(crate) fn elapsed(&self) -> Duration {
+            self.0.elapsed()
+        }
+    }
+
+    pub(super) fn start_deadline(budget_ms: Option<u64>) -> Deadline {
+        let now = Instant::now();
+        // An unrepresentable deadline is as good as no deadline.
+        budget_ms.and_then(|ms| now.checked_add(Duration::from_millis(ms)))
+    }
+
+    pub(super) fn deadline_passed(deadline: &Deadline) -> bool {
+        deadline.is_some_and(|d| Instant::now() >= d)
+    }
+}
+
+// Without the `time` feature, and on wasm32-unknown-unknown (no monotonic
+// clock; `Instant::now()` panics there), the time budget is not enforced and
+// pass timings read zero.
+#[cfg(not(all(
+    feature = "time",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+)))]
+mod clock {
+    use std::time::Duration;
+
+    pub(super) type Deadline = Option<()>;
+
+    /// Stand-in that always reports zero elapsed time.
+    pub(crate) struct Stopwatch;
+
+    impl Stopwatch {
+        pub(crate) fn start() -> Self {
+            Self
+        }
+
+        pub(crate) fn elapsed(&self) -> Duration {
+            Duration::ZERO
+        }
+    }
+
+    pub(super) fn start_deadline(_budget_ms: Option<u64>) -> Deadline {
+        None
+    }
+
+    pub(super) fn deadline_passed(_deadline: &Deadline) -> bool {
+        false
+    }
+}
+
+/// Check an input of `len` bytes against [`ConvertOptions::max_schema_bytes`].
+pub(crate) fn check_schema_bytes(len: usize, options: &ConvertOptions) -> Result<(), ConvertError> {
+    match options.max_schema_bytes {
+        Some(max) if len > max => Err(ConvertError::ResourceLimitExceeded {
+            path: "#".to_string(),
+            resource: "max_schema_bytes".to_string(),
+            limit: max as u64,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Compact serialized size of `schema`, computed without allocating the text.
+pub(crate) fn serialized_len(schema: &Value) -> usize {
+    struct Counter(usize);
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut counter = Counter(0);
+    // Writing a `Value` into an infallible sink cannot fail.
+    let _ = serde_json::to_writer(&mut counter, schema);
+    counter.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use serde_json::json;
+
+    fn opts() -> ConvertOptions {
+        ConvertOptions::default()
+    }
+
+    #[test]
+    fn test_serialized_len_matches_to_string() {
+        let schema = json!({"type": "object", "properties": {"a": {"type": "string"}}});
+        assert_eq!(
+            serialized_len(&schema),
+            serde_json::to_string(&schema).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_check_schema_bytes() {
+        let mut o = opts();
+        assert!(check_schema_bytes(1_000_000, &o).is_ok());
+        o.max_schema_bytes = Some(10);
+        assert!(check_schema_bytes(10, &o).is_ok());
+        let err = check_schema_bytes(11, &o).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::ResourceLimitExceeded);
+    }
+
+    #[test]
+    fn test_tick_node_limit() {
+        let mut o = opts();
+        o.max_nodes = Some(2);
+        let guard = ResourceGuard::new(&o);
+        assert!(guard.tick("#").is_ok());
+        assert!(guard.tick("#/a").is_ok());
+        let err = guard.tick("#/b").unwrap_err();
+        assert_eq!(err.path(), Some("#/b"));
+        assert!(err.to_string().contains("max_nodes"));
+    }
+
+    #[test]
+    fn test_default_options_never_trip() {
+        let guard = ResourceGuard::new(&opts());
+        for _ in 0..1000 {
+            guard.tick("#").unwrap();
+        }
+        guard.tick_n("#", usize::MAX).unwrap();
+        guard.check_time("#").unwrap();
+    }
+
+    #[test]
+    fn test_tick_n_charges_whole_subtree() {
+        let mut o = opts();
+        o.max_nodes = Some(10);
+        let guard = ResourceGuard::new(&o);
+        guard.tick_n("#", 10).unwrap();
+        assert!(guard.tick_n("#/x", 1).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_zero_time_budget_trips() {
+        let mut o = opts();
+        o.time_budget_ms = Some(0);
+        let guard = ResourceGuard::new(&o);
+        let err = guard.check_time("#").unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::ResourceLimitExceeded);
+        assert!(err.to_string().contains("time_budget_ms"));
+    }
+
+    #[test]
+    fn test_cancelled_token_trips_tick_and_check() {
+        let token = CancellationToken::new();
+        let mut o = opts();
+        o.cancellation = Some(token.clone());
+        let guard = ResourceGuard::new(&o);
+        guard.tick("#").unwrap();
+        guard.check("#").unwrap();
+
+        token.cancel();
+        let err = guard.tick("#/a").unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::Cancelled);
+        assert_eq!(err.path(), Some("#/a"));
+        assert_eq!(
+            guard.check("#").unwrap_err().error_code(),
+            ErrorCode::Cancelled
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Pre-flight compatibility analysis.
+//!
+//! [`lint`] runs a conversion in memory and reports everything it would
+//! change — provider diagnostics, rewritten shapes and dropped constraints —
+//! as one flat list of [`LintFinding`]s keyed by stable rule IDs, so schema
+//! authors can see what a target costs them without producing any output.
+//!
+//! ```rust
+//! use json_schema_llm_core::{lint, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {"code": {"type": "string", "minLength": 3}}
+//! });
+//! let report = lint(&schema, &ConvertOptions::default()).unwrap();
+//! assert!(report
+//!     .findings
+//!     .iter()
+//!     .any(|f| f.rule_id == "JSL-CD-001" && f.path == "#/properties/code"));
+//! ```
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::diagnostics::{find_rule, Severity};
+use crate::error::{ConvertError, SourceLocation};
+use crate::{convert, parse_schema_str, span, ConvertOptions, ConvertResult};
+
+/// Rule ID for a constraint the target cannot express.
+const CONSTRAINT_DROPPED: &str = "JSL-CD-001";
+/// Rule ID for a structural rewrite reversed on rehydration.
+const SHAPE_REWRITTEN: &str = "JSL-CD-002";
+
+/// One thing a conversion would transform or drop.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFinding {
+    /// Stable rule ID, e.g. `JSL-P9-001`; see
+    /// [`rule_catalog`](crate::diagnostics::rule_catalog).
+    pub rule_id: &'static str,
+    pub severity: Severity,
+    /// JSON Pointer into the original schema (see
+    /// [`PathMap::original_location`](crate::PathMap::original_location)).
+    pub path: String,
+    pub message: String,
+    /// Position of `path` in the schema text, for [`lint_str`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+}
+
+/// Result of [`lint`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    /// Findings in pipeline order, warnings before informational ones.
+    pub findings: Vec<LintFinding>,
+    /// Findings acknowledged through [`ConvertOptions::suppress`].
+    pub suppressed: usize,
+}
+
+impl LintReport {
+    /// Number of [`Severity::Warning`] findings.
+    pub fn warnings(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == Severity::Warning)
+            .count()
+    }
+}
+
+/// Report what converting `schema` with `options` would transform or drop,
+/// without keeping the converted schema. Fails like [`convert`] does.
+pub fn lint(schema: &Value, options: &ConvertOptions) -> Result<LintReport, ConvertError> {
+    let result = convert(schema, options)?;
+    Ok(report(&result, options))
+}
+
+/// [`lint`] for a schema given as text, with each finding's
+/// [`location`](LintFinding::location) filled in.
+pub fn lint_str(schema_json: &str, options: &ConvertOptions) -> Result<LintReport, ConvertError> {
+    let (schema, diagnostics) = parse_schema_str(schema_json, options)?;
+    let result = convert(&schema, options).map_err(|e| e.with_source_location(schema_json))?;
+    let mut report = report(
+        &result.with_source_diagnostics(diagnostics, options),
+        options,
+    );
+    for finding in &mut report.findings {
+        finding.location = span::locate_pointer(schema_json, &finding.path);
+    }
+    Ok(report)
+}
+
+fn report(result: &ConvertResult, options: &ConvertOptions) -> LintReport {
+    let map = result.path_map();
+    let mut findings = Vec::new();
+    let mut suppressed = result.suppressed_compat_errors.len();
+    let mut push = |rule_id: &'static str, path: String, message: String| {
+        if options
+            .suppress
+            .iter()
+            .any(|s| s.matches_rule_at(rule_id, &path))
+        {
+            suppressed += 1;
+            return;
+        }
+        findings.push(LintFinding {
+            rule_id,
+            severity: find_rule(rule_id).map_or(Severity::Warning, |r| r.severity),
+            path,
+            message,
+            location: None,
+        });
+    };
+
+    for err in &result.provider_compat_errors {
+        push(
+            err.rule_id(),
+            map.original_location(err.path()),
+            err.to_string(),
+        );
+    }
+    let mut last: Option<(&str, &str)> = None;
+    for entry in map.entries() {
+        // A map's value schema moves with it; report the map once.
+        if last.is_some_and(|(kind, path)| {
+            kind == entry.transform
+                && (entry.original == path || entry.original.starts_with(&format!("{path}/")))
+        }) {
+            continue;
+        }
+        last = Some((&entry.transform, &entry.original));
+        push(
+            SHAPE_REWRITTEN,
+            entry.original.clone(),
+            format!(
+                "Rewritten ({}) and restored on rehydration.",
+                entry.transform
+            ),
+        );
+    }
+    for dropped in &result.codec.dropped_constraints {
+        push(
+            CONSTRAINT_DROPPED,
+            map.original_location(&dropped.path),
+            format!(
+                "'{}' is not enforced by the target; it is checked on rehydration.",
+                dropped.constraint
+            ),
+        );
+    }
+
+    findings.sort_by_key(|f| f.severity != Severity::Warning);
+    LintReport {
+        findings,
+        suppressed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::RuleSelector;
+    use serde_json::json;
+
+    #[test]
+    fn test_clean_schema_has_no_findings() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}},
+            "required": ["name"],
+            "additionalProperties": false
+        });
+        let report = lint(&schema, &ConvertOptions::default()).unwrap();
+        assert_eq!(report.findings, vec![]);
+        assert_eq!(report.warnings(), 0);
+    }
+
+    #[test]
+    fn test_reports_rewrites_and_dropped_constraints() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tags": {"type": "object", "additionalProperties": {"type": "string"}},
+                "code": {"type": "string", "minLength": 3}
+            }
+        });
+        let report = lint(&schema, &ConvertOptions::default()).unwrap();
+        let rewrites: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.rule_id == SHAPE_REWRITTEN && f.message.contains("map_to_array"))
+            .collect();
+        assert_eq!(rewrites.len(), 1);
+        assert_eq!(rewrites[0].path, "#/properties/tags");
+        assert_eq!(rewrites[0].severity, Severity::Info);
+
+        let dropped = report
+            .findings
+            .iter()
+            .find(|f| f.rule_id == CONSTRAINT_DROPPED)
+            .unwrap();
+        assert_eq!(dropped.path, "#/properties/code");
+        assert!(dropped.message.contains("minLength"));
+        assert_eq!(report.findings[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_sibling_rewrites_reported_separately() {
+        let map = json!({"type": "object", "additionalProperties": {"type": "string"}});
+        let schema = json!({
+            "type": "object",
+            "properties": {"a": map.clone(), "ab": map}
+        });
+        let report = lint(&schema, &ConvertOptions::default()).unwrap();
+        let paths: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.rule_id == SHAPE_REWRITTEN && f.message.contains("map_to_array"))
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(paths, ["#/properties/a", "#/properties/ab"]);
+    }
+
+    #[test]
+    fn test_compat_errors_use_original_paths() {
+        let schema = json!({"type": "array", "items": {"enum": [1, "a"]}});
+        let report = lint(&schema, &ConvertOptions::default()).unwrap();
+        let mixed = report
+            .findings
+            .iter()
+            .find(|f| f.rule_id == "JSL-P9-003")
+            .unwrap();
+        assert_eq!(mixed.path, "#/items");
+    }
+
+    #[test]
+    fn test_suppress_applies_to_all_findings() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"code": {"type": "string", "minLength": 3}}
+        });
+        let options = ConvertOptions {
+            suppress: vec![RuleSelector::rule(CONSTRAINT_DROPPED)],
+            ..ConvertOptions::default()
+        };
+        let report = lint(&schema, &options).unwrap();
+        assert!(report
+            .findings
+            .iter()
+            .all(|f| f.rule_id != CONSTRAINT_DROPPED));
+        assert_eq!(report.suppressed, 1);
+    }
+
+    #[test]
+    fn test_lint_str_locates_findings() {
+        let text = "{\n  \"type\": \"object\",\n  \"properties\": {\n    \"code\": {\"type\": \"string\", \"minLength\": 3}\n  }\n}";
+        let report = lint_str(text, &ConvertOptions::default()).unwrap();
+        let dropped = report
+            .findings
+            .iter()
+            .find(|f| f.rule_id == CONSTRAINT_DROPPED)
+            .unwrap();
+        assert_eq!(dropped.location.map(|l| l.line), Some(4));
+    }
+}
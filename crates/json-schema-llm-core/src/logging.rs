@@ -0,0 +1,16 @@
+//! Diagnostic logging.
+//!
+//! Re-exports the `tracing` macros when the `tracing` feature is enabled;
+//! otherwise the same macros expand to nothing, so call sites need no
+//! `cfg` of their own.
+
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::{debug, trace, warn};
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! noop {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use {noop as debug, noop as trace, noop as warn};
@@ -0,0 +1,395 @@
+//! Localized text injected into converted schemas.
+//!
+//! Every description the pipeline writes for the model (opaque-string
+//! instructions, constraint hints, enum and union summaries) comes from a
+//! [`MessageCatalog`]. Built-in catalogs exist for each [`Locale`]; callers
+//! can supply their own through [`ConvertOptions::message_catalog`], e.g.
+//! loaded from JSON, where missing entries fall back to English.
+//!
+//! Entries are templates with `{name}` placeholders. Unknown placeholders
+//! are left as written.
+//!
+//! ```rust
+//! use json_schema_llm_core::messages::{Locale, MessageCatalog};
+//! use json_schema_llm_core::{convert, ConvertOptions, Target};
+//! use serde_json::json;
+//!
+//! let schema = json!({"type": "string", "minLength": 3});
+//!
+//! let mut options = ConvertOptions::default();
+//! options.target = Target::Claude;
+//! options.locale = Locale::Es;
+//! let result = convert(&schema, &options).unwrap();
+//! assert_eq!(
+//!     result.schema["description"],
+//!     "Restricciones: longitud mínima: 3 carácter(es)."
+//! );
+//!
+//! let catalog: MessageCatalog =
+//!     serde_json::from_value(json!({"constraints-labeled": "Rules — {list}"})).unwrap();
+//! options.message_catalog = Some(catalog);
+//! let result = convert(&schema, &options).unwrap();
+//! assert_eq!(
+//!     result.schema["description"],
+//!     "Rules — minimum length: 3 character(s)"
+//! );
+//! ```
+//!
+//! [`ConvertOptions::message_catalog`]: crate::ConvertOptions::message_catalog
+
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Language of the built-in [`MessageCatalog`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Ja,
+}
+
+type Text = Cow<'static, str>;
+
+/// Templates for all injected descriptions.
+///
+/// Deserializes from kebab-case keys; entries left out take the English
+/// text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct MessageCatalog {
+    /// JSON-string field summarizing a sub-schema. `{structure}` is the
+    /// structural summary; `{constraints}` is empty or ends with a space.
+    pub json_value_string: Text,
+    /// Opaque object without a description of its own.
+    pub opaque_object: Text,
+    /// Note appended (after a blank line) to an opaque object's description.
+    pub opaque_object_note: Text,
+    /// Recursive `$ref` cut off at the recursion limit. `{type_name}`, `{example}`.
+    pub recursive_ref: Text,
+    /// [`RecursionLeaf::Null`](crate::RecursionLeaf::Null) placeholder. `{type_name}`.
+    pub recursive_null: Text,
+    /// [`RecursionLeaf::NodeId`](crate::RecursionLeaf::NodeId) placeholder. `{type_name}`.
+    pub recursive_node_ref: Text,
+    /// `$node_id` property of an inlined recursive node. `{type_name}`.
+    pub node_id: Text,
+    /// `$ref` that could not be resolved. `{type_name}`.
+    pub unresolved_ref: Text,
+    /// Prefix of the string branch that replaces union variants over the
+    /// cap. `{count}`; the JSON-string summary follows.
+    pub variant_overflow: Text,
+    /// Description of an enum over the cap. `{count}`, `{sample}`; `{more}`
+    /// is `enum_sample_more` when the sample leaves values out, else empty.
+    pub enum_sample: Text,
+    /// Marks a sample that leaves values out.
+    pub enum_sample_more: Text,
+    /// Per-value descriptions of a folded enum. `{list}`.
+    pub enum_values: Text,
+    /// One value in `enum_values`. `{value}`, `{description}`.
+    pub enum_value: Text,
+    /// Separator between `enum_value` entries.
+    pub enum_value_separator: Text,
+    /// Appended to annotated `deprecated` properties.
+    pub deprecated_note: Text,
+
+    /// [`Labeled`](crate::ConstraintHintPolicy::Labeled) hint sentence. `{list}`.
+    pub constraints_labeled: Text,
+    /// Separator between labeled hints.
+    pub labeled_separator: Text,
+    pub hint_minimum: Text,
+    pub hint_maximum: Text,
+    pub hint_exclusive_minimum: Text,
+    pub hint_exclusive_maximum: Text,
+    pub hint_min_length: Text,
+    pub hint_max_length: Text,
+    pub hint_min_items: Text,
+    pub hint_max_items: Text,
+    pub hint_unique_items: Text,
+    pub hint_multiple_of: Text,
+    pub hint_format: Text,
+    pub hint_pattern: Text,
+
+    /// [`Compact`](crate::ConstraintHintPolicy::Compact) hint sentence;
+    /// its first letter is capitalized. `{list}`.
+    pub constraints_compact: Text,
+    /// Separator between compact phrases.
+    pub compact_separator: Text,
+    pub compact_pattern: Text,
+    /// A character count, e.g. `3` or `3–5`.
+    pub compact_chars: Text,
+    /// An item count.
+    pub compact_items: Text,
+    /// A count range. `{min}`, `{max}`.
+    pub compact_range: Text,
+    pub compact_exactly: Text,
+    pub compact_between: Text,
+    pub compact_at_least: Text,
+    pub compact_at_most: Text,
+    pub compact_greater_than: Text,
+    pub compact_less_than: Text,
+    pub compact_multiple_of: Text,
+    pub compact_unique_items: Text,
+    pub compact_format: Text,
+
+    /// Array constraints restated in JSON-string summaries.
+    pub contains_const: Text,
+    pub contains_type: Text,
+    pub contains_any: Text,
+    pub min_contains: Text,
+    pub max_contains: Text,
+    pub pattern_properties: Text,
+}
+
+impl MessageCatalog {
+    /// The built-in catalog for `locale`.
+    pub fn builtin(locale: Locale) -> &'static MessageCatalog {
+        match locale {
+            Locale::En => &EN,
+            Locale::Es => &ES,
+            Locale::Ja => &JA,
+        }
+    }
+}
+
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        EN.clone()
+    }
+}
+
+/// Substitute `{name}` placeholders in `template`. Substituted values are
+/// not scanned again, so they may contain braces.
+pub(crate) fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let tail = &rest[open..];
+        let arg = tail.find('}').and_then(|close| {
+            let name = &tail[1..close];
+            args.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (close, value))
+        });
+        match arg {
+            Some((close, value)) => {
+                out.push_str(&value.to_string());
+                rest = &tail[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+const fn t(s: &'static str) -> Text {
+    Cow::Borrowed(s)
+}
+
+static EN: MessageCatalog = MessageCatalog {
+    json_value_string: t("MUST be a valid JSON value serialized as a string. Structure: {structure}. {constraints}Do NOT output plain text — the value must parse with JSON.parse()."),
+    opaque_object: t("MUST be a valid JSON object serialized as a string, e.g. \"{\\\"key\\\": \\\"value\\\"}\". Do NOT output plain text — the value must parse with JSON.parse()."),
+    opaque_object_note: t("(Note: This field MUST be a valid JSON object serialized as a string. Do NOT output plain text — the value must parse with JSON.parse().)"),
+    recursive_ref: t("MUST be a valid JSON object serialized as a string. This represents a {type_name} that was too deeply nested to inline. Output a complete JSON object as a string value, e.g. \"{example}\". Do NOT output plain text — the value must parse as JSON."),
+    recursive_null: t("Always null: this {type_name} is too deeply nested to include."),
+    recursive_node_ref: t("Reference to a {type_name} too deeply nested to include here: set $node_id to the $node_id of a {type_name} elsewhere in this output."),
+    node_id: t("Identifier of this {type_name}, unique within the output, for references to it elsewhere. Leave empty if nothing references it."),
+    unresolved_ref: t("MUST be a valid JSON value serialized as a string. This represents a {type_name} reference that could not be resolved. Do NOT output plain text — the value must parse with JSON.parse()."),
+    variant_overflow: t("One of {count} further variants, encoded as JSON. "),
+    enum_sample: t("One of {count} allowed values, e.g. {sample}{more}"),
+    enum_sample_more: t(", ..."),
+    enum_values: t("Values: {list}."),
+    enum_value: t("{value}: {description}"),
+    enum_value_separator: t("; "),
+    deprecated_note: t("Deprecated: avoid populating this field."),
+
+    constraints_labeled: t("Constraints: {list}."),
+    labeled_separator: t("; "),
+    hint_minimum: t("minimum value: {value}"),
+    hint_maximum: t("maximum value: {value}"),
+    hint_exclusive_minimum: t("value must be > {value}"),
+    hint_exclusive_maximum: t("value must be < {value}"),
+    hint_min_length: t("minimum length: {value} character(s)"),
+    hint_max_length: t("maximum length: {value} character(s)"),
+    hint_min_items: t("minimum {value} item(s)"),
+    hint_max_items: t("maximum {value} item(s)"),
+    hint_unique_items: t("items must be unique"),
+    hint_multiple_of: t("must be a multiple of {value}"),
+    hint_format: t("format: {value}"),
+    hint_pattern: t("must match pattern: {value}"),
+
+    constraints_compact: t("{list}."),
+    compact_separator: t(", "),
+    compact_pattern: t("must match {value}"),
+    compact_chars: t("{value} chars"),
+    compact_items: t("{value} items"),
+    compact_range: t("{min}–{max}"),
+    compact_exactly: t("exactly {value}"),
+    compact_between: t("between {min} and {max}"),
+    compact_at_least: t("at least {value}"),
+    compact_at_most: t("at most {value}"),
+    compact_greater_than: t("greater than {value}"),
+    compact_less_than: t("less than {value}"),
+    compact_multiple_of: t("a multiple of {value}"),
+    compact_unique_items: t("unique items"),
+    compact_format: t("{value} format"),
+
+    contains_const: t("array must include {value} as an element"),
+    contains_type: t("array must include at least one element of type {value}"),
+    contains_any: t("array must include at least one matching element"),
+    min_contains: t("minimum {value} matching item(s)"),
+    max_contains: t("maximum {value} matching item(s)"),
+    pattern_properties: t("includes properties matching pattern(s) [{value}]"),
+};
+
+static ES: MessageCatalog = MessageCatalog {
+    json_value_string: t("DEBE ser un valor JSON válido serializado como cadena. Estructura: {structure}. {constraints}NO escribas texto plano: el valor debe poder analizarse con JSON.parse()."),
+    opaque_object: t("DEBE ser un objeto JSON válido serializado como cadena, p. ej. \"{\\\"key\\\": \\\"value\\\"}\". NO escribas texto plano: el valor debe poder analizarse con JSON.parse()."),
+    opaque_object_note: t("(Nota: este campo DEBE ser un objeto JSON válido serializado como cadena. NO escribas texto plano: el valor debe poder analizarse con JSON.parse().)"),
+    recursive_ref: t("DEBE ser un objeto JSON válido serializado como cadena. Representa un {type_name} demasiado anidado para incluirlo en línea. Escribe un objeto JSON completo como valor de cadena, p. ej. \"{example}\". NO escribas texto plano: el valor debe ser JSON válido."),
+    recursive_null: t("Siempre null: este {type_name} está demasiado anidado para incluirlo."),
+    recursive_node_ref: t("Referencia a un {type_name} demasiado anidado para incluirlo aquí: asigna a $node_id el $node_id de otro {type_name} de esta salida."),
+    node_id: t("Identificador de este {type_name}, único en la salida, para referenciarlo desde otro lugar. Déjalo vacío si nada lo referencia."),
+    unresolved_ref: t("DEBE ser un valor JSON válido serializado como cadena. Representa una referencia a {type_name} que no se pudo resolver. NO escribas texto plano: el valor debe poder analizarse con JSON.parse()."),
+    variant_overflow: t("Una de otras {count} variantes, codificada como JSON. "),
+    enum_sample: t("Uno de {count} valores permitidos, p. ej. {sample}{more}"),
+    enum_sample_more: t(", ..."),
+    enum_values: t("Valores: {list}."),
+    enum_value: t("{value}: {description}"),
+    enum_value_separator: t("; "),
+    deprecated_note: t("Obsoleto: evita rellenar este campo."),
+
+    constraints_labeled: t("Restricciones: {list}."),
+    labeled_separator: t("; "),
+    hint_minimum: t("valor mínimo: {value}"),
+    hint_maximum: t("valor máximo: {value}"),
+    hint_exclusive_minimum: t("el valor debe ser > {value}"),
+    hint_exclusive_maximum: t("el valor debe ser < {value}"),
+    hint_min_length: t("longitud mínima: {value} carácter(es)"),
+    hint_max_length: t("longitud máxima: {value} carácter(es)"),
+    hint_min_items: t("mínimo {value} elemento(s)"),
+    hint_max_items: t("máximo {value} elemento(s)"),
+    hint_unique_items: t("los elementos deben ser únicos"),
+    hint_multiple_of: t("debe ser múltiplo de {value}"),
+    hint_format: t("formato: {value}"),
+    hint_pattern: t("debe coincidir con el patrón: {value}"),
+
+    constraints_compact: t("{list}."),
+    compact_separator: t(", "),
+    compact_pattern: t("debe coincidir con {value}"),
+    compact_chars: t("{value} caracteres"),
+    compact_items: t("{value} elementos"),
+    compact_range: t("{min}–{max}"),
+    compact_exactly: t("exactamente {value}"),
+    compact_between: t("entre {min} y {max}"),
+    compact_at_least: t("al menos {value}"),
+    compact_at_most: t("como máximo {value}"),
+    compact_greater_than: t("mayor que {value}"),
+    compact_less_than: t("menor que {value}"),
+    compact_multiple_of: t("múltiplo de {value}"),
+    compact_unique_items: t("elementos únicos"),
+    compact_format: t("formato {value}"),
+
+    contains_const: t("el array debe incluir {value} como elemento"),
+    contains_type: t("el array debe incluir al menos un elemento de tipo {value}"),
+    contains_any: t("el array debe incluir al menos un elemento que coincida"),
+    min_contains: t("mínimo {value} elemento(s) coincidente(s)"),
+    max_contains: t("máximo {value} elemento(s) coincidente(s)"),
+    pattern_properties: t("incluye propiedades que coinciden con el/los patrón(es) [{value}]"),
+};
+
+static JA: MessageCatalog = MessageCatalog {
+    json_value_string: t("文字列としてシリアライズされた有効なJSON値でなければなりません。構造: {structure}。{constraints}プレーンテキストは出力しないでください。値はJSON.parse()で解析できる必要があります。"),
+    opaque_object: t("文字列としてシリアライズされた有効なJSONオブジェクトでなければなりません。例: \"{\\\"key\\\": \\\"value\\\"}\"。プレーンテキストは出力しないでください。値はJSON.parse()で解析できる必要があります。"),
+    opaque_object_note: t("(注: このフィールドは文字列としてシリアライズされた有効なJSONオブジェクトでなければなりません。プレーンテキストは出力しないでください。値はJSON.parse()で解析できる必要があります。)"),
+    recursive_ref: t("文字列としてシリアライズされた有効なJSONオブジェクトでなければなりません。インライン化するには入れ子が深すぎる{type_name}を表します。完全なJSONオブジェクトを文字列値として出力してください。例: \"{example}\"。プレーンテキストは出力しないでください。値はJSONとして解析できる必要があります。"),
+    recursive_null: t("常にnullです。この{type_name}は入れ子が深すぎるため含められません。"),
+    recursive_node_ref: t("入れ子が深すぎてここに含められない{type_name}への参照です。$node_idには、この出力内の別の{type_name}の$node_idを設定してください。"),
+    node_id: t("この{type_name}の識別子です。出力内で一意にし、他の場所からの参照に使います。参照されない場合は空のままにしてください。"),
+    unresolved_ref: t("文字列としてシリアライズされた有効なJSON値でなければなりません。解決できなかった{type_name}への参照を表します。プレーンテキストは出力しないでください。値はJSON.parse()で解析できる必要があります。"),
+    variant_overflow: t("その他{count}個のバリアントのいずれか。JSONとしてエンコードされます。"),
+    enum_sample: t("{count}個の許可された値のいずれか。例: {sample}{more}"),
+    enum_sample_more: t("、..."),
+    enum_values: t("値: {list}。"),
+    enum_value: t("{value}: {description}"),
+    enum_value_separator: t("、"),
+    deprecated_note: t("非推奨: このフィールドにはできるだけ値を入れないでください。"),
+
+    constraints_labeled: t("制約: {list}。"),
+    labeled_separator: t("、"),
+    hint_minimum: t("最小値: {value}"),
+    hint_maximum: t("最大値: {value}"),
+    hint_exclusive_minimum: t("値は{value}より大きい"),
+    hint_exclusive_maximum: t("値は{value}より小さい"),
+    hint_min_length: t("最小長: {value}文字"),
+    hint_max_length: t("最大長: {value}文字"),
+    hint_min_items: t("最小{value}件"),
+    hint_max_items: t("最大{value}件"),
+    hint_unique_items: t("要素は一意"),
+    hint_multiple_of: t("{value}の倍数"),
+    hint_format: t("形式: {value}"),
+    hint_pattern: t("パターンに一致: {value}"),
+
+    constraints_compact: t("{list}。"),
+    compact_separator: t("、"),
+    compact_pattern: t("{value}に一致"),
+    compact_chars: t("{value}文字"),
+    compact_items: t("{value}件"),
+    compact_range: t("{min}〜{max}"),
+    compact_exactly: t("ちょうど{value}"),
+    compact_between: t("{min}以上{max}以下"),
+    compact_at_least: t("{value}以上"),
+    compact_at_most: t("{value}以下"),
+    compact_greater_than: t("{value}より大きい"),
+    compact_less_than: t("{value}より小さい"),
+    compact_multiple_of: t("{value}の倍数"),
+    compact_unique_items: t("要素は一意"),
+    compact_format: t("{value}形式"),
+
+    contains_const: t("配列は要素として{value}を含む必要があります"),
+    contains_type: t("配列は{value}型の要素を少なくとも1つ含む必要があります"),
+    contains_any: t("配列は一致する要素を少なくとも1つ含む必要があります"),
+    min_contains: t("一致する要素は最小{value}件"),
+    max_contains: t("一致する要素は最大{value}件"),
+    pattern_properties: t("パターン[{value}]に一致するプロパティを含みます"),
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fill_replaces_named_placeholders() {
+        let text = fill(
+            "{min}–{max} {unknown} {{min}}",
+            &[("min", &"{max}"), ("max", &5)],
+        );
+        assert_eq!(text, "{max}–5 {unknown} {{max}}");
+    }
+
+    #[test]
+    fn test_partial_catalog_falls_back_to_english() {
+        let catalog: MessageCatalog =
+            serde_json::from_value(json!({"hint-minimum": "min {value}"})).unwrap();
+        assert_eq!(catalog.hint_minimum, "min {value}");
+        assert_eq!(catalog.hint_maximum, EN.hint_maximum);
+    }
+
+    #[test]
+    fn test_builtin_catalogs_round_trip() {
+        for locale in [Locale::En, Locale::Es, Locale::Ja] {
+            let catalog = MessageCatalog::builtin(locale);
+            let value = serde_json::to_value(catalog).unwrap();
+            let back: MessageCatalog = serde_json::from_value(value).unwrap();
+            assert_eq!(&back, catalog);
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! Custom conversion passes.
+//!
+//! Built-in passes are plain functions from schema to [`PassResult`]; this
+//! module exposes the same shape as a trait so callers can slot their own
+//! transforms (e.g. stripping company-specific `x-` keywords) into the
+//! pipeline via [`ConvertOptions::extra_passes`] without forking the crate.
+//!
+//! ```rust
+//! use json_schema_llm_core::pass::{BuiltinPass, ConversionPass, ExtraPass, PassContext};
+//! use json_schema_llm_core::{convert, ConvertError, ConvertOptions, PassResult};
+//! use serde_json::{json, Value};
+//!
+//! struct StripInternal;
+//!
+//! impl ConversionPass for StripInternal {
+//!     fn name(&self) -> &str {
+//!         "strip-internal"
+//!     }
+//!
+//!     fn run(&self, mut schema: Value, _ctx: &PassContext<'_>) -> Result<PassResult, ConvertError> {
+//!         if let Some(obj) = schema.as_object_mut() {
+//!             obj.remove("x-internal");
+//!         }
+//!         Ok(PassResult::schema_only(schema))
+//!     }
+//! }
+//!
+//! let mut options = ConvertOptions::default();
+//! options.extra_passes.push(ExtraPass::new(BuiltinPass::Normalize, StripInternal));
+//! let schema = json!({"type": "object", "x-internal": true, "properties": {}});
+//! let result = convert(&schema, &options).unwrap();
+//! assert!(result.schema.get("x-internal").is_none());
+//! ```
+//!
+//! [`ConvertOptions::extra_passes`]: crate::ConvertOptions::extra_passes
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+pub use crate::passes::pass_result::PassResult;
+
+/// A schema-to-schema transform run as part of [`convert`](crate::convert).
+///
+/// Transforms and dropped constraints in the returned [`PassResult`] are
+/// merged into the codec exactly as for built-in passes.
+pub trait ConversionPass: Send + Sync {
+    /// Short identifier, used in debug output.
+    fn name(&self) -> &str;
+
+    /// Transform `schema`. Errors abort the conversion.
+    fn run(&self, schema: Value, ctx: &PassContext<'_>) -> Result<PassResult, ConvertError>;
+}
+
+/// What a custom pass can see besides the schema.
+#[derive(Debug, Clone, Copy)]
+pub struct PassContext<'a> {
+    /// Options of the running conversion.
+    pub options: &'a ConvertOptions,
+    /// The built-in pass this one runs after.
+    pub after: BuiltinPass,
+}
+
+/// The built-in passes, in pipeline order. Used as insertion points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuiltinPass {
+    /// Pass 0: `$ref` resolution and draft normalization.
+    Normalize,
+    /// Pass 1: `allOf` merging.
+    Composition,
+    /// Pass 2: `oneOf` → `anyOf`.
+    Polymorphism,
+    /// Pass 3: maps → key/value arrays.
+    Dictionary,
+    /// Pass 4: open objects → JSON strings.
+    Opaque,
+    /// Pass 5: recursion breaking.
+    Recursion,
+    /// Pass 6: strict-mode enforcement. Passes inserted here still run in
+    /// permissive mode, where pass 6 is skipped.
+    Strict,
+    /// Pass 8: adaptive opaque stringification (runs before pass 7).
+    AdaptiveOpaque,
+    /// Pass 7: constraint pruning.
+    Constraints,
+    /// Pass 9: provider compatibility checks. Passes inserted here see the
+    /// final schema, before Gemini property ordering and the envelope.
+    ProviderCompat,
+}
+
+impl BuiltinPass {
+    /// Every built-in pass, in pipeline order.
+    pub const ALL: [BuiltinPass; 10] = [
+        BuiltinPass::Normalize,
+        BuiltinPass::Composition,
+        BuiltinPass::Polymorphism,
+        BuiltinPass::Dictionary,
+        BuiltinPass::Opaque,
+        BuiltinPass::Recursion,
+        BuiltinPass::Strict,
+        BuiltinPass::AdaptiveOpaque,
+        BuiltinPass::Constraints,
+        BuiltinPass::ProviderCompat,
+    ];
+}
+
+/// A custom pass and where it runs. Several passes at the same point run in
+/// the order they appear in [`ConvertOptions::extra_passes`].
+///
+/// [`ConvertOptions::extra_passes`]: crate::ConvertOptions::extra_passes
+#[derive(Clone)]
+pub struct ExtraPass {
+    /// Run immediately after this built-in pass.
+    pub after: BuiltinPass,
+    pub pass: Arc<dyn ConversionPass>,
+}
+
+impl ExtraPass {
+    pub fn new(after: BuiltinPass, pass: impl ConversionPass + 'static) -> Self {
+        Self {
+            after,
+            pass: Arc::new(pass),
+        }
+    }
+}
+
+impl fmt::Debug for ExtraPass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtraPass")
+            .field("after", &self.after)
+            .field("pass", &self.pass.name())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::DroppedConstraint;
+    use crate::config::Mode;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Records the schema it sees and drops `x-tag` from the root.
+    struct Recorder {
+        label: &'static str,
+        seen: Arc<Mutex<Vec<(&'static str, Value)>>>,
+    }
+
+    impl ConversionPass for Recorder {
+        fn name(&self) -> &str {
+            self.label
+        }
+
+        fn run(
+            &self,
+            mut schema: Value,
+            _ctx: &PassContext<'_>,
+        ) -> Result<PassResult, ConvertError> {
+            self.seen.lock().unwrap().push((self.label, schema.clone()));
+            let dropped = match schema.as_object_mut().and_then(|o| o.remove("x-tag")) {
+                Some(value) => vec![DroppedConstraint {
+                    path: "#".to_string(),
+                    constraint: "x-tag".to_string(),
+                    value,
+                }],
+                None => Vec::new(),
+            };
+            Ok(PassResult::with_dropped(schema, dropped))
+        }
+    }
+
+    struct Fail;
+
+    impl ConversionPass for Fail {
+        fn name(&self) -> &str {
+            "fail"
+        }
+
+        fn run(&self, _schema: Value, ctx: &PassContext<'_>) -> Result<PassResult, ConvertError> {
+            Err(ConvertError::SchemaError {
+                path: "#".to_string(),
+                message: format!("rejected after {:?}", ctx.after),
+                location: None,
+            })
+        }
+    }
+
+    fn recorder(
+        after: BuiltinPass,
+        label: &'static str,
+        seen: &Arc<Mutex<Vec<(&'static str, Value)>>>,
+    ) -> ExtraPass {
+        ExtraPass::new(
+            after,
+            Recorder {
+                label,
+                seen: Arc::clone(seen),
+            },
+        )
+    }
+
+    #[test]
+    fn test_extra_passes_run_at_their_slot_in_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let options = ConvertOptions {
+            extra_passes: vec![
+                recorder(BuiltinPass::Dictionary, "second", &seen),
+                recorder(BuiltinPass::Normalize, "first", &seen),
+                recorder(BuiltinPass::Dictionary, "third", &seen),
+            ],
+            ..ConvertOptions::default()
+        };
+        let schema = json!({
+            "type": "object",
+            "x-tag": "internal",
+            "properties": {
+                "m": {"type": "object", "additionalProperties": {"type": "string"}}
+            }
+        });
+        let result = crate::convert(&schema, &options).unwrap();
+
+        let seen = seen.lock().unwrap();
+        let labels: Vec<_> = seen.iter().map(|(label, _)| *label).collect();
+        assert_eq!(labels, ["first", "second", "third"]);
+        // Before pass 3 the map is still a map; after it, an array.
+        assert!(seen[0].1["properties"]["m"]
+            .get("additionalProperties")
+            .is_some());
+        assert_eq!(seen[1].1["properties"]["m"]["type"], "array");
+        // The first pass's dropped constraint lands in the codec.
+        assert!(result.schema.get("x-tag").is_none());
+        assert!(result
+            .codec
+            .dropped_constraints
+            .iter()
+            .any(|d| d.constraint == "x-tag" && d.value == "internal"));
+    }
+
+    #[test]
+    fn test_strict_slot_runs_in_permissive_mode() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let options = ConvertOptions {
+            mode: Mode::Permissive,
+            extra_passes: vec![recorder(BuiltinPass::Strict, "strict", &seen)],
+            ..ConvertOptions::default()
+        };
+        crate::convert(&json!({"type": "string"}), &options).unwrap();
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_extra_pass_error_aborts_conversion() {
+        let options = ConvertOptions {
+            extra_passes: vec![ExtraPass::new(BuiltinPass::Constraints, Fail)],
+            ..ConvertOptions::default()
+        };
+        let err = crate::convert(&json!({"type": "string"}), &options).unwrap_err();
+        assert!(err.to_string().contains("Constraints"), "{err}");
+    }
+
+    #[test]
+    fn test_extra_pass_debug_shows_name() {
+        let extra = ExtraPass::new(BuiltinPass::Opaque, Fail);
+        assert_eq!(
+            format!("{:?}", extra),
+            "ExtraPass { after: Opaque, pass: \"fail\" }"
+        );
+    }
+}
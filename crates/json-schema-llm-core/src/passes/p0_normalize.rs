@@ -5,10 +5,17 @@
 //! This is the foundational pass — all downstream passes (1–7) assume refs are
 //! resolved and syntax is normalized. Pass 0 performs:
 //!
+//! 0. Legacy keyword upgrade (draft-04 to draft-07, see below)
 //! 1. `items` (array form) → `prefixItems` + `additionalItems` → `items`
 //! 2. `$ref` resolution via JSON Pointer traversal with DFS cycle detection
 //! 3. `definitions` → `$defs` rename (post-resolution, Phase 3)
 //! 4. `$defs` cleanup (strip fully-inlined entries, preserve recursive)
+//! 5. Unresolvable `$ref` recovery per [`ErrorPolicy`] (Phase 3b)
+//! 6. Embedded JSON strings (`contentMediaType: application/json` +
+//!    `contentSchema`) per [`ContentSchemaPolicy`]
+//!
+//! `$dynamicRef` is rewritten to a plain `$ref` before step 2 (see
+//! [Dynamic references](#dynamic-references)).
 //!
 //! ## Why recursion breaking is NOT merged here
 //!
@@ -22,18 +29,56 @@
 //!
 //! See also: inline comments in `p4_opaque.rs` and `p5_recursion.rs`.
 //!
+//! ## Legacy drafts
+//!
+//! Keywords whose meaning is unambiguous are upgraded to 2020-12 whatever
+//! the declared `$schema`: boolean `exclusiveMinimum`/`exclusiveMaximum`,
+//! `dependencies` (split into `dependentRequired` / `dependentSchemas`),
+//! and the draft-03 `divisibleBy`, `extends`, boolean `required` and
+//! `type: "any"`. `id` → `$id` / `$anchor` only applies when the root
+//! declares draft-04 or earlier, since `id` is an ordinary (ignored) keyword
+//! in later drafts. Draft-03 constructs without an equivalent (`disallow`,
+//! schemas inside `type`) are removed and reported as `legacy_keyword`
+//! diagnostics.
+//!
+//! `$ref` siblings keep their 2019-09+ meaning (merged) even under older
+//! drafts, which ignore them: authors writing them usually mean them.
+//!
+//! ## Dynamic references
+//!
+//! A `$dynamicRef` first resolves like `$ref`. When its target declares a
+//! matching `$dynamicAnchor`, the outermost schema resource in the dynamic
+//! scope that declares the same anchor wins instead. Evaluation of a single
+//! document always enters through the root, so that is the root resource
+//! when it declares the anchor (the recursive-extension pattern of
+//! meta-schemas and extensible tree nodes), and the initial target
+//! otherwise. Intermediate resources are not consulted. A `$dynamicRef`
+//! that resolves to nothing is stripped and reported as a
+//! `ref_keyword_stripped` diagnostic. Once every `$dynamicRef` has
+//! resolved, the `$dynamicAnchor`s are removed without one.
+//!
 //! ## Limitations
 //!
-//! - Only root-relative JSON Pointers (`#/...`) are supported.
-//! - `$id` / `$anchor` scoped resolution is not implemented.
-//! - External (`http://...`) and dynamic refs are rejected with errors.
+//! - Only root-relative JSON Pointers (`#/...`) are supported: a bare
+//!   `#/...` inside an embedded `$id` resource still points into the root.
+//!   Refs by `$id` URI (optionally with a pointer fragment) or `$anchor`
+//!   resolve to wherever the resource is embedded.
+//! - External (`http://...`) refs are rejected with errors.
+//! - A root-relative `$ref` to a missing location fails the conversion
+//!   unless [`ConvertOptions::on_error`] says otherwise: the property
+//!   holding it is then removed, or the reference is left for Pass 5 to
+//!   turn into an opaque JSON-string placeholder. Each recovery is
+//!   reported as an `unresolvable_ref_recovered` diagnostic.
 
 use std::collections::{HashMap, HashSet};
 
 use serde_json::{Map, Value};
 
-use crate::config::ConvertOptions;
-use crate::error::ConvertError;
+use crate::codec::Transform;
+use crate::config::{ContentSchemaPolicy, ConvertOptions, ErrorPolicy};
+use crate::error::{ConvertError, ProviderCompatError};
+use crate::limits::ResourceGuard;
+use crate::schema_utils::build_path;
 
 use super::pass_result::PassResult;
 
@@ -51,6 +96,11 @@ struct RefContext<'a> {
     resolver: &'a crate::resolver::ResolverEngine,
     /// Current base URI for $id scoping.
     base_uri: url::Url,
+    /// Node-count and time-budget guard for this conversion.
+    guard: &'a ResourceGuard,
+    /// Object-node counts of `resolved_cache` entries, so cache hits are
+    /// charged against the node budget like a fresh expansion would be.
+    cached_sizes: HashMap<String, usize>,
 }
 
 /// Result of running the schema normalization pass.
@@ -61,6 +111,8 @@ pub struct NormalizePassResult {
     /// JSON Pointer paths where recursive $ref cycles were detected.
     /// These are left as `$ref` for Pass 5 to break.
     pub recursive_refs: Vec<String>,
+    /// Legacy constructs that could not be upgraded.
+    pub diagnostics: Vec<ProviderCompatError>,
 }
 
 // ---------------------------------------------------------------------------
@@ -88,16 +140,32 @@ const ANNOTATION_KEYWORDS: &[&str] = &[
 /// # Returns
 ///
 /// A `NormalizePassResult` with the normalized schema and any recursive ref paths.
+#[cfg(test)]
 pub fn normalize(
     schema: &Value,
     config: &ConvertOptions,
 ) -> Result<NormalizePassResult, ConvertError> {
-    // Phase 1: normalize items array → prefixItems, strip annotations
-    // and meta-schema fragments that would break ref resolution.
-    let mut root = schema.clone();
+    normalize_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`normalize`] with an explicit resource guard shared with the rest of the
+/// pipeline, so node and time budgets span the whole conversion.
+pub fn normalize_with_guard(
+    schema: &Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<NormalizePassResult, ConvertError> {
+    // Phase 1: upgrade legacy keywords, normalize items array →
+    // prefixItems, strip annotations and meta-schema fragments that would
+    // break ref resolution, rewrite $dynamicRef as $ref.
+    let mut diagnostics = Vec::new();
+    let draft = declared_draft(schema);
+    let mut root =
+        upgrade_legacy_keywords(schema.clone(), "#", 0, draft, config, &mut diagnostics)?;
     normalize_items_recursive(&mut root);
     strip_examples_recursive(&mut root);
     strip_problematic_root_defs(&mut root);
+    let dynamic_refs_resolved = resolve_dynamic_refs(&mut root, config, &mut diagnostics)?;
 
     // Phase 2: resolve $ref.
     let frozen_root = root.clone();
@@ -110,19 +178,231 @@ pub fn normalize(
         recursive_refs: Vec::new(),
         resolver: &resolver,
         base_uri: crate::anchor_utils::default_base_uri(),
+        guard,
+        cached_sizes: HashMap::new(),
     };
     let result = resolve_refs(root, "#", 0, &mut ctx)?;
 
     // Phase 3: cleanup.
     let recursive_refs = ctx.recursive_refs;
     let result = cleanup(result, &recursive_refs);
+    // With every `$dynamicRef` rewritten, the anchors they named are inert.
+    let result = if dynamic_refs_resolved {
+        strip_dynamic_anchors(result, "#", 0)?
+    } else {
+        result
+    };
+
+    // Phase 3b: settle unresolvable refs kept under `on_error`, once
+    // fully-inlined definitions are gone.
+    let result = if config.on_error == ErrorPolicy::Fail {
+        result
+    } else {
+        recover_unresolvable_refs(result, "#", 0, config, &mut diagnostics)?
+    };
+
+    // Phase 4: embedded JSON strings.
+    let mut transforms = Vec::new();
+    let result = embed_content_schemas(result, config, &mut transforms)?;
 
     Ok(NormalizePassResult {
-        pass: PassResult::schema_only(result),
+        pass: PassResult::with_transforms(result, transforms),
         recursive_refs,
+        diagnostics,
     })
 }
 
+// ---------------------------------------------------------------------------
+// Phase 1a: Legacy keyword upgrade
+// ---------------------------------------------------------------------------
+
+/// Dialect declared by the root `$schema`. Undeclared and unrecognized
+/// dialects count as 2020-12.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Draft {
+    Draft3,
+    Draft4,
+    Draft6,
+    Draft7,
+    Modern,
+}
+
+fn declared_draft(schema: &Value) -> Draft {
+    let Some(uri) = schema.get("$schema").and_then(Value::as_str) else {
+        return Draft::Modern;
+    };
+    [
+        ("draft-03", Draft::Draft3),
+        ("draft-04", Draft::Draft4),
+        ("draft-06", Draft::Draft6),
+        ("draft-07", Draft::Draft7),
+    ]
+    .into_iter()
+    .find(|(marker, _)| uri.contains(marker))
+    .map_or(Draft::Modern, |(_, draft)| draft)
+}
+
+/// Rewrite legacy keywords of `value` and its sub-schemas in 2020-12 terms.
+fn upgrade_legacy_keywords(
+    value: Value,
+    path: &str,
+    depth: usize,
+    draft: Draft,
+    config: &ConvertOptions,
+    diagnostics: &mut Vec<ProviderCompatError>,
+) -> Result<Value, ConvertError> {
+    let Value::Object(mut obj) = value else {
+        return Ok(value);
+    };
+
+    if draft <= Draft::Draft4 && !obj.contains_key("$id") {
+        if let Some(Value::String(id)) = obj.remove("id") {
+            match id.strip_prefix('#') {
+                Some(anchor) => obj.insert("$anchor".to_string(), Value::String(anchor.into())),
+                None => obj.insert("$id".to_string(), Value::String(id)),
+            };
+        }
+    }
+
+    upgrade_exclusive_bound(&mut obj, "exclusiveMinimum", "minimum");
+    upgrade_exclusive_bound(&mut obj, "exclusiveMaximum", "maximum");
+
+    if let Some(Value::Object(deps)) = obj.remove("dependencies") {
+        for (name, dep) in deps {
+            let keyword = if dep.is_array() {
+                "dependentRequired"
+            } else {
+                "dependentSchemas"
+            };
+            if let Value::Object(target) = obj
+                .entry(keyword)
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                target.entry(name).or_insert(dep);
+            }
+        }
+    }
+
+    // Draft-03.
+    if let Some(divisor) = obj.remove("divisibleBy") {
+        obj.entry("multipleOf").or_insert(divisor);
+    }
+    if let Some(extends) = obj.remove("extends") {
+        let extends = match extends {
+            Value::Array(schemas) => schemas,
+            schema => vec![schema],
+        };
+        if let Value::Array(all_of) = obj
+            .entry("allOf")
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            all_of.extend(extends);
+        }
+    }
+    upgrade_required_flags(&mut obj);
+    upgrade_type(&mut obj, path, config, diagnostics);
+    if obj.remove("disallow").is_some() {
+        diagnostics.push(ProviderCompatError::LegacyKeyword {
+            path: path.to_string(),
+            keyword: "disallow".to_string(),
+            target: config.target,
+            hint: "Draft-03 `disallow` was removed; rewrite it as `not`.".to_string(),
+        });
+    }
+
+    crate::schema_utils::recurse_into_children(
+        &mut obj,
+        path,
+        depth,
+        &mut |val, child_path, child_depth| {
+            upgrade_legacy_keywords(val, child_path, child_depth, draft, config, diagnostics)
+        },
+    )?;
+    Ok(Value::Object(obj))
+}
+
+/// Draft-04 boolean `exclusiveMinimum: true` qualifies `minimum`; 2020-12
+/// carries the bound itself.
+fn upgrade_exclusive_bound(obj: &mut Map<String, Value>, exclusive: &str, inclusive: &str) {
+    if let Some(Value::Bool(flag)) = obj.get(exclusive) {
+        let flag = *flag;
+        obj.remove(exclusive);
+        if flag {
+            if let Some(bound) = obj.remove(inclusive) {
+                obj.insert(exclusive.to_string(), bound);
+            }
+        }
+    }
+}
+
+/// Draft-03 `required: true` on a property → the parent's `required` array.
+fn upgrade_required_flags(obj: &mut Map<String, Value>) {
+    let Some(Value::Object(props)) = obj.get_mut("properties") else {
+        return;
+    };
+    let mut required = Vec::new();
+    for (name, prop) in props.iter_mut() {
+        if let Some(prop) = prop.as_object_mut() {
+            if let Some(Value::Bool(flag)) = prop.get("required") {
+                if *flag {
+                    required.push(name.clone());
+                }
+                prop.remove("required");
+            }
+        }
+    }
+    if required.is_empty() {
+        return;
+    }
+    if let Value::Array(existing) = obj
+        .entry("required")
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        for name in required {
+            if !existing.iter().any(|v| v.as_str() == Some(name.as_str())) {
+                existing.push(Value::String(name));
+            }
+        }
+    }
+}
+
+/// Draft-03 `type: "any"` means no type constraint; schemas inside a
+/// `type` array are unsupported and removed.
+fn upgrade_type(
+    obj: &mut Map<String, Value>,
+    path: &str,
+    config: &ConvertOptions,
+    diagnostics: &mut Vec<ProviderCompatError>,
+) {
+    let Some(ty) = obj.get_mut("type") else {
+        return;
+    };
+    let keep = match ty {
+        Value::String(name) => name != "any",
+        Value::Array(types) => {
+            let before = types.len();
+            types.retain(Value::is_string);
+            if types.len() < before {
+                diagnostics.push(ProviderCompatError::LegacyKeyword {
+                    path: build_path(path, &["type"]),
+                    keyword: "type".to_string(),
+                    target: config.target,
+                    hint: "Draft-03 schemas inside `type` were removed; rewrite them as `anyOf`."
+                        .to_string(),
+                });
+            }
+            if types.iter().any(|t| t == "any") {
+                types.clear();
+            }
+            !types.is_empty()
+        }
+        _ => true,
+    };
+    if !keep {
+        obj.remove("type");
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Phase 1: items array → prefixItems normalization (recursive)
 // ---------------------------------------------------------------------------
@@ -381,6 +661,152 @@ fn neutralize_dead_refs(value: &mut Value, dead_prefixes: &[String]) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Phase 1c: $dynamicRef → $ref
+// ---------------------------------------------------------------------------
+
+/// Anchor maps consulted while rewriting `$dynamicRef`.
+struct DynamicScope<'a> {
+    root: &'a Value,
+    /// Base URI of the root resource — the outermost dynamic scope.
+    root_base: url::Url,
+    anchors: HashMap<String, String>,
+    dynamic_anchors: HashMap<String, String>,
+}
+
+/// Rewrite every `$dynamicRef` into a `$ref` JSON Pointer (see the module
+/// docs), so the ordinary resolution and cycle detection apply to it.
+/// Returns whether every `$dynamicRef` resolved; those that don't are
+/// stripped with a diagnostic.
+fn resolve_dynamic_refs(
+    root: &mut Value,
+    config: &ConvertOptions,
+    diagnostics: &mut Vec<ProviderCompatError>,
+) -> Result<bool, ConvertError> {
+    if !has_dynamic_ref(root) {
+        return Ok(true);
+    }
+    let default_base = crate::anchor_utils::default_base_uri();
+    let frozen = root.clone();
+    let scope = DynamicScope {
+        root: &frozen,
+        root_base: crate::resolver::ResolverEngine::new(&frozen)?
+            .base_uri()
+            .clone(),
+        anchors: crate::anchor_utils::build_anchor_map(&frozen, Some(&default_base))?,
+        dynamic_anchors: crate::anchor_utils::build_dynamic_anchor_map(&frozen, &default_base)?,
+    };
+    let before = diagnostics.len();
+    rewrite_dynamic_refs(root, "#", &default_base, &scope, config, diagnostics);
+    Ok(diagnostics.len() == before)
+}
+
+/// Remove every `$dynamicAnchor` once no `$dynamicRef` is left to name one.
+fn strip_dynamic_anchors(schema: Value, path: &str, depth: usize) -> Result<Value, ConvertError> {
+    let Value::Object(mut obj) = schema else {
+        return Ok(schema);
+    };
+    obj.remove("$dynamicAnchor");
+    crate::schema_utils::recurse_into_children(&mut obj, path, depth, &mut strip_dynamic_anchors)?;
+    Ok(Value::Object(obj))
+}
+
+fn has_dynamic_ref(value: &Value) -> bool {
+    match value {
+        Value::Object(obj) => obj.contains_key("$dynamicRef") || obj.values().any(has_dynamic_ref),
+        Value::Array(arr) => arr.iter().any(has_dynamic_ref),
+        _ => false,
+    }
+}
+
+fn rewrite_dynamic_refs(
+    value: &mut Value,
+    path: &str,
+    base: &url::Url,
+    scope: &DynamicScope<'_>,
+    config: &ConvertOptions,
+    diagnostics: &mut Vec<ProviderCompatError>,
+) {
+    let obj = match value {
+        Value::Object(obj) => obj,
+        Value::Array(arr) => {
+            for (i, item) in arr.iter_mut().enumerate() {
+                let child_path = build_path(path, &[&i.to_string()]);
+                rewrite_dynamic_refs(item, &child_path, base, scope, config, diagnostics);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    let base = match obj.get("$id").and_then(Value::as_str) {
+        Some(id) => base.join(id).unwrap_or_else(|_| base.clone()),
+        None => base.clone(),
+    };
+
+    if let Some(Value::String(dynamic_ref)) = obj.remove("$dynamicRef") {
+        match resolve_dynamic_ref(&dynamic_ref, &base, scope) {
+            Some(pointer) => {
+                let target = Value::String(pointer);
+                if obj.contains_key("$ref") {
+                    // Both apply: keep the `$ref` and AND the dynamic target in.
+                    let all_of = obj
+                        .entry("allOf")
+                        .or_insert_with(|| Value::Array(Vec::new()));
+                    if let Value::Array(members) = all_of {
+                        members.push(Value::Object(Map::from_iter([(
+                            "$ref".to_string(),
+                            target,
+                        )])));
+                    }
+                } else {
+                    obj.insert("$ref".to_string(), target);
+                }
+            }
+            None => diagnostics.push(ProviderCompatError::RefKeywordStripped {
+                path: path.to_string(),
+                keyword: "$dynamicRef".to_string(),
+                target: config.target,
+                hint: format!(
+                    "$dynamicRef '{}' matches no $dynamicAnchor or $anchor in this document; stripped.",
+                    dynamic_ref
+                ),
+            }),
+        }
+    }
+
+    for (key, child) in obj.iter_mut() {
+        let child_path = build_path(path, &[key]);
+        rewrite_dynamic_refs(child, &child_path, &base, scope, config, diagnostics);
+    }
+}
+
+/// The JSON Pointer a `$dynamicRef` evaluates to, if any.
+fn resolve_dynamic_ref(
+    dynamic_ref: &str,
+    base: &url::Url,
+    scope: &DynamicScope<'_>,
+) -> Option<String> {
+    let absolute = base.join(dynamic_ref).ok()?;
+    if let Some(initial) = scope.dynamic_anchors.get(absolute.as_str()) {
+        // Bookended by a `$dynamicAnchor`: the outermost declaration wins.
+        let outermost = absolute
+            .fragment()
+            .and_then(|name| scope.root_base.join(&format!("#{}", name)).ok())
+            .and_then(|uri| scope.dynamic_anchors.get(uri.as_str()));
+        return Some(outermost.unwrap_or(initial).clone());
+    }
+    // No matching `$dynamicAnchor`: behaves like `$ref`.
+    match crate::anchor_utils::resolve_ref(dynamic_ref, base, &scope.anchors) {
+        crate::anchor_utils::ResolvedRef::Pointer(p)
+            if resolve_pointer(scope.root, &p).is_some() =>
+        {
+            Some(p)
+        }
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Phase 2: $ref resolution via DFS with cycle detection
 // ---------------------------------------------------------------------------
@@ -402,6 +828,7 @@ fn resolve_refs(
         Value::Object(obj) => obj,
         other => return Ok(other),
     };
+    ctx.guard.tick(path)?;
 
     // Save base URI — $id scoping is lexical (per-subtree), not global.
     let saved_base = ctx.base_uri.clone();
@@ -451,6 +878,8 @@ fn resolve_single_ref(
     // This turns O(fan_out^depth) into O(unique_defs) for schemas with
     // multiple sibling $refs pointing to the same definition.
     if let Some(cached) = ctx.resolved_cache.get(ref_str).cloned() {
+        let size = ctx.cached_sizes.get(ref_str).copied().unwrap_or(0);
+        ctx.guard.tick_n(path, size)?;
         let siblings: Map<String, Value> = obj
             .iter()
             .filter(|(k, _)| k.as_str() != "$ref")
@@ -466,11 +895,27 @@ fn resolve_single_ref(
     }
 
     // Resolve the pointer against the root document.
-    let target =
-        resolve_pointer(ctx.root, ref_str).ok_or_else(|| ConvertError::UnresolvableRef {
-            path: path.to_string(),
-            reference: ref_str.to_string(),
-        })?;
+    let Some(target) = resolve_pointer(ctx.root, ref_str) else {
+        if ctx.config.on_error == ErrorPolicy::Fail {
+            return Err(ConvertError::UnresolvableRef {
+                path: path.to_string(),
+                reference: ref_str.to_string(),
+                location: None,
+            });
+        }
+        // Settled in Phase 3b; the marker survives copies of cached
+        // definitions that contain it.
+        let mut marker: Map<String, Value> = obj
+            .iter()
+            .filter(|(k, _)| k.as_str() != "$ref")
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        marker.insert(
+            UNRESOLVABLE_REF_MARKER.to_string(),
+            Value::String(ref_str.to_string()),
+        );
+        return Ok(Value::Object(marker));
+    };
 
     // Mark as visiting for cycle detection.
     ctx.visiting.insert(ref_str.to_string());
@@ -487,6 +932,10 @@ fn resolve_single_ref(
     ctx.visiting.remove(ref_str);
 
     // Cache the resolved value for future reuse (only non-recursive refs).
+    if ctx.guard.counts_nodes() {
+        ctx.cached_sizes
+            .insert(ref_str.to_string(), count_objects(&resolved));
+    }
     ctx.resolved_cache
         .insert(ref_str.to_string(), resolved.clone());
 
@@ -500,6 +949,16 @@ fn resolve_single_ref(
     merge_ref_siblings(resolved, siblings)
 }
 
+/// Number of JSON objects in `value`, i.e. the nodes a fresh expansion of it
+/// would have visited.
+fn count_objects(value: &Value) -> usize {
+    match value {
+        Value::Object(obj) => 1 + obj.values().map(count_objects).sum::<usize>(),
+        Value::Array(arr) => arr.iter().map(count_objects).sum(),
+        _ => 0,
+    }
+}
+
 /// Merge sibling keywords from a `$ref` site into the resolved definition.
 ///
 /// Annotations (description, title, etc.) override; structural siblings are
@@ -579,6 +1038,106 @@ fn recurse_children(
     )
 }
 
+// ---------------------------------------------------------------------------
+// Phase 3b: Unresolvable $ref recovery
+// ---------------------------------------------------------------------------
+
+/// Key of the node standing in for an unresolvable `$ref` between Phase 2
+/// and Phase 3b, when [`ConvertOptions::on_error`] is not
+/// [`ErrorPolicy::Fail`].
+const UNRESOLVABLE_REF_MARKER: &str = "x-jsl-unresolvable-ref";
+
+/// Replace every unresolvable-ref marker: drop the properties holding one
+/// under [`ErrorPolicy::SkipSubtree`], and turn the rest back into a `$ref`
+/// beside the marker's sibling keywords, which Pass 5 replaces with an opaque
+/// JSON-string placeholder.
+fn recover_unresolvable_refs(
+    schema: Value,
+    path: &str,
+    depth: usize,
+    config: &ConvertOptions,
+    diagnostics: &mut Vec<ProviderCompatError>,
+) -> Result<Value, ConvertError> {
+    let Value::Object(mut obj) = schema else {
+        return Ok(schema);
+    };
+    if let Some(reference) = unresolvable_ref(&obj).map(String::from) {
+        diagnostics.push(recovered(
+            path,
+            &reference,
+            ErrorPolicy::Placeholder,
+            config,
+        ));
+        obj.remove(UNRESOLVABLE_REF_MARKER);
+        let mut placeholder = Map::from_iter([("$ref".to_string(), Value::String(reference))]);
+        placeholder.extend(obj);
+        return Ok(Value::Object(placeholder));
+    }
+
+    if config.on_error == ErrorPolicy::SkipSubtree {
+        if let Some(Value::Object(props)) = obj.get_mut("properties") {
+            let skipped: Vec<(String, String)> = props
+                .iter()
+                .filter_map(|(name, prop)| {
+                    let reference = prop.as_object().and_then(unresolvable_ref)?;
+                    Some((name.clone(), reference.to_string()))
+                })
+                .collect();
+            for (name, reference) in &skipped {
+                props.remove(name);
+                let prop_path = build_path(path, &["properties", name]);
+                diagnostics.push(recovered(
+                    &prop_path,
+                    reference,
+                    ErrorPolicy::SkipSubtree,
+                    config,
+                ));
+            }
+            if let Some(Value::Array(required)) = obj.get_mut("required") {
+                required.retain(|name| {
+                    !skipped
+                        .iter()
+                        .any(|(skipped, _)| name.as_str() == Some(skipped.as_str()))
+                });
+            }
+        }
+    }
+
+    crate::schema_utils::recurse_into_children(
+        &mut obj,
+        path,
+        depth,
+        &mut |val, child_path, child_depth| {
+            recover_unresolvable_refs(val, child_path, child_depth, config, diagnostics)
+        },
+    )?;
+    Ok(Value::Object(obj))
+}
+
+/// The reference an unresolvable-ref marker stands in for.
+fn unresolvable_ref(obj: &Map<String, Value>) -> Option<&str> {
+    obj.get(UNRESOLVABLE_REF_MARKER).and_then(Value::as_str)
+}
+
+fn recovered(
+    path: &str,
+    reference: &str,
+    action: ErrorPolicy,
+    config: &ConvertOptions,
+) -> ProviderCompatError {
+    let hint = match action {
+        ErrorPolicy::SkipSubtree => "The property was removed; fix the reference to restore it.",
+        _ => "Replaced with an opaque JSON string; fix the reference to restore its structure.",
+    };
+    ProviderCompatError::UnresolvableRefRecovered {
+        path: path.to_string(),
+        reference: reference.to_string(),
+        action,
+        target: config.target,
+        hint: hint.to_string(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Phase 3: Cleanup
 // ---------------------------------------------------------------------------
@@ -645,6 +1204,110 @@ fn cleanup(schema: Value, _recursive_refs: &[String]) -> Value {
     Value::Object(obj)
 }
 
+// ---------------------------------------------------------------------------
+// Phase 4: Embedded JSON strings
+// ---------------------------------------------------------------------------
+
+/// Apply [`ContentSchemaPolicy`] to string fields whose content is JSON.
+///
+/// `contentSchema` is otherwise pure metadata and stripped for every
+/// provider, leaving the model no hint of the expected structure. Entries
+/// left in `$defs` (recursive types) are always made opaque: their
+/// `$defs`-relative paths never match a data location on rehydration.
+fn embed_content_schemas(
+    schema: Value,
+    config: &ConvertOptions,
+    transforms: &mut Vec<Transform>,
+) -> Result<Value, ConvertError> {
+    let Value::Object(mut obj) = schema else {
+        return Ok(schema);
+    };
+    let defs = obj.remove("$defs");
+    let inline = config.content_schema == ContentSchemaPolicy::Inline;
+    let mut root = embed_content_schema(Value::Object(obj), "#", 0, inline, config, transforms)?;
+
+    if let (Some(Value::Object(defs)), Some(root_obj)) = (defs, root.as_object_mut()) {
+        let mut walked = Map::new();
+        for (name, def) in defs {
+            let def_path = crate::schema_utils::build_path("#", &["$defs", &name]);
+            let def = embed_content_schema(def, &def_path, 1, false, config, transforms)?;
+            walked.insert(name, def);
+        }
+        root_obj.insert("$defs".to_string(), Value::Object(walked));
+    }
+    Ok(root)
+}
+
+fn embed_content_schema(
+    value: Value,
+    path: &str,
+    depth: usize,
+    inline: bool,
+    config: &ConvertOptions,
+    transforms: &mut Vec<Transform>,
+) -> Result<Value, ConvertError> {
+    let Value::Object(mut obj) = value else {
+        return Ok(value);
+    };
+
+    if embeds_json(&obj) {
+        let content = obj
+            .remove("contentSchema")
+            .expect("invariant: embeds_json checked contentSchema is present");
+        match content {
+            Value::Object(mut inlined) if inline => {
+                // Site annotations win over the embedded schema's own.
+                for key in ["title", "description"] {
+                    if let Some(v) = obj.remove(key) {
+                        inlined.insert(key.to_string(), v);
+                    }
+                }
+                transforms.push(Transform::JsonStringEncode {
+                    path: path.to_string(),
+                });
+                obj = inlined;
+            }
+            content => {
+                let text =
+                    crate::schema_utils::build_opaque_description(&content, config.messages());
+                crate::schema_utils::append_to_description(&mut obj, &text);
+            }
+        }
+    }
+
+    crate::schema_utils::recurse_into_children(
+        &mut obj,
+        path,
+        depth,
+        &mut |val, child_path, child_depth| {
+            embed_content_schema(val, child_path, child_depth, inline, config, transforms)
+        },
+    )?;
+    Ok(Value::Object(obj))
+}
+
+/// A string whose content is JSON described by an object `contentSchema`.
+/// Encoded content (`contentEncoding`, e.g. base64 of JSON) is left alone.
+fn embeds_json(obj: &Map<String, Value>) -> bool {
+    obj.get("contentMediaType")
+        .and_then(Value::as_str)
+        .is_some_and(is_json_media_type)
+        && !obj.contains_key("contentEncoding")
+        && obj.get("contentSchema").is_some_and(Value::is_object)
+}
+
+/// `application/json` and structured-syntax `+json` types such as
+/// `application/geo+json`, ignoring parameters.
+fn is_json_media_type(media_type: &str) -> bool {
+    let essence = media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
 /// Rewrite `$ref` pointers from `#/definitions/` to `#/$defs/` after rename.
 fn rewrite_definition_refs(value: &mut Value) {
     match value {
@@ -1091,6 +1754,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_id_ref_resolves_embedded_resource() {
+        let input = json!({
+            "$id": "https://example.com/schemas/customer",
+            "type": "object",
+            "properties": {
+                "billing": { "$ref": "https://example.com/schemas/address" },
+                "shipping": { "$ref": "address" },
+                "zip": { "$ref": "address#/properties/zip" }
+            },
+            "$defs": {
+                "addr": {
+                    "$id": "https://example.com/schemas/address",
+                    "type": "object",
+                    "properties": { "zip": { "type": "string" } }
+                }
+            }
+        });
+
+        let (output, _) = run(input);
+        let props = &output["properties"];
+        assert_eq!(props["billing"]["properties"]["zip"]["type"], "string");
+        assert_eq!(props["shipping"]["properties"]["zip"]["type"], "string");
+        assert_eq!(props["zip"], json!({ "type": "string" }));
+    }
+
     // -----------------------------------------------------------------------
     // Test 13: $ref pointing to nonexistent definition → error
     // -----------------------------------------------------------------------
@@ -1522,4 +2211,398 @@ mod tests {
             serde_json::to_string_pretty(step).unwrap()
         );
     }
+
+    // -----------------------------------------------------------------------
+    // Embedded JSON strings (contentSchema)
+    // -----------------------------------------------------------------------
+
+    fn embedded_json_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "payload": {
+                    "type": "string",
+                    "description": "Event payload",
+                    "contentMediaType": "application/json",
+                    "contentSchema": {
+                        "type": "object",
+                        "properties": { "id": { "type": "integer" } },
+                        "required": ["id"]
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_content_schema_opaque_by_default() {
+        let result = normalize(&embedded_json_schema(), &ConvertOptions::default()).unwrap();
+        let payload = &result.pass.schema["properties"]["payload"];
+        assert_eq!(payload["type"], "string");
+        assert!(payload.get("contentSchema").is_none());
+        let desc = payload["description"].as_str().unwrap();
+        assert!(
+            desc.starts_with("Event payload. MUST be a valid JSON"),
+            "{desc}"
+        );
+        assert!(desc.contains("id: integer"), "{desc}");
+        assert!(result.pass.transforms.is_empty());
+    }
+
+    #[test]
+    fn test_content_schema_inline() {
+        let config = ConvertOptions {
+            content_schema: ContentSchemaPolicy::Inline,
+            ..ConvertOptions::default()
+        };
+        let result = normalize(&embedded_json_schema(), &config).unwrap();
+        assert_eq!(
+            result.pass.schema["properties"]["payload"],
+            json!({
+                "type": "object",
+                "properties": { "id": { "type": "integer" } },
+                "required": ["id"],
+                "description": "Event payload"
+            })
+        );
+        assert!(matches!(
+            result.pass.transforms.as_slice(),
+            [Transform::JsonStringEncode { path }] if path == "#/properties/payload"
+        ));
+    }
+
+    #[test]
+    fn test_content_schema_ignored_for_encoded_or_non_json_content() {
+        let config = ConvertOptions {
+            content_schema: ContentSchemaPolicy::Inline,
+            ..ConvertOptions::default()
+        };
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "b64": {
+                    "type": "string",
+                    "contentEncoding": "base64",
+                    "contentMediaType": "application/json",
+                    "contentSchema": { "type": "object" }
+                },
+                "xml": {
+                    "type": "string",
+                    "contentMediaType": "application/xml",
+                    "contentSchema": { "type": "object" }
+                }
+            }
+        });
+        let result = normalize(&schema, &config).unwrap();
+        assert_eq!(result.pass.schema, schema);
+        assert!(result.pass.transforms.is_empty());
+    }
+
+    #[test]
+    fn test_json_media_types() {
+        assert!(is_json_media_type("application/json"));
+        assert!(is_json_media_type("Application/JSON; charset=utf-8"));
+        assert!(is_json_media_type("application/geo+json"));
+        assert!(!is_json_media_type("text/plain"));
+    }
+
+    // -----------------------------------------------------------------------
+    // Legacy drafts
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_draft04_keywords_upgraded() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-04/schema#",
+            "id": "http://example.com/order.json",
+            "type": "object",
+            "properties": {
+                "qty": { "type": "integer", "minimum": 0, "exclusiveMinimum": true },
+                "price": { "type": "number", "maximum": 100, "exclusiveMaximum": false },
+                "card": { "type": "string" },
+                "billing": { "type": "string" }
+            },
+            "dependencies": {
+                "card": ["billing"],
+                "billing": { "properties": { "card": { "minLength": 12 } } }
+            }
+        });
+        let result = normalize(&schema, &ConvertOptions::default()).unwrap();
+        let out = result.pass.schema;
+        assert_eq!(out["$id"], "http://example.com/order.json");
+        assert!(out.get("id").is_none());
+        assert_eq!(
+            out["properties"]["qty"],
+            json!({ "type": "integer", "exclusiveMinimum": 0 })
+        );
+        assert_eq!(
+            out["properties"]["price"],
+            json!({ "type": "number", "maximum": 100 })
+        );
+        assert_eq!(out["dependentRequired"], json!({ "card": ["billing"] }));
+        assert_eq!(
+            out["dependentSchemas"]["billing"]["properties"]["card"]["minLength"],
+            12
+        );
+        assert!(out.get("dependencies").is_none());
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_id_kept_outside_draft04() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "id": "not-an-identifier"
+        });
+        let (output, _) = run(schema);
+        assert_eq!(output["id"], "not-an-identifier");
+        assert!(output.get("$id").is_none());
+    }
+
+    #[test]
+    fn test_draft04_fragment_id_becomes_anchor() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-04/schema#",
+            "type": "object",
+            "properties": {
+                "item": { "$ref": "#item" }
+            },
+            "definitions": {
+                "item": { "id": "#item", "type": "string" }
+            }
+        });
+        let (output, _) = run(schema);
+        assert_eq!(output["properties"]["item"]["type"], "string");
+    }
+
+    #[test]
+    fn test_draft03_constructs_upgraded_or_reported() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-03/schema#",
+            "type": "object",
+            "extends": { "properties": { "base": { "type": "string" } } },
+            "properties": {
+                "name": { "type": "string", "required": true },
+                "step": { "type": "number", "divisibleBy": 5 },
+                "anything": { "type": "any" },
+                "either": { "type": ["string", { "type": "object" }] },
+                "notnull": { "disallow": "null" }
+            }
+        });
+        let result = normalize(&schema, &ConvertOptions::default()).unwrap();
+        let out = result.pass.schema;
+        assert_eq!(out["required"], json!(["name"]));
+        assert!(out["properties"]["name"].get("required").is_none());
+        assert_eq!(out["allOf"][0]["properties"]["base"]["type"], "string");
+        assert_eq!(out["properties"]["step"]["multipleOf"], 5);
+        assert!(out["properties"]["anything"].get("type").is_none());
+        assert_eq!(out["properties"]["either"]["type"], json!(["string"]));
+        assert!(out["properties"]["notnull"].get("disallow").is_none());
+
+        let reported: Vec<_> = result
+            .diagnostics
+            .iter()
+            .map(|d| match d {
+                ProviderCompatError::LegacyKeyword { path, keyword, .. } => {
+                    (path.as_str(), keyword.as_str())
+                }
+                other => panic!("unexpected diagnostic: {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                ("#/properties/either/type", "type"),
+                ("#/properties/notnull", "disallow")
+            ]
+        );
+    }
+
+    fn tree(root_anchor: bool) -> Value {
+        let mut schema = json!({
+            "$id": "https://example.com/strict-tree",
+            "type": "object",
+            "properties": {
+                "tree": { "$ref": "#/$defs/tree" },
+                "color": { "type": "string" }
+            },
+            "$defs": {
+                "tree": {
+                    "$id": "https://example.com/tree",
+                    "$dynamicAnchor": "node",
+                    "type": "object",
+                    "properties": {
+                        "children": { "type": "array", "items": { "$dynamicRef": "#node" } }
+                    }
+                }
+            }
+        });
+        if root_anchor {
+            schema["$dynamicAnchor"] = json!("node");
+        }
+        schema
+    }
+
+    #[test]
+    fn test_dynamic_ref_resolves_to_outermost_anchor() {
+        let config = ConvertOptions::default();
+        let mut diagnostics = Vec::new();
+
+        let mut extended = tree(true);
+        resolve_dynamic_refs(&mut extended, &config, &mut diagnostics).unwrap();
+        let items = &extended["$defs"]["tree"]["properties"]["children"]["items"];
+        assert_eq!(items, &json!({ "$ref": "#" }));
+
+        let mut plain = tree(false);
+        resolve_dynamic_refs(&mut plain, &config, &mut diagnostics).unwrap();
+        let items = &plain["$defs"]["tree"]["properties"]["children"]["items"];
+        assert_eq!(items, &json!({ "$ref": "#/$defs/tree" }));
+        assert!(diagnostics.is_empty());
+
+        // The rewritten refs go through ordinary cycle detection.
+        let result = normalize(&tree(true), &config).unwrap();
+        assert!(!result.recursive_refs.is_empty());
+    }
+
+    #[test]
+    fn test_resolved_dynamic_refs_strip_anchors_silently() {
+        let schema = json!({
+            "$dynamicAnchor": "node",
+            "type": "object",
+            "properties": {
+                "value": { "type": "string" },
+                "children": { "type": "array", "items": { "$dynamicRef": "#node" } }
+            },
+            "required": ["value", "children"]
+        });
+        let result = crate::convert(&schema, &ConvertOptions::default()).unwrap();
+        assert!(
+            result.provider_compat_errors.is_empty(),
+            "{:?}",
+            result.provider_compat_errors
+        );
+        assert!(!result.schema.to_string().contains("$dynamicAnchor"));
+    }
+
+    #[test]
+    fn test_unresolvable_dynamic_ref_stripped() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "x": { "$dynamicRef": "#missing", "type": "string" } }
+        });
+        let result = normalize(&schema, &ConvertOptions::default()).unwrap();
+        assert_eq!(
+            result.pass.schema["properties"]["x"],
+            json!({ "type": "string" })
+        );
+        assert!(matches!(
+            &result.diagnostics[..],
+            [ProviderCompatError::RefKeywordStripped { path, keyword, .. }]
+                if path == "#/properties/x" && keyword == "$dynamicRef"
+        ));
+    }
+
+    fn broken_order() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "customer": { "$ref": "#/$defs/Customer" },
+                "lines": { "type": "array", "items": { "$ref": "#/$defs/Line" } }
+            },
+            "required": ["id", "customer", "lines"],
+            "$defs": { "Customer": { "$ref": "#/$defs/Missing" } }
+        })
+    }
+
+    #[test]
+    fn test_unresolvable_ref_fails_by_default() {
+        let err = normalize(&broken_order(), &ConvertOptions::default()).unwrap_err();
+        assert!(matches!(err, ConvertError::UnresolvableRef { .. }));
+    }
+
+    #[test]
+    fn test_unresolvable_ref_skip_subtree() {
+        let config = ConvertOptions {
+            on_error: ErrorPolicy::SkipSubtree,
+            ..ConvertOptions::default()
+        };
+        let result = normalize(&broken_order(), &config).unwrap();
+        let schema = &result.pass.schema;
+        assert!(schema["properties"].get("customer").is_none());
+        assert_eq!(schema["required"], json!(["id", "lines"]));
+        // Not a property: falls back to a placeholder.
+        assert_eq!(
+            schema["properties"]["lines"]["items"],
+            json!({ "$ref": "#/$defs/Line" })
+        );
+        let reported: Vec<_> = result
+            .diagnostics
+            .iter()
+            .map(|d| match d {
+                ProviderCompatError::UnresolvableRefRecovered {
+                    path,
+                    reference,
+                    action,
+                    ..
+                } => (path.as_str(), reference.as_str(), *action),
+                other => panic!("unexpected diagnostic: {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                (
+                    "#/properties/customer",
+                    "#/$defs/Missing",
+                    ErrorPolicy::SkipSubtree
+                ),
+                (
+                    "#/properties/lines/items",
+                    "#/$defs/Line",
+                    ErrorPolicy::Placeholder
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unresolvable_ref_placeholder() {
+        let config = ConvertOptions {
+            on_error: ErrorPolicy::Placeholder,
+            ..ConvertOptions::default()
+        };
+        let result = normalize(&broken_order(), &config).unwrap();
+        let props = &result.pass.schema["properties"];
+        assert_eq!(props["customer"], json!({ "$ref": "#/$defs/Missing" }));
+        assert_eq!(props["lines"]["items"], json!({ "$ref": "#/$defs/Line" }));
+        assert_eq!(result.diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn test_unresolvable_ref_placeholder_keeps_siblings() {
+        let config = ConvertOptions {
+            on_error: ErrorPolicy::Placeholder,
+            ..ConvertOptions::default()
+        };
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "customer": { "$ref": "#/$defs/Customer", "description": "Who ordered" },
+                "note": { "$ref": "#/$defs/Note", "title": "Note" }
+            },
+            "$defs": { "Customer": { "$ref": "#/$defs/Missing" } }
+        });
+        let result = normalize(&schema, &config).unwrap();
+        let props = &result.pass.schema["properties"];
+        assert_eq!(
+            props["customer"],
+            json!({ "$ref": "#/$defs/Missing", "description": "Who ordered" })
+        );
+        assert_eq!(
+            props["note"],
+            json!({ "$ref": "#/$defs/Note", "title": "Note" })
+        );
+    }
 }
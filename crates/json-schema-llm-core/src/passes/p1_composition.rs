@@ -6,11 +6,27 @@
 //! Merge semantics follow JSON Schema's `allOf` definition: a value must
 //! satisfy ALL sub-schemas simultaneously. Constraints tighten (not loosen),
 //! types narrow (not widen), and strictness wins.
-
-use crate::codec::DroppedConstraint;
-use crate::config::ConvertOptions;
-use crate::error::ConvertError;
-use crate::schema_utils::build_path;
+//!
+//! With `filter_read_only` / `filter_write_only` set, the merged objects
+//! also drop their `readOnly` / `writeOnly` properties here, so later passes
+//! never see them. Each removal is recorded as a `FilteredProperty` transform.
+//! `deprecated` properties are dropped the same way, or annotated, per
+//! `deprecated_properties`, and reported as diagnostics.
+//!
+//! Members that disagree on a keyword (no common `type`, different `const`s,
+//! disjoint `enum`s, different `format`/`pattern`/...) are settled per
+//! `merge_conflicts` and reported as `merge_conflict` diagnostics.
+//!
+//! `unevaluatedProperties: false` / `unevaluatedItems: false` on a merged
+//! object are folded into `additionalProperties: false` / `maxItems` when
+//! nothing else left on the node evaluates properties or items; otherwise
+//! they are recorded as dropped constraints and checked on rehydration.
+
+use crate::codec::{DroppedConstraint, Transform};
+use crate::config::{ConvertOptions, DeprecatedPolicy, MergeConflictPolicy, Target};
+use crate::error::{ConvertError, ProviderCompatError};
+use crate::limits::ResourceGuard;
+use crate::schema_utils::{append_to_description, build_path};
 use serde_json::{Map, Value};
 use std::collections::HashSet;
 
@@ -21,22 +37,52 @@ use super::pass_utils::extract_type_strings;
 // Public API
 // ---------------------------------------------------------------------------
 
+/// Result of Pass 1: the shared pass result plus a diagnostic for every
+/// `deprecated` property dropped or annotated.
+#[derive(Debug)]
+pub struct CompositionPassResult {
+    pub pass: PassResult,
+    pub diagnostics: Vec<ProviderCompatError>,
+}
+
 /// Apply composition compilation to a schema.
 ///
 /// Recursively walks schema objects reachable via `properties`, `items`,
 /// `anyOf`/`oneOf`/`allOf`, and `additionalProperties`. Where `allOf` is
 /// found, all sub-schemas are merged into one flat object.
+#[cfg(test)]
 pub fn compile_composition(
     schema: Value,
     config: &ConvertOptions,
-) -> Result<PassResult, ConvertError> {
+) -> Result<CompositionPassResult, ConvertError> {
+    compile_composition_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`compile_composition`], ticking `guard` at every node visited.
+pub fn compile_composition_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<CompositionPassResult, ConvertError> {
     let mut dropped = Vec::new();
+    let mut transforms = Vec::new();
+    let mut diagnostics = Vec::new();
     let mut folder = CompositionFolder {
+        guard,
         config,
         dropped: &mut dropped,
+        transforms: &mut transforms,
+        diagnostics: &mut diagnostics,
     };
     let result = crate::schema_walker::fold(schema, &mut folder, "#", 0)?;
-    Ok(PassResult::with_dropped(result, dropped))
+    Ok(CompositionPassResult {
+        pass: PassResult {
+            schema: result,
+            transforms,
+            dropped_constraints: dropped,
+        },
+        diagnostics,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -45,7 +91,10 @@ pub fn compile_composition(
 
 struct CompositionFolder<'a> {
     config: &'a ConvertOptions,
+    guard: &'a ResourceGuard,
     dropped: &'a mut Vec<DroppedConstraint>,
+    transforms: &'a mut Vec<Transform>,
+    diagnostics: &'a mut Vec<ProviderCompatError>,
 }
 
 impl crate::schema_walker::SchemaFolder for CompositionFolder<'_> {
@@ -63,6 +112,7 @@ impl crate::schema_walker::SchemaFolder for CompositionFolder<'_> {
                 max_depth: self.config.max_depth,
             });
         }
+        self.guard.tick(path)?;
 
         let Value::Object(mut obj) = schema else {
             return Ok(crate::schema_walker::FoldAction::Replace(schema));
@@ -93,10 +143,36 @@ impl crate::schema_walker::SchemaFolder for CompositionFolder<'_> {
             // Merge: siblings first, then allOf sub-schemas overlay.
             let mut all = vec![siblings];
             all.extend(walked);
-            let merged = merge_allof(all, path, self.dropped)?;
+            let mut ctx = MergeCtx {
+                policy: self.config.merge_conflicts,
+                target: self.config.target,
+                dropped: self.dropped,
+                diagnostics: self.diagnostics,
+            };
+            let mut merged = merge_allof(all, path, &mut ctx)?;
+            if let Some(merged_obj) = merged.as_object_mut() {
+                filter_properties(
+                    merged_obj,
+                    path,
+                    self.config,
+                    self.transforms,
+                    self.diagnostics,
+                );
+                annotate_deprecated(merged_obj, path, self.config, self.diagnostics);
+                fold_unevaluated(merged_obj, path, self.dropped);
+            }
             return Ok(crate::schema_walker::FoldAction::Replace(merged));
         }
 
+        filter_properties(
+            &mut obj,
+            path,
+            self.config,
+            self.transforms,
+            self.diagnostics,
+        );
+        annotate_deprecated(&mut obj, path, self.config, self.diagnostics);
+
         // No allOf — let the generic fold driver handle child recursion.
         Ok(crate::schema_walker::FoldAction::Continue(Value::Object(
             obj,
@@ -104,20 +180,270 @@ impl crate::schema_walker::SchemaFolder for CompositionFolder<'_> {
     }
 }
 
+// ---------------------------------------------------------------------------
+// readOnly / writeOnly filtering
+// ---------------------------------------------------------------------------
+
+/// Remove the properties of `obj` marked with a filtered keyword, along with
+/// their `required` entries, recording a `FilteredProperty` transform for each
+/// (plus a diagnostic for dropped `deprecated` ones).
+fn filter_properties(
+    obj: &mut Map<String, Value>,
+    path: &str,
+    config: &ConvertOptions,
+    transforms: &mut Vec<Transform>,
+    diagnostics: &mut Vec<ProviderCompatError>,
+) {
+    let keywords: Vec<&str> = [
+        ("readOnly", config.filter_read_only),
+        ("writeOnly", config.filter_write_only),
+        (
+            "deprecated",
+            config.deprecated_properties == DeprecatedPolicy::Drop,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(keyword, enabled)| enabled.then_some(keyword))
+    .collect();
+    if keywords.is_empty() {
+        return;
+    }
+    let Some(props) = obj.get_mut("properties").and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    let filtered: Vec<(String, &str)> = props
+        .iter()
+        .filter_map(|(name, prop)| {
+            keywords
+                .iter()
+                .find(|keyword| is_marked(prop, keyword))
+                .map(|keyword| (name.clone(), *keyword))
+        })
+        .collect();
+    if filtered.is_empty() {
+        return;
+    }
+
+    for (name, keyword) in &filtered {
+        let prop = props.remove(name).unwrap_or_default();
+        let placeholder = prop.get("default").or_else(|| prop.get("const")).cloned();
+        if *keyword == "deprecated" {
+            diagnostics.push(ProviderCompatError::DeprecatedProperty {
+                path: build_path(path, &["properties", name]),
+                action: DeprecatedPolicy::Drop,
+                target: config.target,
+                hint: "Removed from the schema; rehydration restores its default, if any."
+                    .to_string(),
+            });
+        }
+        transforms.push(Transform::FilteredProperty {
+            path: path.to_string(),
+            property: name.clone(),
+            keyword: keyword.to_string(),
+            placeholder,
+        });
+    }
+    if let Some(required) = obj.get_mut("required").and_then(Value::as_array_mut) {
+        required.retain(|r| !filtered.iter().any(|(name, _)| r == name.as_str()));
+    }
+}
+
+/// Under [`DeprecatedPolicy::Annotate`], append the catalog's deprecation
+/// note to each `deprecated` property of `obj`.
+fn annotate_deprecated(
+    obj: &mut Map<String, Value>,
+    path: &str,
+    config: &ConvertOptions,
+    diagnostics: &mut Vec<ProviderCompatError>,
+) {
+    if config.deprecated_properties != DeprecatedPolicy::Annotate {
+        return;
+    }
+    let Some(props) = obj.get_mut("properties").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for (name, prop) in props.iter_mut() {
+        if !is_marked(prop, "deprecated") {
+            continue;
+        }
+        let Some(prop_obj) = prop.as_object_mut() else {
+            continue;
+        };
+        append_to_description(prop_obj, &config.messages().deprecated_note);
+        diagnostics.push(ProviderCompatError::DeprecatedProperty {
+            path: build_path(path, &["properties", name]),
+            action: DeprecatedPolicy::Annotate,
+            target: config.target,
+            hint: "Description now asks the model to avoid populating it.".to_string(),
+        });
+    }
+}
+
+/// Whether `prop` is marked `keyword: true`, directly or in an `allOf`
+/// member (not merged yet when the parent is visited).
+fn is_marked(prop: &Value, keyword: &str) -> bool {
+    let marked = |schema: &Value| schema.get(keyword).and_then(Value::as_bool) == Some(true);
+    marked(prop)
+        || prop
+            .get("allOf")
+            .and_then(Value::as_array)
+            .is_some_and(|members| members.iter().any(marked))
+}
+
+// ---------------------------------------------------------------------------
+// unevaluatedProperties / unevaluatedItems
+// ---------------------------------------------------------------------------
+
+/// Keywords whose subschemas may evaluate properties or items of the node
+/// they sit on, so `unevaluated*` cannot be rewritten in local terms.
+const IN_PLACE_APPLICATORS: &[&str] = &["anyOf", "oneOf", "$ref", "dependentSchemas"];
+
+/// Rewrite `unevaluatedProperties: false` / `unevaluatedItems: false` on a
+/// merged `allOf` node.
+///
+/// Once the members are flattened every declared property lives in one
+/// `properties` map, so `unevaluatedProperties: false` means
+/// `additionalProperties: false`, and `unevaluatedItems: false` means at
+/// most `prefixItems.len()` items. That only holds while no in-place
+/// applicator is left on the node; if one is, the keyword is dropped and
+/// recorded with the property names (or item count) every branch evaluates,
+/// for the rehydrator to check. Next to `additionalProperties` / `items`
+/// the keyword is redundant and simply removed.
+fn fold_unevaluated(
+    obj: &mut Map<String, Value>,
+    path: &str,
+    dropped: &mut Vec<DroppedConstraint>,
+) {
+    let sound = !IN_PLACE_APPLICATORS.iter().any(|k| obj.contains_key(*k));
+
+    if obj.get("unevaluatedProperties") == Some(&Value::Bool(false)) {
+        obj.remove("unevaluatedProperties");
+        if obj.contains_key("additionalProperties") {
+            // Already evaluates everything `properties` does not.
+        } else if sound {
+            obj.insert("additionalProperties".to_string(), Value::Bool(false));
+        } else {
+            let mut names: Vec<Value> = Vec::new();
+            for schema in std::iter::once(&*obj).chain(branches(obj)) {
+                for name in schema
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flat_map(|p| p.keys())
+                {
+                    if !names.iter().any(|n| n == name.as_str()) {
+                        names.push(Value::String(name.clone()));
+                    }
+                }
+            }
+            dropped.push(DroppedConstraint {
+                path: path.to_string(),
+                constraint: "unevaluatedProperties".to_string(),
+                value: Value::Array(names),
+            });
+        }
+    }
+
+    if obj.get("unevaluatedItems") == Some(&Value::Bool(false)) {
+        obj.remove("unevaluatedItems");
+        let evaluates_all = |schema: &Map<String, Value>| schema.contains_key("items");
+        let prefix_len = |schema: &Map<String, Value>| {
+            schema
+                .get("prefixItems")
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len)
+        };
+        if evaluates_all(obj) {
+            // Already evaluates everything `prefixItems` does not.
+        } else if sound && !obj.contains_key("contains") {
+            tighten_max(obj, "maxItems", Value::from(prefix_len(obj)));
+        } else if !branches(obj).any(evaluates_all) {
+            // (A branch with `items` evaluates every item, leaving no count
+            // that holds for all branches — nothing to record then.)
+            let evaluated = std::iter::once(&*obj)
+                .chain(branches(obj))
+                .map(prefix_len)
+                .max()
+                .unwrap_or(0);
+            dropped.push(DroppedConstraint {
+                path: path.to_string(),
+                constraint: "unevaluatedItems".to_string(),
+                value: Value::from(evaluated),
+            });
+        }
+    }
+}
+
+/// The object-valued `anyOf` / `oneOf` branches of `obj`.
+fn branches(obj: &Map<String, Value>) -> impl Iterator<Item = &Map<String, Value>> {
+    ["anyOf", "oneOf"]
+        .into_iter()
+        .filter_map(|k| obj.get(k).and_then(Value::as_array))
+        .flatten()
+        .filter_map(Value::as_object)
+}
+
 // ---------------------------------------------------------------------------
 // allOf merge
 // ---------------------------------------------------------------------------
 
+/// State threaded through an `allOf` merge.
+struct MergeCtx<'a> {
+    policy: MergeConflictPolicy,
+    target: Target,
+    dropped: &'a mut Vec<DroppedConstraint>,
+    diagnostics: &'a mut Vec<ProviderCompatError>,
+}
+
+impl MergeCtx<'_> {
+    /// Settle a conflict on `keyword` between the earlier member's `first`
+    /// and the later member's `later`, returning the value to keep.
+    /// `intersection` is what [`MergeConflictPolicy::Intersect`] keeps;
+    /// `None` means nothing satisfies both, which is an error.
+    fn resolve(
+        &mut self,
+        path: &str,
+        keyword: &str,
+        first: &Value,
+        later: &Value,
+        intersection: Option<Value>,
+    ) -> Result<Value, ConvertError> {
+        let kept = match (self.policy, intersection) {
+            (MergeConflictPolicy::PreferFirst, _) => first.clone(),
+            (MergeConflictPolicy::Intersect, Some(kept)) => kept,
+            _ => {
+                return Err(ConvertError::SchemaError {
+                    path: path.to_string(),
+                    message: format!(
+                        "allOf {} conflict: cannot merge {} with {}",
+                        keyword, first, later
+                    ),
+                    location: None,
+                })
+            }
+        };
+        self.diagnostics.push(ProviderCompatError::MergeConflict {
+            path: path.to_string(),
+            keyword: keyword.to_string(),
+            resolution: self.policy,
+            target: self.target,
+            hint: format!("Merged {} with {}; kept {}.", first, later, kept),
+        });
+        Ok(kept)
+    }
+}
+
 /// Fold N sub-schemas into one via pairwise merge.
 fn merge_allof(
     schemas: Vec<Value>,
     path: &str,
-    dropped: &mut Vec<DroppedConstraint>,
+    ctx: &mut MergeCtx<'_>,
 ) -> Result<Value, ConvertError> {
     let mut iter = schemas.into_iter();
     let mut acc = iter.next().unwrap_or(Value::Object(Map::new()));
     for overlay in iter {
-        acc = merge_two(acc, overlay, path, dropped)?;
+        acc = merge_two(acc, overlay, path, ctx)?;
     }
     Ok(acc)
 }
@@ -127,7 +453,7 @@ fn merge_two(
     base: Value,
     overlay: Value,
     path: &str,
-    dropped: &mut Vec<DroppedConstraint>,
+    ctx: &mut MergeCtx<'_>,
 ) -> Result<Value, ConvertError> {
     // Boolean schema `false` is the "impossible" schema — absorbs everything.
     if base == Value::Bool(false) || overlay == Value::Bool(false) {
@@ -148,33 +474,40 @@ fn merge_two(
         match k.as_str() {
             // --- Recursive merge: properties ---
             "properties" => {
-                merge_properties(&mut result, v, path, dropped)?;
+                merge_properties(&mut result, v, path, ctx)?;
             }
 
-            // --- Union: required ---
-            "required" => {
-                merge_required(&mut result, v);
+            // --- Union: required, propertyOrdering ---
+            "required" | "propertyOrdering" => {
+                merge_string_union(&mut result, &k, v);
             }
 
             // --- Intersection: type ---
             "type" => {
-                intersect_type(&mut result, v, path)?;
+                intersect_type(&mut result, v, path, ctx)?;
             }
 
             // --- Conflict: const ---
             "const" => {
-                if let Some(existing) = result.get("const") {
-                    if *existing != v {
-                        return Err(ConvertError::SchemaError {
-                            path: path.to_string(),
-                            message: format!(
-                                "allOf const conflict: cannot merge {:?} with {:?}",
-                                existing, v
-                            ),
-                        });
+                let kept = match result.get("const").cloned() {
+                    Some(existing) if existing != v => {
+                        ctx.resolve(path, "const", &existing, &v, None)?
                     }
-                }
-                result.insert("const".to_string(), v);
+                    _ => v,
+                };
+                result.insert("const".to_string(), kept);
+            }
+
+            // --- Conflict: keywords with no writable intersection ---
+            "format" | "pattern" | "multipleOf" | "contentEncoding" | "contentMediaType" => {
+                let kept = match result.get(&k).cloned() {
+                    Some(existing) if existing != v => {
+                        let later = v.clone();
+                        ctx.resolve(path, &k, &existing, &v, Some(later))?
+                    }
+                    _ => v,
+                };
+                result.insert(k, kept);
             }
 
             // --- Concatenate: description ---
@@ -184,7 +517,7 @@ fn merge_two(
 
             // --- Intersection: enum ---
             "enum" => {
-                intersect_enum(&mut result, v);
+                intersect_enum(&mut result, v, path, ctx)?;
             }
 
             // --- Tighten: numeric lower bounds ---
@@ -199,17 +532,24 @@ fn merge_two(
 
             // --- Strictness intersection: additionalProperties ---
             "additionalProperties" => {
-                merge_additional_properties(&mut result, v, path, dropped)?;
+                merge_additional_properties(&mut result, v, path, ctx)?;
+            }
+
+            // --- Strictness: unevaluated* (`false` wins) ---
+            "unevaluatedProperties" | "unevaluatedItems" => {
+                if result.get(&k) != Some(&Value::Bool(false)) {
+                    result.insert(k, v);
+                }
             }
 
             // --- Recursive merge: items ---
             "items" => {
-                merge_items(&mut result, v, path, dropped)?;
+                merge_items(&mut result, v, path, ctx)?;
             }
 
             // --- Drop: if/then/else ---
             "if" | "then" | "else" => {
-                dropped.push(DroppedConstraint {
+                ctx.dropped.push(DroppedConstraint {
                     path: path.to_string(),
                     constraint: k,
                     value: v,
@@ -226,7 +566,7 @@ fn merge_two(
     // Strip any if/then/else that survived from the base schema
     for kw in &["if", "then", "else"] {
         if let Some(v) = result.remove(*kw) {
-            dropped.push(DroppedConstraint {
+            ctx.dropped.push(DroppedConstraint {
                 path: path.to_string(),
                 constraint: kw.to_string(),
                 value: v,
@@ -246,7 +586,7 @@ fn merge_properties(
     result: &mut Map<String, Value>,
     overlay_val: Value,
     path: &str,
-    dropped: &mut Vec<DroppedConstraint>,
+    ctx: &mut MergeCtx<'_>,
 ) -> Result<(), ConvertError> {
     let Value::Object(overlay_props) = overlay_val else {
         return Ok(());
@@ -261,7 +601,7 @@ fn merge_properties(
         if let Some(existing) = base_map.remove(&prop_key) {
             // Both define this property — full recursive merge
             let prop_path = build_path(path, &["properties", &prop_key]);
-            let merged = merge_two(existing, prop_val, &prop_path, dropped)?;
+            let merged = merge_two(existing, prop_val, &prop_path, ctx)?;
             base_map.insert(prop_key, merged);
         } else {
             base_map.insert(prop_key, prop_val);
@@ -270,13 +610,13 @@ fn merge_properties(
     Ok(())
 }
 
-/// Union `required` arrays (deduplicated).
-fn merge_required(result: &mut Map<String, Value>, overlay_val: Value) {
+/// Union string arrays such as `required` (deduplicated, base order first).
+fn merge_string_union(result: &mut Map<String, Value>, key: &str, overlay_val: Value) {
     let Value::Array(overlay_arr) = overlay_val else {
         return;
     };
     let base_req = result
-        .entry("required")
+        .entry(key)
         .or_insert_with(|| Value::Array(Vec::new()));
     let Some(base_arr) = base_req.as_array_mut() else {
         return;
@@ -299,13 +639,14 @@ fn intersect_type(
     result: &mut Map<String, Value>,
     overlay_val: Value,
     path: &str,
+    ctx: &mut MergeCtx<'_>,
 ) -> Result<(), ConvertError> {
-    let Some(existing) = result.get("type") else {
+    let Some(existing) = result.get("type").cloned() else {
         result.insert("type".to_string(), overlay_val);
         return Ok(());
     };
 
-    let base_type = type_to_string(existing);
+    let base_type = type_to_string(&existing);
     let overlay_type = type_to_string(&overlay_val);
 
     if let (Some(b), Some(o)) = (&base_type, &overlay_type) {
@@ -318,14 +659,13 @@ fn intersect_type(
             result.insert("type".to_string(), Value::String("integer".to_string()));
             return Ok(());
         }
-        return Err(ConvertError::SchemaError {
-            path: path.to_string(),
-            message: format!("allOf type conflict: cannot merge '{}' with '{}'", b, o),
-        });
+        let kept = ctx.resolve(path, "type", &existing, &overlay_val, None)?;
+        result.insert("type".to_string(), kept);
+        return Ok(());
     }
 
     // Handle array-form types: intersect the type sets.
-    let base_types: HashSet<String> = extract_type_strings(existing).into_iter().collect();
+    let base_types: HashSet<String> = extract_type_strings(&existing).into_iter().collect();
     let overlay_types: HashSet<String> = extract_type_strings(&overlay_val).into_iter().collect();
 
     if !base_types.is_empty() && !overlay_types.is_empty() {
@@ -341,15 +681,9 @@ fn intersect_type(
             .into_iter()
             .collect();
         if intersection.is_empty() {
-            return Err(ConvertError::SchemaError {
-                path: path.to_string(),
-                message: format!(
-                    "allOf type conflict: no common type between {:?} and {:?}",
-                    base_types, overlay_types
-                ),
-            });
-        }
-        if intersection.len() == 1 {
+            let kept = ctx.resolve(path, "type", &existing, &overlay_val, None)?;
+            result.insert("type".to_string(), kept);
+        } else if intersection.len() == 1 {
             result.insert("type".to_string(), Value::String(intersection[0].clone()));
         } else {
             let arr: Vec<Value> = intersection.into_iter().map(Value::String).collect();
@@ -375,20 +709,39 @@ fn merge_description(result: &mut Map<String, Value>, overlay_val: Value) {
 }
 
 /// Intersect `enum` values — keep only values present in ALL schemas.
-fn intersect_enum(result: &mut Map<String, Value>, overlay_val: Value) {
+fn intersect_enum(
+    result: &mut Map<String, Value>,
+    overlay_val: Value,
+    path: &str,
+    ctx: &mut MergeCtx<'_>,
+) -> Result<(), ConvertError> {
     let Value::Array(overlay_arr) = overlay_val else {
-        return;
+        return Ok(());
     };
     let Some(Value::Array(base_arr)) = result.get("enum") else {
         result.insert("enum".to_string(), Value::Array(overlay_arr));
-        return;
+        return Ok(());
     };
     let intersection: Vec<Value> = base_arr
         .iter()
         .filter(|v| overlay_arr.contains(v))
         .cloned()
         .collect();
-    result.insert("enum".to_string(), Value::Array(intersection));
+    let kept = if intersection.is_empty() && !base_arr.is_empty() && !overlay_arr.is_empty() {
+        let base = Value::Array(base_arr.clone());
+        let overlay = Value::Array(overlay_arr);
+        ctx.resolve(
+            path,
+            "enum",
+            &base,
+            &overlay,
+            Some(Value::Array(intersection)),
+        )?
+    } else {
+        Value::Array(intersection)
+    };
+    result.insert("enum".to_string(), kept);
+    Ok(())
 }
 
 /// Tighten a minimum-like bound: max of all values.
@@ -426,7 +779,7 @@ fn merge_additional_properties(
     result: &mut Map<String, Value>,
     overlay_val: Value,
     path: &str,
-    dropped: &mut Vec<DroppedConstraint>,
+    ctx: &mut MergeCtx<'_>,
 ) -> Result<(), ConvertError> {
     let Some(existing) = result.get("additionalProperties").cloned() else {
         result.insert("additionalProperties".to_string(), overlay_val);
@@ -451,7 +804,7 @@ fn merge_additional_properties(
     // Both schemas → recursive merge
     if existing.is_object() && overlay_val.is_object() {
         let child_path = build_path(path, &["additionalProperties"]);
-        let merged = merge_two(existing, overlay_val, &child_path, dropped)?;
+        let merged = merge_two(existing, overlay_val, &child_path, ctx)?;
         result.insert("additionalProperties".to_string(), merged);
         return Ok(());
     }
@@ -466,7 +819,7 @@ fn merge_items(
     result: &mut Map<String, Value>,
     overlay_val: Value,
     path: &str,
-    dropped: &mut Vec<DroppedConstraint>,
+    ctx: &mut MergeCtx<'_>,
 ) -> Result<(), ConvertError> {
     let Some(existing) = result.remove("items") else {
         result.insert("items".to_string(), overlay_val);
@@ -475,7 +828,7 @@ fn merge_items(
 
     if existing.is_object() && overlay_val.is_object() {
         let child_path = build_path(path, &["items"]);
-        let merged = merge_two(existing, overlay_val, &child_path, dropped)?;
+        let merged = merge_two(existing, overlay_val, &child_path, ctx)?;
         result.insert("items".to_string(), merged);
     } else {
         // Non-object items: overlay wins
@@ -526,7 +879,9 @@ mod tests {
 
     fn run(schema: Value) -> PassResult {
         let config = ConvertOptions::default();
-        compile_composition(schema, &config).expect("should not error")
+        compile_composition(schema, &config)
+            .expect("should not error")
+            .pass
     }
 
     // -----------------------------------------------------------------------
@@ -962,4 +1317,277 @@ mod tests {
             .iter()
             .any(|d| d.constraint == "else"));
     }
+
+    // -----------------------------------------------------------------------
+    // readOnly / writeOnly filtering
+    // -----------------------------------------------------------------------
+    fn user() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "readOnly": true },
+                "createdAt": { "type": "string", "readOnly": true, "default": "1970-01-01" },
+                "name": { "type": "string" },
+                "password": { "type": "string", "writeOnly": true }
+            },
+            "required": ["id", "name", "password"]
+        })
+    }
+
+    #[test]
+    fn test_filter_read_only() {
+        let config = ConvertOptions {
+            filter_read_only: true,
+            ..ConvertOptions::default()
+        };
+        let result = compile_composition(user(), &config).unwrap().pass;
+        let props = result.schema["properties"].as_object().unwrap();
+        let mut names: Vec<&str> = props.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["name", "password"]);
+        assert_eq!(result.schema["required"], json!(["name", "password"]));
+
+        assert_eq!(result.transforms.len(), 2);
+        let created = result
+            .transforms
+            .iter()
+            .find_map(|t| match t {
+                Transform::FilteredProperty {
+                    path,
+                    property,
+                    keyword,
+                    placeholder,
+                } if property == "createdAt" => Some((path, keyword, placeholder)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            created,
+            (
+                &"#".to_string(),
+                &"readOnly".to_string(),
+                &Some(json!("1970-01-01"))
+            )
+        );
+    }
+
+    #[test]
+    fn test_filter_write_only_inside_allof() {
+        let config = ConvertOptions {
+            filter_write_only: true,
+            ..ConvertOptions::default()
+        };
+        let input = json!({
+            "allOf": [
+                user(),
+                {
+                    "properties": {
+                        "secret": { "allOf": [{ "type": "string" }, { "writeOnly": true }] }
+                    }
+                }
+            ]
+        });
+        let result = compile_composition(input, &config).unwrap().pass;
+        let props = result.schema["properties"].as_object().unwrap();
+        assert!(!props.contains_key("password"));
+        assert!(!props.contains_key("secret"));
+        assert!(props.contains_key("id"), "readOnly kept unless filtered");
+        assert_eq!(result.schema["required"], json!(["id", "name"]));
+    }
+
+    #[test]
+    fn test_filter_off_by_default() {
+        let result = run(user());
+        assert_eq!(result.schema["properties"].as_object().unwrap().len(), 4);
+        assert!(result.transforms.is_empty());
+    }
+
+    fn account() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "email": { "type": "string" },
+                "fax": { "type": "string", "deprecated": true, "description": "Fax number" },
+                "legacyId": { "type": "integer", "deprecated": true, "default": 0 }
+            },
+            "required": ["email", "fax"]
+        })
+    }
+
+    #[test]
+    fn test_deprecated_drop_reports_each_property() {
+        let config = ConvertOptions {
+            deprecated_properties: DeprecatedPolicy::Drop,
+            ..ConvertOptions::default()
+        };
+        let result = compile_composition(account(), &config).unwrap();
+        let props = result.pass.schema["properties"].as_object().unwrap();
+        assert_eq!(props.keys().collect::<Vec<_>>(), ["email"]);
+        assert_eq!(result.pass.schema["required"], json!(["email"]));
+        assert_eq!(result.pass.transforms.len(), 2);
+
+        let paths: Vec<&str> = result.diagnostics.iter().map(|d| d.path()).collect();
+        assert_eq!(paths, ["#/properties/fax", "#/properties/legacyId"]);
+        assert!(result.diagnostics.iter().all(|d| matches!(
+            d,
+            ProviderCompatError::DeprecatedProperty {
+                action: DeprecatedPolicy::Drop,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_deprecated_annotate_keeps_property() {
+        let config = ConvertOptions {
+            deprecated_properties: DeprecatedPolicy::Annotate,
+            ..ConvertOptions::default()
+        };
+        let result = compile_composition(account(), &config).unwrap();
+        let props = &result.pass.schema["properties"];
+        assert_eq!(
+            props["fax"]["description"],
+            "Fax number. Deprecated: avoid populating this field."
+        );
+        assert_eq!(
+            props["legacyId"]["description"],
+            "Deprecated: avoid populating this field."
+        );
+        assert!(props["email"].get("description").is_none());
+        assert!(result.pass.transforms.is_empty());
+        assert_eq!(result.diagnostics.len(), 2);
+        assert_eq!(result.diagnostics[0].rule_id(), "JSL-P1-001");
+    }
+
+    #[test]
+    fn test_deprecated_keep_is_default() {
+        let result = compile_composition(account(), &ConvertOptions::default()).unwrap();
+        assert_eq!(result.pass.schema, account());
+        assert!(result.diagnostics.is_empty());
+    }
+
+    fn conflicting() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "kind": { "enum": ["a", "b"], "format": "email" },
+                "id": { "type": "string" }
+            },
+            "allOf": [{
+                "properties": {
+                    "kind": { "enum": ["c"], "format": "uri" },
+                    "id": { "type": "integer" }
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn test_merge_conflict_policies() {
+        let with = |merge_conflicts| ConvertOptions {
+            merge_conflicts,
+            ..ConvertOptions::default()
+        };
+
+        let err = compile_composition(conflicting(), &with(MergeConflictPolicy::Intersect))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("type conflict"), "{}", err);
+
+        let result =
+            compile_composition(conflicting(), &with(MergeConflictPolicy::PreferFirst)).unwrap();
+        let props = &result.pass.schema["properties"];
+        assert_eq!(
+            props["kind"],
+            json!({ "enum": ["a", "b"], "format": "email" })
+        );
+        assert_eq!(props["id"]["type"], "string");
+        let conflicts: Vec<(&str, &str)> = result
+            .diagnostics
+            .iter()
+            .map(|d| match d {
+                ProviderCompatError::MergeConflict { path, keyword, .. } => {
+                    (path.as_str(), keyword.as_str())
+                }
+                other => panic!("unexpected diagnostic: {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            conflicts,
+            [
+                ("#/properties/id", "type"),
+                ("#/properties/kind", "enum"),
+                ("#/properties/kind", "format"),
+            ]
+        );
+        assert_eq!(result.diagnostics[0].rule_id(), "JSL-P1-002");
+
+        let err = compile_composition(conflicting(), &with(MergeConflictPolicy::Error))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("allOf type conflict"), "{}", err);
+    }
+
+    #[test]
+    fn test_intersect_reports_disjoint_enum_and_later_pattern() {
+        let input = json!({
+            "allOf": [
+                { "enum": ["a"], "pattern": "^a" },
+                { "enum": ["b"], "pattern": "^b" }
+            ]
+        });
+        let result = compile_composition(input, &ConvertOptions::default()).unwrap();
+        assert_eq!(result.pass.schema, json!({ "enum": [], "pattern": "^b" }));
+        assert_eq!(result.diagnostics.len(), 2);
+        assert!(result.diagnostics[0]
+            .to_string()
+            .contains("Merged [\"a\"] with [\"b\"]; kept []."));
+    }
+
+    #[test]
+    fn test_unevaluated_folded_when_sound() {
+        let input = json!({
+            "allOf": [
+                { "type": "object", "properties": { "a": { "type": "string" } } },
+                { "properties": { "b": { "type": "string" } } }
+            ],
+            "unevaluatedProperties": false
+        });
+        let result = run(input);
+        assert_eq!(result.schema["additionalProperties"], json!(false));
+        assert!(result.schema.get("unevaluatedProperties").is_none());
+        assert!(result.dropped_constraints.is_empty());
+
+        let tuple = json!({
+            "allOf": [{ "prefixItems": [{ "type": "string" }, { "type": "integer" }] }],
+            "unevaluatedItems": false
+        });
+        let result = run(tuple);
+        assert_eq!(result.schema["maxItems"], json!(2));
+        assert!(result.schema.get("unevaluatedItems").is_none());
+    }
+
+    #[test]
+    fn test_unevaluated_recorded_with_branch_names() {
+        let input = json!({
+            "allOf": [{ "properties": { "kind": { "type": "string" } } }],
+            "oneOf": [
+                { "properties": { "a": { "type": "string" } } },
+                { "properties": { "b": { "type": "string" }, "kind": {} } }
+            ],
+            "unevaluatedProperties": false
+        });
+        let result = run(input);
+        assert!(result.schema.get("additionalProperties").is_none());
+        assert!(result.schema.get("unevaluatedProperties").is_none());
+        assert_eq!(result.dropped_constraints.len(), 1);
+        assert_eq!(
+            result.dropped_constraints[0].constraint,
+            "unevaluatedProperties"
+        );
+        assert_eq!(
+            result.dropped_constraints[0].value,
+            json!(["kind", "a", "b"])
+        );
+    }
 }
@@ -9,12 +9,27 @@
 //!
 //! When both `oneOf` and `anyOf` exist on the same node, both are wrapped into
 //! an `allOf` array to preserve intersection semantics.
+//!
+//! Unions with an OpenAPI-style `discriminator` are recorded as
+//! `DiscriminatorAnyOf` codec transforms listing the discriminator values
+//! their variants accept, so rehydration can repair near-miss values.
+//!
+//! With [`ConvertOptions::max_anyof_variants`] set, unions over the cap keep
+//! their leading variants and collapse the rest into one opaque JSON-string
+//! branch, recorded as an `AnyOfOverflow` codec transform.
 
-use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 
+use serde_json::{json, Map, Value};
+
+use crate::codec::Transform;
 use crate::config::{ConvertOptions, PolymorphismStrategy, Target};
 use crate::error::ConvertError;
-use crate::schema_utils::recurse_into_children;
+use crate::limits::ResourceGuard;
+use crate::messages::{fill, MessageCatalog};
+use crate::schema_utils::{
+    build_path, build_truncation_description, recurse_into_children, resolve_pointer, split_path,
+};
 
 use super::pass_result::PassResult;
 
@@ -23,24 +38,33 @@ use super::pass_result::PassResult;
 /// Recursively walks the schema tree, renaming `oneOf` to `anyOf`. Handles
 /// key collisions (both `oneOf` and `anyOf` present) by wrapping into `allOf`.
 ///
-/// Skipped when `config.target == Target::Gemini` (Gemini handles `oneOf`
-/// natively) or when `config.polymorphism == PolymorphismStrategy::Flatten`.
+/// The rename is skipped when `config.target == Target::Gemini` (Gemini
+/// handles `oneOf` natively) or when `config.polymorphism ==
+/// PolymorphismStrategy::Flatten`; discriminators are recorded and the
+/// variant cap applies regardless.
+#[cfg(test)]
 pub fn simplify_polymorphism(
     schema: Value,
     config: &ConvertOptions,
 ) -> Result<PassResult, ConvertError> {
-    // Provider gate: Gemini supports oneOf natively.
-    if config.target == Target::Gemini {
-        return Ok(PassResult::schema_only(schema));
-    }
+    simplify_polymorphism_with_guard(schema, config, &ResourceGuard::new(config))
+}
 
+/// [`simplify_polymorphism`], ticking `guard` at every node visited.
+pub fn simplify_polymorphism_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<PassResult, ConvertError> {
+    // Provider gate: Gemini supports oneOf natively.
     // Strategy gate: Flatten is future work.
-    if config.polymorphism == PolymorphismStrategy::Flatten {
-        return Ok(PassResult::schema_only(schema));
-    }
+    let rename =
+        config.target != Target::Gemini && config.polymorphism != PolymorphismStrategy::Flatten;
 
-    let result = walk(schema, "#", 0, config)?;
-    Ok(PassResult::schema_only(result))
+    let mut transforms = Vec::new();
+    collect_discriminators(&schema, &schema, "#", &mut transforms);
+    let result = walk(schema, "#", 0, config, guard, rename, &mut transforms)?;
+    Ok(PassResult::with_transforms(result, transforms))
 }
 
 // ---------------------------------------------------------------------------
@@ -54,19 +78,30 @@ fn walk(
     path: &str,
     depth: usize,
     config: &ConvertOptions,
+    guard: &ResourceGuard,
+    rename: bool,
+    transforms: &mut Vec<Transform>,
 ) -> Result<Value, ConvertError> {
     if depth > config.max_depth {
         return Ok(node);
     }
+    guard.tick(path)?;
 
     match node {
         Value::Object(mut obj) => {
             // --- Rename oneOf → anyOf (with collision handling) ---
-            rename_oneof_to_anyof(&mut obj);
+            if rename {
+                rename_oneof_to_anyof(&mut obj);
+            }
+
+            // --- Cap oversized unions before walking their variants ---
+            if let Some(cap) = config.max_anyof_variants {
+                cap_variants(&mut obj, path, cap, config.messages(), transforms);
+            }
 
             // --- Recurse into all child schemas via shared traversal ---
             recurse_into_children(&mut obj, path, depth, &mut |val, child_path, d| {
-                walk(val, child_path, d, config)
+                walk(val, child_path, d, config, guard, rename, transforms)
             })?;
 
             Ok(Value::Object(obj))
@@ -113,6 +148,206 @@ fn rename_oneof_to_anyof(obj: &mut Map<String, Value>) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Discriminators
+// ---------------------------------------------------------------------------
+
+/// Keywords whose values are instances, not schemas.
+const INSTANCE_KEYWORDS: &[&str] = &["const", "enum", "default", "example", "examples"];
+
+/// Keywords holding schemas reached only through `$ref`.
+const DEFINITION_KEYWORDS: &[&str] = &["$defs", "definitions"];
+
+/// Record a `DiscriminatorAnyOf` transform for every union under `node`
+/// with a `discriminator: {propertyName}`, at the path its data appears at:
+/// a union in `$defs` is recorded at each `$ref` to it, never under `$defs`
+/// (which rehydration cannot tell apart from the root). Read-only, against
+/// the schema as it was before this pass, so `$ref`s still resolve in `root`.
+fn collect_discriminators(root: &Value, node: &Value, path: &str, transforms: &mut Vec<Transform>) {
+    match node {
+        Value::Object(obj) => {
+            record_discriminator(root, obj, path, transforms);
+            if let Some(Value::Object(target)) = obj
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| resolve_pointer(root, r))
+            {
+                record_discriminator(root, target, path, transforms);
+            }
+            for (key, child) in obj {
+                if !INSTANCE_KEYWORDS.contains(&key.as_str())
+                    && !DEFINITION_KEYWORDS.contains(&key.as_str())
+                {
+                    collect_discriminators(root, child, &build_path(path, &[key]), transforms);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                collect_discriminators(
+                    root,
+                    child,
+                    &build_path(path, &[&i.to_string()]),
+                    transforms,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The discriminator values of the union in `obj`: the `mapping` keys, then
+/// each variant's `const`/`enum` for the property (following a `$ref`
+/// variant to its target) or, for an unmapped `$ref` variant without one,
+/// the referenced schema's name (OpenAPI's implicit mapping). Each value's
+/// variant `required` list is recorded alongside, for mismatch reports.
+fn record_discriminator(
+    root: &Value,
+    obj: &Map<String, Value>,
+    path: &str,
+    transforms: &mut Vec<Transform>,
+) {
+    let Some(discriminator) = obj.get("discriminator") else {
+        return;
+    };
+    let Some(property) = discriminator.get("propertyName").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(variants) = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|k| obj.get(*k).and_then(Value::as_array))
+    else {
+        return;
+    };
+
+    let mapping = discriminator.get("mapping").and_then(Value::as_object);
+    let mut values: Vec<String> = Vec::new();
+    let mut required = BTreeMap::new();
+    for (value, reference) in mapping.into_iter().flatten() {
+        values.push(value.clone());
+        let target = reference.as_str().and_then(|r| resolve_pointer(root, r));
+        required.insert(value.clone(), required_of(target));
+    }
+    for variant in variants {
+        let reference = variant.get("$ref").and_then(Value::as_str);
+        let target = match reference {
+            Some(reference) => resolve_pointer(root, reference).unwrap_or(&Value::Null),
+            None => variant,
+        };
+        let schema = target.get("properties").and_then(|p| p.get(property));
+        let mut declared: Vec<String> = Vec::new();
+        if let Some(value) = schema.and_then(|s| s.get("const")).and_then(Value::as_str) {
+            declared.push(value.to_string());
+        }
+        if let Some(options) = schema.and_then(|s| s.get("enum")).and_then(Value::as_array) {
+            declared.extend(options.iter().filter_map(Value::as_str).map(String::from));
+        }
+        if declared.is_empty() {
+            let mapped = mapping.is_some_and(|m| m.values().any(|v| v.as_str() == reference));
+            if let (Some(reference), false) = (reference, mapped) {
+                declared.extend(split_path(reference).pop());
+            }
+        }
+        for value in &declared {
+            required
+                .entry(value.clone())
+                .or_insert_with(|| required_of(Some(target)));
+        }
+        values.extend(declared);
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    values.retain(|v| seen.insert(v.clone()));
+    if values.is_empty() {
+        return;
+    }
+    required.retain(|_, fields: &mut Vec<String>| !fields.is_empty());
+    transforms.push(Transform::DiscriminatorAnyOf {
+        path: path.to_string(),
+        discriminator: property.to_string(),
+        variants: values,
+        required,
+    });
+}
+
+/// The `required` property names of a variant schema.
+fn required_of(schema: Option<&Value>) -> Vec<String> {
+    schema
+        .and_then(|s| s.get("required"))
+        .and_then(Value::as_array)
+        .map(|r| {
+            r.iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Variant cap
+// ---------------------------------------------------------------------------
+
+/// Collapse the `anyOf` (or, where not renamed, `oneOf`) variants of `obj`
+/// beyond `cap` into a single JSON-string branch. The first `cap - 1`
+/// variants are kept as-is, so codec paths into them stay valid. Caps below
+/// 2 are treated as 2: a union of one opaque branch would discard everything.
+fn cap_variants(
+    obj: &mut Map<String, Value>,
+    path: &str,
+    cap: usize,
+    messages: &MessageCatalog,
+    transforms: &mut Vec<Transform>,
+) {
+    let cap = cap.max(2);
+    for keyword in ["anyOf", "oneOf"] {
+        let Some(variants) = obj.get_mut(keyword).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        if variants.len() <= cap {
+            continue;
+        }
+
+        let overflow = variants.split_off(cap - 1);
+        let kept_string = variants.iter().any(accepts_string);
+        let description = format!(
+            "{}{}",
+            fill(&messages.variant_overflow, &[("count", &overflow.len())]),
+            build_truncation_description(&json!({ "anyOf": overflow }), messages)
+        );
+        crate::logging::debug!(
+            path,
+            keyword,
+            collapsed = overflow.len(),
+            "collapsed union variants beyond cap"
+        );
+        variants.push(json!({ "type": "string", "description": description }));
+        transforms.push(Transform::AnyOfOverflow {
+            path: path.to_string(),
+            kept_string,
+        });
+    }
+}
+
+/// Whether a kept variant might also produce a plain string, making a string
+/// at the union ambiguous during rehydration. Untyped variants count.
+fn accepts_string(variant: &Value) -> bool {
+    if let Some(t) = variant.get("type") {
+        return match t {
+            Value::String(t) => t == "string",
+            Value::Array(types) => types.iter().any(|t| t == "string"),
+            _ => false,
+        };
+    }
+    if let Some(c) = variant.get("const") {
+        return c.is_string();
+    }
+    if let Some(values) = variant.get("enum").and_then(Value::as_array) {
+        return values.iter().any(Value::is_string);
+    }
+    true
+}
+
 // ===========================================================================
 // Tests
 // ===========================================================================
@@ -168,6 +403,76 @@ mod tests {
         assert_eq!(result["anyOf"].as_array().unwrap().len(), 2);
     }
 
+    #[test]
+    fn test_discriminator_values_recorded() {
+        let input = json!({
+            "properties": {
+                "pet": {
+                    "oneOf": [
+                        { "$ref": "#/$defs/Dog" },
+                        { "$ref": "#/$defs/Cat" },
+                        { "$ref": "#/$defs/Lizard" },
+                        { "properties": { "kind": { "enum": ["bird", "parrot"] } } }
+                    ],
+                    "discriminator": {
+                        "propertyName": "kind",
+                        "mapping": { "tabby": "#/$defs/Cat" }
+                    }
+                }
+            },
+            "$defs": {
+                "Dog": { "properties": { "kind": { "const": "dog" } } },
+                "Cat": { "properties": { "kind": { "type": "string" } } },
+                "Lizard": { "properties": { "kind": { "type": "string" } } }
+            }
+        });
+        let result = simplify_polymorphism(input, &ConvertOptions::default()).unwrap();
+
+        match result.transforms.as_slice() {
+            [Transform::DiscriminatorAnyOf {
+                path,
+                discriminator,
+                variants,
+                ..
+            }] => {
+                assert_eq!(path, "#/properties/pet");
+                assert_eq!(discriminator, "kind");
+                // Mapping keys, the Dog const, Lizard's implicit name, the
+                // inline enum; Cat is covered by the mapping.
+                assert_eq!(variants, &["tabby", "dog", "Lizard", "bird", "parrot"]);
+            }
+            other => panic!("expected one DiscriminatorAnyOf, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_discriminator_in_defs_recorded_at_ref_site() {
+        let input = json!({
+            "properties": { "pet": { "$ref": "#/$defs/Pet" } },
+            "$defs": {
+                "Pet": {
+                    "oneOf": [{ "$ref": "#/$defs/Dog" }, { "$ref": "#/$defs/Cat" }],
+                    "discriminator": { "propertyName": "kind" }
+                },
+                "Dog": {
+                    "properties": { "kind": { "const": "dog" }, "bark": {} },
+                    "required": ["kind", "bark"]
+                },
+                "Cat": { "properties": { "kind": { "const": "cat" } } }
+            }
+        });
+        let result = simplify_polymorphism(input, &ConvertOptions::default()).unwrap();
+
+        match result.transforms.as_slice() {
+            [Transform::DiscriminatorAnyOf { path, required, .. }] => {
+                assert_eq!(path, "#/properties/pet");
+                assert_eq!(required.len(), 1);
+                assert_eq!(required["dog"], ["kind", "bark"]);
+            }
+            other => panic!("expected one DiscriminatorAnyOf, got: {:?}", other),
+        }
+    }
+
     // Test 3: Nested oneOf (inside properties of another schema)
     #[test]
     fn test_nested_oneof() {
@@ -322,4 +627,79 @@ mod tests {
         assert!(result["definitions"]["OldType"].get("anyOf").is_some());
         assert!(result["definitions"]["OldType"].get("oneOf").is_none());
     }
+
+    fn capped(cap: usize) -> ConvertOptions {
+        ConvertOptions {
+            max_anyof_variants: Some(cap),
+            ..ConvertOptions::default()
+        }
+    }
+
+    fn object_variant(kind: &str) -> Value {
+        json!({
+            "type": "object",
+            "title": kind,
+            "properties": { "kind": { "const": kind } }
+        })
+    }
+
+    // Test 12: Variant cap collapses the tail into one string branch
+    #[test]
+    fn test_variant_cap_collapses_overflow() {
+        let variants: Vec<Value> = (0..5).map(|i| object_variant(&format!("v{i}"))).collect();
+        let input = json!({
+            "type": "object",
+            "properties": { "event": { "oneOf": variants } }
+        });
+        let result = simplify_polymorphism(input, &capped(3)).unwrap();
+
+        let any_of = result.schema["properties"]["event"]["anyOf"]
+            .as_array()
+            .unwrap();
+        assert_eq!(any_of.len(), 3);
+        assert_eq!(any_of[1]["title"], "v1");
+        assert_eq!(any_of[2]["type"], "string");
+        let desc = any_of[2]["description"].as_str().unwrap();
+        assert!(desc.starts_with("One of 3 further variants"), "{desc}");
+        assert!(desc.contains("kind"), "{desc}");
+
+        assert_eq!(result.transforms.len(), 1);
+        assert!(matches!(
+            &result.transforms[0],
+            Transform::AnyOfOverflow { path, kept_string: false } if path == "#/properties/event"
+        ));
+    }
+
+    // Test 13: Unions at or under the cap are untouched
+    #[test]
+    fn test_variant_cap_noop_within_cap() {
+        let input = json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] });
+        let result = simplify_polymorphism(input.clone(), &capped(2)).unwrap();
+        assert_eq!(result.schema, input);
+        assert!(result.transforms.is_empty());
+    }
+
+    // Test 14: The cap also applies to Gemini's untouched oneOf
+    #[test]
+    fn test_variant_cap_applies_to_gemini_oneof() {
+        let input = json!({ "oneOf": [
+            { "type": "string" },
+            { "type": "integer" },
+            { "type": "boolean" }
+        ] });
+        let config = ConvertOptions {
+            target: Target::Gemini,
+            ..capped(2)
+        };
+        let result = simplify_polymorphism(input, &config).unwrap();
+        let one_of = result.schema["oneOf"].as_array().unwrap();
+        assert_eq!(one_of.len(), 2);
+        assert!(matches!(
+            &result.transforms[0],
+            Transform::AnyOfOverflow {
+                kept_string: true,
+                ..
+            }
+        ));
+    }
 }
@@ -4,16 +4,26 @@
 //! and converts them to `{type: array, items: {type: object, properties: {key, value}}}`.
 //!
 //! For mixed objects (both `properties` and `additionalProperties: Schema`), the dynamic
-//! entries are extracted into a synthetic `_additional` property, keeping the object
-//! Strict-compatible while preserving all data.
+//! entries are extracted into a synthetic property (`_additional` unless
+//! [`ConvertOptions::additional_properties_name`] says otherwise), keeping the
+//! object Strict-compatible while preserving all data.
+//!
+//! A pure map's `minProperties`/`maxProperties` become the array's
+//! `minItems`/`maxItems`, and are also recorded as dropped constraints so the
+//! restored object is checked against them on rehydration.
+//!
+//! When the value schema is a union (`anyOf`/`oneOf`), each entry also carries
+//! the index of the branch its value takes, so rehydration applies a branch's
+//! nested transforms only to the entries of that branch.
 //!
 //! Nested maps are handled via natural recursion — each level transpiles independently.
 
 use serde_json::{json, Map, Value};
 
-use crate::codec::Transform;
+use crate::codec::{DroppedConstraint, Transform};
 use crate::config::{ConvertOptions, Target};
 use crate::error::ConvertError;
+use crate::limits::ResourceGuard;
 use crate::schema_utils::{build_path, recurse_into_children};
 
 use super::pass_result::PassResult;
@@ -22,7 +32,10 @@ use super::pass_utils::is_typed_object;
 /// Field name for the map key in the transpiled array item.
 const KEY_FIELD: &str = "key";
 /// Field name for the map value in the transpiled array item.
-const VALUE_FIELD: &str = "value";
+pub(crate) const VALUE_FIELD: &str = "value";
+/// Field name for the value's union branch index, in items of maps whose
+/// value schema is a union.
+pub(crate) const VARIANT_FIELD: &str = "variant";
 /// Default property name for extracted `additionalProperties` in mixed objects.
 const ADDITIONAL_PROPERTY: &str = "_additional";
 
@@ -31,21 +44,40 @@ const ADDITIONAL_PROPERTY: &str = "_additional";
 /// Recursively walks schema objects reachable via `properties`, `items`,
 /// `anyOf`/`oneOf`/`allOf`, and `additionalProperties`. For map-pattern objects,
 /// converts them to typed arrays. For mixed objects, extracts
-/// `additionalProperties` into a synthetic `_additional` property.
+/// `additionalProperties` into a synthetic property named by
+/// [`ConvertOptions::additional_properties_name`] (default `_additional`).
+///
+/// Skipped entirely when `config.target == Target::Gemini`, or when
+/// [`AdditionalPropertiesPolicy::Preserve`] keeps maps as maps.
 ///
-/// Skipped entirely when `config.target == Target::Gemini`.
+/// [`AdditionalPropertiesPolicy::Preserve`]: crate::AdditionalPropertiesPolicy::Preserve
+#[cfg(test)]
 pub fn transpile_dictionaries(
     schema: Value,
     config: &ConvertOptions,
+) -> Result<PassResult, ConvertError> {
+    transpile_dictionaries_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`transpile_dictionaries`], ticking `guard` at every node visited.
+pub fn transpile_dictionaries_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
 ) -> Result<PassResult, ConvertError> {
     // Provider gate: Gemini supports additionalProperties natively.
-    if config.target == Target::Gemini {
+    if config.target == Target::Gemini || config.preserves_additional_properties() {
         return Ok(PassResult::schema_only(schema));
     }
 
     let mut transforms = Vec::new();
-    let result = walk(schema, "#", 0, config, &mut transforms)?;
-    Ok(PassResult::with_transforms(result, transforms))
+    let mut dropped = Vec::new();
+    let result = walk(schema, "#", 0, config, guard, &mut transforms, &mut dropped)?;
+    Ok(PassResult {
+        schema: result,
+        transforms,
+        dropped_constraints: dropped,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -59,7 +91,9 @@ fn walk(
     path: &str,
     depth: usize,
     config: &ConvertOptions,
+    guard: &ResourceGuard,
     transforms: &mut Vec<Transform>,
+    dropped: &mut Vec<DroppedConstraint>,
 ) -> Result<Value, ConvertError> {
     if depth > config.max_depth {
         return Err(ConvertError::RecursionDepthExceeded {
@@ -67,6 +101,7 @@ fn walk(
             max_depth: config.max_depth,
         });
     }
+    guard.tick(path)?;
 
     let mut result = match node {
         Value::Object(obj) => obj,
@@ -76,19 +111,31 @@ fn walk(
     // Check for map patterns BEFORE recursing into children.
     if is_pure_map(&result) {
         // Pure map: convert entire object to array.
-        let array_schema = transpile_pure_map(&result, path, transforms);
+        let array_schema = transpile_pure_map(&result, path, transforms, dropped);
         // Recurse into the newly created items schema.
-        return walk(array_schema, path, depth + 1, config, transforms);
+        return walk(
+            array_schema,
+            path,
+            depth + 1,
+            config,
+            guard,
+            transforms,
+            dropped,
+        );
     }
 
     if is_mixed_map(&result) {
         // Mixed: extract additionalProperties into _additional, then transpile it.
-        extract_additional_properties(&mut result, path, transforms);
+        let name = config
+            .additional_properties_name
+            .as_deref()
+            .unwrap_or(ADDITIONAL_PROPERTY);
+        extract_additional_properties(&mut result, path, name, transforms);
     }
 
     // Recurse into all structural children via shared traversal.
     recurse_into_children(&mut result, path, depth, &mut |val, child_path, d| {
-        walk(val, child_path, d, config, transforms)
+        walk(val, child_path, d, config, guard, transforms, dropped)
     })?;
 
     Ok(Value::Object(result))
@@ -129,10 +176,14 @@ fn has_non_empty_properties(obj: &Map<String, Value>) -> bool {
 ///
 /// Input:  `{type: object, additionalProperties: ValueSchema, description?, title?}`
 /// Output: `{type: array, items: {type: object, properties: {key: {type: string}, value: ValueSchema}, required: [key, value], additionalProperties: false}, description?, title?}`
+///
+/// `minProperties`/`maxProperties` carry over as `minItems`/`maxItems` and
+/// are recorded in `dropped`.
 fn transpile_pure_map(
     obj: &Map<String, Value>,
     path: &str,
     transforms: &mut Vec<Transform>,
+    dropped: &mut Vec<DroppedConstraint>,
 ) -> Value {
     let value_schema = obj
         .get("additionalProperties")
@@ -153,25 +204,41 @@ fn transpile_pure_map(
             .expect("invariant: build_array_schema always returns Value::Object")
             .insert("title".to_string(), title.clone());
     }
+    for (size, count) in [("minProperties", "minItems"), ("maxProperties", "maxItems")] {
+        if let Some(bound) = obj.get(size) {
+            array_schema
+                .as_object_mut()
+                .expect("invariant: build_array_schema always returns Value::Object")
+                .insert(count.to_string(), bound.clone());
+            dropped.push(DroppedConstraint {
+                path: path.to_string(),
+                constraint: size.to_string(),
+                value: bound.clone(),
+            });
+        }
+    }
 
     transforms.push(Transform::MapToArray {
         path: path.to_string(),
         key_field: KEY_FIELD.to_string(),
+        variant_field: variant_field(&value_schema),
     });
 
     array_schema
 }
 
 /// Handle a mixed object by extracting `additionalProperties` into a synthetic
-/// `_additional` property of type array.
+/// property of type array, named `name` unless the object already declares it.
 fn extract_additional_properties(
     obj: &mut Map<String, Value>,
     path: &str,
+    name: &str,
     transforms: &mut Vec<Transform>,
 ) {
     let value_schema = obj.remove("additionalProperties").unwrap_or(json!({}));
 
     let array_schema = build_array_schema(&value_schema, KEY_FIELD);
+    let variant_field = variant_field(&value_schema);
 
     // Choose a property name that doesn't collide with existing properties.
     let props = obj
@@ -180,15 +247,15 @@ fn extract_additional_properties(
         .as_object_mut()
         .expect("invariant: or_insert_with inserted json!({}) which is Value::Object");
 
-    let property_name = if props.contains_key(ADDITIONAL_PROPERTY) {
+    let property_name = if props.contains_key(name) {
         // Find a unique name by appending underscores.
-        let mut candidate = format!("{}_{}", ADDITIONAL_PROPERTY, "extra");
+        let mut candidate = format!("{}_{}", name, "extra");
         while props.contains_key(&candidate) {
             candidate.push('_');
         }
         candidate
     } else {
-        ADDITIONAL_PROPERTY.to_string()
+        name.to_string()
     };
 
     props.insert(property_name.clone(), array_schema);
@@ -205,27 +272,59 @@ fn extract_additional_properties(
     transforms.push(Transform::MapToArray {
         path: build_path(path, &["properties", &property_name]),
         key_field: KEY_FIELD.to_string(),
+        variant_field,
     });
 }
 
 /// Build the standard array schema for a map transpilation.
 ///
 /// Returns: `{type: array, items: {type: object, properties: {<key_field>: {type: string}, value: <value_schema>}, required: [<key_field>, value], additionalProperties: false}}`
+///
+/// A union value schema adds a required `variant` property, `{type: integer,
+/// enum: [0, .., n-1]}`, between the key and the value.
 fn build_array_schema(value_schema: &Value, key_field: &str) -> Value {
+    let mut properties = Map::new();
+    properties.insert(key_field.to_string(), json!({ "type": "string" }));
+    let mut required = vec![json!(key_field)];
+    if let Some(count) = union_len(value_schema) {
+        properties.insert(
+            VARIANT_FIELD.to_string(),
+            json!({
+                "type": "integer",
+                "enum": (0..count).collect::<Vec<_>>(),
+                "description": "Index of the `value` variant this entry uses.",
+            }),
+        );
+        required.push(json!(VARIANT_FIELD));
+    }
+    properties.insert(VALUE_FIELD.to_string(), value_schema.clone());
+    required.push(json!(VALUE_FIELD));
+
     json!({
         "type": "array",
         "items": {
             "type": "object",
-            "properties": {
-                key_field: { "type": "string" },
-                VALUE_FIELD: value_schema,
-            },
-            "required": [key_field, VALUE_FIELD],
+            "properties": properties,
+            "required": required,
             "additionalProperties": false,
         }
     })
 }
 
+/// Number of branches when `value_schema` is a union of two or more.
+fn union_len(value_schema: &Value) -> Option<usize> {
+    ["anyOf", "oneOf"]
+        .iter()
+        .find_map(|keyword| value_schema.get(*keyword).and_then(Value::as_array))
+        .map(Vec::len)
+        .filter(|&len| len >= 2)
+}
+
+/// The codec's `variant_field` for a map with this value schema.
+fn variant_field(value_schema: &Value) -> Option<String> {
+    union_len(value_schema).map(|_| VARIANT_FIELD.to_string())
+}
+
 // ===========================================================================
 // Tests
 // ===========================================================================
@@ -269,7 +368,12 @@ mod tests {
         assert_eq!(output["items"]["additionalProperties"], json!(false));
         assert_eq!(transforms.len(), 1);
         match &transforms[0] {
-            Transform::MapToArray { path, key_field } => {
+            Transform::MapToArray {
+                path,
+                key_field,
+                variant_field,
+            } => {
+                assert_eq!(variant_field, &None);
                 assert_eq!(path, "#");
                 assert_eq!(key_field, "key");
             }
@@ -434,7 +538,12 @@ mod tests {
             other => panic!("expected ExtractAdditionalProperties, got: {:?}", other),
         }
         match &transforms[1] {
-            Transform::MapToArray { path, key_field } => {
+            Transform::MapToArray {
+                path,
+                key_field,
+                variant_field,
+            } => {
+                assert_eq!(variant_field, &None);
                 assert_eq!(path, "#/properties/_additional");
                 assert_eq!(key_field, "key");
             }
@@ -558,4 +667,106 @@ mod tests {
             other => panic!("expected MapToArray, got: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_mixed_object_custom_name() {
+        let input = json!({
+            "type": "object",
+            "properties": {
+                "extras": { "type": "string" }
+            },
+            "additionalProperties": { "type": "integer" }
+        });
+        let config = ConvertOptions {
+            additional_properties_name: Some("extras".to_string()),
+            ..ConvertOptions::default()
+        };
+        let result = transpile_dictionaries(input, &config).unwrap();
+
+        assert_eq!(
+            result.schema["properties"]["extras"],
+            json!({"type": "string"})
+        );
+        assert_eq!(result.schema["properties"]["extras_extra"]["type"], "array");
+        assert!(result.schema["properties"].get("_additional").is_none());
+        match &result.transforms[0] {
+            Transform::ExtractAdditionalProperties { property_name, .. } => {
+                assert_eq!(property_name, "extras_extra");
+            }
+            other => panic!("expected ExtractAdditionalProperties, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_map_with_union_values_tags_variant() {
+        let input = json!({
+            "type": "object",
+            "additionalProperties": {
+                "anyOf": [
+                    { "type": "string" },
+                    { "type": "object", "additionalProperties": { "type": "integer" } }
+                ]
+            }
+        });
+
+        let (output, transforms) = run(input);
+
+        let entry = &output["items"];
+        assert_eq!(entry["properties"]["variant"]["enum"], json!([0, 1]));
+        assert_eq!(entry["required"], json!(["key", "variant", "value"]));
+        // The nested map inside branch 1 is transpiled at its own path.
+        assert_eq!(entry["properties"]["value"]["anyOf"][1]["type"], "array");
+
+        assert_eq!(transforms.len(), 2);
+        match &transforms[0] {
+            Transform::MapToArray {
+                path,
+                variant_field,
+                ..
+            } => {
+                assert_eq!(path, "#");
+                assert_eq!(variant_field.as_deref(), Some("variant"));
+            }
+            other => panic!("expected MapToArray, got: {:?}", other),
+        }
+        match &transforms[1] {
+            Transform::MapToArray {
+                path,
+                variant_field,
+                ..
+            } => {
+                assert_eq!(path, "#/items/properties/value/anyOf/1");
+                assert_eq!(variant_field, &None);
+            }
+            other => panic!("expected MapToArray, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_map_size_becomes_array_size() {
+        let input = json!({
+            "type": "object",
+            "additionalProperties": { "type": "string" },
+            "minProperties": 1,
+            "maxProperties": 3
+        });
+
+        let result = transpile_dictionaries(input, &ConvertOptions::default()).unwrap();
+
+        assert_eq!(result.schema["minItems"], json!(1));
+        assert_eq!(result.schema["maxItems"], json!(3));
+        assert!(result.schema.get("minProperties").is_none());
+        let dropped: Vec<_> = result
+            .dropped_constraints
+            .iter()
+            .map(|dc| (dc.path.as_str(), dc.constraint.as_str(), dc.value.clone()))
+            .collect();
+        assert_eq!(
+            dropped,
+            vec![
+                ("#", "minProperties", json!(1)),
+                ("#", "maxProperties", json!(3)),
+            ]
+        );
+    }
 }
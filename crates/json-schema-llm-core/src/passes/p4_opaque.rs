@@ -23,29 +23,33 @@ use serde_json::{Map, Value};
 use crate::codec::Transform;
 use crate::config::ConvertOptions;
 use crate::error::ConvertError;
+use crate::limits::ResourceGuard;
+use crate::messages::MessageCatalog;
 
 use super::pass_result::PassResult;
 use crate::schema_utils::recurse_into_children;
 
-/// Default description for opaque objects that have no existing description.
-const DEFAULT_OPAQUE_DESC: &str =
-    "MUST be a valid JSON object serialized as a string, e.g. \"{\\\"key\\\": \\\"value\\\"}\". Do NOT output plain text — the value must parse with JSON.parse().";
-
-/// Suffix appended to existing descriptions for opaque objects.
-const OPAQUE_DESC_SUFFIX: &str =
-    "\n\n(Note: This field MUST be a valid JSON object serialized as a string. Do NOT output plain text — the value must parse with JSON.parse().)";
-
 /// Apply opaque type stringification to a schema.
 ///
 /// Recursively walks every node. For opaque objects (type: object with no
 /// structural constraints), converts them to `{type: string}` with a
 /// descriptive instruction and emits a `JsonStringParse` codec transform.
+#[cfg(test)]
 pub fn stringify_opaque(
     schema: Value,
     config: &ConvertOptions,
+) -> Result<PassResult, ConvertError> {
+    stringify_opaque_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`stringify_opaque`], ticking `guard` at every node visited.
+pub fn stringify_opaque_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
 ) -> Result<PassResult, ConvertError> {
     let mut transforms = Vec::new();
-    let result = walk(schema, "#", 0, config, &mut transforms)?;
+    let result = walk(schema, "#", 0, config, guard, &mut transforms)?;
     Ok(PassResult::with_transforms(result, transforms))
 }
 
@@ -60,6 +64,7 @@ fn walk(
     path: &str,
     depth: usize,
     config: &ConvertOptions,
+    guard: &ResourceGuard,
     transforms: &mut Vec<Transform>,
 ) -> Result<Value, ConvertError> {
     if depth > config.max_depth {
@@ -68,6 +73,7 @@ fn walk(
             max_depth: config.max_depth,
         });
     }
+    guard.tick(path)?;
 
     let mut result = match node {
         Value::Object(obj) => obj,
@@ -76,13 +82,13 @@ fn walk(
 
     // Check for opaque patterns BEFORE recursing into children.
     if is_opaque(&result) || is_untyped_opaque(&result) {
-        let stringified = stringify_object(&result, path, transforms);
+        let stringified = stringify_object(&result, path, config.messages(), transforms);
         return Ok(stringified);
     }
 
     // Recurse into all structural children via shared traversal.
     recurse_into_children(&mut result, path, depth, &mut |val, child_path, d| {
-        walk(val, child_path, d, config, transforms)
+        walk(val, child_path, d, config, guard, transforms)
     })?;
 
     Ok(Value::Object(result))
@@ -291,6 +297,7 @@ fn is_untyped_opaque(obj: &Map<String, Value>) -> bool {
 fn stringify_object(
     obj: &Map<String, Value>,
     path: &str,
+    messages: &MessageCatalog,
     transforms: &mut Vec<Transform>,
 ) -> Value {
     // 2. Clone the object and modify into a string schema.
@@ -339,12 +346,12 @@ fn stringify_object(
 
     // If a description exists, append the opaque suffix; otherwise, set the default description.
     if let Some(desc) = result.get("description").and_then(Value::as_str) {
-        let new_desc = format!("{}{}", desc, OPAQUE_DESC_SUFFIX);
+        let new_desc = format!("{}\n\n{}", desc, messages.opaque_object_note);
         result.insert("description".to_string(), Value::String(new_desc));
     } else {
         result.insert(
             "description".to_string(),
-            Value::String(DEFAULT_OPAQUE_DESC.to_string()),
+            Value::String(messages.opaque_object.to_string()),
         );
     }
 
@@ -408,7 +415,10 @@ mod tests {
         let (output, transforms) = run(input);
 
         assert_eq!(output["type"], "string");
-        assert_eq!(output["description"], DEFAULT_OPAQUE_DESC);
+        assert_eq!(
+            output["description"],
+            ConvertOptions::default().messages().opaque_object.as_ref()
+        );
         assert_eq!(transforms.len(), 1);
         match &transforms[0] {
             Transform::JsonStringParse { path } => assert_eq!(path, "#"),
@@ -807,7 +817,10 @@ mod tests {
         let (output, transforms) = run(input);
 
         assert_eq!(output["type"], "string");
-        assert_eq!(output["description"], DEFAULT_OPAQUE_DESC);
+        assert_eq!(
+            output["description"],
+            ConvertOptions::default().messages().opaque_object.as_ref()
+        );
         assert_eq!(transforms.len(), 1);
         match &transforms[0] {
             Transform::JsonStringParse { path } => assert_eq!(path, "#"),
@@ -943,4 +956,21 @@ mod tests {
         assert_eq!(output, input);
         assert_eq!(transforms.len(), 0);
     }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_time_budget_trips_mid_pass() {
+        let properties: serde_json::Map<String, Value> = (0..300)
+            .map(|i| (format!("p{i}"), json!({ "type": "string" })))
+            .collect();
+        let schema = json!({ "type": "object", "properties": properties });
+        let config = ConvertOptions {
+            time_budget_ms: Some(0),
+            ..ConvertOptions::default()
+        };
+        let err =
+            stringify_opaque_with_guard(schema, &config, &ResourceGuard::new(&config)).unwrap_err();
+        assert!(err.to_string().contains("time_budget_ms"), "got: {err}");
+        assert_ne!(err.path(), Some("#"));
+    }
 }
@@ -1,9 +1,12 @@
 //! Pass 5: Recursion Breaking
 //!
 //! Walks the schema tree, inlines all remaining `$ref` nodes, and breaks
-//! recursive cycles at `config.recursion_limit` by replacing them with
-//! opaque JSON-string placeholders. Emits `RecursiveInflate` codec entries
-//! for round-trip rehydration.
+//! recursive cycles at `config.recursion_limit` by replacing them with a
+//! placeholder chosen by `config.recursion_leaf`: an opaque JSON string
+//! (`RecursiveInflate`), `null` (`RecursiveTerminate`), or a `$node_id`
+//! reference to another node (`RecursiveNodeRef`), with the matching codec
+//! entry for round-trip rehydration. Each break is also reported as a
+//! [`CycleInfo`] naming the refs around the cycle.
 //!
 //! ## Pipeline Position
 //!
@@ -21,16 +24,47 @@ use std::collections::HashMap;
 use serde_json::Value;
 
 use crate::codec::Transform;
-use crate::config::{ConvertOptions, Target};
+use crate::config::{ConvertOptions, RecursionLeaf, Target};
+use crate::diagnostics::CycleInfo;
 use crate::error::ConvertError;
+use crate::limits::ResourceGuard;
+use crate::messages::fill;
 
 use super::pass_result::PassResult;
 use super::pass_utils::REF_META_KEYWORDS;
 
-pub fn break_recursion(schema: Value, config: &ConvertOptions) -> Result<PassResult, ConvertError> {
+/// Property naming a node for [`RecursionLeaf::NodeId`] references.
+pub(crate) const NODE_ID_FIELD: &str = "$node_id";
+
+/// Result of running the recursion-breaking pass.
+#[derive(Debug)]
+pub struct RecursionPassResult {
+    /// Shared pass result containing the acyclic schema.
+    pub pass: PassResult,
+    /// The cycles broken, one per placeholder.
+    pub cycles: Vec<CycleInfo>,
+}
+
+#[cfg(test)]
+pub fn break_recursion(
+    schema: Value,
+    config: &ConvertOptions,
+) -> Result<RecursionPassResult, ConvertError> {
+    break_recursion_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`break_recursion`], ticking `guard` at every node visited.
+pub fn break_recursion_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<RecursionPassResult, ConvertError> {
     // Gemini gate: native recursion support
     if config.target == Target::Gemini {
-        return Ok(PassResult::schema_only(schema));
+        return Ok(RecursionPassResult {
+            pass: PassResult::schema_only(schema),
+            cycles: Vec::new(),
+        });
     }
 
     // Extract $defs for ref resolution
@@ -41,24 +75,31 @@ pub fn break_recursion(schema: Value, config: &ConvertOptions) -> Result<PassRes
 
     let mut transforms = Vec::new();
     let mut ref_counts: HashMap<String, usize> = HashMap::new();
+    let mut cycles = Vec::new();
 
     let mut folder = RecursionFolder {
+        guard,
         defs: &defs,
         config,
         ref_counts: &mut ref_counts,
+        inlining: Vec::new(),
         transforms: &mut transforms,
+        cycles: &mut cycles,
     };
     let result = crate::schema_walker::fold(schema, &mut folder, "#", 0)?;
 
     // Safety check: only strip $defs if no dangling $ref nodes remain
     let result = if has_remaining_refs(&result) {
-        tracing::warn!("Schema still contains $ref nodes after Pass 5 — keeping $defs");
+        crate::logging::warn!("Schema still contains $ref nodes after Pass 5 — keeping $defs");
         result
     } else {
         strip_defs(result)
     };
 
-    Ok(PassResult::with_transforms(result, transforms))
+    Ok(RecursionPassResult {
+        pass: PassResult::with_transforms(result, transforms),
+        cycles,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -68,8 +109,12 @@ pub fn break_recursion(schema: Value, config: &ConvertOptions) -> Result<PassRes
 struct RecursionFolder<'a> {
     defs: &'a Value,
     config: &'a ConvertOptions,
+    guard: &'a ResourceGuard,
     ref_counts: &'a mut HashMap<String, usize>,
+    /// The `$ref`s being inlined around the current node, outermost first.
+    inlining: Vec<String>,
     transforms: &'a mut Vec<Transform>,
+    cycles: &'a mut Vec<CycleInfo>,
 }
 
 impl crate::schema_walker::SchemaFolder for RecursionFolder<'_> {
@@ -81,6 +126,7 @@ impl crate::schema_walker::SchemaFolder for RecursionFolder<'_> {
         path: &str,
         depth: usize,
     ) -> Result<crate::schema_walker::FoldAction, Self::Error> {
+        self.guard.tick(path)?;
         let Value::Object(mut obj) = schema else {
             return Ok(crate::schema_walker::FoldAction::Continue(schema));
         };
@@ -91,34 +137,16 @@ impl crate::schema_walker::SchemaFolder for RecursionFolder<'_> {
             let count = self.ref_counts.get(&ref_str).copied().unwrap_or(0);
 
             if count >= self.config.recursion_limit {
-                // Break: replace with opaque string placeholder.
-                self.transforms.push(Transform::RecursiveInflate {
-                    path: path.to_string(),
-                    original_ref: ref_str.clone(),
-                });
-
-                let example = lookup_def(&ref_str, self.defs)
-                    .as_ref()
-                    .map(|def| build_example_from_def(def, &type_name))
-                    .unwrap_or_else(|| "{\\\"key\\\": \\\"value\\\"}".to_string());
-
                 return Ok(crate::schema_walker::FoldAction::Replace(
-                    serde_json::json!({
-                        "type": "string",
-                        "description": format!(
-                            "MUST be a valid JSON object serialized as a string. \
-                             This represents a {type_name} that was too deeply nested to inline. \
-                             Output a complete JSON object as a string value, e.g. \
-                             \"{example}\". \
-                             Do NOT output plain text — the value must parse as JSON.",
-                        )
-                    }),
+                    self.leaf(&ref_str, &type_name, path),
                 ));
             }
 
             // Inline: look up the definition and fold it.
             if let Some(def) = lookup_def(&ref_str, self.defs) {
                 *self.ref_counts.entry(ref_str.clone()).or_insert(0) += 1;
+                self.inlining.push(ref_str.clone());
+                let first_new = self.transforms.len();
                 let mut result = crate::schema_walker::fold(def, self, path, depth)?;
 
                 // Strip resolution-mechanism keywords from the inlined def.
@@ -144,7 +172,7 @@ impl crate::schema_walker::SchemaFolder for RecursionFolder<'_> {
                     })
                     .collect();
 
-                let final_result = if siblings.is_empty() {
+                let mut final_result = if siblings.is_empty() {
                     result
                 } else {
                     let folded_siblings =
@@ -155,6 +183,14 @@ impl crate::schema_walker::SchemaFolder for RecursionFolder<'_> {
                 if let Some(c) = self.ref_counts.get_mut(&ref_str) {
                     *c -= 1;
                 }
+                self.inlining.pop();
+
+                // References cut off beneath this node may name it.
+                if self.transforms[first_new..].iter().any(|t| {
+                    matches!(t, Transform::RecursiveNodeRef { original_ref, .. } if *original_ref == ref_str)
+                }) {
+                    self.add_node_id(&mut final_result, &type_name);
+                }
 
                 return Ok(crate::schema_walker::FoldAction::Replace(final_result));
             }
@@ -169,11 +205,9 @@ impl crate::schema_walker::SchemaFolder for RecursionFolder<'_> {
             return Ok(crate::schema_walker::FoldAction::Replace(
                 serde_json::json!({
                     "type": "string",
-                    "description": format!(
-                        "MUST be a valid JSON value serialized as a string. \
-                         This represents a {} reference that could not be resolved. \
-                         Do NOT output plain text — the value must parse with JSON.parse().",
-                        type_name
+                    "description": fill(
+                        &self.config.messages().unresolved_ref,
+                        &[("type_name", &type_name)],
                     )
                 }),
             ));
@@ -190,6 +224,89 @@ impl crate::schema_walker::SchemaFolder for RecursionFolder<'_> {
     }
 }
 
+impl RecursionFolder<'_> {
+    /// The placeholder for `ref_str` at `path`, past the recursion limit,
+    /// recording its codec entry.
+    fn leaf(&mut self, ref_str: &str, type_name: &str, path: &str) -> Value {
+        let messages = self.config.messages();
+        let (transform, leaf) = match self.config.recursion_leaf {
+            RecursionLeaf::OpaqueString => {
+                let example = lookup_def(ref_str, self.defs)
+                    .as_ref()
+                    .map(|def| build_example_from_def(def, type_name))
+                    .unwrap_or_else(|| "{\\\"key\\\": \\\"value\\\"}".to_string());
+                (
+                    Transform::RecursiveInflate {
+                        path: path.to_string(),
+                        original_ref: ref_str.to_string(),
+                    },
+                    serde_json::json!({
+                        "type": "string",
+                        "description": fill(
+                            &messages.recursive_ref,
+                            &[("type_name", &type_name), ("example", &example)],
+                        )
+                    }),
+                )
+            }
+            RecursionLeaf::Null => (
+                Transform::RecursiveTerminate {
+                    path: path.to_string(),
+                    original_ref: ref_str.to_string(),
+                },
+                serde_json::json!({
+                    "type": "null",
+                    "description": fill(&messages.recursive_null, &[("type_name", &type_name)]),
+                }),
+            ),
+            RecursionLeaf::NodeId => (
+                Transform::RecursiveNodeRef {
+                    path: path.to_string(),
+                    original_ref: ref_str.to_string(),
+                },
+                serde_json::json!({
+                    "type": "object",
+                    "description": fill(&messages.recursive_node_ref, &[("type_name", &type_name)]),
+                    "properties": { NODE_ID_FIELD: { "type": "string" } },
+                    "required": [NODE_ID_FIELD],
+                    "additionalProperties": false,
+                }),
+            ),
+        };
+        self.transforms.push(transform);
+
+        let refs = match self.inlining.iter().rposition(|r| r == ref_str) {
+            Some(start) => self.inlining[start..].to_vec(),
+            None => vec![ref_str.to_string()],
+        };
+        self.cycles.push(CycleInfo {
+            refs,
+            break_path: path.to_string(),
+            strategy: self.config.recursion_leaf,
+        });
+        leaf
+    }
+
+    /// Give an inlined object schema an optional `$node_id` property.
+    fn add_node_id(&self, schema: &mut Value, type_name: &str) {
+        let Some(props) = schema.get_mut("properties").and_then(Value::as_object_mut) else {
+            return;
+        };
+        if !props.contains_key(NODE_ID_FIELD) {
+            props.insert(
+                NODE_ID_FIELD.to_string(),
+                serde_json::json!({
+                    "type": "string",
+                    "description": fill(
+                        &self.config.messages().node_id,
+                        &[("type_name", &type_name)],
+                    ),
+                }),
+            );
+        }
+    }
+}
+
 /// Look up a `$ref` target in the `$defs` map.
 fn lookup_def(ref_str: &str, defs: &Value) -> Option<Value> {
     // Expected format: "#/$defs/TypeName"
@@ -425,7 +542,7 @@ mod tests {
         });
 
         let config = config_with_limit(1);
-        let result = break_recursion(schema, &config).unwrap();
+        let result = break_recursion(schema, &config).unwrap().pass;
 
         // After recursion_limit=1: first expansion of B inlines B's content.
         // Inside that inlined B, the ref to A hits limit (A→B→A), so A becomes opaque string.
@@ -483,7 +600,7 @@ mod tests {
         });
 
         let config = config_with_limit(1);
-        let result = break_recursion(schema, &config).unwrap();
+        let result = break_recursion(schema, &config).unwrap().pass;
 
         assert!(
             !serde_json::to_string(&result.schema)
@@ -523,7 +640,7 @@ mod tests {
         });
 
         let config = config_with_limit(2);
-        let result = break_recursion(schema, &config).unwrap();
+        let result = break_recursion(schema, &config).unwrap().pass;
 
         assert!(
             !serde_json::to_string(&result.schema)
@@ -560,7 +677,9 @@ mod tests {
         });
 
         // With limit=1: Node expands once, then next occurrence is opaque.
-        let result_1 = break_recursion(schema.clone(), &config_with_limit(1)).unwrap();
+        let result_1 = break_recursion(schema.clone(), &config_with_limit(1))
+            .unwrap()
+            .pass;
         let count_1 = result_1
             .transforms
             .iter()
@@ -568,7 +687,9 @@ mod tests {
             .count();
 
         // With limit=3: Node expands three times before breaking.
-        let result_3 = break_recursion(schema.clone(), &config_with_limit(3)).unwrap();
+        let result_3 = break_recursion(schema.clone(), &config_with_limit(3))
+            .unwrap()
+            .pass;
         let count_3 = result_3
             .transforms
             .iter()
@@ -591,6 +712,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_recursion_leaf_styles() {
+        let schema = json!({
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": {
+                        "value": { "type": "integer" },
+                        "next": { "$ref": "#/$defs/Node" }
+                    }
+                }
+            }
+        });
+
+        let null_leaf = ConvertOptions {
+            recursion_leaf: RecursionLeaf::Null,
+            ..config_with_limit(1)
+        };
+        let result = break_recursion(schema.clone(), &null_leaf).unwrap().pass;
+        assert_eq!(result.schema["properties"]["next"]["type"], "null");
+        assert!(result.schema["properties"].get(NODE_ID_FIELD).is_none());
+        assert!(matches!(
+            result.transforms.as_slice(),
+            [Transform::RecursiveTerminate { path, .. }] if path == "#/properties/next"
+        ));
+
+        let node_id_leaf = ConvertOptions {
+            recursion_leaf: RecursionLeaf::NodeId,
+            ..config_with_limit(2)
+        };
+        let result = break_recursion(schema, &node_id_leaf).unwrap().pass;
+        let next = &result.schema["properties"]["next"];
+        let leaf = &next["properties"]["next"];
+        assert_eq!(leaf["required"], json!([NODE_ID_FIELD]));
+        assert_eq!(leaf["properties"][NODE_ID_FIELD]["type"], "string");
+        // Both inlined levels can be referenced; neither requires an id.
+        assert_eq!(result.schema["properties"][NODE_ID_FIELD]["type"], "string");
+        assert_eq!(next["properties"][NODE_ID_FIELD]["type"], "string");
+        assert!(matches!(
+            result.transforms.as_slice(),
+            [Transform::RecursiveNodeRef { path, .. }] if path == "#/properties/next/properties/next"
+        ));
+    }
+
+    #[test]
+    fn test_cycles_reported_per_break() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "b": { "$ref": "#/$defs/B" } },
+            "$defs": {
+                "A": { "type": "object", "properties": { "b": { "$ref": "#/$defs/B" } } },
+                "B": {
+                    "type": "object",
+                    "properties": {
+                        "a": { "$ref": "#/$defs/A" },
+                        "self": { "$ref": "#/$defs/B" }
+                    }
+                }
+            }
+        });
+
+        let result = break_recursion(schema, &config_with_limit(1)).unwrap();
+        assert_eq!(
+            result.cycles,
+            vec![
+                CycleInfo {
+                    refs: vec!["#/$defs/B".to_string(), "#/$defs/A".to_string()],
+                    break_path: "#/properties/b/properties/a/properties/b".to_string(),
+                    strategy: RecursionLeaf::OpaqueString,
+                },
+                CycleInfo {
+                    refs: vec!["#/$defs/B".to_string()],
+                    break_path: "#/properties/b/properties/self".to_string(),
+                    strategy: RecursionLeaf::OpaqueString,
+                },
+            ]
+        );
+        assert_eq!(result.cycles.len(), result.pass.transforms.len());
+    }
+
     // -----------------------------------------------------------------------
     // Test 5: Gemini target skips recursion breaking
     // -----------------------------------------------------------------------
@@ -608,7 +810,9 @@ mod tests {
             }
         });
 
-        let result = break_recursion(schema.clone(), &gemini_config()).unwrap();
+        let result = break_recursion(schema.clone(), &gemini_config())
+            .unwrap()
+            .pass;
 
         // Schema should be returned unchanged
         assert_eq!(
@@ -635,7 +839,9 @@ mod tests {
             "required": ["name"]
         });
 
-        let result = break_recursion(schema.clone(), &default_config()).unwrap();
+        let result = break_recursion(schema.clone(), &default_config())
+            .unwrap()
+            .pass;
 
         assert_eq!(
             result.schema, schema,
@@ -665,7 +871,7 @@ mod tests {
         });
 
         let config = config_with_limit(1);
-        let result = break_recursion(schema, &config).unwrap();
+        let result = break_recursion(schema, &config).unwrap().pass;
 
         let inflate = result
             .transforms
@@ -712,7 +918,7 @@ mod tests {
             }
         });
 
-        let result = break_recursion(schema, &default_config()).unwrap();
+        let result = break_recursion(schema, &default_config()).unwrap().pass;
 
         // Both refs are non-recursive (DAG), should be fully inlined
         assert!(
@@ -754,7 +960,7 @@ mod tests {
             }
         });
 
-        let result = break_recursion(schema, &default_config()).unwrap();
+        let result = break_recursion(schema, &default_config()).unwrap().pass;
 
         // Both properties should have the Address schema inlined
         let billing = &result.schema["properties"]["billing"];
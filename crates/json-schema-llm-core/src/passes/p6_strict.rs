@@ -1,17 +1,23 @@
 //! Pass 6: Strict Mode Enforcement
 //!
 //! For every `type: object` node:
-//! 1. Set `additionalProperties: false`
+//! 1. Set `additionalProperties: false` (unless
+//!    [`AdditionalPropertiesPolicy::Preserve`] keeps the declared value)
 //! 2. Move all properties into `required`
-//! 3. Wrap originally-optional properties in `anyOf: [OriginalType, {type: null}]`
+//! 3. Make originally-optional properties nullable, per
+//!    [`ConvertOptions::nullable_style`] (by default
+//!    `anyOf: [OriginalType, {type: null}]`)
 //!
 //! Emits `NullableOptional` codec entries for each optional→nullable transformation.
+//!
+//! [`AdditionalPropertiesPolicy::Preserve`]: crate::AdditionalPropertiesPolicy::Preserve
 
 use serde_json::{json, Value};
 
 use crate::codec::Transform;
 use crate::config::ConvertOptions;
 use crate::error::ConvertError;
+use crate::limits::ResourceGuard;
 use crate::schema_utils::recurse_into_children;
 
 use super::pass_result::PassResult;
@@ -22,9 +28,19 @@ use super::pass_utils::{enforce_object_strict, is_typed_object};
 /// Recursively walks every node. For `type: object` nodes with `properties`,
 /// seals them with `additionalProperties: false`, makes all properties required,
 /// and wraps optional properties with `anyOf: [T, {type: null}]`.
+#[cfg(test)]
 pub fn enforce_strict(schema: Value, config: &ConvertOptions) -> Result<PassResult, ConvertError> {
+    enforce_strict_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`enforce_strict`], ticking `guard` at every node visited.
+pub fn enforce_strict_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<PassResult, ConvertError> {
     let mut transforms = Vec::new();
-    let result = walk(schema, "#", 0, config, &mut transforms)?;
+    let result = walk(schema, "#", 0, config, guard, &mut transforms)?;
     Ok(PassResult::with_transforms(result, transforms))
 }
 
@@ -39,6 +55,7 @@ fn walk(
     path: &str,
     depth: usize,
     config: &ConvertOptions,
+    guard: &ResourceGuard,
     transforms: &mut Vec<Transform>,
 ) -> Result<Value, ConvertError> {
     if depth > config.max_depth {
@@ -47,6 +64,7 @@ fn walk(
             max_depth: config.max_depth,
         });
     }
+    guard.tick(path)?;
 
     let mut result = match node {
         Value::Object(obj) => obj,
@@ -66,12 +84,20 @@ fn walk(
         if !result.contains_key("properties") {
             result.insert("properties".to_string(), json!({}));
         }
-        enforce_object_strict(&mut result, path, transforms);
+        let style = config.effective_nullable_style();
+        if config.preserves_additional_properties() {
+            let declared = take_open_keywords(&mut result);
+            enforce_object_strict(&mut result, path, style, transforms);
+            result.remove("additionalProperties");
+            result.extend(declared);
+        } else {
+            enforce_object_strict(&mut result, path, style, transforms);
+        }
     }
 
     // Recurse into all structural children that may contain nested schemas.
     recurse_into_children(&mut result, path, depth, &mut |val, child_path, d| {
-        walk(val, child_path, d, config, transforms)
+        walk(val, child_path, d, config, guard, transforms)
     })?;
 
     Ok(Value::Object(result))
@@ -81,6 +107,18 @@ fn walk(
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Remove the keywords that govern undeclared properties, to restore after
+/// sealing.
+fn take_open_keywords(obj: &mut serde_json::Map<String, Value>) -> Vec<(String, Value)> {
+    let mut declared = Vec::new();
+    for key in ["additionalProperties", "unevaluatedProperties"] {
+        if let Some(value) = obj.remove(key) {
+            declared.push((key.to_string(), value));
+        }
+    }
+    declared
+}
+
 /// Detect schemas that are implicitly object-like: they have `properties`
 /// but no explicit `type` field. OpenAI strict mode demands
 /// `additionalProperties: false` on these too.
@@ -542,4 +580,152 @@ mod tests {
         let any_of = tag["anyOf"].as_array().unwrap();
         assert_eq!(any_of.len(), 2, "should not add another null variant");
     }
+
+    #[test]
+    fn test_preserve_keeps_declared_additional_properties() {
+        let input = json!({
+            "type": "object",
+            "properties": {
+                "meta": {
+                    "type": "object",
+                    "properties": {"a": {"type": "string"}},
+                    "additionalProperties": true
+                },
+                "tags": {
+                    "type": "object",
+                    "additionalProperties": {
+                        "type": "object",
+                        "properties": {"n": {"type": "integer"}}
+                    }
+                },
+                "closed": {"type": "object", "properties": {}, "additionalProperties": false}
+            },
+            "required": ["meta", "tags", "closed"]
+        });
+        let config = ConvertOptions {
+            target: crate::config::Target::Claude,
+            additional_properties: crate::config::AdditionalPropertiesPolicy::Preserve,
+            ..ConvertOptions::default()
+        };
+        let output = enforce_strict(input, &config).unwrap().schema;
+
+        assert!(output.get("additionalProperties").is_none());
+        assert_eq!(
+            output["properties"]["meta"]["additionalProperties"],
+            json!(true)
+        );
+        assert_eq!(
+            output["properties"]["meta"]["required"],
+            json!(["a"]),
+            "declared properties are still made required"
+        );
+        let value = &output["properties"]["tags"]["additionalProperties"];
+        assert_eq!(value["required"], json!(["n"]), "map values are walked");
+        assert_eq!(
+            output["properties"]["closed"]["additionalProperties"],
+            json!(false)
+        );
+    }
+
+    #[test]
+    fn test_preserve_ignored_for_openai() {
+        let input = json!({"type": "object", "properties": {}, "additionalProperties": true});
+        let config = ConvertOptions {
+            additional_properties: crate::config::AdditionalPropertiesPolicy::Preserve,
+            ..ConvertOptions::default()
+        };
+        let output = enforce_strict(input, &config).unwrap().schema;
+        assert_eq!(output["additionalProperties"], json!(false));
+    }
+
+    fn run_styled(schema: Value, style: crate::config::NullableStyle) -> Value {
+        let config = ConvertOptions {
+            target: crate::config::Target::Gemini,
+            nullable_style: style,
+            ..ConvertOptions::default()
+        };
+        enforce_strict(schema, &config).unwrap().schema
+    }
+
+    fn styled_input() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "description": "Name"},
+                "kind": {"type": "string", "enum": ["a", "b"]},
+                "fixed": {"type": "integer", "const": 1},
+                "either": {"anyOf": [{"type": "string"}, {"type": "integer"}]}
+            }
+        })
+    }
+
+    #[test]
+    fn test_nullable_style_type_array() {
+        let output = run_styled(styled_input(), crate::config::NullableStyle::TypeArray);
+        let props = &output["properties"];
+        assert_eq!(
+            props["name"],
+            json!({"type": ["string", "null"], "description": "Name"})
+        );
+        assert_eq!(props["kind"]["enum"], json!(["a", "b", null]));
+        assert_eq!(
+            props["fixed"],
+            json!({"type": ["integer", "null"], "enum": [1, null]})
+        );
+        // No single type to extend: falls back to anyOf.
+        assert_eq!(props["either"]["anyOf"][1], json!({"type": "null"}));
+    }
+
+    #[test]
+    fn test_nullable_style_openapi() {
+        let output = run_styled(
+            styled_input(),
+            crate::config::NullableStyle::OpenApiNullable,
+        );
+        let props = &output["properties"];
+        assert_eq!(
+            props["name"],
+            json!({"type": "string", "description": "Name", "nullable": true})
+        );
+        assert_eq!(props["kind"]["enum"], json!(["a", "b"]));
+        assert_eq!(props["either"]["anyOf"][1], json!({"type": "null"}));
+    }
+
+    #[test]
+    fn test_nullable_style_ignored_for_openai() {
+        let config = ConvertOptions {
+            nullable_style: crate::config::NullableStyle::OpenApiNullable,
+            ..ConvertOptions::default()
+        };
+        let output = enforce_strict(styled_input(), &config).unwrap().schema;
+        assert!(output["properties"]["name"]["anyOf"].is_array());
+    }
+
+    #[test]
+    fn test_openapi_nullable_wrapped_for_openai() {
+        // OpenAI ignores `nullable`, so the property still needs a null branch.
+        let input = json!({
+            "type": "object",
+            "properties": {"n": {"type": "integer", "nullable": true}}
+        });
+        let (output, transforms) = run(input);
+        assert_eq!(
+            output["properties"]["n"],
+            json!({"anyOf": [{"type": "integer"}, {"type": "null"}]})
+        );
+        assert_eq!(transforms.len(), 1);
+    }
+
+    #[test]
+    fn test_already_openapi_nullable_not_wrapped() {
+        let input = json!({
+            "type": "object",
+            "properties": {"n": {"type": "integer", "nullable": true}}
+        });
+        let output = run_styled(input, crate::config::NullableStyle::OpenApiNullable);
+        assert_eq!(
+            output["properties"]["n"],
+            json!({"type": "integer", "nullable": true})
+        );
+    }
 }
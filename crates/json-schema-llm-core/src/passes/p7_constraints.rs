@@ -1,32 +1,64 @@
 //! Pass 7: Constraint Pruning & Enum Sorting
 //!
 //! Removes constraints that the target provider doesn't support, normalizes
-//! `const` → `enum`, and sorts enum arrays to place the default value first.
+//! `const` → `enum`, sorts enum arrays to place the default value first, and
+//! replaces enums over [`ConvertOptions::max_enum_values`] with a plain typed
+//! field.
 //!
-//! Emits `DroppedConstraint` codec entries for every pruned keyword.
+//! Emits `DroppedConstraint` codec entries for every pruned keyword, so a
+//! capped enum's full value list is kept in the codec and checked on
+//! rehydration.
 
 use serde_json::{json, Map, Value};
 
 use crate::codec::DroppedConstraint;
-use crate::config::{ConvertOptions, Target};
+use crate::config::{ConstraintHintPolicy, ConvertOptions, Target};
+use crate::diagnostics::ConstraintHint;
 use crate::error::ConvertError;
-use crate::schema_utils::recurse_into_children;
+use crate::limits::ResourceGuard;
+use crate::messages::{fill, MessageCatalog};
+use crate::schema_utils::{append_to_description, recurse_into_children};
 
 use super::pass_result::PassResult;
 
+/// Result of Pass 7: the shared pass result plus a record of every
+/// description that dropped constraints were folded into.
+#[derive(Debug)]
+pub struct ConstraintPassResult {
+    pub pass: PassResult,
+    pub hints: Vec<ConstraintHint>,
+}
+
 /// Prune unsupported constraints from a schema based on the target provider.
 ///
 /// Recursively walks every node and:
 /// 1. Normalizes `const` → `enum: [value]` (except Gemini, which supports `const`)
 /// 2. Sorts `enum` to place `default` value first (before `default` is dropped)
-/// 3. Drops unsupported constraints per target, emitting `DroppedConstraint` entries
+/// 3. Replaces oversized enums when `config.max_enum_values` is set
+/// 4. Drops unsupported constraints per target, emitting `DroppedConstraint`
+///    entries and, per `config.constraint_hints`, restating them in the
+///    node's `description`
+#[cfg(test)]
 pub fn prune_constraints(
     schema: Value,
     config: &ConvertOptions,
-) -> Result<PassResult, ConvertError> {
+) -> Result<ConstraintPassResult, ConvertError> {
+    prune_constraints_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`prune_constraints`], ticking `guard` at every node visited.
+pub fn prune_constraints_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<ConstraintPassResult, ConvertError> {
     let mut dropped = Vec::new();
-    let result = walk(schema, "#", 0, config, &mut dropped)?;
-    Ok(PassResult::with_dropped(result, dropped))
+    let mut hints = Vec::new();
+    let result = walk(schema, "#", 0, config, guard, &mut dropped, &mut hints)?;
+    Ok(ConstraintPassResult {
+        pass: PassResult::with_dropped(result, dropped),
+        hints,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -40,7 +72,9 @@ fn walk(
     path: &str,
     depth: usize,
     config: &ConvertOptions,
+    guard: &ResourceGuard,
     dropped: &mut Vec<DroppedConstraint>,
+    hints: &mut Vec<ConstraintHint>,
 ) -> Result<Value, ConvertError> {
     if depth > config.max_depth {
         return Err(ConvertError::RecursionDepthExceeded {
@@ -48,6 +82,7 @@ fn walk(
             max_depth: config.max_depth,
         });
     }
+    guard.tick(path)?;
 
     // Use the owned node directly, mutating its object map in-place if present.
     let mut result = match node {
@@ -61,12 +96,25 @@ fn walk(
     // 2. Enum default-first sorting (before default is dropped)
     sort_enum_default_first(&mut result);
 
-    // 3. Prune unsupported constraints
-    prune_node_constraints(&mut result, path, config.target, dropped);
+    // 3. Oversized enum externalization (after sorting, so samples lead
+    //    with the default)
+    if let Some(max) = config.max_enum_values {
+        cap_enum(
+            &mut result,
+            path,
+            max,
+            config.target,
+            config.messages(),
+            dropped,
+        );
+    }
+
+    // 4. Prune unsupported constraints
+    prune_node_constraints(&mut result, path, config, dropped, hints);
 
     // Recurse into all structural children
     recurse_into_children(&mut result, path, depth, &mut |val, child_path, d| {
-        walk(val, child_path, d, config, dropped)
+        walk(val, child_path, d, config, guard, dropped, hints)
     })?;
 
     Ok(Value::Object(result))
@@ -79,26 +127,35 @@ fn walk(
 /// Normalize `const` to `enum: [value]` for providers that don't support `const`.
 ///
 /// Gemini supports `const` natively — skip normalization for that target.
-/// If both `const` and `enum` exist, `const` wins (intersection semantics).
-/// Emits a `DroppedConstraint` for the removed `const` keyword to enable rehydration.
+/// If both `const` and `enum` exist, `const` wins (intersection semantics);
+/// the replaced `enum` is recorded so rehydration still checks it.
+/// On every target, emits a `DroppedConstraint` for the `const` keyword,
+/// with which rehydration restores the exact constant after type coercion.
 fn normalize_const_to_enum(
     obj: &mut Map<String, Value>,
     path: &str,
     target: Target,
     dropped: &mut Vec<DroppedConstraint>,
 ) {
-    if target == Target::Gemini {
+    let Some(const_val) = obj.get("const").cloned() else {
         return;
+    };
+    if target != Target::Gemini {
+        if let Some(enum_val) = obj.remove("enum") {
+            dropped.push(DroppedConstraint {
+                path: path.to_string(),
+                constraint: "enum".to_string(),
+                value: enum_val,
+            });
+        }
+        obj.remove("const");
+        obj.insert("enum".to_string(), json!([const_val.clone()]));
     }
-
-    if let Some(const_val) = obj.remove("const") {
-        dropped.push(DroppedConstraint {
-            path: path.to_string(),
-            constraint: "const".to_string(),
-            value: const_val.clone(),
-        });
-        obj.insert("enum".to_string(), json!([const_val]));
-    }
+    dropped.push(DroppedConstraint {
+        path: path.to_string(),
+        constraint: "const".to_string(),
+        value: const_val,
+    });
 }
 
 // ---------------------------------------------------------------------------
@@ -128,37 +185,159 @@ fn sort_enum_default_first(obj: &mut Map<String, Value>) {
 }
 
 // ---------------------------------------------------------------------------
-// Step 3: Constraint pruning (with description hint injection)
+// Step 3: Enum size cap
+// ---------------------------------------------------------------------------
+
+/// Enum values quoted in the description of a capped enum.
+const ENUM_SAMPLE_SIZE: usize = 10;
+
+/// Replace an `enum` with more than `max` values by its value type, a
+/// description quoting the first few values, and (for string values where
+/// the target supports it) a character-class `pattern`. The full list moves
+/// to the codec as a dropped `enum` constraint.
+///
+/// Only homogeneous string, integer, or number enums are capped; anything
+/// else is left for the provider to accept or reject.
+fn cap_enum(
+    obj: &mut Map<String, Value>,
+    path: &str,
+    max: usize,
+    target: Target,
+    messages: &MessageCatalog,
+    dropped: &mut Vec<DroppedConstraint>,
+) {
+    let Some(values) = obj.get("enum").and_then(Value::as_array) else {
+        return;
+    };
+    if values.len() <= max {
+        return;
+    }
+    let value_type = if values.iter().all(Value::is_string) {
+        "string"
+    } else if values.iter().all(|v| v.is_i64() || v.is_u64()) {
+        "integer"
+    } else if values.iter().all(Value::is_number) {
+        "number"
+    } else {
+        return;
+    };
+
+    let pattern = if value_type == "string" && target != Target::Claude {
+        let strings: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+        derive_pattern(&strings)
+    } else {
+        None
+    };
+    let sample: Vec<String> = values
+        .iter()
+        .take(ENUM_SAMPLE_SIZE)
+        .map(Value::to_string)
+        .collect();
+    let more = if values.len() > ENUM_SAMPLE_SIZE {
+        &*messages.enum_sample_more
+    } else {
+        ""
+    };
+    let hint = fill(
+        &messages.enum_sample,
+        &[
+            ("count", &values.len()),
+            ("sample", &sample.join(", ")),
+            ("more", &more),
+        ],
+    );
+
+    let values = obj.remove("enum").expect("enum checked above");
+    dropped.push(DroppedConstraint {
+        path: path.to_string(),
+        constraint: "enum".to_string(),
+        value: values,
+    });
+    obj.insert("type".to_string(), json!(value_type));
+    if let Some(pattern) = pattern {
+        obj.entry("pattern").or_insert(json!(pattern));
+    }
+    append_to_description(obj, &hint);
+}
+
+/// A `^[class]{min,max}$` pattern covering every value, if all values are
+/// non-empty and drawn from ASCII letters, digits, `_` and `-`.
+fn derive_pattern(values: &[&str]) -> Option<String> {
+    let (mut upper, mut lower, mut digit, mut underscore, mut dash) =
+        (false, false, false, false, false);
+    let (mut min, mut max) = (usize::MAX, 0);
+    for value in values {
+        if value.is_empty() {
+            return None;
+        }
+        for c in value.chars() {
+            match c {
+                'A'..='Z' => upper = true,
+                'a'..='z' => lower = true,
+                '0'..='9' => digit = true,
+                '_' => underscore = true,
+                '-' => dash = true,
+                _ => return None,
+            }
+        }
+        min = min.min(value.len());
+        max = max.max(value.len());
+    }
+
+    let mut class = String::new();
+    for (seen, range) in [
+        (upper, "A-Z"),
+        (lower, "a-z"),
+        (digit, "0-9"),
+        (underscore, "_"),
+        (dash, "-"),
+    ] {
+        if seen {
+            class.push_str(range);
+        }
+    }
+    let repeat = if min == max {
+        format!("{{{min}}}")
+    } else {
+        format!("{{{min},{max}}}")
+    };
+    Some(format!("^[{class}]{repeat}$"))
+}
+
+// ---------------------------------------------------------------------------
+// Step 4: Constraint pruning (with description hint injection)
 // ---------------------------------------------------------------------------
 
 /// Convert a pruned constraint into a human-readable hint for the LLM.
 ///
 /// Returns `None` for constraints that aren't meaningful as generation hints
 /// (e.g., `default`, `not`, `if/then/else`, `dependencies`).
-fn constraint_to_hint(keyword: &str, value: &Value) -> Option<String> {
+fn constraint_to_hint(keyword: &str, value: &Value, messages: &MessageCatalog) -> Option<String> {
+    let with_value = |template: &str| fill(template, &[("value", value)]);
+    let with_count = |template: &str| value.as_u64().map(|n| fill(template, &[("value", &n)]));
     match keyword {
-        "minimum" => Some(format!("minimum value: {}", value)),
-        "maximum" => Some(format!("maximum value: {}", value)),
-        "exclusiveMinimum" => Some(format!("value must be > {}", value)),
-        "exclusiveMaximum" => Some(format!("value must be < {}", value)),
-        "minLength" => value
-            .as_u64()
-            .map(|n| format!("minimum length: {n} character(s)")),
-        "maxLength" => value
-            .as_u64()
-            .map(|n| format!("maximum length: {n} character(s)")),
-        "minItems" => value.as_u64().map(|n| format!("minimum {n} item(s)")),
-        "maxItems" => value.as_u64().map(|n| format!("maximum {n} item(s)")),
+        "minimum" => Some(with_value(&messages.hint_minimum)),
+        "maximum" => Some(with_value(&messages.hint_maximum)),
+        "exclusiveMinimum" => Some(with_value(&messages.hint_exclusive_minimum)),
+        "exclusiveMaximum" => Some(with_value(&messages.hint_exclusive_maximum)),
+        "minLength" => with_count(&messages.hint_min_length),
+        "maxLength" => with_count(&messages.hint_max_length),
+        "minItems" => with_count(&messages.hint_min_items),
+        "maxItems" => with_count(&messages.hint_max_items),
         "uniqueItems" => {
             if value.as_bool() == Some(true) {
-                Some("items must be unique".to_string())
+                Some(messages.hint_unique_items.to_string())
             } else {
                 None
             }
         }
-        "multipleOf" => Some(format!("must be a multiple of {}", value)),
-        "format" => value.as_str().map(|f| format!("format: {f}")),
-        "pattern" => value.as_str().map(|p| format!("must match pattern: {p}")),
+        "multipleOf" => Some(with_value(&messages.hint_multiple_of)),
+        "format" => value
+            .as_str()
+            .map(|f| fill(&messages.hint_format, &[("value", &f)])),
+        "pattern" => value
+            .as_str()
+            .map(|p| fill(&messages.hint_pattern, &[("value", &p)])),
         // Advisory/structural constraints — no useful hint for generation
         "default" | "not" | "if" | "then" | "else" | "dependencies" | "dependentRequired"
         | "dependentSchemas" | "contains" | "minContains" | "maxContains" | "minProperties"
@@ -167,47 +346,21 @@ fn constraint_to_hint(keyword: &str, value: &Value) -> Option<String> {
     }
 }
 
-/// Append hint text to an object's `description` field.
-///
-/// If no `description` exists, creates one. If one exists, appends with a
-/// period separator. Keeps descriptions concise — just the constraint facts.
-fn append_to_description(obj: &mut Map<String, Value>, hint_text: &str) {
-    match obj.get_mut("description") {
-        Some(Value::String(existing)) => {
-            // Append to existing description
-            if !existing.ends_with('.') && !existing.ends_with(' ') {
-                existing.push('.');
-            }
-            existing.push(' ');
-            existing.push_str(hint_text);
-        }
-        _ => {
-            obj.insert(
-                "description".to_string(),
-                Value::String(hint_text.to_string()),
-            );
-        }
-    }
-}
-
 /// Remove unsupported constraints from a single node, emitting `DroppedConstraint`
-/// codec entries for each one and injecting description hints so the LLM knows
-/// about the original bounds.
+/// codec entries for each one and, unless the hint policy is `Off`, injecting
+/// description hints so the LLM knows about the original bounds.
 fn prune_node_constraints(
     obj: &mut Map<String, Value>,
     path: &str,
-    target: Target,
+    config: &ConvertOptions,
     dropped: &mut Vec<DroppedConstraint>,
+    hints: &mut Vec<ConstraintHint>,
 ) {
-    let (universal, extra) = unsupported_constraints(target);
-    let mut hints: Vec<String> = Vec::new();
+    let (universal, extra) = unsupported_constraints(config.target);
+    let first_dropped = dropped.len();
 
     for keyword in universal.iter().chain(extra.iter()) {
         if let Some(value) = obj.remove(*keyword) {
-            // Build human-readable hint before moving value into codec
-            if let Some(hint) = constraint_to_hint(keyword, &value) {
-                hints.push(hint);
-            }
             dropped.push(DroppedConstraint {
                 path: path.to_string(),
                 constraint: keyword.to_string(),
@@ -216,11 +369,166 @@ fn prune_node_constraints(
         }
     }
 
+    let pruned: Vec<&DroppedConstraint> = dropped[first_dropped..]
+        .iter()
+        .filter(|d| config.numeric_constraint_hints || !NUMERIC.contains(&d.constraint.as_str()))
+        .collect();
+    let parts = match config.constraint_hints {
+        ConstraintHintPolicy::Off => return,
+        ConstraintHintPolicy::Labeled => labeled_parts(&pruned, config.messages()),
+        ConstraintHintPolicy::Compact => compact_parts(&pruned, config.messages()),
+    };
+
+    // Keep as many leading parts as fit the per-path budget.
+    let mut fitting = parts.len();
+    let mut hint_text = render_hint(
+        config.constraint_hints,
+        &parts[..fitting],
+        config.messages(),
+    );
+    if let Some(budget) = config.constraint_hint_budget {
+        while fitting > 0 && hint_text.chars().count() > budget {
+            fitting -= 1;
+            hint_text = render_hint(
+                config.constraint_hints,
+                &parts[..fitting],
+                config.messages(),
+            );
+        }
+    }
+    if fitting == 0 {
+        return;
+    }
+
     // Inject combined hint into description field
-    if !hints.is_empty() {
-        let hint_text = format!("Constraints: {}.", hints.join("; "));
-        append_to_description(obj, &hint_text);
+    append_to_description(obj, &hint_text);
+    hints.push(ConstraintHint {
+        path: path.to_string(),
+        constraints: parts[..fitting]
+            .iter()
+            .flat_map(|(_, keywords)| keywords.iter().cloned())
+            .collect(),
+        text: hint_text,
+    });
+}
+
+/// Numeric bound keywords, governed by `ConvertOptions::numeric_constraint_hints`.
+const NUMERIC: &[&str] = &[
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+];
+
+/// One hint phrase and the dropped keywords it restates.
+type HintPart = (String, Vec<String>);
+
+/// Join hint phrases in the policy's style; empty for no parts.
+fn render_hint(
+    policy: ConstraintHintPolicy,
+    parts: &[HintPart],
+    messages: &MessageCatalog,
+) -> String {
+    if parts.is_empty() {
+        return String::new();
     }
+    let phrases: Vec<&str> = parts.iter().map(|(phrase, _)| phrase.as_str()).collect();
+    match policy {
+        ConstraintHintPolicy::Compact => {
+            let list = phrases.join(&messages.compact_separator);
+            let mut chars = list.chars();
+            let first = chars.next().map(|c| c.to_uppercase().to_string());
+            let list = format!("{}{}", first.unwrap_or_default(), chars.as_str());
+            fill(&messages.constraints_compact, &[("list", &list)])
+        }
+        _ => fill(
+            &messages.constraints_labeled,
+            &[("list", &phrases.join(&messages.labeled_separator))],
+        ),
+    }
+}
+
+/// One `keyword: value`-style phrase per dropped constraint, in drop order.
+fn labeled_parts(pruned: &[&DroppedConstraint], messages: &MessageCatalog) -> Vec<HintPart> {
+    pruned
+        .iter()
+        .filter_map(|d| {
+            constraint_to_hint(&d.constraint, &d.value, messages)
+                .map(|h| (h, vec![d.constraint.clone()]))
+        })
+        .collect()
+}
+
+/// Short natural-language phrases, e.g. `must match ^[A-Z]{3}$`, `3–5 chars`,
+/// `between 0 and 100`. Paired bounds are merged into one phrase.
+fn compact_parts(pruned: &[&DroppedConstraint], messages: &MessageCatalog) -> Vec<HintPart> {
+    let get = |keyword: &str| {
+        pruned
+            .iter()
+            .find(|d| d.constraint == keyword)
+            .map(|d| &d.value)
+    };
+    let one = |template: &str, value: &dyn std::fmt::Display| fill(template, &[("value", value)]);
+    let mut parts = Vec::new();
+    let mut push = |phrase: String, keywords: &[&str]| {
+        let covered = keywords
+            .iter()
+            .filter(|k| get(k).is_some())
+            .map(|k| k.to_string())
+            .collect();
+        parts.push((phrase, covered));
+    };
+
+    if let Some(p) = get("pattern").and_then(Value::as_str) {
+        push(one(&messages.compact_pattern, &p), &["pattern"]);
+    }
+    for (min, max, unit) in [
+        ("minLength", "maxLength", &messages.compact_chars),
+        ("minItems", "maxItems", &messages.compact_items),
+    ] {
+        let lo = get(min).and_then(Value::as_u64);
+        let hi = get(max).and_then(Value::as_u64);
+        let phrase = match (lo, hi) {
+            (Some(lo), Some(hi)) if lo == hi => one(&messages.compact_exactly, &one(unit, &lo)),
+            (Some(lo), Some(hi)) => {
+                let range = fill(&messages.compact_range, &[("min", &lo), ("max", &hi)]);
+                one(unit, &range)
+            }
+            (Some(lo), None) => one(&messages.compact_at_least, &one(unit, &lo)),
+            (None, Some(hi)) => one(&messages.compact_at_most, &one(unit, &hi)),
+            (None, None) => continue,
+        };
+        push(phrase, &[min, max]);
+    }
+    match (get("minimum"), get("maximum")) {
+        (Some(lo), Some(hi)) => push(
+            fill(&messages.compact_between, &[("min", lo), ("max", hi)]),
+            &["minimum", "maximum"],
+        ),
+        (Some(lo), None) => push(one(&messages.compact_at_least, lo), &["minimum"]),
+        (None, Some(hi)) => push(one(&messages.compact_at_most, hi), &["maximum"]),
+        (None, None) => {}
+    }
+    if let Some(v) = get("exclusiveMinimum") {
+        push(
+            one(&messages.compact_greater_than, v),
+            &["exclusiveMinimum"],
+        );
+    }
+    if let Some(v) = get("exclusiveMaximum") {
+        push(one(&messages.compact_less_than, v), &["exclusiveMaximum"]);
+    }
+    if let Some(v) = get("multipleOf") {
+        push(one(&messages.compact_multiple_of, v), &["multipleOf"]);
+    }
+    if get("uniqueItems").and_then(Value::as_bool) == Some(true) {
+        push(messages.compact_unique_items.to_string(), &["uniqueItems"]);
+    }
+    if let Some(f) = get("format").and_then(Value::as_str) {
+        push(one(&messages.compact_format, &f), &["format"]);
+    }
+    parts
 }
 
 /// Return constraint keywords to prune for a given target.
@@ -303,7 +611,7 @@ mod tests {
             ..ConvertOptions::default()
         };
         let result = prune_constraints(schema, &config).unwrap();
-        (result.schema, result.dropped_constraints)
+        (result.pass.schema, result.pass.dropped_constraints)
     }
 
     fn run_openai(schema: Value) -> (Value, Vec<DroppedConstraint>) {
@@ -375,10 +683,25 @@ mod tests {
         assert!(claude_out.get("const").is_none());
         assert!(claude_dropped.iter().any(|d| d.constraint == "const"));
 
-        // Gemini: const preserved as-is, no codec entry
+        // Gemini: const preserved as-is, codec entry still emitted
         let (gemini_out, gemini_dropped) = run(input, Target::Gemini);
         assert_eq!(gemini_out["const"], json!("active"));
-        assert!(!gemini_dropped.iter().any(|d| d.constraint == "const"));
+        assert!(gemini_out.get("enum").is_none());
+        assert!(gemini_dropped
+            .iter()
+            .any(|d| d.constraint == "const" && d.value == json!("active")));
+    }
+
+    #[test]
+    fn test_const_records_replaced_enum() {
+        let input = json!({ "enum": ["x", "y"], "const": "x" });
+        for target in [Target::OpenaiStrict, Target::Claude] {
+            let (out, dropped) = run(input.clone(), target);
+            assert_eq!(out, json!({ "enum": ["x"] }));
+            assert!(dropped
+                .iter()
+                .any(|d| d.constraint == "enum" && d.value == json!(["x", "y"])));
+        }
     }
 
     // -----------------------------------------------------------------------
@@ -658,4 +981,259 @@ mod tests {
         assert_eq!(age_drops[0].path, "#/properties/age");
         assert_eq!(age_drops[0].constraint, "minimum");
     }
+
+    // -----------------------------------------------------------------------
+    // Enum size cap
+    // -----------------------------------------------------------------------
+
+    fn run_capped(schema: Value, target: Target, max: usize) -> (Value, Vec<DroppedConstraint>) {
+        let config = ConvertOptions {
+            target,
+            max_enum_values: Some(max),
+            ..ConvertOptions::default()
+        };
+        let result = prune_constraints(schema, &config).unwrap();
+        (result.pass.schema, result.pass.dropped_constraints)
+    }
+
+    #[test]
+    fn test_enum_cap_externalizes_values() {
+        let currencies = json!(["EUR", "USD", "GBP", "JPY"]);
+        let input = json!({
+            "type": "object",
+            "properties": {
+                "currency": { "enum": currencies, "description": "ISO 4217 code" }
+            }
+        });
+        let (out, dropped) = run_capped(input, Target::OpenaiStrict, 3);
+        let currency = &out["properties"]["currency"];
+        assert!(currency.get("enum").is_none());
+        assert_eq!(currency["type"], "string");
+        assert_eq!(currency["pattern"], "^[A-Z]{3}$");
+        assert_eq!(
+            currency["description"],
+            r#"ISO 4217 code. One of 4 allowed values, e.g. "EUR", "USD", "GBP", "JPY""#
+        );
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].path, "#/properties/currency");
+        assert_eq!(dropped[0].constraint, "enum");
+        assert_eq!(dropped[0].value, currencies);
+    }
+
+    #[test]
+    fn test_enum_cap_marks_omitted_values() {
+        let codes: Vec<String> = (0..12).map(|i| format!("c{i:02}")).collect();
+        let (out, _) = run_capped(json!({ "enum": codes }), Target::OpenaiStrict, 3);
+        let description = out["description"].as_str().unwrap();
+        assert!(
+            description.ends_with(r#""c08", "c09", ..."#),
+            "{description}"
+        );
+        assert!(!description.contains("c10"), "{description}");
+    }
+
+    #[test]
+    fn test_enum_cap_leaves_small_and_mixed_enums() {
+        let small = json!({ "enum": ["a", "b"] });
+        assert_eq!(run_capped(small.clone(), Target::OpenaiStrict, 2).0, small);
+
+        let mixed = json!({ "enum": ["a", 1, true] });
+        assert_eq!(run_capped(mixed.clone(), Target::OpenaiStrict, 2).0, mixed);
+    }
+
+    #[test]
+    fn test_enum_cap_integer_and_claude_have_no_pattern() {
+        let (out, _) = run_capped(json!({ "enum": [1, 2, 3] }), Target::OpenaiStrict, 2);
+        assert_eq!(out["type"], "integer");
+        assert!(out.get("pattern").is_none());
+
+        let (out, dropped) = run_capped(json!({ "enum": ["a", "b", "c"] }), Target::Claude, 2);
+        assert_eq!(out["type"], "string");
+        assert!(out.get("pattern").is_none());
+        assert_eq!(dropped.len(), 1, "no pattern to prune: {dropped:?}");
+    }
+
+    #[test]
+    fn test_derive_pattern() {
+        assert_eq!(
+            derive_pattern(&["en", "en-US", "zh_Hant"]).as_deref(),
+            Some("^[A-Za-z_-]{2,7}$")
+        );
+        assert_eq!(derive_pattern(&["a b"]), None);
+        assert_eq!(derive_pattern(&[""]), None);
+    }
+
+    // -----------------------------------------------------------------------
+    // Constraint hint policy
+    // -----------------------------------------------------------------------
+
+    fn run_hints(schema: Value, policy: ConstraintHintPolicy) -> ConstraintPassResult {
+        let config = ConvertOptions {
+            target: Target::Claude,
+            constraint_hints: policy,
+            ..ConvertOptions::default()
+        };
+        prune_constraints(schema, &config).unwrap()
+    }
+
+    fn currency() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "code": {
+                    "type": "string",
+                    "description": "Currency",
+                    "minLength": 3,
+                    "maxLength": 5,
+                    "format": "iso-4217",
+                    "pattern": "^[A-Z]{3}$"
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_hint_policy_compact() {
+        let result = run_hints(currency(), ConstraintHintPolicy::Compact);
+        assert_eq!(
+            result.pass.schema["properties"]["code"]["description"],
+            "Currency. Must match ^[A-Z]{3}$, 3–5 chars, iso-4217 format."
+        );
+        assert_eq!(result.hints.len(), 1);
+        assert_eq!(result.hints[0].path, "#/properties/code");
+        assert_eq!(
+            result.hints[0].constraints,
+            ["pattern", "minLength", "maxLength", "format"]
+        );
+    }
+
+    #[test]
+    fn test_hint_policy_labeled_is_recorded() {
+        let result = run_hints(currency(), ConstraintHintPolicy::Labeled);
+        let description = result.pass.schema["properties"]["code"]["description"]
+            .as_str()
+            .unwrap();
+        assert!(
+            description.starts_with("Currency. Constraints: format: iso-4217;"),
+            "{description}"
+        );
+        assert_eq!(result.hints[0].text, description["Currency. ".len()..]);
+    }
+
+    #[test]
+    fn test_hint_policy_off_keeps_description() {
+        let result = run_hints(currency(), ConstraintHintPolicy::Off);
+        assert_eq!(
+            result.pass.schema["properties"]["code"]["description"],
+            "Currency"
+        );
+        assert!(result.hints.is_empty());
+        assert_eq!(
+            result.pass.dropped_constraints.len(),
+            4,
+            "still in the codec"
+        );
+    }
+
+    fn price() -> Value {
+        json!({
+            "type": "number",
+            "description": "Price",
+            "minimum": 0,
+            "exclusiveMaximum": 1000,
+            "multipleOf": 0.01
+        })
+    }
+
+    #[test]
+    fn test_numeric_hints_compact() {
+        let result = run_hints(price(), ConstraintHintPolicy::Compact);
+        assert_eq!(
+            result.pass.schema["description"],
+            "Price. At least 0, less than 1000, a multiple of 0.01."
+        );
+        assert_eq!(
+            result.hints[0].constraints,
+            ["minimum", "exclusiveMaximum", "multipleOf"]
+        );
+    }
+
+    #[test]
+    fn test_numeric_hints_toggle_off() {
+        let config = ConvertOptions {
+            target: Target::Claude,
+            numeric_constraint_hints: false,
+            ..ConvertOptions::default()
+        };
+        let result = prune_constraints(price(), &config).unwrap();
+        assert_eq!(result.pass.schema["description"], "Price");
+        assert!(result.hints.is_empty());
+        assert_eq!(
+            result.pass.dropped_constraints.len(),
+            3,
+            "still in the codec"
+        );
+
+        // Non-numeric constraints on other nodes are still hinted.
+        let result = prune_constraints(currency(), &config).unwrap();
+        assert_eq!(result.hints.len(), 1);
+    }
+
+    #[test]
+    fn test_hint_budget_cuts_at_whole_constraint() {
+        let config = ConvertOptions {
+            target: Target::Claude,
+            constraint_hints: ConstraintHintPolicy::Compact,
+            constraint_hint_budget: Some(30),
+            ..ConvertOptions::default()
+        };
+        let result = prune_constraints(price(), &config).unwrap();
+        assert_eq!(result.hints[0].text, "At least 0, less than 1000.");
+        assert_eq!(result.hints[0].constraints, ["minimum", "exclusiveMaximum"]);
+
+        let config = ConvertOptions {
+            constraint_hint_budget: Some(5),
+            ..config
+        };
+        let result = prune_constraints(price(), &config).unwrap();
+        assert_eq!(result.pass.schema["description"], "Price");
+        assert!(result.hints.is_empty());
+    }
+
+    #[test]
+    fn test_compact_parts_single_bounds() {
+        let dropped = |constraint: &str, value: Value| DroppedConstraint {
+            path: "#".to_string(),
+            constraint: constraint.to_string(),
+            value,
+        };
+        let compact = |pruned: &[DroppedConstraint]| {
+            let refs: Vec<&DroppedConstraint> = pruned.iter().collect();
+            let messages = MessageCatalog::default();
+            render_hint(
+                ConstraintHintPolicy::Compact,
+                &compact_parts(&refs, &messages),
+                &messages,
+            )
+        };
+        assert_eq!(
+            compact(&[
+                dropped("minItems", json!(1)),
+                dropped("uniqueItems", json!(true))
+            ]),
+            "At least 1 items, unique items."
+        );
+        assert_eq!(
+            compact(&[dropped("minimum", json!(0)), dropped("maximum", json!(100))]),
+            "Between 0 and 100."
+        );
+        assert_eq!(
+            compact(&[
+                dropped("exclusiveMinimum", json!(0)),
+                dropped("multipleOf", json!(0.5))
+            ]),
+            "Greater than 0, a multiple of 0.5."
+        );
+        assert_eq!(compact(&[dropped("default", json!(1))]), "");
+    }
 }
@@ -10,20 +10,31 @@
 use crate::codec::Transform;
 use crate::config::{ConvertOptions, Mode, Target};
 use crate::error::ConvertError;
+use crate::limits::ResourceGuard;
 use crate::schema_utils::{build_opaque_description, recurse_into_children};
 use serde_json::{json, Value};
 
 use super::pass_result::PassResult;
 
 /// Run adaptive opaque stringification.
+#[cfg(test)]
 pub fn adaptive_opaque(schema: Value, config: &ConvertOptions) -> Result<PassResult, ConvertError> {
+    adaptive_opaque_with_guard(schema, config, &ResourceGuard::new(config))
+}
+
+/// [`adaptive_opaque`], ticking `guard` at every node visited.
+pub fn adaptive_opaque_with_guard(
+    schema: Value,
+    config: &ConvertOptions,
+    guard: &ResourceGuard,
+) -> Result<PassResult, ConvertError> {
     // Only active for OpenAI Strict mode
     if !(config.target == Target::OpenaiStrict && config.mode == Mode::Strict) {
         return Ok(PassResult::schema_only(schema));
     }
 
     let mut transforms = Vec::new();
-    let result = walk(schema, "#", 0, config, &mut transforms)?;
+    let result = walk(schema, "#", 0, config, guard, &mut transforms)?;
 
     Ok(PassResult::with_transforms(result, transforms))
 }
@@ -34,6 +45,7 @@ fn walk(
     path: &str,
     depth: usize,
     config: &ConvertOptions,
+    guard: &ResourceGuard,
     transforms: &mut Vec<Transform>,
 ) -> Result<Value, ConvertError> {
     if depth > config.max_depth {
@@ -42,6 +54,7 @@ fn walk(
             max_depth: config.max_depth,
         });
     }
+    guard.tick(path)?;
 
     let mut result = match node {
         Value::Object(obj) => obj,
@@ -51,7 +64,7 @@ fn walk(
     // Check detection heuristics on the current object
     if has_unreliable_prefix_items(&result) || has_unreliable_enum(&result) {
         // Replace with opaque string
-        let description = build_opaque_description(&Value::Object(result));
+        let description = build_opaque_description(&Value::Object(result), config.messages());
         transforms.push(Transform::JsonStringParse {
             path: path.to_string(),
         });
@@ -63,7 +76,7 @@ fn walk(
 
     // Recurse into children if not transformed
     recurse_into_children(&mut result, path, depth, &mut |val, child_path, d| {
-        walk(val, child_path, d, config, transforms)
+        walk(val, child_path, d, config, guard, transforms)
     })?;
 
     Ok(Value::Object(result))
@@ -4,7 +4,9 @@
 //! strict-sealed, etc.) and both transforms and emits advisory `ProviderCompatError`s
 //! for constructs that would be rejected by the target provider.
 //!
-//! Active only when `target == OpenaiStrict && mode == Strict`.
+//! Active only when `target == OpenaiStrict && mode == Strict`. For any other
+//! target or mode, only the depth budget (#95) runs, and only when
+//! [`ConvertOptions::provider_max_depth`] is set.
 //!
 //! ## Checks
 //!
@@ -16,15 +18,17 @@
 //! | #97   | Boolean / empty schema | Transform  |
 
 use crate::codec::Transform;
-use crate::config::{ConvertOptions, Mode, Target};
+use crate::config::{ConvertOptions, Mode, NullableStyle, Target};
 use crate::error::ProviderCompatError;
-use crate::schema_utils::{build_opaque_description, build_path};
+use crate::messages::MessageCatalog;
+use crate::schema_utils::{build_opaque_description, build_path, build_truncation_description};
 use serde_json::{json, Value};
 
 use super::pass_result::PassResult;
 use super::pass_utils::{enforce_object_strict, extract_types, REF_META_KEYWORDS};
 
-/// OpenAI Strict Mode maximum nesting depth.
+/// OpenAI Strict Mode maximum nesting depth. Overridable via
+/// [`ConvertOptions::provider_max_depth`].
 ///
 /// OpenAI enforces a 5-level nesting limit for structured output schemas.
 /// Schemas exceeding this are rejected with:
@@ -65,7 +69,9 @@ pub fn check_provider_compat(schema: Value, config: &ConvertOptions) -> Provider
                     errors: &mut errors,
                     transforms: &mut transforms,
                     target: config.target,
+                    max_depth: config.provider_max_depth.unwrap_or(OPENAI_MAX_DEPTH),
                     max_depth_observed: 0,
+                    messages: config.messages(),
                 };
                 visitor.visit(&mut schema, "#", 0, 0);
             }
@@ -77,9 +83,12 @@ pub fn check_provider_compat(schema: Value, config: &ConvertOptions) -> Provider
                 errors,
             }
         }
-        _ => ProviderCompatResult {
-            pass: PassResult::schema_only(schema),
-            errors: vec![],
+        _ => match config.provider_max_depth {
+            Some(max_depth) => enforce_depth_budget(schema, config, max_depth),
+            None => ProviderCompatResult {
+                pass: PassResult::schema_only(schema),
+                errors: vec![],
+            },
         },
     }
 }
@@ -184,7 +193,12 @@ fn check_root_type(
             .is_some_and(|p| !p.is_empty());
 
         if has_properties {
-            enforce_object_strict(inner, "#/properties/result", transforms);
+            enforce_object_strict(
+                inner,
+                "#/properties/result",
+                NullableStyle::AnyOfNull,
+                transforms,
+            );
         }
     }
 
@@ -202,7 +216,10 @@ struct CompatVisitor<'a> {
     errors: &'a mut Vec<ProviderCompatError>,
     transforms: &'a mut Vec<Transform>,
     target: Target,
+    /// Semantic depth at which sub-trees are truncated to opaque strings.
+    max_depth: usize,
     max_depth_observed: usize,
+    messages: &'a MessageCatalog,
 }
 
 impl CompatVisitor<'_> {
@@ -212,7 +229,7 @@ impl CompatVisitor<'_> {
     /// - `recursion_depth`: **always** increments — guards against stack overflow
     ///   via `HARD_RECURSION_LIMIT`.
     /// - `semantic_depth`: increments only on data-shape edges (`properties`, `items`,
-    ///   `additionalProperties`, etc.) — used for `OPENAI_MAX_DEPTH` budget.
+    ///   `additionalProperties`, etc.) — used for the `max_depth` budget.
     ///
     /// - #95 Depth budget: diagnostic only (tracks max semantic depth)
     /// - #96 Enum homogeneity: **transforms** mixed enums → all strings + codec entry
@@ -279,69 +296,21 @@ impl CompatVisitor<'_> {
 
         // ── #95 Depth budget: TRUNCATE at limit ───────────────
         // Only truncate schemas that contribute to nesting (objects, arrays).
-        // Primitive leaves (string, integer, number, boolean) don't add depth
-        // and should pass through untouched.
-        // Also recognises nullable-wrapped primitives from p6:
-        //   anyOf: [{type: "string"}, {type: "null"}]
-        if semantic_depth >= OPENAI_MAX_DEPTH && path != "#" {
-            let schema_type = schema.get("type").and_then(|v| v.as_str()).unwrap_or("");
-            let is_primitive = matches!(
-                schema_type,
-                "string" | "integer" | "number" | "boolean" | "null"
-            );
-            // Check for nullable-wrapped primitive from p6 strict pass:
-            // anyOf: [{type: <primitive>}, {type: "null"}]
-            let is_nullable_primitive = !is_primitive
-                && schema
-                    .get("anyOf")
-                    .and_then(|v| v.as_array())
-                    .map(|variants| {
-                        variants.len() == 2
-                            && variants
-                                .iter()
-                                .any(|v| v.get("type").and_then(|t| t.as_str()) == Some("null"))
-                            && variants.iter().any(|v| {
-                                matches!(
-                                    v.get("type").and_then(|t| t.as_str()),
-                                    Some("string" | "integer" | "number" | "boolean")
-                                )
-                            })
-                    })
-                    .unwrap_or(false);
-            // Also skip if the schema has no sub-structure (no properties, items, etc.)
-            let has_sub_structure = schema.get("properties").is_some()
-                || schema.get("items").is_some()
-                || schema.get("additionalProperties").is_some()
-                || schema.get("anyOf").is_some()
-                || schema.get("oneOf").is_some()
-                || schema.get("allOf").is_some()
-                || schema.get("prefixItems").is_some();
-
-            if (is_primitive && !has_sub_structure) || is_nullable_primitive {
-                // Primitive leaf — no nesting contribution, leave it alone
-                return;
+        if semantic_depth >= self.max_depth && path != "#" {
+            if adds_nesting(schema) {
+                let error = truncate_sub_tree(
+                    schema,
+                    path,
+                    semantic_depth,
+                    self.max_depth,
+                    self.target,
+                    self.messages,
+                );
+                self.errors.push(error);
+                self.transforms.push(Transform::JsonStringParse {
+                    path: path.to_string(),
+                });
             }
-
-            // Build a structural description so the LLM knows what JSON to produce
-            let desc = build_opaque_description(schema);
-
-            self.errors.push(ProviderCompatError::DepthBudgetExceeded {
-                actual_depth: semantic_depth,
-                max_depth: OPENAI_MAX_DEPTH,
-                target: self.target,
-                hint: format!(
-                    "Sub-schema at '{}' reaches depth limit {}. Replaced with opaque string.",
-                    path, OPENAI_MAX_DEPTH,
-                ),
-            });
-
-            *schema = json!({
-                "type": "string",
-                "description": desc
-            });
-            self.transforms.push(Transform::JsonStringParse {
-                path: path.to_string(),
-            });
             return;
         }
 
@@ -410,7 +379,7 @@ impl CompatVisitor<'_> {
                     // Branch 2: meaningful patternProperties → opaque-stringify
                     // Meaningful patterns (typed constraints, `false`, etc.) carry
                     // structural intent that can't be silently dropped.
-                    let desc = build_opaque_description(schema);
+                    let desc = build_opaque_description(schema, self.messages);
                     self.errors.push(ProviderCompatError::PatternPropertiesStringified {
                         path: path.to_string(),
                         target: self.target,
@@ -864,6 +833,185 @@ impl CompatVisitor<'_> {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// Depth budget (#95)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Whether `schema` contributes to nesting and so may be truncated at the
+/// depth limit. Primitive leaves (string, integer, number, boolean) and the
+/// nullable-wrapped primitives p6 emits (`anyOf: [{type: "string"}, {type:
+/// "null"}]`) don't add depth and pass through untouched.
+fn adds_nesting(schema: &Value) -> bool {
+    let schema_type = schema.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    let is_primitive = matches!(
+        schema_type,
+        "string" | "integer" | "number" | "boolean" | "null"
+    );
+    // Check for nullable-wrapped primitive from p6 strict pass:
+    // anyOf: [{type: <primitive>}, {type: "null"}]
+    let is_nullable_primitive = !is_primitive
+        && schema
+            .get("anyOf")
+            .and_then(|v| v.as_array())
+            .map(|variants| {
+                variants.len() == 2
+                    && variants
+                        .iter()
+                        .any(|v| v.get("type").and_then(|t| t.as_str()) == Some("null"))
+                    && variants.iter().any(|v| {
+                        matches!(
+                            v.get("type").and_then(|t| t.as_str()),
+                            Some("string" | "integer" | "number" | "boolean")
+                        )
+                    })
+            })
+            .unwrap_or(false);
+    // Also skip if the schema has no sub-structure (no properties, items, etc.)
+    let has_sub_structure = schema.get("properties").is_some()
+        || schema.get("items").is_some()
+        || schema.get("additionalProperties").is_some()
+        || schema.get("anyOf").is_some()
+        || schema.get("oneOf").is_some()
+        || schema.get("allOf").is_some()
+        || schema.get("prefixItems").is_some();
+
+    !((is_primitive && !has_sub_structure) || is_nullable_primitive)
+}
+
+/// Replace the sub-tree at `path` with an opaque string, returning the
+/// error to report. The caller records the matching `JsonStringParse`.
+fn truncate_sub_tree(
+    schema: &mut Value,
+    path: &str,
+    semantic_depth: usize,
+    max_depth: usize,
+    target: Target,
+    messages: &MessageCatalog,
+) -> ProviderCompatError {
+    // Build a bounded structural summary so the LLM knows what JSON
+    // to produce without re-inlining an arbitrarily deep sub-tree.
+    let desc = build_truncation_description(schema, messages);
+    *schema = json!({
+        "type": "string",
+        "description": desc
+    });
+    ProviderCompatError::DepthBudgetExceeded {
+        path: path.to_string(),
+        actual_depth: semantic_depth,
+        max_depth,
+        target,
+        hint: format!(
+            "Sub-schema at '{}' reaches depth limit {}. Replaced with opaque string.",
+            path, max_depth,
+        ),
+    }
+}
+
+/// Enforce only the depth budget, for targets other than OpenAI strict mode
+/// that set [`ConvertOptions::provider_max_depth`]. Every other construct is
+/// left as is.
+fn enforce_depth_budget(
+    mut schema: Value,
+    config: &ConvertOptions,
+    max_depth: usize,
+) -> ProviderCompatResult {
+    let mut walk = DepthBudgetWalk {
+        errors: Vec::new(),
+        transforms: Vec::new(),
+        target: config.target,
+        max_depth,
+        messages: config.messages(),
+    };
+    walk.visit(&mut schema, "#", 0, 0);
+    ProviderCompatResult {
+        pass: PassResult::with_transforms(schema, walk.transforms),
+        errors: walk.errors,
+    }
+}
+
+/// Truncates sub-trees at the depth budget, counting semantic depth the way
+/// [`CompatVisitor`] does: data-shape keywords add a level, combinators,
+/// conditionals and definitions don't.
+struct DepthBudgetWalk<'a> {
+    errors: Vec<ProviderCompatError>,
+    transforms: Vec<Transform>,
+    target: Target,
+    max_depth: usize,
+    messages: &'a MessageCatalog,
+}
+
+impl DepthBudgetWalk<'_> {
+    fn visit(
+        &mut self,
+        schema: &mut Value,
+        path: &str,
+        recursion_depth: usize,
+        semantic_depth: usize,
+    ) {
+        if recursion_depth > HARD_RECURSION_LIMIT {
+            return;
+        }
+        if semantic_depth >= self.max_depth && path != "#" {
+            if adds_nesting(schema) {
+                let error = truncate_sub_tree(
+                    schema,
+                    path,
+                    semantic_depth,
+                    self.max_depth,
+                    self.target,
+                    self.messages,
+                );
+                self.errors.push(error);
+                self.transforms.push(Transform::JsonStringParse {
+                    path: path.to_string(),
+                });
+            }
+            return;
+        }
+        let Some(obj) = schema.as_object_mut() else {
+            return;
+        };
+
+        let rd = recursion_depth + 1;
+        for (keyword, child) in obj.iter_mut() {
+            let sd = match keyword.as_str() {
+                "properties"
+                | "patternProperties"
+                | "additionalProperties"
+                | "unevaluatedProperties"
+                | "unevaluatedItems"
+                | "contains"
+                | "items"
+                | "prefixItems" => semantic_depth + 1,
+                "anyOf" | "oneOf" | "allOf" | "if" | "then" | "else" | "not" | "propertyNames"
+                | "$defs" | "definitions" | "dependentSchemas" => semantic_depth,
+                _ => continue,
+            };
+            let is_map = matches!(
+                keyword.as_str(),
+                "properties" | "patternProperties" | "$defs" | "definitions" | "dependentSchemas"
+            );
+            match child {
+                Value::Object(map) if is_map => {
+                    for (key, sub) in map.iter_mut() {
+                        self.visit(sub, &build_path(path, &[keyword, key]), rd, sd);
+                    }
+                }
+                Value::Array(items) => {
+                    for (i, sub) in items.iter_mut().enumerate() {
+                        self.visit(sub, &build_path(path, &[keyword, &i.to_string()]), rd, sd);
+                    }
+                }
+                // `false` is a constraint, not a sub-tree.
+                Value::Object(_) | Value::Bool(true) if !is_map => {
+                    self.visit(child, &build_path(path, &[keyword]), rd, sd);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // patternProperties classification
 // ═══════════════════════════════════════════════════════════════════════════
@@ -1091,6 +1239,7 @@ fn is_unconstrained(obj: &serde_json::Map<String, Value>) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::messages::Locale;
     use serde_json::json;
 
     fn opts() -> ConvertOptions {
@@ -1358,6 +1507,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn provider_max_depth_overrides_default() {
+        // 4 levels deep: within the default budget of 5
+        let mut schema = json!({"type": "string"});
+        for i in (0..4).rev() {
+            schema = json!({"type": "object", "properties": {format!("l{i}"): schema}});
+        }
+        let r = check_provider_compat(schema.clone(), &opts());
+        assert!(!r
+            .errors
+            .iter()
+            .any(|e| matches!(e, ProviderCompatError::DepthBudgetExceeded { .. })));
+
+        let mut tight = opts();
+        tight.provider_max_depth = Some(2);
+        let r = check_provider_compat(schema, &tight);
+        let truncated = &r.pass.schema["properties"]["l0"]["properties"]["l1"];
+        assert_eq!(truncated["type"], "string");
+        assert!(r.errors.iter().any(|e| matches!(
+            e,
+            ProviderCompatError::DepthBudgetExceeded { max_depth: 2, .. }
+        )));
+        assert!(r.pass.transforms.iter().any(|t| matches!(
+            t,
+            Transform::JsonStringParse { path } if path == "#/properties/l0/properties/l1"
+        )));
+    }
+
+    #[test]
+    fn provider_max_depth_applies_to_other_targets() {
+        let mut schema = json!({"type": "string"});
+        for i in (0..4).rev() {
+            schema = json!({"type": "object", "properties": {format!("l{i}"): schema}});
+        }
+        let mut gemini = ConvertOptions {
+            target: Target::Gemini,
+            ..ConvertOptions::default()
+        };
+        let r = check_provider_compat(schema.clone(), &gemini);
+        assert_eq!(
+            r.pass.schema, schema,
+            "no budget without provider_max_depth"
+        );
+        assert!(r.errors.is_empty());
+
+        gemini.provider_max_depth = Some(2);
+        let r = check_provider_compat(schema, &gemini);
+        let truncated = &r.pass.schema["properties"]["l0"]["properties"]["l1"];
+        assert_eq!(truncated["type"], "string");
+        assert!(matches!(
+            r.errors.as_slice(),
+            [ProviderCompatError::DepthBudgetExceeded {
+                max_depth: 2,
+                target: Target::Gemini,
+                ..
+            }]
+        ));
+        assert_eq!(r.pass.transforms.len(), 1);
+    }
+
+    #[test]
+    fn depth_budget_leaves_other_constructs_for_other_targets() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "mixed": {"enum": ["a", 1]},
+                "any": {},
+                "tags": {"type": ["string", "null"]}
+            }
+        });
+        let gemini = ConvertOptions {
+            target: Target::Gemini,
+            provider_max_depth: Some(5),
+            ..ConvertOptions::default()
+        };
+        let r = check_provider_compat(schema.clone(), &gemini);
+        assert_eq!(r.pass.schema, schema);
+        assert!(r.errors.is_empty());
+        assert!(r.pass.transforms.is_empty());
+    }
+
+    #[test]
+    fn depth_truncation_summary_lists_names_two_levels() {
+        let mut tight = opts();
+        tight.provider_max_depth = Some(1);
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "order": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string"},
+                        "customer": {
+                            "type": "object",
+                            "properties": {
+                                "email": {"type": "string"},
+                                "address": {
+                                    "type": "object",
+                                    "properties": {"zip": {"type": "string"}}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        let r = check_provider_compat(schema, &tight);
+        let desc = r.pass.schema["properties"]["order"]["description"]
+            .as_str()
+            .unwrap();
+        assert!(
+            desc.contains("{customer: {address: object, email: string}, id: string}"),
+            "got: {desc}"
+        );
+        assert!(
+            !desc.contains("zip"),
+            "summary should stop at 2 levels: {desc}"
+        );
+    }
+
     // ── Enum homogeneity ──────────────────────────────────────
     #[test]
     fn homo_enum_clean() {
@@ -1475,7 +1744,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         // patternProperties should be stripped at root
@@ -1507,7 +1778,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         let uc_errs: Vec<_> = errors
@@ -1539,7 +1812,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         assert_eq!(
@@ -1569,7 +1844,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         assert_eq!(
@@ -1596,7 +1873,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         let enum_errs: Vec<_> = errors
@@ -1630,7 +1909,9 @@ mod tests {
                 errors: &mut errors,
                 transforms: &mut transforms,
                 target: Target::OpenaiStrict,
+                max_depth: OPENAI_MAX_DEPTH,
                 max_depth_observed: 0,
+                messages: MessageCatalog::builtin(Locale::En),
             };
             visitor.visit(&mut schema, "#", 0, 0);
             let unconstrained: Vec<_> = errors
@@ -1665,7 +1946,9 @@ mod tests {
                 errors: &mut errors,
                 transforms: &mut transforms,
                 target: Target::OpenaiStrict,
+                max_depth: OPENAI_MAX_DEPTH,
                 max_depth_observed: 0,
+                messages: MessageCatalog::builtin(Locale::En),
             };
             visitor.visit(&mut schema, "#", 0, 0);
             let unconstrained: Vec<_> = errors
@@ -1701,7 +1984,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         let unconstrained: Vec<_> = errors
@@ -1746,7 +2031,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         // The `false` should remain untouched — no UnconstrainedSchema error for it
@@ -1793,7 +2080,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         let unconstrained_paths: Vec<_> = errors
@@ -1840,7 +2129,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         let unconstrained_paths: Vec<_> = errors
@@ -1886,7 +2177,9 @@ mod tests {
             errors: &mut errors,
             transforms: &mut transforms,
             target: Target::OpenaiStrict,
+            max_depth: OPENAI_MAX_DEPTH,
             max_depth_observed: 0,
+            messages: MessageCatalog::builtin(Locale::En),
         };
         visitor.visit(&mut schema, "#", 0, 0);
         // properties(1) → anyOf(1, combinator, no increment) → items(2) → properties(3)
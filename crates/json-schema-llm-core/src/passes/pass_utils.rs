@@ -10,6 +10,7 @@ use std::collections::HashSet;
 use serde_json::{json, Map, Value};
 
 use crate::codec::Transform;
+use crate::config::NullableStyle;
 use crate::schema_utils::build_path;
 
 // ---------------------------------------------------------------------------
@@ -82,11 +83,18 @@ pub fn type_contains_null(type_val: &Value) -> bool {
 
 /// Check if a schema already allows `null`.
 ///
-/// Two forms are recognised:
+/// Three forms are recognised:
 /// - `type: ["...", "null"]` (type array containing "null")
 /// - `anyOf: [... , V]` where V has `type: "null"` or `type: ["null"]`
-pub fn is_already_nullable(schema: &Value) -> bool {
+/// - OpenAPI 3.0 `nullable: true`, only when emitting in that `style` —
+///   other dialects (notably OpenAI) ignore the keyword
+pub fn is_already_nullable(schema: &Value, style: NullableStyle) -> bool {
     if let Some(obj) = schema.as_object() {
+        if style == NullableStyle::OpenApiNullable
+            && obj.get("nullable") == Some(&Value::Bool(true))
+        {
+            return true;
+        }
         // Check type array form: type: ["string", "null"]
         if let Some(type_val) = obj.get("type") {
             if type_contains_null(type_val) {
@@ -130,6 +138,40 @@ pub fn wrap_nullable(mut schema: Value) -> Value {
     })
 }
 
+/// Make `schema` accept `null` in the given style. Schemas without a single
+/// `type` string to extend (e.g. compositions) fall back to
+/// [`wrap_nullable`]. Outside [`NullableStyle::OpenApiNullable`] a stray
+/// `nullable` keyword is dropped, since the target would ignore it.
+pub fn make_nullable(mut schema: Value, style: NullableStyle) -> Value {
+    if style != NullableStyle::OpenApiNullable {
+        if let Some(obj) = schema.as_object_mut() {
+            obj.remove("nullable");
+        }
+    }
+    let mut obj = match schema {
+        Value::Object(obj) if obj.get("type").is_some_and(Value::is_string) => obj,
+        other => return wrap_nullable(other),
+    };
+    match style {
+        NullableStyle::AnyOfNull => return wrap_nullable(Value::Object(obj)),
+        NullableStyle::TypeArray => {
+            let ty = obj.remove("type").unwrap_or_default();
+            obj.insert("type".to_string(), json!([ty, "null"]));
+            if let Some(value) = obj.remove("const") {
+                obj.insert("enum".to_string(), json!([value, null]));
+            } else if let Some(values) = obj.get_mut("enum").and_then(Value::as_array_mut) {
+                if !values.contains(&Value::Null) {
+                    values.push(Value::Null);
+                }
+            }
+        }
+        NullableStyle::OpenApiNullable => {
+            obj.insert("nullable".to_string(), Value::Bool(true));
+        }
+    }
+    Value::Object(obj)
+}
+
 // ---------------------------------------------------------------------------
 // Property introspection
 // ---------------------------------------------------------------------------
@@ -166,14 +208,16 @@ pub fn set_all_required(obj: &mut Map<String, Value>, all_keys: &[String]) {
 // Strict enforcement
 // ---------------------------------------------------------------------------
 
-/// Wrap each optional property in `anyOf: [original_schema, {type: null}]`.
-/// If the property schema is already nullable (has `type: ["...", "null"]`
-/// or `anyOf` containing `{type: "null"}`), skips the wrap but still emits
-/// a `NullableOptional` transform so the rehydrator knows to strip `null`.
+/// Make each optional property nullable in `style` (by default
+/// `anyOf: [original_schema, {type: null}]`). If the property schema is
+/// already nullable (see [`is_already_nullable`]), skips the wrap but still
+/// emits a `NullableOptional` transform so the rehydrator knows to strip
+/// `null`.
 pub fn wrap_optional_properties(
     obj: &mut Map<String, Value>,
     optional_keys: &[String],
     path: &str,
+    style: NullableStyle,
     transforms: &mut Vec<Transform>,
 ) {
     let props = match obj.get_mut("properties").and_then(Value::as_object_mut) {
@@ -183,9 +227,9 @@ pub fn wrap_optional_properties(
 
     for key in optional_keys {
         if let Some(prop_schema) = props.get(key) {
-            if !is_already_nullable(prop_schema) {
+            if !is_already_nullable(prop_schema, style) {
                 // Only clone when we actually need to wrap
-                let wrapped = wrap_nullable(prop_schema.clone());
+                let wrapped = make_nullable(prop_schema.clone(), style);
                 props.insert(key.clone(), wrapped);
             }
             // Always emit transform — rehydrator needs to know null → undefined
@@ -199,7 +243,7 @@ pub fn wrap_optional_properties(
 
 /// Apply the three strict-mode transformations to a single object node:
 ///
-/// 1. Wrap each optional property with `anyOf: [T, {type: null}]`
+/// 1. Make each optional property nullable in `style`
 /// 2. Set `required` to all property keys in `properties` order
 /// 3. Seal the object with `additionalProperties: false`
 ///
@@ -207,6 +251,7 @@ pub fn wrap_optional_properties(
 pub fn enforce_object_strict(
     obj: &mut Map<String, Value>,
     path: &str,
+    style: NullableStyle,
     transforms: &mut Vec<Transform>,
 ) {
     let required_keys = extract_required_set(obj);
@@ -218,7 +263,7 @@ pub fn enforce_object_strict(
         .collect();
 
     // 1. Wrap each optional property with anyOf: [T, {type: null}]
-    wrap_optional_properties(obj, &optional_keys, path, transforms);
+    wrap_optional_properties(obj, &optional_keys, path, style, transforms);
 
     // 2. Set `required` to all property keys in `properties` order
     set_all_required(obj, &all_keys);
@@ -295,19 +340,27 @@ mod tests {
     #[test]
     fn test_is_already_nullable_type_array() {
         let schema = json!({"type": ["string", "null"]});
-        assert!(is_already_nullable(&schema));
+        assert!(is_already_nullable(&schema, NullableStyle::AnyOfNull));
     }
 
     #[test]
     fn test_is_already_nullable_anyof() {
         let schema = json!({"anyOf": [{"type": "string"}, {"type": "null"}]});
-        assert!(is_already_nullable(&schema));
+        assert!(is_already_nullable(&schema, NullableStyle::AnyOfNull));
     }
 
     #[test]
     fn test_is_already_nullable_no() {
         let schema = json!({"type": "string"});
-        assert!(!is_already_nullable(&schema));
+        assert!(!is_already_nullable(&schema, NullableStyle::AnyOfNull));
+    }
+
+    #[test]
+    fn test_is_already_nullable_openapi_only_in_that_style() {
+        let schema = json!({"type": "string", "nullable": true});
+        assert!(is_already_nullable(&schema, NullableStyle::OpenApiNullable));
+        assert!(!is_already_nullable(&schema, NullableStyle::AnyOfNull));
+        assert!(!is_already_nullable(&schema, NullableStyle::TypeArray));
     }
 
     #[test]
@@ -369,7 +422,7 @@ mod tests {
         let obj = schema.as_object_mut().unwrap();
         let mut transforms = Vec::new();
 
-        enforce_object_strict(obj, "#", &mut transforms);
+        enforce_object_strict(obj, "#", NullableStyle::AnyOfNull, &mut transforms);
 
         // additionalProperties sealed
         assert_eq!(obj.get("additionalProperties"), Some(&json!(false)));
@@ -411,7 +464,7 @@ mod tests {
         let obj = schema.as_object_mut().unwrap();
         let mut transforms = Vec::new();
 
-        enforce_object_strict(obj, "#", &mut transforms);
+        enforce_object_strict(obj, "#", NullableStyle::AnyOfNull, &mut transforms);
 
         // additionalProperties sealed
         assert_eq!(obj.get("additionalProperties"), Some(&json!(false)));
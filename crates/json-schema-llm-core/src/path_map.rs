@@ -0,0 +1,545 @@
+//! Original ↔ converted schema path mapping.
+//!
+//! Codec transform paths are recorded in the coordinates of the schema *as
+//! each pass saw it*: a Pass 9 path already includes the `anyOf/0` that
+//! Pass 6 inserted for a nullable wrapper, while a Pass 3 path predates it.
+//! [`PathMap`] replays the structural moves implied by the codec to express
+//! every transformed location in both the original and the final converted
+//! schema.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use json_schema_llm_core::{convert, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({"type": "array", "items": {"type": "string"}});
+//! let result = convert(&schema, &ConvertOptions::default()).unwrap();
+//!
+//! let map = result.path_map();
+//! assert_eq!(map.converted_path("#"), Some("#/properties/result"));
+//! assert_eq!(map.original_path("#/properties/result"), Some("#"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::codec::{Codec, Transform};
+use crate::schema_utils::{build_path, resolve_pointer};
+
+/// Keyword under which [`ConvertOptions::annotate`](crate::ConvertOptions::annotate)
+/// lists the transforms applied to a converted schema node.
+pub const TRANSFORM_ANNOTATION: &str = "x-jsl-transform";
+
+/// One transformed location, expressed in both coordinate systems.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathMapping {
+    /// JSON Pointer into the original (pre-conversion) schema.
+    pub original: String,
+    /// JSON Pointer into the converted schema.
+    pub converted: String,
+    /// The codec transform type responsible, e.g. `"map_to_array"`.
+    pub transform: String,
+}
+
+/// Bidirectional mapping between original and converted schema paths.
+///
+/// Built by [`ConvertResult::path_map`](crate::ConvertResult::path_map).
+/// Entries appear in codec order; a location touched by several transforms
+/// appears once per transform.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PathMap {
+    entries: Vec<PathMapping>,
+    /// Every structural move, in codec order, for [`Self::original_location`].
+    #[serde(skip)]
+    moves: Vec<Move>,
+}
+
+impl PathMap {
+    /// Build the mapping from a codec and the converted schema it belongs to.
+    ///
+    /// The converted schema is consulted to tell whether a `NullableOptional`
+    /// property was actually wrapped in `anyOf: [T, {type: null}]` (and so
+    /// moved its children under `anyOf/0`) or was already nullable. A
+    /// property that was *originally* `anyOf: [T, {type: null}]` looks the
+    /// same as a wrapped one, so paths beneath it are mapped as if wrapped.
+    pub fn from_codec(codec: &Codec, converted_schema: &Value) -> Self {
+        let transforms = &codec.transforms;
+        let moves = structural_moves(codec, converted_schema);
+
+        let mut entries = Vec::new();
+        let mut synthetic: Vec<String> = Vec::new();
+        for (i, t) in transforms.iter().enumerate() {
+            let path = transform_path(t);
+            let original = backward(path, &moves[..i]);
+            // The property synthesized by an additionalProperties extraction
+            // has no original location; its value schema is covered by the
+            // extraction entry itself.
+            if let Transform::ExtractAdditionalProperties { property_name, .. } = t {
+                synthetic.push(build_path(&original, &["properties", property_name]));
+            }
+            if synthetic.contains(&original) {
+                continue;
+            }
+
+            let converted = forward(path, &moves[i..]);
+            let kind = transform_kind(t);
+            entries.push(PathMapping {
+                original,
+                converted,
+                transform: kind.to_string(),
+            });
+
+            // Map and extraction transforms also relocate the value schema.
+            if let Some(mv) = &moves[i] {
+                if matches!(
+                    t,
+                    Transform::MapToArray { .. } | Transform::ExtractAdditionalProperties { .. }
+                ) {
+                    entries.push(PathMapping {
+                        original: backward(&mv.from, &moves[..i]),
+                        converted: forward(&mv.to, &moves[i + 1..]),
+                        transform: kind.to_string(),
+                    });
+                }
+            }
+        }
+
+        Self {
+            entries,
+            moves: moves.into_iter().flatten().collect(),
+        }
+    }
+
+    /// All mapped locations, in codec order.
+    pub fn entries(&self) -> &[PathMapping] {
+        &self.entries
+    }
+
+    /// Where an original-schema location ended up in the converted schema.
+    pub fn converted_path(&self, original: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.original == original)
+            .map(|e| e.converted.as_str())
+    }
+
+    /// Which original-schema location a converted-schema path came from.
+    pub fn original_path(&self, converted: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.converted == converted)
+            .map(|e| e.original.as_str())
+    }
+
+    /// The original-schema location of any converted-schema path, including
+    /// ones beneath a moved node that no transform touched directly (e.g. a
+    /// field inside a nullable wrapper's `anyOf/0`). Paths outside every move
+    /// come back unchanged. A map deserialized from JSON has no moves and
+    /// always returns `converted`.
+    pub fn original_location(&self, converted: &str) -> String {
+        self.moves
+            .iter()
+            .rev()
+            .fold(converted.to_string(), |p, mv| mv.invert(&p).unwrap_or(p))
+    }
+
+    /// Whether no location was moved or transformed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// The structural move each transform makes, one slot per transform.
+/// Resolved last-to-first so the nullable check can forward-map through
+/// every later move.
+fn structural_moves(codec: &Codec, converted_schema: &Value) -> Vec<Option<Move>> {
+    let transforms = &codec.transforms;
+    let mut moves: Vec<Option<Move>> = vec![None; transforms.len()];
+    for (i, t) in transforms.iter().enumerate().rev() {
+        moves[i] = match t {
+            Transform::MapToArray { path, .. } => Some(Move::inclusive(
+                build_path(path, &["additionalProperties"]),
+                build_path(path, &["items", "properties", "value"]),
+            )),
+            Transform::ExtractAdditionalProperties {
+                path,
+                property_name,
+            } => Some(Move::inclusive(
+                build_path(path, &["additionalProperties"]),
+                build_path(
+                    path,
+                    &["properties", property_name, "items", "properties", "value"],
+                ),
+            )),
+            Transform::DiscriminatorAnyOf { path, .. } => Some(Move::inclusive(
+                build_path(path, &["oneOf"]),
+                build_path(path, &["anyOf"]),
+            )),
+            Transform::RootObjectWrapper { path, wrapper_key } => Some(Move::inclusive(
+                path.clone(),
+                build_path(path, &["properties", wrapper_key]),
+            )),
+            Transform::NullableOptional { path, .. } => {
+                let final_path = forward(path, &moves[i + 1..]);
+                resolve_pointer(converted_schema, &final_path)
+                    .filter(|node| is_nullable_wrapper(node))
+                    .map(|_| Move::descendants(path.clone(), build_path(path, &["anyOf", "0"])))
+            }
+            Transform::JsonStringEncode { path } => Some(Move::descendants(
+                build_path(path, &["contentSchema"]),
+                path.clone(),
+            )),
+            Transform::JsonStringParse { .. }
+            | Transform::RecursiveInflate { .. }
+            | Transform::RecursiveTerminate { .. }
+            | Transform::RecursiveNodeRef { .. }
+            | Transform::EnumStringify { .. }
+            | Transform::AnyOfOverflow { .. }
+            | Transform::FilteredProperty { .. }
+            | Transform::ProjectedVariants { .. }
+            | Transform::Custom { .. } => None,
+        };
+    }
+    moves
+}
+
+/// Where each of `codec`'s transforms landed in `converted_schema`, in
+/// codec order.
+pub(crate) fn converted_transform_paths(codec: &Codec, converted_schema: &Value) -> Vec<String> {
+    let moves = structural_moves(codec, converted_schema);
+    codec
+        .transforms
+        .iter()
+        .enumerate()
+        .map(|(i, t)| forward(transform_path(t), &moves[i..]))
+        .collect()
+}
+
+/// Add a [`TRANSFORM_ANNOTATION`] array to every node of `converted_schema`
+/// that a transform of `codec` applies to, naming the transform types in
+/// codec order.
+pub(crate) fn annotate_transforms(converted_schema: &mut Value, codec: &Codec) {
+    let map = PathMap::from_codec(codec, converted_schema);
+    for entry in map.entries() {
+        let pointer = entry
+            .converted
+            .strip_prefix('#')
+            .unwrap_or(&entry.converted);
+        let Some(Value::Object(node)) = converted_schema.pointer_mut(pointer) else {
+            continue;
+        };
+        let marks = node
+            .entry(TRANSFORM_ANNOTATION)
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(marks) = marks {
+            let kind = Value::String(entry.transform.clone());
+            if !marks.contains(&kind) {
+                marks.push(kind);
+            }
+        }
+    }
+}
+
+/// A prefix substitution applied to paths by one transform.
+#[derive(Debug, Clone)]
+struct Move {
+    from: String,
+    to: String,
+    /// Whether `from` itself moves, or only paths beneath it. A nullable
+    /// wrapper leaves the property at its path but pushes its children down.
+    inclusive: bool,
+}
+
+impl Move {
+    fn inclusive(from: String, to: String) -> Self {
+        Self {
+            from,
+            to,
+            inclusive: true,
+        }
+    }
+
+    fn descendants(from: String, to: String) -> Self {
+        Self {
+            from,
+            to,
+            inclusive: false,
+        }
+    }
+
+    fn apply(&self, path: &str) -> Option<String> {
+        match strip_path_prefix(path, &self.from) {
+            Some("") if !self.inclusive => None,
+            Some(rest) => Some(format!("{}{}", self.to, rest)),
+            None => None,
+        }
+    }
+
+    fn invert(&self, path: &str) -> Option<String> {
+        strip_path_prefix(path, &self.to).map(|rest| format!("{}{}", self.from, rest))
+    }
+}
+
+/// Apply `moves` in order.
+fn forward(path: &str, moves: &[Option<Move>]) -> String {
+    moves
+        .iter()
+        .flatten()
+        .fold(path.to_string(), |p, mv| mv.apply(&p).unwrap_or(p))
+}
+
+/// Undo `moves`, latest first.
+fn backward(path: &str, moves: &[Option<Move>]) -> String {
+    moves
+        .iter()
+        .rev()
+        .flatten()
+        .fold(path.to_string(), |p, mv| mv.invert(&p).unwrap_or(p))
+}
+
+/// Strip `prefix` from `path` on a segment boundary, returning the remainder
+/// (empty, or starting with `/`).
+fn strip_path_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix == "#" {
+        return path.strip_prefix('#');
+    }
+    let rest = path.strip_prefix(prefix)?;
+    (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+}
+
+/// `anyOf: [T, {type: null}]` as produced by strict-mode nullable wrapping.
+fn is_nullable_wrapper(node: &Value) -> bool {
+    node.get("anyOf")
+        .and_then(Value::as_array)
+        .is_some_and(|variants| {
+            variants.len() == 2 && variants[1].get("type").and_then(Value::as_str) == Some("null")
+        })
+}
+
+pub(crate) fn transform_path(t: &Transform) -> &str {
+    match t {
+        Transform::MapToArray { path, .. }
+        | Transform::JsonStringParse { path }
+        | Transform::NullableOptional { path, .. }
+        | Transform::DiscriminatorAnyOf { path, .. }
+        | Transform::ExtractAdditionalProperties { path, .. }
+        | Transform::RecursiveInflate { path, .. }
+        | Transform::RecursiveTerminate { path, .. }
+        | Transform::RecursiveNodeRef { path, .. }
+        | Transform::RootObjectWrapper { path, .. }
+        | Transform::EnumStringify { path, .. }
+        | Transform::AnyOfOverflow { path, .. }
+        | Transform::FilteredProperty { path, .. }
+        | Transform::ProjectedVariants { path, .. }
+        | Transform::JsonStringEncode { path }
+        | Transform::Custom { path, .. } => path,
+    }
+}
+
+pub(crate) fn transform_kind(t: &Transform) -> &str {
+    match t {
+        Transform::MapToArray { .. } => "map_to_array",
+        Transform::JsonStringParse { .. } => "json_string_parse",
+        Transform::NullableOptional { .. } => "nullable_optional",
+        Transform::DiscriminatorAnyOf { .. } => "discriminator_any_of",
+        Transform::ExtractAdditionalProperties { .. } => "extract_additional_properties",
+        Transform::RecursiveInflate { .. } => "recursive_inflate",
+        Transform::RecursiveTerminate { .. } => "recursive_terminate",
+        Transform::RecursiveNodeRef { .. } => "recursive_node_ref",
+        Transform::RootObjectWrapper { .. } => "root_object_wrapper",
+        Transform::EnumStringify { .. } => "enum_stringify",
+        Transform::AnyOfOverflow { .. } => "any_of_overflow",
+        Transform::FilteredProperty { .. } => "filtered_property",
+        Transform::ProjectedVariants { .. } => "projected_variants",
+        Transform::JsonStringEncode { .. } => "json_string_encode",
+        Transform::Custom { kind, .. } => kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, ConvertOptions};
+    use serde_json::json;
+
+    #[test]
+    fn test_annotate_marks_transformed_nodes() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "tags": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "required": ["name"]
+        });
+        let plain = convert(&schema, &ConvertOptions::default()).unwrap();
+        assert!(!plain.schema.to_string().contains(TRANSFORM_ANNOTATION));
+
+        let options = ConvertOptions {
+            annotate: true,
+            ..ConvertOptions::default()
+        };
+        let result = convert(&schema, &options).unwrap();
+        let tags = &result.schema["properties"]["tags"];
+        assert_eq!(
+            tags[TRANSFORM_ANNOTATION],
+            json!(["map_to_array", "nullable_optional"])
+        );
+        assert_eq!(
+            tags["anyOf"][0]["items"]["properties"]["value"][TRANSFORM_ANNOTATION],
+            json!(["map_to_array"])
+        );
+        assert!(result.schema["properties"]["name"]
+            .get(TRANSFORM_ANNOTATION)
+            .is_none());
+        // The codec and path map are unaffected.
+        assert_eq!(
+            serde_json::to_value(&result.codec).unwrap(),
+            serde_json::to_value(&plain.codec).unwrap()
+        );
+        assert_eq!(result.path_map().entries(), plain.path_map().entries());
+    }
+
+    fn map_for(schema: &Value) -> PathMap {
+        convert(schema, &ConvertOptions::default())
+            .unwrap()
+            .path_map()
+    }
+
+    #[test]
+    fn test_root_wrapper_moves_everything() {
+        let schema = json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {"tags": {"type": "object", "additionalProperties": {"type": "string"}}},
+                "required": ["tags"]
+            }
+        });
+        let map = map_for(&schema);
+        assert_eq!(map.converted_path("#"), Some("#/properties/result"));
+        assert_eq!(
+            map.converted_path("#/items/properties/tags"),
+            Some("#/properties/result/items/properties/tags")
+        );
+        assert_eq!(
+            map.original_path("#/properties/result/items/properties/tags"),
+            Some("#/items/properties/tags")
+        );
+    }
+
+    #[test]
+    fn test_map_value_schema_moves_to_entry_form() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"tags": {"type": "object", "additionalProperties": {"type": "integer"}}},
+            "required": ["tags"]
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+        let map = result.path_map();
+        let converted = map
+            .converted_path("#/properties/tags/additionalProperties")
+            .unwrap();
+        assert_eq!(converted, "#/properties/tags/items/properties/value");
+        assert_eq!(
+            resolve_pointer(&result.schema, converted).unwrap()["type"],
+            "integer"
+        );
+    }
+
+    #[test]
+    fn test_nullable_wrapper_pushes_children_under_any_of() {
+        // `inner` is optional → wrapped; its map child is then at anyOf/0.
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "inner": {
+                    "type": "object",
+                    "properties": {
+                        "deep": {"type": "object", "additionalProperties": {"type": "string"}}
+                    },
+                    "required": ["deep"]
+                }
+            }
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+        let map = result.path_map();
+        assert_eq!(
+            map.converted_path("#/properties/inner"),
+            Some("#/properties/inner")
+        );
+        let deep = map
+            .converted_path("#/properties/inner/properties/deep/additionalProperties")
+            .unwrap();
+        assert_eq!(
+            deep,
+            "#/properties/inner/anyOf/0/properties/deep/items/properties/value"
+        );
+        assert!(resolve_pointer(&result.schema, deep).is_some());
+    }
+
+    #[test]
+    fn test_original_location_of_untouched_descendant() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "inner": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string", "minLength": 1}},
+                    "required": ["name"]
+                }
+            }
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+        let map = result.path_map();
+        let converted = "#/properties/inner/anyOf/0/properties/name";
+        assert_eq!(map.original_path(converted), None);
+        assert!(resolve_pointer(&result.schema, converted).is_some());
+        assert_eq!(
+            map.original_location(converted),
+            "#/properties/inner/properties/name"
+        );
+        assert_eq!(map.original_location("#/unrelated"), "#/unrelated");
+    }
+
+    #[test]
+    fn test_extracted_additional_properties() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}},
+            "required": ["name"],
+            "additionalProperties": {"type": "number"}
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+        let map = result.path_map();
+        let converted = map.converted_path("#/additionalProperties").unwrap();
+        // The synthetic property is optional, so strict mode wraps it too.
+        assert_eq!(
+            converted,
+            "#/properties/_additional/anyOf/0/items/properties/value"
+        );
+        assert_eq!(
+            resolve_pointer(&result.schema, converted).unwrap()["type"],
+            "number"
+        );
+        assert_eq!(map.original_path(converted), Some("#/additionalProperties"));
+        assert!(
+            !map.entries()
+                .iter()
+                .any(|e| e.original == "#/properties/_additional"),
+            "synthetic map property has no original location"
+        );
+    }
+
+    #[test]
+    fn test_identity_when_nothing_moves() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"a": {"type": "string"}},
+            "required": ["a"]
+        });
+        assert!(map_for(&schema).is_empty());
+    }
+}
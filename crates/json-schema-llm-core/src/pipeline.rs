@@ -0,0 +1,776 @@
+//! Reusable conversion pipelines.
+//!
+//! [`convert`](crate::convert) takes its whole configuration on every call.
+//! A [`ConvertPipeline`] is built once — options, built-in passes to skip, a
+//! resolver for external `$ref`s, custom passes and lifecycle hooks — and
+//! then converts any number of schemas, which suits services that embed the
+//! converter behind a long-lived handle.
+//!
+//! ```rust
+//! use json_schema_llm_core::pass::BuiltinPass;
+//! use json_schema_llm_core::pipeline::{ConvertPipeline, PassHook};
+//! use json_schema_llm_core::{ConvertError, Target};
+//! use serde_json::{json, Value};
+//!
+//! struct Trace;
+//!
+//! impl PassHook for Trace {
+//!     fn before_pass(&self, pass: BuiltinPass, _schema: &Value) -> Result<(), ConvertError> {
+//!         println!("running {pass:?}");
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let pipeline = ConvertPipeline::new()
+//!     .target(Target::Gemini)
+//!     .skip_pass(BuiltinPass::Opaque)
+//!     .resolver(|uri: &str| match uri {
+//!         "https://example.com/pet.json" => Some(json!({"type": "string"})),
+//!         _ => None,
+//!     })
+//!     .hook(Trace);
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {"pet": {"$ref": "https://example.com/pet.json"}},
+//!     "required": ["pet"]
+//! });
+//! let result = pipeline.convert(&schema).unwrap();
+//! assert_eq!(result.schema["properties"]["pet"]["type"], "string");
+//! ```
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use url::Url;
+
+use crate::codec::Codec;
+use crate::config::{ConvertOptions, Mode, Progress, Target};
+use crate::error::ConvertError;
+use crate::limits::ResourceGuard;
+use crate::pass::{BuiltinPass, ConversionPass, ExtraPass};
+use crate::ConvertResult;
+
+/// Observer called around each built-in pass of a [`ConvertPipeline`].
+///
+/// Both methods default to doing nothing. An error aborts the conversion.
+pub trait PassHook: Send + Sync {
+    /// Called with the schema about to enter `pass`.
+    fn before_pass(&self, pass: BuiltinPass, schema: &Value) -> Result<(), ConvertError> {
+        let _ = (pass, schema);
+        Ok(())
+    }
+
+    /// Called once `pass`, and any custom passes slotted after it, have run.
+    /// `codec` holds every transform recorded so far.
+    fn after_pass(
+        &self,
+        pass: BuiltinPass,
+        schema: &Value,
+        codec: &Codec,
+    ) -> Result<(), ConvertError> {
+        let _ = (pass, schema, codec);
+        Ok(())
+    }
+}
+
+/// Source of external documents for `$ref`s that point outside the schema.
+///
+/// Implemented for closures `Fn(&str) -> Option<Value>`.
+pub trait RefResolver: Send + Sync {
+    /// Fetch the document at `uri`, an absolute URI without fragment.
+    /// `Ok(None)` leaves references to it unresolved, as without a resolver.
+    fn resolve(&self, uri: &str) -> Result<Option<Value>, ConvertError>;
+}
+
+impl<F> RefResolver for F
+where
+    F: Fn(&str) -> Option<Value> + Send + Sync,
+{
+    fn resolve(&self, uri: &str) -> Result<Option<Value>, ConvertError> {
+        Ok(self(uri))
+    }
+}
+
+/// A configured, reusable conversion pipeline.
+///
+/// Cheap to clone; hooks, resolver and custom passes are shared.
+#[derive(Clone, Default)]
+pub struct ConvertPipeline {
+    options: ConvertOptions,
+    skipped: Vec<BuiltinPass>,
+    hooks: Vec<Arc<dyn PassHook>>,
+    resolver: Option<Arc<dyn RefResolver>>,
+}
+
+impl ConvertPipeline {
+    /// A pipeline with [`ConvertOptions::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A pipeline starting from `options`, including their custom passes.
+    pub fn with_options(options: ConvertOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Set the target provider.
+    pub fn target(mut self, target: Target) -> Self {
+        self.options.target = target;
+        self
+    }
+
+    /// Set the conversion mode.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+
+    /// Leave out a built-in pass. Custom passes slotted after it still run.
+    ///
+    /// Later passes assume the earlier ones ran — skipping
+    /// [`BuiltinPass::Normalize`], for instance, leaves `$ref`s in place —
+    /// so check the output against the target before relying on it.
+    pub fn skip_pass(mut self, pass: BuiltinPass) -> Self {
+        if !self.skipped.contains(&pass) {
+            self.skipped.push(pass);
+        }
+        self
+    }
+
+    /// Resolve `$ref`s to other documents through `resolver`. Fetched
+    /// documents are inlined under the root's `$defs` before conversion.
+    pub fn resolver(mut self, resolver: impl RefResolver + 'static) -> Self {
+        self.resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    /// Add a lifecycle hook. Hooks run in the order they were added.
+    pub fn hook(mut self, hook: impl PassHook + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Run a custom pass after the built-in pass `after`.
+    pub fn pass(mut self, after: BuiltinPass, pass: impl ConversionPass + 'static) -> Self {
+        self.options.extra_passes.push(ExtraPass::new(after, pass));
+        self
+    }
+
+    /// The options every conversion runs with.
+    pub fn options(&self) -> &ConvertOptions {
+        &self.options
+    }
+
+    /// Convert `schema`, as [`convert`](crate::convert) does with this
+    /// pipeline's options.
+    pub fn convert(&self, schema: &Value) -> Result<ConvertResult, ConvertError> {
+        match &self.resolver {
+            Some(resolver) => {
+                let bundled = bundle_external_refs(schema, resolver.as_ref())?;
+                crate::convert_staged(&bundled, &self.options, &self.stages())
+            }
+            None => crate::convert_staged(schema, &self.options, &self.stages()),
+        }
+    }
+
+    /// Convert a schema given as text, as [`convert_str`](crate::convert_str)
+    /// does with this pipeline's options.
+    pub fn convert_str(&self, schema_json: &str) -> Result<ConvertResult, ConvertError> {
+        let (schema, diagnostics) = crate::parse_schema_str(schema_json, &self.options)?;
+        let result = self
+            .convert(&schema)
+            .map_err(|e| e.with_source_location(schema_json))?;
+        Ok(result.with_source_diagnostics(diagnostics, &self.options))
+    }
+
+    fn stages(&self) -> Stages<'_> {
+        Stages {
+            skipped: &self.skipped,
+            hooks: &self.hooks,
+            timings: RefCell::default(),
+        }
+    }
+}
+
+impl fmt::Debug for ConvertPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConvertPipeline")
+            .field("options", &self.options)
+            .field("skipped", &self.skipped)
+            .field("hooks", &self.hooks.len())
+            .field("resolver", &self.resolver.is_some())
+            .finish()
+    }
+}
+
+/// Cost of one built-in pass in a conversion, from
+/// [`ConvertResult::timings`].
+///
+/// Recorded on every conversion; with the `tracing` feature each pass also
+/// runs inside a `pass` span.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PassTiming {
+    pub pass: BuiltinPass,
+    /// Wall-clock time spent in the pass itself, excluding hooks and custom
+    /// passes slotted after it. Zero where no clock is available (see the
+    /// crate's `time` feature).
+    pub duration: Duration,
+    /// Schema nodes the pass visited, as counted against
+    /// [`ConvertOptions::max_nodes`]. Zero for provider compatibility checks,
+    /// which are not metered.
+    pub nodes_visited: usize,
+    /// Codec transforms the pass recorded.
+    pub transforms_emitted: usize,
+}
+
+/// Per-pipeline controls over the built-in passes run by `convert_staged`.
+#[derive(Default)]
+pub(crate) struct Stages<'a> {
+    skipped: &'a [BuiltinPass],
+    hooks: &'a [Arc<dyn PassHook>],
+    timings: RefCell<Vec<PassTiming>>,
+}
+
+impl Stages<'_> {
+    /// Run built-in `pass` via `run` unless it is skipped, then the custom
+    /// passes slotted after it, with hooks on either side. A skipped pass
+    /// yields `T::default()` and fires no hooks or progress. `guard` is the
+    /// one `run` meters its node visits on.
+    pub(crate) fn run<T: Default>(
+        &self,
+        pass: BuiltinPass,
+        schema: Value,
+        options: &ConvertOptions,
+        guard: &ResourceGuard,
+        codec: &mut Codec,
+        run: impl FnOnce(Value, &mut Codec) -> Result<(Value, T), ConvertError>,
+    ) -> Result<(Value, T), ConvertError> {
+        if self.skipped.contains(&pass) {
+            crate::logging::debug!(?pass, "skipping built-in pass");
+            let schema = crate::run_extra_passes(pass, schema, options, codec)?;
+            return Ok((schema, T::default()));
+        }
+        if let Some(progress) = &options.progress {
+            let index = BuiltinPass::ALL
+                .iter()
+                .position(|p| *p == pass)
+                .unwrap_or(0);
+            progress.report(Progress::Pass {
+                pass,
+                index: index + 1,
+                total: BuiltinPass::ALL.len(),
+            });
+        }
+        for hook in self.hooks {
+            hook.before_pass(pass, &schema)?;
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("pass", ?pass).entered();
+        let nodes_before = guard.nodes();
+        let transforms_before = codec.transforms.len();
+        let stopwatch = crate::limits::Stopwatch::start();
+        let (schema, extra) = run(schema, codec)?;
+        let nodes_visited = guard.nodes().saturating_sub(nodes_before);
+        let timing = PassTiming {
+            pass,
+            duration: stopwatch.elapsed(),
+            nodes_visited,
+            transforms_emitted: codec.transforms.len().saturating_sub(transforms_before),
+        };
+        crate::logging::debug!(
+            duration_us = timing.duration.as_micros() as u64,
+            nodes_visited,
+            transforms_emitted = timing.transforms_emitted,
+            "pass finished"
+        );
+        self.timings.borrow_mut().push(timing);
+        let schema = crate::run_extra_passes(pass, schema, options, codec)?;
+        for hook in self.hooks {
+            hook.after_pass(pass, &schema, codec)?;
+        }
+        Ok((schema, extra))
+    }
+
+    /// The timings recorded so far, leaving none behind.
+    pub(crate) fn take_timings(&self) -> Vec<PassTiming> {
+        self.timings.take()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// External $ref bundling
+// ---------------------------------------------------------------------------
+
+/// Inline every document `resolver` returns for a non-local `$ref` under the
+/// root's `$defs`, rewriting the refs (and the documents' own local refs) to
+/// point there. A fetched document's own `$defs` are hoisted beside it as
+/// `<document>_<name>`, since definitions nested below the root are not
+/// resolved by the conversion passes. Refs to resources embedded in the
+/// schema by `$id` are left for normalization to resolve.
+fn bundle_external_refs(schema: &Value, resolver: &dyn RefResolver) -> Result<Value, ConvertError> {
+    let Value::Object(_) = schema else {
+        return Ok(schema.clone());
+    };
+    let default_base = crate::anchor_utils::default_base_uri();
+    let root_base = match schema.get("$id").and_then(Value::as_str) {
+        Some(id) => default_base.join(id).unwrap_or(default_base),
+        None => default_base,
+    };
+
+    let mut bundler = Bundler {
+        resolver,
+        embedded: crate::anchor_utils::build_anchor_map(schema, None)?,
+        placements: HashMap::from([(document_uri(&root_base), None)]),
+        taken: schema
+            .get("$defs")
+            .and_then(Value::as_object)
+            .map(|defs| defs.keys().cloned().collect())
+            .unwrap_or_default(),
+        bundled: Vec::new(),
+    };
+    let mut root = schema.clone();
+    bundler.rewrite(&mut root, &root_base, None)?;
+
+    if !bundler.bundled.is_empty() {
+        let obj = root.as_object_mut().expect("root is an object");
+        let defs = obj
+            .entry("$defs")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(defs) = defs {
+            defs.extend(bundler.bundled);
+        }
+    }
+    Ok(root)
+}
+
+/// Where a fetched document landed in the bundle.
+#[derive(Clone)]
+struct Placement {
+    /// Its `$defs` key.
+    key: String,
+    /// `/$defs/<name>` or `/definitions/<name>` pointer prefix (escaped, as
+    /// written in refs) → `$defs` key of the hoisted definition.
+    defs: HashMap<String, String>,
+}
+
+impl Placement {
+    /// Re-root `fragment`, a JSON Pointer into the fetched document.
+    fn relocate(&self, fragment: &str) -> String {
+        for (prefix, key) in &self.defs {
+            if let Some(rest) = fragment.strip_prefix(prefix.as_str()) {
+                if rest.is_empty() || rest.starts_with('/') {
+                    return format!("/$defs/{}{rest}", crate::escape_pointer_segment(key));
+                }
+            }
+        }
+        format!(
+            "/$defs/{}{fragment}",
+            crate::escape_pointer_segment(&self.key)
+        )
+    }
+}
+
+struct Bundler<'a> {
+    resolver: &'a dyn RefResolver,
+    /// Anchors and `$id` resources of the root document.
+    embedded: HashMap<String, String>,
+    /// Document URI → its placement, `None` for the root document.
+    placements: HashMap<String, Option<Placement>>,
+    /// `$defs` keys already in use.
+    taken: HashSet<String>,
+    /// Entries to add to the root's `$defs`.
+    bundled: Vec<(String, Value)>,
+}
+
+impl Bundler<'_> {
+    /// Rewrite the `$ref`s in `node`, part of the document at `base` that is
+    /// bundled at `placement`.
+    fn rewrite(
+        &mut self,
+        node: &mut Value,
+        base: &Url,
+        placement: Option<&Placement>,
+    ) -> Result<(), ConvertError> {
+        match node {
+            Value::Object(obj) => {
+                let base = match obj.get("$id").and_then(Value::as_str) {
+                    Some(id) => base.join(id).unwrap_or_else(|_| base.clone()),
+                    None => base.clone(),
+                };
+                for (key, child) in obj.iter_mut() {
+                    match child {
+                        Value::String(target) if key == "$ref" => {
+                            if let Some(rewritten) = self.rewrite_ref(target, &base, placement)? {
+                                *target = rewritten;
+                            }
+                        }
+                        _ => self.rewrite(child, &base, placement)?,
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.rewrite(item, base, placement)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn rewrite_ref(
+        &mut self,
+        target: &str,
+        base: &Url,
+        placement: Option<&Placement>,
+    ) -> Result<Option<String>, ConvertError> {
+        if let Some(fragment) = target.strip_prefix('#') {
+            // Local pointer: re-root it if the document was bundled.
+            return Ok(match placement {
+                Some(placement) if fragment.is_empty() || fragment.starts_with('/') => {
+                    Some(format!("#{}", placement.relocate(fragment)))
+                }
+                _ => None,
+            });
+        }
+
+        if crate::anchor_utils::resolve_ref_via_anchor_map(target, base, &self.embedded).is_some() {
+            // Embedded resource: normalization resolves it in place.
+            return Ok(None);
+        }
+        let Ok(url) = base.join(target) else {
+            return Ok(None);
+        };
+        let fragment = url.fragment().unwrap_or("").to_string();
+        if !fragment.is_empty() && !fragment.starts_with('/') {
+            // Anchors in other documents cannot be addressed by pointer.
+            return Ok(None);
+        }
+        let uri = document_uri(&url);
+        let placement = match self.placements.get(&uri) {
+            Some(placement) => placement.clone(),
+            None => match self.fetch(&url, &uri)? {
+                Some(placement) => Some(placement),
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(match placement {
+            Some(placement) => format!("#{}", placement.relocate(&fragment)),
+            None => format!("#{fragment}"),
+        }))
+    }
+
+    /// Fetch the document at `uri` and queue it (and its hoisted
+    /// definitions) for the bundle. `None` if the resolver has no document.
+    fn fetch(&mut self, url: &Url, uri: &str) -> Result<Option<Placement>, ConvertError> {
+        let Some(mut document) = self.resolver.resolve(uri)? else {
+            return Ok(None);
+        };
+        let key = self.unique_key(document_key(url));
+        let mut definitions = Vec::new();
+        if let Value::Object(obj) = &mut document {
+            obj.remove("$id");
+            obj.remove("$schema");
+            for keyword in ["$defs", "definitions"] {
+                if let Some(Value::Object(defs)) = obj.remove(keyword) {
+                    definitions.extend(defs.into_iter().map(|(name, def)| (keyword, name, def)));
+                }
+            }
+        }
+        let mut placement = Placement {
+            key: key.clone(),
+            defs: HashMap::new(),
+        };
+        let mut hoisted = Vec::new();
+        for (keyword, name, def) in definitions {
+            let def_key = self.unique_key(format!("{key}_{name}"));
+            let prefix = format!("/{keyword}/{}", crate::escape_pointer_segment(&name));
+            placement.defs.insert(prefix, def_key.clone());
+            hoisted.push((def_key, def));
+        }
+        // Register before rewriting so cycles back to this document resolve.
+        self.placements
+            .insert(uri.to_string(), Some(placement.clone()));
+
+        let mut document_base = url.clone();
+        document_base.set_fragment(None);
+        self.rewrite(&mut document, &document_base, Some(&placement))?;
+        self.bundled.push((key, document));
+        for (def_key, mut def) in hoisted {
+            self.rewrite(&mut def, &document_base, Some(&placement))?;
+            self.bundled.push((def_key, def));
+        }
+        Ok(Some(placement))
+    }
+
+    /// `stem`, or `stem_2`, `stem_3`, … if already taken.
+    fn unique_key(&mut self, stem: String) -> String {
+        let mut key = stem.clone();
+        let mut n = 2usize;
+        while self.taken.contains(&key) {
+            key = format!("{stem}_{n}");
+            n += 1;
+        }
+        self.taken.insert(key.clone());
+        key
+    }
+}
+
+/// `url` without its fragment.
+fn document_uri(url: &Url) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url.to_string()
+}
+
+/// A readable `$defs` key for the document at `url`: its file name without
+/// extension.
+fn document_key(url: &Url) -> String {
+    let name = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or("");
+    let stem = name.split('.').next().unwrap_or("");
+    match stem {
+        "" => "external".to_string(),
+        _ => stem.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl PassHook for Recorder {
+        fn before_pass(&self, pass: BuiltinPass, _schema: &Value) -> Result<(), ConvertError> {
+            self.0.lock().unwrap().push(format!("before {pass:?}"));
+            Ok(())
+        }
+
+        fn after_pass(
+            &self,
+            pass: BuiltinPass,
+            _schema: &Value,
+            _codec: &Codec,
+        ) -> Result<(), ConvertError> {
+            self.0.lock().unwrap().push(format!("after {pass:?}"));
+            Ok(())
+        }
+    }
+
+    struct Veto;
+
+    impl PassHook for Veto {
+        fn before_pass(&self, pass: BuiltinPass, _schema: &Value) -> Result<(), ConvertError> {
+            match pass {
+                BuiltinPass::Dictionary => Err(ConvertError::SchemaError {
+                    path: "#".to_string(),
+                    message: "vetoed".to_string(),
+                    location: None,
+                }),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_pipeline_matches_convert() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tags": {"type": "object", "additionalProperties": {"type": "string"}}
+            }
+        });
+        let pipeline = ConvertPipeline::new().target(Target::Claude);
+        let expected = crate::convert(&schema, pipeline.options()).unwrap();
+        let actual = pipeline.convert(&schema).unwrap();
+        assert_eq!(actual.schema, expected.schema);
+        assert_eq!(
+            actual.codec.transforms.len(),
+            expected.codec.transforms.len()
+        );
+    }
+
+    #[test]
+    fn test_hooks_wrap_each_pass_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let pipeline = ConvertPipeline::new()
+            .skip_pass(BuiltinPass::Opaque)
+            .hook(Recorder(Arc::clone(&log)));
+        pipeline.convert(&json!({"type": "string"})).unwrap();
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 18, "{log:?}");
+        assert_eq!(log[0], "before Normalize");
+        assert_eq!(log[1], "after Normalize");
+        assert!(!log.iter().any(|entry| entry.ends_with(" Opaque")));
+        assert_eq!(log[17], "after ProviderCompat");
+    }
+
+    #[test]
+    fn test_skipped_pass_leaves_schema_untouched() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tags": {"type": "object", "additionalProperties": {"type": "string"}}
+            }
+        });
+        let result = ConvertPipeline::new()
+            .skip_pass(BuiltinPass::Dictionary)
+            .convert(&schema)
+            .unwrap();
+        assert_ne!(result.schema["properties"]["tags"]["type"], "array");
+    }
+
+    #[test]
+    fn test_timings_cover_each_pass_that_ran() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tags": {"type": "object", "additionalProperties": {"type": "string"}}
+            }
+        });
+        let result = ConvertPipeline::new()
+            .skip_pass(BuiltinPass::Opaque)
+            .convert(&schema)
+            .unwrap();
+        let passes: Vec<_> = result.timings.iter().map(|t| t.pass).collect();
+        let expected: Vec<_> = BuiltinPass::ALL
+            .into_iter()
+            .filter(|p| *p != BuiltinPass::Opaque)
+            .collect();
+        assert_eq!(passes, expected);
+
+        let dictionary = &result.timings[3];
+        assert_eq!(dictionary.pass, BuiltinPass::Dictionary);
+        assert_eq!(dictionary.nodes_visited, 6);
+        let compat = result.timings.last().unwrap();
+        assert_eq!(compat.pass, BuiltinPass::ProviderCompat);
+        assert_eq!(compat.nodes_visited, 0);
+        assert_eq!(dictionary.transforms_emitted, 1);
+        assert_eq!(
+            result
+                .timings
+                .iter()
+                .map(|t| t.transforms_emitted)
+                .sum::<usize>(),
+            result.codec.transforms.len()
+        );
+    }
+
+    #[test]
+    fn test_hook_error_aborts_conversion() {
+        let err = ConvertPipeline::new()
+            .hook(Veto)
+            .convert(&json!({"type": "string"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("vetoed"), "{err}");
+    }
+
+    #[test]
+    fn test_resolver_inlines_external_documents() {
+        let fetched = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&fetched);
+        let pipeline = ConvertPipeline::new().resolver(move |uri: &str| {
+            log.lock().unwrap().push(uri.to_string());
+            match uri {
+                "https://example.com/schemas/pet.json" => Some(json!({
+                    "$id": "https://example.com/schemas/pet.json",
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "owner": {"$ref": "owner.json"},
+                        "kind": {"$ref": "#/$defs/Kind"}
+                    },
+                    "required": ["name", "owner", "kind"],
+                    "$defs": {"Kind": {"enum": ["cat", "dog"]}}
+                })),
+                "https://example.com/schemas/owner.json" => Some(json!({
+                    "type": "object",
+                    "properties": {"email": {"type": "string"}},
+                    "required": ["email"]
+                })),
+                _ => None,
+            }
+        });
+        let schema = json!({
+            "$id": "https://example.com/schemas/root.json",
+            "type": "object",
+            "properties": {
+                "pet": {"$ref": "pet.json"},
+                "kind": {"$ref": "pet.json#/$defs/Kind"},
+                "other": {"$ref": "https://elsewhere.example/x.json"}
+            },
+            "required": ["pet", "kind"]
+        });
+
+        let bundled = bundle_external_refs(&schema, pipeline.resolver.as_deref().unwrap()).unwrap();
+        assert_eq!(bundled["properties"]["pet"]["$ref"], "#/$defs/pet");
+        assert_eq!(bundled["properties"]["kind"]["$ref"], "#/$defs/pet_Kind");
+        assert_eq!(
+            bundled["properties"]["other"]["$ref"],
+            "https://elsewhere.example/x.json"
+        );
+        let pet = &bundled["$defs"]["pet"];
+        assert!(pet.get("$id").is_none());
+        assert!(pet.get("$defs").is_none());
+        assert_eq!(pet["properties"]["owner"]["$ref"], "#/$defs/owner");
+        assert_eq!(pet["properties"]["kind"]["$ref"], "#/$defs/pet_Kind");
+        assert_eq!(bundled["$defs"]["pet_Kind"]["enum"], json!(["cat", "dog"]));
+        // Each document is fetched once.
+        assert_eq!(fetched.lock().unwrap().len(), 3);
+
+        let result = pipeline.convert(&schema).unwrap();
+        let pet = &result.schema["properties"]["pet"];
+        assert_eq!(
+            pet["properties"]["owner"]["properties"]["email"]["type"],
+            "string"
+        );
+        assert_eq!(pet["properties"]["kind"]["enum"], json!(["cat", "dog"]));
+    }
+
+    #[test]
+    fn test_resolver_skips_embedded_resources() {
+        let fetched = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&fetched);
+        let pipeline = ConvertPipeline::new().resolver(move |uri: &str| {
+            log.lock().unwrap().push(uri.to_string());
+            None
+        });
+        let schema = json!({
+            "$id": "https://example.com/schemas/root.json",
+            "type": "object",
+            "properties": { "home": { "$ref": "address.json" } },
+            "required": ["home"],
+            "$defs": {
+                "address": {
+                    "$id": "address.json",
+                    "type": "object",
+                    "properties": { "zip": { "type": "string" } },
+                    "required": ["zip"]
+                }
+            }
+        });
+
+        let result = pipeline.convert(&schema).unwrap();
+        assert!(fetched.lock().unwrap().is_empty());
+        assert_eq!(
+            result.schema["properties"]["home"]["properties"]["zip"]["type"],
+            "string"
+        );
+    }
+}
@@ -0,0 +1,737 @@
+//! Schema projection onto a set of data paths.
+//!
+//! For token-constrained extraction, [`project_schema`] narrows a schema to
+//! just the output fields a task asks for, e.g. `/user/name` and
+//! `/items/*/price`, and converts the result. Everything off those paths is
+//! dropped from the schema; its codec records each dropped property so that
+//! rehydration puts the projected answer back into the original envelope,
+//! with `null` wherever a field was left out. The fields dropped from an
+//! `anyOf` / `oneOf` branch are only restored into objects taking that
+//! branch, and branches that project to the same schema are merged.
+//!
+//! Data paths are JSON Pointers (RFC 6901) into the *data*, not the schema:
+//! each segment names an object property, and `*` stands for every element
+//! of an array. A path selects its whole subtree, so `/user` keeps every
+//! field of `user`. Local `$ref`s on the way to a selected field are inlined;
+//! refs inside a selected subtree are kept, along with the `$defs` /
+//! `definitions` entries they reach.
+//!
+//! ```rust,no_run
+//! use json_schema_llm_core::{project_schema, rehydrate, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "user": {
+//!             "type": "object",
+//!             "properties": { "name": { "type": "string" }, "bio": { "type": "string" } }
+//!         },
+//!         "notes": { "type": "string" }
+//!     }
+//! });
+//!
+//! let result = project_schema(&schema, &["/user/name"], &ConvertOptions::default()).unwrap();
+//! let answer = json!({ "user": { "name": "Ada" } });
+//! let restored = rehydrate(&answer, &result.codec, &schema).unwrap();
+//! assert_eq!(restored.data, json!({ "user": { "name": "Ada", "bio": null }, "notes": null }));
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{Map, Value};
+
+use crate::codec::{ProjectedVariant, Transform};
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+use crate::schema_utils::{build_path, resolve_pointer, unescape_pointer_segment};
+use crate::ConvertResult;
+
+/// `Transform::FilteredProperty` keyword recorded for projected-out fields.
+const PROJECTION_KEYWORD: &str = "projection";
+
+/// Data path segment matching every array element.
+const WILDCARD: &str = "*";
+
+/// Hops through a chain of `$ref`s before giving up on a path.
+const MAX_REF_HOPS: usize = 64;
+
+/// Keywords that can constrain or mention properties outside the projection,
+/// removed from every object the projection narrows.
+const OBJECT_SHAPE_KEYWORDS: &[&str] = &[
+    "additionalProperties",
+    "dependentRequired",
+    "dependentSchemas",
+    "else",
+    "if",
+    "maxProperties",
+    "minProperties",
+    "not",
+    "patternProperties",
+    "propertyNames",
+    "then",
+    "unevaluatedProperties",
+];
+
+/// Keywords constraining array elements as a whole, removed from every array
+/// the projection narrows.
+const ARRAY_SHAPE_KEYWORDS: &[&str] =
+    &["contains", "maxContains", "minContains", "unevaluatedItems"];
+
+/// Convert only the parts of `schema` covering `data_paths`.
+///
+/// The projected schema keeps each path's fields, the objects and arrays
+/// leading to them, and their annotations; every other property is removed.
+/// The returned codec rehydrates an answer to the projected schema into the
+/// shape of the original, filling each removed property with `null`.
+///
+/// An empty path (`""` or `"/"`) selects the whole document.
+///
+/// # Errors
+///
+/// [`ConvertError::SchemaError`] if `data_paths` is empty or a path names a
+/// field the schema does not declare; otherwise whatever [`convert`] returns
+/// for the projected schema.
+///
+/// [`convert`]: crate::convert
+pub fn project_schema(
+    schema: &Value,
+    data_paths: &[&str],
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    let (projected, transforms) = project(schema, data_paths)?;
+    let mut result = crate::convert(&projected, options)?;
+    // Rehydration runs transforms last-to-first; putting the projection first
+    // restores the envelope only after the projected fields are rehydrated.
+    result.codec.transforms.splice(0..0, transforms);
+    Ok(result)
+}
+
+/// The projected schema and its `FilteredProperty` transforms, before
+/// conversion.
+fn project(schema: &Value, data_paths: &[&str]) -> Result<(Value, Vec<Transform>), ConvertError> {
+    if data_paths.is_empty() {
+        return Err(schema_error("#", "no data paths to project onto"));
+    }
+    let mut selection = Selection::default();
+    for path in data_paths {
+        selection.insert(path)?;
+    }
+
+    let mut ctx = ProjectCtx {
+        root: schema,
+        transforms: Vec::new(),
+    };
+    let mut projected = ctx.project_node(schema, &selection, "#", "")?;
+    if !selection.whole {
+        retain_reachable_defs(schema, &mut projected);
+    }
+    Ok((projected, ctx.transforms))
+}
+
+// ---------------------------------------------------------------------------
+// Data path selection
+// ---------------------------------------------------------------------------
+
+/// The data paths to keep, as a tree of segments.
+#[derive(Debug, Default)]
+struct Selection {
+    /// A path ends here, so the whole subtree is kept.
+    whole: bool,
+    children: BTreeMap<String, Selection>,
+}
+
+impl Selection {
+    fn insert(&mut self, path: &str) -> Result<(), ConvertError> {
+        let trimmed = path.strip_prefix('#').unwrap_or(path);
+        let mut node = self;
+        if !trimmed.is_empty() && trimmed != "/" {
+            let rest = trimmed.strip_prefix('/').ok_or_else(|| {
+                schema_error(path, "data path must be a JSON Pointer starting with `/`")
+            })?;
+            for segment in rest.split('/') {
+                node = node
+                    .children
+                    .entry(unescape_pointer_segment(segment).into_owned())
+                    .or_default();
+            }
+        }
+        node.whole = true;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Projection walk
+// ---------------------------------------------------------------------------
+
+struct ProjectCtx<'a> {
+    root: &'a Value,
+    transforms: Vec<Transform>,
+}
+
+impl ProjectCtx<'_> {
+    /// Project `node` (at `path` in the projected schema) onto `selection`,
+    /// reached by `data_path`. Errors if a selected field is declared
+    /// neither here nor in a composition branch.
+    fn project_node(
+        &mut self,
+        node: &Value,
+        selection: &Selection,
+        path: &str,
+        data_path: &str,
+    ) -> Result<Value, ConvertError> {
+        if selection.whole {
+            return Ok(node.clone());
+        }
+        let mut found = BTreeSet::new();
+        let projected = self.project_fields(node, selection, path, data_path, &mut found)?;
+        if let Some(missing) = selection.children.keys().find(|k| !found.contains(*k)) {
+            let missing_path = format!("{}/{}", data_path, missing);
+            return Err(schema_error(
+                path,
+                &format!("data path `{}` is not declared by the schema", missing_path),
+            ));
+        }
+        Ok(projected)
+    }
+
+    /// Narrow `node` and its `allOf`/`anyOf`/`oneOf` branches to the fields
+    /// in `selection`, adding each one declared somewhere to `found`.
+    fn project_fields(
+        &mut self,
+        node: &Value,
+        selection: &Selection,
+        path: &str,
+        data_path: &str,
+        found: &mut BTreeSet<String>,
+    ) -> Result<Value, ConvertError> {
+        let Some(mut obj) = inline_refs(self.root, node, path)? else {
+            return Ok(node.clone());
+        };
+
+        if selection.children.contains_key(WILDCARD) {
+            self.project_items(&mut obj, selection, path, data_path, found)?;
+        }
+        if obj.contains_key("properties") {
+            self.project_properties(&mut obj, selection, path, data_path, found)?;
+        }
+
+        for keyword in ["allOf", "anyOf", "oneOf"] {
+            let Some(Value::Array(branches)) = obj.remove(keyword) else {
+                continue;
+            };
+            let mut projected = Vec::with_capacity(branches.len());
+            let mut variants = Vec::new();
+            for (i, branch) in branches.iter().enumerate() {
+                let branch_path = build_path(path, &[keyword, &i.to_string()]);
+                let first = self.transforms.len();
+                let narrowed =
+                    self.project_fields(branch, selection, &branch_path, data_path, found)?;
+                if keyword == "allOf" {
+                    projected.push(narrowed);
+                    continue;
+                }
+                // A union branch's transforms only apply to objects taking
+                // it, and one projected like an earlier branch never does.
+                let mut transforms = self.transforms.split_off(first);
+                if projected.contains(&narrowed) {
+                    continue;
+                }
+                rebase(&mut transforms, &branch_path);
+                variants.push(projected_variant_of(&narrowed, transforms));
+                projected.push(narrowed);
+            }
+            if variants.iter().any(|v| !v.transforms.is_empty()) {
+                self.transforms.push(Transform::ProjectedVariants {
+                    path: path.to_string(),
+                    variants,
+                });
+            }
+            obj.insert(keyword.to_string(), Value::Array(projected));
+        }
+        Ok(Value::Object(obj))
+    }
+
+    /// Keep only the selected `properties` of `obj`, recording the rest.
+    fn project_properties(
+        &mut self,
+        obj: &mut Map<String, Value>,
+        selection: &Selection,
+        path: &str,
+        data_path: &str,
+        found: &mut BTreeSet<String>,
+    ) -> Result<(), ConvertError> {
+        let Some(Value::Object(properties)) = obj.remove("properties") else {
+            return Ok(());
+        };
+        let mut kept = Map::new();
+        for (name, prop) in properties {
+            match selection.children.get(&name) {
+                Some(child) => {
+                    let prop_path = build_path(path, &["properties", &name]);
+                    let child_data_path = format!("{}/{}", data_path, name);
+                    let projected =
+                        self.project_node(&prop, child, &prop_path, &child_data_path)?;
+                    found.insert(name.clone());
+                    kept.insert(name, projected);
+                }
+                None => self.transforms.push(Transform::FilteredProperty {
+                    path: path.to_string(),
+                    property: name,
+                    keyword: PROJECTION_KEYWORD.to_string(),
+                    placeholder: Some(Value::Null),
+                }),
+            }
+        }
+        if let Some(Value::Array(required)) = obj.get_mut("required") {
+            required.retain(|r| r.as_str().is_some_and(|r| kept.contains_key(r)));
+        }
+        obj.insert("properties".to_string(), Value::Object(kept));
+        for keyword in OBJECT_SHAPE_KEYWORDS {
+            obj.remove(*keyword);
+        }
+        Ok(())
+    }
+
+    /// Project `items` and `prefixItems` of `obj` onto the `*` selection.
+    fn project_items(
+        &mut self,
+        obj: &mut Map<String, Value>,
+        selection: &Selection,
+        path: &str,
+        data_path: &str,
+        found: &mut BTreeSet<String>,
+    ) -> Result<(), ConvertError> {
+        let element = &selection.children[WILDCARD];
+        let element_data_path = format!("{}/{}", data_path, WILDCARD);
+        let mut declared = false;
+        if let Some(items) = obj.get("items").filter(|i| i.is_object()).cloned() {
+            let items_path = build_path(path, &["items"]);
+            let projected = self.project_node(&items, element, &items_path, &element_data_path)?;
+            obj.insert("items".to_string(), projected);
+            declared = true;
+        }
+        if let Some(Value::Array(prefix)) = obj.get("prefixItems").cloned() {
+            let mut projected = Vec::with_capacity(prefix.len());
+            for (i, item) in prefix.iter().enumerate() {
+                let item_path = build_path(path, &["prefixItems", &i.to_string()]);
+                projected.push(self.project_node(item, element, &item_path, &element_data_path)?);
+            }
+            obj.insert("prefixItems".to_string(), Value::Array(projected));
+            declared = true;
+        }
+        if declared {
+            found.insert(WILDCARD.to_string());
+            for keyword in ARRAY_SHAPE_KEYWORDS {
+                obj.remove(*keyword);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Make the paths of a union branch's `transforms`, recorded under
+/// `branch_path`, relative to the branch.
+fn rebase(transforms: &mut [Transform], branch_path: &str) {
+    for transform in transforms {
+        if let Transform::FilteredProperty { path, .. }
+        | Transform::ProjectedVariants { path, .. } = transform
+        {
+            *path = format!("#{}", &path[branch_path.len()..]);
+        }
+    }
+}
+
+/// The [`ProjectedVariant`] for the projected union branch `branch`.
+fn projected_variant_of(branch: &Value, transforms: Vec<Transform>) -> ProjectedVariant {
+    let mut properties = BTreeSet::new();
+    let mut required = BTreeSet::new();
+    collect_declared(branch, true, &mut properties, &mut required);
+    ProjectedVariant {
+        properties: properties.into_iter().collect(),
+        required: required.into_iter().collect(),
+        transforms,
+    }
+}
+
+/// Add the properties `node` and its composition branches declare to
+/// `properties`, and, if `requires`, those every instance has to `required`.
+fn collect_declared(
+    node: &Value,
+    requires: bool,
+    properties: &mut BTreeSet<String>,
+    required: &mut BTreeSet<String>,
+) {
+    if let Some(props) = node.get("properties").and_then(Value::as_object) {
+        properties.extend(props.keys().cloned());
+    }
+    if requires {
+        if let Some(names) = node.get("required").and_then(Value::as_array) {
+            required.extend(names.iter().filter_map(Value::as_str).map(String::from));
+        }
+    }
+    for keyword in ["allOf", "anyOf", "oneOf"] {
+        for branch in node
+            .get(keyword)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            collect_declared(branch, requires && keyword == "allOf", properties, required);
+        }
+    }
+}
+
+/// The first of `variants` an object answering the projected schema fits.
+pub(crate) fn projected_variant<'a>(
+    variants: &'a [ProjectedVariant],
+    obj: &Map<String, Value>,
+) -> Option<&'a ProjectedVariant> {
+    first_fit(variants, obj, |variant| {
+        variant.properties.iter().map(String::as_str).collect()
+    })
+}
+
+/// The first of `variants` an object of the original schema fits, counting
+/// the properties each variant removed as its own.
+pub(crate) fn original_variant<'a>(
+    variants: &'a [ProjectedVariant],
+    obj: &Map<String, Value>,
+) -> Option<&'a ProjectedVariant> {
+    first_fit(variants, obj, |variant| {
+        let removed = variant.transforms.iter().filter_map(|t| match t {
+            Transform::FilteredProperty { path, property, .. } if path == "#" => {
+                Some(property.as_str())
+            }
+            _ => None,
+        });
+        variant
+            .properties
+            .iter()
+            .map(String::as_str)
+            .chain(removed)
+            .collect()
+    })
+}
+
+/// The first variant that declares every key of `obj` some variant
+/// declares, and whose required properties `obj` has. Keys no variant
+/// declares belong to the object around the union and are ignored.
+fn first_fit<'a>(
+    variants: &'a [ProjectedVariant],
+    obj: &Map<String, Value>,
+    declared: impl Fn(&'a ProjectedVariant) -> BTreeSet<&'a str>,
+) -> Option<&'a ProjectedVariant> {
+    let declared: Vec<BTreeSet<&str>> = variants.iter().map(declared).collect();
+    let any: BTreeSet<&str> = declared.iter().flatten().copied().collect();
+    variants
+        .iter()
+        .zip(&declared)
+        .find(|(variant, names)| {
+            variant.required.iter().all(|name| obj.contains_key(name))
+                && obj
+                    .keys()
+                    .all(|key| names.contains(key.as_str()) || !any.contains(key.as_str()))
+        })
+        .map(|(variant, _)| variant)
+}
+
+/// `node` as an object with its local `$ref` chain inlined (siblings win
+/// over the target), resolved against `root`; `None` for boolean schemas.
+pub(crate) fn inline_refs(
+    root: &Value,
+    node: &Value,
+    path: &str,
+) -> Result<Option<Map<String, Value>>, ConvertError> {
+    let Some(obj) = node.as_object() else {
+        return Ok(None);
+    };
+    let mut obj = obj.clone();
+    for _ in 0..MAX_REF_HOPS {
+        let Some(Value::String(reference)) = obj.remove("$ref") else {
+            return Ok(Some(obj));
+        };
+        let Some(Value::Object(target)) = resolve_pointer(root, &reference) else {
+            return Err(schema_error(
+                path,
+                &format!("cannot follow unresolvable $ref `{}`", reference),
+            ));
+        };
+        let mut merged = target.clone();
+        merged.extend(obj);
+        obj = merged;
+    }
+    Err(schema_error(path, "cannot follow a $ref cycle"))
+}
+
+// ---------------------------------------------------------------------------
+// Definitions
+// ---------------------------------------------------------------------------
+
+/// Trim the root `$defs` / `definitions` of `projected` to the entries its
+/// remaining `$ref`s reach. All are kept if any ref is not a plain pointer
+/// into them (e.g. an `$anchor`), since its target can't be told apart.
+pub(crate) fn retain_reachable_defs(root: &Value, projected: &mut Value) {
+    let Some(obj) = projected.as_object_mut() else {
+        return;
+    };
+    let defs: Vec<(String, Value)> = ["$defs", "definitions"]
+        .into_iter()
+        .filter_map(|k| obj.remove(k).map(|v| (k.to_string(), v)))
+        .collect();
+    if defs.is_empty() {
+        return;
+    }
+
+    let mut pending = Vec::new();
+    collect_refs(&Value::Object(obj.clone()), &mut pending);
+    let mut reached: BTreeSet<(String, String)> = BTreeSet::new();
+    let mut keep_all = false;
+    while let Some(reference) = pending.pop() {
+        let Some(entry) = def_entry(&reference) else {
+            keep_all = true;
+            break;
+        };
+        if reached.insert(entry.clone()) {
+            if let Some(target) = resolve_pointer(root, &reference) {
+                collect_refs(target, &mut pending);
+            }
+        }
+    }
+
+    for (keyword, value) in defs {
+        let Value::Object(entries) = value else {
+            continue;
+        };
+        let kept: Map<String, Value> = entries
+            .into_iter()
+            .filter(|(name, _)| keep_all || reached.contains(&(keyword.clone(), name.clone())))
+            .collect();
+        if !kept.is_empty() {
+            obj.insert(keyword, Value::Object(kept));
+        }
+    }
+}
+
+/// The root `$defs` / `definitions` entry `reference` points into.
+fn def_entry(reference: &str) -> Option<(String, String)> {
+    let rest = reference.strip_prefix("#/")?;
+    let mut segments = rest.split('/');
+    let keyword = segments.next()?;
+    if keyword != "$defs" && keyword != "definitions" {
+        return None;
+    }
+    let name = unescape_pointer_segment(segments.next()?).into_owned();
+    Some((keyword.to_string(), name))
+}
+
+fn collect_refs(node: &Value, out: &mut Vec<String>) {
+    match node {
+        Value::Object(obj) => {
+            for (key, value) in obj {
+                match (key.as_str(), value) {
+                    ("$ref", Value::String(reference)) => out.push(reference.clone()),
+                    _ => collect_refs(value, out),
+                }
+            }
+        }
+        Value::Array(arr) => arr.iter().for_each(|v| collect_refs(v, out)),
+        _ => {}
+    }
+}
+
+fn schema_error(path: &str, message: &str) -> ConvertError {
+    ConvertError::SchemaError {
+        path: path.to_string(),
+        message: message.to_string(),
+        location: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn order_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "user": { "$ref": "#/$defs/User" },
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "sku": { "type": "string" },
+                            "price": { "$ref": "#/$defs/Money" }
+                        },
+                        "required": ["sku", "price"]
+                    }
+                },
+                "notes": { "type": "string" }
+            },
+            "required": ["user", "items", "notes"],
+            "$defs": {
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "email": { "type": "string" }
+                    },
+                    "required": ["name", "email"]
+                },
+                "Money": { "type": "number", "minimum": 0 },
+                "Unused": { "type": "string" }
+            }
+        })
+    }
+
+    #[test]
+    fn test_project_keeps_only_selected_fields() {
+        let (projected, transforms) =
+            project(&order_schema(), &["/user/name", "/items/*/price"]).unwrap();
+        assert_eq!(
+            projected,
+            json!({
+                "type": "object",
+                "properties": {
+                    "user": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"]
+                    },
+                    "items": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": { "price": { "$ref": "#/$defs/Money" } },
+                            "required": ["price"]
+                        }
+                    }
+                },
+                "required": ["user", "items"],
+                "$defs": { "Money": { "type": "number", "minimum": 0 } }
+            })
+        );
+        let dropped: Vec<(&str, &str)> = transforms
+            .iter()
+            .filter_map(|t| match t {
+                Transform::FilteredProperty { path, property, .. } => {
+                    Some((path.as_str(), property.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            dropped,
+            vec![
+                ("#/properties/items/items", "sku"),
+                ("#", "notes"),
+                ("#/properties/user", "email"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_unknown_path_errors() {
+        let err = project(&order_schema(), &["/user/nmae"]).unwrap_err();
+        assert!(err.to_string().contains("/user/nmae"), "{}", err);
+        assert!(project(&order_schema(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_project_round_trip_fills_nulls() {
+        let schema = order_schema();
+        let result = project_schema(
+            &schema,
+            &["/user/name", "/items/*/price"],
+            &ConvertOptions::default(),
+        )
+        .unwrap();
+        let answer = json!({ "user": { "name": "Ada" }, "items": [{ "price": 3.5 }] });
+        let restored = crate::rehydrate(&answer, &result.codec, &schema).unwrap();
+        assert_eq!(
+            restored.data,
+            json!({
+                "user": { "name": "Ada", "email": null },
+                "items": [{ "sku": null, "price": 3.5 }],
+                "notes": null
+            })
+        );
+    }
+
+    fn pet_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pet": {
+                    "anyOf": [
+                        {
+                            "type": "object",
+                            "properties": {
+                                "kind": { "const": "dog" },
+                                "name": { "type": "string" },
+                                "bark": { "type": "boolean" }
+                            },
+                            "required": ["kind", "name"]
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "kind": { "const": "cat" },
+                                "name": { "type": "string" },
+                                "lives": { "type": "integer" }
+                            },
+                            "required": ["kind", "name"]
+                        }
+                    ]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_project_merges_identical_branches() {
+        let (projected, _) = project(&pet_schema(), &["/pet/name"]).unwrap();
+        assert_eq!(
+            projected["properties"]["pet"],
+            json!({
+                "anyOf": [{
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } },
+                    "required": ["name"]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_project_restores_only_the_matching_branch() {
+        let schema = pet_schema();
+        let options = ConvertOptions::default();
+
+        let result = project_schema(&schema, &["/pet/name"], &options).unwrap();
+        let answer = json!({ "pet": { "name": "Rex" } });
+        let restored = crate::rehydrate(&answer, &result.codec, &schema).unwrap();
+        assert_eq!(
+            restored.data,
+            json!({ "pet": { "name": "Rex", "kind": null, "bark": null } })
+        );
+
+        let result = project_schema(&schema, &["/pet/name", "/pet/lives"], &options).unwrap();
+        let answer = json!({ "pet": { "name": "Tom", "lives": 9 } });
+        let restored = crate::rehydrate(&answer, &result.codec, &schema).unwrap();
+        assert_eq!(
+            restored.data,
+            json!({ "pet": { "name": "Tom", "lives": 9, "kind": null } })
+        );
+        let dehydrated = crate::dehydrate(
+            &json!({ "pet": { "kind": "cat", "name": "Tom", "lives": 9 } }),
+            &result.codec,
+        )
+        .unwrap();
+        assert_eq!(dehydrated, answer);
+    }
+}
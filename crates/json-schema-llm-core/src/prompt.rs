@@ -0,0 +1,525 @@
+//! System-prompt scaffolds derived from converted schemas.
+//!
+//! Teams usually restate the output schema in their system prompt: what each
+//! field means, which ones take a fixed set of values, which strings must
+//! hold serialized JSON. Written by hand, that text drifts from the schema.
+//! [`generate_prompt_scaffold`] renders it from the converted schema instead,
+//! as a Markdown block to paste into (or template into) a prompt:
+//!
+//! - one line per field, by dotted path (`items[].price`), with its type,
+//!   whether it is required or nullable, its description and its enum values;
+//! - a reminder for each convention the schema relies on (`null` for unknown
+//!   values, JSON-encoded strings, exact enum spelling).
+//!
+//! JSON-string fields are only recognized with the conversion's codec
+//! ([`PromptOptions::codec`]).
+//!
+//! ```rust
+//! use json_schema_llm_core::prompt::{generate_prompt_scaffold, PromptOptions};
+//! use json_schema_llm_core::{convert, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "status": { "enum": ["open", "closed"], "description": "Ticket state." }
+//!     },
+//!     "required": ["status"]
+//! });
+//! let result = convert(&schema, &ConvertOptions::default()).unwrap();
+//!
+//! let prompt = generate_prompt_scaffold(&result.schema, &PromptOptions::default());
+//! assert!(prompt.contains("- `status` (string, required): Ticket state. One of: \"open\", \"closed\"."));
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::codec::Codec;
+use crate::path_map::PathMap;
+use crate::schema_utils::{build_path, resolve_pointer};
+
+/// Nesting depth past which fields are no longer listed.
+const MAX_DEPTH: usize = 32;
+
+/// Options for [`generate_prompt_scaffold`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(rename_all = "kebab-case", default)]
+pub struct PromptOptions {
+    /// Codec of the conversion that produced the schema. Needed to mark
+    /// fields that hold JSON-encoded strings.
+    pub codec: Option<Codec>,
+    /// Enum values listed per field before the rest are summarized.
+    pub max_enum_values: usize,
+}
+
+impl Default for PromptOptions {
+    fn default() -> Self {
+        Self {
+            codec: None,
+            max_enum_values: 20,
+        }
+    }
+}
+
+/// Render a system-prompt block describing `converted_schema` field by
+/// field.
+pub fn generate_prompt_scaffold(converted_schema: &Value, options: &PromptOptions) -> String {
+    let json_strings = options
+        .codec
+        .as_ref()
+        .map(|codec| {
+            PathMap::from_codec(codec, converted_schema)
+                .entries()
+                .iter()
+                .filter(|e| {
+                    matches!(
+                        e.transform.as_str(),
+                        "json_string_parse" | "recursive_inflate"
+                    )
+                })
+                .map(|e| e.converted.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut scaffold = Scaffold {
+        root: converted_schema,
+        json_strings,
+        max_enum_values: options.max_enum_values,
+        fields: Vec::new(),
+        listed: HashSet::new(),
+        all_required: true,
+        nullable: false,
+        json_string: false,
+        enums: false,
+    };
+    scaffold.children(converted_schema, "#", "", 0, &mut Vec::new());
+    scaffold.render(converted_schema)
+}
+
+/// Fields and conventions collected from a walk of the converted schema.
+struct Scaffold<'a> {
+    root: &'a Value,
+    /// Converted-schema paths of fields holding JSON-encoded strings.
+    json_strings: HashSet<String>,
+    max_enum_values: usize,
+    fields: Vec<String>,
+    /// Labels already listed, so union branches sharing a field list it once.
+    listed: HashSet<String>,
+    all_required: bool,
+    nullable: bool,
+    json_string: bool,
+    enums: bool,
+}
+
+impl<'a> Scaffold<'a> {
+    /// List the fields under `node` (at `pointer`), labelled below `label`.
+    /// `refs` holds the `$ref`s being expanded, to stop at recursion.
+    fn children(
+        &mut self,
+        node: &'a Value,
+        pointer: &str,
+        label: &str,
+        depth: usize,
+        refs: &mut Vec<String>,
+    ) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let Some((node, pointer, expanded)) = self.deref(node, pointer, refs) else {
+            return;
+        };
+
+        if let Some(properties) = node.get("properties").and_then(Value::as_object) {
+            let required: HashSet<&str> = node
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|r| r.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            for (name, prop) in properties {
+                let prop_label = if label.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", label, name)
+                };
+                let prop_pointer = build_path(&pointer, &["properties", name]);
+                self.field(
+                    prop,
+                    &prop_pointer,
+                    &prop_label,
+                    required.contains(name.as_str()),
+                    depth,
+                    refs,
+                );
+            }
+        }
+        if let Some(items) = node.get("items").filter(|i| i.is_object()) {
+            let items_pointer = build_path(&pointer, &["items"]);
+            self.children(
+                items,
+                &items_pointer,
+                &format!("{}[]", label),
+                depth + 1,
+                refs,
+            );
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(branches) = node.get(keyword).and_then(Value::as_array) {
+                for (i, branch) in branches.iter().enumerate() {
+                    let branch_pointer = build_path(&pointer, &[keyword, &i.to_string()]);
+                    self.children(branch, &branch_pointer, label, depth + 1, refs);
+                }
+            }
+        }
+        if expanded {
+            refs.pop();
+        }
+    }
+
+    /// Add the line for one property, then its own fields.
+    fn field(
+        &mut self,
+        node: &'a Value,
+        pointer: &str,
+        label: &str,
+        required: bool,
+        depth: usize,
+        refs: &mut Vec<String>,
+    ) {
+        if !self.listed.insert(label.to_string()) {
+            self.children(node, pointer, label, depth + 1, refs);
+            return;
+        }
+        let resolved = self
+            .deref(node, pointer, &mut Vec::new())
+            .map_or(node, |(n, _, _)| n);
+        let (types, nullable) = type_summary(resolved, self.root);
+        let json_string = self.json_strings.contains(pointer)
+            || non_null_branches(resolved).iter().any(|(i, keyword)| {
+                self.json_strings
+                    .contains(&build_path(pointer, &[keyword, &i.to_string()]))
+            });
+
+        let mut traits = vec![if json_string {
+            "JSON string".to_string()
+        } else {
+            types
+        }];
+        if nullable {
+            traits.push("nullable".to_string());
+            self.nullable = true;
+        }
+        traits.push(if required { "required" } else { "optional" }.to_string());
+        self.all_required &= required;
+        self.json_string |= json_string;
+
+        let mut line = format!("- `{}` ({})", label, traits.join(", "));
+        let mut notes = Vec::new();
+        if let Some(description) = summary(resolved) {
+            notes.push(description);
+        }
+        if let Some(values) = enum_values(resolved, self.root) {
+            self.enums = true;
+            notes.push(self.enum_note(&values));
+        }
+        if !notes.is_empty() {
+            let _ = write!(line, ": {}", notes.join(" "));
+        }
+        self.fields.push(line);
+
+        if !json_string {
+            self.children(node, pointer, label, depth + 1, refs);
+        }
+    }
+
+    /// `node` with its `$ref` followed, its pointer, and whether the ref was
+    /// pushed onto `refs`. `None` for a ref already being expanded.
+    fn deref(
+        &self,
+        node: &'a Value,
+        pointer: &str,
+        refs: &mut Vec<String>,
+    ) -> Option<(&'a Value, String, bool)> {
+        let Some(reference) = node.get("$ref").and_then(Value::as_str) else {
+            return Some((node, pointer.to_string(), false));
+        };
+        if refs.iter().any(|r| r == reference) {
+            return None;
+        }
+        let target = resolve_pointer(self.root, reference)?;
+        refs.push(reference.to_string());
+        Some((target, reference.to_string(), true))
+    }
+
+    fn enum_note(&self, values: &[Value]) -> String {
+        let shown: Vec<String> = values
+            .iter()
+            .take(self.max_enum_values)
+            .map(Value::to_string)
+            .collect();
+        let rest = values.len().saturating_sub(self.max_enum_values);
+        if rest > 0 {
+            format!("One of: {}, … ({} more).", shown.join(", "), rest)
+        } else {
+            format!("One of: {}.", shown.join(", "))
+        }
+    }
+
+    fn render(&self, schema: &Value) -> String {
+        let mut out = String::from("## Response format\n\n");
+        if let Some(intro) = summary(schema) {
+            let _ = writeln!(out, "{}\n", intro);
+        }
+        out.push_str("Respond with a single JSON value that matches the response schema.");
+        if self.all_required && !self.fields.is_empty() {
+            out.push_str(" Include every field listed below.");
+        }
+        out.push('\n');
+
+        if !self.fields.is_empty() {
+            out.push_str("\n### Fields\n\n");
+            for field in &self.fields {
+                out.push_str(field);
+                out.push('\n');
+            }
+        }
+
+        let mut reminders = Vec::new();
+        if self.nullable {
+            reminders.push("Use `null` for a nullable field whose value is unknown or absent; do not leave the field out.");
+        }
+        if self.json_string {
+            reminders.push("A JSON string field holds serialized JSON, not plain text: for example \"{\\\"key\\\": \\\"value\\\"}\". It must parse with JSON.parse().");
+        }
+        if self.enums {
+            reminders.push("A field with a list of values takes exactly one of them, with the same spelling and case.");
+        }
+        if !reminders.is_empty() {
+            out.push_str("\n### Reminders\n\n");
+            for reminder in reminders {
+                let _ = writeln!(out, "- {}", reminder);
+            }
+        }
+        out
+    }
+}
+
+/// The types `node` takes, e.g. `string` or `object | string`, and whether
+/// it also admits `null`.
+fn type_summary(node: &Value, root: &Value) -> (String, bool) {
+    let mut types: Vec<String> = Vec::new();
+    let mut nullable = false;
+    let mut add = |t: &str, types: &mut Vec<String>| {
+        if t == "null" {
+            nullable = true;
+        } else if !types.iter().any(|x| x == t) {
+            types.push(t.to_string());
+        }
+    };
+    collect_types(node, root, 0, &mut |t| add(t, &mut types));
+    let summary = if types.is_empty() {
+        "any".to_string()
+    } else {
+        types.join(" | ")
+    };
+    (summary, nullable)
+}
+
+fn collect_types(node: &Value, root: &Value, depth: usize, add: &mut dyn FnMut(&str)) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    if let Some(target) = node
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| resolve_pointer(root, r))
+    {
+        return collect_types(target, root, depth + 1, add);
+    }
+    match node.get("type") {
+        Some(Value::String(t)) => add(t),
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).for_each(&mut *add),
+        _ => {}
+    }
+    for value in node
+        .get("enum")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .chain(node.get("const"))
+    {
+        add(json_type(value));
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        for branch in node
+            .get(keyword)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            collect_types(branch, root, depth + 1, add);
+        }
+    }
+}
+
+/// Index and keyword of each union branch of `node` that is not `null`.
+fn non_null_branches(node: &Value) -> Vec<(usize, &'static str)> {
+    let mut branches = Vec::new();
+    for keyword in ["anyOf", "oneOf"] {
+        for (i, branch) in node
+            .get(keyword)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            if branch.get("type").and_then(Value::as_str) != Some("null") {
+                branches.push((i, keyword));
+            }
+        }
+    }
+    branches
+}
+
+/// The values of `node`'s `enum` (or `const`), looking through a nullable
+/// wrapper. `null` is left out.
+fn enum_values(node: &Value, root: &Value) -> Option<Vec<Value>> {
+    let values = if let Some(values) = node.get("enum").and_then(Value::as_array) {
+        values.clone()
+    } else if let Some(value) = node.get("const") {
+        vec![value.clone()]
+    } else {
+        let only = nullable_inner(node)?;
+        let only = only
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| resolve_pointer(root, r))
+            .unwrap_or(only);
+        return enum_values(only, root);
+    };
+    let values: Vec<Value> = values.into_iter().filter(|v| !v.is_null()).collect();
+    (!values.is_empty()).then_some(values)
+}
+
+/// The one non-`null` branch of a nullable union.
+fn nullable_inner(node: &Value) -> Option<&Value> {
+    let mut branches = ["anyOf", "oneOf"]
+        .iter()
+        .filter_map(|k| node.get(*k).and_then(Value::as_array))
+        .flatten()
+        .filter(|b| b.get("type").and_then(Value::as_str) != Some("null"));
+    let only = branches.next()?;
+    branches.next().is_none().then_some(only)
+}
+
+/// First paragraph of `node`'s description (or its title), on one line,
+/// looking through a nullable wrapper.
+fn summary(node: &Value) -> Option<String> {
+    let text = node
+        .get("description")
+        .or_else(|| node.get("title"))
+        .and_then(Value::as_str);
+    let Some(text) = text else {
+        return nullable_inner(node).and_then(summary);
+    };
+    let paragraph = text.split("\n\n").next().unwrap_or(text);
+    let line = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!line.is_empty()).then_some(line)
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, ConvertOptions};
+    use serde_json::json;
+
+    fn scaffold_for(schema: &Value) -> String {
+        let result = convert(schema, &ConvertOptions::default()).unwrap();
+        let options = PromptOptions {
+            codec: Some(result.codec),
+            ..PromptOptions::default()
+        };
+        generate_prompt_scaffold(&result.schema, &options)
+    }
+
+    #[test]
+    fn test_scaffold_lists_nested_fields() {
+        let prompt = scaffold_for(&json!({
+            "description": "An order.\n\nInternal note.",
+            "type": "object",
+            "properties": {
+                "customer": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string", "description": "Full name." } },
+                    "required": ["name"]
+                },
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "price": { "type": "number" } },
+                        "required": ["price"]
+                    }
+                },
+                "note": { "type": "string", "description": "Anything else." }
+            },
+            "required": ["customer", "items"]
+        }));
+        assert!(
+            prompt.starts_with("## Response format\n\nAn order.\n\n"),
+            "{}",
+            prompt
+        );
+        assert!(prompt.contains("- `customer.name` (string, required): Full name.\n"));
+        assert!(prompt.contains("- `items[].price` (number, required)\n"));
+        assert!(prompt.contains("- `note` (string, nullable, required): Anything else.\n"));
+        assert!(prompt.contains("Use `null` for a nullable field"));
+        assert!(!prompt.contains("Internal note"));
+    }
+
+    #[test]
+    fn test_scaffold_marks_json_strings_and_caps_enums() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "meta": { "description": "Free-form metadata." },
+                "code": { "enum": ["a", "b", "c"] }
+            },
+            "required": ["meta", "code"]
+        });
+        let result = convert(&schema, &ConvertOptions::default()).unwrap();
+        let options = PromptOptions {
+            codec: Some(result.codec),
+            max_enum_values: 2,
+        };
+        let prompt = generate_prompt_scaffold(&result.schema, &options);
+        assert!(
+            prompt.contains("- `meta` (JSON string, required): Free-form metadata.\n"),
+            "{}",
+            prompt
+        );
+        assert!(prompt.contains("One of: \"a\", \"b\", … (1 more)."));
+        assert!(prompt.contains("serialized JSON, not plain text"));
+
+        // Without the codec the field reads as a plain string.
+        let plain = generate_prompt_scaffold(&result.schema, &PromptOptions::default());
+        assert!(plain.contains("- `meta` (string, required)"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Gemini `propertyOrdering` emission.
+//!
+//! Gemini generates object fields in `propertyOrdering` order, which matters
+//! when later fields depend on earlier ones (e.g. reasoning before answer).
+//! Source order is seeded onto the input before any pass runs, so it travels
+//! with each object through `$ref` inlining and `allOf` merging; a final step
+//! then gives every object with `properties` a complete ordering.
+
+use serde_json::{Map, Value};
+
+use crate::config::{ConvertOptions, PropertyOrderFn, PropertyOrdering, Target};
+use crate::error::ConvertError;
+use crate::schema_utils::build_path;
+use crate::schema_walker::{fold, FoldAction, SchemaFolder};
+use crate::span;
+
+const KEYWORD: &str = "propertyOrdering";
+
+/// Whether `options` ask for `propertyOrdering` output.
+pub(crate) fn enabled(options: &ConvertOptions) -> bool {
+    options.target == Target::Gemini
+        && (options.property_ordering == PropertyOrdering::Original
+            || options.property_order_fn.is_some())
+}
+
+/// Record the key order of each `properties` object in `text` as a
+/// `propertyOrdering` on the parsed `schema`. Existing orderings win.
+pub(crate) fn seed_from_source(schema: Value, text: &str) -> Result<Value, ConvertError> {
+    let Some(orders) = span::properties_key_orders(text) else {
+        return Ok(schema);
+    };
+    let mut seeder = Seeder { orders };
+    fold(schema, &mut seeder, "#", 0)
+}
+
+struct Seeder {
+    orders: std::collections::HashMap<String, Vec<String>>,
+}
+
+impl SchemaFolder for Seeder {
+    type Error = ConvertError;
+
+    fn fold_schema(
+        &mut self,
+        mut schema: Value,
+        path: &str,
+        _depth: usize,
+    ) -> Result<FoldAction, ConvertError> {
+        if let Some(obj) = schema.as_object_mut() {
+            if !obj.contains_key(KEYWORD) {
+                let order = self.orders.remove(&build_path(path, &["properties"]));
+                if let (Some(order), Some(Value::Object(props))) = (order, obj.get("properties")) {
+                    let order: Vec<Value> = order
+                        .into_iter()
+                        .filter(|k| props.contains_key(k))
+                        .map(Value::String)
+                        .collect();
+                    obj.insert(KEYWORD.to_string(), Value::Array(order));
+                }
+            }
+        }
+        Ok(FoldAction::Continue(schema))
+    }
+}
+
+/// Give every object with `properties` a `propertyOrdering` that lists each
+/// property exactly once.
+pub(crate) fn apply(schema: Value, options: &ConvertOptions) -> Result<Value, ConvertError> {
+    let mut orderer = Orderer {
+        order_fn: options.property_order_fn.as_ref(),
+    };
+    fold(schema, &mut orderer, "#", 0)
+}
+
+struct Orderer<'a> {
+    order_fn: Option<&'a PropertyOrderFn>,
+}
+
+impl SchemaFolder for Orderer<'_> {
+    type Error = ConvertError;
+
+    fn fold_schema(
+        &mut self,
+        mut schema: Value,
+        path: &str,
+        _depth: usize,
+    ) -> Result<FoldAction, ConvertError> {
+        let Some(obj) = schema.as_object_mut() else {
+            return Ok(FoldAction::Continue(schema));
+        };
+        let Some(Value::Object(props)) = obj.get("properties") else {
+            return Ok(FoldAction::Continue(schema));
+        };
+        let mut order = complete_order(obj.get(KEYWORD), props);
+        if let Some(order_fn) = self.order_fn {
+            let custom: Vec<Value> = order_fn
+                .call(path, &order)
+                .into_iter()
+                .map(Value::String)
+                .collect();
+            order = complete_order(Some(&Value::Array(custom)), props);
+        }
+        obj.insert(
+            KEYWORD.to_string(),
+            Value::Array(order.into_iter().map(Value::String).collect()),
+        );
+        Ok(FoldAction::Continue(schema))
+    }
+}
+
+/// `preferred` entries that name a property (first occurrence wins), then the
+/// remaining properties in map order.
+fn complete_order(preferred: Option<&Value>, props: &Map<String, Value>) -> Vec<String> {
+    let mut order: Vec<String> = Vec::with_capacity(props.len());
+    let preferred = preferred.and_then(Value::as_array).into_iter().flatten();
+    for name in preferred.filter_map(Value::as_str) {
+        if props.contains_key(name) && !order.iter().any(|k| k == name) {
+            order.push(name.to_string());
+        }
+    }
+    for name in props.keys() {
+        if !order.contains(name) {
+            order.push(name.clone());
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn gemini() -> ConvertOptions {
+        ConvertOptions {
+            target: Target::Gemini,
+            property_ordering: PropertyOrdering::Original,
+            ..ConvertOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_seed_from_source_follows_text() {
+        let text = r#"{"type": "object", "properties": {"b": {}, "a": {}}, "$defs": {
+            "X": {"type": "object", "properties": {"z": {}, "y": {}}}}}"#;
+        let schema = seed_from_source(serde_json::from_str(text).unwrap(), text).unwrap();
+        assert_eq!(schema["propertyOrdering"], json!(["b", "a"]));
+        assert_eq!(schema["$defs"]["X"]["propertyOrdering"], json!(["z", "y"]));
+    }
+
+    #[test]
+    fn test_apply_completes_partial_ordering() {
+        let schema = json!({
+            "type": "object",
+            "propertyOrdering": ["c", "gone", "c"],
+            "properties": {"a": {}, "b": {}, "c": {}}
+        });
+        let out = apply(schema, &gemini()).unwrap();
+        assert_eq!(out["propertyOrdering"], json!(["c", "a", "b"]));
+    }
+
+    #[test]
+    fn test_apply_order_fn_sees_path_and_default_order() {
+        let mut options = gemini();
+        options.property_order_fn = Some(PropertyOrderFn::new(|path, props| {
+            if path == "#/properties/inner" {
+                props.iter().rev().cloned().collect()
+            } else {
+                vec!["inner".to_string()]
+            }
+        }));
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "a": {},
+                "inner": {"type": "object", "properties": {"x": {}, "y": {}}}
+            }
+        });
+        let out = apply(schema, &options).unwrap();
+        assert_eq!(out["propertyOrdering"], json!(["inner", "a"]));
+        assert_eq!(
+            out["properties"]["inner"]["propertyOrdering"],
+            json!(["y", "x"])
+        );
+    }
+
+    #[test]
+    fn test_enabled_only_for_gemini() {
+        assert!(enabled(&gemini()));
+        let mut options = gemini();
+        options.target = Target::OpenaiStrict;
+        assert!(!enabled(&options));
+        assert!(!enabled(&ConvertOptions {
+            target: Target::Gemini,
+            ..ConvertOptions::default()
+        }));
+    }
+}
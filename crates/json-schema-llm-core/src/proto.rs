@@ -0,0 +1,719 @@
+//! Protocol Buffers input (feature `proto`).
+//!
+//! Turns a message type from a compiled `FileDescriptorSet` into a JSON
+//! Schema that follows the [proto3 JSON mapping], then runs it through
+//! [`convert`](crate::convert). Rehydrated output is therefore proto3 JSON,
+//! ready for `protojson.Unmarshal` / `JsonFormat.parser()`.
+//!
+//! Build the descriptor set with imports so referenced types resolve, and
+//! with source info to carry comments over as descriptions:
+//!
+//! ```text
+//! protoc --include_imports --include_source_info \
+//!     --descriptor_set_out=order.pb order.proto
+//! ```
+//!
+//! ```rust,no_run
+//! use json_schema_llm_core::{proto, ConvertOptions};
+//!
+//! let descriptors = std::fs::read("order.pb").unwrap();
+//! let result = proto::convert_message(&descriptors, "my.pkg.Order", &ConvertOptions::default())
+//!     .unwrap();
+//! println!("{}", result.schema);
+//! ```
+//!
+//! ## Mapping
+//!
+//! | Protobuf                          | JSON Schema                                       |
+//! | --------------------------------- | ------------------------------------------------- |
+//! | message                           | closed object in `$defs`, referenced by `$ref`    |
+//! | field name                        | its `json_name` (lowerCamelCase)                  |
+//! | `int64`, `uint64`, … (64-bit)     | decimal string                                    |
+//! | 32-bit integers / floats / `bool` | `integer` / `number` / `boolean`                  |
+//! | `bytes`                           | base64 string                                     |
+//! | enum                              | string enum of value names                        |
+//! | `repeated T` / `map<K, V>`        | array of `T` / object with `additionalProperties` |
+//! | `oneof`                           | `anyOf` of variants, each allowing one member     |
+//! | `proto2 required`                 | `required`                                        |
+//! | `[deprecated = true]`             | `deprecated: true`                                |
+//!
+//! Well-known types map to their JSON forms (`Timestamp` → RFC 3339
+//! string, `Struct` → open object, wrappers → the wrapped scalar, …).
+//!
+//! [proto3 JSON mapping]: https://protobuf.dev/programming-guides/json/
+
+use std::collections::{HashMap, HashSet};
+
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorSet};
+use serde_json::{json, Map, Value};
+
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+use crate::ConvertResult;
+
+/// Cartesian product of `oneof` members above which a message's oneofs are
+/// flattened into plain optional properties instead of `anyOf` variants.
+const MAX_ONEOF_VARIANTS: usize = 16;
+
+// `DescriptorProto` field numbers, for `SourceCodeInfo` paths.
+const FILE_MESSAGE_TYPE: i32 = 4;
+const FILE_ENUM_TYPE: i32 = 5;
+const MESSAGE_FIELD: i32 = 2;
+const MESSAGE_NESTED_TYPE: i32 = 3;
+const MESSAGE_ENUM_TYPE: i32 = 4;
+
+/// JSON Schema for message `message` (fully qualified, e.g. `my.pkg.Order`)
+/// of the encoded `FileDescriptorSet` in `descriptor_set`.
+pub fn message_schema(descriptor_set: &[u8], message: &str) -> Result<Value, ConvertError> {
+    let set = FileDescriptorSet::decode(descriptor_set).map_err(|e| ConvertError::SchemaError {
+        path: "#".to_string(),
+        message: format!("invalid FileDescriptorSet: {}", e),
+        location: None,
+    })?;
+    let index = Index::new(&set);
+    let name = message.trim_start_matches('.');
+    let root = index
+        .messages
+        .get(name)
+        .ok_or_else(|| ConvertError::SchemaError {
+            path: "#".to_string(),
+            message: format!("message type `{}` not found in descriptor set", name),
+            location: None,
+        })?;
+
+    let mut builder = SchemaBuilder {
+        index: &index,
+        defs: Map::new(),
+        queued: HashSet::new(),
+        pending: Vec::new(),
+    };
+    let mut schema = builder.message(root)?;
+    while let Some(def_name) = builder.pending.pop() {
+        let def = builder.message(&index.messages[def_name.as_str()])?;
+        builder.defs.insert(def_name, def);
+    }
+
+    let obj = schema
+        .as_object_mut()
+        .expect("invariant: message schemas are objects");
+    obj.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    obj.insert("title".to_string(), json!(root.desc.name()));
+    if !builder.defs.is_empty() {
+        obj.insert("$defs".to_string(), Value::Object(builder.defs));
+    }
+    Ok(schema)
+}
+
+/// [`message_schema`] followed by [`convert`](crate::convert).
+pub fn convert_message(
+    descriptor_set: &[u8],
+    message: &str,
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    crate::convert(&message_schema(descriptor_set, message)?, options)
+}
+
+/// A descriptor plus where its comments live.
+struct Described<'a, T> {
+    desc: &'a T,
+    file: usize,
+    path: Vec<i32>,
+}
+
+/// Every message and enum of the set by fully qualified name, and the
+/// comments of each file by `SourceCodeInfo` path.
+struct Index<'a> {
+    messages: HashMap<String, Described<'a, DescriptorProto>>,
+    enums: HashMap<String, Described<'a, EnumDescriptorProto>>,
+    comments: Vec<HashMap<Vec<i32>, String>>,
+}
+
+impl<'a> Index<'a> {
+    fn new(set: &'a FileDescriptorSet) -> Self {
+        let mut index = Index {
+            messages: HashMap::new(),
+            enums: HashMap::new(),
+            comments: Vec::new(),
+        };
+        for (file_idx, file) in set.file.iter().enumerate() {
+            let prefix = file.package().to_string();
+            for (i, message) in file.message_type.iter().enumerate() {
+                index.add_message(
+                    message,
+                    &prefix,
+                    file_idx,
+                    vec![FILE_MESSAGE_TYPE, i as i32],
+                );
+            }
+            for (i, enum_type) in file.enum_type.iter().enumerate() {
+                index.add_enum(enum_type, &prefix, file_idx, vec![FILE_ENUM_TYPE, i as i32]);
+            }
+            let comments = file
+                .source_code_info
+                .iter()
+                .flat_map(|info| &info.location)
+                .filter_map(|loc| {
+                    let text = loc
+                        .leading_comments
+                        .as_deref()
+                        .or(loc.trailing_comments.as_deref())?;
+                    let text = clean_comment(text);
+                    (!text.is_empty()).then(|| (loc.path.clone(), text))
+                })
+                .collect();
+            index.comments.push(comments);
+        }
+        index
+    }
+
+    fn add_message(
+        &mut self,
+        message: &'a DescriptorProto,
+        prefix: &str,
+        file: usize,
+        path: Vec<i32>,
+    ) {
+        let name = qualify(prefix, message.name());
+        for (i, nested) in message.nested_type.iter().enumerate() {
+            let mut nested_path = path.clone();
+            nested_path.extend([MESSAGE_NESTED_TYPE, i as i32]);
+            self.add_message(nested, &name, file, nested_path);
+        }
+        for (i, enum_type) in message.enum_type.iter().enumerate() {
+            let mut enum_path = path.clone();
+            enum_path.extend([MESSAGE_ENUM_TYPE, i as i32]);
+            self.add_enum(enum_type, &name, file, enum_path);
+        }
+        self.messages.insert(
+            name,
+            Described {
+                desc: message,
+                file,
+                path,
+            },
+        );
+    }
+
+    fn add_enum(
+        &mut self,
+        enum_type: &'a EnumDescriptorProto,
+        prefix: &str,
+        file: usize,
+        path: Vec<i32>,
+    ) {
+        self.enums.insert(
+            qualify(prefix, enum_type.name()),
+            Described {
+                desc: enum_type,
+                file,
+                path,
+            },
+        );
+    }
+
+    fn comment(&self, file: usize, path: &[i32]) -> Option<&str> {
+        self.comments[file].get(path).map(String::as_str)
+    }
+}
+
+struct SchemaBuilder<'a> {
+    index: &'a Index<'a>,
+    defs: Map<String, Value>,
+    /// Messages referenced so far, built or waiting in `pending`.
+    queued: HashSet<String>,
+    pending: Vec<String>,
+}
+
+impl SchemaBuilder<'_> {
+    fn message(&mut self, message: &Described<'_, DescriptorProto>) -> Result<Value, ConvertError> {
+        let desc = message.desc;
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut groups: Vec<Vec<(String, Value)>> = vec![Vec::new(); desc.oneof_decl.len()];
+
+        for (i, field) in desc.field.iter().enumerate() {
+            let mut field_path = message.path.clone();
+            field_path.extend([MESSAGE_FIELD, i as i32]);
+            let mut schema = self.field(field)?;
+            if let Some(text) = self.index.comment(message.file, &field_path) {
+                schema["description"] = json!(text);
+            }
+            if field.options.as_ref().is_some_and(|o| o.deprecated()) {
+                schema["deprecated"] = json!(true);
+            }
+
+            let name = json_name(field);
+            match field.oneof_index {
+                Some(group) if !field.proto3_optional() => {
+                    groups[group as usize].push((name, schema));
+                }
+                _ => {
+                    if field.label() == Label::Required {
+                        required.push(json!(name));
+                    }
+                    properties.insert(name, schema);
+                }
+            }
+        }
+
+        groups.retain(|members| !members.is_empty());
+        let variant_count = groups
+            .iter()
+            .try_fold(1usize, |n, members| n.checked_mul(members.len()));
+        let mut schema = match variant_count {
+            Some(count) if !groups.is_empty() && count <= MAX_ONEOF_VARIANTS => {
+                // Each variant admits one member per oneof; being closed, it
+                // rejects the others, so "at most one" holds.
+                let mut variants = vec![properties];
+                for members in &groups {
+                    variants = variants
+                        .iter()
+                        .flat_map(|base| {
+                            members.iter().map(move |(name, member)| {
+                                let mut props = base.clone();
+                                props.insert(name.clone(), member.clone());
+                                props
+                            })
+                        })
+                        .collect();
+                }
+                let variants: Vec<Value> = variants
+                    .into_iter()
+                    .map(|props| object_schema(props, &required))
+                    .collect();
+                json!({ "anyOf": variants })
+            }
+            _ => {
+                if !groups.is_empty() {
+                    crate::logging::debug!(
+                        message = desc.name(),
+                        "oneof variants exceed {}, flattening",
+                        MAX_ONEOF_VARIANTS
+                    );
+                }
+                properties.extend(groups.into_iter().flatten());
+                object_schema(properties, &required)
+            }
+        };
+
+        if let Some(text) = self.index.comment(message.file, &message.path) {
+            schema["description"] = json!(text);
+        }
+        Ok(schema)
+    }
+
+    fn field(&mut self, field: &FieldDescriptorProto) -> Result<Value, ConvertError> {
+        let type_name = field.type_name().trim_start_matches('.');
+        let value = match field.r#type() {
+            Type::Message | Type::Group => {
+                let entry = self.index.messages.get(type_name);
+                match entry.filter(|m| m.desc.options.as_ref().is_some_and(|o| o.map_entry())) {
+                    // Map keys are always strings in JSON.
+                    Some(entry) => {
+                        let value_field = entry
+                            .desc
+                            .field
+                            .iter()
+                            .find(|f| f.number() == 2)
+                            .ok_or_else(|| unknown_type(type_name))?;
+                        let value = self.field(value_field)?;
+                        return Ok(json!({ "type": "object", "additionalProperties": value }));
+                    }
+                    None => self.message_ref(type_name)?,
+                }
+            }
+            Type::Enum => self.enum_schema(type_name)?,
+            scalar => scalar_schema(scalar),
+        };
+        Ok(if field.label() == Label::Repeated {
+            json!({ "type": "array", "items": value })
+        } else {
+            value
+        })
+    }
+
+    fn message_ref(&mut self, name: &str) -> Result<Value, ConvertError> {
+        if let Some(schema) = well_known_type(name) {
+            return Ok(schema);
+        }
+        if !self.index.messages.contains_key(name) {
+            return Err(unknown_type(name));
+        }
+        if self.queued.insert(name.to_string()) {
+            self.pending.push(name.to_string());
+        }
+        Ok(json!({ "$ref": crate::schema_utils::build_path("#", &["$defs", name]) }))
+    }
+
+    fn enum_schema(&self, name: &str) -> Result<Value, ConvertError> {
+        if name == "google.protobuf.NullValue" {
+            return Ok(json!({ "type": "null" }));
+        }
+        let entry = self
+            .index
+            .enums
+            .get(name)
+            .ok_or_else(|| unknown_type(name))?;
+        let values: Vec<&str> = entry.desc.value.iter().map(|v| v.name()).collect();
+        let mut schema = json!({ "type": "string", "enum": values });
+        if let Some(text) = self.index.comment(entry.file, &entry.path) {
+            schema["description"] = json!(text);
+        }
+        Ok(schema)
+    }
+}
+
+fn object_schema(properties: Map<String, Value>, required: &[Value]) -> Value {
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required.to_vec());
+    }
+    schema
+}
+
+fn scalar_schema(ty: Type) -> Value {
+    match ty {
+        Type::Double | Type::Float => json!({ "type": "number" }),
+        Type::Int32 | Type::Sint32 | Type::Sfixed32 | Type::Uint32 | Type::Fixed32 => {
+            json!({ "type": "integer" })
+        }
+        // 64-bit integers are decimal strings in proto3 JSON.
+        Type::Int64 | Type::Sint64 | Type::Sfixed64 => {
+            json!({ "type": "string", "pattern": "^-?[0-9]+$" })
+        }
+        Type::Uint64 | Type::Fixed64 => json!({ "type": "string", "pattern": "^[0-9]+$" }),
+        Type::Bool => json!({ "type": "boolean" }),
+        Type::String => json!({ "type": "string" }),
+        Type::Bytes => json!({ "type": "string", "contentEncoding": "base64" }),
+        Type::Message | Type::Group | Type::Enum => {
+            unreachable!("named types are resolved by the caller")
+        }
+    }
+}
+
+/// JSON form of a `google.protobuf` well-known type, if `name` is one.
+fn well_known_type(name: &str) -> Option<Value> {
+    let schema = match name.strip_prefix("google.protobuf.")? {
+        "Timestamp" => json!({ "type": "string", "format": "date-time" }),
+        "Duration" => json!({ "type": "string", "pattern": "^-?[0-9]+(\\.[0-9]{1,9})?s$" }),
+        "FieldMask" => json!({ "type": "string" }),
+        "Struct" => json!({ "type": "object" }),
+        "Value" => json!({}),
+        "ListValue" => json!({ "type": "array", "items": {} }),
+        "Any" => json!({
+            "type": "object",
+            "properties": { "@type": { "type": "string" } },
+            "required": ["@type"],
+        }),
+        "Empty" => json!({ "type": "object", "properties": {}, "additionalProperties": false }),
+        "DoubleValue" | "FloatValue" => scalar_schema(Type::Double),
+        "Int64Value" => scalar_schema(Type::Int64),
+        "UInt64Value" => scalar_schema(Type::Uint64),
+        "Int32Value" | "UInt32Value" => scalar_schema(Type::Int32),
+        "BoolValue" => scalar_schema(Type::Bool),
+        "StringValue" => scalar_schema(Type::String),
+        "BytesValue" => scalar_schema(Type::Bytes),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+fn unknown_type(name: &str) -> ConvertError {
+    ConvertError::SchemaError {
+        path: "#".to_string(),
+        message: format!(
+            "type `{}` not found in descriptor set (compile with --include_imports)",
+            name
+        ),
+        location: None,
+    }
+}
+
+fn qualify(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// The field's JSON name; `protoc` fills it in, other producers may not.
+fn json_name(field: &FieldDescriptorProto) -> String {
+    if let Some(name) = &field.json_name {
+        return name.clone();
+    }
+    let mut out = String::with_capacity(field.name().len());
+    let mut upper = false;
+    for c in field.name().chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Comment text with per-line indentation removed, joined into one line.
+fn clean_comment(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::source_code_info::Location;
+    use prost_types::{
+        EnumValueDescriptorProto, FieldOptions, FileDescriptorProto, MessageOptions,
+        OneofDescriptorProto, SourceCodeInfo,
+    };
+
+    fn field(name: &str, number: i32, ty: Type, label: Label) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            r#type: Some(ty as i32),
+            label: Some(label as i32),
+            ..Default::default()
+        }
+    }
+
+    fn named(mut f: FieldDescriptorProto, type_name: &str) -> FieldDescriptorProto {
+        f.type_name = Some(type_name.to_string());
+        f
+    }
+
+    fn message(name: &str, fields: Vec<FieldDescriptorProto>) -> DescriptorProto {
+        DescriptorProto {
+            name: Some(name.to_string()),
+            field: fields,
+            ..Default::default()
+        }
+    }
+
+    /// `shop.Order` with scalars, an enum, a repeated message, a map, a
+    /// oneof and a well-known type.
+    fn order_set() -> Vec<u8> {
+        let mut payment_card = named(
+            field("card", 6, Type::Message, Label::Optional),
+            ".shop.Card",
+        );
+        payment_card.oneof_index = Some(0);
+        let mut payment_iban = field("iban", 7, Type::String, Label::Optional);
+        payment_iban.oneof_index = Some(0);
+        let mut legacy = field("legacy_code", 9, Type::String, Label::Optional);
+        legacy.options = Some(FieldOptions {
+            deprecated: Some(true),
+            ..Default::default()
+        });
+
+        let mut order = message(
+            "Order",
+            vec![
+                field("order_id", 1, Type::Int64, Label::Optional),
+                named(
+                    field("status", 2, Type::Enum, Label::Optional),
+                    ".shop.Status",
+                ),
+                named(
+                    field("lines", 3, Type::Message, Label::Repeated),
+                    ".shop.Order.Line",
+                ),
+                named(
+                    field("labels", 4, Type::Message, Label::Repeated),
+                    ".shop.Order.LabelsEntry",
+                ),
+                named(
+                    field("created_at", 5, Type::Message, Label::Optional),
+                    ".google.protobuf.Timestamp",
+                ),
+                payment_card,
+                payment_iban,
+                field("note_bytes", 8, Type::Bytes, Label::Optional),
+                legacy,
+            ],
+        );
+        order.oneof_decl = vec![OneofDescriptorProto {
+            name: Some("payment".to_string()),
+            ..Default::default()
+        }];
+        order.nested_type = vec![
+            message(
+                "Line",
+                vec![
+                    field("sku", 1, Type::String, Label::Optional),
+                    field("qty", 2, Type::Uint32, Label::Optional),
+                ],
+            ),
+            DescriptorProto {
+                options: Some(MessageOptions {
+                    map_entry: Some(true),
+                    ..Default::default()
+                }),
+                ..message(
+                    "LabelsEntry",
+                    vec![
+                        field("key", 1, Type::String, Label::Optional),
+                        field("value", 2, Type::String, Label::Optional),
+                    ],
+                )
+            },
+        ];
+
+        let card = message(
+            "Card",
+            vec![
+                field("number", 1, Type::String, Label::Required),
+                named(
+                    field("backup", 2, Type::Message, Label::Optional),
+                    ".shop.Card",
+                ),
+            ],
+        );
+        let status = EnumDescriptorProto {
+            name: Some("Status".to_string()),
+            value: ["STATUS_UNSPECIFIED", "PAID"]
+                .iter()
+                .enumerate()
+                .map(|(i, name)| EnumValueDescriptorProto {
+                    name: Some(name.to_string()),
+                    number: Some(i as i32),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let comment = |path: Vec<i32>, text: &str| Location {
+            path,
+            leading_comments: Some(text.to_string()),
+            ..Default::default()
+        };
+
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("shop.proto".to_string()),
+                package: Some("shop".to_string()),
+                message_type: vec![order, card],
+                enum_type: vec![status],
+                source_code_info: Some(SourceCodeInfo {
+                    location: vec![
+                        comment(vec![4, 0], " A customer order.\n"),
+                        comment(vec![4, 0, 2, 0], " Server-assigned\n id.\n"),
+                    ],
+                }),
+                ..Default::default()
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn test_message_schema_maps_fields() {
+        let schema = message_schema(&order_set(), "shop.Order").unwrap();
+        assert_eq!(schema["title"], "Order");
+        assert_eq!(schema["description"], "A customer order.");
+
+        let variants = schema["anyOf"].as_array().unwrap();
+        assert_eq!(variants.len(), 2);
+        let props = &variants[0]["properties"];
+        assert_eq!(
+            props["orderId"],
+            json!({"type": "string", "pattern": "^-?[0-9]+$", "description": "Server-assigned id."})
+        );
+        assert_eq!(
+            props["status"],
+            json!({"type": "string", "enum": ["STATUS_UNSPECIFIED", "PAID"]})
+        );
+        assert_eq!(
+            props["lines"],
+            json!({"type": "array", "items": {"$ref": "#/$defs/shop.Order.Line"}})
+        );
+        assert_eq!(
+            props["labels"],
+            json!({"type": "object", "additionalProperties": {"type": "string"}})
+        );
+        assert_eq!(props["createdAt"]["format"], "date-time");
+        assert_eq!(props["noteBytes"]["contentEncoding"], "base64");
+        assert_eq!(props["legacyCode"]["deprecated"], true);
+
+        // One oneof member per variant.
+        assert_eq!(props["card"], json!({"$ref": "#/$defs/shop.Card"}));
+        assert!(props.get("iban").is_none());
+        assert_eq!(variants[1]["properties"]["iban"]["type"], "string");
+        assert!(variants[1]["properties"].get("card").is_none());
+        assert_eq!(variants[0]["additionalProperties"], false);
+
+        let card = &schema["$defs"]["shop.Card"];
+        assert_eq!(card["required"], json!(["number"]));
+        assert_eq!(
+            card["properties"]["backup"],
+            json!({"$ref": "#/$defs/shop.Card"})
+        );
+        assert!(schema["$defs"].get("shop.Order.LabelsEntry").is_none());
+    }
+
+    #[test]
+    fn test_convert_message_round_trip() {
+        let result =
+            convert_message(&order_set(), ".shop.Order", &ConvertOptions::default()).unwrap();
+        let output = json!({
+            "result": {
+                "orderId": "42", "status": "PAID", "lines": [], "labels": [],
+                "createdAt": "2024-01-01T00:00:00Z", "noteBytes": null, "legacyCode": null,
+                "iban": "DE00"
+            }
+        });
+        let schema = message_schema(&order_set(), "shop.Order").unwrap();
+        let rehydrated = crate::rehydrate(&output, &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data["iban"], "DE00");
+        assert_eq!(rehydrated.data["labels"], json!({}));
+        assert!(rehydrated.data.get("noteBytes").is_none());
+    }
+
+    #[test]
+    fn test_unknown_message_and_type_errors() {
+        let err = message_schema(&order_set(), "shop.Missing").unwrap_err();
+        assert!(err.to_string().contains("shop.Missing"), "{err}");
+
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                package: Some("p".to_string()),
+                message_type: vec![message(
+                    "M",
+                    vec![named(
+                        field("x", 1, Type::Message, Label::Optional),
+                        ".other.X",
+                    )],
+                )],
+                ..Default::default()
+            }],
+        };
+        let err = message_schema(&set.encode_to_vec(), "p.M").unwrap_err();
+        assert!(err.to_string().contains("--include_imports"), "{err}");
+
+        let err = message_schema(b"\xff\xff", "p.M").unwrap_err();
+        assert!(err.to_string().contains("FileDescriptorSet"), "{err}");
+    }
+
+    #[test]
+    fn test_json_name_fallback() {
+        let f = field("created_at_ms", 1, Type::Int64, Label::Optional);
+        assert_eq!(json_name(&f), "createdAtMs");
+    }
+}
@@ -0,0 +1,632 @@
+//! Provider request formats.
+//!
+//! The one place that knows how each provider's API expects a structured
+//! output schema to be delivered: [`build_request`] assembles a complete
+//! request body, and [`ConvertOptions::envelope`](crate::ConvertOptions::envelope)
+//! uses the same wrappers for just the schema-carrying fragment.
+//! [`extract_structured_output`] goes the other way, pulling the model's JSON
+//! out of a raw response.
+//!
+//! ```rust
+//! use json_schema_llm_core::providers::{build_request, PromptParams};
+//! use json_schema_llm_core::{convert, ConvertOptions, Target};
+//!
+//! let schema = serde_json::json!({"type": "object", "properties": {"a": {"type": "string"}}});
+//! let converted = convert(&schema, &ConvertOptions::default()).unwrap();
+//! let params = PromptParams {
+//!     model: Some("gpt-4o".to_string()),
+//!     user: Some("Extract the fields.".to_string()),
+//!     ..PromptParams::default()
+//! };
+//! let body = build_request(Target::OpenaiStrict, &converted.schema, &params);
+//! assert_eq!(body["response_format"]["type"], "json_schema");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::config::{ConvertOptions, Envelope, Mode, Target};
+use crate::error::ConvertError;
+
+/// Name used when no explicit name or schema `title` yields one.
+pub const DEFAULT_NAME: &str = "response";
+
+/// Stand-in for the model when [`PromptParams::model`] is unset.
+pub const MODEL_PLACEHOLDER: &str = "{{model}}";
+
+/// Stand-in for the user message when [`PromptParams::user`] is unset.
+pub const PROMPT_PLACEHOLDER: &str = "{{prompt}}";
+
+/// `max_tokens` sent to Claude (where it is mandatory) when unset.
+pub const DEFAULT_CLAUDE_MAX_TOKENS: u32 = 4096;
+
+/// OpenAI rejects names longer than this.
+const MAX_NAME_LEN: usize = 64;
+
+/// Prompt-side inputs for [`build_request`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct PromptParams {
+    /// Model ID. Default: [`MODEL_PLACEHOLDER`]. Gemini takes the model in
+    /// the URL, so it is not part of that body.
+    pub model: Option<String>,
+    /// System prompt. Omitted when unset.
+    pub system: Option<String>,
+    /// User message. Default: [`PROMPT_PLACEHOLDER`].
+    pub user: Option<String>,
+    /// Output token cap. Omitted when unset, except for Claude which
+    /// requires it (default [`DEFAULT_CLAUDE_MAX_TOKENS`]).
+    pub max_tokens: Option<u32>,
+    /// How the schema is delivered. Default: [`Envelope::ResponseFormat`].
+    /// [`Envelope::ToolFunction`] also forces the model to call the tool.
+    pub envelope: Option<Envelope>,
+    /// Schema/tool name. Default: [`DEFAULT_NAME`].
+    pub name: Option<String>,
+    /// Schema/tool description. Omitted when unset.
+    pub description: Option<String>,
+    /// Set OpenAI `strict: true`. Default: `true`; turn off for schemas
+    /// converted in permissive mode.
+    pub strict: Option<bool>,
+}
+
+/// Assemble a complete request body for `target` that constrains output
+/// to `converted` (a schema produced by [`convert`](crate::convert)).
+///
+/// Shapes: OpenAI Chat Completions, Gemini `generateContent`, and the
+/// Anthropic Messages API.
+pub fn build_request(target: Target, converted: &Value, params: &PromptParams) -> Value {
+    let envelope = params.envelope.unwrap_or(Envelope::ResponseFormat);
+    let name = params
+        .name
+        .as_deref()
+        .map(sanitize_name)
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| DEFAULT_NAME.to_string());
+    let wrapped = wrap_schema(
+        target,
+        envelope,
+        converted.clone(),
+        &name,
+        params.description.as_deref(),
+        params.strict.unwrap_or(true),
+    );
+    let model = params.model.as_deref().unwrap_or(MODEL_PLACEHOLDER);
+    let user = params.user.as_deref().unwrap_or(PROMPT_PLACEHOLDER);
+
+    let mut body = Map::new();
+    match target {
+        Target::OpenaiStrict => {
+            body.insert("model".into(), json!(model));
+            let mut messages = Vec::new();
+            if let Some(system) = &params.system {
+                messages.push(json!({ "role": "system", "content": system }));
+            }
+            messages.push(json!({ "role": "user", "content": user }));
+            body.insert("messages".into(), Value::Array(messages));
+            if let Some(max_tokens) = params.max_tokens {
+                body.insert("max_completion_tokens".into(), json!(max_tokens));
+            }
+            match envelope {
+                Envelope::ResponseFormat => {
+                    body.insert("response_format".into(), wrapped);
+                }
+                Envelope::ToolFunction => {
+                    body.insert("tools".into(), json!([wrapped]));
+                    body.insert(
+                        "tool_choice".into(),
+                        json!({ "type": "function", "function": { "name": name } }),
+                    );
+                }
+            }
+        }
+        Target::Gemini => {
+            if let Some(system) = &params.system {
+                body.insert(
+                    "systemInstruction".into(),
+                    json!({ "parts": [{ "text": system }] }),
+                );
+            }
+            body.insert(
+                "contents".into(),
+                json!([{ "role": "user", "parts": [{ "text": user }] }]),
+            );
+            let mut generation_config = Map::new();
+            if let Some(max_tokens) = params.max_tokens {
+                generation_config.insert("maxOutputTokens".into(), json!(max_tokens));
+            }
+            match envelope {
+                Envelope::ResponseFormat => {
+                    if let Value::Object(fields) = wrapped {
+                        generation_config.extend(fields);
+                    }
+                }
+                Envelope::ToolFunction => {
+                    body.insert(
+                        "tools".into(),
+                        json!([{ "functionDeclarations": [wrapped] }]),
+                    );
+                    body.insert(
+                        "toolConfig".into(),
+                        json!({ "functionCallingConfig": {
+                            "mode": "ANY",
+                            "allowedFunctionNames": [name],
+                        } }),
+                    );
+                }
+            }
+            if !generation_config.is_empty() {
+                body.insert("generationConfig".into(), Value::Object(generation_config));
+            }
+        }
+        Target::Claude => {
+            body.insert("model".into(), json!(model));
+            body.insert(
+                "max_tokens".into(),
+                json!(params.max_tokens.unwrap_or(DEFAULT_CLAUDE_MAX_TOKENS)),
+            );
+            if let Some(system) = &params.system {
+                body.insert("system".into(), json!(system));
+            }
+            body.insert(
+                "messages".into(),
+                json!([{ "role": "user", "content": user }]),
+            );
+            match envelope {
+                Envelope::ResponseFormat => {
+                    body.insert("output_format".into(), wrapped);
+                }
+                Envelope::ToolFunction => {
+                    body.insert("tools".into(), json!([wrapped]));
+                    body.insert(
+                        "tool_choice".into(),
+                        json!({ "type": "tool", "name": name }),
+                    );
+                }
+            }
+        }
+    }
+    Value::Object(body)
+}
+
+/// Wrap `converted` for [`ConvertOptions::envelope`]. `original` supplies
+/// the default name (`title`) and the description.
+pub(crate) fn envelope_for(
+    envelope: Envelope,
+    converted: &Value,
+    original: &Value,
+    options: &ConvertOptions,
+) -> Value {
+    let name = options
+        .envelope_name
+        .as_deref()
+        .or_else(|| original.get("title").and_then(Value::as_str))
+        .map(sanitize_name)
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| DEFAULT_NAME.to_string());
+    let description = original.get("description").and_then(Value::as_str);
+    wrap_schema(
+        options.target,
+        envelope,
+        converted.clone(),
+        &name,
+        description,
+        options.mode == Mode::Strict,
+    )
+}
+
+/// The schema-carrying fragment of a request for `target`.
+pub(crate) fn wrap_schema(
+    target: Target,
+    envelope: Envelope,
+    schema: Value,
+    name: &str,
+    description: Option<&str>,
+    strict: bool,
+) -> Value {
+    match (target, envelope) {
+        // response_format for Chat Completions
+        (Target::OpenaiStrict, Envelope::ResponseFormat) => {
+            let mut inner = Map::new();
+            inner.insert("name".into(), json!(name));
+            with_description(&mut inner, description);
+            inner.insert("strict".into(), json!(strict));
+            inner.insert("schema".into(), schema);
+            json!({ "type": "json_schema", "json_schema": inner })
+        }
+        // tools[] entry for Chat Completions
+        (Target::OpenaiStrict, Envelope::ToolFunction) => {
+            let mut function = Map::new();
+            function.insert("name".into(), json!(name));
+            with_description(&mut function, description);
+            function.insert("strict".into(), json!(strict));
+            function.insert("parameters".into(), schema);
+            json!({ "type": "function", "function": function })
+        }
+        // generationConfig fields
+        (Target::Gemini, Envelope::ResponseFormat) => json!({
+            "responseMimeType": "application/json",
+            "responseSchema": schema,
+        }),
+        // functionDeclarations[] entry
+        (Target::Gemini, Envelope::ToolFunction) => {
+            let mut function = Map::new();
+            function.insert("name".into(), json!(name));
+            with_description(&mut function, description);
+            function.insert("parameters".into(), schema);
+            Value::Object(function)
+        }
+        // output_format for the Messages API
+        (Target::Claude, Envelope::ResponseFormat) => json!({
+            "type": "json_schema",
+            "schema": schema,
+        }),
+        // tools[] entry for the Messages API
+        (Target::Claude, Envelope::ToolFunction) => {
+            let mut tool = Map::new();
+            tool.insert("name".into(), json!(name));
+            with_description(&mut tool, description);
+            tool.insert("input_schema".into(), schema);
+            Value::Object(tool)
+        }
+    }
+}
+
+/// Pull the structured output out of a raw `target` response body.
+///
+/// Tool/function calls win over text: OpenAI `tool_calls[0]` arguments,
+/// Gemini `functionCall` args, or a Claude `tool_use` input. Otherwise the
+/// text (`choices[0].message.content`, Gemini text parts, Claude text
+/// blocks) is parsed as JSON, tolerating a Markdown code fence and trailing
+/// characters. A refusal or a response with no output is a
+/// `rehydration_error`.
+pub fn extract_structured_output(
+    target: Target,
+    raw_response: &Value,
+) -> Result<Value, ConvertError> {
+    match target {
+        Target::OpenaiStrict => {
+            let message = raw_response
+                .pointer("/choices/0/message")
+                .ok_or_else(|| missing("choices[0].message"))?;
+            if let Some(arguments) = message.pointer("/tool_calls/0/function/arguments") {
+                return match arguments {
+                    Value::String(text) => parse_text(text),
+                    other => Ok(other.clone()),
+                };
+            }
+            if let Some(refusal) = message.get("refusal").and_then(Value::as_str) {
+                return Err(ConvertError::RehydrationError(format!(
+                    "Model refused: {}",
+                    refusal
+                )));
+            }
+            match message.get("content") {
+                Some(Value::String(text)) => parse_text(text),
+                _ => Err(missing("choices[0].message.content")),
+            }
+        }
+        Target::Gemini => {
+            let parts = raw_response
+                .pointer("/candidates/0/content/parts")
+                .and_then(Value::as_array)
+                .ok_or_else(|| missing("candidates[0].content.parts"))?;
+            if let Some(args) = parts.iter().find_map(|p| p.pointer("/functionCall/args")) {
+                return Ok(args.clone());
+            }
+            let text = join_text(parts.iter().filter_map(|p| p.get("text")));
+            if text.is_empty() {
+                return Err(missing("candidates[0].content.parts[].text"));
+            }
+            parse_text(&text)
+        }
+        Target::Claude => {
+            let blocks = raw_response
+                .get("content")
+                .and_then(Value::as_array)
+                .ok_or_else(|| missing("content"))?;
+            if let Some(input) = blocks
+                .iter()
+                .find(|b| b.get("type").and_then(Value::as_str) == Some("tool_use"))
+                .and_then(|b| b.get("input"))
+            {
+                return Ok(input.clone());
+            }
+            let text = join_text(
+                blocks
+                    .iter()
+                    .filter(|b| b.get("type").and_then(Value::as_str) == Some("text"))
+                    .filter_map(|b| b.get("text")),
+            );
+            if text.is_empty() {
+                return Err(missing("content[].text"));
+            }
+            parse_text(&text)
+        }
+    }
+}
+
+/// Guess which provider produced a raw response body from its shape:
+/// `choices[]` is OpenAI Chat Completions, `candidates[]` is Gemini, and a
+/// `content[]` array of typed blocks is the Claude Messages API.
+pub fn detect_provider(raw_response: &Value) -> Option<Target> {
+    if raw_response.get("choices").is_some_and(Value::is_array) {
+        return Some(Target::OpenaiStrict);
+    }
+    if raw_response.get("candidates").is_some_and(Value::is_array) {
+        return Some(Target::Gemini);
+    }
+    let blocks = raw_response.get("content").and_then(Value::as_array)?;
+    blocks
+        .iter()
+        .all(|b| b.get("type").is_some_and(Value::is_string))
+        .then_some(Target::Claude)
+}
+
+fn missing(location: &str) -> ConvertError {
+    ConvertError::RehydrationError(format!(
+        "Provider response has no structured output at {}",
+        location
+    ))
+}
+
+fn join_text<'a>(values: impl Iterator<Item = &'a Value>) -> String {
+    values.filter_map(Value::as_str).collect()
+}
+
+/// Parse model text as JSON, unwrapping a ```json fence if present and
+/// ignoring anything after the first value.
+pub(crate) fn parse_text(text: &str) -> Result<Value, ConvertError> {
+    let mut body = text.trim();
+    if let Some(rest) = body.strip_prefix("```") {
+        // Drop the info string (e.g. `json`) up to the end of the line.
+        body = rest.split_once('\n').map_or("", |(_, after)| after);
+        body = body.trim_end().strip_suffix("```").unwrap_or(body);
+    }
+    let mut de = serde_json::Deserializer::from_str(body);
+    Ok(Value::deserialize(&mut de)?)
+}
+
+fn with_description(obj: &mut Map<String, Value>, description: Option<&str>) {
+    if let Some(description) = description {
+        obj.insert("description".into(), json!(description));
+    }
+}
+
+/// Reduce `raw` to the `^[a-zA-Z0-9_-]{1,64}$` shape providers accept:
+/// other characters become `_` and the result is truncated.
+pub(crate) fn sanitize_name(raw: &str) -> String {
+    raw.trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_NAME_LEN)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(target: Target) -> ConvertOptions {
+        ConvertOptions {
+            target,
+            ..ConvertOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_openai_response_format_envelope() {
+        let original = json!({"title": "Weather Report", "description": "Today's weather"});
+        let converted = json!({"type": "object"});
+        let out = envelope_for(
+            Envelope::ResponseFormat,
+            &converted,
+            &original,
+            &options(Target::OpenaiStrict),
+        );
+        assert_eq!(
+            out,
+            json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "Weather_Report",
+                    "description": "Today's weather",
+                    "strict": true,
+                    "schema": {"type": "object"}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_tool_function_envelope_per_target() {
+        let original = json!({});
+        let converted = json!({"type": "object"});
+        let mut opts = options(Target::OpenaiStrict);
+        opts.envelope_name = Some("get_weather".to_string());
+
+        let openai = envelope_for(Envelope::ToolFunction, &converted, &original, &opts);
+        assert_eq!(openai["type"], "function");
+        assert_eq!(openai["function"]["name"], "get_weather");
+        assert_eq!(openai["function"]["parameters"], converted);
+
+        opts.target = Target::Claude;
+        let claude = envelope_for(Envelope::ToolFunction, &converted, &original, &opts);
+        assert_eq!(claude["input_schema"], converted);
+
+        opts.target = Target::Gemini;
+        let gemini = envelope_for(Envelope::ToolFunction, &converted, &original, &opts);
+        assert_eq!(gemini["parameters"], converted);
+        assert!(gemini.get("description").is_none());
+    }
+
+    #[test]
+    fn test_permissive_mode_envelope_is_not_strict() {
+        let mut opts = options(Target::OpenaiStrict);
+        opts.mode = Mode::Permissive;
+        let out = envelope_for(Envelope::ResponseFormat, &json!({}), &json!({}), &opts);
+        assert_eq!(out["json_schema"]["strict"], false);
+        assert_eq!(out["json_schema"]["name"], DEFAULT_NAME);
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("  my.schema/v2 "), "my_schema_v2");
+        assert_eq!(sanitize_name(&"x".repeat(100)).len(), MAX_NAME_LEN);
+    }
+
+    #[test]
+    fn test_extract_openai_content_and_tool_call() {
+        let response = json!({"choices": [{"message": {"content": "{\"a\": 1}"}}]});
+        assert_eq!(
+            extract_structured_output(Target::OpenaiStrict, &response).unwrap(),
+            json!({"a": 1})
+        );
+        let response = json!({"choices": [{"message": {
+            "content": null,
+            "tool_calls": [{"function": {"name": "f", "arguments": "{\"b\": 2}"}}]
+        }}]});
+        assert_eq!(
+            extract_structured_output(Target::OpenaiStrict, &response).unwrap(),
+            json!({"b": 2})
+        );
+    }
+
+    #[test]
+    fn test_extract_openai_refusal_is_error() {
+        let response = json!({"choices": [{"message": {"content": null, "refusal": "no"}}]});
+        let err = extract_structured_output(Target::OpenaiStrict, &response).unwrap_err();
+        assert!(err.to_string().contains("refused"));
+    }
+
+    #[test]
+    fn test_extract_gemini_text_parts_and_function_call() {
+        let response = json!({"candidates": [{"content": {"parts": [
+            {"text": "{\"a\": "}, {"text": "1}"}
+        ]}}]});
+        assert_eq!(
+            extract_structured_output(Target::Gemini, &response).unwrap(),
+            json!({"a": 1})
+        );
+        let response = json!({"candidates": [{"content": {"parts": [
+            {"functionCall": {"name": "f", "args": {"b": 2}}}
+        ]}}]});
+        assert_eq!(
+            extract_structured_output(Target::Gemini, &response).unwrap(),
+            json!({"b": 2})
+        );
+    }
+
+    #[test]
+    fn test_extract_claude_tool_use_and_fenced_text() {
+        let response = json!({"content": [
+            {"type": "text", "text": "Sure."},
+            {"type": "tool_use", "name": "f", "input": {"b": 2}}
+        ]});
+        assert_eq!(
+            extract_structured_output(Target::Claude, &response).unwrap(),
+            json!({"b": 2})
+        );
+        let response = json!({"content": [{"type": "text", "text": "```json\n{\"a\": 1}\n```"}]});
+        assert_eq!(
+            extract_structured_output(Target::Claude, &response).unwrap(),
+            json!({"a": 1})
+        );
+    }
+
+    #[test]
+    fn test_extract_wrong_shape_is_rehydration_error() {
+        let err = extract_structured_output(Target::Claude, &json!({"choices": []})).unwrap_err();
+        assert_eq!(err.error_code(), crate::ErrorCode::RehydrationError);
+    }
+
+    #[test]
+    fn test_detect_provider_from_shape() {
+        let openai = json!({"choices": [{"message": {"content": "{}"}}]});
+        let gemini = json!({"candidates": [{"content": {"parts": []}}]});
+        let claude = json!({"content": [{"type": "text", "text": "{}"}]});
+        assert_eq!(detect_provider(&openai), Some(Target::OpenaiStrict));
+        assert_eq!(detect_provider(&gemini), Some(Target::Gemini));
+        assert_eq!(detect_provider(&claude), Some(Target::Claude));
+        assert_eq!(detect_provider(&json!({"name": "Alice"})), None);
+        assert_eq!(detect_provider(&json!({"content": ["text"]})), None);
+    }
+
+    #[test]
+    fn test_build_request_openai_defaults() {
+        let schema = json!({"type": "object"});
+        let body = build_request(Target::OpenaiStrict, &schema, &PromptParams::default());
+        assert_eq!(
+            body,
+            json!({
+                "model": MODEL_PLACEHOLDER,
+                "messages": [{"role": "user", "content": PROMPT_PLACEHOLDER}],
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": {"name": DEFAULT_NAME, "strict": true, "schema": schema}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_request_claude_tool_forces_call() {
+        let schema = json!({"type": "object"});
+        let params = PromptParams {
+            model: Some("claude-sonnet-4-5".to_string()),
+            system: Some("Be terse.".to_string()),
+            envelope: Some(Envelope::ToolFunction),
+            name: Some("record".to_string()),
+            ..PromptParams::default()
+        };
+        let body = build_request(Target::Claude, &schema, &params);
+        assert_eq!(body["max_tokens"], DEFAULT_CLAUDE_MAX_TOKENS);
+        assert_eq!(body["system"], "Be terse.");
+        assert_eq!(body["tools"][0]["input_schema"], schema);
+        assert_eq!(
+            body["tool_choice"],
+            json!({"type": "tool", "name": "record"})
+        );
+    }
+
+    #[test]
+    fn test_build_request_gemini_generation_config() {
+        let schema = json!({"type": "object"});
+        let params = PromptParams {
+            user: Some("hi".to_string()),
+            max_tokens: Some(256),
+            ..PromptParams::default()
+        };
+        let body = build_request(Target::Gemini, &schema, &params);
+        assert!(body.get("model").is_none());
+        assert_eq!(body["contents"][0]["parts"][0]["text"], "hi");
+        assert_eq!(
+            body["generationConfig"],
+            json!({
+                "maxOutputTokens": 256,
+                "responseMimeType": "application/json",
+                "responseSchema": schema
+            })
+        );
+
+        let tool = PromptParams {
+            envelope: Some(Envelope::ToolFunction),
+            ..PromptParams::default()
+        };
+        let body = build_request(Target::Gemini, &schema, &tool);
+        assert_eq!(
+            body["tools"][0]["functionDeclarations"][0]["parameters"],
+            schema
+        );
+        assert_eq!(
+            body["toolConfig"]["functionCallingConfig"]["allowedFunctionNames"],
+            json!([DEFAULT_NAME])
+        );
+        assert!(body.get("generationConfig").is_none());
+    }
+}
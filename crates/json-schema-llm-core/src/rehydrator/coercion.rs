@@ -4,6 +4,8 @@
 //! a string was expected). This module walks the original schema and data in
 //! parallel, applying safe, lossless coercions.
 
+use std::collections::HashSet;
+
 use serde_json::Value;
 
 use crate::codec_warning::{Warning, WarningKind};
@@ -22,16 +24,34 @@ use crate::schema_utils::escape_pointer_segment;
 /// | boolean | `"string"` | `"true"` / `"false"` |
 /// | `"string"` | `"number"` | parse as f64 (validate roundtrip) |
 /// | `"string"` | `"integer"` | parse as i64 (validate roundtrip) |
+/// | integral float (`1.0`) | `"integer"` | integer `1` |
 ///
-/// Returns warnings for each coercion applied.
+/// A non-integral number where `"integer"` was expected is left as is and
+/// reported. Returns warnings for each coercion applied.
 pub fn coerce_types(data: &mut Value, original_schema: &Value) -> Vec<Warning> {
+    coerce_types_recording(data, original_schema, &mut HashSet::new())
+}
+
+/// [`coerce_types`], also adding to `coerced` the data path (`""` for the
+/// root) of every value whose JSON type was changed.
+pub(crate) fn coerce_types_recording(
+    data: &mut Value,
+    original_schema: &Value,
+    coerced: &mut HashSet<String>,
+) -> Vec<Warning> {
     let mut warnings = Vec::new();
-    coerce_walk(data, original_schema, "", &mut warnings);
+    coerce_walk(data, original_schema, "", &mut warnings, coerced);
     warnings
 }
 
 /// Recursive walker for type coercion.
-fn coerce_walk(data: &mut Value, schema: &Value, path: &str, warnings: &mut Vec<Warning>) {
+fn coerce_walk(
+    data: &mut Value,
+    schema: &Value,
+    path: &str,
+    warnings: &mut Vec<Warning>,
+    coerced: &mut HashSet<String>,
+) {
     let schema_obj = match schema.as_object() {
         Some(o) => o,
         None => return,
@@ -44,26 +64,32 @@ fn coerce_walk(data: &mut Value, schema: &Value, path: &str, warnings: &mut Vec<
         _ => vec![],
     };
 
-    // If data is null and null is an allowed type, skip coercion
-    if data.is_null() && expected_types.contains(&"null") {
+    // If data is null and null is an allowed type (including OpenAPI 3.0
+    // `nullable: true`), skip coercion
+    let nullable = schema_obj.get("nullable") == Some(&Value::Bool(true));
+    if data.is_null() && (nullable || expected_types.contains(&"null")) {
         return;
     }
 
     // --- Attempt type coercion at this node ---
     if !expected_types.is_empty() {
+        let actual_type = json_type_name(data);
         if let Some(msg) = try_coerce(data, &expected_types) {
-            warnings.push(Warning {
-                data_path: if path.is_empty() {
+            if json_type_name(data) != actual_type {
+                coerced.insert(path.to_string());
+            }
+            warnings.push(Warning::new(
+                if path.is_empty() {
                     "/".to_string()
                 } else {
                     path.to_string()
                 },
-                schema_path: path.to_string(),
-                kind: WarningKind::ConstraintViolation {
+                path.to_string(),
+                WarningKind::ConstraintViolation {
                     constraint: "type".to_string(),
                 },
-                message: msg,
-            });
+                msg,
+            ));
         }
     }
 
@@ -80,7 +106,7 @@ fn coerce_walk(data: &mut Value, schema: &Value, path: &str, warnings: &mut Vec<
                 if let Some(prop_schema) = props.get(&key) {
                     let child_path = format!("{}/{}", path, escape_pointer_segment(&key));
                     if let Some(child_data) = data_obj.get_mut(&key) {
-                        coerce_walk(child_data, prop_schema, &child_path, warnings);
+                        coerce_walk(child_data, prop_schema, &child_path, warnings, coerced);
                     }
                 }
             }
@@ -100,9 +126,9 @@ fn coerce_walk(data: &mut Value, schema: &Value, path: &str, warnings: &mut Vec<
                 let child_path = format!("{}/{}", path, i);
                 // Use positional schema from prefixItems if available, else fallback to items
                 if let Some(positional) = prefix_items.and_then(|pi| pi.get(i)) {
-                    coerce_walk(item, positional, &child_path, warnings);
+                    coerce_walk(item, positional, &child_path, warnings, coerced);
                 } else if let Some(uniform) = items_schema {
-                    coerce_walk(item, uniform, &child_path, warnings);
+                    coerce_walk(item, uniform, &child_path, warnings, coerced);
                 }
             }
         }
@@ -122,7 +148,7 @@ fn coerce_walk(data: &mut Value, schema: &Value, path: &str, warnings: &mut Vec<
                 let matches = variant_types.contains(&data_type)
                     || (data_type == "integer" && variant_types.contains(&"number"));
                 if matches {
-                    coerce_walk(data, variant, path, warnings);
+                    coerce_walk(data, variant, path, warnings, coerced);
                     break;
                 }
             }
@@ -131,7 +157,8 @@ fn coerce_walk(data: &mut Value, schema: &Value, path: &str, warnings: &mut Vec<
 }
 
 /// Attempt to coerce a value to match one of the expected types.
-/// Returns `Some(message)` if coercion was applied, `None` if no action needed.
+/// Returns `Some(message)` if coercion was applied or a fractional number sits
+/// where an integer is expected, `None` if no action needed.
 fn try_coerce(value: &mut Value, expected_types: &[&str]) -> Option<String> {
     let actual_type = json_type_name(value);
 
@@ -161,6 +188,15 @@ fn try_coerce(value: &mut Value, expected_types: &[&str]) -> Option<String> {
                 _ => {}
             },
             "integer" => {
+                if let Some(f) = value.as_f64().filter(|_| value.is_f64()) {
+                    // Integral floats beyond i64 are still integers in JSON
+                    // Schema terms; leave them alone.
+                    if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+                        let n = f as i64;
+                        *value = Value::Number(serde_json::Number::from(n));
+                        return Some(format!("coerced number {:?} to integer {}", f, n));
+                    }
+                }
                 if let Some(s) = value.as_str() {
                     if let Ok(n) = s.parse::<i64>() {
                         // Roundtrip check: parsed.to_string() == original
@@ -208,6 +244,16 @@ fn try_coerce(value: &mut Value, expected_types: &[&str]) -> Option<String> {
         }
     }
 
+    // No other expected type took it: a fractional number stays, but is flagged.
+    if expected_types.contains(&"integer") {
+        if let Some(f) = value.as_f64().filter(|f| f.fract() != 0.0) {
+            return Some(format!(
+                "non-integral number {} where integer expected; left unchanged",
+                f
+            ));
+        }
+    }
+
     None
 }
 
@@ -5,15 +5,16 @@
 //! Also houses `locate_data_nodes` and `collect_data_paths` for schema-path
 //! → data-node resolution, since they are exclusively used by constraint logic.
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
-use regex::Regex;
-use serde_json::{json, Value};
+use super::pattern::Regex;
+use serde_json::{json, Number, Value};
 
 use super::{SKIP_PAIR, SKIP_SINGLE};
-use crate::codec::Codec;
+use crate::codec::{Codec, Transform};
 use crate::codec_warning::{Warning, WarningKind};
-use crate::schema_utils::{escape_pointer_segment, split_path};
+use crate::schema_utils::{build_path, escape_pointer_segment, split_path};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -24,6 +25,7 @@ const ADVISORY_CONSTRAINTS: &[&str] = &["if", "then", "else"];
 
 /// Constraints that can be automatically enforced by modifying data.
 const ENFORCEABLE_CONSTRAINTS: &[&str] = &[
+    "const",
     "maximum",
     "minimum",
     "exclusiveMaximum",
@@ -58,31 +60,26 @@ pub(crate) fn validate_constraints(
                 match regex_cache.get(pat) {
                     Some(Err(err)) => {
                         // Invalid regex (Err contains error string from compilation)
-                        warnings.push(Warning {
-                            data_path: "/".to_string(),
-                            schema_path: dc.path.clone(),
-                            kind: WarningKind::ConstraintUnevaluable {
+                        warnings.push(Warning::new(
+                            "/",
+                            dc.path.clone(),
+                            WarningKind::ConstraintUnevaluable {
                                 constraint: "pattern".to_string(),
                             },
-                            message: format!(
+                            format!(
                                 "constraint 'pattern' ({}) cannot be validated: {}",
                                 pat, err
                             ),
-                        });
+                        ));
                     }
                     None => {
                         // Cache miss (internal error - should never happen)
-                        warnings.push(Warning {
-                            data_path: "/".to_string(),
-                            schema_path: dc.path.clone(),
-                            kind: WarningKind::ConstraintUnevaluable {
+                        warnings.push(Warning::new("/", dc.path.clone(), WarningKind::ConstraintUnevaluable {
                                 constraint: "pattern".to_string(),
-                            },
-                            message: format!(
+                            }, format!(
                                 "constraint 'pattern' ({}) cannot be validated: regex missing from cache (internal error)",
                                 pat
-                            ),
-                        });
+                            )));
                     }
                     Some(Ok(_)) => {
                         // Valid regex, no warning needed during validation_constraints pass
@@ -91,17 +88,17 @@ pub(crate) fn validate_constraints(
                 }
             } else {
                 // Non-string pattern value — cannot evaluate
-                warnings.push(Warning {
-                    data_path: "/".to_string(),
-                    schema_path: dc.path.clone(),
-                    kind: WarningKind::ConstraintUnevaluable {
+                warnings.push(Warning::new(
+                    "/",
+                    dc.path.clone(),
+                    WarningKind::ConstraintUnevaluable {
                         constraint: "pattern".to_string(),
                     },
-                    message: format!(
+                    format!(
                         "constraint 'pattern' value ({}) is not a string and cannot be validated",
                         dc.value
                     ),
-                });
+                ));
             }
         }
     }
@@ -109,17 +106,21 @@ pub(crate) fn validate_constraints(
     for dc in &codec.dropped_constraints {
         // Advisory constraints — just note they were dropped
         if ADVISORY_CONSTRAINTS.contains(&dc.constraint.as_str()) {
-            warnings.push(Warning {
-                data_path: "/".to_string(),
-                schema_path: dc.path.clone(),
-                kind: WarningKind::ConstraintUnevaluable {
+            warnings.push(Warning::new(
+                "/",
+                dc.path.clone(),
+                WarningKind::ConstraintUnevaluable {
                     constraint: dc.constraint.clone(),
                 },
-                message: format!(
+                format!(
                     "constraint '{}' was dropped during compilation and cannot be validated",
                     dc.constraint
                 ),
-            });
+            ));
+            continue;
+        }
+
+        if dc.constraint == "const" && !const_applies(&dc.path, codec) {
             continue;
         }
 
@@ -149,18 +150,18 @@ pub(crate) fn validate_constraints(
 
         for (data_path, value) in &nodes {
             if let Some(warning) = check_constraint(value, &dc.constraint, &dc.value, regex_cache) {
-                warnings.push(Warning {
-                    data_path: if data_path.is_empty() {
+                warnings.push(Warning::new(
+                    if data_path.is_empty() {
                         "/".to_string()
                     } else {
                         data_path.clone()
                     },
-                    schema_path: dc.path.clone(),
-                    kind: WarningKind::ConstraintViolation {
+                    dc.path.clone(),
+                    WarningKind::ConstraintViolation {
                         constraint: dc.constraint.clone(),
                     },
-                    message: warning,
-                });
+                    warning,
+                ));
             }
         }
     }
@@ -175,6 +176,10 @@ pub(crate) fn validate_constraints(
 /// Enforce dropped constraints by modifying data in-place.
 ///
 /// For constraints where we can safely fix violations without data loss:
+/// - `const`: restore the exact constant when type coercion changed the
+///   value's JSON type (`coerced` holds those data paths) and it equals the
+///   constant up to type (`7` coerced to `"7"` for `"007"`), or when it is
+///   the same number (`2` for `2.0`)
 /// - `maximum` / `minimum`: clamp numeric values
 /// - `exclusiveMaximum` / `exclusiveMinimum`: clamp to boundary ± 1
 /// - `maxLength`: truncate strings
@@ -185,6 +190,7 @@ pub(crate) fn enforce_constraints(
     data: &mut Value,
     codec: &Codec,
     regex_cache: &HashMap<String, Result<Regex, String>>,
+    coerced: &HashSet<String>,
 ) -> Vec<Warning> {
     let mut warnings = Vec::new();
 
@@ -192,6 +198,9 @@ pub(crate) fn enforce_constraints(
         if !ENFORCEABLE_CONSTRAINTS.contains(&dc.constraint.as_str()) {
             continue;
         }
+        if dc.constraint == "const" && !const_applies(&dc.path, codec) {
+            continue;
+        }
 
         // Locate mutable data nodes for this constraint's path
         let segments = split_path(&dc.path);
@@ -212,19 +221,24 @@ pub(crate) fn enforce_constraints(
                 None => continue,
             };
 
-            if let Some(msg) = enforce_single_constraint(node, &dc.constraint, &dc.value) {
-                warnings.push(Warning {
-                    data_path: if data_path.is_empty() {
+            let action = if dc.constraint == "const" {
+                restore_const(node, &dc.value, coerced.contains(data_path))
+            } else {
+                enforce_single_constraint(node, &dc.constraint, &dc.value)
+            };
+            if let Some(msg) = action {
+                warnings.push(Warning::new(
+                    if data_path.is_empty() {
                         "/".to_string()
                     } else {
                         data_path.clone()
                     },
-                    schema_path: dc.path.clone(),
-                    kind: WarningKind::ConstraintViolation {
+                    dc.path.clone(),
+                    WarningKind::ConstraintViolation {
                         constraint: dc.constraint.clone(),
                     },
-                    message: msg,
-                });
+                    msg,
+                ));
             }
         }
     }
@@ -232,6 +246,106 @@ pub(crate) fn enforce_constraints(
     warnings
 }
 
+/// Whether the `const` dropped at `path` binds every data node the path
+/// resolves to. Under a union branch it only binds values taking that
+/// branch, which the data walker cannot tell apart, so it is skipped —
+/// unless the union is the `anyOf: [X, {type: null}]` wrapper of a nullable
+/// optional property, whose `null` is removed before constraints run.
+fn const_applies(path: &str, codec: &Codec) -> bool {
+    let segments = split_path(path);
+    let nullable_wrapper = |prefix: &[String]| {
+        let prefix: Vec<&str> = prefix.iter().map(String::as_str).collect();
+        let wrapper = build_path("#", &prefix);
+        codec
+            .transforms
+            .iter()
+            .any(|t| matches!(t, Transform::NullableOptional { path, .. } if *path == wrapper))
+    };
+    let mut pos = 0;
+    while pos < segments.len() {
+        match segments[pos].as_str() {
+            "anyOf" | "oneOf" if !nullable_wrapper(&segments[..pos]) => return false,
+            // Keyword + name or index: the second segment is never a keyword.
+            "anyOf" | "oneOf" | "allOf" | "properties" | "patternProperties" | "$defs"
+            | "definitions" | "dependentSchemas" => pos += 2,
+            _ => pos += 1,
+        }
+    }
+    true
+}
+
+/// Whether `value` is `constant` up to JSON type: numbers (or numeric
+/// strings) of equal value, or a string spelling the scalar.
+fn equals_up_to_type(value: &Value, constant: &Value) -> bool {
+    let text = |v: &Value| match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(_) | Value::Null | Value::Number(_) => Some(v.to_string()),
+        _ => None,
+    };
+    match (numeric(value), numeric(constant)) {
+        (Some(a), Some(b)) => compare_numbers(&a, &b) == Some(Ordering::Equal),
+        _ => text(value).is_some_and(|t| text(constant) == Some(t)),
+    }
+}
+
+/// A number, or a string spelling one (`" 007"` reads as 7).
+fn numeric(value: &Value) -> Option<Number> {
+    match value {
+        Value::Number(n) => Some(n.clone()),
+        Value::String(s) => {
+            let s = s.trim();
+            let digits = s.strip_prefix('-').unwrap_or(s);
+            if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                let trimmed = digits.trim_start_matches('0');
+                let magnitude = if trimmed.is_empty() { "0" } else { trimmed };
+                let sign = if digits.len() < s.len() { "-" } else { "" };
+                format!("{}{}", sign, magnitude).parse().ok()
+            } else {
+                s.parse::<f64>()
+                    .ok()
+                    .filter(|f| f.is_finite())
+                    .and_then(Number::from_f64)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Order two numbers, exactly when both are integers (however large, under
+/// `arbitrary_precision`) and through `f64` otherwise.
+fn compare_numbers(a: &Number, b: &Number) -> Option<Ordering> {
+    match (integer_text(a), integer_text(b)) {
+        (Some((neg_a, a)), Some((neg_b, b))) => {
+            let magnitude = a.len().cmp(&b.len()).then_with(|| a.cmp(&b));
+            Some(match (neg_a, neg_b) {
+                (false, false) => magnitude,
+                (true, true) => magnitude.reverse(),
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+            })
+        }
+        _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+    }
+}
+
+/// Sign and digits of an integer-valued number's text; zero is never
+/// negative.
+fn integer_text(n: &Number) -> Option<(bool, String)> {
+    let text = n.to_string();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.as_str()),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Some((false, "0".to_string()));
+    }
+    Some((negative, digits.to_string()))
+}
+
 // ---------------------------------------------------------------------------
 // Data node resolution
 // ---------------------------------------------------------------------------
@@ -279,21 +393,16 @@ fn locate_data_nodes<'d>(
             if let Some(obj) = data.as_object() {
                 // Extract pattern from the next segment; bail with warning if missing
                 let Some(pattern_segment) = ctx.segments.get(pos + 1) else {
-                    tracing::warn!(
+                    crate::logging::warn!(
                         "missing regex segment after patternProperties in schema path, skipping"
                     );
-                    warnings.push(Warning {
-                        data_path: if current_data_path.is_empty() {
+                    warnings.push(Warning::new(if current_data_path.is_empty() {
                             "/".to_string()
                         } else {
                             current_data_path.clone()
-                        },
-                        schema_path: ctx.schema_path.to_string(),
-                        kind: WarningKind::ConstraintUnevaluable {
+                        }, ctx.schema_path.to_string(), WarningKind::ConstraintUnevaluable {
                             constraint: "patternProperties".to_string(),
-                        },
-                        message: "missing regex segment after patternProperties in schema path; constraint cannot be evaluated".to_string(),
-                    });
+                        }, "missing regex segment after patternProperties in schema path; constraint cannot be evaluated"));
                     return;
                 };
                 let pattern = pattern_segment.as_str();
@@ -320,48 +429,43 @@ fn locate_data_nodes<'d>(
                     }
                     Some(Err(err)) => {
                         // Invalid regex (Err contains compile error string)
-                        tracing::debug!(
+                        crate::logging::debug!(
                             pattern,
                             error = %err,
                             "patternProperties regex invalid, skipping constraint path"
                         );
-                        warnings.push(Warning {
-                            data_path: if current_data_path.is_empty() {
+                        warnings.push(Warning::new(
+                            if current_data_path.is_empty() {
                                 "/".to_string()
                             } else {
                                 current_data_path.clone()
                             },
-                            schema_path: ctx.schema_path.to_string(),
-                            kind: WarningKind::ConstraintUnevaluable {
+                            ctx.schema_path.to_string(),
+                            WarningKind::ConstraintUnevaluable {
                                 constraint: "patternProperties".to_string(),
                             },
-                            message: format!(
+                            format!(
                                 "patternProperties regex '{}' cannot be evaluated: {}",
                                 pattern, err
                             ),
-                        });
+                        ));
                     }
                     None => {
                         // Cache miss (internal error - should never happen)
-                        tracing::debug!(
+                        crate::logging::debug!(
                             pattern,
                             "patternProperties regex not in cache, skipping constraint path"
                         );
-                        warnings.push(Warning {
-                            data_path: if current_data_path.is_empty() {
+                        warnings.push(Warning::new(if current_data_path.is_empty() {
                                 "/".to_string()
                             } else {
                                 current_data_path.clone()
-                            },
-                            schema_path: ctx.schema_path.to_string(),
-                            kind: WarningKind::ConstraintUnevaluable {
+                            }, ctx.schema_path.to_string(), WarningKind::ConstraintUnevaluable {
                                 constraint: "patternProperties".to_string(),
-                            },
-                            message: format!(
+                            }, format!(
                                 "patternProperties regex '{}' cannot be evaluated: regex missing from cache (internal error)",
                                 pattern
-                            ),
-                        });
+                            )));
                     }
                 }
             }
@@ -646,6 +750,28 @@ fn check_constraint(
             }
             None
         }
+        "const" => {
+            if value == expected {
+                None
+            } else {
+                Some(format!(
+                    "value {} does not equal constant {}",
+                    value, expected
+                ))
+            }
+        }
+        "enum" => {
+            let allowed = expected.as_array()?;
+            if allowed.contains(value) {
+                None
+            } else {
+                Some(format!(
+                    "value {} is not one of the {} allowed enum values",
+                    value,
+                    allowed.len()
+                ))
+            }
+        }
         "minLength" => {
             let s = value.as_str()?;
             let bound = expected.as_u64()? as usize;
@@ -698,6 +824,64 @@ fn check_constraint(
                 None
             }
         }
+        "minProperties" => {
+            let obj = value.as_object()?;
+            let bound = expected.as_u64()? as usize;
+            if obj.len() < bound {
+                Some(format!(
+                    "object has {} properties, fewer than minProperties {}",
+                    obj.len(),
+                    bound
+                ))
+            } else {
+                None
+            }
+        }
+        "maxProperties" => {
+            let obj = value.as_object()?;
+            let bound = expected.as_u64()? as usize;
+            if obj.len() > bound {
+                Some(format!(
+                    "object has {} properties, more than maxProperties {}",
+                    obj.len(),
+                    bound
+                ))
+            } else {
+                None
+            }
+        }
+        "unevaluatedProperties" => {
+            // Recorded by p1 as the property names some subschema declares.
+            let obj = value.as_object()?;
+            let evaluated = expected.as_array()?;
+            let extra: Vec<&str> = obj
+                .keys()
+                .filter(|k| !evaluated.iter().any(|e| e == k.as_str()))
+                .map(String::as_str)
+                .collect();
+            if extra.is_empty() {
+                None
+            } else {
+                Some(format!(
+                    "properties {:?} are not declared by any subschema (unevaluatedProperties: false)",
+                    extra
+                ))
+            }
+        }
+        "unevaluatedItems" => {
+            // Recorded by p1 as the number of positions `prefixItems` covers.
+            let arr = value.as_array()?;
+            let bound = expected.as_u64()? as usize;
+            if arr.len() > bound {
+                Some(format!(
+                    "array length {} exceeds the {} evaluated items (unevaluatedItems: false)",
+                    arr.len(),
+                    bound
+                ))
+            } else {
+                None
+            }
+        }
         _ => None,
     }
 }
@@ -706,6 +890,28 @@ fn check_constraint(
 // Constraint enforcement (in-place modification)
 // ---------------------------------------------------------------------------
 
+/// Restore the exact `constant` over a value equal to it up to type.
+///
+/// `2` for `2.0` is the same number and is restored without a warning. Any
+/// other difference is only undone when coercion changed the value's JSON
+/// type (`coerced`); a value the model produced with the constant's type,
+/// such as `"7"` for `"007"`, is left for validation to report.
+fn restore_const(value: &mut Value, constant: &Value, coerced: bool) -> Option<String> {
+    if value == constant || !equals_up_to_type(value, constant) {
+        return None;
+    }
+    if value.is_number() && constant.is_number() {
+        *value = constant.clone();
+        return None;
+    }
+    if !coerced {
+        return None;
+    }
+    let msg = format!("value {} restored to constant {}", value, constant);
+    *value = constant.clone();
+    Some(msg)
+}
+
 /// Enforce a single constraint on a value, modifying it in-place if needed.
 ///
 /// Returns `Some(message)` if the value was modified, `None` if no action needed.
@@ -716,42 +922,31 @@ fn enforce_single_constraint(
 ) -> Option<String> {
     match constraint {
         "maximum" => {
-            let bound_f = expected.as_f64()?;
-            let actual_f = value.as_f64()?;
-            if actual_f > bound_f {
-                let msg = format!("value {} exceeded maximum {}; clamped", actual_f, bound_f);
-                // Preserve integer type if the bound is integer
-                if let Some(bound_i) = expected.as_i64() {
-                    *value = Value::Number(serde_json::Number::from(bound_i));
-                } else {
-                    *value = json!(bound_f);
-                }
+            let (actual, bound) = (value.as_number()?, expected.as_number()?);
+            if compare_numbers(actual, bound)? == Ordering::Greater {
+                let msg = format!("value {} exceeded maximum {}; clamped", actual, bound);
+                *value = Value::Number(bound.clone());
                 return Some(msg);
             }
             None
         }
         "minimum" => {
-            let bound_f = expected.as_f64()?;
-            let actual_f = value.as_f64()?;
-            if actual_f < bound_f {
-                let msg = format!("value {} below minimum {}; clamped", actual_f, bound_f);
-                if let Some(bound_i) = expected.as_i64() {
-                    *value = Value::Number(serde_json::Number::from(bound_i));
-                } else {
-                    *value = json!(bound_f);
-                }
+            let (actual, bound) = (value.as_number()?, expected.as_number()?);
+            if compare_numbers(actual, bound)? == Ordering::Less {
+                let msg = format!("value {} below minimum {}; clamped", actual, bound);
+                *value = Value::Number(bound.clone());
                 return Some(msg);
             }
             None
         }
         "exclusiveMaximum" => {
-            let bound_f = expected.as_f64()?;
-            let actual_f = value.as_f64()?;
-            if actual_f >= bound_f {
+            let (actual, bound) = (value.as_number()?, expected.as_number()?);
+            if compare_numbers(actual, bound)? != Ordering::Less {
                 // Clamp to bound - 1 for integers, bound - epsilon for floats
+                let bound_f = bound.as_f64()?;
                 let msg = format!(
                     "value {} not less than exclusive maximum {}; clamped",
-                    actual_f, bound_f
+                    actual, bound
                 );
                 if let Some(bound_i) = expected.as_i64() {
                     if let Some(clamped) = bound_i.checked_sub(1) {
@@ -767,12 +962,12 @@ fn enforce_single_constraint(
             None
         }
         "exclusiveMinimum" => {
-            let bound_f = expected.as_f64()?;
-            let actual_f = value.as_f64()?;
-            if actual_f <= bound_f {
+            let (actual, bound) = (value.as_number()?, expected.as_number()?);
+            if compare_numbers(actual, bound)? != Ordering::Greater {
+                let bound_f = bound.as_f64()?;
                 let msg = format!(
                     "value {} not greater than exclusive minimum {}; clamped",
-                    actual_f, bound_f
+                    actual, bound
                 );
                 if let Some(bound_i) = expected.as_i64() {
                     if let Some(clamped) = bound_i.checked_add(1) {
@@ -932,6 +1127,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "regex")]
     fn collect_data_paths_pattern_properties() {
         let data = json!({"foo_1": "a", "foo_2": "b", "bar": "c"});
         let segments: Vec<String> = ["patternProperties", "^foo_"]
@@ -991,6 +1187,22 @@ mod tests {
         assert_eq!(value, json!(100));
     }
 
+    #[test]
+    fn enforce_maximum_compares_large_integers_exactly() {
+        let mut value = json!(9007199254740993u64);
+        let result = enforce_single_constraint(&mut value, "maximum", &json!(9007199254740992u64));
+        assert_eq!(
+            result.as_deref(),
+            Some("value 9007199254740993 exceeded maximum 9007199254740992; clamped")
+        );
+        assert_eq!(value, json!(9007199254740992u64));
+
+        let mut value = json!(-9007199254740993i64);
+        let result = enforce_single_constraint(&mut value, "minimum", &json!(-9007199254740992i64));
+        assert!(result.is_some());
+        assert_eq!(value, json!(-9007199254740992i64));
+    }
+
     #[test]
     fn enforce_minimum_clamps_integer() {
         let mut value = json!(5);
@@ -1005,4 +1217,86 @@ mod tests {
         let result = enforce_single_constraint(&mut value, "unknownConstraint", &json!(42));
         assert!(result.is_none());
     }
+
+    #[test]
+    fn restore_const_restores_coerced_constant() {
+        let mut value = json!("7");
+        let result = restore_const(&mut value, &json!("007"), true);
+        assert_eq!(
+            result.as_deref(),
+            Some("value \"7\" restored to constant \"007\"")
+        );
+        assert_eq!(value, json!("007"));
+
+        let mut value = json!("true");
+        assert!(restore_const(&mut value, &json!(true), true).is_some());
+        assert_eq!(value, json!(true));
+    }
+
+    #[test]
+    fn restore_const_restores_number_representation_silently() {
+        let mut value = json!(2);
+        assert!(restore_const(&mut value, &json!(2.0), false).is_none());
+        assert_eq!(value, json!(2.0));
+    }
+
+    #[test]
+    fn restore_const_leaves_distinct_integers_sharing_an_f64() {
+        // 2^53 and 2^53 + 1 round to the same f64.
+        let mut value = json!(9007199254740992u64);
+        assert!(restore_const(&mut value, &json!(9007199254740993u64), false).is_none());
+        assert_eq!(value, json!(9007199254740992u64));
+        assert!(!equals_up_to_type(
+            &json!("9007199254740992"),
+            &json!(9007199254740993u64)
+        ));
+    }
+
+    #[test]
+    fn compare_numbers_orders_integers_exactly() {
+        let n = |v: Value| v.as_number().unwrap().clone();
+        let cmp = |a: Value, b: Value| compare_numbers(&n(a), &n(b));
+        assert_eq!(
+            cmp(json!(9007199254740993u64), json!(9007199254740992u64)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(cmp(json!(-3), json!(-12)), Some(Ordering::Greater));
+        assert_eq!(cmp(json!(-1), json!(0)), Some(Ordering::Less));
+        assert_eq!(cmp(json!(2), json!(2.0)), Some(Ordering::Equal));
+        assert_eq!(cmp(json!(1.5), json!(2)), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn restore_const_leaves_uncoerced_strings() {
+        for text in ["7", "7.0", " 7"] {
+            let mut value = json!(text);
+            assert!(restore_const(&mut value, &json!("007"), false).is_none());
+            assert_eq!(value, json!(text));
+        }
+    }
+
+    #[test]
+    fn restore_const_leaves_other_values() {
+        let mut value = json!("other");
+        assert!(restore_const(&mut value, &json!("007"), true).is_none());
+        assert_eq!(value, json!("other"));
+    }
+
+    // -----------------------------------------------------------------------
+    // const_applies
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn const_applies_outside_unions_and_nullable_wrappers() {
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::NullableOptional {
+            path: "#/properties/opt".to_string(),
+            original_required: false,
+        });
+        assert!(const_applies("#/properties/kind", &codec));
+        assert!(const_applies("#/properties/anyOf/items", &codec));
+        assert!(const_applies("#/properties/opt/anyOf/0", &codec));
+        assert!(!const_applies("#/properties/size/anyOf/1", &codec));
+        assert!(!const_applies("#/oneOf/0/properties/kind", &codec));
+    }
 }
@@ -6,23 +6,30 @@
 
 mod coercion;
 mod constraints;
+mod pattern;
 mod transforms;
 mod walker;
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 
-use regex::Regex;
+use pattern::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::codec::{Codec, Transform, CODEC_MAJOR_VERSION};
+use crate::codec_warning::{Warning, WarningKind};
 use crate::error::ConvertError;
 use crate::schema_utils::split_path;
 
 // Re-export public API items
 pub use coercion::coerce_types;
+pub(crate) use coercion::coerce_types_recording;
 pub(crate) use constraints::{enforce_constraints, validate_constraints};
+use transforms::resolve_node_refs;
 use walker::apply_transform;
+pub(crate) use walker::{walk, Rehydrate, TransformSite};
 
 /// Result of rehydration, including the restored data and any warnings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +40,198 @@ pub struct RehydrateResult {
     pub warnings: Vec<crate::codec_warning::Warning>,
 }
 
+/// Rehydration settings: handlers for [`Transform::Custom`] entries,
+/// repairs of near-miss output, and bounds on the warnings returned.
+///
+/// Deserializes from `kebab-case` keys (e.g. `max-warnings`), missing keys
+/// taking their defaults, for the FFI bridges. Handlers are registered in
+/// code only.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+#[non_exhaustive]
+pub struct RehydrateOptions {
+    #[serde(skip)]
+    handlers: HashMap<String, TransformHandler>,
+    /// Merge warnings of the same kind at the same schema path (typically
+    /// one per array element) into a single entry with a
+    /// [`count`](Warning::count), keeping up to this many
+    /// [`sample_paths`](Warning::sample_paths). Default: `None`, one warning
+    /// per occurrence.
+    pub aggregate_warnings: Option<usize>,
+    /// Keep at most this many warnings, after aggregation, followed by one
+    /// [`WarningKind::Truncated`] entry counting the rest. Default: `None`,
+    /// unlimited.
+    pub max_warnings: Option<usize>,
+    /// What to do when a restored map (a [`Transform::MapToArray`] array)
+    /// has two entries with the same key. Default:
+    /// [`DuplicateKeyPolicy::Warn`].
+    pub duplicate_key_policy: DuplicateKeyPolicy,
+    /// Rewrite a discriminator value that matches one of its union's
+    /// variants only up to case and surrounding whitespace (`"Dog "` for
+    /// `"dog"`) to that variant's value, with a
+    /// [`WarningKind::DiscriminatorNormalized`]. Default: `true`.
+    pub normalize_discriminators: bool,
+    /// Most data nodes that resolving [`Transform::RecursiveNodeRef`]
+    /// references may copy in, in total. References past the budget are kept
+    /// with a [`WarningKind::UnresolvedNodeRef`], so output where each node
+    /// references the next several times cannot grow exponentially.
+    /// Default: 100 000.
+    pub max_expanded_nodes: usize,
+}
+
+impl Default for RehydrateOptions {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            aggregate_warnings: None,
+            max_warnings: None,
+            duplicate_key_policy: DuplicateKeyPolicy::default(),
+            normalize_discriminators: true,
+            max_expanded_nodes: 100_000,
+        }
+    }
+}
+
+/// How [`Transform::MapToArray`] restoration resolves entries that repeat a
+/// key. The model has no way to express a map key twice, so a repeat means
+/// one of the values is lost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateKeyPolicy {
+    /// Keep the last value, silently.
+    LastWins,
+    /// Keep the first value, silently.
+    FirstWins,
+    /// Keep the last value and report a [`WarningKind::DuplicateKey`].
+    #[default]
+    Warn,
+    /// Fail rehydration.
+    Error,
+}
+
+/// Compiled `pattern` / `patternProperties` regexes, keyed by source.
+pub(crate) type RegexCache = HashMap<String, Result<Regex, String>>;
+
+/// A codec prepared for repeated rehydration: its version is checked and its
+/// regexes compiled once, instead of on every call.
+///
+/// Build one per conversion and pass it to
+/// [`rehydrate_compiled`](crate::rehydrate_compiled) for each response.
+#[derive(Debug, Clone)]
+pub struct CompiledCodec {
+    codec: Codec,
+    regex_cache: RegexCache,
+}
+
+impl CompiledCodec {
+    /// Check `codec`'s version and compile its patterns. Fails like
+    /// [`rehydrate`](crate::rehydrate) does for an incompatible codec.
+    pub fn new(codec: Codec) -> Result<Self, ConvertError> {
+        validate_codec_version(&codec)?;
+        let regex_cache = build_pattern_properties_cache(&codec);
+        Ok(Self { codec, regex_cache })
+    }
+
+    /// The underlying codec.
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+
+    pub(crate) fn regex_cache(&self) -> &RegexCache {
+        &self.regex_cache
+    }
+}
+
+/// Reverses one kind of custom transform. Called with the data at the
+/// transform's path and the transform's `payload`; may rewrite the data in
+/// place. Errors abort rehydration.
+pub type TransformHandler = Arc<HandlerFn>;
+
+type HandlerFn = dyn Fn(&mut Value, &Value) -> Result<(), ConvertError> + Send + Sync;
+
+impl RehydrateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the handler for custom transforms of `kind`, replacing any
+    /// previous one.
+    pub fn register_handler(
+        &mut self,
+        kind: impl Into<String>,
+        handler: impl Fn(&mut Value, &Value) -> Result<(), ConvertError> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.handlers.insert(kind.into(), Arc::new(handler));
+        self
+    }
+
+    pub(crate) fn handler(&self, kind: &str) -> Option<&TransformHandler> {
+        self.handlers.get(kind)
+    }
+
+    /// Apply [`aggregate_warnings`](Self::aggregate_warnings) and
+    /// [`max_warnings`](Self::max_warnings) to a finished warning list.
+    pub(crate) fn bound_warnings(&self, warnings: Vec<Warning>) -> Vec<Warning> {
+        let mut warnings = match self.aggregate_warnings {
+            Some(max_samples) => aggregate(warnings, max_samples),
+            None => warnings,
+        };
+        if let Some(max) = self.max_warnings {
+            if warnings.len() > max {
+                let omitted = warnings.drain(max..).map(|w| w.count).sum();
+                warnings.push(Warning::new(
+                    "/",
+                    "#",
+                    WarningKind::Truncated { omitted },
+                    format!("{} more warning(s) omitted", omitted),
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+/// Merge warnings sharing a schema path and kind, in first-seen order.
+fn aggregate(warnings: Vec<Warning>, max_samples: usize) -> Vec<Warning> {
+    let mut merged: Vec<Warning> = Vec::new();
+    let mut index: HashMap<(String, WarningKind), usize> = HashMap::new();
+    for warning in warnings {
+        let key = (warning.schema_path.clone(), warning.kind.clone());
+        match index.get(&key) {
+            Some(&i) => {
+                let entry = &mut merged[i];
+                if entry.sample_paths.is_empty() {
+                    entry.sample_paths.push(entry.data_path.clone());
+                }
+                entry.count += warning.count;
+                entry.sample_paths.push(warning.data_path);
+                entry.sample_paths.extend(warning.sample_paths);
+                entry.sample_paths.truncate(max_samples);
+            }
+            None => {
+                index.insert(key, merged.len());
+                merged.push(warning);
+            }
+        }
+    }
+    merged
+}
+
+impl fmt::Debug for RehydrateOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut kinds: Vec<&str> = self.handlers.keys().map(String::as_str).collect();
+        kinds.sort_unstable();
+        f.debug_struct("RehydrateOptions")
+            .field("handlers", &kinds)
+            .field("aggregate_warnings", &self.aggregate_warnings)
+            .field("max_warnings", &self.max_warnings)
+            .field("duplicate_key_policy", &self.duplicate_key_policy)
+            .field("normalize_discriminators", &self.normalize_discriminators)
+            .field("max_expanded_nodes", &self.max_expanded_nodes)
+            .finish()
+    }
+}
+
 /// Schema-structural keywords that should be skipped (keyword only).
 pub(super) const SKIP_SINGLE: &[&str] = &[
     "additionalProperties",
@@ -65,14 +264,45 @@ pub(super) const SKIP_PAIR: &[&str] = &[
 /// those are orchestrated by the public `rehydrate()` in `lib.rs` after type
 /// coercion so that constraints evaluate against correctly-typed values.
 pub fn apply_transforms(data: &Value, codec: &Codec) -> Result<RehydrateResult, ConvertError> {
+    apply_transforms_owned(data.clone(), codec)
+}
+
+/// Owning variant of [`apply_transforms`] — mutates `data` in place instead of
+/// cloning it first. Used by the text-in/text-out rehydration path where the
+/// caller already owns a freshly parsed `Value`.
+pub fn apply_transforms_owned(
+    result: Value,
+    codec: &Codec,
+) -> Result<RehydrateResult, ConvertError> {
+    apply_transforms_with_options(result, codec, &RehydrateOptions::default())
+}
+
+/// [`apply_transforms_owned`] with handlers for [`Transform::Custom`]
+/// entries. Custom transforms without a handler are skipped and reported as
+/// [`WarningKind::UnhandledTransform`].
+pub fn apply_transforms_with_options(
+    result: Value,
+    codec: &Codec,
+    options: &RehydrateOptions,
+) -> Result<RehydrateResult, ConvertError> {
     // Validate codec version — hard-fail on incompatible major version
     validate_codec_version(codec)?;
 
-    let mut result = data.clone();
-
     // Pre-compile all patternProperties regexes from transform and constraint paths
     let regex_cache = build_pattern_properties_cache(codec);
 
+    apply_transforms_cached(result, codec, &regex_cache, options)
+}
+
+/// [`apply_transforms_with_options`] for a codec whose version is already
+/// checked and whose regexes are compiled into `regex_cache`.
+pub(crate) fn apply_transforms_cached(
+    mut result: Value,
+    codec: &Codec,
+    regex_cache: &RegexCache,
+    options: &RehydrateOptions,
+) -> Result<RehydrateResult, ConvertError> {
+    let mut warnings = Vec::new();
     for transform in codec.transforms.iter().rev() {
         let path_str = match transform {
             Transform::MapToArray { path, .. } => path,
@@ -81,15 +311,47 @@ pub fn apply_transforms(data: &Value, codec: &Codec) -> Result<RehydrateResult,
             Transform::DiscriminatorAnyOf { path, .. } => path,
             Transform::ExtractAdditionalProperties { path, .. } => path,
             Transform::RecursiveInflate { path, .. } => path,
+            Transform::RecursiveTerminate { path, .. } => path,
+            Transform::RecursiveNodeRef { path, .. } => path,
             Transform::RootObjectWrapper { path, .. } => path,
             Transform::EnumStringify { path, .. } => path,
+            Transform::AnyOfOverflow { path, .. } => path,
+            Transform::FilteredProperty { path, .. } => path,
+            Transform::ProjectedVariants { path, .. } => path,
+            Transform::JsonStringEncode { path } => path,
+            Transform::Custom { kind, path, .. } => {
+                if options.handler(kind).is_none() {
+                    crate::logging::warn!(kind = %kind, path = %path, "no handler for custom transform");
+                    warnings.push(Warning::new(
+                        "/",
+                        path.clone(),
+                        WarningKind::UnhandledTransform {
+                            transform: kind.clone(),
+                        },
+                        format!(
+                            "No rehydration handler registered for custom transform `{}`",
+                            kind
+                        ),
+                    ));
+                    continue;
+                }
+                path
+            }
         };
 
         let segments = split_path(path_str);
         let seg_refs: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
 
-        tracing::debug!(path = %path_str, "applying transform");
-        apply_transform(&mut result, &seg_refs, transform, &regex_cache)?;
+        crate::logging::debug!(path = %path_str, "applying transform");
+        apply_transform(
+            &mut result,
+            &seg_refs,
+            transform,
+            options,
+            regex_cache,
+            "",
+            &mut warnings,
+        )?;
     }
 
     // ── #120: Replay $defs-sourced transforms at RecursiveInflate sites ──
@@ -100,11 +362,27 @@ pub fn apply_transforms(data: &Value, codec: &Codec) -> Result<RehydrateResult,
     // root-level `properties/data` instead of the nested data inside recursive
     // nodes. After RecursiveInflate has expanded JSON strings into objects,
     // replay those JSP transforms at each RI location.
-    replay_defs_transforms_at_inflate_sites(&mut result, codec, &regex_cache)?;
+    replay_defs_transforms_at_inflate_sites(
+        &mut result,
+        codec,
+        options,
+        regex_cache,
+        &mut warnings,
+    )?;
+
+    // Node references can point anywhere in the output, so they are
+    // resolved against the fully restored tree.
+    if codec
+        .transforms
+        .iter()
+        .any(|t| matches!(t, Transform::RecursiveNodeRef { .. }))
+    {
+        resolve_node_refs(&mut result, options.max_expanded_nodes, &mut warnings);
+    }
 
     Ok(RehydrateResult {
         data: result,
-        warnings: Vec::new(),
+        warnings,
     })
 }
 
@@ -116,10 +394,14 @@ pub fn apply_transforms(data: &Value, codec: &Codec) -> Result<RehydrateResult,
 /// each inline-expanded copy of the recursive type. This function extracts all
 /// intermediate recursion boundaries from the RI path and replays the $defs JSP
 /// suffix at each one (including the RI terminal itself).
+// Some bindings exist only for logging.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
 fn replay_defs_transforms_at_inflate_sites(
     data: &mut Value,
     codec: &Codec,
+    options: &RehydrateOptions,
     regex_cache: &HashMap<String, Result<Regex, String>>,
+    warnings: &mut Vec<Warning>,
 ) -> Result<(), ConvertError> {
     // Collect RecursiveInflate paths and their original $ref values
     let inflate_sites: Vec<(&str, &str)> = codec
@@ -220,12 +502,20 @@ fn replay_defs_transforms_at_inflate_sites(
                     path: synthetic_path.clone(),
                 };
 
-                tracing::debug!(
+                crate::logging::debug!(
                     original_jsp = %jsp_path,
                     concrete_path = %synthetic_path,
                     "replaying $defs JSP at recursive expansion site"
                 );
-                apply_transform(data, &seg_refs, &synthetic_transform, regex_cache)?;
+                apply_transform(
+                    data,
+                    &seg_refs,
+                    &synthetic_transform,
+                    options,
+                    regex_cache,
+                    "",
+                    warnings,
+                )?;
             }
         }
     }
@@ -276,6 +566,7 @@ fn extract_recursion_prefixes(ri_segments: &[String]) -> Vec<Vec<String>> {
 
 /// Pre-scan transform and constraint paths for patternProperties segments
 /// and compile their regex patterns into a reusable cache.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
 pub(crate) fn build_pattern_properties_cache(
     codec: &Codec,
 ) -> HashMap<String, Result<Regex, String>> {
@@ -289,8 +580,15 @@ pub(crate) fn build_pattern_properties_cache(
         Transform::DiscriminatorAnyOf { path, .. } => path.as_str(),
         Transform::ExtractAdditionalProperties { path, .. } => path.as_str(),
         Transform::RecursiveInflate { path, .. } => path.as_str(),
+        Transform::RecursiveTerminate { path, .. } => path.as_str(),
+        Transform::RecursiveNodeRef { path, .. } => path.as_str(),
         Transform::RootObjectWrapper { path, .. } => path.as_str(),
         Transform::EnumStringify { path, .. } => path.as_str(),
+        Transform::AnyOfOverflow { path, .. } => path.as_str(),
+        Transform::FilteredProperty { path, .. } => path.as_str(),
+        Transform::ProjectedVariants { path, .. } => path.as_str(),
+        Transform::JsonStringEncode { path } => path.as_str(),
+        Transform::Custom { path, .. } => path.as_str(),
     });
     let constraint_paths = codec.dropped_constraints.iter().map(|dc| dc.path.as_str());
 
@@ -303,7 +601,7 @@ pub(crate) fn build_pattern_properties_cache(
                 if !cache.contains_key(pattern.as_str()) {
                     let result = Regex::new(pattern).map_err(|e| e.to_string());
                     if let Err(ref err) = result {
-                        tracing::warn!(
+                        crate::logging::warn!(
                             pattern = %pattern,
                             error = %err,
                             "invalid patternProperties regex, will skip at usage sites"
@@ -322,7 +620,7 @@ pub(crate) fn build_pattern_properties_cache(
                 if !cache.contains_key(pat) {
                     let result = Regex::new(pat).map_err(|e| e.to_string());
                     if let Err(ref err) = result {
-                        tracing::warn!(
+                        crate::logging::warn!(
                             pattern = %pat,
                             error = %err,
                             "invalid regex in dropped constraint pattern — will emit ConstraintUnevaluable warning"
@@ -342,7 +640,7 @@ pub(crate) fn build_pattern_properties_cache(
 /// The `$schema` URI is expected to end with `/v{major}` (e.g.
 /// `https://json-schema-llm.dev/codec/v1`). Hard-fails on incompatible
 /// major version or malformed URI.
-fn validate_codec_version(codec: &Codec) -> Result<(), ConvertError> {
+pub(crate) fn validate_codec_version(codec: &Codec) -> Result<(), ConvertError> {
     let uri = &codec.schema;
 
     // Extract the last path segment after the final '/'
@@ -380,7 +678,7 @@ fn validate_codec_version(codec: &Codec) -> Result<(), ConvertError> {
 mod tests {
     use super::*;
     use crate::codec::CODEC_SCHEMA_URI;
-    use crate::codec_warning::WarningKind;
+    use crate::codec_warning::{VariantCandidate, WarningKind};
     use serde_json::json;
 
     /// Helper: run the full rehydration pipeline (transforms + constraints)
@@ -392,7 +690,8 @@ mod tests {
     ) -> Result<RehydrateResult, ConvertError> {
         let mut result = apply_transforms(data, codec)?;
         let regex_cache = build_pattern_properties_cache(codec);
-        let enforcement = enforce_constraints(&mut result.data, codec, &regex_cache);
+        let enforcement =
+            enforce_constraints(&mut result.data, codec, &regex_cache, &HashSet::new());
         let validation = validate_constraints(&result.data, codec, &regex_cache);
         result.warnings.extend(enforcement);
         result.warnings.extend(validation);
@@ -432,6 +731,7 @@ mod tests {
         codec.transforms.push(Transform::MapToArray {
             path: "#/properties/map".to_string(),
             key_field: "key".to_string(),
+            variant_field: None,
         });
 
         let data = json!({
@@ -450,6 +750,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_restore_map_applies_transforms_per_variant() {
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::MapToArray {
+            path: "#/properties/map".to_string(),
+            key_field: "key".to_string(),
+            variant_field: Some("variant".to_string()),
+        });
+        codec.transforms.push(Transform::JsonStringParse {
+            path: "#/properties/map/items/properties/value/anyOf/1".to_string(),
+        });
+
+        // Only the entry in branch 1 holds an encoded object; the branch 0
+        // string must survive as-is even though it parses as JSON.
+        let data = json!({
+            "map": [
+                {"key": "a", "variant": 0, "value": "[1, 2]"},
+                {"key": "b", "variant": 1, "value": "{\"x\": 1}"}
+            ]
+        });
+
+        let result = apply_transforms(&data, &codec).unwrap();
+        assert_eq!(
+            result.data,
+            json!({
+                "map": {"a": "[1, 2]", "b": {"x": 1}}
+            })
+        );
+    }
+
     // Test 3: Parse JSON String
     #[test]
     fn test_parse_json_string() {
@@ -498,6 +828,7 @@ mod tests {
         codec.transforms.push(Transform::MapToArray {
             path: "#/properties/map".to_string(),
             key_field: "key".to_string(),
+            variant_field: None,
         });
 
         let data = json!({
@@ -580,6 +911,7 @@ mod tests {
         codec.transforms.push(Transform::MapToArray {
             path: "#/properties/map".to_string(),
             key_field: "key".to_string(),
+            variant_field: None,
         });
 
         let data = json!({
@@ -594,6 +926,174 @@ mod tests {
         assert_eq!(result.data["map"]["dup"], json!(2));
     }
 
+    #[test]
+    fn test_restore_map_duplicate_key_policies() {
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::MapToArray {
+            path: "#/properties/maps/items".to_string(),
+            key_field: "key".to_string(),
+            variant_field: None,
+        });
+        let data = json!({
+            "maps": [[
+                {"key": "dup", "value": 1},
+                {"key": "dup", "value": 2}
+            ]]
+        });
+        let run = |policy| {
+            let options = RehydrateOptions {
+                duplicate_key_policy: policy,
+                ..RehydrateOptions::default()
+            };
+            apply_transforms_with_options(data.clone(), &codec, &options)
+        };
+
+        let warned = run(DuplicateKeyPolicy::Warn).unwrap();
+        assert_eq!(warned.data["maps"][0]["dup"], json!(2));
+        assert_eq!(warned.warnings.len(), 1);
+        assert_eq!(warned.warnings[0].data_path, "/maps/0");
+        assert_eq!(
+            warned.warnings[0].kind,
+            WarningKind::DuplicateKey {
+                key: "dup".to_string()
+            }
+        );
+        assert_eq!(warned.warnings[0].rule_id(), "JSL-RH-006");
+
+        let first = run(DuplicateKeyPolicy::FirstWins).unwrap();
+        assert_eq!(first.data["maps"][0]["dup"], json!(1));
+        assert!(first.warnings.is_empty());
+
+        let last = run(DuplicateKeyPolicy::LastWins).unwrap();
+        assert_eq!(last.data["maps"][0]["dup"], json!(2));
+        assert!(last.warnings.is_empty());
+
+        let err = run(DuplicateKeyPolicy::Error).unwrap_err();
+        assert!(err.to_string().contains("`dup`"));
+    }
+
+    #[test]
+    fn test_restore_nested_map_duplicate_key_path() {
+        let mut codec = Codec::new();
+        for path in ["#/properties/m", "#/properties/m/items/properties/value"] {
+            codec.transforms.push(Transform::MapToArray {
+                path: path.to_string(),
+                key_field: "key".to_string(),
+                variant_field: None,
+            });
+        }
+        let data = json!({
+            "m": [
+                {"key": "first", "value": []},
+                {"key": "o/uter", "value": [
+                    {"key": "a", "value": 1},
+                    {"key": "a", "value": 2}
+                ]}
+            ]
+        });
+
+        let result = apply_transforms(&data, &codec).unwrap();
+        assert_eq!(result.data["m"]["o/uter"]["a"], json!(2));
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].data_path, "/m/o~1uter");
+    }
+
+    #[test]
+    fn test_discriminator_near_miss_normalized() {
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::DiscriminatorAnyOf {
+            path: "#/properties/pets/items".to_string(),
+            discriminator: "kind".to_string(),
+            variants: vec!["dog".to_string(), "cat".to_string()],
+            required: Default::default(),
+        });
+        let data = json!({
+            "pets": [{"kind": "Dog "}, {"kind": "cat"}, {"kind": "snake"}]
+        });
+
+        let result = apply_transforms(&data, &codec).unwrap();
+        assert_eq!(
+            result.data,
+            json!({"pets": [{"kind": "dog"}, {"kind": "cat"}, {"kind": "snake"}]})
+        );
+        // The normalization, then "snake" reported as matching no variant.
+        assert_eq!(result.warnings.len(), 2);
+        assert_eq!(result.warnings[0].data_path, "/pets/0/kind");
+        assert_eq!(
+            result.warnings[0].kind,
+            WarningKind::DiscriminatorNormalized {
+                from: "Dog ".to_string(),
+                to: "dog".to_string()
+            }
+        );
+        assert_eq!(result.warnings[1].data_path, "/pets/2");
+        assert!(matches!(
+            result.warnings[1].kind,
+            WarningKind::VariantMismatch { .. }
+        ));
+
+        let options = RehydrateOptions {
+            normalize_discriminators: false,
+            ..RehydrateOptions::default()
+        };
+        let kept = apply_transforms_with_options(data.clone(), &codec, &options).unwrap();
+        assert_eq!(kept.data, data);
+        assert!(kept
+            .warnings
+            .iter()
+            .all(|w| matches!(w.kind, WarningKind::VariantMismatch { .. })));
+    }
+
+    #[test]
+    fn test_variant_mismatch_reports_candidates() {
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::DiscriminatorAnyOf {
+            path: "#/properties/pet".to_string(),
+            discriminator: "kind".to_string(),
+            variants: vec!["dog".to_string(), "cat".to_string()],
+            required: [
+                (
+                    "dog".to_string(),
+                    vec!["kind".to_string(), "bark".to_string()],
+                ),
+                (
+                    "cat".to_string(),
+                    vec!["kind".to_string(), "lives".to_string()],
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        });
+
+        let clean = apply_transforms(&json!({"pet": {"kind": "dog", "bark": true}}), &codec);
+        assert!(clean.unwrap().warnings.is_empty());
+
+        let result =
+            apply_transforms(&json!({"pet": {"kind": "dog", "lives": 9}}), &codec).unwrap();
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].data_path, "/pet");
+        assert_eq!(
+            result.warnings[0].kind,
+            WarningKind::VariantMismatch {
+                value: Some("dog".to_string()),
+                candidates: vec![
+                    VariantCandidate {
+                        variant: "dog".to_string(),
+                        missing: vec!["bark".to_string()],
+                    },
+                    VariantCandidate {
+                        variant: "cat".to_string(),
+                        missing: vec![],
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            result.warnings[0].message,
+            "kind \"dog\" matches no variant cleanly; candidates: dog (missing bark); cat"
+        );
+    }
+
     // Test 10: Nested transforms at different depths
     #[test]
     fn test_nested_depth() {
@@ -625,6 +1125,7 @@ mod tests {
         codec.transforms.push(Transform::MapToArray {
             path: "#/properties/map".to_string(),
             key_field: "key".to_string(),
+            variant_field: None,
         });
 
         // Second entry is missing "value" field → entire transform should be skipped
@@ -762,6 +1263,7 @@ mod tests {
 
     // Test 18: Pattern violation warning
     #[test]
+    #[cfg(feature = "regex")]
     fn test_pattern_violation_warning() {
         use crate::codec::DroppedConstraint;
         let mut codec = Codec::new();
@@ -780,8 +1282,29 @@ mod tests {
             .contains("does not match pattern"));
     }
 
+    #[test]
+    fn test_unevaluated_properties_validated() {
+        use crate::codec::DroppedConstraint;
+        let mut codec = Codec::new();
+        codec.dropped_constraints.push(DroppedConstraint {
+            path: "#".to_string(),
+            constraint: "unevaluatedProperties".to_string(),
+            value: json!(["kind", "a"]),
+        });
+
+        let ok = json!({"kind": "x", "a": "y"});
+        let result = apply_transforms_with_constraints(&ok, &codec).unwrap();
+        assert!(result.warnings.is_empty());
+
+        let extra = json!({"kind": "x", "b": "y"});
+        let result = apply_transforms_with_constraints(&extra, &codec).unwrap();
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("[\"b\"]"));
+    }
+
     // Test 19: Pattern match — no warning
     #[test]
+    #[cfg(feature = "regex")]
     fn test_pattern_match_no_warning() {
         use crate::codec::DroppedConstraint;
         let mut codec = Codec::new();
@@ -796,6 +1319,28 @@ mod tests {
         assert!(result.warnings.is_empty());
     }
 
+    // Test 19b: Without the regex feature, patterns are unevaluable
+    #[test]
+    #[cfg(not(feature = "regex"))]
+    fn test_pattern_unevaluable_without_regex_feature() {
+        use crate::codec::DroppedConstraint;
+        use crate::codec_warning::WarningKind;
+        let mut codec = Codec::new();
+        codec.dropped_constraints.push(DroppedConstraint {
+            path: "#/properties/email".to_string(),
+            constraint: "pattern".to_string(),
+            value: json!("^[a-z]+@[a-z]+\\.[a-z]+$"),
+        });
+
+        let data = json!({"email": "test@example.com"});
+        let result = apply_transforms_with_constraints(&data, &codec).unwrap();
+        assert_eq!(result.warnings.len(), 1);
+        assert!(matches!(
+            &result.warnings[0].kind,
+            WarningKind::ConstraintUnevaluable { constraint } if constraint == "pattern"
+        ));
+    }
+
     // Test 20: Numeric bounds warning
     #[test]
     fn test_minimum_violation_warning() {
@@ -874,6 +1419,7 @@ mod tests {
 
     // Test 24: Warning data path through array items
     #[test]
+    #[cfg(feature = "regex")]
     fn test_warning_data_path_in_array() {
         use crate::codec::DroppedConstraint;
         let mut codec = Codec::new();
@@ -924,6 +1470,7 @@ mod tests {
 
     // Test 27: patternProperties constraint only warns for matching keys
     #[test]
+    #[cfg(feature = "regex")]
     fn test_warning_pattern_properties_regex_filter() {
         use crate::codec::DroppedConstraint;
         let mut codec = Codec::new();
@@ -1093,6 +1640,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_recursive_terminate_drops_null() {
+        let mut codec = Codec::new();
+        for path in ["#/properties/next", "#/properties/children/items"] {
+            codec.transforms.push(Transform::RecursiveTerminate {
+                path: path.to_string(),
+                original_ref: "#/$defs/Node".to_string(),
+            });
+        }
+        let data = json!({"value": 1, "next": null, "children": [null, null]});
+
+        let result = apply_transforms(&data, &codec).unwrap();
+        assert_eq!(result.data, json!({"value": 1, "children": []}));
+    }
+
+    #[test]
+    fn test_recursive_node_ref_resolution() {
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::RecursiveNodeRef {
+            path: "#/properties/children/items/properties/children/items".to_string(),
+            original_ref: "#/$defs/Node".to_string(),
+        });
+        let data = json!({
+            "$node_id": "root",
+            "name": "a",
+            "children": [
+                {"$node_id": "b", "name": "b", "children": [{"$node_id": "c"}]},
+                {"$node_id": "c", "name": "c", "children": [{"$node_id": "root"}, {"$node_id": "zz"}]}
+            ]
+        });
+
+        let result = apply_transforms(&data, &codec).unwrap();
+        assert_eq!(
+            result.data,
+            json!({
+                "name": "a",
+                "children": [
+                    {"name": "b", "children": [
+                        {"name": "c", "children": [{"$node_id": "root"}, {"$node_id": "zz"}]}
+                    ]},
+                    {"name": "c", "children": [{"$node_id": "root"}, {"$node_id": "zz"}]}
+                ]
+            })
+        );
+        let unresolved: Vec<_> = result
+            .warnings
+            .iter()
+            .map(|w| (w.data_path.as_str(), &w.kind))
+            .collect();
+        let kind = |id: &str| WarningKind::UnresolvedNodeRef { id: id.to_string() };
+        assert_eq!(
+            unresolved,
+            [
+                ("/children/0/children/0/children/0", &kind("root")),
+                ("/children/0/children/0/children/1", &kind("zz")),
+                ("/children/1/children/0", &kind("root")),
+                ("/children/1/children/1", &kind("zz")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_node_ref_bare_node_not_a_reference() {
+        // A node whose other properties were all dropped as null is left
+        // with just its id, outside any reference position.
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::RecursiveNodeRef {
+            path: "#/properties/children/items/properties/link".to_string(),
+            original_ref: "#/$defs/Node".to_string(),
+        });
+        let data = json!({
+            "$node_id": "root",
+            "children": [{"$node_id": "b"}, {"$node_id": "c", "link": {"$node_id": "b"}}]
+        });
+
+        let result = apply_transforms(&data, &codec).unwrap();
+        assert_eq!(result.data, json!({"children": [{}, {"link": {}}]}));
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn test_recursive_node_ref_expansion_capped() {
+        // Each node references the next twice: full expansion doubles per
+        // level, so the budget has to stop it.
+        let depth = 24;
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::RecursiveNodeRef {
+            path: "#/properties/nodes/items/properties/refs/items".to_string(),
+            original_ref: "#/$defs/Node".to_string(),
+        });
+        let nodes: Vec<Value> = (0..depth)
+            .map(|i| {
+                let next = json!({"$node_id": format!("n{}", i + 1)});
+                json!({"$node_id": format!("n{}", i), "refs": [next.clone(), next]})
+            })
+            .collect();
+        let data = json!({"nodes": nodes});
+
+        let mut options = RehydrateOptions::new();
+        options.max_expanded_nodes = 1_000;
+        let result = apply_transforms_with_options(data, &codec, &options).unwrap();
+        assert!(result.data.to_string().len() < 100_000);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| matches!(w.kind, WarningKind::UnresolvedNodeRef { .. })
+                && w.message.contains("max_expanded_nodes")));
+    }
+
     // ═══════════════════════════════════════════════════════════════════════
     // Type Coercion Tests
     // ═══════════════════════════════════════════════════════════════════════
@@ -1154,6 +1810,40 @@ mod tests {
         assert_eq!(warnings.len(), 0);
     }
 
+    #[test]
+    fn test_coerce_integral_float_to_integer() {
+        let schema = json!({"type": "object", "properties": {"count": {"type": "integer"}}});
+        let mut data = json!({"count": 3.0});
+        let warnings = coerce_types(&mut data, &schema);
+        assert!(data["count"].is_i64());
+        assert_eq!(data["count"], json!(3));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("to integer 3"));
+    }
+
+    #[test]
+    fn test_coerce_non_integral_float_warns() {
+        let schema = json!({"type": "object", "properties": {"count": {"type": "integer"}}});
+        let mut data = json!({"count": 2.5});
+        let warnings = coerce_types(&mut data, &schema);
+        assert_eq!(data["count"], json!(2.5));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("non-integral"));
+    }
+
+    #[test]
+    fn test_coerce_float_kept_for_number_and_string_union() {
+        let schema = json!({"type": "object", "properties": {
+            "score": {"type": "number"},
+            "id": {"type": ["integer", "string"]}
+        }});
+        let mut data = json!({"score": 3.0, "id": 2.5});
+        let warnings = coerce_types(&mut data, &schema);
+        assert!(data["score"].is_f64());
+        assert_eq!(data["id"], json!("2.5"));
+        assert_eq!(warnings.len(), 1);
+    }
+
     #[test]
     fn test_coerce_nested_objects() {
         let schema = json!({
@@ -1196,6 +1886,15 @@ mod tests {
         assert_eq!(warnings.len(), 0);
     }
 
+    #[test]
+    fn test_coerce_null_passthrough_openapi_nullable() {
+        let schema = json!({"type": "integer", "nullable": true});
+        let mut data = json!(null);
+        let warnings = coerce_types(&mut data, &schema);
+        assert_eq!(data, json!(null));
+        assert_eq!(warnings.len(), 0);
+    }
+
     #[test]
     fn test_coerce_no_op_when_types_match() {
         let schema = json!({"type": "object", "properties": {"name": {"type": "string"}}});
@@ -1301,4 +2000,135 @@ mod tests {
             "nested data inside inflated target should be parsed from JSON string"
         );
     }
+
+    fn custom_codec() -> Codec {
+        let mut codec = Codec::new();
+        codec.transforms.push(Transform::Custom {
+            kind: "upper".to_string(),
+            path: "#/properties/items/items/properties/code".to_string(),
+            payload: json!({"suffix": "!"}),
+        });
+        codec
+    }
+
+    #[test]
+    fn test_custom_transform_uses_registered_handler() {
+        let mut options = RehydrateOptions::new();
+        options.register_handler("upper", |data, payload| {
+            let s = data
+                .as_str()
+                .ok_or_else(|| ConvertError::RehydrationError("expected string".to_string()))?;
+            *data = json!(format!(
+                "{}{}",
+                s.to_uppercase(),
+                payload["suffix"].as_str().unwrap()
+            ));
+            Ok(())
+        });
+        let data = json!({"items": [{"code": "ab"}, {"code": "cd"}]});
+        let result = apply_transforms_with_options(data, &custom_codec(), &options).unwrap();
+        assert_eq!(
+            result.data,
+            json!({"items": [{"code": "AB!"}, {"code": "CD!"}]})
+        );
+        assert!(result.warnings.is_empty());
+
+        let err = apply_transforms_with_options(
+            json!({"items": [{"code": 1}]}),
+            &custom_codec(),
+            &options,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ConvertError::RehydrationError(_)));
+    }
+
+    #[test]
+    fn test_custom_transform_without_handler_warns() {
+        let data = json!({"items": [{"code": "ab"}]});
+        let result = apply_transforms(&data, &custom_codec()).unwrap();
+        assert_eq!(result.data, data);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(matches!(
+            &result.warnings[0].kind,
+            WarningKind::UnhandledTransform { transform } if transform == "upper"
+        ));
+        assert_eq!(result.warnings[0].rule_id(), "JSL-RH-004");
+    }
+
+    #[test]
+    fn test_custom_transform_serde_round_trip() {
+        let json = serde_json::to_value(custom_codec()).unwrap();
+        assert_eq!(json["transforms"][0]["type"], "custom");
+        assert_eq!(json["transforms"][0]["kind"], "upper");
+        let back: Codec = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            &back.transforms[0],
+            Transform::Custom { payload, .. } if payload["suffix"] == "!"
+        ));
+
+        // `payload` is optional on the wire.
+        let bare: Transform =
+            serde_json::from_value(json!({"type": "custom", "kind": "k", "path": "#"})).unwrap();
+        assert!(matches!(bare, Transform::Custom { payload, .. } if payload.is_null()));
+    }
+
+    fn violation(index: usize) -> Warning {
+        Warning::new(
+            format!("/tags/{}", index),
+            "#/properties/tags/items",
+            WarningKind::ConstraintViolation {
+                constraint: "maxLength".to_string(),
+            },
+            format!("tag {} too long", index),
+        )
+    }
+
+    #[test]
+    fn test_bound_warnings_default_is_unchanged() {
+        let warnings: Vec<_> = (0..10).map(violation).collect();
+        assert_eq!(RehydrateOptions::new().bound_warnings(warnings).len(), 10);
+    }
+
+    #[test]
+    fn test_aggregate_warnings_merges_same_path_and_kind() {
+        let mut warnings: Vec<_> = (0..1000).map(violation).collect();
+        let mut other = violation(0);
+        other.kind = WarningKind::PathNotFound;
+        warnings.insert(3, other);
+
+        let options = RehydrateOptions {
+            aggregate_warnings: Some(3),
+            ..RehydrateOptions::default()
+        };
+        let bounded = options.bound_warnings(warnings);
+        assert_eq!(bounded.len(), 2);
+        assert_eq!(bounded[0].count, 1000);
+        assert_eq!(bounded[0].message, "tag 0 too long");
+        assert_eq!(bounded[0].sample_paths, ["/tags/0", "/tags/1", "/tags/2"]);
+        assert_eq!(bounded[1].count, 1);
+        assert!(bounded[1].sample_paths.is_empty());
+
+        let json = serde_json::to_value(&bounded[0]).unwrap();
+        assert_eq!(json["count"], 1000);
+        assert_eq!(json["samplePaths"].as_array().unwrap().len(), 3);
+        let single = serde_json::to_value(&bounded[1]).unwrap();
+        assert!(single.get("count").is_none());
+        assert!(single.get("samplePaths").is_none());
+        let back: Warning = serde_json::from_value(single).unwrap();
+        assert_eq!(back.count, 1);
+    }
+
+    #[test]
+    fn test_max_warnings_appends_summary() {
+        let warnings: Vec<_> = (0..10).map(violation).collect();
+        let options = RehydrateOptions {
+            max_warnings: Some(4),
+            ..RehydrateOptions::default()
+        };
+        let bounded = options.bound_warnings(warnings);
+        assert_eq!(bounded.len(), 5);
+        assert_eq!(bounded[3].data_path, "/tags/3");
+        assert_eq!(bounded[4].kind, WarningKind::Truncated { omitted: 6 });
+        assert_eq!(bounded[4].rule_id(), "JSL-RH-005");
+    }
 }
@@ -0,0 +1,26 @@
+//! Regular expressions for `pattern` and `patternProperties`.
+//!
+//! Backed by the `regex` crate when the `regex` feature is enabled. Without
+//! it, every pattern fails to compile: dropped `pattern` constraints surface
+//! as [`ConstraintUnevaluable`](crate::codec_warning::WarningKind) warnings
+//! and transforms under `patternProperties` are skipped, exactly as for an
+//! invalid pattern.
+
+#[cfg(feature = "regex")]
+pub(crate) use regex::Regex;
+
+/// Stand-in that never compiles a pattern.
+#[cfg(not(feature = "regex"))]
+#[derive(Debug, Clone)]
+pub(crate) struct Regex(std::convert::Infallible);
+
+#[cfg(not(feature = "regex"))]
+impl Regex {
+    pub(crate) fn new(_pattern: &str) -> Result<Self, &'static str> {
+        Err("pattern matching is unavailable (built without the `regex` feature)")
+    }
+
+    pub(crate) fn is_match(&self, _haystack: &str) -> bool {
+        match self.0 {}
+    }
+}
@@ -2,21 +2,62 @@
 //!
 //! Each function handles one type of codec transform: map restoration,
 //! JSON string parsing, additional properties restoration, root object unwrapping,
-//! and enum de-stringification.
+//! enum de-stringification, filtered property placeholders, projected union
+//! variants, JSON string re-encoding, and dispatch to registered custom
+//! handlers; plus resolution
+//! of recursive node references, which needs the whole tree.
+
+use std::collections::{BTreeMap, HashMap};
 
 use serde_json::Value;
 
+use super::walker::apply_transform;
+use super::{DuplicateKeyPolicy, RehydrateOptions};
 use crate::codec::Transform;
+use crate::codec_warning::{VariantCandidate, Warning, WarningKind};
 use crate::error::ConvertError;
+use crate::passes::p5_recursion::NODE_ID_FIELD;
+use crate::path_map::transform_path;
+use crate::schema_utils::{escape_pointer_segment, split_path};
 
-/// Execute a value-level transform at the current data node.
+/// Execute a value-level transform at the current data node, found at
+/// `data_path`.
 pub(super) fn execute_transform(
     data: &mut Value,
     transform: &Transform,
+    options: &RehydrateOptions,
+    data_path: &str,
+    warnings: &mut Vec<Warning>,
 ) -> Result<(), ConvertError> {
     match transform {
-        Transform::MapToArray { key_field, .. } => {
-            restore_map(data, key_field)?;
+        Transform::MapToArray {
+            path, key_field, ..
+        } => {
+            let entry_keys: Vec<Option<String>> = data
+                .as_array()
+                .map(|entries| {
+                    entries
+                        .iter()
+                        .map(|e| e.get(key_field).and_then(Value::as_str).map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let duplicates = restore_map(data, key_field, options.duplicate_key_policy)?;
+            if data.is_object() {
+                rekey_entry_paths(warnings, data_path, &entry_keys);
+            }
+            for key in duplicates {
+                warnings.push(Warning::new(
+                    if data_path.is_empty() {
+                        "/".to_string()
+                    } else {
+                        data_path.to_string()
+                    },
+                    path.clone(),
+                    WarningKind::DuplicateKey { key: key.clone() },
+                    format!("map has several entries for key `{}`; kept the last", key),
+                ));
+            }
         }
         Transform::JsonStringParse { .. } => {
             parse_json_string(data)?;
@@ -27,12 +68,61 @@ pub(super) fn execute_transform(
         Transform::NullableOptional { .. } => {
             // Handled in the navigation step.
         }
-        Transform::DiscriminatorAnyOf { .. } => {
-            // No-op
+        Transform::DiscriminatorAnyOf {
+            path,
+            discriminator,
+            variants,
+            required,
+        } => {
+            if options.normalize_discriminators {
+                if let Some((from, to)) = normalize_discriminator(data, discriminator, variants) {
+                    let message = format!("discriminator value {:?} normalized to {:?}", from, to);
+                    warnings.push(Warning::new(
+                        format!("{}/{}", data_path, escape_pointer_segment(discriminator)),
+                        path.clone(),
+                        WarningKind::DiscriminatorNormalized { from, to },
+                        message,
+                    ));
+                }
+            }
+            if let Some((value, candidates)) =
+                check_variant(data, discriminator, variants, required)
+            {
+                let summary: Vec<String> = candidates
+                    .iter()
+                    .map(|c| match c.missing.as_slice() {
+                        [] => c.variant.clone(),
+                        missing => format!("{} (missing {})", c.variant, missing.join(", ")),
+                    })
+                    .collect();
+                let message = format!(
+                    "{} {} matches no variant cleanly; candidates: {}",
+                    discriminator,
+                    value
+                        .as_deref()
+                        .map_or("(absent)".to_string(), |v| format!("{:?}", v)),
+                    summary.join("; ")
+                );
+                warnings.push(Warning::new(
+                    if data_path.is_empty() {
+                        "/".to_string()
+                    } else {
+                        data_path.to_string()
+                    },
+                    path.clone(),
+                    WarningKind::VariantMismatch { value, candidates },
+                    message,
+                ));
+            }
         }
         Transform::RecursiveInflate { .. } => {
             parse_json_string(data)?;
         }
+        // The walker drops a terminating `null` from its parent.
+        Transform::RecursiveTerminate { .. } => {}
+        // Node references are resolved once every transform has run; mark
+        // them here, where the schema says a reference belongs.
+        Transform::RecursiveNodeRef { .. } => mark_node_ref(data),
         Transform::RootObjectWrapper { wrapper_key, .. } => {
             // Unwrap: extract data[wrapper_key] and promote it to root.
             // Fail loudly if the wrapper object is missing/invalid to avoid silently
@@ -61,7 +151,7 @@ pub(super) fn execute_transform(
                     .filter(|k| k.as_str() != wrapper_key)
                     .cloned()
                     .collect();
-                tracing::warn!(
+                crate::logging::warn!(
                     "Root wrapper object had extra keys beyond `{}`: {:?}; stripping",
                     wrapper_key,
                     extra_keys
@@ -92,11 +182,63 @@ pub(super) fn execute_transform(
                 }
             }
         }
+        Transform::AnyOfOverflow { kept_string, .. } => {
+            restore_overflow_variant(data, *kept_string);
+        }
+        Transform::FilteredProperty {
+            property,
+            placeholder,
+            ..
+        } => {
+            if let (Some(obj), Some(placeholder)) = (data.as_object_mut(), placeholder) {
+                obj.entry(property.clone())
+                    .or_insert_with(|| placeholder.clone());
+            }
+        }
+        Transform::ProjectedVariants { variants, .. } => {
+            let variant = data
+                .as_object()
+                .and_then(|obj| crate::project::projected_variant(variants, obj));
+            if let Some(variant) = variant {
+                // Projection paths never run through `patternProperties`.
+                let regex_cache = HashMap::new();
+                for inner in variant.transforms.iter().rev() {
+                    let segments = split_path(transform_path(inner));
+                    let seg_refs: Vec<&str> = segments.iter().map(String::as_str).collect();
+                    apply_transform(
+                        data,
+                        &seg_refs,
+                        inner,
+                        options,
+                        &regex_cache,
+                        data_path,
+                        warnings,
+                    )?;
+                }
+            }
+        }
+        Transform::JsonStringEncode { .. } => {
+            encode_json_string(data)?;
+        }
+        Transform::Custom { kind, payload, .. } => {
+            // Unhandled kinds are filtered out (with a warning) before the walk.
+            if let Some(handler) = options.handler(kind) {
+                handler(data, payload)?;
+            }
+        }
     }
     Ok(())
 }
 
-fn restore_map(data: &mut Value, key_field: &str) -> Result<(), ConvertError> {
+/// Turn an array of `{<key_field>, value}` entries back into an object,
+/// resolving repeated keys per `policy`. Returns the repeated keys the
+/// caller should warn about (only under [`DuplicateKeyPolicy::Warn`]).
+fn restore_map(
+    data: &mut Value,
+    key_field: &str,
+    policy: DuplicateKeyPolicy,
+) -> Result<Vec<String>, ConvertError> {
+    let mut duplicates = Vec::new();
     // Expecting Array of Objects -> Object
     if let Some(arr) = data.as_array() {
         // Pre-validate: every entry must be an object with key_field (string) and "value".
@@ -111,7 +253,7 @@ fn restore_map(data: &mut Value, key_field: &str) -> Result<(), ConvertError> {
         });
 
         if !all_valid {
-            return Ok(()); // Skip silently — preserve original array
+            return Ok(duplicates); // Skip silently — preserve original array
         }
 
         let mut map = serde_json::Map::new();
@@ -127,11 +269,258 @@ fn restore_map(data: &mut Value, key_field: &str) -> Result<(), ConvertError> {
             let v = obj
                 .get("value")
                 .expect("invariant: all_valid guard above ensures 'value' key is present");
-            map.insert(k.to_string(), v.clone()); // Duplicate keys: last wins
+            if map.contains_key(k) {
+                match policy {
+                    DuplicateKeyPolicy::LastWins => {}
+                    DuplicateKeyPolicy::FirstWins => continue,
+                    DuplicateKeyPolicy::Warn => {
+                        if !duplicates.iter().any(|d| d == k) {
+                            duplicates.push(k.to_string());
+                        }
+                    }
+                    DuplicateKeyPolicy::Error => {
+                        return Err(ConvertError::RehydrationError(format!(
+                            "Map has several entries for key `{}`",
+                            k
+                        )));
+                    }
+                }
+            }
+            map.insert(k.to_string(), v.clone());
         }
         *data = Value::Object(map);
     }
-    Ok(())
+    Ok(duplicates)
+}
+
+/// Rewrite the data paths of warnings raised inside the entries of a map
+/// just restored at `data_path` (`<map>/<index>/value/...`, recorded while
+/// it was still an entry array) to point into the map (`<map>/<key>/...`).
+fn rekey_entry_paths(warnings: &mut [Warning], data_path: &str, keys: &[Option<String>]) {
+    let prefix = format!("{}/", data_path);
+    for warning in warnings {
+        let Some(rest) = warning.data_path.strip_prefix(&prefix) else {
+            continue;
+        };
+        let (index, inner) = rest.split_once('/').unwrap_or((rest, ""));
+        let Some(Some(key)) = index.parse::<usize>().ok().and_then(|i| keys.get(i)) else {
+            continue;
+        };
+        let inner = match inner.strip_prefix("value") {
+            Some(after) if after.is_empty() || after.starts_with('/') => after.to_string(),
+            _ => String::new(),
+        };
+        warning.data_path = format!("{}{}{}", prefix, escape_pointer_segment(key), inner);
+    }
+}
+
+/// Rewrite `data[discriminator]` to the one variant it equals up to case and
+/// surrounding whitespace. Returns the old and new value when it did.
+fn normalize_discriminator(
+    data: &mut Value,
+    discriminator: &str,
+    variants: &[String],
+) -> Option<(String, String)> {
+    let value = data.get_mut(discriminator)?;
+    let current = value.as_str()?;
+    if variants.iter().any(|v| v == current) {
+        return None;
+    }
+    let wanted = current.trim().to_lowercase();
+    let mut matches = variants.iter().filter(|v| v.to_lowercase() == wanted);
+    let variant = matches.next()?;
+    if matches.next().is_some() {
+        // Ambiguous (e.g. both "Dog" and "dog" are variants): leave it.
+        return None;
+    }
+    let from = std::mem::replace(value, Value::String(variant.clone()));
+    Some((
+        from.as_str().unwrap_or_default().to_string(),
+        variant.clone(),
+    ))
+}
+
+/// Compare an object under a discriminated union with its variants. `None`
+/// when the discriminator names a variant whose required properties are
+/// all present (or `data` is not an object); otherwise the value found and
+/// every variant with its missing properties, closest first. A variant the
+/// value names sorts before the rest.
+fn check_variant(
+    data: &Value,
+    discriminator: &str,
+    variants: &[String],
+    required: &BTreeMap<String, Vec<String>>,
+) -> Option<(Option<String>, Vec<VariantCandidate>)> {
+    if variants.is_empty() {
+        return None;
+    }
+    let obj = data.as_object()?;
+    let value = obj.get(discriminator).and_then(Value::as_str);
+    let missing = |variant: &str| -> Vec<String> {
+        required
+            .get(variant)
+            .into_iter()
+            .flatten()
+            .filter(|field| !obj.contains_key(field.as_str()))
+            .cloned()
+            .collect()
+    };
+    if let Some(value) = value.filter(|v| variants.iter().any(|variant| variant == v)) {
+        if missing(value).is_empty() {
+            return None;
+        }
+    }
+
+    let mut candidates: Vec<VariantCandidate> = variants
+        .iter()
+        .map(|variant| VariantCandidate {
+            variant: variant.clone(),
+            missing: missing(variant),
+        })
+        .collect();
+    candidates.sort_by_key(|c| (Some(c.variant.as_str()) != value, c.missing.len()));
+    Some((value.map(String::from), candidates))
+}
+
+/// Key a `{"$node_id": id}` reference is moved to by [`mark_node_ref`], so
+/// that a node whose other properties were all dropped as `null` is not
+/// mistaken for one.
+const NODE_REF_FIELD: &str = "\u{0}node_ref";
+
+/// Mark `data` as a node reference if it has the `{"$node_id": id}` shape.
+fn mark_node_ref(data: &mut Value) {
+    if let Some(obj) = data.as_object_mut() {
+        if obj.len() == 1 && obj.get(NODE_ID_FIELD).is_some_and(Value::is_string) {
+            if let Some(id) = obj.remove(NODE_ID_FIELD) {
+                obj.insert(NODE_REF_FIELD.to_string(), id);
+            }
+        }
+    }
+}
+
+/// Replace every marked reference in `data` with a copy of the node
+/// carrying that `$node_id`, then strip the ids. A reference to an unknown
+/// id, to a node enclosing it, or past `max_expanded_nodes` copied nodes is
+/// kept and reported.
+pub(super) fn resolve_node_refs(
+    data: &mut Value,
+    max_expanded_nodes: usize,
+    warnings: &mut Vec<Warning>,
+) {
+    let mut nodes = HashMap::new();
+    collect_nodes(data, &mut nodes);
+    let mut budget = max_expanded_nodes;
+    link_nodes(data, &nodes, &mut budget, &mut Vec::new(), "", warnings);
+}
+
+/// The id a node is referenced by.
+fn node_id(obj: &serde_json::Map<String, Value>) -> Option<&str> {
+    obj.get(NODE_ID_FIELD).and_then(Value::as_str)
+}
+
+/// The id a marked reference points to.
+fn node_ref(obj: &serde_json::Map<String, Value>) -> Option<&str> {
+    if obj.len() == 1 {
+        obj.get(NODE_REF_FIELD).and_then(Value::as_str)
+    } else {
+        None
+    }
+}
+
+/// Each node by id, with the number of values a copy of it holds.
+fn collect_nodes(data: &Value, nodes: &mut HashMap<String, (Value, usize)>) -> usize {
+    match data {
+        Value::Object(obj) => {
+            let size = 1 + obj
+                .values()
+                .map(|child| collect_nodes(child, nodes))
+                .sum::<usize>();
+            if let Some(id) = node_id(obj) {
+                nodes
+                    .entry(id.to_string())
+                    .or_insert_with(|| (data.clone(), size));
+            }
+            size
+        }
+        Value::Array(items) => {
+            1 + items
+                .iter()
+                .map(|child| collect_nodes(child, nodes))
+                .sum::<usize>()
+        }
+        _ => 1,
+    }
+}
+
+/// Resolve references under `data`, copying in at most `budget` more
+/// values; `enclosing` holds the ids of the nodes being copied or walked
+/// through, which a reference may not expand into.
+fn link_nodes(
+    data: &mut Value,
+    nodes: &HashMap<String, (Value, usize)>,
+    budget: &mut usize,
+    enclosing: &mut Vec<String>,
+    data_path: &str,
+    warnings: &mut Vec<Warning>,
+) {
+    let child_path = |key: &str| format!("{}/{}", data_path, escape_pointer_segment(key));
+    match data {
+        Value::Object(obj) => {
+            if let Some(id) = node_ref(obj).map(String::from) {
+                let reason = match nodes.get(&id) {
+                    Some(_) if enclosing.contains(&id) => "names an enclosing node",
+                    Some((_, size)) if *size > *budget => "exceeds max_expanded_nodes",
+                    Some((node, size)) => {
+                        *budget -= size;
+                        *data = node.clone();
+                        link_nodes(data, nodes, budget, enclosing, data_path, warnings);
+                        return;
+                    }
+                    None => "names no node",
+                };
+                obj.clear();
+                obj.insert(NODE_ID_FIELD.to_string(), Value::String(id.clone()));
+                let message = format!("{} {:?} {}; reference kept", NODE_ID_FIELD, id, reason);
+                warnings.push(Warning::new(
+                    if data_path.is_empty() {
+                        "/".to_string()
+                    } else {
+                        data_path.to_string()
+                    },
+                    String::new(),
+                    WarningKind::UnresolvedNodeRef { id },
+                    message,
+                ));
+                return;
+            }
+            let own = match obj.remove(NODE_ID_FIELD) {
+                Some(Value::String(id)) => Some(id),
+                _ => None,
+            };
+            if let Some(id) = &own {
+                enclosing.push(id.clone());
+            }
+            for (key, child) in obj.iter_mut() {
+                link_nodes(child, nodes, budget, enclosing, &child_path(key), warnings);
+            }
+            if own.is_some() {
+                enclosing.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                link_nodes(
+                    child,
+                    nodes,
+                    budget,
+                    enclosing,
+                    &child_path(&index.to_string()),
+                    warnings,
+                );
+            }
+        }
+        _ => {}
+    }
 }
 
 fn parse_json_string(data: &mut Value) -> Result<(), ConvertError> {
@@ -151,6 +540,29 @@ fn parse_json_string(data: &mut Value) -> Result<(), ConvertError> {
     Ok(())
 }
 
+/// Serialize an inlined `contentSchema` value back into the JSON string the
+/// original schema expects.
+fn encode_json_string(data: &mut Value) -> Result<(), ConvertError> {
+    let text = serde_json::to_string(data).map_err(|e| {
+        ConvertError::RehydrationError(format!("Failed to serialize JSON string: {}", e))
+    })?;
+    *data = Value::String(text);
+    Ok(())
+}
+
+/// Parse a string produced by a collapsed-variants branch. Unlike
+/// [`parse_json_string`], unparseable strings are left alone: they may be
+/// legitimate values of a kept string variant.
+fn restore_overflow_variant(data: &mut Value, kept_string: bool) {
+    if let Some(s) = data.as_str() {
+        if let Ok(parsed) = serde_json::from_str::<Value>(s) {
+            if !kept_string || parsed.is_object() || parsed.is_array() {
+                *data = parsed;
+            }
+        }
+    }
+}
+
 fn restore_additional_properties(
     data: &mut Value,
     property_name: &str,
@@ -190,7 +602,7 @@ mod tests {
     #[test]
     fn restore_map_empty_array_yields_empty_object() {
         let mut data = json!([]);
-        restore_map(&mut data, "key").unwrap();
+        restore_map(&mut data, "key", DuplicateKeyPolicy::Warn).unwrap();
         // Empty array → all_valid = true (vacuously), empty map
         assert_eq!(data, json!({}));
     }
@@ -199,7 +611,7 @@ mod tests {
     fn restore_map_non_array_is_no_op() {
         let mut data = json!({"not": "an array"});
         let original = data.clone();
-        restore_map(&mut data, "key").unwrap();
+        restore_map(&mut data, "key", DuplicateKeyPolicy::Warn).unwrap();
         assert_eq!(data, original);
     }
 
@@ -208,7 +620,7 @@ mod tests {
         // Missing "value" field — should skip the entire transform
         let mut data = json!([{"key": "a"}]);
         let original = data.clone();
-        restore_map(&mut data, "key").unwrap();
+        restore_map(&mut data, "key", DuplicateKeyPolicy::Warn).unwrap();
         assert_eq!(data, original);
     }
 
@@ -237,6 +649,40 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn encode_json_string_serializes_value() {
+        let mut data = json!({"a": [1, 2]});
+        encode_json_string(&mut data).unwrap();
+        assert_eq!(data, json!(r#"{"a":[1,2]}"#));
+
+        let mut data = json!("plain");
+        encode_json_string(&mut data).unwrap();
+        assert_eq!(data, json!(r#""plain""#));
+    }
+
+    // -----------------------------------------------------------------------
+    // restore_overflow_variant
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn restore_overflow_variant_parses_json() {
+        let mut data = json!(r#"{"kind": "v4"}"#);
+        restore_overflow_variant(&mut data, false);
+        assert_eq!(data, json!({"kind": "v4"}));
+    }
+
+    #[test]
+    fn restore_overflow_variant_keeps_plain_strings() {
+        let mut data = json!("hello");
+        restore_overflow_variant(&mut data, false);
+        assert_eq!(data, json!("hello"));
+
+        // With a kept string variant, scalar JSON stays a string.
+        let mut data = json!("42");
+        restore_overflow_variant(&mut data, true);
+        assert_eq!(data, json!("42"));
+    }
+
     // -----------------------------------------------------------------------
     // restore_additional_properties
     // -----------------------------------------------------------------------
@@ -268,7 +714,14 @@ mod tests {
             path: String::new(),
             original_values: vec![json!(42), json!(99)],
         };
-        execute_transform(&mut data, &transform).unwrap();
+        execute_transform(
+            &mut data,
+            &transform,
+            &RehydrateOptions::default(),
+            "",
+            &mut Vec::new(),
+        )
+        .unwrap();
         assert_eq!(data, json!(42));
     }
 
@@ -279,7 +732,14 @@ mod tests {
             path: String::new(),
             original_values: vec![json!(true), json!(false)],
         };
-        execute_transform(&mut data, &transform).unwrap();
+        execute_transform(
+            &mut data,
+            &transform,
+            &RehydrateOptions::default(),
+            "",
+            &mut Vec::new(),
+        )
+        .unwrap();
         assert_eq!(data, json!(true));
     }
 
@@ -290,7 +750,14 @@ mod tests {
             path: String::new(),
             original_values: vec![json!(1), json!(2)],
         };
-        execute_transform(&mut data, &transform).unwrap();
+        execute_transform(
+            &mut data,
+            &transform,
+            &RehydrateOptions::default(),
+            "",
+            &mut Vec::new(),
+        )
+        .unwrap();
         assert_eq!(data, json!("unknown"));
     }
 }
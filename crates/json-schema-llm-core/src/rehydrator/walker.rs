@@ -5,27 +5,127 @@
 
 use std::collections::HashMap;
 
-use regex::Regex;
+use super::pattern::Regex;
 use serde_json::Value;
 
-use super::{SKIP_PAIR, SKIP_SINGLE};
+use super::{RehydrateOptions, SKIP_PAIR, SKIP_SINGLE};
 use crate::codec::Transform;
+use crate::codec_warning::Warning;
 use crate::error::ConvertError;
+use crate::passes::p3_dictionary::{VALUE_FIELD, VARIANT_FIELD};
+use crate::schema_utils::escape_pointer_segment;
 
 use super::transforms::execute_transform;
 
+/// What a transform does where its path ends. The walk to get there is
+/// shared by rehydration and by [`crate::fewshot`], which runs the codec's
+/// transforms forward.
+pub(crate) trait TransformSite {
+    /// Apply `transform` at `data`, the node its path leads to, found at
+    /// `data_path`.
+    fn at_node(
+        &mut self,
+        data: &mut Value,
+        transform: &Transform,
+        data_path: &str,
+    ) -> Result<(), ConvertError>;
+
+    /// Apply `transform` to property `key` of `parent` when its path ends on
+    /// that property. Returns `false` to walk into the property instead.
+    fn at_property(&mut self, parent: &mut Value, key: &str, transform: &Transform) -> bool;
+
+    /// Apply `transform` to a whole array when its path ends on `items`.
+    /// Returns `false` to apply it to each element instead.
+    fn at_items(&mut self, items: &mut Vec<Value>, transform: &Transform) -> bool;
+}
+
+/// Rehydration: the inverse of each transform.
+pub(crate) struct Rehydrate<'a> {
+    options: &'a RehydrateOptions,
+    warnings: &'a mut Vec<Warning>,
+}
+
+impl<'a> Rehydrate<'a> {
+    pub(crate) fn new(options: &'a RehydrateOptions, warnings: &'a mut Vec<Warning>) -> Self {
+        Self { options, warnings }
+    }
+}
+
+impl TransformSite for Rehydrate<'_> {
+    fn at_node(
+        &mut self,
+        data: &mut Value,
+        transform: &Transform,
+        data_path: &str,
+    ) -> Result<(), ConvertError> {
+        execute_transform(data, transform, self.options, data_path, self.warnings)
+    }
+
+    fn at_property(&mut self, parent: &mut Value, key: &str, transform: &Transform) -> bool {
+        let drops_null = match transform {
+            Transform::NullableOptional {
+                original_required, ..
+            } => !original_required,
+            Transform::RecursiveTerminate { .. } => true,
+            _ => return false,
+        };
+        if drops_null {
+            if let Some(obj) = parent.as_object_mut() {
+                if obj.get(key).is_some_and(Value::is_null) {
+                    obj.remove(key);
+                }
+            }
+        }
+        true
+    }
+
+    fn at_items(&mut self, items: &mut Vec<Value>, transform: &Transform) -> bool {
+        if !matches!(transform, Transform::RecursiveTerminate { .. }) {
+            return false;
+        }
+        items.retain(|item| !item.is_null());
+        true
+    }
+}
+
 /// Recursively walk the data following schema path segments and apply the transform
-/// at the terminal node.
+/// at the terminal node. `data_path` is the JSON Pointer of `data` (empty at the
+/// root), for the warnings the transform pushes onto `warnings`.
 pub(super) fn apply_transform(
     data: &mut Value,
     path_parts: &[&str],
     transform: &Transform,
+    options: &RehydrateOptions,
     regex_cache: &HashMap<String, Result<Regex, String>>,
+    data_path: &str,
+    warnings: &mut Vec<Warning>,
+) -> Result<(), ConvertError> {
+    let mut site = Rehydrate::new(options, warnings);
+    walk(
+        data,
+        path_parts,
+        transform,
+        regex_cache,
+        data_path,
+        &mut site,
+    )
+}
+
+/// Walk the data following schema path segments and let `site` apply the
+/// transform where the path ends. `data_path` is the JSON Pointer of `data`
+/// (empty at the root).
+pub(crate) fn walk<S: TransformSite>(
+    data: &mut Value,
+    path_parts: &[&str],
+    transform: &Transform,
+    regex_cache: &HashMap<String, Result<Regex, String>>,
+    data_path: &str,
+    site: &mut S,
 ) -> Result<(), ConvertError> {
     // End of path — execute the transform
     if path_parts.is_empty() {
-        tracing::trace!("reached end of path, executing transform");
-        return execute_transform(data, transform);
+        crate::logging::trace!("reached end of path, executing transform");
+        return site.at_node(data, transform, data_path);
     }
 
     let segment = path_parts[0];
@@ -33,16 +133,16 @@ pub(super) fn apply_transform(
 
     // 1. Schema-structural: skip keyword only
     if SKIP_SINGLE.contains(&segment) {
-        tracing::trace!(segment, "skipping schema-structural keyword");
-        return apply_transform(data, rest, transform, regex_cache);
+        crate::logging::trace!(segment, "skipping schema-structural keyword");
+        return walk(data, rest, transform, regex_cache, data_path, site);
     }
 
     // 2. Schema-structural: skip keyword + next segment (index/name)
     if SKIP_PAIR.contains(&segment) {
-        tracing::trace!(segment, "skipping schema-structural keyword pair");
+        crate::logging::trace!(segment, "skipping schema-structural keyword pair");
         // Skip the keyword and the following segment (e.g. "anyOf" + "0")
         if rest.is_empty() {
-            tracing::trace!(
+            crate::logging::trace!(
                 segment,
                 "incomplete schema-structural keyword pair, stopping"
             );
@@ -58,7 +158,14 @@ pub(super) fn apply_transform(
                         if let Some(obj) = data.as_object_mut() {
                             for (key, val) in obj.iter_mut() {
                                 if re.is_match(key) {
-                                    apply_transform(val, skip_to, transform, regex_cache)?;
+                                    walk(
+                                        val,
+                                        skip_to,
+                                        transform,
+                                        regex_cache,
+                                        &child_path(data_path, key),
+                                        site,
+                                    )?;
                                 }
                             }
                         }
@@ -66,7 +173,7 @@ pub(super) fn apply_transform(
                     Some(Err(_)) | None => {
                         // Invalid regex (Err) or cache miss (None) — already warned during cache build.
                         // Skipping this transform is intentional: transforms are best-effort.
-                        tracing::debug!(
+                        crate::logging::debug!(
                             pattern = %pattern,
                             "patternProperties regex invalid or not in cache, skipping transform"
                         );
@@ -76,14 +183,28 @@ pub(super) fn apply_transform(
             return Ok(());
         }
 
-        return apply_transform(data, skip_to, transform, regex_cache);
+        return walk(data, skip_to, transform, regex_cache, data_path, site);
     }
 
     // 3. Array iteration: "items"
     if segment == "items" {
         if let Some(arr) = data.as_array_mut() {
-            for item in arr {
-                apply_transform(item, rest, transform, regex_cache)?;
+            // SPECIAL CASE: transforms acting on the whole array at the final hop
+            if rest.is_empty() && site.at_items(arr, transform) {
+                return Ok(());
+            }
+            for (index, item) in arr.iter_mut().enumerate() {
+                if in_other_variant(item, rest) {
+                    continue;
+                }
+                walk(
+                    item,
+                    rest,
+                    transform,
+                    regex_cache,
+                    &child_path(data_path, &index.to_string()),
+                    site,
+                )?;
             }
         }
         return Ok(());
@@ -93,7 +214,14 @@ pub(super) fn apply_transform(
     if let Ok(index) = segment.parse::<usize>() {
         if let Some(arr) = data.as_array_mut() {
             if let Some(item) = arr.get_mut(index) {
-                return apply_transform(item, rest, transform, regex_cache);
+                return walk(
+                    item,
+                    rest,
+                    transform,
+                    regex_cache,
+                    &child_path(data_path, segment),
+                    site,
+                );
             }
         }
         return Ok(());
@@ -104,29 +232,22 @@ pub(super) fn apply_transform(
         if let Some(key) = rest.first() {
             let remaining = &rest[1..];
 
-            // SPECIAL CASE: NullableOptional at final hop
-            if let Transform::NullableOptional {
-                original_required, ..
-            } = transform
-            {
-                if remaining.is_empty() {
-                    if !original_required {
-                        if let Some(obj) = data.as_object_mut() {
-                            if let Some(val) = obj.get(*key) {
-                                if val.is_null() {
-                                    obj.remove(*key);
-                                }
-                            }
-                        }
-                    }
-                    return Ok(());
-                }
+            // SPECIAL CASE: transforms acting on the property itself at the final hop
+            if remaining.is_empty() && site.at_property(data, key, transform) {
+                return Ok(());
             }
 
             // Normal navigation into property
             if let Some(obj) = data.as_object_mut() {
                 if let Some(child) = obj.get_mut(*key) {
-                    return apply_transform(child, remaining, transform, regex_cache);
+                    return walk(
+                        child,
+                        remaining,
+                        transform,
+                        regex_cache,
+                        &child_path(data_path, key),
+                        site,
+                    );
                 }
             }
             return Ok(());
@@ -134,10 +255,36 @@ pub(super) fn apply_transform(
     }
 
     // Unknown segment — skip silently for forward compatibility
-    tracing::trace!(segment, "unknown path segment, skipping");
+    crate::logging::trace!(segment, "unknown path segment, skipping");
     Ok(())
 }
 
+/// JSON Pointer of the child `key` of the node at `data_path`.
+fn child_path(data_path: &str, key: &str) -> String {
+    format!("{}/{}", data_path, escape_pointer_segment(key))
+}
+
+/// Whether `item` is a map entry whose value takes a different union branch
+/// than the one `rest` (`properties/value/anyOf/<n>/...`) leads into.
+/// Only entries of maps with union values carry the variant field (see
+/// [`Transform::MapToArray`]).
+fn in_other_variant(item: &Value, rest: &[&str]) -> bool {
+    let [properties, value, union, index, ..] = rest else {
+        return false;
+    };
+    if *properties != "properties" || *value != VALUE_FIELD || !matches!(*union, "anyOf" | "oneOf")
+    {
+        return false;
+    }
+    let (Ok(index), Some(variant)) = (
+        index.parse::<u64>(),
+        item.get(VARIANT_FIELD).and_then(Value::as_u64),
+    ) else {
+        return false;
+    };
+    variant != index
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,7 +308,16 @@ mod tests {
             original_required: false,
         };
         let path = &["dependentSchemas", "foo", "properties", "name"];
-        apply_transform(&mut data, path, &transform, &empty_cache()).unwrap();
+        apply_transform(
+            &mut data,
+            path,
+            &transform,
+            &RehydrateOptions::default(),
+            &empty_cache(),
+            "",
+            &mut Vec::new(),
+        )
+        .unwrap();
         // name was null-optional and null → removed, but "Alice" is non-null → kept
         assert_eq!(data, json!({"name": "Alice"}));
     }
@@ -175,7 +331,16 @@ mod tests {
             original_required: false,
         };
         let path = &["$defs", "Thing", "properties", "x"];
-        apply_transform(&mut data, path, &transform, &empty_cache()).unwrap();
+        apply_transform(
+            &mut data,
+            path,
+            &transform,
+            &RehydrateOptions::default(),
+            &empty_cache(),
+            "",
+            &mut Vec::new(),
+        )
+        .unwrap();
         // x was null and not originally required → removed
         assert_eq!(data, json!({}));
     }
@@ -194,7 +359,15 @@ mod tests {
         };
         let path = &["futureKeyword", "properties", "a"];
         // futureKeyword is unknown — should return Ok without modifying data
-        let result = apply_transform(&mut data, path, &transform, &empty_cache());
+        let result = apply_transform(
+            &mut data,
+            path,
+            &transform,
+            &RehydrateOptions::default(),
+            &empty_cache(),
+            "",
+            &mut Vec::new(),
+        );
         assert!(result.is_ok());
         assert_eq!(data, original);
     }
@@ -210,7 +383,15 @@ mod tests {
             path: String::new(),
             wrapper_key: "expected_key".to_string(),
         };
-        let result = apply_transform(&mut data, &[], &transform, &empty_cache());
+        let result = apply_transform(
+            &mut data,
+            &[],
+            &transform,
+            &RehydrateOptions::default(),
+            &empty_cache(),
+            "",
+            &mut Vec::new(),
+        );
         assert!(result.is_err());
         let err_msg = format!("{}", result.unwrap_err());
         assert!(err_msg.contains("expected_key"));
@@ -223,7 +404,15 @@ mod tests {
             path: String::new(),
             wrapper_key: "wrapper".to_string(),
         };
-        let result = apply_transform(&mut data, &[], &transform, &empty_cache());
+        let result = apply_transform(
+            &mut data,
+            &[],
+            &transform,
+            &RehydrateOptions::default(),
+            &empty_cache(),
+            "",
+            &mut Vec::new(),
+        );
         assert!(result.is_err());
     }
 
@@ -234,7 +423,16 @@ mod tests {
             path: String::new(),
             wrapper_key: "wrapper".to_string(),
         };
-        apply_transform(&mut data, &[], &transform, &empty_cache()).unwrap();
+        apply_transform(
+            &mut data,
+            &[],
+            &transform,
+            &RehydrateOptions::default(),
+            &empty_cache(),
+            "",
+            &mut Vec::new(),
+        )
+        .unwrap();
         // Should unwrap to inner value, stripping "leaked"
         assert_eq!(data, json!({"inner": 42}));
     }
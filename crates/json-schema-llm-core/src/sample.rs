@@ -0,0 +1,442 @@
+//! Synthetic instances of converted schemas, for round-trip self-checks.
+//!
+//! [`sample_converted`] answers a converted schema the way a model would:
+//! every property filled in, the first non-null branch of each union taken,
+//! and constraints the conversion dropped (recorded in the codec) honoured
+//! where a value can be built for them. Nodes the codec rehydrates from a
+//! JSON string get a JSON-encoded sample of the matching original subschema.
+//! Rehydrating the result should therefore satisfy the original schema;
+//! when it does not, the conversion lost something.
+//!
+//! Values are deterministic and minimal. `pattern` is not synthesized.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+use url::Url;
+
+use crate::anchor_utils::{build_anchor_map, default_base_uri, resolve_ref, ResolvedRef};
+use crate::codec::Codec;
+use crate::path_map::PathMap;
+use crate::schema_utils::{build_path, resolve_pointer, split_path};
+
+/// Depth past which arrays stop at `minItems` and optional properties are
+/// left out, and past twice which unions take their `null` branch, so
+/// recursive schemas still produce a finite instance.
+const MAX_DEPTH: usize = 16;
+
+/// Hops allowed when following `$ref` chains.
+const MAX_REF_HOPS: usize = 32;
+
+/// A synthetic instance of `converted`, the schema in `codec`'s conversion
+/// of `original`.
+pub fn sample_converted(converted: &Value, codec: &Codec, original: &Value) -> Value {
+    let mut dropped: HashMap<&str, Map<String, Value>> = HashMap::new();
+    for constraint in &codec.dropped_constraints {
+        dropped
+            .entry(constraint.path.as_str())
+            .or_default()
+            .insert(constraint.constraint.clone(), constraint.value.clone());
+    }
+
+    // Where each JSON-string transform landed in the converted schema, and
+    // the original subschema its string encodes.
+    let mut json_strings = HashMap::new();
+    for entry in PathMap::from_codec(codec, converted).entries() {
+        if matches!(
+            entry.transform.as_str(),
+            "json_string_parse" | "recursive_inflate"
+        ) {
+            json_strings.insert(
+                entry.converted.clone(),
+                original_at(original, &entry.original),
+            );
+        }
+    }
+
+    let sampler = Sampler {
+        root: converted,
+        anchors: Anchors::of(converted),
+        dropped,
+        json_strings,
+        original: Some(original),
+        all_properties: true,
+    };
+    sampler.sample(converted, "#", 0)
+}
+
+/// A synthetic instance of a plain JSON Schema: required properties only,
+/// first branch of each union, local `$ref`s resolved against `schema`.
+pub fn sample(schema: &Value) -> Value {
+    Sampler::plain(schema).sample(schema, "#", 0)
+}
+
+struct Sampler<'a> {
+    root: &'a Value,
+    anchors: Anchors,
+    /// Dropped constraints by converted-schema path.
+    dropped: HashMap<&'a str, Map<String, Value>>,
+    /// Converted paths rehydrated from a JSON string, with the original
+    /// subschema the string encodes (if it could be located).
+    json_strings: HashMap<String, Option<&'a Value>>,
+    original: Option<&'a Value>,
+    /// Fill optional properties too (converted schemas require them all).
+    all_properties: bool,
+}
+
+impl<'a> Sampler<'a> {
+    fn plain(root: &'a Value) -> Self {
+        Self {
+            root,
+            anchors: Anchors::of(root),
+            dropped: HashMap::new(),
+            json_strings: HashMap::new(),
+            original: None,
+            all_properties: false,
+        }
+    }
+
+    fn sample(&self, schema: &Value, path: &str, depth: usize) -> Value {
+        if let Some(source) = self.json_strings.get(path) {
+            let inner = match (source, self.original) {
+                (Some(source), Some(original)) => {
+                    Sampler::plain(original).sample(source, "#", depth + 1)
+                }
+                _ => json!({}),
+            };
+            return Value::String(inner.to_string());
+        }
+
+        let Some(obj) = schema.as_object() else {
+            return Value::Null;
+        };
+
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            // An unresolvable (e.g. remote) reference gets an empty object,
+            // which is at least a valid schema and a valid map.
+            let pointer = match resolve_ref(reference, &self.anchors.base, &self.anchors.map) {
+                ResolvedRef::Pointer(pointer) => pointer,
+                ResolvedRef::Unresolvable(_) => String::new(),
+            };
+            let referenced = match resolve_pointer(self.root, &pointer) {
+                Some(target) if depth < MAX_REF_HOPS => self.sample(target, &pointer, depth + 1),
+                Some(_) => Value::Null,
+                None => json!({}),
+            };
+            // A `$ref` with sibling keywords applies both, as with `allOf`.
+            let mut siblings = obj.clone();
+            siblings.remove("$ref");
+            if !SHAPE_KEYWORDS.iter().any(|k| siblings.contains_key(*k)) {
+                return referenced;
+            }
+            let own = self.sample(&Value::Object(siblings), path, depth);
+            return merge(own, referenced);
+        }
+        if let Some(value) = obj.get("const") {
+            return value.clone();
+        }
+        if let Some(first) = obj
+            .get("enum")
+            .and_then(Value::as_array)
+            .and_then(|e| e.first())
+        {
+            return first.clone();
+        }
+        for union in ["anyOf", "oneOf"] {
+            if let Some(variants) = obj.get(union).and_then(Value::as_array) {
+                let branch = self.sample_union(variants, &build_path(path, &[union]), depth);
+                // A union beside a `type` narrows that type rather than
+                // replacing it (e.g. `anyOf: [{required: [a]}, ...]`).
+                return if ["type", "properties", "items"]
+                    .iter()
+                    .any(|k| obj.contains_key(*k))
+                {
+                    merge(self.sample_typed(obj, path, depth), branch)
+                } else {
+                    branch
+                };
+            }
+        }
+        if let Some(parts) = obj.get("allOf").and_then(Value::as_array) {
+            let own = self.sample_typed(obj, path, depth);
+            return parts.iter().enumerate().fold(own, |merged, (i, part)| {
+                let part_path = build_path(path, &["allOf", &i.to_string()]);
+                merge(merged, self.sample(part, &part_path, depth))
+            });
+        }
+        self.sample_typed(obj, path, depth)
+    }
+
+    fn sample_union(&self, variants: &[Value], path: &str, depth: usize) -> Value {
+        let is_null = |v: &Value| v.get("type").and_then(Value::as_str) == Some("null");
+        let pick = if depth >= 2 * MAX_DEPTH {
+            variants.iter().position(is_null)
+        } else {
+            variants.iter().position(|v| !is_null(v))
+        };
+        match pick.or((!variants.is_empty()).then_some(0)) {
+            Some(i) => self.sample(
+                &variants[i],
+                &build_path(path, &[&i.to_string()]),
+                depth + 1,
+            ),
+            None => Value::Null,
+        }
+    }
+
+    fn sample_typed(&self, obj: &Map<String, Value>, path: &str, depth: usize) -> Value {
+        // Constraints dropped from this node still shape the sample.
+        let keyword = |name: &str| {
+            obj.get(name)
+                .or_else(|| self.dropped.get(path).and_then(|d| d.get(name)))
+        };
+        let ty = match obj.get("type") {
+            Some(Value::String(ty)) => ty.as_str(),
+            Some(Value::Array(types)) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|t| *t != "null")
+                .unwrap_or("null"),
+            _ if obj.contains_key("properties") || obj.contains_key("required") => "object",
+            _ if obj.contains_key("items") || obj.contains_key("prefixItems") => "array",
+            _ => "null",
+        };
+        let number = |name: &str| keyword(name).and_then(Value::as_f64);
+        let count = |name: &str| keyword(name).and_then(Value::as_u64).map(|n| n as usize);
+
+        match ty {
+            "object" => {
+                let required: Vec<&str> = obj
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .map(|r| r.iter().filter_map(Value::as_str).collect())
+                    .unwrap_or_default();
+                let mut out = Map::new();
+                if let Some(properties) = obj.get("properties").and_then(Value::as_object) {
+                    for (name, prop) in properties {
+                        let fill_all = self.all_properties && depth < MAX_DEPTH;
+                        if fill_all || required.contains(&name.as_str()) {
+                            let prop_path = build_path(path, &["properties", name]);
+                            out.insert(name.clone(), self.sample(prop, &prop_path, depth + 1));
+                        }
+                    }
+                }
+                Value::Object(out)
+            }
+            "array" => {
+                let mut out = Vec::new();
+                if let Some(prefix) = obj.get("prefixItems").and_then(Value::as_array) {
+                    for (i, item) in prefix.iter().enumerate() {
+                        let item_path = build_path(path, &["prefixItems", &i.to_string()]);
+                        out.push(self.sample(item, &item_path, depth + 1));
+                    }
+                }
+                if let Some(items) = obj.get("items").filter(|i| i.is_object()) {
+                    let min = count("minItems").unwrap_or(0);
+                    let max = count("maxItems").unwrap_or(usize::MAX);
+                    let want = if depth >= MAX_DEPTH { min } else { min.max(1) };
+                    let item_path = build_path(path, &["items"]);
+                    while out.len() < want.min(max) {
+                        out.push(self.sample(items, &item_path, depth + 1));
+                    }
+                }
+                Value::Array(out)
+            }
+            "string" => {
+                let format = obj.get("format").and_then(Value::as_str).unwrap_or("");
+                let mut text = sample_format(format).to_string();
+                if let Some(min) = count("minLength") {
+                    while text.chars().count() < min {
+                        text.push('x');
+                    }
+                }
+                if let Some(max) = count("maxLength") {
+                    text = text.chars().take(max).collect();
+                }
+                Value::String(text)
+            }
+            "integer" | "number" => {
+                let integer = ty == "integer";
+                let step = if integer { 1.0 } else { 0.5 };
+                let low = number("minimum")
+                    .into_iter()
+                    .chain(number("exclusiveMinimum").map(|m| m + step))
+                    .reduce(f64::max);
+                let high = number("maximum")
+                    .into_iter()
+                    .chain(number("exclusiveMaximum").map(|m| m - step))
+                    .reduce(f64::min);
+                let mut value = 0f64;
+                if let Some(low) = low {
+                    value = value.max(low);
+                }
+                if let Some(high) = high {
+                    value = value.min(high);
+                }
+                if let Some(multiple) = number("multipleOf").filter(|m| *m > 0.0) {
+                    value = (value / multiple).ceil() * multiple;
+                }
+                if integer {
+                    json!(value.ceil() as i64)
+                } else {
+                    json!(value)
+                }
+            }
+            "boolean" => Value::Bool(true),
+            _ => Value::Null,
+        }
+    }
+}
+
+/// Keywords that give a schema a shape of its own, beyond annotations.
+const SHAPE_KEYWORDS: &[&str] = &[
+    "type",
+    "properties",
+    "items",
+    "prefixItems",
+    "enum",
+    "const",
+    "anyOf",
+    "oneOf",
+    "allOf",
+];
+
+/// Combine samples of two schemas that both apply to one value: objects
+/// merge, anything else keeps the first non-null sample.
+fn merge(into: Value, from: Value) -> Value {
+    match (into, from) {
+        (Value::Object(mut into), Value::Object(from)) => {
+            for (key, value) in from {
+                into.entry(key).or_insert(value);
+            }
+            Value::Object(into)
+        }
+        (Value::Null, from) => from,
+        (into, _) => into,
+    }
+}
+
+/// A value for common `format`s, or a plain word.
+fn sample_format(format: &str) -> &'static str {
+    match format {
+        "date-time" => "2024-01-01T00:00:00Z",
+        "date" => "2024-01-01",
+        "time" => "00:00:00Z",
+        "duration" => "P1D",
+        "email" | "idn-email" => "user@example.com",
+        "hostname" | "idn-hostname" => "example.com",
+        "ipv4" => "192.0.2.1",
+        "ipv6" => "2001:db8::1",
+        "uri" | "iri" | "uri-reference" | "iri-reference" | "url" => "https://example.com/",
+        "uuid" => "00000000-0000-4000-8000-000000000000",
+        "json-pointer" => "/sample",
+        "regex" => "sample",
+        _ => "sample",
+    }
+}
+
+/// `$anchor` URIs in a schema, for anchor-style `$ref`s.
+struct Anchors {
+    base: Url,
+    map: HashMap<String, String>,
+}
+
+impl Anchors {
+    fn of(root: &Value) -> Self {
+        let default_base = default_base_uri();
+        let base = match root.get("$id").and_then(Value::as_str) {
+            Some(id) => default_base.join(id).unwrap_or(default_base),
+            None => default_base,
+        };
+        let map = build_anchor_map(root, Some(&base)).unwrap_or_default();
+        Self { base, map }
+    }
+}
+
+/// Walk `path` through `original`, following local `$ref`s on the way, so
+/// paths that pass through a referenced definition still resolve.
+fn original_at<'o>(original: &'o Value, path: &str) -> Option<&'o Value> {
+    let follow = |mut node: &'o Value| {
+        for _ in 0..MAX_REF_HOPS {
+            match node.get("$ref").and_then(Value::as_str) {
+                Some(reference) => node = resolve_pointer(original, reference)?,
+                None => break,
+            }
+        }
+        Some(node)
+    };
+    let mut node = follow(original)?;
+    for segment in split_path(path) {
+        node = match node {
+            Value::Object(obj) => obj.get(&segment)?,
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+        node = follow(node)?;
+    }
+    Some(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, rehydrate, ConvertOptions, Target};
+
+    #[test]
+    fn test_sample_honours_types_and_bounds() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 8, "maxLength": 10},
+                "age": {"type": "integer", "exclusiveMinimum": 17},
+                "when": {"type": "string", "format": "date"},
+                "tags": {"type": "array", "items": {"enum": ["a", "b"]}, "minItems": 2},
+                "note": {"type": "string"}
+            },
+            "required": ["name", "age", "when", "tags"]
+        });
+        assert_eq!(
+            sample(&schema),
+            json!({"name": "samplexx", "age": 18, "when": "2024-01-01", "tags": ["a", "a"]})
+        );
+    }
+
+    #[test]
+    fn test_sample_converted_applies_dropped_constraints() {
+        let original = json!({
+            "type": "object",
+            "properties": {"code": {"type": "string", "minLength": 12}}
+        });
+        let options = ConvertOptions {
+            target: Target::Claude,
+            ..ConvertOptions::default()
+        };
+        let result = convert(&original, &options).unwrap();
+        assert!(!result.codec.dropped_constraints.is_empty());
+
+        let data = sample_converted(&result.schema, &result.codec, &original);
+        let restored = rehydrate(&data, &result.codec, &original).unwrap();
+        assert_eq!(restored.data["code"].as_str().unwrap().len(), 12);
+        assert!(restored.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_sample_converted_encodes_json_strings() {
+        let original = json!({
+            "type": "object",
+            "properties": {
+                "meta": {
+                    "type": "object",
+                    "additionalProperties": true,
+                    "properties": {"id": {"type": "integer", "minimum": 3}},
+                    "required": ["id"]
+                }
+            },
+            "required": ["meta"]
+        });
+        let result = convert(&original, &ConvertOptions::default()).unwrap();
+        let data = sample_converted(&result.schema, &result.codec, &original);
+        let restored = rehydrate(&data, &result.codec, &original).unwrap();
+        assert_eq!(restored.data, json!({"meta": {"id": 3}}));
+    }
+}
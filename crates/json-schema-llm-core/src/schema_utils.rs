@@ -12,9 +12,11 @@
 
 use std::borrow::Cow;
 
+use serde::de::DeserializeOwned;
 use serde_json::{Map, Value};
 
 use crate::error::ConvertError;
+use crate::messages::{fill, MessageCatalog};
 
 // ---------------------------------------------------------------------------
 // JSON Pointer escaping (RFC 6901)
@@ -238,6 +240,62 @@ where
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Number precision
+// ---------------------------------------------------------------------------
+
+/// Round every number in `value` to what parsing it without the
+/// `arbitrary-precision` feature yields: an `i64` or `u64` when it is an
+/// integer in range, the nearest `f64` otherwise. A no-op without the
+/// feature.
+pub fn round_numbers(value: &mut Value) {
+    if !cfg!(feature = "arbitrary-precision") {
+        return;
+    }
+    match value {
+        Value::Number(n) => {
+            let rounded = match (n.as_i64(), n.as_u64()) {
+                (Some(i), _) => Some(i.into()),
+                (_, Some(u)) => Some(u.into()),
+                _ => n.as_f64().and_then(serde_json::Number::from_f64),
+            };
+            if let Some(rounded) = rounded {
+                *n = rounded;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(round_numbers),
+        Value::Object(obj) => obj.values_mut().for_each(round_numbers),
+        _ => {}
+    }
+}
+
+/// Parse JSON from `reader`, rounding numbers (see [`round_numbers`]) unless
+/// `preserve_precision`, which fails in a build without the
+/// `arbitrary-precision` feature.
+pub fn parse_value<T: DeserializeOwned>(
+    reader: impl std::io::Read,
+    preserve_precision: bool,
+) -> Result<T, ConvertError> {
+    check_preserve_precision(preserve_precision)?;
+    let mut value: Value = serde_json::from_reader(reader)?;
+    if !preserve_precision {
+        round_numbers(&mut value);
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Fail `preserve_precision` in a build that cannot honor it.
+pub(crate) fn check_preserve_precision(preserve_precision: bool) -> Result<(), ConvertError> {
+    if preserve_precision && !cfg!(feature = "arbitrary-precision") {
+        return Err(ConvertError::UnsupportedFeature {
+            path: "#".to_string(),
+            feature: "preserve_precision needs a build with the arbitrary-precision feature"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // JSON Pointer resolution (RFC 6901)
 // ---------------------------------------------------------------------------
@@ -274,6 +332,29 @@ pub(crate) fn resolve_pointer<'a>(root: &'a Value, pointer: &str) -> Option<&'a
     Some(current)
 }
 
+/// Append hint text to an object's `description` field.
+///
+/// If no `description` exists, creates one. If one exists, appends with a
+/// period separator.
+pub(crate) fn append_to_description(obj: &mut Map<String, Value>, hint_text: &str) {
+    match obj.get_mut("description") {
+        Some(Value::String(existing)) => {
+            // Append to existing description
+            if !existing.ends_with('.') && !existing.ends_with(' ') {
+                existing.push('.');
+            }
+            existing.push(' ');
+            existing.push_str(hint_text);
+        }
+        _ => {
+            obj.insert(
+                "description".to_string(),
+                Value::String(hint_text.to_string()),
+            );
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Opaque string description helpers
 // ---------------------------------------------------------------------------
@@ -288,21 +369,43 @@ pub(crate) fn resolve_pointer<'a>(root: &'a Value, pointer: &str) -> Option<&'a
 ///
 /// Example output:
 ///   `"A JSON-encoded string. Structure: [any]. Must contain: \"magic_token\". Produce valid, parseable JSON."`
-pub(crate) fn build_opaque_description(schema: &Value) -> String {
-    let structure = describe_schema_structure(schema, 0);
-    let constraints = collect_constraint_hints(schema);
-    if constraints.is_empty() {
-        format!("MUST be a valid JSON value serialized as a string. Structure: {structure}. Do NOT output plain text — the value must parse with JSON.parse().")
-    } else {
-        format!(
-            "MUST be a valid JSON value serialized as a string. Structure: {structure}. {constraints} Do NOT output plain text — the value must parse with JSON.parse()."
-        )
+pub(crate) fn build_opaque_description(schema: &Value, messages: &MessageCatalog) -> String {
+    wrap_opaque_description(schema, &describe_schema_structure(schema, 0), messages)
+}
+
+/// Number of nesting levels whose property names and types are spelled out
+/// in a depth-truncation summary. Anything deeper collapses to its kind.
+const TRUNCATION_SUMMARY_LEVELS: usize = 2;
+
+/// Like [`build_opaque_description`], but for sub-trees cut off by a
+/// provider depth budget.
+///
+/// Those sub-trees are deep by construction, so describing them in full
+/// would re-inline the very nesting that was just removed. Instead the
+/// summary lists property names and types for the first
+/// [`TRUNCATION_SUMMARY_LEVELS`] levels and collapses anything deeper to
+/// `object` / `array`.
+///
+/// Example structure: `{id: string, owner: {name: string, address: object}}`
+pub(crate) fn build_truncation_description(schema: &Value, messages: &MessageCatalog) -> String {
+    let structure = describe_structure(schema, 0, Some(TRUNCATION_SUMMARY_LEVELS));
+    wrap_opaque_description(schema, &structure, messages)
+}
+
+fn wrap_opaque_description(schema: &Value, structure: &str, messages: &MessageCatalog) -> String {
+    let mut constraints = collect_constraint_hints(schema, messages);
+    if !constraints.is_empty() {
+        constraints.push(' ');
     }
+    fill(
+        &messages.json_value_string,
+        &[("structure", &structure), ("constraints", &constraints)],
+    )
 }
 
 /// Extract human-readable constraint hints from a schema that is about to be
 /// opaque-stringified. These constraints would otherwise be silently lost.
-fn collect_constraint_hints(schema: &Value) -> String {
+fn collect_constraint_hints(schema: &Value, messages: &MessageCatalog) -> String {
     let obj = match schema.as_object() {
         Some(o) => o,
         None => return String::new(),
@@ -314,46 +417,44 @@ fn collect_constraint_hints(schema: &Value) -> String {
     if let Some(contains) = obj.get("contains") {
         if let Some(const_val) = contains.get("const") {
             let val_str = serde_json::to_string(const_val).unwrap_or_default();
-            hints.push(format!("array must include {val_str} as an element"));
+            hints.push(fill(&messages.contains_const, &[("value", &val_str)]));
         } else if let Some(type_val) = contains.get("type").and_then(|v| v.as_str()) {
-            hints.push(format!(
-                "array must include at least one element of type {type_val}"
-            ));
+            hints.push(fill(&messages.contains_type, &[("value", &type_val)]));
         } else {
-            hints.push("array must include at least one matching element".to_string());
+            hints.push(messages.contains_any.to_string());
         }
     }
 
     // minContains / maxContains
     if let Some(n) = obj.get("minContains").and_then(|v| v.as_u64()) {
         if n > 1 {
-            hints.push(format!("minimum {n} matching item(s)"));
+            hints.push(fill(&messages.min_contains, &[("value", &n)]));
         }
     }
     if let Some(n) = obj.get("maxContains").and_then(|v| v.as_u64()) {
-        hints.push(format!("maximum {n} matching item(s)"));
+        hints.push(fill(&messages.max_contains, &[("value", &n)]));
     }
 
     // minItems / maxItems
     if let Some(n) = obj.get("minItems").and_then(|v| v.as_u64()) {
-        hints.push(format!("minimum {n} item(s)"));
+        hints.push(fill(&messages.hint_min_items, &[("value", &n)]));
     }
     if let Some(n) = obj.get("maxItems").and_then(|v| v.as_u64()) {
-        hints.push(format!("maximum {n} item(s)"));
+        hints.push(fill(&messages.hint_max_items, &[("value", &n)]));
     }
 
     // uniqueItems
     if obj.get("uniqueItems").and_then(|v| v.as_bool()) == Some(true) {
-        hints.push("items must be unique".to_string());
+        hints.push(messages.hint_unique_items.to_string());
     }
 
     // patternProperties
     if let Some(pp) = obj.get("patternProperties").and_then(|v| v.as_object()) {
         let patterns: Vec<String> = pp.keys().cloned().collect();
         if !patterns.is_empty() {
-            hints.push(format!(
-                "includes properties matching pattern(s) [{}]",
-                patterns.join(", ")
+            hints.push(fill(
+                &messages.pattern_properties,
+                &[("value", &patterns.join(", "))],
             ));
         }
     }
@@ -369,6 +470,14 @@ fn collect_constraint_hints(schema: &Value) -> String {
 /// Uses a TypeScript-like object/array syntax for clarity.
 /// Depth parameter prevents runaway recursion (cap at 10 levels of description).
 pub(crate) fn describe_schema_structure(schema: &Value, depth: usize) -> String {
+    describe_structure(schema, depth, None)
+}
+
+/// [`describe_schema_structure`] with an optional level cap: at
+/// `depth >= levels`, objects and arrays are named by kind only. Capped
+/// (truncation) summaries also spell out unions, which the uncapped
+/// description leaves as `any`.
+fn describe_structure(schema: &Value, depth: usize, levels: Option<usize>) -> String {
     if depth > 10 {
         return "...".to_string();
     }
@@ -389,8 +498,19 @@ pub(crate) fn describe_schema_structure(schema: &Value, depth: usize) -> String
         }
     }
 
+    // Unions: `anyOf` variants or a `type` array (e.g. nullable wrappers).
+    if levels.is_some() {
+        if let Some(summary) = describe_union(schema, depth, levels) {
+            return summary;
+        }
+    }
+
     let schema_type = schema.get("type").and_then(|v| v.as_str()).unwrap_or("any");
 
+    if levels.is_some_and(|max| depth >= max) && matches!(schema_type, "object" | "array") {
+        return schema_type.to_string();
+    }
+
     match schema_type {
         "object" => {
             if let Some(props) = schema.get("properties").and_then(|v| v.as_object()) {
@@ -398,7 +518,7 @@ pub(crate) fn describe_schema_structure(schema: &Value, depth: usize) -> String
                     .iter()
                     .take(30) // cap field count
                     .map(|(name, sub)| {
-                        let desc = describe_schema_structure(sub, depth + 1);
+                        let desc = describe_structure(sub, depth + 1, levels);
                         format!("{name}: {desc}")
                     })
                     .collect();
@@ -417,7 +537,7 @@ pub(crate) fn describe_schema_structure(schema: &Value, depth: usize) -> String
             if schema.get("prefixItems").is_none() {
                 if let Some(items) = schema.get("items") {
                     if items.is_object() {
-                        let item_desc = describe_schema_structure(items, depth + 1);
+                        let item_desc = describe_structure(items, depth + 1, levels);
                         return format!("[{item_desc}]");
                     }
                 }
@@ -427,12 +547,12 @@ pub(crate) fn describe_schema_structure(schema: &Value, depth: usize) -> String
             let mut parts = Vec::new();
             if let Some(prefix) = schema.get("prefixItems").and_then(|v| v.as_array()) {
                 for item in prefix.iter().take(10) {
-                    parts.push(describe_schema_structure(item, depth + 1));
+                    parts.push(describe_structure(item, depth + 1, levels));
                 }
             } else if let Some(items_arr) = schema.get("items").and_then(|v| v.as_array()) {
                 // draft-4 tuple (if not normalized)
                 for item in items_arr.iter().take(10) {
-                    parts.push(describe_schema_structure(item, depth + 1));
+                    parts.push(describe_structure(item, depth + 1, levels));
                 }
             }
 
@@ -442,7 +562,7 @@ pub(crate) fn describe_schema_structure(schema: &Value, depth: usize) -> String
                     closed = true;
                 } else if items.is_object() {
                     // prefix + open items
-                    let item_desc = describe_schema_structure(items, depth + 1);
+                    let item_desc = describe_structure(items, depth + 1, levels);
                     parts.push(format!("...{}", item_desc));
                 } else if items.as_bool() == Some(true) {
                     if parts.is_empty() {
@@ -467,6 +587,23 @@ pub(crate) fn describe_schema_structure(schema: &Value, depth: usize) -> String
     }
 }
 
+/// `a | b` for an `anyOf` without a `type`, or for a `type` array.
+fn describe_union(schema: &Value, depth: usize, levels: Option<usize>) -> Option<String> {
+    if let Some(variants) = schema.get("anyOf").and_then(|v| v.as_array()) {
+        if !variants.is_empty() && schema.get("type").is_none() {
+            let parts: Vec<String> = variants
+                .iter()
+                .take(10)
+                .map(|v| describe_structure(v, depth, levels))
+                .collect();
+            return Some(parts.join(" | "));
+        }
+    }
+    let types = schema.get("type").and_then(|v| v.as_array())?;
+    let parts: Vec<&str> = types.iter().filter_map(|t| t.as_str()).collect();
+    (!parts.is_empty()).then(|| parts.join(" | "))
+}
+
 // ===========================================================================
 // Tests
 // ===========================================================================
@@ -723,4 +860,82 @@ mod tests {
     fn test_split_path_no_fragment() {
         assert_eq!(split_path("/properties/x"), vec!["properties", "x"]);
     }
+
+    // --- Number precision tests ---
+
+    #[test]
+    fn test_round_numbers_matches_plain_parsing() {
+        let text = r#"{"big": [12345678901234567890123, 0.1234567890123456789], "small": 42}"#;
+        let mut value: Value = serde_json::from_str(text).unwrap();
+        round_numbers(&mut value);
+        assert_eq!(
+            value.to_string(),
+            r#"{"big":[1.2345678901234568e+22,0.12345678901234568],"small":42}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_value() {
+        let value: Value = parse_value(r#"{"maximum": 10}"#.as_bytes(), false).unwrap();
+        assert_eq!(value, json!({"maximum": 10}));
+    }
+
+    #[test]
+    #[cfg(not(feature = "arbitrary-precision"))]
+    fn test_parse_value_preserve_precision_needs_feature() {
+        let err = parse_value::<Value>("1".as_bytes(), true).unwrap_err();
+        assert!(matches!(err, ConvertError::UnsupportedFeature { .. }));
+    }
+
+    // --- Structure description tests ---
+
+    #[test]
+    fn test_truncation_summary_describes_nullable_union() {
+        let capped = Some(TRUNCATION_SUMMARY_LEVELS);
+        let schema = json!({"anyOf": [{"type": "string"}, {"type": "null"}]});
+        assert_eq!(describe_structure(&schema, 0, capped), "string | null");
+        let schema = json!({"type": ["integer", "null"]});
+        assert_eq!(describe_structure(&schema, 0, capped), "integer | null");
+    }
+
+    #[test]
+    fn test_describe_schema_structure_leaves_unions_as_any() {
+        let schema = json!({"anyOf": [{"type": "string"}, {"type": "null"}]});
+        assert_eq!(describe_schema_structure(&schema, 0), "any");
+        let schema = json!({"type": ["integer", "null"]});
+        assert_eq!(describe_schema_structure(&schema, 0), "any");
+    }
+
+    #[test]
+    fn test_truncation_description_caps_levels() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string"},
+                "owner": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "address": {
+                            "type": "object",
+                            "properties": {"street": {"type": "string"}}
+                        },
+                        "tags": {"type": "array", "items": {"type": "string"}}
+                    }
+                }
+            }
+        });
+        let desc = build_truncation_description(&schema, &MessageCatalog::default());
+        assert!(
+            desc.contains(
+                "Structure: {id: string, owner: {address: object, name: string, tags: array}}."
+            ),
+            "got: {desc}"
+        );
+        // The unbounded description still spells out the full tree.
+        assert!(
+            build_opaque_description(&schema, &MessageCatalog::default())
+                .contains("street: string")
+        );
+    }
 }
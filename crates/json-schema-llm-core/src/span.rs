@@ -0,0 +1,468 @@
+//! Map JSON Pointers back to line/column positions in schema source text.
+//!
+//! `serde_json::Value` drops all position information, so instead of
+//! carrying spans through the pipeline we re-scan the original text on the
+//! (cold) error path and walk it segment by segment.
+//!
+//! The same scanner recovers source key order, which `Value`'s sorted maps
+//! also lose, for Gemini `propertyOrdering`, and finds the duplicate keys
+//! that parsing silently collapses.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::SourceLocation;
+use crate::schema_utils::{build_path, split_path};
+
+/// Locate `pointer` in `text`, falling back to the nearest enclosing value
+/// that exists. Returns `None` only if the text is not scannable JSON.
+pub(crate) fn locate_pointer(text: &str, pointer: &str) -> Option<SourceLocation> {
+    let (offset, _) = walk(text, pointer)?;
+    Some(location_of(text, offset))
+}
+
+/// Locate `pointer` in `text` only if every segment exists.
+pub(crate) fn locate_pointer_exact(text: &str, pointer: &str) -> Option<SourceLocation> {
+    match walk(text, pointer)? {
+        (offset, true) => Some(location_of(text, offset)),
+        (_, false) => None,
+    }
+}
+
+/// Walk `pointer` through `text`, returning the byte offset of the deepest
+/// value reached and whether the full pointer resolved.
+fn walk(text: &str, pointer: &str) -> Option<(usize, bool)> {
+    let mut scanner = Scanner {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    scanner.skip_ws();
+    let mut current = scanner.pos;
+    for segment in split_path(pointer) {
+        scanner.pos = current;
+        match scanner.find_child(&segment) {
+            Some(child) => current = child,
+            None => return Some((current, false)),
+        }
+    }
+    Some((current, true))
+}
+
+/// Key order, as written, of every object that is the value of a
+/// `properties` key, indexed by the pointer to that object (e.g.
+/// `#/properties`, `#/$defs/User/properties`). Returns `None` if the text is
+/// not scannable JSON.
+pub(crate) fn properties_key_orders(text: &str) -> Option<HashMap<String, Vec<String>>> {
+    let mut scanner = Scanner {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    let mut out = HashMap::new();
+    scanner.collect_keys("#", false, 0, &mut out)?;
+    Some(out)
+}
+
+/// Every key that repeats an earlier key of the same object, as the pointer
+/// to it (e.g. `#/properties/id`) and the location of the repeat, in source
+/// order. `serde_json` keeps the last occurrence. Returns `None` if the text
+/// is not scannable JSON.
+pub(crate) fn duplicate_keys(text: &str) -> Option<Vec<(String, SourceLocation)>> {
+    let mut scanner = Scanner {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    let mut offsets = Vec::new();
+    scanner.collect_duplicates("#", 0, &mut offsets)?;
+    Some(
+        offsets
+            .into_iter()
+            .map(|(pointer, offset)| (pointer, location_of(text, offset)))
+            .collect(),
+    )
+}
+
+/// Nesting depth past which the recursive scans give up, matching
+/// `serde_json`'s recursion limit so any text that parses is scanned.
+const MAX_DEPTH: usize = 128;
+
+fn location_of(text: &str, offset: usize) -> SourceLocation {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    SourceLocation { line, column }
+}
+
+/// Minimal JSON scanner: just enough to skip values and match keys.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    /// With `pos` at an object or array, return the offset of the child
+    /// named by `segment` (a key, or an array index).
+    fn find_child(&mut self, segment: &str) -> Option<usize> {
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                loop {
+                    self.skip_ws();
+                    if self.peek()? == b'}' {
+                        return None;
+                    }
+                    let key = self.read_string()?;
+                    self.skip_ws();
+                    self.expect(b':')?;
+                    self.skip_ws();
+                    if key == segment {
+                        return Some(self.pos);
+                    }
+                    self.skip_value()?;
+                    self.skip_ws();
+                    if self.peek()? == b',' {
+                        self.pos += 1;
+                    }
+                }
+            }
+            b'[' => {
+                let index: usize = segment.parse().ok()?;
+                self.pos += 1;
+                for i in 0.. {
+                    self.skip_ws();
+                    if self.peek()? == b']' {
+                        return None;
+                    }
+                    if i == index {
+                        return Some(self.pos);
+                    }
+                    self.skip_value()?;
+                    self.skip_ws();
+                    if self.peek()? == b',' {
+                        self.pos += 1;
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Walk the value at `pos` (named by `pointer`), recording the key order
+    /// of each `properties` object into `out`. `record` is set when this
+    /// value is itself a `properties` object. `None` past [`MAX_DEPTH`].
+    fn collect_keys(
+        &mut self,
+        pointer: &str,
+        record: bool,
+        depth: usize,
+        out: &mut HashMap<String, Vec<String>>,
+    ) -> Option<()> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        self.skip_ws();
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                let mut keys = Vec::new();
+                loop {
+                    self.skip_ws();
+                    match self.peek()? {
+                        b'}' => {
+                            self.pos += 1;
+                            break;
+                        }
+                        b',' => {
+                            self.pos += 1;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    let key = self.read_string()?;
+                    self.skip_ws();
+                    self.expect(b':')?;
+                    let child = build_path(pointer, &[&key]);
+                    self.collect_keys(&child, key == "properties", depth + 1, out)?;
+                    if record {
+                        keys.push(key);
+                    }
+                }
+                if record {
+                    out.insert(pointer.to_string(), keys);
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut index = 0usize;
+                loop {
+                    self.skip_ws();
+                    match self.peek()? {
+                        b']' => {
+                            self.pos += 1;
+                            break;
+                        }
+                        b',' => {
+                            self.pos += 1;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    let child = build_path(pointer, &[&index.to_string()]);
+                    self.collect_keys(&child, false, depth + 1, out)?;
+                    index += 1;
+                }
+            }
+            _ => self.skip_value()?,
+        }
+        Some(())
+    }
+
+    /// Walk the value at `pos` (named by `pointer`), recording each repeated
+    /// key's pointer and byte offset into `out`. `None` past [`MAX_DEPTH`].
+    fn collect_duplicates(
+        &mut self,
+        pointer: &str,
+        depth: usize,
+        out: &mut Vec<(String, usize)>,
+    ) -> Option<()> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        self.skip_ws();
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                let mut seen = HashSet::new();
+                loop {
+                    self.skip_ws();
+                    match self.peek()? {
+                        b'}' => {
+                            self.pos += 1;
+                            break;
+                        }
+                        b',' => {
+                            self.pos += 1;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    let start = self.pos;
+                    let key = self.read_string()?;
+                    self.skip_ws();
+                    self.expect(b':')?;
+                    let child = build_path(pointer, &[&key]);
+                    if !seen.insert(key) {
+                        out.push((child.clone(), start));
+                    }
+                    self.collect_duplicates(&child, depth + 1, out)?;
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut index = 0usize;
+                loop {
+                    self.skip_ws();
+                    match self.peek()? {
+                        b']' => {
+                            self.pos += 1;
+                            break;
+                        }
+                        b',' => {
+                            self.pos += 1;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    let child = build_path(pointer, &[&index.to_string()]);
+                    self.collect_duplicates(&child, depth + 1, out)?;
+                    index += 1;
+                }
+            }
+            _ => self.skip_value()?,
+        }
+        Some(())
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => self.skip_string(),
+            b'{' | b'[' => {
+                // Strings are skipped whole, so brackets inside them don't count.
+                let mut depth = 0usize;
+                loop {
+                    match self.peek()? {
+                        b'"' => self.skip_string()?,
+                        b'{' | b'[' => {
+                            depth += 1;
+                            self.pos += 1;
+                        }
+                        b'}' | b']' => {
+                            depth -= 1;
+                            self.pos += 1;
+                            if depth == 0 {
+                                return Some(());
+                            }
+                        }
+                        _ => self.pos += 1,
+                    }
+                }
+            }
+            _ => {
+                // Scalar: runs until a delimiter.
+                while let Some(b) = self.peek() {
+                    if matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace() {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                Some(())
+            }
+        }
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.expect(b'"')?;
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    /// Read and unescape a string literal.
+    fn read_string(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.skip_string()?;
+        let raw = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?;
+        serde_json::from_str(raw).ok()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.peek()? == byte).then(|| self.pos += 1)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r##"{
+  "type": "object",
+  "properties": {
+    "a/b": { "type": "string", "description": "has } and ] inside" },
+    "list": {
+      "prefixItems": [
+        { "type": "integer" },
+        { "$ref": "#/$defs/Missing" }
+      ]
+    }
+  }
+}"##;
+
+    fn loc(line: usize, column: usize) -> Option<SourceLocation> {
+        Some(SourceLocation { line, column })
+    }
+
+    #[test]
+    fn test_locate_root() {
+        assert_eq!(locate_pointer(TEXT, "#"), loc(1, 1));
+    }
+
+    #[test]
+    fn test_locate_escaped_key() {
+        assert_eq!(locate_pointer(TEXT, "#/properties/a~1b"), loc(4, 12));
+    }
+
+    #[test]
+    fn test_locate_array_element_past_brackets_in_strings() {
+        assert_eq!(
+            locate_pointer(TEXT, "#/properties/list/prefixItems/1/$ref"),
+            loc(8, 19)
+        );
+    }
+
+    #[test]
+    fn test_missing_segment_falls_back_to_parent() {
+        assert_eq!(
+            locate_pointer(TEXT, "#/properties/list/items/properties/x"),
+            loc(5, 13)
+        );
+        assert_eq!(locate_pointer_exact(TEXT, "#/properties/list/items"), None);
+    }
+
+    #[test]
+    fn test_properties_key_orders_keep_source_order() {
+        let text = r#"{
+          "properties": {
+            "zeta": { "type": "object", "properties": { "b": {}, "a": {} } },
+            "alpha": { "enum": [{ "properties": { "ignored": 1 } }] }
+          }
+        }"#;
+        let orders = properties_key_orders(text).unwrap();
+        assert_eq!(orders["#/properties"], ["zeta", "alpha"]);
+        assert_eq!(orders["#/properties/zeta/properties"], ["b", "a"]);
+        // Recorded by key name alone; callers only look up schema paths.
+        assert_eq!(orders.len(), 3);
+    }
+
+    #[test]
+    fn test_duplicate_keys_report_each_repeat() {
+        let text = r#"{
+  "type": "object",
+  "properties": {
+    "id": { "type": "string" },
+    "id": { "type": "integer" },
+    "tags": { "items": [{ "a": 1, "a": 2 }] }
+  },
+  "type": "array"
+}"#;
+        assert_eq!(
+            duplicate_keys(text).unwrap(),
+            [
+                (
+                    "#/properties/id".to_string(),
+                    SourceLocation { line: 5, column: 5 }
+                ),
+                (
+                    "#/properties/tags/items/0/a".to_string(),
+                    SourceLocation {
+                        line: 6,
+                        column: 35
+                    }
+                ),
+                ("#/type".to_string(), SourceLocation { line: 8, column: 3 }),
+            ]
+        );
+        assert_eq!(duplicate_keys(r#"{"a": {"a": 1}}"#).unwrap(), []);
+    }
+
+    #[test]
+    fn test_scans_stop_at_max_depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(duplicate_keys(&nested(MAX_DEPTH)).unwrap(), []);
+        assert!(properties_key_orders(&nested(MAX_DEPTH)).is_some());
+        assert!(duplicate_keys(&nested(50_000)).is_none());
+        assert!(properties_key_orders(&nested(50_000)).is_none());
+    }
+
+    #[test]
+    fn test_location_counts_chars_not_bytes() {
+        let text = "{\"é\": {\"x\": 1}}";
+        assert_eq!(locate_pointer(text, "#/é/x"), loc(1, 13));
+    }
+}
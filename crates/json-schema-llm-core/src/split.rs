@@ -0,0 +1,471 @@
+//! Splitting of schemas too large for a provider's size limit.
+//!
+//! Some providers reject structured-output schemas above a hard size.
+//! Rather than failing on one, [`split_schema`] converts it and, while the
+//! converted schema is over the limit, detaches the root property whose
+//! converted form is largest into a separate *part*: a self-contained
+//! component schema the model fills in a follow-up call. Parts still over
+//! the limit are split the same way, so a plan can nest (`/order`, then
+//! `/order/items`).
+//!
+//! The primary codec records each detached property as a
+//! `FilteredProperty` with keyword `split`. [`SplitPlan::codec`] bundles it
+//! with every part's codec, and [`SplitCodec::rehydrate`] reassembles the
+//! answers to all calls into one document of the original shape.
+//!
+//! Only properties of the root object (after inlining a root `$ref`) are
+//! detached. Each part carries the `$defs` / `definitions` entries it
+//! reaches, so its `$ref`s still resolve, and is converted as a root schema:
+//! an array part is wrapped like any non-object root.
+//!
+//! ```rust
+//! use json_schema_llm_core::{convert, split_schema, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "id": { "type": "string" },
+//!         "address": {
+//!             "type": "object",
+//!             "properties": {
+//!                 "street": { "type": "string", "description": "Street and number." },
+//!                 "city": { "type": "string", "description": "City or town." }
+//!             }
+//!         }
+//!     }
+//! });
+//! let options = ConvertOptions::default();
+//! let full = convert(&schema, &options).unwrap();
+//! let limit = serde_json::to_string(&full.schema).unwrap().len() - 1;
+//!
+//! let plan = split_schema(&schema, limit, &options).unwrap();
+//! assert_eq!(plan.parts[0].data_path, "/address");
+//!
+//! let codec = plan.codec();
+//! let primary = json!({ "id": "a1" });
+//! let address = json!({ "street": "1 Main St", "city": null });
+//! let restored = codec.rehydrate(&primary, &[address], &schema).unwrap();
+//! assert_eq!(restored.data["address"]["street"], "1 Main St");
+//! ```
+
+use std::cmp::Reverse;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::codec::{Codec, Transform};
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+use crate::project::{inline_refs, retain_reachable_defs};
+use crate::rehydrator::RehydrateResult;
+use crate::schema_utils::{escape_pointer_segment, unescape_pointer_segment};
+use crate::ConvertResult;
+
+/// `Transform::FilteredProperty` keyword recorded for detached properties.
+const SPLIT_KEYWORD: &str = "split";
+
+/// A schema converted in several calls: the primary schema, then one part
+/// per detached property.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitPlan {
+    /// The conversion of the schema with every part removed.
+    pub primary: ConvertResult,
+    /// Detached properties, each before the parts split off from it.
+    pub parts: Vec<SplitPart>,
+}
+
+/// One follow-up call of a [`SplitPlan`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitPart {
+    /// Where the answer goes in the rehydrated document, as a JSON Pointer
+    /// into the data (e.g. `/order/items`).
+    pub data_path: String,
+    /// The conversion of the detached component.
+    pub result: ConvertResult,
+}
+
+/// The codecs of a [`SplitPlan`], for reassembling its answers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitCodec {
+    pub primary: Codec,
+    pub parts: Vec<SplitCodecPart>,
+}
+
+/// The codec of one [`SplitPart`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitCodecPart {
+    pub data_path: String,
+    pub codec: Codec,
+}
+
+impl SplitPlan {
+    /// The codecs of the primary schema and every part.
+    pub fn codec(&self) -> SplitCodec {
+        SplitCodec {
+            primary: self.primary.codec.clone(),
+            parts: self
+                .parts
+                .iter()
+                .map(|part| SplitCodecPart {
+                    data_path: part.data_path.clone(),
+                    codec: part.result.codec.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl SplitCodec {
+    /// Rehydrate the answer to the primary schema and the answers to the
+    /// parts, in plan order, into one document shaped like `original`.
+    /// Warnings from a part carry data paths into the whole document.
+    ///
+    /// A part whose parent came back `null` (an optional object left out)
+    /// is dropped.
+    ///
+    /// # Errors
+    ///
+    /// [`ConvertError::SchemaError`] if the number of part answers differs
+    /// from the plan's or a part's data path is not declared by `original`;
+    /// otherwise whatever [`rehydrate`](crate::rehydrate) returns.
+    pub fn rehydrate(
+        &self,
+        primary: &Value,
+        parts: &[Value],
+        original: &Value,
+    ) -> Result<RehydrateResult, ConvertError> {
+        if parts.len() != self.parts.len() {
+            return Err(schema_error(
+                "#",
+                &format!(
+                    "split plan has {} part(s), got {} answer(s)",
+                    self.parts.len(),
+                    parts.len()
+                ),
+            ));
+        }
+        let mut restored = crate::rehydrate(primary, &self.primary, original)?;
+        for (part, answer) in self.parts.iter().zip(parts) {
+            let component = component_at(original, &part.data_path)?;
+            let result = crate::rehydrate(answer, &part.codec, &component)?;
+            restored
+                .warnings
+                .extend(result.warnings.into_iter().map(|mut warning| {
+                    warning.data_path = join_data_path(&part.data_path, &warning.data_path);
+                    warning
+                }));
+            insert_at(&mut restored.data, &part.data_path, result.data);
+        }
+        Ok(restored)
+    }
+}
+
+/// Convert `schema`, splitting off root properties until every converted
+/// schema serializes to at most `max_bytes` bytes.
+///
+/// A schema that already fits comes back as a plan without parts.
+///
+/// # Errors
+///
+/// [`ConvertError::ResourceLimitExceeded`] if a schema over the limit has no
+/// root property left to detach; otherwise whatever [`convert`] returns for
+/// the primary schema or a part.
+///
+/// [`convert`]: crate::convert
+pub fn split_schema(
+    schema: &Value,
+    max_bytes: usize,
+    options: &ConvertOptions,
+) -> Result<SplitPlan, ConvertError> {
+    let mut parts = Vec::new();
+    let primary = split_node(schema, "", max_bytes, options, &mut parts)?;
+    Ok(SplitPlan { primary, parts })
+}
+
+/// Convert `schema` (the component at `data_path`), detaching properties
+/// until it fits and appending the parts split off to `parts`.
+fn split_node(
+    schema: &Value,
+    data_path: &str,
+    max_bytes: usize,
+    options: &ConvertOptions,
+    parts: &mut Vec<SplitPart>,
+) -> Result<ConvertResult, ConvertError> {
+    let mut schema = schema.clone();
+    let mut detached = Vec::new();
+    let mut result = loop {
+        let result = crate::convert(&schema, options)?;
+        if serialized_len(&result.schema) <= max_bytes {
+            break result;
+        }
+        let Some((name, rest, component)) = detach_largest(&schema, &result.schema)? else {
+            return Err(ConvertError::ResourceLimitExceeded {
+                path: "#".to_string(),
+                resource: format!(
+                    "converted schema at data path `{}`",
+                    if data_path.is_empty() { "/" } else { data_path }
+                ),
+                limit: max_bytes as u64,
+            });
+        };
+        schema = rest;
+        detached.push((name, component));
+    };
+
+    result.codec.transforms.splice(
+        0..0,
+        detached
+            .iter()
+            .map(|(name, _)| Transform::FilteredProperty {
+                path: "#".to_string(),
+                property: name.clone(),
+                keyword: SPLIT_KEYWORD.to_string(),
+                placeholder: None,
+            }),
+    );
+    for (name, component) in detached {
+        let part_path = format!("{}/{}", data_path, escape_pointer_segment(&name));
+        let index = parts.len();
+        let part = split_node(&component, &part_path, max_bytes, options, parts)?;
+        parts.insert(
+            index,
+            SplitPart {
+                data_path: part_path,
+                result: part,
+            },
+        );
+    }
+    Ok(result)
+}
+
+/// Detach the root property of `schema` that takes up the most of
+/// `converted`, returning its name, the rest of the schema and the
+/// component. `None` if no root property is left.
+fn detach_largest(
+    schema: &Value,
+    converted: &Value,
+) -> Result<Option<(String, Value, Value)>, ConvertError> {
+    let Some(Value::Object(properties)) = converted.get("properties") else {
+        return Ok(None);
+    };
+    let mut candidates: Vec<(usize, &String)> = properties
+        .iter()
+        .map(|(name, node)| (serialized_len(node), name))
+        .collect();
+    candidates.sort_by_key(|&(len, name)| (Reverse(len), name));
+
+    let Some(mut obj) = inline_refs(schema, schema, "#")? else {
+        return Ok(None);
+    };
+    for (_, name) in candidates {
+        let Some(Value::Object(declared)) = obj.get_mut("properties") else {
+            return Ok(None);
+        };
+        let Some(component) = declared.remove(name) else {
+            continue;
+        };
+        if let Some(Value::Array(required)) = obj.get_mut("required") {
+            required.retain(|r| r.as_str() != Some(name));
+        }
+        let mut rest = Value::Object(obj);
+        retain_reachable_defs(schema, &mut rest);
+        return Ok(Some((name.clone(), rest, with_defs(schema, component))));
+    }
+    Ok(None)
+}
+
+/// The component at `data_path` of `root`, as [`split_schema`] detached it.
+fn component_at(root: &Value, data_path: &str) -> Result<Value, ConvertError> {
+    let mut node = root.clone();
+    for segment in data_path.split('/').skip(1) {
+        let name = unescape_pointer_segment(segment);
+        node = inline_refs(root, &node, "#")?
+            .and_then(|mut obj| match obj.remove("properties") {
+                Some(Value::Object(mut properties)) => properties.remove(name.as_ref()),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                schema_error(
+                    "#",
+                    &format!("data path `{}` is not declared by the schema", data_path),
+                )
+            })?;
+    }
+    Ok(with_defs(root, node))
+}
+
+/// `component` with the `$defs` / `definitions` entries of `root` it
+/// reaches.
+fn with_defs(root: &Value, component: Value) -> Value {
+    let Value::Object(mut obj) = component else {
+        return component;
+    };
+    for keyword in ["$defs", "definitions"] {
+        if let Some(defs) = root.get(keyword) {
+            obj.entry(keyword).or_insert_with(|| defs.clone());
+        }
+    }
+    let mut component = Value::Object(obj);
+    retain_reachable_defs(root, &mut component);
+    component
+}
+
+/// Put `value` at `data_path` of `data`, if its parent is an object.
+fn insert_at(data: &mut Value, data_path: &str, value: Value) {
+    let Some((parent, name)) = data_path.rsplit_once('/') else {
+        return;
+    };
+    if let Some(Value::Object(obj)) = data.pointer_mut(parent) {
+        obj.insert(unescape_pointer_segment(name).into_owned(), value);
+    }
+}
+
+/// `path`, a data path within the part at `prefix`, as a path into the
+/// whole document.
+fn join_data_path(prefix: &str, path: &str) -> String {
+    match path {
+        "" | "/" => prefix.to_string(),
+        _ => format!("{}{}", prefix, path),
+    }
+}
+
+fn serialized_len(value: &Value) -> usize {
+    serde_json::to_string(value).map_or(0, |s| s.len())
+}
+
+fn schema_error(path: &str, message: &str) -> ConvertError {
+    ConvertError::SchemaError {
+        path: path.to_string(),
+        message: message.to_string(),
+        location: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn order_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "order": {
+                    "type": "object",
+                    "properties": {
+                        "customer": { "type": "string" },
+                        "items": {
+                            "type": "array",
+                            "items": { "$ref": "#/$defs/Item" }
+                        }
+                    },
+                    "required": ["customer", "items"]
+                }
+            },
+            "required": ["id", "order"],
+            "$defs": {
+                "Item": {
+                    "type": "object",
+                    "properties": {
+                        "sku": { "type": "string", "description": "Stock keeping unit of the item." },
+                        "name": { "type": "string", "description": "Display name of the item." },
+                        "price": { "type": "number", "description": "Unit price in the order currency." },
+                        "quantity": { "type": "integer", "description": "Number of units ordered." }
+                    },
+                    "required": ["sku", "name", "price", "quantity"]
+                }
+            }
+        })
+    }
+
+    fn size_of(schema: &Value) -> usize {
+        serialized_len(
+            &crate::convert(schema, &ConvertOptions::default())
+                .unwrap()
+                .schema,
+        )
+    }
+
+    #[test]
+    fn test_split_fits_without_parts() {
+        let schema = order_schema();
+        let plan = split_schema(&schema, usize::MAX, &ConvertOptions::default()).unwrap();
+        assert!(plan.parts.is_empty());
+        assert!(plan.primary.schema["properties"].get("order").is_some());
+    }
+
+    #[test]
+    fn test_split_nests_and_round_trips() {
+        let schema = order_schema();
+        let order = component_at(&schema, "/order").unwrap();
+        // The order component is over the limit on its own; its items are
+        // what has to go.
+        let limit = size_of(&order) - 1;
+        let plan = split_schema(&schema, limit, &ConvertOptions::default()).unwrap();
+
+        let paths: Vec<&str> = plan.parts.iter().map(|p| p.data_path.as_str()).collect();
+        assert_eq!(paths, ["/order", "/order/items"]);
+        assert!(plan.primary.schema["properties"].get("order").is_none());
+        assert_eq!(plan.primary.schema["required"], json!(["id"]));
+        for part in &plan.parts {
+            assert!(serialized_len(&part.result.schema) <= limit);
+        }
+        // The items part keeps the definition its `$ref` reaches.
+        let items = component_at(&schema, "/order/items").unwrap();
+        assert!(items["$defs"].get("Item").is_some());
+
+        let codec: SplitCodec =
+            serde_json::from_value(serde_json::to_value(plan.codec()).unwrap()).unwrap();
+        let item = json!({ "sku": "A-1", "name": "Widget", "price": 2.5, "quantity": 4 });
+        // The array part is converted as a root, so it comes back wrapped.
+        let restored = codec
+            .rehydrate(
+                &json!({ "id": "o-1" }),
+                &[json!({ "customer": "Ada" }), json!({ "result": [item] })],
+                &schema,
+            )
+            .unwrap();
+        assert_eq!(
+            restored.data,
+            json!({ "id": "o-1", "order": { "customer": "Ada", "items": [item] } })
+        );
+    }
+
+    #[test]
+    fn test_split_records_detached_properties() {
+        let schema = order_schema();
+        let limit = size_of(&schema) - 1;
+        let plan = split_schema(&schema, limit, &ConvertOptions::default()).unwrap();
+        assert_eq!(plan.parts.len(), 1);
+        assert!(matches!(
+            &plan.primary.codec.transforms[0],
+            Transform::FilteredProperty { property, keyword, placeholder: None, .. }
+                if property == "order" && keyword == SPLIT_KEYWORD
+        ));
+        let err = plan
+            .codec()
+            .rehydrate(&json!({ "id": "o-1" }), &[], &schema)
+            .unwrap_err();
+        assert!(err.to_string().contains("1 part(s)"), "{}", err);
+    }
+
+    #[test]
+    fn test_split_leaf_over_limit_errors() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "status": { "enum": ["draft", "submitted", "approved"] } }
+        });
+        let err = split_schema(&schema, 8, &ConvertOptions::default()).unwrap_err();
+        assert!(
+            matches!(err, ConvertError::ResourceLimitExceeded { limit: 8, .. }),
+            "{:?}",
+            err
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Minimal-token variant of a converted schema, for
+//! [`ConvertOptions::strip_descriptions`](crate::ConvertOptions::strip_descriptions).
+//!
+//! Descriptions and titles are often most of a converted schema's tokens:
+//! the source's own documentation plus the constraint hints Pass 7 folds
+//! in. High-volume callers that don't need them can drop them. Nodes whose
+//! description is the model's only instruction — JSON-string fields, the
+//! JSON-string branch of an overflowed union, `$node_id` references — keep
+//! theirs, as do nodes matching
+//! [`ConvertOptions::keep_descriptions`](crate::ConvertOptions::keep_descriptions).
+//!
+//! Stripping never changes the codec, so the full and the stripped schema
+//! rehydrate with the same one.
+
+use serde_json::Value;
+
+use crate::codec::{Codec, Transform};
+use crate::diagnostics::glob_match;
+use crate::error::ConvertError;
+use crate::path_map::converted_transform_paths;
+use crate::schema_utils::{recurse_into_children, split_path};
+
+/// Remove `description` and `title` from every node of `schema` except the
+/// instruction-bearing ones and those matching a `keep` glob.
+pub(crate) fn strip_descriptions(schema: Value, codec: &Codec, keep: &[String]) -> Value {
+    let instructions: Vec<String> = codec
+        .transforms
+        .iter()
+        .zip(converted_transform_paths(codec, &schema))
+        .filter(|(t, _)| {
+            matches!(
+                t,
+                Transform::JsonStringParse { .. }
+                    | Transform::RecursiveInflate { .. }
+                    | Transform::RecursiveNodeRef { .. }
+                    | Transform::AnyOfOverflow { .. }
+            )
+        })
+        .map(|(_, path)| path)
+        .collect();
+    strip_node(schema, "#", 0, &instructions, keep).expect("stripping does not fail")
+}
+
+fn strip_node(
+    schema: Value,
+    path: &str,
+    depth: usize,
+    instructions: &[String],
+    keep: &[String],
+) -> Result<Value, ConvertError> {
+    let Value::Object(mut obj) = schema else {
+        return Ok(schema);
+    };
+    recurse_into_children(&mut obj, path, depth, &mut |node, child, depth| {
+        strip_node(node, child, depth, instructions, keep)
+    })?;
+    let kept = instructions.iter().any(|site| within_union(site, path))
+        || keep.iter().any(|glob| glob_match(glob, path));
+    if !kept {
+        obj.remove("description");
+        obj.remove("title");
+    }
+    Ok(Value::Object(obj))
+}
+
+/// Whether `path` is `site` itself or one of its union branches, at any
+/// depth (`site/anyOf/0/anyOf/1`).
+fn within_union(site: &str, path: &str) -> bool {
+    let Some(rest) = path.strip_prefix(site) else {
+        return false;
+    };
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return false;
+    }
+    let segments = split_path(&format!("#{}", rest));
+    segments.chunks(2).all(|pair| match pair {
+        [keyword, index] => {
+            (keyword == "anyOf" || keyword == "oneOf") && index.parse::<usize>().is_ok()
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, ConvertOptions};
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "title": "Order",
+            "description": "A customer order.",
+            "properties": {
+                "id": { "type": "string", "description": "Order id.", "minLength": 3 },
+                "title": { "type": "string", "title": "Title" },
+                "meta": { "description": "Free-form metadata." },
+                "extra": { "type": "object", "additionalProperties": true }
+            },
+            "required": ["id", "meta"]
+        })
+    }
+
+    #[test]
+    fn test_strip_keeps_json_string_instructions() {
+        let full = convert(&schema(), &ConvertOptions::default()).unwrap();
+        let options = ConvertOptions {
+            strip_descriptions: true,
+            ..ConvertOptions::default()
+        };
+        let stripped = convert(&schema(), &options).unwrap();
+
+        let props = &stripped.schema["properties"];
+        assert!(stripped.schema.get("description").is_none());
+        assert!(stripped.schema.get("title").is_none());
+        assert!(props["id"].get("description").is_none());
+        // A property named `title` is not a keyword.
+        assert_eq!(props["title"]["anyOf"][0]["type"], "string");
+        assert!(props["title"]["anyOf"][0].get("title").is_none());
+        // JSON-string fields keep their instructions, nullable or not.
+        assert_eq!(
+            props["meta"]["description"],
+            full.schema["properties"]["meta"]["description"]
+        );
+        assert!(props["extra"]["anyOf"][0]["description"]
+            .as_str()
+            .unwrap()
+            .contains("JSON"));
+
+        assert_eq!(
+            serde_json::to_value(&stripped.codec).unwrap(),
+            serde_json::to_value(&full.codec).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_keep_descriptions_globs() {
+        let options = ConvertOptions {
+            strip_descriptions: true,
+            keep_descriptions: vec!["#/properties/id".to_string()],
+            ..ConvertOptions::default()
+        };
+        let stripped = convert(&schema(), &options).unwrap();
+        assert!(stripped.schema["properties"]["id"]["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("Order id."));
+    }
+
+    #[test]
+    fn test_within_union() {
+        assert!(within_union("#/properties/u", "#/properties/u"));
+        assert!(within_union(
+            "#/properties/u",
+            "#/properties/u/anyOf/0/oneOf/1"
+        ));
+        assert!(!within_union("#/properties/u", "#/properties/u/items"));
+        assert!(!within_union("#/properties/u", "#/properties/user"));
+    }
+}
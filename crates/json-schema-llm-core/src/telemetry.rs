@@ -0,0 +1,201 @@
+//! Operation spans for distributed tracing (`otel` feature).
+//!
+//! With `otel`, [`convert`](crate::convert), the rehydrate functions and
+//! [`extract_component`](crate::extract_component) each run inside an
+//! `info`-level `tracing` span (`jsl.convert`, `jsl.rehydrate`,
+//! `jsl.extract`) whose fields follow OpenTelemetry naming, including
+//! `otel.status_code` and `error.type` on failure. Install a
+//! `tracing-opentelemetry` layer to export them to a collector.
+//!
+//! Without the feature, [`OpSpan`] is an empty struct and nothing is hashed
+//! or counted.
+
+use serde_json::Value;
+
+use crate::codec::Codec;
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+use crate::extract::ExtractResult;
+use crate::rehydrator::RehydrateResult;
+use crate::ConvertResult;
+
+/// Span around one public operation.
+pub(crate) struct OpSpan {
+    #[cfg(feature = "otel")]
+    span: tracing::Span,
+}
+
+#[cfg(feature = "otel")]
+impl OpSpan {
+    pub(crate) fn convert(schema: &Value, options: &ConvertOptions) -> Self {
+        let target = serde_json::to_value(options.target).unwrap_or_default();
+        let span = tracing::info_span!(
+            "jsl.convert",
+            jsl.target = target.as_str().unwrap_or_default(),
+            jsl.schema.hash = %schema_hash(schema),
+            jsl.schema.nodes = count_nodes(schema),
+            jsl.result.schema_bytes = tracing::field::Empty,
+            jsl.result.nodes = tracing::field::Empty,
+            jsl.result.transforms = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+        );
+        Self { span }
+    }
+
+    pub(crate) fn rehydrate(data: &Value, codec: &Codec) -> Self {
+        let span = tracing::info_span!(
+            "jsl.rehydrate",
+            jsl.data.bytes = crate::limits::serialized_len(data),
+            jsl.codec.transforms = codec.transforms.len(),
+            jsl.result.warnings = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+        );
+        Self { span }
+    }
+
+    pub(crate) fn extract(schema: &Value, pointer: &str) -> Self {
+        let span = tracing::info_span!(
+            "jsl.extract",
+            jsl.schema.hash = %schema_hash(schema),
+            jsl.pointer = pointer,
+            jsl.result.schema_bytes = tracing::field::Empty,
+            jsl.result.dependencies = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+        );
+        Self { span }
+    }
+
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.span.in_scope(f)
+    }
+
+    pub(crate) fn record_convert(&self, result: &Result<ConvertResult, ConvertError>) {
+        if let Some(result) = self.record_status(result) {
+            self.span.record(
+                "jsl.result.schema_bytes",
+                crate::limits::serialized_len(&result.schema),
+            );
+            self.span
+                .record("jsl.result.nodes", count_nodes(&result.schema));
+            self.span
+                .record("jsl.result.transforms", result.codec.transforms.len());
+        }
+    }
+
+    pub(crate) fn record_rehydrate(&self, result: &Result<RehydrateResult, ConvertError>) {
+        if let Some(result) = self.record_status(result) {
+            self.span
+                .record("jsl.result.warnings", result.warnings.len());
+        }
+    }
+
+    pub(crate) fn record_extract(&self, result: &Result<ExtractResult, ConvertError>) {
+        if let Some(result) = self.record_status(result) {
+            self.span.record(
+                "jsl.result.schema_bytes",
+                crate::limits::serialized_len(&result.schema),
+            );
+            self.span
+                .record("jsl.result.dependencies", result.dependency_count);
+        }
+    }
+
+    fn record_status<'r, T>(&self, result: &'r Result<T, ConvertError>) -> Option<&'r T> {
+        match result {
+            Ok(value) => {
+                self.span.record("otel.status_code", "OK");
+                Some(value)
+            }
+            Err(err) => {
+                let code = serde_json::to_value(err.error_code()).unwrap_or_default();
+                self.span.record("otel.status_code", "ERROR");
+                self.span
+                    .record("error.type", code.as_str().unwrap_or_default());
+                None
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+impl OpSpan {
+    pub(crate) fn convert(_schema: &Value, _options: &ConvertOptions) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn rehydrate(_data: &Value, _codec: &Codec) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn extract(_schema: &Value, _pointer: &str) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        f()
+    }
+
+    pub(crate) fn record_convert(&self, _result: &Result<ConvertResult, ConvertError>) {}
+
+    pub(crate) fn record_rehydrate(&self, _result: &Result<RehydrateResult, ConvertError>) {}
+
+    pub(crate) fn record_extract(&self, _result: &Result<ExtractResult, ConvertError>) {}
+}
+
+/// Stable 64-bit FNV-1a hash of `schema`'s compact serialization, as hex.
+/// Identifies the same schema across processes and releases.
+#[cfg(feature = "otel")]
+pub(crate) fn schema_hash(schema: &Value) -> String {
+    struct Fnv(u64);
+    impl std::io::Write for Fnv {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            for byte in buf {
+                self.0 ^= u64::from(*byte);
+                self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+            }
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+    // Writing a `Value` into an infallible sink cannot fail.
+    let _ = serde_json::to_writer(&mut hasher, schema);
+    format!("{:016x}", hasher.0)
+}
+
+/// Number of JSON objects in `schema`, counted without recursion.
+#[cfg(feature = "otel")]
+fn count_nodes(schema: &Value) -> usize {
+    let mut count = 0;
+    let mut stack = vec![schema];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Object(obj) => {
+                count += 1;
+                stack.extend(obj.values());
+            }
+            Value::Array(items) => stack.extend(items),
+            _ => {}
+        }
+    }
+    count
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_hash_is_stable() {
+        let a = json!({"type": "object", "properties": {"a": {"type": "string"}}});
+        assert_eq!(schema_hash(&a), schema_hash(&a.clone()));
+        assert_ne!(schema_hash(&a), schema_hash(&json!({"type": "string"})));
+        assert_eq!(schema_hash(&json!(null)), "5b9bc4ba528108e4");
+    }
+}
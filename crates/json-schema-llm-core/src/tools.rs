@@ -0,0 +1,338 @@
+//! Several schemas offered to a model as tools.
+//!
+//! Agent frameworks hand the model a list of tools and get back calls naming
+//! one of them. [`ToolRegistry`] converts each named schema once, emits the
+//! provider's `tools` array for all of them, keeps each tool's codec, and
+//! rehydrates a call's arguments with the codec of the tool it names.
+//!
+//! ```rust
+//! use json_schema_llm_core::{ConvertOptions, ToolRegistry};
+//! use serde_json::json;
+//!
+//! let mut registry = ToolRegistry::new(ConvertOptions::default());
+//! registry
+//!     .register("get_weather", &json!({
+//!         "type": "object",
+//!         "properties": { "city": { "type": "string" } },
+//!         "required": ["city"]
+//!     }))
+//!     .unwrap();
+//! registry
+//!     .register("set_alarm", &json!({
+//!         "type": "object",
+//!         "properties": { "at": { "type": "string" }, "label": { "type": "string" } },
+//!         "required": ["at"]
+//!     }))
+//!     .unwrap();
+//!
+//! let tools = registry.tools();
+//! assert_eq!(tools[1]["function"]["name"], "set_alarm");
+//!
+//! // OpenAI sends the arguments as a JSON string.
+//! let call = registry
+//!     .rehydrate_tool_call("set_alarm", &json!("{\"at\": \"07:00\", \"label\": null}"))
+//!     .unwrap();
+//! assert_eq!(call.data, json!({"at": "07:00"}));
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::codec::Codec;
+use crate::config::{ConvertOptions, Envelope, Mode, Target};
+use crate::error::ConvertError;
+use crate::extract::{extract_component, ExtractOptions};
+use crate::providers::{parse_text, sanitize_name, wrap_schema};
+use crate::rehydrator::RehydrateResult;
+use crate::ConvertResult;
+
+/// One schema registered as a tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredTool {
+    /// Tool name as sent to the provider.
+    pub name: String,
+    /// Tool description, from the schema's `description`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The original schema, used to rehydrate the tool's calls.
+    pub schema: Value,
+    /// The schema's conversion.
+    pub result: ConvertResult,
+}
+
+/// Named schemas converted for one target and offered together as tools.
+///
+/// Tools keep the order they were registered in.
+#[derive(Debug, Clone, Default)]
+pub struct ToolRegistry {
+    options: ConvertOptions,
+    tools: Vec<RegisteredTool>,
+}
+
+impl ToolRegistry {
+    /// An empty registry converting with `options`.
+    pub fn new(options: ConvertOptions) -> Self {
+        Self {
+            options,
+            tools: Vec::new(),
+        }
+    }
+
+    /// A registry of `schemas`, given as `(name, schema)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// The first error [`register`](Self::register) returns.
+    pub fn from_schemas<'a, N: AsRef<str>>(
+        schemas: impl IntoIterator<Item = (N, &'a Value)>,
+        options: ConvertOptions,
+    ) -> Result<Self, ConvertError> {
+        let mut registry = Self::new(options);
+        for (name, schema) in schemas {
+            registry.register(name.as_ref(), schema)?;
+        }
+        Ok(registry)
+    }
+
+    /// Convert `schema` and add it as the tool `name`.
+    ///
+    /// The name is reduced to the characters providers accept.
+    ///
+    /// # Errors
+    ///
+    /// A [`ConvertError::SchemaError`] if the name is empty or already
+    /// taken, or whatever [`convert`](crate::convert) returns for `schema`.
+    pub fn register(
+        &mut self,
+        name: &str,
+        schema: &Value,
+    ) -> Result<&RegisteredTool, ConvertError> {
+        let name = sanitize_name(name);
+        if name.is_empty() {
+            return Err(name_error("#", "tool name is empty"));
+        }
+        if self.get(&name).is_some() {
+            return Err(name_error(
+                "#",
+                &format!("a tool named `{}` is already registered", name),
+            ));
+        }
+        let result = crate::convert(schema, &self.options)?;
+        self.tools.push(RegisteredTool {
+            description: schema
+                .get("description")
+                .and_then(Value::as_str)
+                .map(String::from),
+            name,
+            schema: schema.clone(),
+            result,
+        });
+        Ok(self.tools.last().expect("tool just pushed"))
+    }
+
+    /// Extract the component at `pointer` (e.g. `#/$defs/CreateUser`) from
+    /// `document` and register it under its last pointer segment.
+    ///
+    /// # Errors
+    ///
+    /// Whatever [`extract_component`] or [`register`](Self::register)
+    /// returns.
+    pub fn register_component(
+        &mut self,
+        document: &Value,
+        pointer: &str,
+    ) -> Result<&RegisteredTool, ConvertError> {
+        let extracted = extract_component(document, pointer, &ExtractOptions::default())?;
+        let name = pointer.rsplit('/').next().unwrap_or_default().to_string();
+        if name.is_empty() || name == "#" {
+            return Err(name_error(pointer, "pointer does not name a component"));
+        }
+        self.register(&name, &extracted.schema)
+    }
+
+    /// The registered tools, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredTool> {
+        self.tools.iter()
+    }
+
+    /// The tool named `name`.
+    pub fn get(&self, name: &str) -> Option<&RegisteredTool> {
+        self.tools.iter().find(|t| t.name == name)
+    }
+
+    /// Number of registered tools.
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether no tool is registered.
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// The request body's `tools` array for the registry's target: OpenAI
+    /// `function` entries, one Gemini `functionDeclarations` group, or
+    /// Claude tool entries.
+    pub fn tools(&self) -> Value {
+        let strict = self.options.mode == Mode::Strict;
+        let entries: Vec<Value> = self
+            .tools
+            .iter()
+            .map(|tool| {
+                wrap_schema(
+                    self.options.target,
+                    Envelope::ToolFunction,
+                    tool.result.schema.clone(),
+                    &tool.name,
+                    tool.description.as_deref(),
+                    strict,
+                )
+            })
+            .collect();
+        match self.options.target {
+            Target::Gemini => json!([{ "functionDeclarations": entries }]),
+            Target::OpenaiStrict | Target::Claude => Value::Array(entries),
+        }
+    }
+
+    /// Each tool's codec, keyed by tool name.
+    pub fn codec_index(&self) -> BTreeMap<String, Codec> {
+        self.tools
+            .iter()
+            .map(|tool| (tool.name.clone(), tool.result.codec.clone()))
+            .collect()
+    }
+
+    /// Rehydrate the arguments of a call to the tool `name`.
+    ///
+    /// `args` may be the arguments object or, as OpenAI sends it, the same
+    /// object serialized to a string.
+    ///
+    /// # Errors
+    ///
+    /// A [`ConvertError::RehydrationError`] naming an unknown tool, a
+    /// [`ConvertError::JsonError`] for unparseable string arguments, or
+    /// whatever [`rehydrate`](crate::rehydrate) returns.
+    pub fn rehydrate_tool_call(
+        &self,
+        name: &str,
+        args: &Value,
+    ) -> Result<RehydrateResult, ConvertError> {
+        let tool = self
+            .get(name)
+            .ok_or_else(|| ConvertError::RehydrationError(format!("Unknown tool: {}", name)))?;
+        match args {
+            Value::String(text) => {
+                let parsed = parse_text(text)?;
+                crate::rehydrate(&parsed, &tool.result.codec, &tool.schema)
+            }
+            _ => crate::rehydrate(args, &tool.result.codec, &tool.schema),
+        }
+    }
+}
+
+fn name_error(path: &str, message: &str) -> ConvertError {
+    ConvertError::SchemaError {
+        path: path.to_string(),
+        message: message.to_string(),
+        location: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weather() -> Value {
+        json!({
+            "type": "object",
+            "description": "Look up the weather.",
+            "properties": {
+                "city": { "type": "string" },
+                "units": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "required": ["city"]
+        })
+    }
+
+    #[test]
+    fn test_tools_per_target() {
+        let schema = weather();
+        for target in [Target::OpenaiStrict, Target::Gemini, Target::Claude] {
+            let options = ConvertOptions {
+                target,
+                ..ConvertOptions::default()
+            };
+            let registry = ToolRegistry::from_schemas([("get weather", &schema)], options).unwrap();
+            let tools = registry.tools();
+            let converted = &registry.get("get_weather").unwrap().result.schema;
+            match target {
+                Target::OpenaiStrict => {
+                    assert_eq!(tools[0]["function"]["name"], "get_weather");
+                    assert_eq!(tools[0]["function"]["description"], "Look up the weather.");
+                    assert_eq!(&tools[0]["function"]["parameters"], converted);
+                }
+                Target::Gemini => {
+                    let declaration = &tools[0]["functionDeclarations"][0];
+                    assert_eq!(declaration["name"], "get_weather");
+                    assert_eq!(&declaration["parameters"], converted);
+                }
+                Target::Claude => {
+                    assert_eq!(tools[0]["name"], "get_weather");
+                    assert_eq!(&tools[0]["input_schema"], converted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rehydrate_tool_call_uses_named_codec() {
+        let schema = weather();
+        let mut registry = ToolRegistry::new(ConvertOptions::default());
+        registry.register("get_weather", &schema).unwrap();
+        registry
+            .register("noop", &json!({ "type": "object", "properties": {} }))
+            .unwrap();
+        assert_eq!(
+            registry.codec_index().keys().collect::<Vec<_>>(),
+            ["get_weather", "noop"]
+        );
+
+        let args = json!({
+            "city": "Oslo",
+            "units": [{ "key": "temp", "value": "C" }]
+        });
+        let call = registry.rehydrate_tool_call("get_weather", &args).unwrap();
+        assert_eq!(
+            call.data,
+            json!({ "city": "Oslo", "units": { "temp": "C" } })
+        );
+
+        let err = registry.rehydrate_tool_call("missing", &args).unwrap_err();
+        assert!(matches!(err, ConvertError::RehydrationError(_)));
+    }
+
+    #[test]
+    fn test_register_rejects_duplicates_and_names_components() {
+        let document = json!({
+            "$defs": {
+                "CreateUser": {
+                    "type": "object",
+                    "properties": { "address": { "$ref": "#/$defs/Address" } }
+                },
+                "Address": { "type": "object", "properties": { "city": { "type": "string" } } }
+            }
+        });
+        let mut registry = ToolRegistry::new(ConvertOptions::default());
+        registry
+            .register_component(&document, "#/$defs/CreateUser")
+            .unwrap();
+        assert_eq!(registry.iter().next().unwrap().name, "CreateUser");
+
+        let err = registry.register("CreateUser", &json!({})).unwrap_err();
+        assert!(err.to_string().contains("already registered"));
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,549 @@
+//! Type declaration output for converted schemas.
+//!
+//! [`emit_types`] renders two sets of declarations side by side: the shape
+//! the model returns (the converted schema) and the shape rehydration hands
+//! back (the original schema). Application code can then type both ends of
+//! [`crate::rehydrate`] without hand-maintaining either.
+//!
+//! Each `$defs` (or `definitions`) entry becomes a named declaration and
+//! `$ref`s to it become references by name; anything else is inlined.
+//! Constructs with no static equivalent (external refs, nested `$defs`,
+//! `patternProperties` alongside named properties) widen to `unknown` rather
+//! than fail.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use json_schema_llm_core::typegen::{emit_types, TypegenOptions};
+//! use json_schema_llm_core::{convert, ConvertOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "title": "Pet",
+//!     "type": "object",
+//!     "properties": { "name": { "type": "string" }, "tag": { "type": "string" } },
+//!     "required": ["name"]
+//! });
+//! let result = convert(&schema, &ConvertOptions::default()).unwrap();
+//!
+//! let dts = emit_types(&schema, &result.schema, &TypegenOptions::default());
+//! assert!(dts.contains("export namespace Converted"));
+//! assert!(dts.contains("tag: string | null;"));
+//! assert!(dts.contains("tag?: string;"));
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::schema_utils::escape_pointer_segment;
+
+// ---------------------------------------------------------------------------
+// Public types
+// ---------------------------------------------------------------------------
+
+/// Language to emit declarations in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeLanguage {
+    /// TypeScript declaration file (`.d.ts`).
+    #[default]
+    Typescript,
+}
+
+/// Options for [`emit_types`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(rename_all = "kebab-case", default)]
+pub struct TypegenOptions {
+    pub language: TypeLanguage,
+    /// Name of the root type. Defaults to the original schema's `title`,
+    /// else `Root`.
+    pub root_name: Option<String>,
+}
+
+/// Namespace holding the converted (model output) declarations.
+pub const CONVERTED_NAMESPACE: &str = "Converted";
+/// Namespace holding the original (rehydrated) declarations.
+pub const ORIGINAL_NAMESPACE: &str = "Original";
+
+/// Render declarations for both the converted and the original shape.
+///
+/// The root type carries the same name in both namespaces, so callers write
+/// `Converted.Pet` for what the model returns and `Original.Pet` for what
+/// rehydration produces.
+pub fn emit_types(original: &Value, converted: &Value, options: &TypegenOptions) -> String {
+    let root_name = options
+        .root_name
+        .as_deref()
+        .or_else(|| original.get("title").and_then(Value::as_str))
+        .map(pascal_case)
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "Root".to_string());
+
+    match options.language {
+        TypeLanguage::Typescript => {
+            let mut out = String::from("// Generated by json-schema-llm. Do not edit.\n\n");
+            out.push_str("/** Shape the model returns: the converted schema. */\n");
+            Emitter::new(converted, &root_name).namespace(CONVERTED_NAMESPACE, &mut out);
+            out.push_str("\n/** Shape after rehydration: the original schema. */\n");
+            Emitter::new(original, &root_name).namespace(ORIGINAL_NAMESPACE, &mut out);
+            out
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TypeScript emitter
+// ---------------------------------------------------------------------------
+
+const INDENT: &str = "  ";
+
+struct Emitter<'a> {
+    root: &'a Value,
+    root_name: &'a str,
+    /// `$ref` pointer → declared name.
+    names: HashMap<String, String>,
+    /// Declarations in `$defs` order, root first.
+    decls: Vec<(String, &'a Value)>,
+}
+
+/// A rendered type expression. `compound` marks a top-level `|` or `&`,
+/// which needs parentheses when nested under another operator or `[]`.
+struct Ty {
+    text: String,
+    compound: bool,
+}
+
+impl Ty {
+    fn simple(text: impl Into<String>) -> Self {
+        Ty {
+            text: text.into(),
+            compound: false,
+        }
+    }
+
+    fn grouped(self) -> String {
+        if self.compound {
+            format!("({})", self.text)
+        } else {
+            self.text
+        }
+    }
+}
+
+impl<'a> Emitter<'a> {
+    fn new(root: &'a Value, root_name: &'a str) -> Self {
+        let mut taken: HashSet<String> = HashSet::from([root_name.to_string()]);
+        let mut names = HashMap::from([("#".to_string(), root_name.to_string())]);
+        let mut decls = vec![(root_name.to_string(), root)];
+
+        for keyword in ["$defs", "definitions"] {
+            let Some(defs) = root.get(keyword).and_then(Value::as_object) else {
+                continue;
+            };
+            for (key, schema) in defs {
+                let base = match pascal_case(key) {
+                    n if n.is_empty() => "Def".to_string(),
+                    n if n.starts_with(|c: char| c.is_ascii_digit()) => format!("_{n}"),
+                    n => n,
+                };
+                let mut name = base.clone();
+                let mut suffix = 2;
+                while !taken.insert(name.clone()) {
+                    name = format!("{base}{suffix}");
+                    suffix += 1;
+                }
+                names.insert(
+                    format!("#/{keyword}/{}", escape_pointer_segment(key)),
+                    name.clone(),
+                );
+                decls.push((name, schema));
+            }
+        }
+
+        Emitter {
+            root,
+            root_name,
+            names,
+            decls,
+        }
+    }
+
+    fn namespace(&self, namespace: &str, out: &mut String) {
+        let _ = writeln!(out, "export namespace {namespace} {{");
+        for (i, (name, schema)) in self.decls.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            self.declaration(name, schema, out);
+        }
+        out.push_str("}\n");
+    }
+
+    fn declaration(&self, name: &str, schema: &Value, out: &mut String) {
+        // The root's title already names the type; only its description
+        // is worth repeating.
+        let title = if name == self.root_name && std::ptr::eq(schema, self.root) {
+            None
+        } else {
+            schema.get("title").and_then(Value::as_str)
+        };
+        doc_comment(schema, title, 1, out);
+
+        match schema.as_object().filter(|o| is_plain_object(o)) {
+            Some(obj) => {
+                let _ = write!(out, "{INDENT}export interface {name} ");
+                out.push_str(&self.object_body(obj, 1));
+                out.push('\n');
+            }
+            None => {
+                let ty = self.render(schema, 1);
+                let _ = writeln!(out, "{INDENT}export type {name} = {};", ty.text);
+            }
+        }
+    }
+
+    fn render(&self, schema: &Value, depth: usize) -> Ty {
+        let obj = match schema {
+            Value::Bool(true) => return Ty::simple("unknown"),
+            Value::Bool(false) => return Ty::simple("never"),
+            Value::Object(obj) => obj,
+            _ => return Ty::simple("unknown"),
+        };
+
+        if let Some(pointer) = obj.get("$ref").and_then(Value::as_str) {
+            return Ty::simple(self.names.get(pointer).map_or("unknown", String::as_str));
+        }
+        if let Some(value) = obj.get("const") {
+            return Ty::simple(literal(value));
+        }
+        if let Some(values) = obj.get("enum").and_then(Value::as_array) {
+            return union(values.iter().map(|v| Ty::simple(literal(v))).collect());
+        }
+
+        let mut parts = Vec::new();
+        if let Some(base) = self.render_typed(obj, depth) {
+            parts.push(base);
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(branches) = obj.get(keyword).and_then(Value::as_array) {
+                parts.push(union(
+                    branches.iter().map(|b| self.render(b, depth)).collect(),
+                ));
+            }
+        }
+        if let Some(branches) = obj.get("allOf").and_then(Value::as_array) {
+            parts.extend(branches.iter().map(|b| self.render(b, depth)));
+        }
+        intersection(parts)
+    }
+
+    /// Render the `type`-driven part of a schema, if it has one.
+    fn render_typed(&self, obj: &Map<String, Value>, depth: usize) -> Option<Ty> {
+        let types: Vec<&str> = match obj.get("type") {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+            _ if obj.contains_key("properties") || obj.contains_key("additionalProperties") => {
+                vec!["object"]
+            }
+            _ if obj.contains_key("items") || obj.contains_key("prefixItems") => vec!["array"],
+            _ => return None,
+        };
+
+        Some(union(
+            types
+                .into_iter()
+                .map(|t| match t {
+                    "string" => Ty::simple("string"),
+                    "integer" | "number" => Ty::simple("number"),
+                    "boolean" => Ty::simple("boolean"),
+                    "null" => Ty::simple("null"),
+                    "array" => self.render_array(obj, depth),
+                    "object" => self.render_object(obj, depth),
+                    _ => Ty::simple("unknown"),
+                })
+                .collect(),
+        ))
+    }
+
+    fn render_array(&self, obj: &Map<String, Value>, depth: usize) -> Ty {
+        let rest = obj.get("items").filter(|items| items.is_object());
+        let Some(prefix) = obj.get("prefixItems").and_then(Value::as_array) else {
+            let item = rest.map_or(Ty::simple("unknown"), |s| self.render(s, depth));
+            return Ty::simple(format!("{}[]", item.grouped()));
+        };
+
+        let mut elements: Vec<String> = prefix
+            .iter()
+            .map(|item| self.render(item, depth).text)
+            .collect();
+        if let Some(rest) = rest {
+            elements.push(format!("...{}[]", self.render(rest, depth).grouped()));
+        }
+        Ty::simple(format!("[{}]", elements.join(", ")))
+    }
+
+    fn render_object(&self, obj: &Map<String, Value>, depth: usize) -> Ty {
+        let has_properties = obj
+            .get("properties")
+            .and_then(Value::as_object)
+            .is_some_and(|p| !p.is_empty());
+        if has_properties {
+            return Ty::simple(self.object_body(obj, depth));
+        }
+        Ty::simple(match obj.get("additionalProperties") {
+            Some(Value::Bool(false)) => "Record<string, never>".to_string(),
+            Some(value @ Value::Object(_)) => {
+                format!("Record<string, {}>", self.render(value, depth).text)
+            }
+            _ => "Record<string, unknown>".to_string(),
+        })
+    }
+
+    /// `{ ... }` with one member per property, closed by an index signature
+    /// when the schema admits extra keys.
+    fn object_body(&self, obj: &Map<String, Value>, depth: usize) -> String {
+        let required: BTreeSet<&str> = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let pad = INDENT.repeat(depth + 1);
+
+        let mut out = String::from("{\n");
+        if let Some(props) = obj.get("properties").and_then(Value::as_object) {
+            for (key, schema) in props {
+                doc_comment(schema, None, depth + 1, &mut out);
+                let optional = if required.contains(key.as_str()) {
+                    ""
+                } else {
+                    "?"
+                };
+                let ty = self.render(schema, depth + 1);
+                let _ = writeln!(out, "{pad}{}{optional}: {};", property_key(key), ty.text);
+            }
+        }
+        // Named members must be assignable to the index signature, so once
+        // both are present the signature can only say `unknown`.
+        let extra = match obj.get("additionalProperties") {
+            Some(Value::Bool(false)) => None,
+            Some(value @ Value::Object(_)) if !obj.contains_key("properties") => {
+                Some(self.render(value, depth + 1).text)
+            }
+            Some(_) => Some("unknown".to_string()),
+            None if obj.contains_key("patternProperties") => Some("unknown".to_string()),
+            None => None,
+        };
+        if let Some(extra) = extra {
+            let _ = writeln!(out, "{pad}[key: string]: {extra};");
+        }
+        out.push_str(&INDENT.repeat(depth));
+        out.push('}');
+        out
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Whether a schema is exactly an object type, so it can be declared as an
+/// `interface` rather than a type alias.
+fn is_plain_object(obj: &Map<String, Value>) -> bool {
+    let is_object = match obj.get("type") {
+        Some(Value::String(t)) => t == "object",
+        None => obj.contains_key("properties"),
+        _ => false,
+    };
+    is_object
+        && obj.get("properties").is_some_and(Value::is_object)
+        && !["$ref", "const", "enum", "anyOf", "oneOf", "allOf"]
+            .iter()
+            .any(|k| obj.contains_key(*k))
+}
+
+fn union(mut members: Vec<Ty>) -> Ty {
+    let mut seen = HashSet::new();
+    members.retain(|m| seen.insert(m.text.clone()));
+    match members.len() {
+        0 => Ty::simple("never"),
+        1 => members.pop().unwrap(),
+        _ => Ty {
+            text: members
+                .into_iter()
+                .map(|m| m.text)
+                .collect::<Vec<_>>()
+                .join(" | "),
+            compound: true,
+        },
+    }
+}
+
+fn intersection(mut members: Vec<Ty>) -> Ty {
+    match members.len() {
+        0 => Ty::simple("unknown"),
+        1 => members.pop().unwrap(),
+        _ => Ty {
+            text: members
+                .into_iter()
+                .map(Ty::grouped)
+                .collect::<Vec<_>>()
+                .join(" & "),
+            compound: true,
+        },
+    }
+}
+
+/// A JSON value as a TypeScript literal type. Objects and arrays come out as
+/// object and tuple types, which JSON syntax already spells correctly.
+fn literal(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn property_key(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        key.to_string()
+    } else {
+        literal(&Value::String(key.to_string()))
+    }
+}
+
+/// `pet_store-v2` → `PetStoreV2`.
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Write a JSDoc block from `title`, `description` and `deprecated`.
+fn doc_comment(schema: &Value, title: Option<&str>, depth: usize, out: &mut String) {
+    let description = schema.get("description").and_then(Value::as_str);
+    let deprecated = schema.get("deprecated").and_then(Value::as_bool) == Some(true);
+
+    let mut lines: Vec<&str> = Vec::new();
+    for text in [title, description].into_iter().flatten() {
+        if !lines.is_empty() {
+            lines.push("");
+        }
+        lines.extend(text.lines());
+    }
+    if deprecated {
+        lines.push("@deprecated");
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    let pad = INDENT.repeat(depth);
+    if let [line] = lines.as_slice() {
+        let _ = writeln!(out, "{pad}/** {} */", line.replace("*/", "*\\/"));
+        return;
+    }
+    let _ = writeln!(out, "{pad}/**");
+    for line in lines {
+        let line = line.replace("*/", "*\\/");
+        let _ = writeln!(
+            out,
+            "{pad} *{}{line}",
+            if line.is_empty() { "" } else { " " }
+        );
+    }
+    let _ = writeln!(out, "{pad} */");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn emit(schema: &Value) -> String {
+        let mut out = String::new();
+        Emitter::new(schema, "Root").namespace("T", &mut out);
+        out
+    }
+
+    #[test]
+    fn test_object_interface_with_optional_and_docs() {
+        let out = emit(&json!({
+            "type": "object",
+            "description": "A pet.",
+            "properties": {
+                "name": { "type": "string", "description": "Display name." },
+                "legacy-id": { "type": "integer", "deprecated": true }
+            },
+            "required": ["name"],
+            "additionalProperties": false
+        }));
+        assert_eq!(
+            out,
+            "export namespace T {\n  /** A pet. */\n  export interface Root {\n    /** @deprecated */\n    \"legacy-id\"?: number;\n    /** Display name. */\n    name: string;\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_defs_become_named_declarations() {
+        let out = emit(&json!({
+            "type": "object",
+            "properties": {
+                "owner": { "$ref": "#/$defs/person" },
+                "kind": { "$ref": "#/$defs/pet-kind" },
+                "elsewhere": { "$ref": "other.json#/Thing" }
+            },
+            "required": ["owner", "kind", "elsewhere"],
+            "$defs": {
+                "person": { "type": "object", "properties": { "name": { "type": "string" } } },
+                "pet-kind": { "enum": ["cat", "dog"] }
+            }
+        }));
+        assert!(out.contains("    owner: Person;\n"));
+        assert!(out.contains("    kind: PetKind;\n"));
+        assert!(out.contains("    elsewhere: unknown;\n"));
+        assert!(out.contains("  export interface Person {\n    name?: string;\n  }\n"));
+        assert!(out.contains("  export type PetKind = \"cat\" | \"dog\";\n"));
+    }
+
+    #[test]
+    fn test_unions_arrays_and_maps() {
+        let out = emit(&json!({
+            "type": "object",
+            "properties": {
+                "tags": { "type": "array", "items": { "type": ["string", "null"] } },
+                "pair": { "prefixItems": [{ "type": "string" }, { "type": "number" }] },
+                "shape": { "anyOf": [{ "const": 1 }, { "type": "boolean" }] },
+                "labels": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "required": ["tags", "pair", "shape", "labels"]
+        }));
+        assert!(out.contains("tags: (string | null)[];"));
+        assert!(out.contains("pair: [string, number];"));
+        assert!(out.contains("shape: 1 | boolean;"));
+        assert!(out.contains("labels: Record<string, string>;"));
+    }
+
+    #[test]
+    fn test_def_name_collision_with_root() {
+        let schema = json!({
+            "title": "Pet",
+            "$ref": "#/$defs/Pet",
+            "$defs": { "Pet": { "type": "string" } }
+        });
+        let out = emit_types(&schema, &schema, &TypegenOptions::default());
+        assert!(out.contains("export type Pet = Pet2;"));
+        assert!(out.contains("export type Pet2 = string;"));
+    }
+}
@@ -0,0 +1,112 @@
+//! Conformance of a converted schema to its target provider.
+//!
+//! Provides [`validate_conformance`] — the check behind
+//! [`ConvertOptions::verify_output`]. It holds the converted schema to the
+//! requirements the target provider publishes, reusing the
+//! [`strict_mode`](super::strict_mode) rules: OpenAI strict mode gets all of
+//! them, Gemini and Claude the subset they document. A violation means the
+//! converter produced something the provider API would reject.
+
+use serde_json::Value;
+
+use super::strict_mode::{validate_strict_mode, StrictModeRule, StrictModeViolation};
+use crate::config::{AdditionalPropertiesPolicy, ConvertOptions, Mode, Target};
+
+/// Validate a converted schema against the requirements of
+/// `options.target`.
+///
+/// Only [`Mode::Strict`] output is checked; permissive output makes no
+/// provider guarantees and always passes. For `openai-strict` every
+/// strict-mode rule applies, except the depth limit when a custom
+/// [`provider_max_depth`](ConvertOptions::provider_max_depth) is set. Gemini
+/// requires `items` on arrays; Claude requires sealed objects unless
+/// [`AdditionalPropertiesPolicy::Preserve`] asked for them to stay open.
+///
+/// Returns an empty list when the schema conforms.
+pub fn validate_conformance(schema: &Value, options: &ConvertOptions) -> Vec<StrictModeViolation> {
+    if options.mode != Mode::Strict {
+        return Vec::new();
+    }
+    let mut violations = validate_strict_mode(schema);
+    violations.retain(|v| applies(&v.rule_id, options));
+    violations
+}
+
+/// Whether `rule` is a published requirement of the options' target.
+fn applies(rule: &StrictModeRule, options: &ConvertOptions) -> bool {
+    match options.target {
+        Target::OpenaiStrict => {
+            *rule != StrictModeRule::DepthExceeded || options.provider_max_depth.is_none()
+        }
+        Target::Gemini => *rule == StrictModeRule::MissingItems,
+        Target::Claude => {
+            *rule == StrictModeRule::MissingAdditionalProperties
+                && options.additional_properties == AdditionalPropertiesPolicy::Seal
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(target: Target) -> ConvertOptions {
+        ConvertOptions {
+            target,
+            ..ConvertOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_rules_follow_target() {
+        // Open object with a partial `required`: fine for Gemini only.
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["name"]
+        });
+        let openai = validate_conformance(&schema, &options(Target::OpenaiStrict));
+        let rules: Vec<_> = openai.iter().map(|v| &v.rule_id).collect();
+        assert_eq!(
+            rules,
+            [
+                &StrictModeRule::MissingAdditionalProperties,
+                &StrictModeRule::RequiredIncomplete
+            ]
+        );
+
+        let claude = validate_conformance(&schema, &options(Target::Claude));
+        assert_eq!(claude.len(), 1);
+        assert_eq!(
+            claude[0].rule_id,
+            StrictModeRule::MissingAdditionalProperties
+        );
+
+        assert!(validate_conformance(&schema, &options(Target::Gemini)).is_empty());
+    }
+
+    #[test]
+    fn test_permissive_and_preserve_are_not_checked() {
+        let schema = json!({ "type": "object", "properties": { "a": { "type": "array" } } });
+        let permissive = ConvertOptions {
+            mode: Mode::Permissive,
+            ..ConvertOptions::default()
+        };
+        assert!(validate_conformance(&schema, &permissive).is_empty());
+
+        let preserve = ConvertOptions {
+            target: Target::Claude,
+            additional_properties: AdditionalPropertiesPolicy::Preserve,
+            ..ConvertOptions::default()
+        };
+        assert!(validate_conformance(&schema, &preserve).is_empty());
+
+        let gemini = validate_conformance(&schema, &options(Target::Gemini));
+        assert_eq!(gemini.len(), 1);
+        assert_eq!(gemini[0].path, "#/properties/a");
+    }
+}
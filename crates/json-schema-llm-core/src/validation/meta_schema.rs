@@ -0,0 +1,315 @@
+//! Structural validation of an input schema against the 2020-12 meta-schema.
+//!
+//! Provides [`validate_input`] — the check behind
+//! [`ConvertOptions::validate_input`](crate::ConvertOptions::validate_input).
+//! The passes assume well-formed keywords and fail on the first malformed
+//! one they happen to reach, often deep inside a `$ref` expansion. This
+//! pre-pass reports every malformed keyword at once, at its path in the
+//! input.
+//!
+//! Only the shape of known keywords is checked: a `required` that is not an
+//! array of unique strings, `properties` that is not an object of schemas, a
+//! negative `minLength`, and so on. Unknown keywords are allowed, as the
+//! meta-schema allows them. Legacy forms that Pass 0 upgrades (boolean
+//! `required` and `exclusiveMinimum`, array `items`, `type: "any"`) are
+//! accepted whatever the declared draft.
+
+use serde_json::{Map, Value};
+
+use crate::schema_utils::build_path;
+
+/// A malformed keyword found by [`validate_input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaIssue {
+    /// JSON Pointer to the schema holding the keyword.
+    pub path: String,
+    /// The malformed keyword.
+    pub keyword: String,
+    /// Human-readable explanation of the issue.
+    pub message: String,
+}
+
+/// Keywords whose value is a single schema.
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "contentSchema",
+    "else",
+    "if",
+    "not",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Keywords whose value is a non-empty array of schemas.
+const SCHEMA_ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "oneOf", "prefixItems"];
+
+/// Keywords whose value is an object of schemas.
+const SCHEMA_MAP_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+    "patternProperties",
+    "properties",
+];
+
+/// Keywords whose value is a non-negative integer.
+const COUNT_KEYWORDS: &[&str] = &[
+    "maxContains",
+    "maxItems",
+    "maxLength",
+    "maxProperties",
+    "minContains",
+    "minItems",
+    "minLength",
+    "minProperties",
+];
+
+/// Keywords whose value is a string.
+const STRING_KEYWORDS: &[&str] = &[
+    "$anchor",
+    "$comment",
+    "$dynamicAnchor",
+    "$dynamicRef",
+    "$id",
+    "$ref",
+    "$schema",
+    "contentEncoding",
+    "contentMediaType",
+    "description",
+    "format",
+    "pattern",
+    "title",
+];
+
+/// Keywords whose value is a boolean.
+const BOOLEAN_KEYWORDS: &[&str] = &["deprecated", "readOnly", "uniqueItems", "writeOnly"];
+
+const SIMPLE_TYPES: &[&str] = &[
+    "array", "boolean", "integer", "null", "number", "object", "string",
+];
+
+/// Validate the structure of `schema` against the 2020-12 meta-schema.
+///
+/// Returns every issue found. An empty list means the schema is
+/// well-formed.
+pub fn validate_input(schema: &Value) -> Vec<SchemaIssue> {
+    let mut issues = Vec::new();
+    check_schema(schema, "#", &mut issues);
+    issues
+}
+
+/// Check one schema position: a boolean or an object of keywords.
+fn check_schema(value: &Value, path: &str, issues: &mut Vec<SchemaIssue>) {
+    match value {
+        Value::Bool(_) => {}
+        Value::Object(obj) => check_keywords(obj, path, issues),
+        other => issues.push(SchemaIssue {
+            path: path.to_string(),
+            keyword: String::new(),
+            message: format!(
+                "expected a schema (object or boolean), found {}",
+                kind(other)
+            ),
+        }),
+    }
+}
+
+fn check_keywords(obj: &Map<String, Value>, path: &str, issues: &mut Vec<SchemaIssue>) {
+    for (keyword, value) in obj {
+        let mut issue = |expected: &str| {
+            issues.push(SchemaIssue {
+                path: path.to_string(),
+                keyword: keyword.clone(),
+                message: format!("`{}` must be {}, found {}", keyword, expected, kind(value)),
+            })
+        };
+        let key = keyword.as_str();
+        let child = |segments: &[&str]| {
+            let mut all = vec![key];
+            all.extend_from_slice(segments);
+            build_path(path, &all)
+        };
+
+        if SCHEMA_KEYWORDS.contains(&key) {
+            check_schema(value, &child(&[]), issues);
+        } else if SCHEMA_ARRAY_KEYWORDS.contains(&key) {
+            match value.as_array() {
+                Some(items) if !items.is_empty() => {
+                    for (i, item) in items.iter().enumerate() {
+                        check_schema(item, &child(&[&i.to_string()]), issues);
+                    }
+                }
+                _ => issue("a non-empty array of schemas"),
+            }
+        } else if SCHEMA_MAP_KEYWORDS.contains(&key) {
+            match value.as_object() {
+                Some(entries) => {
+                    for (name, entry) in entries {
+                        check_schema(entry, &child(&[name]), issues);
+                    }
+                }
+                None => issue("an object of schemas"),
+            }
+        } else if COUNT_KEYWORDS.contains(&key) {
+            if value.as_u64().is_none() {
+                issue("a non-negative integer");
+            }
+        } else if STRING_KEYWORDS.contains(&key) {
+            if !value.is_string() {
+                issue("a string");
+            }
+        } else if BOOLEAN_KEYWORDS.contains(&key) {
+            if !value.is_boolean() {
+                issue("a boolean");
+            }
+        } else {
+            match key {
+                // An array of schemas is the pre-2020-12 tuple form.
+                "items" => match value.as_array() {
+                    Some(items) => {
+                        for (i, item) in items.iter().enumerate() {
+                            check_schema(item, &child(&[&i.to_string()]), issues);
+                        }
+                    }
+                    None => check_schema(value, &child(&[]), issues),
+                },
+                "type" if !is_type(value) => issue("a type name or an array of unique type names"),
+                // Boolean `required` is the draft-03 form.
+                "required" if !value.is_boolean() && !is_string_set(value) => {
+                    issue("an array of unique strings")
+                }
+                "enum" if !value.is_array() => issue("an array"),
+                "minimum" | "maximum" if !value.is_number() => issue("a number"),
+                // Boolean bounds are the draft-04 form.
+                "exclusiveMinimum" | "exclusiveMaximum"
+                    if !value.is_number() && !value.is_boolean() =>
+                {
+                    issue("a number")
+                }
+                "multipleOf" | "divisibleBy" if !value.as_f64().is_some_and(|n| n > 0.0) => {
+                    issue("a number greater than 0")
+                }
+                "dependentRequired" => match value.as_object() {
+                    Some(entries) if entries.values().all(is_string_set) => {}
+                    _ => issue("an object of arrays of unique strings"),
+                },
+                "dependencies" => match value.as_object() {
+                    Some(entries) => {
+                        for (name, entry) in entries {
+                            if !is_string_set(entry) {
+                                check_schema(entry, &child(&[name]), issues);
+                            }
+                        }
+                    }
+                    None => issue("an object of schemas or arrays of unique strings"),
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A `type` value: a simple type name, or an array of unique ones. Draft-03
+/// `"any"` and schemas inside the array are accepted too.
+fn is_type(value: &Value) -> bool {
+    let is_name = |v: &Value| {
+        v.as_str()
+            .is_some_and(|name| SIMPLE_TYPES.contains(&name) || name == "any")
+    };
+    match value {
+        Value::Array(types) => {
+            types.iter().all(|t| is_name(t) || t.is_object()) && all_unique(types)
+        }
+        other => is_name(other),
+    }
+}
+
+fn is_string_set(value: &Value) -> bool {
+    value
+        .as_array()
+        .is_some_and(|items| items.iter().all(Value::is_string) && all_unique(items))
+}
+
+fn all_unique(items: &[Value]) -> bool {
+    items
+        .iter()
+        .enumerate()
+        .all(|(i, item)| !items[..i].contains(item))
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_well_formed_schema_has_no_issues() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "id": { "type": ["string", "null"], "minLength": 1 },
+                "tags": { "type": "array", "items": { "enum": ["a", "b"] }, "uniqueItems": true },
+                "any": true
+            },
+            "required": ["id"],
+            "dependentRequired": { "id": ["tags"] },
+            "$defs": { "Legacy": { "type": "any", "required": true, "exclusiveMinimum": true } }
+        });
+        assert_eq!(validate_input(&schema), []);
+    }
+
+    #[test]
+    fn test_reports_every_malformed_keyword() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "text", "minLength": -1 },
+                "nested": { "properties": [], "required": ["a", "a"] }
+            },
+            "required": "name",
+            "anyOf": [],
+            "$defs": { "Bad": 42 }
+        });
+        let issues: Vec<_> = validate_input(&schema)
+            .into_iter()
+            .map(|i| (i.path, i.keyword))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                ("#/$defs/Bad".to_string(), String::new()),
+                ("#".to_string(), "anyOf".to_string()),
+                ("#/properties/name".to_string(), "minLength".to_string()),
+                ("#/properties/name".to_string(), "type".to_string()),
+                ("#/properties/nested".to_string(), "properties".to_string()),
+                ("#/properties/nested".to_string(), "required".to_string()),
+                ("#".to_string(), "required".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_issue_message_names_expected_and_found() {
+        let issues = validate_input(&json!({ "required": "name" }));
+        assert_eq!(
+            issues[0].message,
+            "`required` must be an array of unique strings, found a string"
+        );
+    }
+}
@@ -3,4 +3,6 @@
 //! This module provides read-only validation functions that audit schemas
 //! against provider-specific constraints without mutating them.
 
+pub mod conformance;
+pub mod meta_schema;
 pub mod strict_mode;
@@ -28,6 +28,7 @@ pub enum StrictModeRule {
     RootNotObject,
     MissingType,
     MissingAdditionalProperties,
+    RequiredIncomplete,
     MissingItems,
     BannedPatternProperties,
     BannedSchemaRef,
@@ -259,6 +260,32 @@ fn walk(
         }
     }
 
+    // ── RequiredIncomplete ───────────────────────────────────────
+    // Every declared property must be listed in `required`; optional
+    // fields are expressed as nullable instead.
+    if let Some(Value::Object(props)) = obj.get("properties") {
+        let required: Vec<&str> = obj
+            .get("required")
+            .and_then(|v| v.as_array())
+            .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+            .unwrap_or_default();
+        let missing: Vec<&str> = props
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !required.contains(name))
+            .collect();
+        if !missing.is_empty() {
+            violations.push(StrictModeViolation {
+                path: path.to_string(),
+                rule_id: StrictModeRule::RequiredIncomplete,
+                message: format!(
+                    "Object at '{}' does not list {:?} in 'required'",
+                    path, missing
+                ),
+            });
+        }
+    }
+
     // ── MissingItems ─────────────────────────────────────────────
     // OpenAI strict mode requires `items` on array-typed schemas.
     let is_array_type = obj.get("type").and_then(|v| v.as_str()) == Some("array");
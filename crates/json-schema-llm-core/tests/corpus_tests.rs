@@ -0,0 +1,33 @@
+//! Snapshot regression over `tests/corpus/` (see its README).
+
+use json_schema_llm_core::corpus::{check, discover, CaseStatus};
+use std::path::Path;
+
+#[test]
+fn test_corpus_matches_snapshots() {
+    let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/corpus"));
+    let cases = discover(root).expect("tests/corpus should exist");
+    assert!(!cases.is_empty(), "corpus has no cases");
+
+    let failures: Vec<String> = cases
+        .iter()
+        .filter_map(|case| match check(case) {
+            CaseStatus::Match => None,
+            CaseStatus::Mismatch { expected, actual } => Some(format!(
+                "{}: differs from expected.json\n--- expected\n{}\n+++ actual\n{}",
+                case.name,
+                serde_json::to_string_pretty(&expected).unwrap(),
+                serde_json::to_string_pretty(&actual).unwrap(),
+            )),
+            CaseStatus::Missing { .. } => Some(format!("{}: has no expected.json", case.name)),
+            CaseStatus::Invalid(e) => Some(format!("{}: {}", case.name, e)),
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "{} corpus case(s) failed — if intentional, bless with: cargo run -p json-schema-llm -- corpus update\n\n{}",
+        failures.len(),
+        failures.join("\n\n")
+    );
+}
@@ -144,11 +144,11 @@ fn test_e2e_fixture_codec_entry_counts() {
     let cases: Vec<(&str, usize)> = vec![
         ("simple", 2),
         ("maps", 9),
-        ("discriminator", 1),
+        ("discriminator", 2),
         ("opaque", 6),
         ("allof", 3),
         ("recursive", 6),
-        ("kitchen_sink", 22),
+        ("kitchen_sink", 23),
     ];
 
     // Cache results to avoid duplicate convert calls
@@ -431,6 +431,72 @@ fn test_e2e_rehydrate_coercion_then_minimum_violation() {
     );
 }
 
+#[test]
+fn test_e2e_discriminator_case_normalized() {
+    use json_schema_llm_core::codec_warning::WarningKind;
+
+    let schema = load_fixture("discriminator");
+    let result = convert(&schema, &openai_options()).unwrap();
+
+    let llm_output = json!({
+        "event": { "type": " Scroll", "deltaX": 1.0, "deltaY": 2.0 }
+    });
+    let rehydrated = rehydrate(&llm_output, &result.codec, &schema).unwrap();
+    assert_eq!(rehydrated.data["event"]["type"], json!("scroll"));
+    assert!(rehydrated.warnings.iter().any(|w| {
+        matches!(&w.kind, WarningKind::DiscriminatorNormalized { to, .. } if to == "scroll")
+    }));
+}
+
+#[test]
+fn test_e2e_map_size_constraints_checked_on_restored_object() {
+    use json_schema_llm_core::codec_warning::WarningKind;
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "labels": {
+                "type": "object",
+                "additionalProperties": { "type": "string" },
+                "minProperties": 1,
+                "maxProperties": 2
+            }
+        },
+        "required": ["labels"]
+    });
+
+    let result = convert(&schema, &openai_options()).unwrap();
+    let dropped: Vec<_> = result
+        .codec
+        .dropped_constraints
+        .iter()
+        .filter(|dc| dc.path == "#/properties/labels")
+        .map(|dc| dc.constraint.as_str())
+        .collect();
+    assert!(dropped.contains(&"minProperties") && dropped.contains(&"maxProperties"));
+
+    let llm_output = json!({
+        "labels": [
+            { "key": "a", "value": "1" },
+            { "key": "b", "value": "2" },
+            { "key": "c", "value": "3" }
+        ]
+    });
+    let rehydrated = rehydrate(&llm_output, &result.codec, &schema).unwrap();
+    assert_eq!(rehydrated.data["labels"].as_object().unwrap().len(), 3);
+    let violation = rehydrated
+        .warnings
+        .iter()
+        .find(|w| {
+            matches!(&w.kind, WarningKind::ConstraintViolation { constraint } if constraint == "maxProperties")
+        })
+        .expect("restored map should violate maxProperties");
+    assert_eq!(violation.data_path, "/labels");
+    assert!(!rehydrated.warnings.iter().any(|w| {
+        matches!(&w.kind, WarningKind::ConstraintViolation { constraint } if constraint == "minProperties")
+    }));
+}
+
 // 15. Coercion then enforcement: string→integer with maximum, clamped
 #[test]
 fn test_e2e_rehydrate_coercion_then_maximum_enforcement() {
@@ -722,3 +788,409 @@ fn test_e2e_recursive_graph_nested_opaque() {
         "Level 3 data contents should be preserved"
     );
 }
+
+#[test]
+fn test_e2e_anyof_variant_cap_round_trip() {
+    let variants: Vec<Value> = (0..6)
+        .map(|i| {
+            json!({
+                "type": "object",
+                "properties": {
+                    "kind": { "const": format!("k{i}") },
+                    "size": { "type": "integer" }
+                },
+                "required": ["kind", "size"]
+            })
+        })
+        .collect();
+    let schema = json!({
+        "type": "object",
+        "properties": { "shape": { "oneOf": variants } },
+        "required": ["shape"]
+    });
+    let mut options = openai_options();
+    options.max_anyof_variants = Some(4);
+    let result = convert(&schema, &options).unwrap();
+    assert_eq!(
+        result.schema["properties"]["shape"]["anyOf"]
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+
+    // A collapsed variant comes back as a JSON string and is parsed.
+    let llm_output = json!({ "shape": r#"{"kind": "k5", "size": 3}"# });
+    let rehydrated = rehydrate(&llm_output, &result.codec, &schema).unwrap();
+    assert_eq!(
+        rehydrated.data,
+        json!({ "shape": { "kind": "k5", "size": 3 } })
+    );
+
+    // A kept variant passes through untouched.
+    let llm_output = json!({ "shape": { "kind": "k0", "size": 1 } });
+    let rehydrated = rehydrate(&llm_output, &result.codec, &schema).unwrap();
+    assert_eq!(rehydrated.data, llm_output);
+}
+
+#[test]
+fn test_e2e_enum_cap_validates_membership() {
+    let schema = json!({
+        "type": "object",
+        "properties": { "country": { "enum": ["DE", "FR", "IT", "NL", "US"] } },
+        "required": ["country"]
+    });
+    let mut options = openai_options();
+    options.max_enum_values = Some(3);
+    let result = convert(&schema, &options).unwrap();
+    assert_eq!(result.schema["properties"]["country"]["type"], "string");
+
+    let ok = rehydrate(&json!({"country": "NL"}), &result.codec, &schema).unwrap();
+    assert!(ok.warnings.is_empty(), "{:?}", ok.warnings);
+
+    let bad = rehydrate(&json!({"country": "XX"}), &result.codec, &schema).unwrap();
+    assert_eq!(bad.data, json!({"country": "XX"}));
+    assert_eq!(bad.warnings.len(), 1);
+    assert_eq!(bad.warnings[0].data_path, "/country");
+    assert!(bad.warnings[0].message.contains("not one of the 5"));
+}
+
+#[test]
+fn test_e2e_locale_localizes_injected_descriptions() {
+    use json_schema_llm_core::messages::{Locale, MessageCatalog};
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "code": { "type": "string", "minLength": 3 },
+            "extra": { "type": "object" }
+        },
+        "required": ["code", "extra"]
+    });
+    let mut options = ConvertOptions::default();
+    options.target = Target::Claude;
+    options.locale = Locale::Ja;
+    let result = convert(&schema, &options).unwrap();
+
+    let ja = MessageCatalog::builtin(Locale::Ja);
+    assert_eq!(
+        result.schema["properties"]["code"]["description"],
+        "制約: 最小長: 3文字。"
+    );
+    assert_eq!(
+        result.schema["properties"]["extra"]["description"],
+        ja.opaque_object.as_ref()
+    );
+
+    // Only the text changes; the codec still round-trips.
+    let data = json!({"code": "abc", "extra": "{\"k\": 1}"});
+    let rehydrated = rehydrate(&data, &result.codec, &schema).unwrap();
+    assert_eq!(rehydrated.data["extra"], json!({"k": 1}));
+}
+
+#[test]
+fn test_e2e_filter_read_only_round_trip() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "id": { "type": "string", "readOnly": true },
+            "status": { "type": "string", "readOnly": true, "default": "pending" },
+            "title": { "type": "string" }
+        },
+        "required": ["id", "status", "title"]
+    });
+    let mut options = openai_options();
+    options.filter_read_only = true;
+    let result = convert(&schema, &options).unwrap();
+    assert_eq!(result.schema["required"], json!(["title"]));
+    assert_strict_compliance(&result.schema, "#");
+
+    let rehydrated = rehydrate(&json!({"title": "Buy milk"}), &result.codec, &schema).unwrap();
+    assert_eq!(
+        rehydrated.data,
+        json!({"title": "Buy milk", "status": "pending"})
+    );
+}
+
+#[test]
+fn test_e2e_content_schema_inline_round_trip() {
+    use json_schema_llm_core::ContentSchemaPolicy;
+
+    let schema = json!({
+        "$defs": {
+            "Tag": { "type": "string", "enum": ["a", "b"] }
+        },
+        "type": "object",
+        "properties": {
+            "payload": {
+                "type": "string",
+                "contentMediaType": "application/json",
+                "contentSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "tag": { "$ref": "#/$defs/Tag" },
+                        "note": { "type": "string" }
+                    },
+                    "required": ["id", "tag"]
+                }
+            }
+        },
+        "required": ["payload"]
+    });
+    let mut options = openai_options();
+    options.content_schema = ContentSchemaPolicy::Inline;
+    let result = convert(&schema, &options).unwrap();
+    let payload = &result.schema["properties"]["payload"];
+    assert_eq!(payload["type"], "object");
+    assert_eq!(payload["properties"]["tag"]["enum"], json!(["a", "b"]));
+    assert_strict_compliance(&result.schema, "#");
+
+    let output = json!({"payload": {"id": 7, "tag": "b", "note": null}});
+    let rehydrated = rehydrate(&output, &result.codec, &schema).unwrap();
+    assert_eq!(rehydrated.data, json!({"payload": r#"{"id":7,"tag":"b"}"#}));
+}
+
+#[test]
+fn test_e2e_draft07_schema_upgraded() {
+    let schema = json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "definitions": {
+            "Amount": { "type": "number", "minimum": 0, "exclusiveMinimum": true }
+        },
+        "type": "object",
+        "properties": {
+            "total": { "$ref": "#/definitions/Amount" },
+            "coupon": { "type": "string" },
+            "discount": { "$ref": "#/definitions/Amount" }
+        },
+        "required": ["total"],
+        "dependencies": { "coupon": ["discount"] }
+    });
+    let result = convert(&schema, &openai_options()).unwrap();
+    assert_strict_compliance(&result.schema, "#");
+    assert!(!result
+        .provider_compat_errors
+        .iter()
+        .any(|e| e.rule_id() == "JSL-P0-001"));
+    assert!(result
+        .codec
+        .dropped_constraints
+        .iter()
+        .any(|d| d.constraint == "exclusiveMinimum" && d.value == json!(0)));
+    assert!(result
+        .codec
+        .dropped_constraints
+        .iter()
+        .any(|d| d.constraint == "dependentRequired"));
+}
+
+#[test]
+fn test_e2e_deprecated_drop_is_reported_and_suppressible() {
+    use json_schema_llm_core::{DeprecatedPolicy, RuleSelector};
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "nickname": { "type": "string", "deprecated": true }
+        },
+        "required": ["name"]
+    });
+    let mut options = openai_options();
+    options.deprecated_properties = DeprecatedPolicy::Drop;
+    let result = convert(&schema, &options).unwrap();
+    assert!(result.schema["properties"].get("nickname").is_none());
+    let ids: Vec<&str> = result
+        .provider_compat_errors
+        .iter()
+        .map(|e| e.rule_id())
+        .collect();
+    assert_eq!(ids, ["JSL-P1-001"]);
+
+    options.suppress = vec!["JSL-P1-001".parse::<RuleSelector>().unwrap()];
+    let result = convert(&schema, &options).unwrap();
+    assert!(result.provider_compat_errors.is_empty());
+    assert_eq!(result.suppressed_compat_errors.len(), 1);
+}
+
+#[test]
+fn test_e2e_nullable_styles_round_trip() {
+    use json_schema_llm_core::NullableStyle;
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "age": { "type": "integer", "minimum": 0 },
+            "role": { "type": "string", "enum": ["admin", "user"] }
+        },
+        "required": ["name"]
+    });
+    for style in [
+        NullableStyle::AnyOfNull,
+        NullableStyle::TypeArray,
+        NullableStyle::OpenApiNullable,
+    ] {
+        let mut options = ConvertOptions::default();
+        options.target = Target::Gemini;
+        options.nullable_style = style;
+        let result = convert(&schema, &options).unwrap();
+        assert_eq!(
+            result.schema["required"],
+            json!(["age", "name", "role"]),
+            "{style:?}"
+        );
+
+        let output = json!({ "name": "Ada", "age": null, "role": null });
+        let rehydrated = rehydrate(&output, &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data, json!({ "name": "Ada" }), "{style:?}");
+        assert!(
+            rehydrated.warnings.is_empty(),
+            "{style:?}: {:?}",
+            rehydrated.warnings
+        );
+
+        let output = json!({ "name": "Ada", "age": 36, "role": "admin" });
+        let rehydrated = rehydrate(&output, &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data, output, "{style:?}");
+    }
+}
+
+#[test]
+fn test_e2e_recursion_node_id_leaf_round_trip() {
+    use json_schema_llm_core::RecursionLeaf;
+
+    let schema = json!({
+        "$ref": "#/$defs/Node",
+        "$defs": {
+            "Node": {
+                "type": "object",
+                "properties": {
+                    "value": { "type": "string" },
+                    "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } }
+                },
+                "required": ["value"]
+            }
+        }
+    });
+    let mut options = openai_options();
+    options.recursion_limit = 2;
+    options.recursion_leaf = RecursionLeaf::NodeId;
+    let result = convert(&schema, &options).unwrap();
+
+    let output = json!({
+        "$node_id": null,
+        "value": "root",
+        "children": [
+            { "$node_id": "shared", "value": "s", "children": [] },
+            { "$node_id": null, "value": "t", "children": [
+                { "$node_id": null, "value": "u", "children": "[{\"$node_id\": \"shared\"}]" }
+            ] }
+        ]
+    });
+    let rehydrated = rehydrate(&output, &result.codec, &schema).unwrap();
+    assert_eq!(
+        rehydrated.data,
+        json!({
+            "value": "root",
+            "children": [
+                { "value": "s", "children": [] },
+                { "value": "t", "children": [
+                    { "value": "u", "children": [{ "value": "s", "children": [] }] }
+                ] }
+            ]
+        })
+    );
+    assert!(rehydrated.warnings.is_empty(), "{:?}", rehydrated.warnings);
+}
+
+#[test]
+fn test_e2e_custom_additional_properties_name_round_trip() {
+    let schema = json!({
+        "type": "object",
+        "properties": { "name": { "type": "string" } },
+        "required": ["name"],
+        "additionalProperties": { "type": "integer" }
+    });
+    let mut options = ConvertOptions::default();
+    options.additional_properties_name = Some("counts".to_string());
+    let result = convert(&schema, &options).unwrap();
+    assert!(result.schema["properties"].get("counts").is_some());
+
+    let output = json!({
+        "name": "stock",
+        "counts": [{ "key": "apples", "value": 3 }]
+    });
+    let rehydrated = rehydrate(&output, &result.codec, &schema).unwrap();
+    assert_eq!(rehydrated.data, json!({ "name": "stock", "apples": 3 }));
+}
+
+#[test]
+fn test_e2e_bounded_warnings_for_large_arrays() {
+    use json_schema_llm_core::{rehydrate_with_options, RehydrateOptions};
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "tags": { "type": "array", "items": { "type": "string", "maxLength": 2 } }
+        },
+        "required": ["tags"]
+    });
+    let result = convert(&schema, &ConvertOptions::default()).unwrap();
+    let output = json!({ "tags": vec!["long"; 500] });
+
+    let unbounded = rehydrate(&output, &result.codec, &schema).unwrap();
+    assert_eq!(unbounded.warnings.len(), 500);
+
+    let mut options = RehydrateOptions::new();
+    options.aggregate_warnings = Some(5);
+    options.max_warnings = Some(10);
+    let bounded = rehydrate_with_options(&output, &result.codec, &schema, &options).unwrap();
+    assert_eq!(bounded.warnings.len(), 1);
+    assert_eq!(bounded.warnings[0].count, 500);
+    assert_eq!(bounded.warnings[0].sample_paths.len(), 5);
+}
+
+#[test]
+fn test_e2e_preserve_additional_properties_round_trip() {
+    use json_schema_llm_core::AdditionalPropertiesPolicy;
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "labels": { "type": "object", "additionalProperties": { "type": "string" } },
+            "meta": {
+                "type": "object",
+                "properties": { "source": { "type": "string" } },
+                "required": ["source"],
+                "additionalProperties": true
+            }
+        },
+        "required": ["labels", "meta"]
+    });
+    for target in [Target::Gemini, Target::Claude] {
+        let mut options = ConvertOptions::default();
+        options.target = target;
+        options.additional_properties = AdditionalPropertiesPolicy::Preserve;
+        let result = convert(&schema, &options).unwrap();
+
+        assert_eq!(
+            result.schema["properties"]["labels"]["additionalProperties"],
+            json!({ "type": "string" }),
+            "{target:?}: map should stay a typed map"
+        );
+        assert_eq!(
+            result.schema["properties"]["meta"]["additionalProperties"],
+            json!(true),
+            "{target:?}: open object should stay open"
+        );
+
+        let output = json!({
+            "labels": { "env": "prod" },
+            "meta": { "source": "api", "trace": "abc" }
+        });
+        let rehydrated = rehydrate(&output, &result.codec, &schema).unwrap();
+        assert_eq!(rehydrated.data, output);
+    }
+}
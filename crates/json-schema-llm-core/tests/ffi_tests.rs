@@ -2,7 +2,10 @@
 //!
 //! These tests are written FIRST (TDD gate) before any implementation.
 
-use json_schema_llm_core::{convert_json, rehydrate_json, ConvertError, ErrorCode};
+use json_schema_llm_core::{
+    compile_codec_json, convert_json, rehydrate_compiled_json, rehydrate_json,
+    rehydrate_json_with_options, ConvertError, ErrorCode,
+};
 
 // ---------------------------------------------------------------------------
 // Issue #51 — Error Code Mapping
@@ -16,10 +19,7 @@ fn test_error_code_json_parse() {
 
 #[test]
 fn test_error_code_schema_error() {
-    let err = ConvertError::SchemaError {
-        path: "#/properties/name".to_string(),
-        message: "invalid type".to_string(),
-    };
+    let err = ConvertError::schema_error("#/properties/name", "invalid type");
     assert_eq!(err.error_code(), ErrorCode::SchemaError);
 }
 
@@ -43,10 +43,7 @@ fn test_error_code_unsupported_feature() {
 
 #[test]
 fn test_error_code_unresolvable_ref() {
-    let err = ConvertError::UnresolvableRef {
-        path: "#/properties/link".to_string(),
-        reference: "#/definitions/Missing".to_string(),
-    };
+    let err = ConvertError::unresolvable_ref("#/properties/link", "#/definitions/Missing");
     assert_eq!(err.error_code(), ErrorCode::UnresolvableRef);
 }
 
@@ -62,10 +59,7 @@ fn test_error_code_rehydration() {
 
 #[test]
 fn test_error_to_json_structure() {
-    let err = ConvertError::SchemaError {
-        path: "#/properties/name".to_string(),
-        message: "invalid type".to_string(),
-    };
+    let err = ConvertError::schema_error("#/properties/name", "invalid type");
     let json = err.to_json();
     assert!(json.get("code").unwrap().is_string());
     assert!(json.get("message").unwrap().is_string());
@@ -331,8 +325,8 @@ fn test_convert_json_has_api_version() {
     );
     assert_eq!(
         parsed["apiVersion"].as_str().unwrap(),
-        "1.0",
-        "apiVersion must be '1.0'"
+        "2.0",
+        "apiVersion must be '2.0'"
     );
 }
 
@@ -352,16 +346,17 @@ fn test_rehydrate_json_has_api_version() {
     );
     assert_eq!(
         parsed["apiVersion"].as_str().unwrap(),
-        "1.0",
-        "apiVersion must be '1.0'"
+        "2.0",
+        "apiVersion must be '2.0'"
     );
 }
 
-/// Stability contract: apiVersion string must be exactly "1.0".
+/// Stability contract: apiVersion string must be exactly "2.0" (2.0 made
+/// `list_components` entries objects rather than pointer strings).
 #[test]
 fn test_api_version_stability() {
     use json_schema_llm_core::API_VERSION;
-    assert_eq!(API_VERSION, "1.0", "API_VERSION constant must be '1.0'");
+    assert_eq!(API_VERSION, "2.0", "API_VERSION constant must be '2.0'");
 }
 
 /// Codec must serialize with camelCase field names.
@@ -389,14 +384,14 @@ fn test_warning_camel_case_serialization() {
     use json_schema_llm_core::codec_warning::WarningKind;
     use json_schema_llm_core::Warning;
 
-    let warning = Warning {
-        data_path: "/foo".to_string(),
-        schema_path: "#/properties/foo".to_string(),
-        kind: WarningKind::ConstraintViolation {
+    let warning = Warning::new(
+        "/foo",
+        "#/properties/foo",
+        WarningKind::ConstraintViolation {
             constraint: "minimum".to_string(),
         },
-        message: "test".to_string(),
-    };
+        "test",
+    );
     let json = serde_json::to_string(&warning).unwrap();
 
     assert!(
@@ -454,6 +449,30 @@ fn test_error_code_codec_version_mismatch_stability() {
     );
 }
 
+/// ErrorCode::ResourceLimitExceeded must serialize to exact string.
+#[test]
+fn test_error_code_resource_limit_exceeded_stability() {
+    let code = ErrorCode::ResourceLimitExceeded;
+    let json = serde_json::to_value(code).unwrap();
+    assert_eq!(
+        json.as_str().unwrap(),
+        "resource_limit_exceeded",
+        "ErrorCode::ResourceLimitExceeded must serialize to 'resource_limit_exceeded'"
+    );
+}
+
+/// ErrorCode::Cancelled must serialize to exact string.
+#[test]
+fn test_error_code_cancelled_stability() {
+    let code = ErrorCode::Cancelled;
+    let json = serde_json::to_value(code).unwrap();
+    assert_eq!(
+        json.as_str().unwrap(),
+        "cancelled",
+        "ErrorCode::Cancelled must serialize to 'cancelled'"
+    );
+}
+
 /// Bridge convert output fields must be camelCase (schema, codec stay as-is since single-word).
 #[test]
 fn test_convert_bridge_camel_case_codec_fields() {
@@ -494,7 +513,7 @@ fn test_convert_json_partial_options_use_defaults() {
     );
 
     let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
-    assert_eq!(parsed["apiVersion"].as_str().unwrap(), "1.0");
+    assert_eq!(parsed["apiVersion"].as_str().unwrap(), "2.0");
 }
 
 /// Panic-safety: empty schema `{}` (valid Draft 2020-12, means "accept anything").
@@ -619,10 +638,7 @@ fn test_convert_result_deserialize_roundtrip() {
 fn test_error_json_all_variants_shape() {
     let errors: Vec<ConvertError> = vec![
         ConvertError::JsonError(serde_json::from_str::<serde_json::Value>("{{").unwrap_err()),
-        ConvertError::SchemaError {
-            path: "#/test".to_string(),
-            message: "test error".to_string(),
-        },
+        ConvertError::schema_error("#/test", "test error"),
         ConvertError::RecursionDepthExceeded {
             path: "#/test".to_string(),
             max_depth: 50,
@@ -631,15 +647,17 @@ fn test_error_json_all_variants_shape() {
             path: "#/test".to_string(),
             feature: "testFeature".to_string(),
         },
-        ConvertError::UnresolvableRef {
-            path: "#/test".to_string(),
-            reference: "#/missing".to_string(),
-        },
+        ConvertError::unresolvable_ref("#/test", "#/missing"),
         ConvertError::RehydrationError("test rehydration error".to_string()),
         ConvertError::CodecVersionMismatch {
             found: "v99".to_string(),
             expected: "v1".to_string(),
         },
+        ConvertError::ResourceLimitExceeded {
+            path: "#".to_string(),
+            resource: "max_nodes".to_string(),
+            limit: 10,
+        },
     ];
 
     for err in &errors {
@@ -681,7 +699,7 @@ fn test_convert_json_concurrent_calls() {
                 assert!(result.is_ok(), "Thread {} failed: {:?}", i, result.err());
 
                 let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
-                assert_eq!(parsed["apiVersion"].as_str().unwrap(), "1.0");
+                assert_eq!(parsed["apiVersion"].as_str().unwrap(), "2.0");
                 let prop_key = format!("field{}", i);
                 assert!(
                     parsed["schema"]["properties"].get(&prop_key).is_some(),
@@ -722,3 +740,80 @@ fn test_bridge_error_matches_typed_error() {
         "Bridge and typed API must produce identical error codes"
     );
 }
+
+#[test]
+fn test_rehydrate_json_rejects_trailing_garbage() {
+    let codec = r#"{"$schema": "https://json-schema-llm.dev/codec/v1", "transforms": [], "droppedConstraints": []}"#;
+    let result = rehydrate_json(r#"{"a": 1}}"#, codec, r#"{"type": "object"}"#);
+    let err_json: serde_json::Value = serde_json::from_str(&result.unwrap_err()).unwrap();
+    assert_eq!(err_json["code"].as_str().unwrap(), "json_parse_error");
+}
+
+#[test]
+fn test_rehydrate_compiled_json_matches_rehydrate_json() {
+    let schema = r#"{"type": "object", "properties": {"n": {"type": "integer"}}}"#;
+    let converted: serde_json::Value =
+        serde_json::from_str(&convert_json(schema, "{}").unwrap()).unwrap();
+    let codec_json = converted["codec"].to_string();
+    let compiled = compile_codec_json(&codec_json).expect("codec compiles");
+    for data in [r#"{"n": 1}"#, r#"{"n": "2"}"#] {
+        assert_eq!(
+            rehydrate_compiled_json(data, &compiled, schema, "{}").unwrap(),
+            rehydrate_json(data, &codec_json, schema).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_rehydrate_json_with_options_bounds_warnings() {
+    let schema = r#"{"type": "object", "properties": {"tags": {"type": "array", "items": {"type": "string", "maxLength": 2}}}}"#;
+    let converted: serde_json::Value =
+        serde_json::from_str(&convert_json(schema, "{}").unwrap()).unwrap();
+    let codec_json = converted["codec"].to_string();
+    let data = r#"{"tags": ["long", "longer", "longest"]}"#;
+
+    let all: serde_json::Value =
+        serde_json::from_str(&rehydrate_json(data, &codec_json, schema).unwrap()).unwrap();
+    assert_eq!(all["warnings"].as_array().unwrap().len(), 3);
+
+    let merged: serde_json::Value = serde_json::from_str(
+        &rehydrate_json_with_options(data, &codec_json, schema, r#"{"aggregate-warnings": 2}"#)
+            .unwrap(),
+    )
+    .unwrap();
+    let warnings = merged["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["count"], 3);
+    assert_eq!(
+        warnings[0]["samplePaths"],
+        serde_json::json!(["/tags/0", "/tags/1"])
+    );
+
+    let capped: serde_json::Value = serde_json::from_str(
+        &rehydrate_json_with_options(data, &codec_json, schema, r#"{"max-warnings": 1}"#).unwrap(),
+    )
+    .unwrap();
+    let kinds: Vec<&str> = capped["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["kind"]["type"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["constraint_violation", "truncated"]);
+}
+
+#[test]
+fn test_rehydrate_json_with_options_invalid_options() {
+    let codec = r#"{"$schema": "https://json-schema-llm.dev/codec/v1", "transforms": [], "droppedConstraints": []}"#;
+    let err =
+        rehydrate_json_with_options("{}", codec, "{}", r#"{"max-warnings": "x"}"#).unwrap_err();
+    let err_json: serde_json::Value = serde_json::from_str(&err).unwrap();
+    assert_eq!(err_json["code"], "json_parse_error");
+}
+
+#[test]
+fn test_compile_codec_json_invalid_codec() {
+    let err = compile_codec_json("not valid json").unwrap_err();
+    let err_json: serde_json::Value = serde_json::from_str(&err).unwrap();
+    assert_eq!(err_json["code"].as_str().unwrap(), "json_parse_error");
+}
@@ -314,6 +314,60 @@ fn test_convert_full_roundtrip() {
     assert_eq!(tags["team"], json!("platform"));
 }
 
+#[test]
+fn test_convert_roundtrip_map_with_union_values() {
+    // Branch 0 is a list of pairs that looks just like a transpiled map;
+    // only entries in branch 1 may be restored to one.
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "headers": {
+                "type": "object",
+                "additionalProperties": {
+                    "anyOf": [
+                        {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "key": { "type": "string" },
+                                    "value": { "type": "string" }
+                                },
+                                "required": ["key", "value"]
+                            }
+                        },
+                        {
+                            "type": "object",
+                            "additionalProperties": { "type": "string" }
+                        }
+                    ]
+                }
+            }
+        },
+        "required": ["headers"]
+    });
+
+    let result = convert(&schema, &openai_options()).expect("convert should succeed");
+    let entry = &result.schema["properties"]["headers"]["items"];
+    assert_eq!(entry["properties"]["variant"]["enum"], json!([0, 1]));
+
+    let pairs = json!([{ "key": "accept", "value": "json" }]);
+    let llm_output = json!({
+        "headers": [
+            { "key": "list", "variant": 0, "value": pairs },
+            { "key": "map", "variant": 1, "value": [{ "key": "accept", "value": "json" }] }
+        ]
+    });
+
+    let rehydrated =
+        rehydrate(&llm_output, &result.codec, &schema).expect("rehydrate should succeed");
+    assert_eq!(rehydrated.data["headers"]["list"], pairs);
+    assert_eq!(
+        rehydrated.data["headers"]["map"],
+        json!({ "accept": "json" })
+    );
+}
+
 // ── Target-Specific Skips ───────────────────────────────────────────────────
 
 #[test]
@@ -548,3 +602,32 @@ fn test_mode_defaults_to_strict() {
     let defaults = ConvertOptions::default();
     assert_eq!(defaults.mode, Mode::Strict, "Default mode should be Strict");
 }
+
+// ── Reusable Pipeline ───────────────────────────────────────────────────────
+
+#[test]
+fn test_convert_pipeline_is_reusable_and_round_trips() {
+    use json_schema_llm_core::ConvertPipeline;
+
+    let pipeline = ConvertPipeline::new().target(Target::Gemini);
+    let schemas = [
+        json!({"type": "object", "properties": {"n": {"type": "integer"}}}),
+        json!({
+            "type": "object",
+            "properties": {
+                "tags": {"type": "object", "additionalProperties": {"type": "string"}}
+            },
+            "required": ["tags"]
+        }),
+    ];
+    for schema in &schemas {
+        let result = pipeline.convert(schema).unwrap();
+        let expected = convert(schema, &gemini_options()).unwrap();
+        assert_eq!(result.schema, expected.schema);
+    }
+
+    let result = ConvertPipeline::new().convert(&schemas[1]).unwrap();
+    let llm_output = json!({"tags": [{"key": "a", "value": "x"}]});
+    let rehydrated = rehydrate(&llm_output, &result.codec, &schemas[1]).unwrap();
+    assert_eq!(rehydrated.data, json!({"tags": {"a": "x"}}));
+}
@@ -8,8 +8,11 @@
 //! recursion), `rehydrate(data, convert(schema).codec, schema).data == data`
 //! for any data conforming to the schema, since the converted schema is
 //! structurally identical to the original.
+//!
+//! With every property optional, `dehydrate` fills a missing one with `null`
+//! and rehydration drops it again.
 
-use json_schema_llm_core::{convert, rehydrate, ConvertOptions};
+use json_schema_llm_core::{convert, dehydrate, rehydrate, ConvertOptions};
 use proptest::prelude::*;
 use serde_json::{json, Map, Value};
 
@@ -170,6 +173,24 @@ proptest! {
         );
     }
 
+    /// Property: dehydrate→rehydrate restores data missing optional fields.
+    #[test]
+    fn dehydrate_rehydrate_roundtrip((mut schema, mut data) in arb_schema_and_data()) {
+        schema.as_object_mut().unwrap().remove("required");
+        let first = data.as_object().unwrap().keys().next().cloned().unwrap();
+        data.as_object_mut().unwrap().remove(&first);
+
+        let result = convert(&schema, &ConvertOptions::default())
+            .expect("convert should not fail on well-formed schema");
+        let converted = dehydrate(&data, &result.codec)
+            .expect("dehydrate should not fail on conforming data");
+        prop_assert_eq!(&converted[first.as_str()], &Value::Null);
+
+        let rehydrated = rehydrate(&converted, &result.codec, &schema)
+            .expect("rehydrate should not fail with matching codec");
+        prop_assert_eq!(&rehydrated.data, &data);
+    }
+
     /// Property: convert never panics on arbitrary simple schemas.
     #[test]
     fn convert_no_panic((schema, _data) in arb_schema_and_data()) {
@@ -107,6 +107,22 @@ fn test_missing_items() {
     assert_has_violation(&v, &StrictModeRule::MissingItems);
 }
 
+#[test]
+fn test_required_incomplete() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "nickname": { "type": "string" }
+        },
+        "required": ["name"],
+        "additionalProperties": false
+    });
+    let v = validate_strict_mode(&schema);
+    assert_has_violation(&v, &StrictModeRule::RequiredIncomplete);
+    assert!(v[0].message.contains("nickname"));
+}
+
 #[test]
 fn test_banned_pattern_properties() {
     let schema = json!({
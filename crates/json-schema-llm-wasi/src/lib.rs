@@ -21,6 +21,14 @@
 //!
 //! - `jsl_convert(schema_ptr, schema_len, opts_ptr, opts_len) → result_ptr`
 //! - `jsl_rehydrate(data_ptr, data_len, codec_ptr, codec_len, schema_ptr, schema_len) → result_ptr`
+//! - `jsl_rehydrate_with_options(data_ptr, data_len, codec_ptr, codec_len, schema_ptr, schema_len, opts_ptr, opts_len) → result_ptr`
+//! - `jsl_schema_fingerprint(schema_ptr, schema_len, opts_ptr, opts_len) → result_ptr`
+//!
+//! ### Build Info
+//!
+//! - `jsl_abi_version() → u32` — the export contract version
+//! - `jsl_version() → result_ptr` — JSON with crate semver, codec major
+//!   version, supported targets and compiled feature flags
 //!
 //! ### Result Protocol
 //!
@@ -37,6 +45,30 @@
 //!
 //! The host must: read the result → copy the JSON bytes → call `jsl_result_free`.
 //!
+//! ### Instances and Reentrancy
+//!
+//! Apart from the handle table below, the module keeps no global mutable
+//! state: every other export works only from its arguments, so exports are
+//! reentrant and separate module instances (e.g. one per goroutine from a
+//! shared compiled module) share nothing.
+//!
+//! Hosts that reuse one instance for several logical callers can give each a
+//! context handle:
+//!
+//! - `jsl_instance_new() → handle`
+//! - `jsl_instance_free(handle) → status` — `1` for an unknown or busy handle
+//! - `jsl_instance_<op>(handle, …)` for every operation above, e.g.
+//!   `jsl_instance_convert(handle, schema_ptr, schema_len, opts_ptr, opts_len)`
+//!
+//! Per-caller state — so far the compiled codec `jsl_instance_rehydrate`
+//! reuses across calls — lives on the handle, never in a global. Handles
+//! index a table and carry a generation, so they are checked without ever
+//! being dereferenced. A handle serves one call at a time: a call on a handle
+//! that is already mid-call fails with code `instance_busy`, and a freed,
+//! stale or bogus handle fails with `invalid_handle`. A slot's generation
+//! wraps after 65536 frees, after which a long-stale handle to it could
+//! validate again.
+//!
 //! ### Panic Behavior
 //!
 //! This module compiles with `panic = "abort"` (the `wasm32-wasip1` default).
@@ -63,6 +95,17 @@ pub extern "C" fn jsl_abi_version() -> u32 {
     ABI_VERSION
 }
 
+/// Describe this build: crate semver, codec major version, supported targets
+/// and compiled feature flags.
+///
+/// Returns a `JslResult` whose payload is `{"apiVersion", "version",
+/// "codecMajorVersion", "targets", "features"}`; it never fails. Wrappers
+/// use it beyond [`jsl_abi_version`] to gate functionality at runtime.
+#[no_mangle]
+pub extern "C" fn jsl_version() -> u32 {
+    result_from_bridge(Ok(json_schema_llm_core::version_json())) as u32
+}
+
 /// C-ABI result struct returned from `jsl_convert` and `jsl_rehydrate`.
 ///
 /// Layout: 12 bytes (3 × u32), `#[repr(C)]` for stable ABI.
@@ -121,6 +164,163 @@ fn result_from_input_error(code: &str, message: &str) -> *mut JslResult {
     .into_raw()
 }
 
+// ---------------------------------------------------------------------------
+// Instance handles
+// ---------------------------------------------------------------------------
+
+/// Bits of a handle holding its slot index (plus one, so `0` is never a
+/// handle); the rest hold the slot's generation.
+const HANDLE_INDEX_BITS: u32 = 16;
+const HANDLE_INDEX_MASK: u32 = (1 << HANDLE_INDEX_BITS) - 1;
+
+/// Per-caller context behind a `jsl_instance_new` handle.
+#[derive(Default)]
+struct JslInstance {
+    /// The last codec this caller rehydrated with, keyed by its JSON text,
+    /// so a stream of responses for one conversion compiles it once.
+    codec: Option<(String, json_schema_llm_core::CompiledCodec)>,
+}
+
+impl JslInstance {
+    /// The compiled form of `codec_json`, from the cache when it matches.
+    fn codec(&mut self, codec_json: &str) -> Result<&json_schema_llm_core::CompiledCodec, String> {
+        let cached = match self.codec.take() {
+            Some((text, compiled)) if text == codec_json => (text, compiled),
+            _ => (
+                codec_json.to_string(),
+                json_schema_llm_core::compile_codec_json(codec_json)?,
+            ),
+        };
+        Ok(&self.codec.insert(cached).1)
+    }
+}
+
+/// One entry of the handle table.
+#[derive(Default)]
+struct Slot {
+    /// Bumped on every free, so handles to earlier occupants go stale.
+    generation: u16,
+    state: SlotState,
+}
+
+/// What a [`Slot`] holds.
+#[derive(Default)]
+enum SlotState {
+    #[default]
+    Free,
+    Idle(Box<JslInstance>),
+    /// Mid-call: the instance is lent to the running export.
+    Busy,
+}
+
+thread_local! {
+    /// Live instances, indexed by handle. Handles are only ever decoded into
+    /// an index and generation and checked against this table — never
+    /// dereferenced — so stale, freed or forged handles are rejected.
+    static INSTANCES: std::cell::RefCell<Vec<Slot>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Why a handle could not be used.
+#[derive(Debug, PartialEq)]
+enum HandleError {
+    Invalid,
+    Busy,
+}
+
+impl HandleError {
+    fn into_result(self) -> *mut JslResult {
+        match self {
+            HandleError::Invalid => {
+                result_from_input_error("invalid_handle", "unknown or freed instance handle")
+            }
+            HandleError::Busy => {
+                result_from_input_error("instance_busy", "instance is already in a call")
+            }
+        }
+    }
+}
+
+/// The live slot `handle` names, if any.
+fn slot_mut(table: &mut [Slot], handle: u32) -> Option<&mut Slot> {
+    let index = (handle & HANDLE_INDEX_MASK).checked_sub(1)?;
+    let generation = handle >> HANDLE_INDEX_BITS;
+    table
+        .get_mut(index as usize)
+        .filter(|slot| u32::from(slot.generation) == generation)
+        .filter(|slot| !matches!(slot.state, SlotState::Free))
+}
+
+/// Take the instance behind `handle` for the duration of a call.
+fn claim(handle: u32) -> Result<Box<JslInstance>, HandleError> {
+    INSTANCES.with_borrow_mut(|table| {
+        let slot = slot_mut(table, handle).ok_or(HandleError::Invalid)?;
+        match std::mem::replace(&mut slot.state, SlotState::Busy) {
+            SlotState::Idle(instance) => Ok(instance),
+            _ => Err(HandleError::Busy),
+        }
+    })
+}
+
+/// Return an instance taken with [`claim`].
+fn release(handle: u32, instance: Box<JslInstance>) {
+    INSTANCES.with_borrow_mut(|table| {
+        if let Some(slot) = slot_mut(table, handle) {
+            slot.state = SlotState::Idle(instance);
+        }
+    });
+}
+
+/// Run `op` on behalf of the instance `handle`, one call at a time.
+fn with_instance(handle: u32, op: impl FnOnce(&mut JslInstance) -> u32) -> u32 {
+    let mut instance = match claim(handle) {
+        Ok(instance) => instance,
+        Err(err) => return err.into_result() as u32,
+    };
+    let result = op(&mut instance);
+    release(handle, instance);
+    result
+}
+
+/// Create a context handle for one logical caller of this module instance.
+///
+/// Release it with [`jsl_instance_free`]. Returns `0` if all 65535 slots
+/// are in use.
+#[no_mangle]
+pub extern "C" fn jsl_instance_new() -> u32 {
+    INSTANCES.with_borrow_mut(|table| {
+        let index = match table
+            .iter()
+            .position(|slot| matches!(slot.state, SlotState::Free))
+        {
+            Some(index) => index,
+            None if table.len() < HANDLE_INDEX_MASK as usize => {
+                table.push(Slot::default());
+                table.len() - 1
+            }
+            None => return 0,
+        };
+        let slot = &mut table[index];
+        slot.state = SlotState::Idle(Box::default());
+        (u32::from(slot.generation) << HANDLE_INDEX_BITS) | (index as u32 + 1)
+    })
+}
+
+/// Free a handle from [`jsl_instance_new`].
+///
+/// Returns `0` on success, `1` if `handle` is unknown, already freed or in
+/// the middle of a call (in which case nothing is freed).
+#[no_mangle]
+pub extern "C" fn jsl_instance_free(handle: u32) -> u32 {
+    INSTANCES.with_borrow_mut(|table| match slot_mut(table, handle) {
+        Some(slot) if matches!(slot.state, SlotState::Idle(_)) => {
+            slot.state = SlotState::Free;
+            slot.generation = slot.generation.wrapping_add(1);
+            STATUS_OK
+        }
+        _ => STATUS_ERROR,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Memory management exports
 // ---------------------------------------------------------------------------
@@ -280,7 +480,7 @@ pub extern "C" fn jsl_convert(
 ///
 /// # Returns
 ///
-/// Pointer to a `JslResult` in linear memory. `status=0` payload: `{"apiVersion":"1.0","schema":{...}}`.
+/// Pointer to a `JslResult` in linear memory. `status=0` payload: `{"apiVersion":"2.0","schema":{...}}`.
 #[no_mangle]
 pub extern "C" fn jsl_apply_patch(
     schema_ptr: u32,
@@ -322,6 +522,51 @@ pub extern "C" fn jsl_rehydrate(
     codec_len: u32,
     schema_ptr: u32,
     schema_len: u32,
+) -> u32 {
+    rehydrate_with(
+        data_ptr, data_len, codec_ptr, codec_len, schema_ptr, schema_len, "{}",
+    )
+}
+
+/// [`jsl_rehydrate`] with rehydrate options.
+///
+/// # Arguments
+///
+/// - `opts_ptr` / `opts_len`: Options JSON (UTF-8 bytes) with `kebab-case`
+///   keys, e.g. `{"aggregate-warnings": 3, "max-warnings": 100}`
+///
+/// # Returns
+///
+/// Pointer to a `JslResult` in linear memory.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn jsl_rehydrate_with_options(
+    data_ptr: u32,
+    data_len: u32,
+    codec_ptr: u32,
+    codec_len: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    opts_ptr: u32,
+    opts_len: u32,
+) -> u32 {
+    let opts_str = match unsafe { read_guest_str(opts_ptr, opts_len) } {
+        Ok(s) => s,
+        Err(err_ptr) => return err_ptr as u32,
+    };
+    rehydrate_with(
+        data_ptr, data_len, codec_ptr, codec_len, schema_ptr, schema_len, &opts_str,
+    )
+}
+
+fn rehydrate_with(
+    data_ptr: u32,
+    data_len: u32,
+    codec_ptr: u32,
+    codec_len: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    opts_str: &str,
 ) -> u32 {
     // --- Read all three inputs (checked UTF-8) ---
     let data_str = match unsafe { read_guest_str(data_ptr, data_len) } {
@@ -337,10 +582,11 @@ pub extern "C" fn jsl_rehydrate(
         Err(err_ptr) => return err_ptr as u32,
     };
 
-    result_from_bridge(json_schema_llm_core::rehydrate_json(
+    result_from_bridge(json_schema_llm_core::rehydrate_json_with_options(
         &data_str,
         &codec_str,
         &schema_str,
+        opts_str,
     )) as u32
 }
 
@@ -352,7 +598,7 @@ pub extern "C" fn jsl_rehydrate(
 ///
 /// # Returns
 ///
-/// Pointer to a `JslResult`. `status=0` payload: `{"apiVersion":"1.0","components":[...]}`.
+/// Pointer to a `JslResult`. `status=0` payload: `{"apiVersion":"2.0","components":[...]}`.
 #[no_mangle]
 pub extern "C" fn jsl_list_components(schema_ptr: u32, schema_len: u32) -> u32 {
     let schema_str = match unsafe { read_guest_str(schema_ptr, schema_len) } {
@@ -372,7 +618,7 @@ pub extern "C" fn jsl_list_components(schema_ptr: u32, schema_len: u32) -> u32 {
 ///
 /// # Returns
 ///
-/// Pointer to a `JslResult`. `status=0` payload: `{"apiVersion":"1.0","schema":{...},...}`.
+/// Pointer to a `JslResult`. `status=0` payload: `{"apiVersion":"2.0","schema":{...},...}`.
 #[no_mangle]
 pub extern "C" fn jsl_extract_component(
     schema_ptr: u32,
@@ -426,7 +672,7 @@ pub extern "C" fn jsl_extract_component(
 ///
 /// # Returns
 ///
-/// Pointer to a `JslResult`. `status=0` payload: `{"apiVersion":"1.0","full":{...},"components":[...]}`.
+/// Pointer to a `JslResult`. `status=0` payload: `{"apiVersion":"2.0","full":{...},"components":[...]}`.
 #[no_mangle]
 pub extern "C" fn jsl_convert_all_components(
     schema_ptr: u32,
@@ -482,3 +728,305 @@ pub extern "C" fn jsl_convert_all_components(
         &effective_ext_opts,
     )) as u32
 }
+
+/// Fingerprint a JSON Schema as converted with the given options.
+///
+/// # Arguments
+///
+/// - `schema_ptr` / `schema_len`: JSON Schema document (UTF-8 bytes)
+/// - `opts_ptr` / `opts_len`: Conversion options JSON (UTF-8 bytes). Pass 0/0 for defaults.
+///
+/// # Returns
+///
+/// Pointer to a `JslResult`. `status=0` payload: `{"apiVersion":"2.0","fingerprint":"sha256:..."}`.
+#[no_mangle]
+pub extern "C" fn jsl_schema_fingerprint(
+    schema_ptr: u32,
+    schema_len: u32,
+    opts_ptr: u32,
+    opts_len: u32,
+) -> u32 {
+    let schema_str = match unsafe { read_guest_str(schema_ptr, schema_len) } {
+        Ok(s) => s,
+        Err(err_ptr) => return err_ptr as u32,
+    };
+    let opts_str = if opts_ptr == 0 || opts_len == 0 {
+        "{}".to_string()
+    } else {
+        match unsafe { read_guest_str(opts_ptr, opts_len) } {
+            Ok(s) => s,
+            Err(err_ptr) => return err_ptr as u32,
+        }
+    };
+    result_from_bridge(json_schema_llm_core::schema_fingerprint_json(
+        &schema_str,
+        &opts_str,
+    )) as u32
+}
+
+// ---------------------------------------------------------------------------
+// Instance-scoped operation exports
+// ---------------------------------------------------------------------------
+
+/// [`jsl_convert`] on behalf of an instance handle.
+#[no_mangle]
+pub extern "C" fn jsl_instance_convert(
+    handle: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    opts_ptr: u32,
+    opts_len: u32,
+) -> u32 {
+    with_instance(handle, |_| {
+        jsl_convert(schema_ptr, schema_len, opts_ptr, opts_len)
+    })
+}
+
+/// [`jsl_apply_patch`] on behalf of an instance handle.
+#[no_mangle]
+pub extern "C" fn jsl_instance_apply_patch(
+    handle: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    patch_ptr: u32,
+    patch_len: u32,
+) -> u32 {
+    with_instance(handle, |_| {
+        jsl_apply_patch(schema_ptr, schema_len, patch_ptr, patch_len)
+    })
+}
+
+/// [`jsl_rehydrate`] on behalf of an instance handle. The instance keeps the
+/// last codec compiled, so repeated calls with the same codec text skip
+/// parsing and compiling it.
+#[no_mangle]
+pub extern "C" fn jsl_instance_rehydrate(
+    handle: u32,
+    data_ptr: u32,
+    data_len: u32,
+    codec_ptr: u32,
+    codec_len: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+) -> u32 {
+    with_instance(handle, |instance| {
+        instance_rehydrate_with(
+            instance, data_ptr, data_len, codec_ptr, codec_len, schema_ptr, schema_len, "{}",
+        )
+    })
+}
+
+/// [`jsl_rehydrate_with_options`] on behalf of an instance handle.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn jsl_instance_rehydrate_with_options(
+    handle: u32,
+    data_ptr: u32,
+    data_len: u32,
+    codec_ptr: u32,
+    codec_len: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    opts_ptr: u32,
+    opts_len: u32,
+) -> u32 {
+    with_instance(handle, |instance| {
+        let opts_str = match unsafe { read_guest_str(opts_ptr, opts_len) } {
+            Ok(s) => s,
+            Err(err_ptr) => return err_ptr as u32,
+        };
+        instance_rehydrate_with(
+            instance, data_ptr, data_len, codec_ptr, codec_len, schema_ptr, schema_len, &opts_str,
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn instance_rehydrate_with(
+    instance: &mut JslInstance,
+    data_ptr: u32,
+    data_len: u32,
+    codec_ptr: u32,
+    codec_len: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    opts_str: &str,
+) -> u32 {
+    let data_str = match unsafe { read_guest_str(data_ptr, data_len) } {
+        Ok(s) => s,
+        Err(err_ptr) => return err_ptr as u32,
+    };
+    let codec_str = match unsafe { read_guest_str(codec_ptr, codec_len) } {
+        Ok(s) => s,
+        Err(err_ptr) => return err_ptr as u32,
+    };
+    let schema_str = match unsafe { read_guest_str(schema_ptr, schema_len) } {
+        Ok(s) => s,
+        Err(err_ptr) => return err_ptr as u32,
+    };
+    let outcome = instance.codec(&codec_str).and_then(|codec| {
+        json_schema_llm_core::rehydrate_compiled_json(&data_str, codec, &schema_str, opts_str)
+    });
+    result_from_bridge(outcome) as u32
+}
+
+/// [`jsl_list_components`] on behalf of an instance handle.
+#[no_mangle]
+pub extern "C" fn jsl_instance_list_components(
+    handle: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+) -> u32 {
+    with_instance(handle, |_| jsl_list_components(schema_ptr, schema_len))
+}
+
+/// [`jsl_extract_component`] on behalf of an instance handle.
+#[no_mangle]
+pub extern "C" fn jsl_instance_extract_component(
+    handle: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    pointer_ptr: u32,
+    pointer_len: u32,
+    opts_ptr: u32,
+    opts_len: u32,
+) -> u32 {
+    with_instance(handle, |_| {
+        jsl_extract_component(
+            schema_ptr,
+            schema_len,
+            pointer_ptr,
+            pointer_len,
+            opts_ptr,
+            opts_len,
+        )
+    })
+}
+
+/// [`jsl_convert_all_components`] on behalf of an instance handle.
+#[no_mangle]
+pub extern "C" fn jsl_instance_convert_all_components(
+    handle: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    conv_opts_ptr: u32,
+    conv_opts_len: u32,
+    ext_opts_ptr: u32,
+    ext_opts_len: u32,
+) -> u32 {
+    with_instance(handle, |_| {
+        jsl_convert_all_components(
+            schema_ptr,
+            schema_len,
+            conv_opts_ptr,
+            conv_opts_len,
+            ext_opts_ptr,
+            ext_opts_len,
+        )
+    })
+}
+
+/// [`jsl_schema_fingerprint`] on behalf of an instance handle.
+#[no_mangle]
+pub extern "C" fn jsl_instance_schema_fingerprint(
+    handle: u32,
+    schema_ptr: u32,
+    schema_len: u32,
+    opts_ptr: u32,
+    opts_len: u32,
+) -> u32 {
+    with_instance(handle, |_| {
+        jsl_schema_fingerprint(schema_ptr, schema_len, opts_ptr, opts_len)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEC: &str = r#"{"$schema": "https://json-schema-llm.dev/codec/v1", "transforms": [], "droppedConstraints": []}"#;
+
+    #[test]
+    fn test_instance_new_and_free() {
+        let a = jsl_instance_new();
+        let b = jsl_instance_new();
+        assert_ne!(a, 0);
+        assert_ne!(a, b);
+        assert_eq!(jsl_instance_free(a), STATUS_OK);
+        assert_eq!(jsl_instance_free(b), STATUS_OK);
+    }
+
+    #[test]
+    fn test_double_free_is_rejected() {
+        let handle = jsl_instance_new();
+        assert_eq!(jsl_instance_free(handle), STATUS_OK);
+        assert_eq!(jsl_instance_free(handle), STATUS_ERROR);
+    }
+
+    #[test]
+    fn test_use_after_free_is_rejected() {
+        let handle = jsl_instance_new();
+        jsl_instance_free(handle);
+        assert_eq!(claim(handle).err(), Some(HandleError::Invalid));
+    }
+
+    #[test]
+    fn test_stale_handle_after_slot_reuse_is_rejected() {
+        let stale = jsl_instance_new();
+        jsl_instance_free(stale);
+        let fresh = jsl_instance_new();
+        assert_eq!(fresh & HANDLE_INDEX_MASK, stale & HANDLE_INDEX_MASK);
+        assert_ne!(fresh, stale);
+
+        assert_eq!(claim(stale).err(), Some(HandleError::Invalid));
+        assert_eq!(jsl_instance_free(stale), STATUS_ERROR);
+        assert!(claim(fresh).is_ok(), "the slot's new occupant is untouched");
+    }
+
+    #[test]
+    fn test_forged_handles_are_rejected() {
+        jsl_instance_new();
+        for handle in [0, HANDLE_INDEX_MASK, 0xdead_beef, u32::MAX] {
+            assert_eq!(claim(handle).err(), Some(HandleError::Invalid));
+            assert_eq!(jsl_instance_free(handle), STATUS_ERROR);
+        }
+    }
+
+    #[test]
+    fn test_busy_instance_is_neither_reentered_nor_freed() {
+        let handle = jsl_instance_new();
+        let instance = claim(handle).expect("idle instance");
+        assert_eq!(claim(handle).err(), Some(HandleError::Busy));
+        assert_eq!(jsl_instance_free(handle), STATUS_ERROR);
+        release(handle, instance);
+        assert_eq!(jsl_instance_free(handle), STATUS_OK);
+    }
+
+    #[test]
+    fn test_instance_caches_compiled_codec() {
+        let mut instance = JslInstance::default();
+        assert!(instance.codec(CODEC).is_ok());
+        assert!(instance.codec("not valid json").is_err());
+        assert!(
+            instance.codec.is_none(),
+            "a failed compile clears the cache"
+        );
+        instance.codec(CODEC).unwrap();
+        assert_eq!(
+            instance.codec.as_ref().map(|(text, _)| text.as_str()),
+            Some(CODEC)
+        );
+    }
+
+    #[test]
+    fn test_instances_keep_separate_codecs() {
+        let a = jsl_instance_new();
+        let b = jsl_instance_new();
+        let mut instance = claim(a).expect("idle instance");
+        instance.codec(CODEC).unwrap();
+        release(a, instance);
+        let other = claim(b).expect("idle instance");
+        assert!(other.codec.is_none());
+        release(b, other);
+    }
+}
@@ -0,0 +1,21 @@
+import type { ConvertOptions, ConvertResult, Codec, RehydrateResult } from "./json_schema_llm_wasm.js";
+
+type JsonInput = unknown | string | Uint8Array;
+
+export interface ConvertPoolOptions {
+  /** Worker count; defaults to `navigator.hardwareConcurrency`. */
+  size?: number;
+  /** Worker script; defaults to the bundled `worker.js`. */
+  workerUrl?: string | URL;
+}
+
+/** Runs convert / rehydrate on Web Workers, exchanging UTF-8 JSON buffers. */
+export class ConvertPool {
+  constructor(options?: ConvertPoolOptions);
+  convert(schema: JsonInput, options?: ConvertOptions | string | Uint8Array | null): Promise<ConvertResult>;
+  /** The `ConvertResult` JSON, transferred rather than cloned. */
+  convertBytes(schema: JsonInput, options?: ConvertOptions | string | Uint8Array | null): Promise<Uint8Array>;
+  rehydrate(data: JsonInput, codec: Codec | string | Uint8Array, originalSchema: JsonInput): Promise<RehydrateResult>;
+  /** Stop every worker; pending calls reject with code "cancelled". */
+  terminate(): void;
+}
@@ -0,0 +1,101 @@
+// Runs convert / rehydrate on a pool of Web Workers so large schemas never
+// block the UI thread. Inputs are sent as UTF-8 JSON bytes and results come
+// back as transferred buffers (no structured clone of big object graphs).
+//
+//   import { ConvertPool } from "json-schema-llm/worker-pool";
+//   const pool = new ConvertPool({ size: 2 });
+//   const { schema, codec } = await pool.convert(bigSchema, { target: "gemini" });
+//   pool.terminate();
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+/**
+ * Encode a value or JSON string as UTF-8 JSON bytes, adding the new buffer to
+ * `transfer`. Byte arrays pass through and stay with the caller.
+ */
+function toBytes(value, transfer) {
+  if (value instanceof Uint8Array) return value;
+  const bytes = encoder.encode(typeof value === "string" ? value : JSON.stringify(value));
+  transfer.push(bytes.buffer);
+  return bytes;
+}
+
+export class ConvertPool {
+  /**
+   * @param {{ size?: number, workerUrl?: string | URL }} [options]
+   *   `size` defaults to `navigator.hardwareConcurrency` (at least 1);
+   *   `workerUrl` defaults to the `worker.js` shipped next to this file.
+   */
+  constructor({ size, workerUrl } = {}) {
+    const count = Math.max(1, size ?? globalThis.navigator?.hardwareConcurrency ?? 2);
+    const url = workerUrl ?? new URL("./worker.js", import.meta.url);
+    this._idle = [];
+    this._queue = [];
+    this._pending = new Map();
+    this._nextId = 0;
+    this._workers = Array.from({ length: count }, () => {
+      const worker = new Worker(url, { type: "module" });
+      worker.onmessage = ({ data }) => this._settle(worker, data);
+      this._idle.push(worker);
+      return worker;
+    });
+  }
+
+  /** Convert on a worker; resolves to the parsed `ConvertResult`. */
+  async convert(schema, options) {
+    return JSON.parse(decoder.decode(await this.convertBytes(schema, options)));
+  }
+
+  /** Convert on a worker; resolves to the `ConvertResult` as UTF-8 JSON bytes. */
+  convertBytes(schema, options) {
+    const transfer = [];
+    const opts = options == null ? new Uint8Array() : toBytes(options, transfer);
+    return this._run("convert", [toBytes(schema, transfer), opts], transfer);
+  }
+
+  /** Rehydrate on a worker; resolves to the parsed `RehydrateResult`. */
+  async rehydrate(data, codec, originalSchema) {
+    const transfer = [];
+    const args = [toBytes(data, transfer), toBytes(codec, transfer), toBytes(originalSchema, transfer)];
+    return JSON.parse(decoder.decode(await this._run("rehydrate", args, transfer)));
+  }
+
+  /** Stop every worker. Pending calls are rejected. */
+  terminate() {
+    for (const worker of this._workers) worker.terminate();
+    const error = { code: "cancelled", message: "worker pool terminated", path: null };
+    for (const { reject } of this._pending.values()) reject(error);
+    for (const { reject } of this._queue) reject(error);
+    this._pending.clear();
+    this._queue = [];
+    this._idle = [];
+  }
+
+  _run(op, args, transfer) {
+    return new Promise((resolve, reject) => {
+      this._queue.push({ id: this._nextId++, op, args, transfer, resolve, reject });
+      this._dispatch();
+    });
+  }
+
+  _dispatch() {
+    while (this._idle.length > 0 && this._queue.length > 0) {
+      const worker = this._idle.pop();
+      const { id, op, args, transfer, resolve, reject } = this._queue.shift();
+      this._pending.set(id, { resolve, reject });
+      worker.postMessage({ id, op, args }, transfer);
+    }
+  }
+
+  _settle(worker, { id, bytes, error }) {
+    const call = this._pending.get(id);
+    this._pending.delete(id);
+    this._idle.push(worker);
+    if (call) {
+      if (error !== undefined) call.reject(error);
+      else call.resolve(bytes);
+    }
+    this._dispatch();
+  }
+}
@@ -0,0 +1,17 @@
+// Worker side of worker-pool.js: runs the byte API of the web build and
+// transfers each result buffer back instead of cloning it.
+import init, { convertBytes, rehydrateBytes } from "./json_schema_llm_wasm.js";
+
+const ready = init();
+const ops = { convert: convertBytes, rehydrate: rehydrateBytes };
+
+self.onmessage = async ({ data: { id, op, args } }) => {
+  try {
+    await ready;
+    const bytes = ops[op](...args);
+    self.postMessage({ id, bytes }, [bytes.buffer]);
+  } catch (error) {
+    // Structured errors ({ code, message, path }) clone as plain objects.
+    self.postMessage({ id, error });
+  }
+};
@@ -10,15 +10,24 @@
 //! - Errors are structured JS objects `{ code, message, path }`.
 //! - The `options` parameter defaults to `ConvertOptions::default()` when omitted.
 //! - Options accept **camelCase** (`maxDepth`) with kebab-case fallback (`max-depth`).
+//! - `compileCodec` returns a `CodecHandle` that `rehydrateWithHandle` reuses,
+//!   so streams of responses don't re-parse the codec or recompile its regexes.
+//! - `convertBytes` / `rehydrateBytes` take and return UTF-8 JSON as
+//!   `Uint8Array`, so results can be transferred across Worker boundaries
+//!   without structured-clone cost (see `js/worker-pool.js`).
 
 use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
 use serde_wasm_bindgen::Serializer;
 
+use json_schema_llm_core::messages::{Locale, MessageCatalog};
 use json_schema_llm_core::{
-    ConvertError, ConvertOptions, Mode, PolymorphismStrategy, ProviderCompatError, Target,
-    API_VERSION,
+    AdditionalPropertiesPolicy, ConstraintHint, ConstraintHintPolicy, ContentSchemaPolicy,
+    ConvertError, ConvertOptions, CycleInfo, DeprecatedPolicy, DuplicateKeyCheck,
+    DuplicateKeyPolicy, EnumDescriptionStyle, Envelope, ErrorPolicy, MergeConflictPolicy, Mode,
+    NullableStyle, PolymorphismStrategy, Preset, PropertyOrdering, ProviderCompatError,
+    RecursionLeaf, RehydrateOptions, RuleSelector, Target, API_VERSION,
 };
 
 // ---------------------------------------------------------------------------
@@ -39,6 +48,14 @@ struct WasmConvertResult<'a> {
     codec: &'a json_schema_llm_core::Codec,
     #[serde(skip_serializing_if = "is_empty_slice")]
     provider_compat_errors: &'a [ProviderCompatError],
+    #[serde(skip_serializing_if = "is_empty_slice")]
+    suppressed_compat_errors: &'a [ProviderCompatError],
+    #[serde(skip_serializing_if = "is_empty_slice")]
+    constraint_hints: &'a [ConstraintHint],
+    #[serde(skip_serializing_if = "is_empty_slice")]
+    recursion_cycles: &'a [CycleInfo],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    envelope: Option<&'a serde_json::Value>,
 }
 
 /// WASM envelope for `rehydrate` results.
@@ -59,11 +76,14 @@ struct WasmRehydrateResult<'a> {
 ///
 /// NOTE: Keep in sync with `json_schema_llm_core::ConvertOptions`.
 /// If core adds new options, update this struct to match.
-/// Defaults are sourced from `ConvertOptions::default()` (single source of truth).
+/// Defaults are sourced from `ConvertOptions::default()` (single source of truth),
+/// or from `ConvertOptions::preset()` when `preset` is given.
 #[derive(Default, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 struct WasmConvertOptions {
+    #[serde(alias = "preset")]
+    preset: Option<Preset>,
     #[serde(alias = "target")]
     target: Option<Target>,
     #[serde(alias = "mode")]
@@ -72,15 +92,83 @@ struct WasmConvertOptions {
     max_depth: Option<usize>,
     #[serde(alias = "recursion-limit")]
     recursion_limit: Option<usize>,
+    #[serde(alias = "recursion-leaf")]
+    recursion_leaf: Option<RecursionLeaf>,
     #[serde(alias = "polymorphism")]
     polymorphism: Option<PolymorphismStrategy>,
+    #[serde(alias = "merge-conflicts")]
+    merge_conflicts: Option<MergeConflictPolicy>,
+    #[serde(alias = "on-error")]
+    on_error: Option<ErrorPolicy>,
     #[serde(alias = "skip-components")]
     skip_components: Option<bool>,
+    #[serde(alias = "max-schema-bytes")]
+    max_schema_bytes: Option<usize>,
+    #[serde(alias = "duplicate-keys")]
+    duplicate_keys: Option<DuplicateKeyCheck>,
+    #[serde(alias = "max-nodes")]
+    max_nodes: Option<usize>,
+    #[serde(alias = "time-budget-ms")]
+    time_budget_ms: Option<u64>,
+    #[serde(alias = "provider-max-depth")]
+    provider_max_depth: Option<usize>,
+    #[serde(alias = "max-anyof-variants")]
+    max_anyof_variants: Option<usize>,
+    #[serde(alias = "max-enum-values")]
+    max_enum_values: Option<usize>,
+    #[serde(alias = "enum-descriptions")]
+    enum_descriptions: Option<EnumDescriptionStyle>,
+    #[serde(alias = "constraint-hints")]
+    constraint_hints: Option<ConstraintHintPolicy>,
+    #[serde(alias = "numeric-constraint-hints")]
+    numeric_constraint_hints: Option<bool>,
+    #[serde(alias = "constraint-hint-budget")]
+    constraint_hint_budget: Option<usize>,
+    #[serde(alias = "nullable-style")]
+    nullable_style: Option<NullableStyle>,
+    #[serde(alias = "additional-properties")]
+    additional_properties: Option<AdditionalPropertiesPolicy>,
+    #[serde(alias = "additional-properties-name")]
+    additional_properties_name: Option<String>,
+    #[serde(alias = "filter-read-only")]
+    filter_read_only: Option<bool>,
+    #[serde(alias = "filter-write-only")]
+    filter_write_only: Option<bool>,
+    #[serde(alias = "deprecated-properties")]
+    deprecated_properties: Option<DeprecatedPolicy>,
+    #[serde(alias = "content-schema")]
+    content_schema: Option<ContentSchemaPolicy>,
+    #[serde(alias = "locale")]
+    locale: Option<Locale>,
+    #[serde(alias = "message-catalog")]
+    message_catalog: Option<MessageCatalog>,
+    #[serde(alias = "suppress")]
+    suppress: Option<Vec<RuleSelector>>,
+    #[serde(alias = "verify-output")]
+    verify_output: Option<bool>,
+    #[serde(alias = "validate-input")]
+    validate_input: Option<bool>,
+    #[serde(alias = "strip-descriptions")]
+    strip_descriptions: Option<bool>,
+    #[serde(alias = "keep-descriptions")]
+    keep_descriptions: Option<Vec<String>>,
+    #[serde(alias = "annotate")]
+    annotate: Option<bool>,
+    #[serde(alias = "canonical")]
+    canonical: Option<bool>,
+    #[serde(alias = "property-ordering")]
+    property_ordering: Option<PropertyOrdering>,
+    #[serde(alias = "envelope")]
+    envelope: Option<Envelope>,
+    #[serde(alias = "envelope-name")]
+    envelope_name: Option<String>,
 }
 
 impl From<WasmConvertOptions> for ConvertOptions {
     fn from(wasm: WasmConvertOptions) -> Self {
-        let mut opts = ConvertOptions::default();
+        let mut opts = wasm
+            .preset
+            .map_or_else(ConvertOptions::default, ConvertOptions::preset);
         if let Some(target) = wasm.target {
             opts.target = target;
         }
@@ -93,16 +181,143 @@ impl From<WasmConvertOptions> for ConvertOptions {
         if let Some(recursion_limit) = wasm.recursion_limit {
             opts.recursion_limit = recursion_limit;
         }
+        if let Some(recursion_leaf) = wasm.recursion_leaf {
+            opts.recursion_leaf = recursion_leaf;
+        }
         if let Some(polymorphism) = wasm.polymorphism {
             opts.polymorphism = polymorphism;
         }
+        if let Some(merge_conflicts) = wasm.merge_conflicts {
+            opts.merge_conflicts = merge_conflicts;
+        }
+        if let Some(on_error) = wasm.on_error {
+            opts.on_error = on_error;
+        }
         if let Some(skip_components) = wasm.skip_components {
             opts.skip_components = skip_components;
         }
+        opts.max_schema_bytes = wasm.max_schema_bytes.or(opts.max_schema_bytes);
+        if let Some(duplicate_keys) = wasm.duplicate_keys {
+            opts.duplicate_keys = duplicate_keys;
+        }
+        opts.max_nodes = wasm.max_nodes.or(opts.max_nodes);
+        opts.time_budget_ms = wasm.time_budget_ms.or(opts.time_budget_ms);
+        opts.provider_max_depth = wasm.provider_max_depth.or(opts.provider_max_depth);
+        opts.max_anyof_variants = wasm.max_anyof_variants.or(opts.max_anyof_variants);
+        opts.max_enum_values = wasm.max_enum_values.or(opts.max_enum_values);
+        if let Some(enum_descriptions) = wasm.enum_descriptions {
+            opts.enum_descriptions = enum_descriptions;
+        }
+        if let Some(constraint_hints) = wasm.constraint_hints {
+            opts.constraint_hints = constraint_hints;
+        }
+        if let Some(numeric_constraint_hints) = wasm.numeric_constraint_hints {
+            opts.numeric_constraint_hints = numeric_constraint_hints;
+        }
+        opts.constraint_hint_budget = wasm.constraint_hint_budget.or(opts.constraint_hint_budget);
+        if let Some(nullable_style) = wasm.nullable_style {
+            opts.nullable_style = nullable_style;
+        }
+        if let Some(additional_properties) = wasm.additional_properties {
+            opts.additional_properties = additional_properties;
+        }
+        opts.additional_properties_name = wasm
+            .additional_properties_name
+            .or(opts.additional_properties_name);
+        if let Some(filter_read_only) = wasm.filter_read_only {
+            opts.filter_read_only = filter_read_only;
+        }
+        if let Some(filter_write_only) = wasm.filter_write_only {
+            opts.filter_write_only = filter_write_only;
+        }
+        if let Some(deprecated_properties) = wasm.deprecated_properties {
+            opts.deprecated_properties = deprecated_properties;
+        }
+        if let Some(content_schema) = wasm.content_schema {
+            opts.content_schema = content_schema;
+        }
+        if let Some(locale) = wasm.locale {
+            opts.locale = locale;
+        }
+        opts.message_catalog = wasm.message_catalog.or(opts.message_catalog);
+        if let Some(suppress) = wasm.suppress {
+            opts.suppress = suppress;
+        }
+        if let Some(verify_output) = wasm.verify_output {
+            opts.verify_output = verify_output;
+        }
+        if let Some(validate_input) = wasm.validate_input {
+            opts.validate_input = validate_input;
+        }
+        if let Some(strip_descriptions) = wasm.strip_descriptions {
+            opts.strip_descriptions = strip_descriptions;
+        }
+        if let Some(keep_descriptions) = wasm.keep_descriptions {
+            opts.keep_descriptions = keep_descriptions;
+        }
+        if let Some(annotate) = wasm.annotate {
+            opts.annotate = annotate;
+        }
+        if let Some(canonical) = wasm.canonical {
+            opts.canonical = canonical;
+        }
+        if let Some(property_ordering) = wasm.property_ordering {
+            opts.property_ordering = property_ordering;
+        }
+        opts.envelope = wasm.envelope.or(opts.envelope);
+        opts.envelope_name = wasm.envelope_name.or(opts.envelope_name);
         opts
     }
 }
 
+/// WASM-local rehydrate options DTO, accepting camelCase or kebab-case like
+/// [`WasmConvertOptions`].
+///
+/// NOTE: Keep in sync with `json_schema_llm_core::RehydrateOptions`.
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+struct WasmRehydrateOptions {
+    #[serde(alias = "aggregate-warnings")]
+    aggregate_warnings: Option<usize>,
+    #[serde(alias = "max-warnings")]
+    max_warnings: Option<usize>,
+    #[serde(alias = "duplicate-key-policy")]
+    duplicate_key_policy: Option<DuplicateKeyPolicy>,
+    #[serde(alias = "normalize-discriminators")]
+    normalize_discriminators: Option<bool>,
+    #[serde(alias = "max-expanded-nodes")]
+    max_expanded_nodes: Option<usize>,
+}
+
+impl From<WasmRehydrateOptions> for RehydrateOptions {
+    fn from(wasm: WasmRehydrateOptions) -> Self {
+        let mut opts = RehydrateOptions::new();
+        opts.aggregate_warnings = wasm.aggregate_warnings;
+        opts.max_warnings = wasm.max_warnings;
+        if let Some(duplicate_key_policy) = wasm.duplicate_key_policy {
+            opts.duplicate_key_policy = duplicate_key_policy;
+        }
+        if let Some(normalize_discriminators) = wasm.normalize_discriminators {
+            opts.normalize_discriminators = normalize_discriminators;
+        }
+        if let Some(max_expanded_nodes) = wasm.max_expanded_nodes {
+            opts.max_expanded_nodes = max_expanded_nodes;
+        }
+        opts
+    }
+}
+
+/// Read optional rehydrate options; `undefined` or `null` means defaults.
+fn rehydrate_options(options: JsValue) -> Result<RehydrateOptions, JsValue> {
+    if options.is_undefined() || options.is_null() {
+        return Ok(RehydrateOptions::default());
+    }
+    let wasm_opts: WasmRehydrateOptions =
+        serde_wasm_bindgen::from_value(options).map_err(to_serde_js_error)?;
+    Ok(wasm_opts.into())
+}
+
 // ---------------------------------------------------------------------------
 // Error helpers
 // ---------------------------------------------------------------------------
@@ -184,6 +399,10 @@ pub fn convert(schema: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
         schema: &result.schema,
         codec: &result.codec,
         provider_compat_errors: &result.provider_compat_errors,
+        suppressed_compat_errors: &result.suppressed_compat_errors,
+        constraint_hints: &result.constraint_hints,
+        recursion_cycles: &result.recursion_cycles,
+        envelope: result.envelope.as_ref(),
     };
 
     let serializer = Serializer::json_compatible();
@@ -192,16 +411,19 @@ pub fn convert(schema: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
 
 /// Rehydrate LLM output back to the original schema shape.
 ///
-/// Accepts a JS object (data), a JS object (codec), and the original
-/// JSON Schema (for type coercion).
+/// Accepts a JS object (data), a JS object (codec), the original
+/// JSON Schema (for type coercion), and an optional JS object (options).
 /// Returns a JS object: `{ apiVersion: "1.0", data, warnings }`.
 ///
+/// Options accept camelCase (`maxWarnings`) or kebab-case (`max-warnings`).
+///
 /// On error, throws a structured JS object `{ code, message, path }`.
 #[wasm_bindgen(skip_typescript)]
 pub fn rehydrate(
     data: JsValue,
     codec: JsValue,
     original_schema: JsValue,
+    options: JsValue,
 ) -> Result<JsValue, JsValue> {
     let data: serde_json::Value =
         serde_wasm_bindgen::from_value(data).map_err(to_serde_js_error)?;
@@ -209,9 +431,11 @@ pub fn rehydrate(
         serde_wasm_bindgen::from_value(codec).map_err(to_serde_js_error)?;
     let original_schema: serde_json::Value =
         serde_wasm_bindgen::from_value(original_schema).map_err(to_serde_js_error)?;
+    let options = rehydrate_options(options)?;
 
-    let result = json_schema_llm_core::rehydrate(&data, &codec, &original_schema)
-        .map_err(|e| to_structured_js_error(&e))?;
+    let result =
+        json_schema_llm_core::rehydrate_with_options(&data, &codec, &original_schema, &options)
+            .map_err(|e| to_structured_js_error(&e))?;
 
     let bridge = WasmRehydrateResult {
         api_version: API_VERSION,
@@ -223,6 +447,182 @@ pub fn rehydrate(
     bridge.serialize(&serializer).map_err(to_serde_js_error)
 }
 
+/// Fingerprint a schema as converted with `options`.
+///
+/// Accepts the same arguments as [`convert`] and returns a
+/// `"sha256:<hex>"` string that is stable across key ordering.
+///
+/// On error, throws a structured JS object `{ code, message, path }`.
+#[wasm_bindgen(js_name = schemaFingerprint, skip_typescript)]
+pub fn schema_fingerprint(schema: JsValue, options: JsValue) -> Result<String, JsValue> {
+    let schema: serde_json::Value =
+        serde_wasm_bindgen::from_value(schema).map_err(to_serde_js_error)?;
+
+    let options: ConvertOptions = if options.is_undefined() || options.is_null() {
+        ConvertOptions::default()
+    } else {
+        let wasm_opts: WasmConvertOptions =
+            serde_wasm_bindgen::from_value(options).map_err(to_serde_js_error)?;
+        wasm_opts.into()
+    };
+
+    Ok(json_schema_llm_core::schema_fingerprint(&schema, &options))
+}
+
+// ---------------------------------------------------------------------------
+// Compiled codec handles
+// ---------------------------------------------------------------------------
+
+/// A codec compiled once for repeated rehydration, with the original schema
+/// used for type coercion. Release with `free()` when done.
+#[wasm_bindgen(skip_typescript)]
+pub struct CodecHandle {
+    codec: json_schema_llm_core::CompiledCodec,
+    original_schema: serde_json::Value,
+}
+
+/// Parse and compile a codec (version check, `pattern` / `patternProperties`
+/// regexes) for [`rehydrate_with_handle`].
+///
+/// `originalSchema` enables type coercion as in [`rehydrate`]; when omitted,
+/// data is rehydrated without coercion.
+///
+/// On error, throws a structured JS object `{ code, message, path }`.
+#[wasm_bindgen(js_name = compileCodec, skip_typescript)]
+pub fn compile_codec(codec: JsValue, original_schema: JsValue) -> Result<CodecHandle, JsValue> {
+    let codec: json_schema_llm_core::Codec =
+        serde_wasm_bindgen::from_value(codec).map_err(to_serde_js_error)?;
+    let original_schema = if original_schema.is_undefined() || original_schema.is_null() {
+        serde_json::Value::Bool(true)
+    } else {
+        serde_wasm_bindgen::from_value(original_schema).map_err(to_serde_js_error)?
+    };
+    let codec =
+        json_schema_llm_core::CompiledCodec::new(codec).map_err(|e| to_structured_js_error(&e))?;
+    Ok(CodecHandle {
+        codec,
+        original_schema,
+    })
+}
+
+/// [`rehydrate`] with a codec from [`compile_codec`].
+///
+/// Returns a JS object: `{ apiVersion: "1.0", data, warnings }`.
+///
+/// On error, throws a structured JS object `{ code, message, path }`.
+#[wasm_bindgen(js_name = rehydrateWithHandle, skip_typescript)]
+pub fn rehydrate_with_handle(
+    data: JsValue,
+    handle: &CodecHandle,
+    options: JsValue,
+) -> Result<JsValue, JsValue> {
+    let data: serde_json::Value =
+        serde_wasm_bindgen::from_value(data).map_err(to_serde_js_error)?;
+    let options = rehydrate_options(options)?;
+
+    let result = json_schema_llm_core::rehydrate_compiled(
+        data,
+        &handle.codec,
+        &handle.original_schema,
+        &options,
+    )
+    .map_err(|e| to_structured_js_error(&e))?;
+
+    let bridge = WasmRehydrateResult {
+        api_version: API_VERSION,
+        data: &result.data,
+        warnings: &result.warnings,
+    };
+
+    let serializer = Serializer::json_compatible();
+    bridge.serialize(&serializer).map_err(to_serde_js_error)
+}
+
+// ---------------------------------------------------------------------------
+// Transferable (byte) API
+// ---------------------------------------------------------------------------
+
+/// Parse UTF-8 JSON bytes, mapping failures to `{ code: "json_parse_error", ... }`.
+fn parse_json_bytes<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, JsValue> {
+    serde_json::from_slice(bytes).map_err(|e| to_structured_js_error(&ConvertError::JsonError(e)))
+}
+
+/// Serialize a result envelope to UTF-8 JSON bytes.
+fn to_json_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>, JsValue> {
+    serde_json::to_vec(value).map_err(|e| to_structured_js_error(&ConvertError::JsonError(e)))
+}
+
+/// [`convert`] over UTF-8 JSON bytes: takes the schema and options as
+/// `Uint8Array`s and returns the result envelope as a `Uint8Array`.
+///
+/// The returned buffer can be transferred across a Worker boundary instead
+/// of structured-cloned. An empty `options` uses defaults. Because the
+/// schema arrives as text, `propertyOrdering: "original"` follows its key
+/// order, and `maxSchemaBytes` is checked before parsing.
+///
+/// On error, throws a structured JS object `{ code, message, path }`.
+#[wasm_bindgen(js_name = convertBytes, skip_typescript)]
+pub fn convert_bytes(schema: &[u8], options: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let options: ConvertOptions = if options.is_empty() {
+        ConvertOptions::default()
+    } else {
+        parse_json_bytes::<WasmConvertOptions>(options)?.into()
+    };
+    let schema = std::str::from_utf8(schema).map_err(|e| {
+        to_structured_js_error(&ConvertError::schema_error(
+            "",
+            format!("schema is not valid UTF-8: {}", e),
+        ))
+    })?;
+
+    let result = json_schema_llm_core::convert_str(schema, &options)
+        .map_err(|e| to_structured_js_error(&e))?;
+
+    to_json_bytes(&WasmConvertResult {
+        api_version: API_VERSION,
+        schema: &result.schema,
+        codec: &result.codec,
+        provider_compat_errors: &result.provider_compat_errors,
+        suppressed_compat_errors: &result.suppressed_compat_errors,
+        constraint_hints: &result.constraint_hints,
+        recursion_cycles: &result.recursion_cycles,
+        envelope: result.envelope.as_ref(),
+    })
+}
+
+/// [`rehydrate`] over UTF-8 JSON bytes: takes data, codec and original
+/// schema as `Uint8Array`s and returns the result envelope as a `Uint8Array`.
+///
+/// On error, throws a structured JS object `{ code, message, path }`.
+#[wasm_bindgen(js_name = rehydrateBytes, skip_typescript)]
+pub fn rehydrate_bytes(
+    data: &[u8],
+    codec: &[u8],
+    original_schema: &[u8],
+) -> Result<Vec<u8>, JsValue> {
+    let data: serde_json::Value = parse_json_bytes(data)?;
+    let codec: json_schema_llm_core::Codec = parse_json_bytes(codec)?;
+    let original_schema: serde_json::Value = parse_json_bytes(original_schema)?;
+
+    // `data` is ours, so hand it over rather than have `rehydrate` clone it.
+    let result = json_schema_llm_core::CompiledCodec::new(codec)
+        .and_then(|codec| {
+            json_schema_llm_core::rehydrate_compiled(
+                data,
+                &codec,
+                &original_schema,
+                &json_schema_llm_core::RehydrateOptions::default(),
+            )
+        })
+        .map_err(|e| to_structured_js_error(&e))?;
+
+    to_json_bytes(&WasmRehydrateResult {
+        api_version: API_VERSION,
+        data: &result.data,
+        warnings: &result.warnings,
+    })
+}
+
 // ⚠️ SYNC WARNING: These TypeScript types are hand-authored to match the
 // serialized JS shapes produced by serde + Serializer::json_compatible().
 // If you modify any of these Rust types, you MUST update the corresponding
@@ -231,8 +631,12 @@ pub fn rehydrate(
 //   - WasmConvertOptions (this file)      → ConvertOptions
 //   - WasmConvertResult (this file)       → ConvertResult
 //   - WasmRehydrateResult (this file)     → RehydrateResult
+//   - WasmRehydrateOptions (this file)    → RehydrateOptions
 //   - Target (config.rs)                  → Target
 //   - PolymorphismStrategy (config.rs)    → PolymorphismStrategy
+//   - PropertyOrdering (config.rs)        → PropertyOrdering
+//   - Envelope (config.rs)                → Envelope
+//   - RuleSelector (diagnostics.rs)       → RuleSelector
 //   - Codec (codec.rs)                    → Codec
 //   - Transform (codec.rs)               → Transform
 //   - DroppedConstraint (codec.rs)        → DroppedConstraint
@@ -245,14 +649,84 @@ const TS_TYPES: &str = r#"
 export type Target = "openai-strict" | "gemini" | "claude";
 export type Mode = "strict" | "permissive";
 export type PolymorphismStrategy = "any-of" | "flatten";
+export type PropertyOrdering = "off" | "original";
+export type MergeConflictPolicy = "intersect" | "prefer-first" | "error";
+export type ErrorPolicy = "fail" | "skip-subtree" | "placeholder";
+export type DuplicateKeyCheck = "ignore" | "warn" | "error";
+export type EnumDescriptionStyle = "auto" | "fold" | "one-of" | "ignore";
+export type DeprecatedPolicy = "keep" | "drop" | "annotate";
+export type ContentSchemaPolicy = "opaque" | "inline";
+export type Envelope = "response-format" | "tool-function";
+export type ConstraintHintPolicy = "off" | "labeled" | "compact";
+export type RecursionLeaf = "opaque-string" | "null" | "node-id";
+export type NullableStyle = "any-of-null" | "type-array" | "open-api-nullable";
+export type AdditionalPropertiesPolicy = "seal" | "preserve";
+export type Locale = "en" | "es" | "ja";
+export type Preset =
+  | "openai-structured-output"
+  | "openai-tool-calling"
+  | "gemini-structured-output"
+  | "claude-tool-use";
 
 export interface ConvertOptions {
+  /** Starting values; the other fields override them. */
+  preset?: Preset;
   target?: Target;
   mode?: Mode;
   maxDepth?: number;
   recursionLimit?: number;
+  recursionLeaf?: RecursionLeaf;
   polymorphism?: PolymorphismStrategy;
+  mergeConflicts?: MergeConflictPolicy;
+  onError?: ErrorPolicy;
   skipComponents?: boolean;
+  maxSchemaBytes?: number;
+  duplicateKeys?: DuplicateKeyCheck;
+  maxNodes?: number;
+  /** Not enforced under wasm32-unknown-unknown (no monotonic clock). */
+  timeBudgetMs?: number;
+  providerMaxDepth?: number;
+  maxAnyofVariants?: number;
+  maxEnumValues?: number;
+  enumDescriptions?: EnumDescriptionStyle;
+  constraintHints?: ConstraintHintPolicy;
+  numericConstraintHints?: boolean;
+  constraintHintBudget?: number;
+  /** Ignored for openai-strict, which always uses "any-of-null". */
+  nullableStyle?: NullableStyle;
+  /** Gemini and Claude only; "preserve" keeps open objects and maps. */
+  additionalProperties?: AdditionalPropertiesPolicy;
+  /** Default "_additional". */
+  additionalPropertiesName?: string;
+  filterReadOnly?: boolean;
+  filterWriteOnly?: boolean;
+  deprecatedProperties?: DeprecatedPolicy;
+  contentSchema?: ContentSchemaPolicy;
+  locale?: Locale;
+  /** Kebab-case template keys (e.g. "hint-minimum"); overrides `locale`. Missing keys fall back to English. */
+  messageCatalog?: Record<string, string>;
+  suppress?: RuleSelector[];
+  /** Fail with "provider_compat_failure" if the output breaks the target's requirements. */
+  verifyOutput?: boolean;
+  validateInput?: boolean;
+  /** Drop descriptions and titles, except JSON-string instructions and `keepDescriptions`. */
+  stripDescriptions?: boolean;
+  /** JSON Pointer globs (`*`, `**`) into the converted schema. */
+  keepDescriptions?: string[];
+  /** Mark transformed nodes with `x-jsl-transform`; for debugging, not for the provider. */
+  annotate?: boolean;
+  /** Diff-stable output: sorted keys and `required`/`type` arrays, integral numbers. */
+  canonical?: boolean;
+  /** Gemini only. Schema objects arrive with sorted keys, so "original" is alphabetical here. */
+  propertyOrdering?: PropertyOrdering;
+  envelope?: Envelope;
+  envelopeName?: string;
+}
+
+/** Acknowledge a diagnostic; `path` is a JSON Pointer glob (`*`, `**`). */
+export interface RuleSelector {
+  rule: RuleId;
+  path?: string;
 }
 
 export interface Codec {
@@ -262,12 +736,31 @@ export interface Codec {
 }
 
 export type Transform =
-  | { type: "map_to_array"; path: string; keyField: string }
+  | { type: "map_to_array"; path: string; keyField: string; variantField?: string }
   | { type: "json_string_parse"; path: string }
   | { type: "nullable_optional"; path: string; originalRequired: boolean }
-  | { type: "discriminator_any_of"; path: string; discriminator: string; variants: string[] }
+  | {
+      type: "discriminator_any_of";
+      path: string;
+      discriminator: string;
+      variants: string[];
+      required?: Record<string, string[]>;
+    }
   | { type: "extract_additional_properties"; path: string; propertyName: string }
-  | { type: "recursive_inflate"; path: string; originalRef: string };
+  | { type: "recursive_inflate"; path: string; originalRef: string }
+  | { type: "recursive_terminate"; path: string; originalRef: string }
+  | { type: "recursive_node_ref"; path: string; originalRef: string }
+  | { type: "any_of_overflow"; path: string; keptString: boolean }
+  | { type: "filtered_property"; path: string; property: string; keyword: string; placeholder?: unknown }
+  | { type: "projected_variants"; path: string; variants: ProjectedVariant[] }
+  | { type: "json_string_encode"; path: string }
+  | { type: "custom"; kind: string; path: string; payload?: unknown };
+
+export interface ProjectedVariant {
+  properties: string[];
+  required?: string[];
+  transforms?: Transform[];
+}
 
 export interface DroppedConstraint {
   path: string;
@@ -275,21 +768,63 @@ export interface DroppedConstraint {
   value: unknown;
 }
 
-export type ProviderCompatError =
+/** Stable diagnostic rule ID, e.g. "JSL-P9-001". */
+export type RuleId = string;
+
+export type ProviderCompatError = { ruleId: RuleId } & (
   | { type: "root_type_incompatible"; actual_type: string; target: Target; hint: string }
-  | { type: "depth_budget_exceeded"; actual_depth: number; max_depth: number; target: Target; hint: string }
+  | { type: "depth_budget_exceeded"; path: string; actual_depth: number; max_depth: number; target: Target; hint: string }
   | { type: "mixed_enum_types"; path: string; types_found: string[]; target: Target; hint: string }
   | { type: "unconstrained_schema"; path: string; schema_kind: string; target: Target; hint: string }
   | { type: "ref_keyword_stripped"; path: string; keyword: string; target: Target; hint: string }
   | { type: "bare_required_stripped"; path: string; target: Target; hint: string }
   | { type: "pattern_properties_stripped"; path: string; target: Target; hint: string }
-  | { type: "pattern_properties_stringified"; path: string; target: Target; hint: string };
+  | { type: "pattern_properties_stringified"; path: string; target: Target; hint: string }
+  | { type: "type_array_converted"; path: string; types: string[]; target: Target; hint: string }
+  | { type: "deprecated_property"; path: string; action: DeprecatedPolicy; target: Target; hint: string }
+  | { type: "legacy_keyword"; path: string; keyword: string; target: Target; hint: string }
+  | { type: "merge_conflict"; path: string; keyword: string; resolution: MergeConflictPolicy; target: Target; hint: string }
+  | { type: "duplicate_schema_key"; path: string; target: Target; hint: string }
+  | { type: "unresolvable_ref_recovered"; path: string; reference: string; action: ErrorPolicy; target: Target; hint: string }
+);
 
 export interface ConvertResult {
   apiVersion: string;
   schema: Record<string, unknown>;
   codec: Codec;
   providerCompatErrors?: ProviderCompatError[];
+  suppressedCompatErrors?: ProviderCompatError[];
+  /** Descriptions that dropped constraints were restated in. */
+  constraintHints?: ConstraintHint[];
+  /** Recursive cycles cut off at the recursion limit, one per break. */
+  recursionCycles?: CycleInfo[];
+  /** Provider request payload wrapping `schema`, when `envelope` is set. */
+  envelope?: Record<string, unknown>;
+}
+
+export interface ConstraintHint {
+  path: string;
+  constraints: string[];
+  text: string;
+}
+
+export interface CycleInfo {
+  /** The `$ref`s around the cycle, starting with the one cut off. */
+  refs: string[];
+  breakPath: string;
+  strategy: RecursionLeaf;
+}
+
+export type DuplicateKeyPolicy = "last-wins" | "first-wins" | "warn" | "error";
+
+export interface RehydrateOptions {
+  /** Merge same-kind warnings at one schema path, keeping this many `samplePaths`. */
+  aggregateWarnings?: number | null;
+  /** Keep this many warnings, then one "truncated" entry counting the rest. */
+  maxWarnings?: number | null;
+  duplicateKeyPolicy?: DuplicateKeyPolicy;
+  normalizeDiscriminators?: boolean;
+  maxExpandedNodes?: number;
 }
 
 export interface RehydrateResult {
@@ -301,13 +836,27 @@ export interface RehydrateResult {
 export type WarningKind =
   | { type: "constraint_violation"; constraint: string }
   | { type: "constraint_unevaluable"; constraint: string }
-  | { type: "path_not_found" };
+  | { type: "path_not_found" }
+  | { type: "unhandled_transform"; transform: string }
+  | { type: "truncated"; omitted: number }
+  | { type: "duplicate_key"; key: string }
+  | { type: "discriminator_normalized"; from: string; to: string }
+  | {
+      type: "variant_mismatch";
+      value: string | null;
+      candidates: { variant: string; missing: string[] }[];
+    }
+  | { type: "unresolved_node_ref"; id: string };
 
 export interface Warning {
   dataPath: string;
   schemaPath: string;
   kind: WarningKind;
   message: string;
+  /** Occurrences merged into this entry; absent means 1. */
+  count?: number;
+  samplePaths?: string[];
+  ruleId: RuleId;
 }
 
 export type ErrorCode =
@@ -318,12 +867,17 @@ export type ErrorCode =
   | "unresolvable_ref"
   | "rehydration_error"
   | "codec_version_mismatch"
-  | "provider_compat_failure";
+  | "provider_compat_failure"
+  | "resource_limit_exceeded"
+  | "cancelled";
 
 export interface StructuredError {
   code: ErrorCode;
   message: string;
   path: string | null;
+  /** 1-based source position; present for schema_error / unresolvable_ref from text input. */
+  line?: number;
+  column?: number;
 }
 
 export function convert(
@@ -334,6 +888,47 @@ export function convert(
 export function rehydrate(
   data: unknown,
   codec: Codec,
-  originalSchema: Record<string, unknown> | boolean
+  originalSchema: Record<string, unknown> | boolean,
+  options?: RehydrateOptions | null
 ): RehydrateResult;
+
+/** `"sha256:<hex>"` of the schema and options, stable across key order. */
+export function schemaFingerprint(
+  schema: Record<string, unknown> | boolean,
+  options?: ConvertOptions | null
+): string;
+
+/** A codec compiled by `compileCodec`; call `free()` to release it. */
+export class CodecHandle {
+  private constructor();
+  free(): void;
+}
+
+/**
+ * Compile a codec once for many `rehydrateWithHandle` calls. Pass the
+ * original schema to enable type coercion.
+ */
+export function compileCodec(
+  codec: Codec,
+  originalSchema?: Record<string, unknown> | boolean | null
+): CodecHandle;
+
+export function rehydrateWithHandle(
+  data: unknown,
+  handle: CodecHandle,
+  options?: RehydrateOptions | null
+): RehydrateResult;
+
+/**
+ * `convert` over UTF-8 JSON: returns the `ConvertResult` JSON as bytes whose
+ * buffer can be transferred to another Worker. Empty `options` means defaults.
+ */
+export function convertBytes(schema: Uint8Array, options: Uint8Array): Uint8Array;
+
+/** `rehydrate` over UTF-8 JSON: returns the `RehydrateResult` JSON as bytes. */
+export function rehydrateBytes(
+  data: Uint8Array,
+  codec: Uint8Array,
+  originalSchema: Uint8Array
+): Uint8Array;
 "#;
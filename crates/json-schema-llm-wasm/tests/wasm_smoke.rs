@@ -9,7 +9,9 @@ use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_node_experimental);
 
-use json_schema_llm_wasm::{convert, rehydrate};
+use json_schema_llm_wasm::{
+    compile_codec, convert, convert_bytes, rehydrate, rehydrate_bytes, rehydrate_with_handle,
+};
 
 // ---------------------------------------------------------------------------
 // Helper: parse a JsValue as a serde_json::Value for assertions
@@ -104,6 +106,15 @@ fn test_convert_kebab_case_options() {
     assert_eq!(json["apiVersion"], "1.0");
 }
 
+#[wasm_bindgen_test]
+fn test_convert_preset_option() {
+    let opts = serde_json::json!({"preset": "gemini-structured-output"});
+    let result = convert(schema_js(), serde_wasm_bindgen::to_value(&opts).unwrap()).unwrap();
+    let json = js_to_json(&result);
+
+    assert!(json["schema"]["propertyOrdering"].is_array());
+}
+
 #[wasm_bindgen_test]
 fn test_convert_all_targets() {
     for target in &["openai-strict", "gemini", "claude"] {
@@ -126,7 +137,7 @@ fn test_rehydrate_round_trip() {
     let sample_data = serde_json::json!({ "name": "Alice", "age": 30 });
     let data_js = serde_wasm_bindgen::to_value(&sample_data).unwrap();
 
-    let rehydrate_result = rehydrate(data_js, codec_js, schema_js()).unwrap();
+    let rehydrate_result = rehydrate(data_js, codec_js, schema_js(), JsValue::UNDEFINED).unwrap();
     let rehydrate_json = js_to_json(&rehydrate_result);
 
     assert_eq!(rehydrate_json["apiVersion"], "1.0", "rehydrate envelope");
@@ -134,6 +145,63 @@ fn test_rehydrate_round_trip() {
     assert!(rehydrate_json["warnings"].is_array(), "rehydrate warnings");
 }
 
+#[wasm_bindgen_test]
+fn test_rehydrate_with_handle_reuses_codec() {
+    let result = js_to_json(&convert(schema_js(), JsValue::UNDEFINED).unwrap());
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    let codec_js = result["codec"].serialize(&serializer).unwrap();
+    let handle = compile_codec(codec_js, schema_js()).unwrap();
+
+    for age in [30, 31] {
+        let data = serde_json::json!({ "name": "Alice", "age": age.to_string() });
+        let data_js = serde_wasm_bindgen::to_value(&data).unwrap();
+        let rehydrated =
+            js_to_json(&rehydrate_with_handle(data_js, &handle, JsValue::NULL).unwrap());
+        assert_eq!(
+            rehydrated["data"]["age"], age,
+            "coerced with the stored schema"
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_rehydrate_options_bound_warnings() {
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "tags": { "type": "array", "items": { "type": "string", "maxLength": 2 } }
+        }
+    });
+    let schema_js = serde_wasm_bindgen::to_value(&schema).unwrap();
+    let result = js_to_json(&convert(schema_js.clone(), JsValue::UNDEFINED).unwrap());
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    let codec_js = result["codec"].serialize(&serializer).unwrap();
+    let data = serde_json::json!({ "tags": ["long", "longer", "longest"] });
+    let data_js = serde_wasm_bindgen::to_value(&data).unwrap();
+    let options = serde_wasm_bindgen::to_value(&serde_json::json!({ "maxWarnings": 1 })).unwrap();
+
+    let rehydrated = js_to_json(&rehydrate(data_js, codec_js, schema_js, options).unwrap());
+    let warnings = rehydrated["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[1]["kind"]["type"], "truncated");
+    assert_eq!(warnings[1]["kind"]["omitted"], 2);
+}
+
+#[wasm_bindgen_test]
+fn test_bytes_round_trip() {
+    let schema =
+        br#"{"type":"object","properties":{"name":{"type":"string"}},"required":["name"]}"#;
+    let result = convert_bytes(schema, b"").unwrap();
+    let result_json: serde_json::Value = serde_json::from_slice(&result).unwrap();
+    assert_eq!(result_json["apiVersion"], "1.0");
+    assert!(result_json["schema"].is_object());
+
+    let codec = serde_json::to_vec(&result_json["codec"]).unwrap();
+    let rehydrated = rehydrate_bytes(br#"{"name":"Alice"}"#, &codec, schema).unwrap();
+    let rehydrated_json: serde_json::Value = serde_json::from_slice(&rehydrated).unwrap();
+    assert_eq!(rehydrated_json["data"]["name"], "Alice");
+}
+
 // ---------------------------------------------------------------------------
 // Error paths
 // ---------------------------------------------------------------------------
@@ -153,9 +221,17 @@ fn test_rehydrate_serde_error_for_bad_codec() {
     let data = serde_wasm_bindgen::to_value(&serde_json::json!({"name": "test"})).unwrap();
     let bad_codec = JsValue::from_f64(42.0);
     let schema_val = serde_wasm_bindgen::to_value(&serde_json::json!({"type": "object"})).unwrap();
-    let err = rehydrate(data, bad_codec, schema_val).unwrap_err();
+    let err = rehydrate(data, bad_codec, schema_val, JsValue::UNDEFINED).unwrap_err();
     let err_json = js_to_json(&err);
 
     assert_eq!(err_json["code"], "json_parse_error");
     assert!(err_json["message"].is_string());
 }
+
+#[wasm_bindgen_test]
+fn test_convert_bytes_parse_error() {
+    let err = convert_bytes(b"{not json", b"").unwrap_err();
+    let err_json = js_to_json(&err);
+
+    assert_eq!(err_json["code"], "json_parse_error");
+}
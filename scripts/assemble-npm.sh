@@ -24,6 +24,8 @@ mkdir -p "${DIST}/web"
 cp "${WASM_CRATE}/pkg-web/json_schema_llm_wasm_bg.wasm" "${DIST}/web/"
 cp "${WASM_CRATE}/pkg-web/json_schema_llm_wasm.js"      "${DIST}/web/"
 cp "${WASM_CRATE}/pkg-web/json_schema_llm_wasm.d.ts"     "${DIST}/web/"
+# Worker pool helper (imports the web build from its own directory)
+cp "${WASM_CRATE}"/js/worker.js "${WASM_CRATE}"/js/worker-pool.js "${WASM_CRATE}"/js/worker-pool.d.ts "${DIST}/web/"
 
 # 3. Patch package.json with jq
 TEMP_PKG=$(mktemp)
@@ -53,6 +55,10 @@ jq '
     "./web": {
       "types": "./web/json_schema_llm_wasm.d.ts",
       "default": "./web/json_schema_llm_wasm.js"
+    },
+    "./worker-pool": {
+      "types": "./web/worker-pool.d.ts",
+      "default": "./web/worker-pool.js"
     }
   }
 ' "${DIST}/package.json" > "${TEMP_PKG}"
@@ -73,6 +73,8 @@ const _opts: ConvertOptions = {
   polymorphism: "any-of",
 };
 
+const _presetOpts: ConvertOptions = { preset: "openai-tool-calling", recursionLimit: 2 };
+
 // ---------------------------------------------------------------------------
 // Codec shape narrowing
 // ---------------------------------------------------------------------------
@@ -169,6 +171,7 @@ const _ec4: ErrorCode = "unsupported_feature";
 const _ec5: ErrorCode = "unresolvable_ref";
 const _ec6: ErrorCode = "rehydration_error";
 const _ec7: ErrorCode = "codec_version_mismatch";
+const _ec8: ErrorCode = "cancelled";
 
 const _err: StructuredError = {
   code: "json_parse_error",